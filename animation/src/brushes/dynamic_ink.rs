@@ -0,0 +1,274 @@
+use super::ink::*;
+use super::super::traits::*;
+
+use std::iter;
+use std::sync::*;
+use std::ops::{Range};
+
+use flo_canvas::*;
+
+/// Distance moved between two input points at which velocity thinning reaches its maximum effect
+const FULL_THINNING_DISTANCE: f32 = 30.0;
+
+/// Minimum amount of tilt before the tilt of the pen is used to set the nib angle
+const MIN_TILT: f32 = 0.01;
+
+/// The number of opacity levels used when the opacity of a stroke follows the pressure (each level is drawn as a separate section of the stroke)
+const OPACITY_STEPS: f32 = 16.0;
+
+///
+/// The dynamic ink brush draws a solid line whose width responds to pressure, tilt and velocity, and whose opacity
+/// responds to pressure
+///
+pub struct DynamicInkBrush {
+    /// The ink brush used to render the strokes generated by this brush
+    ink: InkBrush,
+
+    /// The definition for this brush
+    definition: DynamicInkDefinition,
+
+    /// The style used to draw with this brush
+    drawing_style: BrushDrawingStyle
+}
+
+impl DynamicInkBrush {
+    ///
+    /// Creates a new dynamic ink brush from a definition
+    ///
+    pub fn new(definition: &DynamicInkDefinition, drawing_style: BrushDrawingStyle) -> Self {
+        // Strokes are rendered as ink strokes with the same width range
+        let ink_definition = InkDefinition {
+            min_width:          definition.min_width,
+            max_width:          definition.max_width,
+            scale_up_distance:  definition.taper_in
+        };
+
+        Self {
+            ink:            InkBrush::new(&ink_definition, drawing_style),
+            definition:     definition.clone(),
+            drawing_style:  drawing_style
+        }
+    }
+
+    ///
+    /// Returns the angle of the nib at a particular point
+    ///
+    fn nib_angle(&self, point: &RawPoint) -> f32 {
+        let (tilt_x, tilt_y) = point.tilt;
+
+        if self.definition.tilt_nib_angle && (tilt_x.abs() > MIN_TILT || tilt_y.abs() > MIN_TILT) {
+            tilt_y.atan2(tilt_x)
+        } else {
+            self.definition.nib_angle
+        }
+    }
+
+    ///
+    /// Computes the width of the stroke (0-1) at a particular point
+    ///
+    fn width_at_point(&self, points: &[RawPoint], index: usize) -> f32 {
+        let point       = &points[index];
+        let previous    = &points[if index > 0 { index-1 } else { 0 }];
+        let next        = &points[if index+1 < points.len() { index+1 } else { index }];
+
+        // Velocity thinning is based on how far the pen moved since the last input point
+        let (dx, dy)    = (point.position.0-previous.position.0, point.position.1-previous.position.1);
+        let speed       = (dx*dx + dy*dy).sqrt() / FULL_THINNING_DISTANCE;
+        let thinning    = 1.0 - self.definition.velocity_thinning * speed.min(1.0);
+
+        // Calligraphic nibs are thinnest when the stroke runs along the angle of the nib
+        let (dx, dy)    = (next.position.0-previous.position.0, next.position.1-previous.position.1);
+        let nib_width   = if dx != 0.0 || dy != 0.0 {
+            let direction = dy.atan2(dx);
+            (1.0 - self.definition.calligraphy) + self.definition.calligraphy * (direction - self.nib_angle(point)).sin().abs()
+        } else {
+            1.0
+        };
+
+        (point.pressure * thinning * nib_width).max(0.0).min(1.0)
+    }
+
+    ///
+    /// Returns the opacity to use for the curve segment between two brush points
+    ///
+    /// The width of the stroke follows the pen pressure, so the average width of the segment is used to set the opacity. The
+    /// result is rounded to one of the `OPACITY_STEPS` levels.
+    ///
+    fn opacity_for_segment(&self, properties: &BrushProperties, start: &BrushPoint, end: &BrushPoint) -> f32 {
+        let width   = ((start.width + end.width) / 2.0).max(0.0).min(1.0);
+        let opacity = properties.opacity * (1.0 - self.definition.pressure_opacity * (1.0 - width));
+
+        (opacity * OPACITY_STEPS).round() / OPACITY_STEPS
+    }
+
+    ///
+    /// Divides a set of brush points into sections made up of the segments that are drawn with the same opacity
+    ///
+    /// Each section ends on the first point of the next section, so the sections meet without overlapping.
+    ///
+    fn opacity_sections(&self, properties: &BrushProperties, points: &Vec<BrushPoint>) -> Vec<(f32, Range<usize>)> {
+        let mut sections = vec![];
+        let mut start    = 0;
+
+        while start+1 < points.len() {
+            let opacity = self.opacity_for_segment(properties, &points[start], &points[start+1]);
+
+            // The section continues until a segment with a different opacity is found
+            let mut end = start+1;
+            while end+1 < points.len() && self.opacity_for_segment(properties, &points[end], &points[end+1]) == opacity {
+                end += 1;
+            }
+
+            sections.push((opacity, start..(end+1)));
+            start = end;
+        }
+
+        sections
+    }
+}
+
+impl Brush for DynamicInkBrush {
+    fn brush_points_for_raw_points(&self, points: &[RawPoint]) -> Vec<BrushPoint> {
+        // Bake the dynamics into the pressure of the ink points
+        let ink_points: Vec<_> = (0..points.len())
+            .map(|index| InkCoord::with_pressure(points[index].position, self.width_at_point(points, index) as f64))
+            .collect();
+
+        ink_brush_points(&ink_points, self.definition.taper_in as f64, self.definition.taper_out as f64)
+    }
+
+    fn prepare_to_render<'a>(&'a self, properties: &BrushProperties) -> Box<dyn 'a+Iterator<Item=Draw>> {
+        self.ink.prepare_to_render(properties)
+    }
+
+    fn render_brush<'a>(&'a self, properties: &'a BrushProperties, points: &'a Vec<BrushPoint>, transform: Arc<Vec<Transformation>>) -> Box<dyn 'a+Iterator<Item=Draw>> {
+        // Nothing to do if there are too few points
+        if points.len() < 2 {
+            return Box::new(iter::empty());
+        }
+
+        if self.definition.pressure_opacity > 0.0 {
+            // The opacity follows the pressure, so the stroke is drawn in sections with different opacities
+            let mut drawing = vec![];
+
+            for (opacity, section) in self.opacity_sections(properties, points) {
+                let section_points = points[section].to_vec();
+
+                drawing.extend(properties.fill.to_drawing(properties.color, opacity));
+                drawing.extend(self.ink.brush_path(properties, &section_points, Arc::clone(&transform)));
+                drawing.push(Draw::Fill);
+            }
+
+            Box::new(drawing.into_iter())
+        } else {
+            Box::new(self.ink.brush_path(properties, points, transform)
+                .chain(iter::once(Draw::Fill)))
+        }
+    }
+
+    ///
+    /// Retrieves the definition for this brush
+    ///
+    fn to_definition(&self) -> (BrushDefinition, BrushDrawingStyle) {
+        (BrushDefinition::DynamicInk(self.definition.clone()), self.drawing_style)
+    }
+
+    ///
+    /// Attempts to combine this brush stroke with the specified vector element. Returns the combined element if successful
+    ///
+    fn combine_with(&self, combined_element: &Vector, combined_element_properties: &VectorProperties, next_element: &Vector, next_element_properties: &VectorProperties) -> CombineResult {
+        // Dynamic ink strokes combine in the same way as ink strokes
+        self.ink.combine_with(combined_element, combined_element_properties, next_element, next_element_properties)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn line(pressure: f32, tilt: (f32, f32)) -> Vec<RawPoint> {
+        (0..100).map(|x| RawPoint { position: (x as f32 * 4.0, 0.0), pressure: pressure, tilt: tilt }).collect()
+    }
+
+    fn max_width(points: &Vec<BrushPoint>) -> f32 {
+        points.iter().map(|point| point.width).fold(0.0, f32::max)
+    }
+
+    #[test]
+    fn pressure_sets_width() {
+        let brush   = DynamicInkBrush::new(&DynamicInkDefinition::default(), BrushDrawingStyle::Draw);
+        let light   = brush.brush_points_for_raw_points(&line(0.25, (0.0, 0.0)));
+        let heavy   = brush.brush_points_for_raw_points(&line(1.0, (0.0, 0.0)));
+
+        assert!(light.len() > 1);
+        assert!(max_width(&light) < max_width(&heavy));
+    }
+
+    #[test]
+    fn taper_out_thins_end_of_stroke() {
+        let mut definition  = DynamicInkDefinition::default();
+        definition.taper_out = 40.0;
+
+        let brush           = DynamicInkBrush::new(&definition, BrushDrawingStyle::Draw);
+        let points          = brush.brush_points_for_raw_points(&line(1.0, (0.0, 0.0)));
+
+        assert!(points.last().unwrap().width < 0.1);
+        assert!(max_width(&points) > 0.9);
+    }
+
+    #[test]
+    fn calligraphic_nib_is_thin_along_nib_angle() {
+        let mut definition  = DynamicInkDefinition::default();
+        definition.calligraphy  = 1.0;
+        definition.nib_angle    = 0.0;
+
+        let along           = DynamicInkBrush::new(&definition, BrushDrawingStyle::Draw);
+        definition.nib_angle    = std::f32::consts::PI / 2.0;
+        let across          = DynamicInkBrush::new(&definition, BrushDrawingStyle::Draw);
+
+        assert!(max_width(&along.brush_points_for_raw_points(&line(1.0, (0.0, 0.0)))) < 0.1);
+        assert!(max_width(&across.brush_points_for_raw_points(&line(1.0, (0.0, 0.0)))) > 0.9);
+    }
+
+    #[test]
+    fn pressure_sets_opacity_along_stroke() {
+        let mut definition  = DynamicInkDefinition::default();
+        definition.pressure_opacity = 1.0;
+        definition.taper_in         = 0.0;
+        definition.taper_out        = 0.0;
+
+        // Stroke that starts light and finishes heavy
+        let brush           = DynamicInkBrush::new(&definition, BrushDrawingStyle::Draw);
+        let points          = (0..10).map(|x| {
+            let x = x as f32;
+            BrushPoint { position: (x * 40.0, 0.0), cp1: (x * 40.0 - 26.0, 0.0), cp2: (x * 40.0 - 13.0, 0.0), width: 0.1 + x * 0.1 }
+        }).collect::<Vec<_>>();
+        let properties      = BrushProperties::new();
+
+        let opacities       = brush.render_brush(&properties, &points, Arc::new(vec![]))
+            .filter_map(|draw| match draw {
+                Draw::FillColor(color)  => Some(color.to_rgba_components().3),
+                _                       => None
+            })
+            .collect::<Vec<_>>();
+
+        // The stroke is drawn in sections that get more opaque as the pressure increases
+        assert!(opacities.len() > 2);
+        assert!(opacities.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(opacities[0] < 0.5);
+        assert!(opacities[opacities.len()-1] > 0.9);
+    }
+
+    #[test]
+    fn tilt_sets_nib_angle() {
+        let mut definition  = DynamicInkDefinition::default();
+        definition.calligraphy      = 1.0;
+        definition.nib_angle        = 0.0;
+        definition.tilt_nib_angle   = true;
+
+        let brush           = DynamicInkBrush::new(&definition, BrushDrawingStyle::Draw);
+
+        assert!(max_width(&brush.brush_points_for_raw_points(&line(1.0, (0.0, 0.0)))) < 0.1);
+        assert!(max_width(&brush.brush_points_for_raw_points(&line(1.0, (0.0, 30.0)))) > 0.9);
+    }
+}
@@ -58,7 +58,7 @@ impl InkBrush {
 /// Ink brush coordinate (used for curve fitting)
 ///
 #[derive(Clone, Copy, PartialEq)]
-struct InkCoord {
+pub (super) struct InkCoord {
     x: f64,
     y: f64,
     pressure: f64
@@ -85,6 +85,19 @@ impl<'a> From<&'a RawPoint> for InkCoord {
     }
 }
 
+impl InkCoord {
+    ///
+    /// Creates an ink coordinate for a position and a pressure in the range 0-1
+    ///
+    pub (super) fn with_pressure(position: (f32, f32), pressure: f64) -> InkCoord {
+        InkCoord {
+            x:          position.0 as f64,
+            y:          position.1 as f64,
+            pressure:   pressure*INK_PRESSURE_SCALE
+        }
+    }
+}
+
 impl<'a> From<&'a BrushPoint> for InkCoord {
     fn from(src: &'a BrushPoint) -> Self {
         Self {
@@ -267,127 +280,123 @@ impl BezierCurve for InkCurve {
     }
 }
 
-impl Brush for InkBrush {
-    fn brush_points_for_raw_points(&self, points: &[RawPoint]) -> Vec<BrushPoint> {
-        // Nothing to draw if there are no points in the brush stroke (or only one point)
-        if points.len() <= 2 {
-            return vec![];
-        }
+///
+/// Fits a set of ink coordinates to the brush points for an ink stroke
+///
+/// The pressure is scaled up over the `taper_in` distance at the start of the stroke and scaled down over the `taper_out`
+/// distance at the end.
+///
+pub (super) fn ink_brush_points(ink_points: &[InkCoord], taper_in: f64, taper_out: f64) -> Vec<BrushPoint> {
+    // Nothing to draw if there are no points in the brush stroke (or only one point)
+    if ink_points.len() <= 2 {
+        return vec![];
+    }
 
-        // Convert points to ink points
-        let ink_points: Vec<_> = points.iter().map(|point| InkCoord::from(point)).collect();
+    // Average points that are very close together so we don't overdo
+    // the curve fitting
+    let mut averaged_points = vec![];
+    let mut last_point      = ink_points[0];
+    averaged_points.push(last_point);
+
+    for point in ink_points.iter().skip(1) {
+        // If the distance between this point and the last one is below a
+        // threshold, average them together
+        let distance = last_point.distance_to(point);
+
+        if distance < MIN_DISTANCE {
+            // Average this point with the previous average
+            // TODO: (We should really total up the number of points we're
+            // averaging over)
+            let num_averaged    = averaged_points.len();
+            let current_average = averaged_points[num_averaged-1];
+            let averaged_point  = (current_average + last_point) * 0.5;
+
+            // Update the earlier point (and don't update last_point: we'll
+            // keep averaging until we find a new point far enough away)
+            averaged_points[num_averaged-1] = averaged_point;
+        } else {
+            // Keep this point
+            averaged_points.push(*point);
 
-        // Average points that are very close together so we don't overdo
-        // the curve fitting
-        let mut averaged_points = vec![];
-        let mut last_point      = ink_points[0];
-        averaged_points.push(last_point);
-
-        for point in ink_points.iter().skip(1) {
-            // If the distance between this point and the last one is below a
-            // threshold, average them together
-            let distance = last_point.distance_to(point);
-
-            if distance < MIN_DISTANCE {
-                // Average this point with the previous average
-                // TODO: (We should really total up the number of points we're
-                // averaging over)
-                let num_averaged    = averaged_points.len();
-                let current_average = averaged_points[num_averaged-1];
-                let averaged_point  = (current_average + last_point) * 0.5;
-
-                // Update the earlier point (and don't update last_point: we'll
-                // keep averaging until we find a new point far enough away)
-                averaged_points[num_averaged-1] = averaged_point;
-            } else {
-                // Keep this point
-                averaged_points.push(*point);
-
-                // Update the last point
-                last_point = *point;
-            }
+            // Update the last point
+            last_point = *point;
         }
+    }
 
-        // Smooth out the points to remove any jitteryness
-        let mut ink_points = InkCoord::smooth(&averaged_points, &[0.1, 0.25, 0.3, 0.25, 0.1]);
+    // Smooth out the points to remove any jitteryness
+    let mut ink_points = InkCoord::smooth(&averaged_points, &[0.1, 0.25, 0.3, 0.25, 0.1]);
 
-        // Scale up the pressure at the start of the brush stroke
+    // Scale up the pressure at the start of the brush stroke
+    if taper_in > 0.0 {
         let mut distance    = 0.0;
         let mut last_point  = ink_points[0];
-        let scale_up_distance = self.scale_up_distance as f64;
         for point in ink_points.iter_mut() {
             // Add to the distance
             distance += last_point.distance_to(point);
             last_point = *point;
 
             // Scale the pressure by the distance
-            if distance > scale_up_distance { break; }
+            if distance > taper_in { break; }
 
             let pressure = point.pressure();
-            point.set_pressure(pressure * (distance/scale_up_distance));
+            point.set_pressure(pressure * (distance/taper_in));
         }
+    }
 
-        /*
-         * -- TODO: needs its own config option
-         *
-        // Scale down the pressure at the end of the brush stroke
-        last_point  = *ink_points.last().unwrap();
-        distance    = 0.0;
-        for index in (0..ink_points.len()).rev() {
-            let point = &mut ink_points[index];
-
+    // Scale down the pressure at the end of the brush stroke
+    if taper_out > 0.0 {
+        let mut distance    = 0.0;
+        let mut last_point  = *ink_points.last().unwrap();
+        for point in ink_points.iter_mut().rev() {
             distance += last_point.distance_to(point);
             last_point = *point;
 
             // Scale the pressure by the distance
-            if distance > scale_up_distance { break; }
+            if distance > taper_out { break; }
 
             let pressure = point.pressure();
-            point.set_pressure(pressure * (distance/scale_up_distance));
+            point.set_pressure(pressure * (distance/taper_out));
         }
-        */
+    }
 
-        // Fit these points to a curve
-        let curve = InkCurve::fit_from_points(&ink_points, 1.0);
+    // Fit these points to a curve
+    let curve = InkCurve::fit_from_points(&ink_points, 1.0);
 
-        // Turn into brush points
-        let mut brush_points = vec![];
+    // Turn into brush points
+    let mut brush_points = vec![];
+
+    if let Some(curve) = curve {
+        // First point is the start point, the control points don't matter for this
+        let start = curve[0].start_point();
+        brush_points.push(BrushPoint {
+            position:   (start.x as f32, start.y as f32),
+            cp1:        (0.0, 0.0),
+            cp2:        (0.0, 0.0),
+            width:      (start.pressure/INK_PRESSURE_SCALE) as f32
+        });
+
+        // Convert the remaining curve segments
+        for segment in curve {
+            let end             = segment.end_point();
+            let (cp1, cp2)      = segment.control_points();
 
-        if let Some(curve) = curve {
-            // First point is the start point, the control points don't matter for this
-            let start = curve[0].start_point();
             brush_points.push(BrushPoint {
-                position:   (start.x as f32, start.y as f32),
-                cp1:        (0.0, 0.0),
-                cp2:        (0.0, 0.0),
-                width:      (start.pressure/INK_PRESSURE_SCALE) as f32
+                position:   (end.x as f32, end.y as f32),
+                cp1:        (cp1.x as f32, cp1.y as f32),
+                cp2:        (cp2.x as f32, cp2.y as f32),
+                width:      (end.pressure/INK_PRESSURE_SCALE) as f32
             });
-
-            // Convert the remaining curve segments
-            for segment in curve {
-                let end             = segment.end_point();
-                let (cp1, cp2)      = segment.control_points();
-
-                brush_points.push(BrushPoint {
-                    position:   (end.x as f32, end.y as f32),
-                    cp1:        (cp1.x as f32, cp1.y as f32),
-                    cp2:        (cp2.x as f32, cp2.y as f32),
-                    width:      (end.pressure/INK_PRESSURE_SCALE) as f32
-                });
-            }
         }
-
-        brush_points
     }
 
-    fn prepare_to_render<'a>(&'a self, properties: &BrushProperties) -> Box<dyn 'a+Iterator<Item=Draw>> {
-        Box::new(vec![
-            Draw::BlendMode(self.blend_mode),
-            Draw::FillColor(properties.color.with_alpha(properties.opacity))
-        ].into_iter())
-    }
+    brush_points
+}
 
-    fn render_brush<'a>(&'a self, properties: &'a BrushProperties, points: &'a Vec<BrushPoint>, transform: Arc<Vec<Transformation>>) -> Box<dyn 'a+Iterator<Item=Draw>> {
+impl InkBrush {
+    ///
+    /// Generates the path for a set of brush points (without filling it)
+    ///
+    pub (super) fn brush_path<'a>(&'a self, properties: &'a BrushProperties, points: &'a Vec<BrushPoint>, transform: Arc<Vec<Transformation>>) -> Box<dyn 'a+Iterator<Item=Draw>> {
         let size_ratio = properties.size / self.max_width;
 
         // Nothing to do if there are too few points
@@ -443,17 +452,38 @@ impl Brush for InkBrush {
             .flat_map(|curve_list|  curve_list.into_iter().rev())
             .map(|curve_section|    Draw::from(&curve_section.reverse::<bezier::Curve<_>>()));
 
-        // Finish up
-        let finish = Draw::Fill;
-
         // Assemble the final set of instructions
-        let draw_brush = preamble.into_iter()
+        let brush_path = preamble.into_iter()
             .chain(upper_curves)
             .chain(iter::once(end_cap))
-            .chain(lower_curves)
-            .chain(iter::once(finish));
+            .chain(lower_curves);
+
+        Box::new(brush_path)
+    }
+}
+
+impl Brush for InkBrush {
+    fn brush_points_for_raw_points(&self, points: &[RawPoint]) -> Vec<BrushPoint> {
+        // Convert points to ink points
+        let ink_points: Vec<_> = points.iter().map(|point| InkCoord::from(point)).collect();
+
+        ink_brush_points(&ink_points, self.scale_up_distance as f64, 0.0)
+    }
+
+    fn prepare_to_render<'a>(&'a self, properties: &BrushProperties) -> Box<dyn 'a+Iterator<Item=Draw>> {
+        Box::new(iter::once(Draw::BlendMode(self.blend_mode))
+            .chain(properties.fill.to_drawing(properties.color, properties.opacity)))
+    }
+
+    fn render_brush<'a>(&'a self, properties: &'a BrushProperties, points: &'a Vec<BrushPoint>, transform: Arc<Vec<Transformation>>) -> Box<dyn 'a+Iterator<Item=Draw>> {
+        // Nothing to do if there are too few points
+        if points.len() < 2 {
+            return Box::new(iter::empty());
+        }
 
-        Box::new(draw_brush)
+        // Fill the path for the brush stroke
+        Box::new(self.brush_path(properties, points, transform)
+            .chain(iter::once(Draw::Fill)))
     }
 
     ///
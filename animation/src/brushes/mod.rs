@@ -1,9 +1,11 @@
 mod simple;
 mod ink;
+mod dynamic_ink;
 mod brush_preview;
 
 pub use self::simple::*;
 pub use self::ink::*;
+pub use self::dynamic_ink::*;
 pub use self::brush_preview::*;
 
 use super::traits::*;
@@ -16,7 +18,8 @@ pub fn create_brush_from_definition(definition: &BrushDefinition, drawing_style:
     use BrushDefinition::*;
 
     match definition {
-        &Simple                     => Arc::new(SimpleBrush::new()),
-        &Ink(ref ink_definition)    => Arc::new(InkBrush::new(ink_definition, drawing_style))
+        &Simple                         => Arc::new(SimpleBrush::new()),
+        &Ink(ref ink_definition)        => Arc::new(InkBrush::new(ink_definition, drawing_style)),
+        &DynamicInk(ref ink_definition) => Arc::new(DynamicInkBrush::new(ink_definition, drawing_style))
     }
 }
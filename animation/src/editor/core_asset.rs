@@ -0,0 +1,70 @@
+use super::stream_animation_core::*;
+use crate::serializer::*;
+use crate::storage::storage_api::*;
+
+use futures::prelude::*;
+
+use std::sync::*;
+
+impl StreamAnimationCore {
+    ///
+    /// Assigns an ID for a new asset
+    ///
+    pub fn assign_asset_id<'a>(&'a mut self) -> impl 'a+Future<Output=u64> {
+        async move {
+            let next_asset_id = match self.next_asset_id {
+                Some(next_id)   => next_id,
+                None            => {
+                    // Fetch the asset ID from the storage
+                    match self.request_one(StorageCommand::ReadHighestUnusedAssetId).await {
+                        Some(StorageResponse::HighestUnusedAssetId(next_id))    => next_id,
+                        _                                                       => 0
+                    }
+                }
+            };
+
+            self.next_asset_id = Some(next_asset_id+1);
+
+            next_asset_id
+        }
+    }
+
+    ///
+    /// Writes the data for an asset to the storage
+    ///
+    pub fn write_asset<'a>(&'a mut self, asset_id: u64, data: &'a [u8]) -> impl 'a+Future<Output=()> {
+        async move {
+            // Make sure that the asset ID isn't assigned again
+            if let Some(next_asset_id) = self.next_asset_id.as_mut() {
+                if *next_asset_id <= asset_id {
+                    *next_asset_id = asset_id+1;
+                }
+            }
+
+            let mut serialized = String::new();
+            serialized.write_usize(data.len());
+            serialized.write_bytes(data);
+
+            self.request_one(StorageCommand::WriteAsset(asset_id, serialized)).await;
+        }
+    }
+
+    ///
+    /// Reads the data for an asset from the storage
+    ///
+    pub fn read_asset<'a>(&'a mut self, asset_id: u64) -> impl 'a+Future<Output=Option<Arc<Vec<u8>>>> {
+        async move {
+            match self.request_one(StorageCommand::ReadAsset(asset_id)).await {
+                Some(StorageResponse::Asset(_, asset))  => {
+                    let mut asset   = asset.chars();
+                    let len         = asset.next_usize();
+                    let data        = asset.next_bytes(len);
+
+                    Some(Arc::new(data.into_vec()))
+                }
+
+                _                                       => None
+            }
+        }
+    }
+}
@@ -0,0 +1,123 @@
+use super::core_element::*;
+use super::element_wrapper::*;
+use super::stream_animation_core::*;
+use crate::storage::storage_api::*;
+use crate::traits::*;
+
+use futures::prelude::*;
+
+use std::collections::{HashSet};
+use std::time::{Duration};
+
+impl StreamAnimationCore {
+    ///
+    /// Performs a bone edit on this animation
+    ///
+    pub fn bone_edit<'a>(&'a mut self, bone_id: ElementId, bone_edit: &'a BoneEdit) -> impl 'a+Future<Output=()> {
+        async move {
+            use self::BoneEdit::*;
+
+            // Convert the bone ID to an assigned element
+            let bone_id = match bone_id.id() {
+                Some(id)    => id,
+                None        => { return; }
+            };
+
+            match bone_edit {
+                Create                      => {
+                    // Create the bone element
+                    let bone            = BoneElement::new(ElementId::Assigned(bone_id), Bone::new());
+                    let bone            = Vector::Bone(bone);
+                    let bone            = ElementWrapper::unattached_with_element(bone, Duration::from_millis(0));
+
+                    // Write
+                    self.request_one(StorageCommand::WriteElement(bone_id, bone.serialize_to_data())).await;
+                }
+                Delete                      => { self.request_one(StorageCommand::DeleteElement(bone_id)).await; }
+
+                SetParent(parent_id)        => {
+                    if self.can_set_bone_parent(bone_id, *parent_id).await {
+                        self.update_bone(bone_id, |bone| bone.parent = *parent_id).await;
+                    }
+                }
+
+                SetOrigin(x, y)             => { self.update_bone(bone_id, |bone| bone.origin = (*x, *y)).await; }
+                SetLength(length)           => { self.update_bone(bone_id, |bone| bone.length = *length).await; }
+                SetAngle(angle)             => { self.update_bone(bone_id, |bone| bone.angle = *angle).await; }
+                SetRotation(when, angle)    => { self.update_bone(bone_id, |bone| { bone.rotations.insert(*when, *angle); }).await; }
+                RemoveRotation(when)        => { self.update_bone(bone_id, |bone| { bone.rotations.remove(when); }).await; }
+            }
+
+            // Keyframes load the parents of their bones, so the cached keyframe might contain an out of date copy of the bone
+            self.cached_keyframe = None;
+        }
+    }
+
+    ///
+    /// Reads the bone with the specified ID from storage
+    ///
+    pub (super) fn read_bone<'a>(&'a mut self, bone_id: i64) -> impl 'a+Future<Output=Option<Bone>> {
+        async move {
+            let element = match self.request_one(StorageCommand::ReadElement(bone_id)).await {
+                Some(StorageResponse::Element(_, element))  => element,
+                _                                           => { return None; }
+            };
+
+            // Bones don't depend on any other elements
+            let element = ElementWrapper::deserialize_data(ElementId::Assigned(bone_id), &element)
+                .and_then(|element| element.resolve(&mut |_| None));
+
+            match element.map(|wrapper| wrapper.element) {
+                Some(Vector::Bone(bone))    => Some((*bone.bone()).clone()),
+                _                           => None
+            }
+        }
+    }
+
+    ///
+    /// Returns true if a bone can be connected to the specified parent
+    ///
+    /// The parent has to be an existing bone that's not the bone itself or one of its children
+    ///
+    fn can_set_bone_parent<'a>(&'a mut self, bone_id: i64, parent_id: Option<ElementId>) -> impl 'a+Future<Output=bool> {
+        async move {
+            let mut visited     = HashSet::new();
+            let mut next_parent = match parent_id {
+                None            => { return true; }
+                Some(parent_id) => parent_id.id()
+            };
+
+            while let Some(parent_id) = next_parent {
+                if parent_id == bone_id || !visited.insert(parent_id) {
+                    return false;
+                }
+
+                next_parent = match self.read_bone(parent_id).await {
+                    Some(parent)    => parent.parent.and_then(|parent_id| parent_id.id()),
+                    None            => { return visited.len() > 1; }
+                };
+            }
+
+            true
+        }
+    }
+
+    ///
+    /// Updates an existing bone element
+    ///
+    fn update_bone<'a, UpdateFn>(&'a mut self, bone_id: i64, update_fn: UpdateFn) -> impl 'a+Future<Output=()>
+    where UpdateFn: 'a+Send+Sync+Fn(&mut Bone) {
+        async move {
+            self.update_elements(vec![bone_id], |mut element_wrapper| {
+                if let Vector::Bone(bone_element) = &element_wrapper.element {
+                    let mut new_bone        = (*bone_element.bone()).clone();
+                    update_fn(&mut new_bone);
+
+                    element_wrapper.element = Vector::Bone(BoneElement::new(ElementId::Assigned(bone_id), new_bone));
+                }
+
+                ElementUpdate::ChangeWrapper(element_wrapper)
+            }).await;
+        }
+    }
+}
@@ -48,6 +48,7 @@ impl StreamAnimationCore {
                 AddAttachment(attach_id)            => { self.update_elements(element_ids, |_wrapper| { AddAttachments(vec![*attach_id]) }).await; }
                 RemoveAttachment(attach_id)         => { self.update_elements(element_ids, |_wrapper| { RemoveAttachments(vec![*attach_id]) }).await; }
                 SetPath(new_path)                   => { self.update_elements(element_ids, |mut wrapper| { wrapper.element = wrapper.element.with_path_components(new_path.iter().cloned()); ChangeWrapper(wrapper) }).await; }
+                SetSymbolInstance(instance)         => { self.update_elements(element_ids, |mut wrapper| { wrapper.element = wrapper.element.with_symbol_instance(instance.clone()); ChangeWrapper(wrapper) }).await; }
                 Order(ordering)                     => { self.order_elements(element_ids, *ordering).await; }
                 Group(group_id, group_type)         => { self.group_elements(element_ids, *group_id, *group_type).await; }
                 
@@ -63,6 +64,8 @@ impl StreamAnimationCore {
                     }
                 }
 
+                CombinePaths(combine)               => { self.combine_element_paths(element_ids, *combine).await; }
+
                 CollideWithExistingElements         => { 
                     for id in element_ids.iter() {
                         self.collide_with_existing_elements(ElementId::Assigned(*id)).await;
@@ -107,7 +110,7 @@ impl StreamAnimationCore {
                                     wrapper.element = wrapper.element.with_adjusted_control_points(new_points, &*properties);
 
                                     // Generate the updates for this element
-                                    let updates     = vec![StorageCommand::WriteElement(element_id, wrapper.serialize_to_string())]; 
+                                    let updates     = vec![StorageCommand::WriteElement(element_id, wrapper.serialize_to_data())]; 
                                     frame.elements.insert(ElementId::Assigned(element_id), wrapper);
 
                                     Some(updates)
@@ -201,7 +204,7 @@ impl StreamAnimationCore {
                 match elem_response {
                     StorageResponse::Element(id, serialized) => { 
                         // Deserialize this element
-                        let resolver    = ElementWrapper::deserialize(ElementId::Assigned(id), &mut serialized.chars());
+                        let resolver    = ElementWrapper::deserialize_data(ElementId::Assigned(id), &serialized);
                         let element     = if let Some(keyframe) = keyframe.as_ref() {
                             // Resolve with the existing elements in the keyframe
                             resolver.and_then(|resolver| resolver.resolve(&mut |id| {
@@ -382,7 +385,7 @@ impl StreamAnimationCore {
                     // The element is independent of a keyframe. These elements cannot be edited if they depend on others (at the moment)
                     if let Some(StorageResponse::Element(_, element)) = self.request_one(StorageCommand::ReadElement(root_element)).await {
                         // Decode the element (without looking up any dependencies)
-                        let element = ElementWrapper::deserialize(ElementId::Assigned(root_element), &mut element.chars())
+                        let element = ElementWrapper::deserialize_data(ElementId::Assigned(root_element), &element)
                             .and_then(|element| element.resolve(&mut |_| None));
 
                         if let Some(element) = element {
@@ -0,0 +1,241 @@
+use super::keyframe_core::*;
+use super::element_wrapper::*;
+use super::stream_animation_core::*;
+use crate::storage::storage_api::*;
+use crate::traits::*;
+
+use futures::prelude::*;
+
+use std::time::{Duration};
+use std::collections::{HashMap, BTreeMap};
+
+///
+/// Works out the new start time of an element when the keyframe it's in is moved
+///
+fn retime(start_time: Duration, from: Duration, to: Duration) -> Duration {
+    to + (start_time.checked_sub(from).unwrap_or(Duration::from_millis(0)))
+}
+
+///
+/// Changes the times of an element in a keyframe that's being moved (or copied) from one time to another
+///
+fn retime_element(wrapper: &ElementWrapper, from: Duration, to: Duration) -> ElementWrapper {
+    let mut wrapper     = wrapper.clone();
+    wrapper.start_time  = retime(wrapper.start_time, from, to);
+
+    // Motions are described using absolute times, so they need to be moved along with the keyframe
+    if let Vector::Motion(motion) = &wrapper.element {
+        let offset_millis   = (to_millis(to) - to_millis(from)) as f32;
+        let new_motion      = motion.motion().with_time_offset(offset_millis);
+        wrapper.element     = Vector::Motion(MotionElement::new(motion.id(), new_motion));
+    }
+
+    // Bone rotations are also set at absolute times
+    if let Vector::Bone(bone) = &wrapper.element {
+        let offset_millis   = to_millis(to) - to_millis(from);
+        let new_bone        = bone.bone().with_time_offset(offset_millis);
+        wrapper.element     = Vector::Bone(BoneElement::new(bone.id(), new_bone));
+    }
+
+    wrapper
+}
+
+///
+/// Replaces the IDs used in a vector element using a map of old to new IDs
+///
+fn remap_vector(element: &Vector, id_map: &HashMap<ElementId, ElementId>) -> Vector {
+    let mut element = match element {
+        Vector::Group(group)    => {
+            // Groups also contain copies of their child elements
+            let new_elements = group.elements().map(|child| remap_vector(child, id_map)).collect::<Vec<_>>();
+            Vector::Group(group.with_elements(new_elements))
+        }
+
+        Vector::Bone(bone)      => {
+            // Bones that are copied along with their parent bone are connected to the copy of the parent
+            let mut new_bone    = (*bone.bone()).clone();
+            new_bone.parent     = new_bone.parent.map(|parent_id| id_map.get(&parent_id).cloned().unwrap_or(parent_id));
+            Vector::Bone(BoneElement::new(bone.id(), new_bone))
+        }
+
+        other                   => other.clone()
+    };
+
+    if let Some(new_id) = id_map.get(&element.id()) {
+        element.set_id(*new_id);
+    }
+
+    element
+}
+
+///
+/// Replaces the IDs used in an element wrapper using a map of old to new IDs
+///
+fn remap_wrapper(wrapper: &ElementWrapper, id_map: &HashMap<ElementId, ElementId>) -> ElementWrapper {
+    let remap_id        = |id: &ElementId| id_map.get(id).cloned().unwrap_or(*id);
+    let mut wrapper     = wrapper.clone();
+
+    wrapper.element         = remap_vector(&wrapper.element, id_map);
+    wrapper.attachments     = wrapper.attachments.iter().map(remap_id).collect();
+    wrapper.attached_to     = wrapper.attached_to.iter().map(remap_id).collect();
+    wrapper.parent          = wrapper.parent.as_ref().map(remap_id);
+    wrapper.order_before    = wrapper.order_before.as_ref().map(remap_id);
+    wrapper.order_after     = wrapper.order_after.as_ref().map(remap_id);
+
+    wrapper
+}
+
+impl StreamAnimationCore {
+    ///
+    /// Returns true if there is a keyframe starting at exactly the specified time
+    ///
+    fn has_key_frame_at<'a>(&'a mut self, layer_id: u64, when: Duration) -> impl 'a+Future<Output=bool> {
+        async move {
+            let keyframes = self.request_one(StorageCommand::ReadKeyFrames(layer_id, when..(when + Duration::from_micros(1)))).await;
+
+            match keyframes {
+                Some(StorageResponse::KeyFrame(start, _end))    => start == when,
+                _                                               => false
+            }
+        }
+    }
+
+    ///
+    /// Loads the elements in the keyframe at the 'from' time, provided that there's no keyframe at the 'to' time already
+    ///
+    fn load_keyframe_to_copy<'a>(&'a mut self, layer_id: u64, from: Duration, to: Duration) -> impl 'a+Future<Output=Option<KeyFrameCore>> {
+        async move {
+            if from == to                                   { return None; }
+            if !self.has_key_frame_at(layer_id, from).await { return None; }
+            if self.has_key_frame_at(layer_id, to).await    { return None; }
+
+            KeyFrameCore::from_keyframe(self, layer_id, from).await
+        }
+    }
+
+    ///
+    /// Moves the keyframe starting at the 'from' time so that it starts at the 'to' time
+    ///
+    pub fn move_key_frame<'a>(&'a mut self, layer_id: u64, from: Duration, to: Duration) -> impl 'a+Future<Output=()> {
+        async move {
+            let keyframe = match self.load_keyframe_to_copy(layer_id, from, to).await {
+                Some(keyframe)  => keyframe,
+                None            => { return; }
+            };
+
+            // Order the elements by ID so that the storage requests are consistent
+            let mut elements = keyframe.elements.iter()
+                .filter_map(|(element_id, wrapper)| element_id.id().map(|id| (id, wrapper)))
+                .collect::<Vec<_>>();
+            elements.sort_by_key(|(element_id, _)| *element_id);
+
+            // Create the new keyframe, and update the element times (detaching them from the old keyframe)
+            let mut updates = vec![StorageCommand::AddKeyFrame(layer_id, to)];
+
+            for (element_id, wrapper) in elements.iter() {
+                let wrapper = retime_element(wrapper, from, to);
+
+                updates.push(StorageCommand::WriteElement(*element_id, wrapper.serialize_to_data()));
+                updates.push(StorageCommand::DetachElementFromLayer(*element_id));
+            }
+
+            // Remove the old keyframe and attach the elements to the new one
+            updates.push(StorageCommand::DeleteKeyFrame(layer_id, from));
+            updates.extend(elements.iter().map(|(element_id, _)| StorageCommand::AttachElementToLayer(layer_id, *element_id, to)));
+
+            self.request(updates).await;
+
+            // The tween setting moves along with the keyframe
+            self.update_key_frame_tweens(layer_id, move |tweens| {
+                if let Some(easing) = tweens.remove(&from) {
+                    tweens.insert(to, easing);
+                }
+            }).await;
+
+            // The cached keyframe is no longer valid
+            self.cached_keyframe = None;
+        }
+    }
+
+    ///
+    /// Copies the keyframe starting at the 'from' time to a new keyframe that starts at the 'to' time
+    ///
+    pub fn duplicate_key_frame<'a>(&'a mut self, layer_id: u64, from: Duration, to: Duration) -> impl 'a+Future<Output=()> {
+        async move {
+            let keyframe = match self.load_keyframe_to_copy(layer_id, from, to).await {
+                Some(keyframe)  => keyframe,
+                None            => { return; }
+            };
+
+            // Assign new IDs to all of the elements in the keyframe
+            let mut element_ids = keyframe.elements.keys()
+                .filter(|element_id| element_id.is_assigned())
+                .cloned()
+                .collect::<Vec<_>>();
+            element_ids.sort_by_key(|element_id| element_id.id());
+
+            let mut id_map = HashMap::new();
+            for old_id in element_ids.iter() {
+                let new_id = self.assign_element_id(ElementId::Unassigned).await;
+                if new_id.is_unassigned() { return; }
+
+                id_map.insert(*old_id, new_id);
+            }
+
+            // Write out the new elements and attach them to the new keyframe
+            let mut updates = vec![StorageCommand::AddKeyFrame(layer_id, to)];
+            for old_id in element_ids.iter() {
+                let new_id  = id_map[old_id].id().unwrap();
+                let wrapper = retime_element(&keyframe.elements[old_id], from, to);
+                let wrapper = remap_wrapper(&wrapper, &id_map);
+
+                updates.push(StorageCommand::WriteElement(new_id, wrapper.serialize_to_data()));
+                updates.push(StorageCommand::AttachElementToLayer(layer_id, new_id, to));
+            }
+
+            self.request(updates).await;
+
+            // The copy is tweened in the same way as the original
+            self.update_key_frame_tweens(layer_id, move |tweens| {
+                if let Some(easing) = tweens.get(&from).cloned() {
+                    tweens.insert(to, easing);
+                }
+            }).await;
+        }
+    }
+
+    ///
+    /// Updates the settings for which keyframes in a layer are tweened (the layer properties are only written if they change)
+    ///
+    pub (super) fn update_key_frame_tweens<'a, UpdateFn: 'a+Send+FnOnce(&mut BTreeMap<Duration, TweenEasing>)>(&'a mut self, layer_id: u64, update: UpdateFn) -> impl 'a+Future<Output=()> {
+        async move {
+            let mut properties  = self.read_layer_properties(layer_id).await;
+            let original_tweens = properties.tweens.clone();
+
+            update(&mut properties.tweens);
+
+            if properties.tweens != original_tweens {
+                self.write_layer_properties(layer_id, properties).await;
+            }
+        }
+    }
+
+    ///
+    /// Sets whether or not the keyframe starting at the specified time is tweened into the following keyframe
+    ///
+    pub fn set_key_frame_tween<'a>(&'a mut self, layer_id: u64, when: Duration, easing: Option<TweenEasing>) -> impl 'a+Future<Output=()> {
+        async move {
+            // Only keyframes that exist can be tweened
+            if easing.is_some() && !self.has_key_frame_at(layer_id, when).await {
+                return;
+            }
+
+            self.update_key_frame_tweens(layer_id, move |tweens| {
+                match easing {
+                    Some(easing)    => { tweens.insert(when, easing); }
+                    None            => { tweens.remove(&when); }
+                }
+            }).await;
+        }
+    }
+}
@@ -1,10 +1,13 @@
 use super::stream_animation_core::*;
 use crate::traits::*;
+use crate::serializer::*;
 use crate::storage::storage_api::*;
 use crate::storage::layer_properties::*;
 
 use futures::prelude::*;
 
+use std::time::{Duration};
+
 impl StreamAnimationCore {
     ///
     /// Performs a layer edit on this animation
@@ -14,12 +17,23 @@ impl StreamAnimationCore {
 
         async move {
             match layer_edit {
-                Paint(when, paint_edit)     => { self.paint_edit(layer_id, *when, paint_edit).await }
-                Path(when, path_edit)       => { self.path_edit(layer_id, *when, path_edit).await }
-                AddKeyFrame(when)           => { self.add_key_frame(layer_id, *when).await }
-                RemoveKeyFrame(when)        => { self.remove_key_frame(layer_id, *when).await }
-                SetName(new_name)           => { self.set_layer_name(layer_id, new_name).await }
-                SetOrdering(ordering)       => { self.set_layer_ordering(layer_id, *ordering).await }
+                Paint(when, paint_edit)             => { self.paint_edit(layer_id, *when, paint_edit).await }
+                Path(when, path_edit)               => { self.path_edit(layer_id, *when, path_edit).await }
+                AddKeyFrame(when)                   => { self.add_key_frame(layer_id, *when).await }
+                RemoveKeyFrame(when)                => { self.remove_key_frame(layer_id, *when).await }
+                MoveKeyFrame(from, to)              => { self.move_key_frame(layer_id, *from, *to).await }
+                DuplicateKeyFrame(from, to)         => { self.duplicate_key_frame(layer_id, *from, *to).await }
+                SetKeyFrameTween(when, easing)      => { self.set_key_frame_tween(layer_id, *when, *easing).await }
+                SetName(new_name)                   => { self.set_layer_name(layer_id, new_name).await }
+                SetOrdering(ordering)               => { self.set_layer_ordering(layer_id, *ordering).await }
+                SetReference(settings)              => { self.set_layer_reference(layer_id, settings.clone()).await }
+                AddReferenceImage(when, asset_id)   => { self.add_reference_image(layer_id, *when, *asset_id).await }
+                RemoveReferenceImage(when)          => { self.remove_reference_image(layer_id, *when).await }
+                SetParent(parent_id)                => { self.set_layer_parent(layer_id, *parent_id).await }
+                SetHidden(hidden)                   => { let hidden = *hidden; self.update_layer_properties(layer_id, move |properties| properties.hidden = hidden).await }
+                SetLocked(locked)                   => { let locked = *locked; self.update_layer_properties(layer_id, move |properties| properties.locked = locked).await }
+                SetCollapsed(collapsed)             => { let collapsed = *collapsed; self.update_layer_properties(layer_id, move |properties| properties.collapsed = collapsed).await }
+                CreateSymbolInstance(when, element_id, instance) => { self.create_symbol_instance(layer_id, *when, *element_id, instance).await }
             }
         }
     }
@@ -35,21 +49,11 @@ impl StreamAnimationCore {
             }
 
             // Read all of the layers from storage
-            let layers          = self.request(vec![StorageCommand::ReadLayers]).await;
-            let mut layers      = layers.unwrap_or_else(|| vec![]).into_iter().map(|response| {
-                    if let StorageResponse::LayerProperties(layer_id, properties) = response {
-                        let properties = LayerProperties::deserialize(&mut properties.chars()).unwrap_or_else(|| LayerProperties::default());
-                        Some((layer_id, properties))
-                    } else {
-                        None
-                    }
-                })
-                .flatten()
-                .collect::<Vec<_>>();
+            let mut layers      = self.read_all_layer_properties().await;
 
             // Sort the layers into order
-            layers.sort_by(|(_, layer_a), (_, layer_b)| {
-                layer_a.ordering.cmp(&layer_b.ordering)
+            layers.sort_by(|(id_a, layer_a), (id_b, layer_b)| {
+                (layer_a.ordering, id_a).cmp(&(layer_b.ordering, id_b))
             });
 
             // Find the layer and the layer we need to order behind
@@ -95,6 +99,26 @@ impl StreamAnimationCore {
         } 
     }
 
+    ///
+    /// Reads the properties of every layer in the animation (including the layers in symbols)
+    ///
+    pub fn read_all_layer_properties<'a>(&'a mut self) -> impl 'a+Future<Output=Vec<(u64, LayerProperties)>> {
+        async move {
+            let layers = self.request(vec![StorageCommand::ReadLayers]).await;
+
+            layers.unwrap_or_else(|| vec![]).into_iter().map(|response| {
+                    if let StorageResponse::LayerProperties(layer_id, properties) = response {
+                        let properties = LayerProperties::deserialize(&mut properties.chars()).unwrap_or_else(|| LayerProperties::default());
+                        Some((layer_id, properties))
+                    } else {
+                        None
+                    }
+                })
+                .flatten()
+                .collect::<Vec<_>>()
+        }
+    }
+
     ///
     /// Adds a new layer with a particular ID to this animation
     ///
@@ -110,38 +134,162 @@ impl StreamAnimationCore {
         }
     }
 
+    ///
+    /// Adds a new layer folder with a particular ID to this animation
+    ///
+    pub fn add_new_layer_folder<'a>(&'a mut self, layer_id: u64) -> impl 'a+Future<Output=()> {
+        async move {
+            // Folders are layers that other layers can be moved into
+            let properties      = LayerProperties { folder: true, ..LayerProperties::default() };
+            let mut serialized  = String::new();
+            properties.serialize(&mut serialized);
+
+            self.request_one(StorageCommand::AddLayer(layer_id, serialized)).await;
+        }
+    }
+
     ///
     /// Removes the layer with the specified ID from the animation
     ///
     pub fn remove_layer<'a>(&'a mut self, layer_id: u64) -> impl 'a+Future<Output=()> {
         async move {
+            // The contents of a folder are moved into the folder's parent
+            let properties = self.read_layer_properties(layer_id).await;
+
+            if properties.folder {
+                let children = self.read_all_layer_properties().await.into_iter()
+                    .filter(|(_, child_properties)| child_properties.parent == Some(layer_id))
+                    .collect::<Vec<_>>();
+
+                for (child_id, mut child_properties) in children {
+                    child_properties.parent = properties.parent;
+                    self.write_layer_properties(child_id, child_properties).await;
+                }
+            }
+
             // Remove the layer
             self.request_one(StorageCommand::DeleteLayer(layer_id)).await;
         }
     }
 
     ///
-    /// Sets the name of a layer
+    /// Reads the properties for a layer (or the default properties if the layer can't be found)
     ///
-    pub fn set_layer_name<'a>(&'a mut self, layer_id: u64, name: &'a str) -> impl 'a+Future<Output=()> { 
+    pub fn read_layer_properties<'a>(&'a mut self, layer_id: u64) -> impl 'a+Future<Output=LayerProperties> {
         async move {
-            // Read the current properties for this layer
-            let mut properties = match self.request_one(StorageCommand::ReadLayerProperties(layer_id)).await {
+            match self.request_one(StorageCommand::ReadLayerProperties(layer_id)).await {
                 Some(StorageResponse::LayerProperties(_, properties)) => {
                     LayerProperties::deserialize(&mut properties.chars())
                         .unwrap_or_else(|| LayerProperties::default())
                 }
 
                 _ => LayerProperties::default()
-            };
+            }
+        }
+    }
+
+    ///
+    /// Writes back the properties for a layer
+    ///
+    pub (super) fn write_layer_properties<'a>(&'a mut self, layer_id: u64, properties: LayerProperties) -> impl 'a+Future<Output=()> {
+        async move {
+            let mut serialized = String::new();
+            properties.serialize(&mut serialized);
+            self.request_one(StorageCommand::WriteLayerProperties(layer_id, serialized)).await;
+        }
+    }
+
+    ///
+    /// Reads the properties for a layer, updates them and writes them back again
+    ///
+    pub (super) fn update_layer_properties<'a, UpdateFn: 'a+Send+FnOnce(&mut LayerProperties)>(&'a mut self, layer_id: u64, update: UpdateFn) -> impl 'a+Future<Output=()> {
+        async move {
+            let mut properties = self.read_layer_properties(layer_id).await;
+            update(&mut properties);
+            self.write_layer_properties(layer_id, properties).await;
+        }
+    }
+
+    ///
+    /// Sets the name of a layer
+    ///
+    pub fn set_layer_name<'a>(&'a mut self, layer_id: u64, name: &'a str) -> impl 'a+Future<Output=()> { 
+        async move {
+            // Read the current properties for this layer
+            let mut properties = self.read_layer_properties(layer_id).await;
 
             // Update the name
             properties.name = name.to_string();
 
             // Save back to the storage
-            let mut serialized = String::new();
-            properties.serialize(&mut serialized);
-            self.request_one(StorageCommand::WriteLayerProperties(layer_id, serialized)).await;
+            self.write_layer_properties(layer_id, properties).await;
         } 
     }
+
+    ///
+    /// Turns a layer into a reference layer (or back into a normal layer)
+    ///
+    pub fn set_layer_reference<'a>(&'a mut self, layer_id: u64, reference: Option<ReferenceLayerSettings>) -> impl 'a+Future<Output=()> {
+        async move {
+            let mut properties = self.read_layer_properties(layer_id).await;
+            properties.reference = reference;
+            self.write_layer_properties(layer_id, properties).await;
+        }
+    }
+
+    ///
+    /// Stores the PNG data for the reference image displayed from the specified time
+    ///
+    pub fn add_reference_image<'a>(&'a mut self, layer_id: u64, when: Duration, asset_id: u64) -> impl 'a+Future<Output=()> {
+        async move {
+            let mut serialized = String::new();
+            serialized.write_small_u64(asset_id);
+
+            self.request_one(StorageCommand::WriteReferenceImage(layer_id, when, serialized)).await;
+        }
+    }
+
+    ///
+    /// Removes the reference image displayed from the specified time
+    ///
+    pub fn remove_reference_image<'a>(&'a mut self, layer_id: u64, when: Duration) -> impl 'a+Future<Output=()> {
+        async move {
+            self.request_one(StorageCommand::DeleteReferenceImage(layer_id, when)).await;
+        }
+    }
+
+    ///
+    /// Moves a layer into a folder (or out to the top level if the parent is None)
+    ///
+    pub fn set_layer_parent<'a>(&'a mut self, layer_id: u64, parent_id: Option<u64>) -> impl 'a+Future<Output=()> {
+        async move {
+            let layers          = self.read_all_layer_properties().await;
+            let find_layer      = |find_id: u64| layers.iter().filter(|(id, _)| *id == find_id).map(|(_, properties)| properties).nth(0);
+
+            let layer           = match find_layer(layer_id) {
+                Some(layer)     => layer,
+                None            => { return; }
+            };
+
+            if let Some(parent_id) = parent_id {
+                // The parent must be a folder in the same animation or symbol as the layer
+                match find_layer(parent_id) {
+                    Some(parent) if parent.folder && parent.symbol == layer.symbol  => { }
+                    _                                                               => { return; }
+                }
+
+                // A layer can't be moved inside itself
+                let mut ancestor_id = Some(parent_id);
+                let mut depth       = 0;
+                while let Some(check_id) = ancestor_id {
+                    if check_id == layer_id || depth > layers.len() { return; }
+
+                    ancestor_id = find_layer(check_id).and_then(|ancestor| ancestor.parent);
+                    depth       += 1;
+                }
+            }
+
+            self.update_layer_properties(layer_id, move |properties| properties.parent = parent_id).await;
+        }
+    }
 }
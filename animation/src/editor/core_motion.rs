@@ -31,7 +31,7 @@ impl StreamAnimationCore {
                     let motion          = ElementWrapper::unattached_with_element(motion, Duration::from_millis(0));
 
                     // Write
-                    self.request_one(StorageCommand::WriteElement(motion_id, motion.serialize_to_string())).await;
+                    self.request_one(StorageCommand::WriteElement(motion_id, motion.serialize_to_data())).await;
                 }
                 Delete                  => { self.request_one(StorageCommand::DeleteElement(motion_id)).await; }
 
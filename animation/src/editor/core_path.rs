@@ -69,10 +69,7 @@ impl StreamAnimationCore {
                     let element_id              = element_id.id().unwrap_or(0);
                     let element_wrapper         = ElementWrapper::unattached_with_element(element, when);
 
-                    let mut element_string  = String::new();
-                    element_wrapper.serialize(&mut element_string);
-
-                    self.request(vec![StorageCommand::WriteElement(element_id, element_string)]).await;
+                    self.request(vec![StorageCommand::WriteElement(element_id, element_wrapper.serialize_to_data())]).await;
                 }
 
                 BrushProperties(element_id, properties) => {
@@ -85,10 +82,7 @@ impl StreamAnimationCore {
                     let element_id              = element_id.id().unwrap_or(0);
                     let element_wrapper         = ElementWrapper::unattached_with_element(element, when);
 
-                    let mut element_string  = String::new();
-                    element_wrapper.serialize(&mut element_string);
-
-                    self.request(vec![StorageCommand::WriteElement(element_id, element_string)]).await;
+                    self.request(vec![StorageCommand::WriteElement(element_id, element_wrapper.serialize_to_data())]).await;
                 }
             };
         }
@@ -0,0 +1,273 @@
+use super::element_wrapper::*;
+use super::keyframe_core::*;
+use super::stream_frame::*;
+use super::stream_animation_core::*;
+use crate::traits::*;
+use crate::storage::storage_api::*;
+use crate::storage::layer_properties::*;
+use crate::storage::symbol_properties::*;
+
+use futures::prelude::*;
+use futures::future::{BoxFuture};
+
+use std::sync::*;
+use std::time::{Duration};
+use std::collections::{HashMap, HashSet};
+
+///
+/// The content that has already been loaded for each symbol ID and time within the symbol
+///
+type LoadedSymbolContent = HashMap<(u64, Duration), Vec<Arc<dyn Frame>>>;
+
+impl StreamAnimationCore {
+    ///
+    /// Performs an edit on a symbol
+    ///
+    pub fn symbol_edit<'a>(&'a mut self, symbol_id: u64, symbol_edit: &'a SymbolEdit) -> impl 'a+Future<Output=()> {
+        use self::SymbolEdit::*;
+
+        async move {
+            match symbol_edit {
+                SetName(new_name)       => { self.set_symbol_name(symbol_id, new_name).await }
+                SetLength(length)       => { self.set_symbol_length(symbol_id, *length).await }
+                AddNewLayer(layer_id)   => { self.add_new_symbol_layer(symbol_id, *layer_id).await }
+            }
+        }
+    }
+
+    ///
+    /// Reads the properties for all of the symbols in this animation
+    ///
+    pub fn read_symbols<'a>(&'a mut self) -> impl 'a+Future<Output=Vec<(u64, SymbolProperties)>> {
+        async move {
+            let symbols = self.request(vec![StorageCommand::ReadSymbols]).await.unwrap_or_else(|| vec![]);
+
+            symbols.into_iter()
+                .filter_map(|response| match response {
+                    StorageResponse::Symbol(symbol_id, properties)  => Some((symbol_id, SymbolProperties::deserialize(&mut properties.chars()).unwrap_or_else(|| SymbolProperties::default()))),
+                    _                                               => None
+                })
+                .collect()
+        }
+    }
+
+    ///
+    /// Reads the properties for a symbol (or None if the symbol doesn't exist)
+    ///
+    pub fn read_symbol_properties<'a>(&'a mut self, symbol_id: u64) -> impl 'a+Future<Output=Option<SymbolProperties>> {
+        async move {
+            self.read_symbols().await
+                .into_iter()
+                .filter(|(id, _)| *id == symbol_id)
+                .map(|(_, properties)| properties)
+                .next()
+        }
+    }
+
+    ///
+    /// Writes back the properties for a symbol
+    ///
+    fn write_symbol_properties<'a>(&'a mut self, symbol_id: u64, properties: SymbolProperties) -> impl 'a+Future<Output=()> {
+        async move {
+            let mut serialized = String::new();
+            properties.serialize(&mut serialized);
+            self.request_one(StorageCommand::WriteSymbol(symbol_id, serialized)).await;
+        }
+    }
+
+    ///
+    /// Retrieves the IDs of the layers that belong to a symbol, in the order they're drawn
+    ///
+    pub fn symbol_layer_ids<'a>(&'a mut self, symbol_id: u64) -> impl 'a+Future<Output=Vec<u64>> {
+        async move {
+            let layers = self.read_all_layer_properties().await.into_iter()
+                .filter(|(_, properties)| properties.symbol == Some(symbol_id))
+                .collect::<Vec<_>>();
+
+            LayerProperties::hierarchy_order(layers)
+        }
+    }
+
+    ///
+    /// Adds a new symbol with a particular ID to this animation
+    ///
+    pub fn add_new_symbol<'a>(&'a mut self, symbol_id: u64) -> impl 'a+Future<Output=()> {
+        async move {
+            // Adding a symbol that already exists would reset its properties
+            if self.read_symbol_properties(symbol_id).await.is_some() {
+                return;
+            }
+
+            self.write_symbol_properties(symbol_id, SymbolProperties::default()).await;
+        }
+    }
+
+    ///
+    /// Removes a symbol and all of its layers from the animation
+    ///
+    pub fn remove_symbol<'a>(&'a mut self, symbol_id: u64) -> impl 'a+Future<Output=()> {
+        async move {
+            let layer_ids = self.symbol_layer_ids(symbol_id).await;
+
+            let mut updates = layer_ids.into_iter().map(|layer_id| StorageCommand::DeleteLayer(layer_id)).collect::<Vec<_>>();
+            updates.push(StorageCommand::DeleteSymbol(symbol_id));
+
+            self.request(updates).await;
+
+            // The cached keyframe might have been in one of the layers that was removed
+            self.cached_keyframe = None;
+        }
+    }
+
+    ///
+    /// Sets the name of a symbol
+    ///
+    pub fn set_symbol_name<'a>(&'a mut self, symbol_id: u64, name: &'a str) -> impl 'a+Future<Output=()> {
+        async move {
+            if let Some(mut properties) = self.read_symbol_properties(symbol_id).await {
+                properties.name = name.to_string();
+                self.write_symbol_properties(symbol_id, properties).await;
+            }
+        }
+    }
+
+    ///
+    /// Sets the length of a symbol's timeline
+    ///
+    pub fn set_symbol_length<'a>(&'a mut self, symbol_id: u64, length: Duration) -> impl 'a+Future<Output=()> {
+        async move {
+            if let Some(mut properties) = self.read_symbol_properties(symbol_id).await {
+                properties.length = length;
+                self.write_symbol_properties(symbol_id, properties).await;
+            }
+        }
+    }
+
+    ///
+    /// Adds a new layer to a symbol
+    ///
+    pub fn add_new_symbol_layer<'a>(&'a mut self, symbol_id: u64, layer_id: u64) -> impl 'a+Future<Output=()> {
+        async move {
+            // Layers can only be added to symbols that exist
+            if self.read_symbol_properties(symbol_id).await.is_none() {
+                return;
+            }
+
+            // Layer IDs are shared with the main animation
+            if let Some(StorageResponse::LayerProperties(_, _)) = self.request_one(StorageCommand::ReadLayerProperties(layer_id)).await {
+                return;
+            }
+
+            // New layers are drawn in front of the existing layers in the symbol
+            let existing_layers = self.symbol_layer_ids(symbol_id).await;
+            let properties      = LayerProperties {
+                symbol:     Some(symbol_id),
+                ordering:   existing_layers.len() as i64,
+                ..LayerProperties::default()
+            };
+
+            let mut serialized  = String::new();
+            properties.serialize(&mut serialized);
+
+            self.request_one(StorageCommand::AddLayer(layer_id, serialized)).await;
+        }
+    }
+
+    ///
+    /// Adds a new instance of a symbol to a layer
+    ///
+    pub fn create_symbol_instance<'a>(&'a mut self, layer_id: u64, when: Duration, element_id: ElementId, instance: &'a SymbolInstance) -> impl 'a+Future<Output=()> {
+        async move {
+            // Instances can only be added on a keyframe
+            let current_keyframe = match self.edit_keyframe(layer_id, when).await {
+                None            => { return; }
+                Some(keyframe)  => keyframe
+            };
+
+            // Create the instance element
+            let element = Vector::Symbol(SymbolElement::new(element_id, instance.clone()));
+
+            // Add it to the end of the keyframe
+            let storage_updates = current_keyframe.future(move |current_keyframe| {
+                async move {
+                    let wrapper = ElementWrapper::attached_with_element(element, when);
+                    current_keyframe.add_element_to_end(element_id, wrapper)
+                }.boxed()
+            }).await;
+
+            self.request(storage_updates.unwrap()).await;
+        }
+    }
+
+    ///
+    /// Loads the content of any symbol instances in a keyframe, so that they can be rendered at the specified time
+    ///
+    pub fn load_symbol_content<'a>(&'a mut self, keyframe: &'a mut KeyFrameCore, when: Duration) -> impl 'a+Future<Output=()> {
+        async move {
+            let mut loading = HashSet::new();
+            let mut loaded  = HashMap::new();
+
+            self.load_nested_symbol_content(keyframe, when, &mut loading, &mut loaded).await;
+        }
+    }
+
+    ///
+    /// Loads the content of any symbol instances in a keyframe
+    ///
+    /// `loading` is the set of symbols whose content is being loaded: instances of these symbols are inside themselves, so
+    /// they're left without any content. `loaded` stores the content that has already been loaded, so symbols that are
+    /// used many times are only loaded once.
+    ///
+    fn load_nested_symbol_content<'a>(&'a mut self, keyframe: &'a mut KeyFrameCore, when: Duration, loading: &'a mut HashSet<u64>, loaded: &'a mut LoadedSymbolContent) -> BoxFuture<'a, ()> {
+        async move {
+            // Find the instances that are visible at this time
+            let mut instances = keyframe.elements.iter()
+                .filter(|(_, wrapper)| wrapper.start_time <= when)
+                .filter_map(|(element_id, wrapper)| match &wrapper.element {
+                    Vector::Symbol(symbol)  => Some((*element_id, wrapper.start_time, symbol.clone())),
+                    _                       => None
+                })
+                .collect::<Vec<_>>();
+            instances.sort_by_key(|(element_id, _, _)| element_id.id());
+
+            for (element_id, start_time, symbol) in instances {
+                let symbol_id   = symbol.instance().symbol_id;
+                if loading.contains(&symbol_id) {
+                    continue;
+                }
+
+                let properties  = match self.read_symbol_properties(symbol_id).await {
+                    Some(properties)    => properties,
+                    None                => { continue; }
+                };
+
+                // Work out which frame of the symbol to display
+                let symbol_time = symbol.instance().symbol_time(when - start_time, properties.length);
+
+                // Load the frames from each of the symbol's layers (unless they've already been loaded for another instance)
+                let content = if let Some(content) = loaded.get(&(symbol_id, symbol_time)) {
+                    content.clone()
+                } else {
+                    let mut content = vec![];
+
+                    loading.insert(symbol_id);
+                    for layer_id in self.symbol_layer_ids(symbol_id).await {
+                        if let Some(mut layer_keyframe) = self.load_tweened_keyframe(layer_id, symbol_time).await {
+                            self.load_nested_symbol_content(&mut layer_keyframe, symbol_time, loading, loaded).await;
+
+                            content.push(Arc::new(StreamFrame::new(symbol_time, Some(layer_keyframe))) as Arc<dyn Frame>);
+                        }
+                    }
+                    loading.remove(&symbol_id);
+
+                    loaded.insert((symbol_id, symbol_time), content.clone());
+                    content
+                };
+
+                if let Some(wrapper) = keyframe.elements.get_mut(&element_id) {
+                    wrapper.element = Vector::Symbol(symbol.with_content(content));
+                }
+            }
+        }.boxed()
+    }
+}
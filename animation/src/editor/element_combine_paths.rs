@@ -0,0 +1,112 @@
+use super::stream_animation_core::*;
+use crate::storage::storage_api::*;
+use crate::traits::*;
+
+use flo_curves::bezier::path::*;
+use futures::prelude::*;
+
+use std::sync::*;
+
+impl StreamAnimationCore {
+    ///
+    /// Combines the paths of a set of elements using a path arithmetic operation
+    ///
+    /// The first element is replaced by a path element containing the result, and the other elements are deleted. Elements that
+    /// are not in the same keyframe as the first element are left alone. Nothing is changed if the result is an empty path.
+    ///
+    pub fn combine_element_paths<'a>(&'a mut self, element_ids: Vec<i64>, combine: PathCombine) -> impl 'a+Send+Future<Output=()> {
+        async move {
+            // Need at least two elements to combine
+            if element_ids.len() < 2 {
+                return;
+            }
+
+            // Fetch the frame for the first element
+            let first_element_id = element_ids[0];
+
+            let frame = match self.edit_keyframe_for_element(first_element_id).await {
+                Some(frame)     => frame,
+                None            => { return; }
+            };
+
+            let (updates, removed_ids) = frame.future(move |frame| {
+                async move {
+                    // Convert each of the elements in this frame to a set of subpaths
+                    let mut element_paths = vec![];
+
+                    for element_id in element_ids.iter() {
+                        if let Some(wrapper) = frame.elements.get(&ElementId::Assigned(*element_id)) {
+                            let properties  = frame.apply_properties_for_element(&wrapper.element, Arc::new(VectorProperties::default()), frame.start);
+                            let paths       = wrapper.element.to_path(&*properties, PathConversion::RemoveInteriorPoints).unwrap_or(vec![]);
+                            let paths       = paths.into_iter().flat_map(|path| path.to_subpaths()).collect::<Vec<_>>();
+
+                            element_paths.push((*element_id, paths));
+                        }
+                    }
+
+                    // The first element must be in the frame, and there must be something to combine it with
+                    if element_paths.len() < 2 || element_paths[0].0 != first_element_id {
+                        return (vec![], vec![]);
+                    }
+
+                    // Apply the operation to each element in turn
+                    let mut combined = element_paths[0].1.clone();
+
+                    for (_, paths) in element_paths.iter().skip(1) {
+                        combined = match combine {
+                            PathCombine::Union      => path_add::<_, _, _, Path>(&combined, paths, 0.01),
+                            PathCombine::Subtract   => path_sub::<_, _, _, Path>(&combined, paths, 0.01),
+                            PathCombine::Intersect  => path_intersect::<_, _, _, Path>(&combined, paths, 0.01),
+
+                            PathCombine::Exclude    => {
+                                let added       = path_add::<_, _, _, Path>(&combined, paths, 0.01);
+                                let overlapping = path_intersect::<_, _, _, Path>(&combined, paths, 0.01);
+
+                                path_sub::<_, _, _, Path>(&added, &overlapping, 0.01)
+                            }
+                        };
+                    }
+
+                    if combined.len() == 0 {
+                        return (vec![], vec![]);
+                    }
+
+                    // The path element uses the brush from the first element
+                    let mut wrapper             = frame.elements.get(&ElementId::Assigned(first_element_id)).unwrap().clone();
+                    let mut brush_definition    = BrushDefinitionElement::default();
+                    let mut brush_properties    = BrushPropertiesElement::default();
+
+                    for attachment_id in wrapper.attachments.iter() {
+                        match frame.elements.get(attachment_id).map(|attachment| &attachment.element) {
+                            Some(Vector::BrushDefinition(brush_defn))   => { brush_definition = brush_defn.clone(); },
+                            Some(Vector::BrushProperties(brush_props))  => { brush_properties = brush_props.clone(); },
+                            _                                           => { }
+                        }
+                    }
+
+                    // Replace the first element with the combined path
+                    let path        = Path::from_paths(&combined);
+                    let path        = PathElement::new(ElementId::Assigned(first_element_id), path, Arc::new(brush_definition), Arc::new(brush_properties));
+                    wrapper.element = Vector::Path(path);
+
+                    let updates     = vec![StorageCommand::WriteElement(first_element_id, wrapper.serialize_to_data())];
+                    frame.elements.insert(ElementId::Assigned(first_element_id), wrapper);
+
+                    // The other elements have been merged into the first one
+                    let removed_ids = element_paths.into_iter().skip(1).map(|(element_id, _)| element_id).collect::<Vec<_>>();
+
+                    (updates, removed_ids)
+                }.boxed()
+            }).await.unwrap();
+
+            // Update the combined element
+            self.request(updates).await;
+
+            // Delete the elements that were combined into it
+            if removed_ids.len() > 0 {
+                self.remove_from_attachments(&removed_ids).await;
+                self.request(removed_ids.into_iter().map(|id| StorageCommand::DeleteElement(id))).await;
+            }
+        }
+    }
+}
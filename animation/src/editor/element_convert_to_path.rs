@@ -70,7 +70,7 @@ impl StreamAnimationCore {
                     wrapper.element = path;
                     
                     // Create the updates to send to storage
-                    let updates     = vec![StorageCommand::WriteElement(assigned_element_id, wrapper.serialize_to_string())];
+                    let updates     = vec![StorageCommand::WriteElement(assigned_element_id, wrapper.serialize_to_data())];
 
                     // Replace the wrapper in the frame
                     frame.elements.insert(convert_element_id, wrapper);
@@ -126,6 +126,44 @@ impl TransformsForElements {
         }
     }
 
+    ///
+    /// Spaces out the elements evenly within the bounding box
+    ///
+    /// The elements are ordered by their left (or bottom) edge, and the elements at either end stay where they are
+    ///
+    fn distribute(&mut self, overall_bounds: Rect, horizontally: bool) {
+        // Order the elements by their position along the axis
+        let mut ordered_bounds = self.bounds_for_element.iter()
+            .map(|(elem_id, bounds)| (*elem_id, *bounds))
+            .collect::<Vec<_>>();
+
+        if ordered_bounds.len() < 3 {
+            return;
+        }
+
+        let start_of    = |bounds: &Rect| if horizontally { bounds.x1 } else { bounds.y1 };
+        let length_of   = |bounds: &Rect| if horizontally { bounds.width() } else { bounds.height() };
+        ordered_bounds.sort_by(|(_, a), (_, b)| start_of(a).partial_cmp(&start_of(b)).unwrap_or(std::cmp::Ordering::Equal));
+
+        // The space between each element is whatever is left over after the elements themselves are accounted for
+        let total_length    = ordered_bounds.iter().map(|(_, bounds)| length_of(bounds)).sum::<f32>();
+        let spacing         = (length_of(&overall_bounds) - total_length) / ((ordered_bounds.len()-1) as f32);
+
+        // Move each element along
+        let mut pos         = start_of(&overall_bounds);
+        for (elem_id, bounds) in ordered_bounds {
+            let offset = (pos - start_of(&bounds)) as f64;
+            let (x, y) = if horizontally { (offset, 0.0) } else { (0.0, offset) };
+
+            if x != 0.0 || y != 0.0 {
+                self.transformations_for_element.get_mut(&elem_id)
+                    .map(|transform| transform.push(Transformation::Translate(x, y)));
+            }
+
+            pos += length_of(&bounds) + spacing;
+        }
+    }
+
     ///
     /// Flips the elements horizontally around a point
     ///
@@ -246,6 +284,18 @@ impl StreamAnimationCore {
                         }
                     }
 
+                    ElementTransform::DistributeHorizontally => {
+                        if let Some(bounding_box) = bounding_box {
+                            element_transforms.distribute(bounding_box, true);
+                        }
+                    }
+
+                    ElementTransform::DistributeVertically => {
+                        if let Some(bounding_box) = bounding_box {
+                            element_transforms.distribute(bounding_box, false);
+                        }
+                    }
+
                     ElementTransform::FlipHorizontal    => {
                         if let Some(origin) = transform_origin {
                             element_transforms.flip_horizontal(origin);
@@ -287,7 +337,7 @@ impl StreamAnimationCore {
                             let transform_id            = existing_attachment_id.id()?;
                             transform_wrapper.element   = Vector::Transformation((transform_wrapper.element.id(), new_transformations.clone()));
 
-                            update_elements.push(StorageCommand::WriteElement(transform_id, transform_wrapper.serialize_to_string()));
+                            update_elements.push(StorageCommand::WriteElement(transform_id, transform_wrapper.serialize_to_data()));
 
                             Some(())
                         });
@@ -298,7 +348,7 @@ impl StreamAnimationCore {
 
                         frame.sync(|frame| {
                             // Create the element and attach it
-                            update_elements.push(StorageCommand::WriteElement(attachment_id.id().unwrap(), attachment_wrapper.serialize_to_string()));
+                            update_elements.push(StorageCommand::WriteElement(attachment_id.id().unwrap(), attachment_wrapper.serialize_to_data()));
                             update_elements.push(StorageCommand::AttachElementToLayer(frame.layer_id, attachment_id.id().unwrap(), frame.start));
                             new_attachments.insert(*element_id, attachment_id);
 
@@ -1,7 +1,6 @@
 use crate::traits::*;
 use crate::serializer::*;
 
-use std::str::{Chars};
 use std::time::{Duration};
 
 ///
@@ -82,12 +81,12 @@ impl ElementWrapper {
     }
 
     ///
-    /// Generates a string version of this element
+    /// Serializes this element using the binary encoding
     ///
-    pub fn serialize_to_string(&self) -> String {
-        let mut result = String::new();
-        self.serialize(&mut result);
-        result
+    pub fn serialize_to_data(&self) -> SerializedData {
+        let mut data: Vec<u8> = vec![];
+        self.serialize(&mut data);
+        SerializedData::Binary(data)
     }
 
     ///
@@ -141,7 +140,7 @@ impl ElementWrapper {
     ///
     /// Deserializes from a data source
     ///
-    pub fn deserialize(element_id: ElementId, data: &mut Chars) -> Option<impl ResolveElements<ElementWrapper>> {
+    pub fn deserialize<Src: AnimationDataSource>(element_id: ElementId, data: &mut Src) -> Option<BoxedResolver<ElementWrapper>> {
         match data.next_small_u64() {
             0 => {
                 // Version 0
@@ -179,7 +178,7 @@ impl ElementWrapper {
                 }?;
 
                 // Result is a resolver that creates the wrapper
-                Some(ElementResolver(move |mapper| {
+                Some(BoxedResolver::new(move |mapper| {
                     let element = element.resolve(mapper)?;
 
                     Some(ElementWrapper {
@@ -198,4 +197,14 @@ impl ElementWrapper {
             _ => None
         }
     }
+
+    ///
+    /// Deserializes from serialized data in either encoding
+    ///
+    pub fn deserialize_data(element_id: ElementId, data: &SerializedData) -> Option<BoxedResolver<ElementWrapper>> {
+        match data {
+            SerializedData::Text(text)      => ElementWrapper::deserialize(element_id, &mut text.chars()),
+            SerializedData::Binary(bytes)   => ElementWrapper::deserialize(element_id, &mut &bytes[..])
+        }
+    }
 }
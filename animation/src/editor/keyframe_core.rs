@@ -5,12 +5,18 @@ use crate::storage::storage_api::*;
 use crate::traits::*;
 use crate::serializer::*;
 
+use flo_logging::*;
+
 use futures::prelude::*;
 
 use std::sync::*;
 use std::time::{Duration};
 use std::collections::{HashSet, HashMap};
 
+lazy_static! {
+    static ref KEYFRAME_CORE_LOG: LogPublisher = LogPublisher::new(module_path!());
+}
+
 ///
 /// The keyframe core represents the elements in a keyframe in a particular layer
 ///
@@ -35,7 +41,10 @@ pub (super) struct KeyFrameCore {
     pub (super) end: Duration,
 
     /// The brush that's active on the last_element, or none if this has not been calculated yet
-    pub (super) active_brush: Option<Arc<dyn Brush>>
+    pub (super) active_brush: Option<Arc<dyn Brush>>,
+
+    /// The parents of the bones in this keyframe that are not attached to the keyframe themselves
+    pub (super) parent_bones: HashMap<ElementId, BoneElement>
 }
 
 ///
@@ -74,6 +83,10 @@ impl KeyFrameCore {
     ///
     pub fn from_keyframe<'a>(core: &'a mut StreamAnimationCore, layer_id: u64, frame: Duration) -> impl 'a+Future<Output=Option<KeyFrameCore>> {
         async move {
+            let _span = KEYFRAME_CORE_LOG.span("from_keyframe")
+                .with_field("layer_id", layer_id)
+                .with_field("frame_ms", frame.as_millis());
+
             // Request the keyframe from the core
             let responses = core.request(vec![StorageCommand::ReadElementsForKeyFrame(layer_id, frame)]).await.unwrap_or_else(|| vec![]);
 
@@ -92,7 +105,7 @@ impl KeyFrameCore {
                     Element(element_id, serialized)     => {
                         // Add the element to the list we know about for this keyframe
                         let element_id  = ElementId::Assigned(element_id);
-                        let element     = ElementWrapper::deserialize(element_id, &mut serialized.chars());
+                        let element     = ElementWrapper::deserialize_data(element_id, &serialized);
 
                         elements.insert(element_id, element);
                         element_ids.push(element_id);
@@ -176,6 +189,9 @@ impl KeyFrameCore {
                 None
             };
 
+            // Bones are posed by their parents, so these need to be loaded even if they aren't in this keyframe
+            let parent_bones = Self::load_parent_bones(core, &resolved).await;
+
             // Create the keyframe
             Some(KeyFrameCore {
                 layer_id:           layer_id,
@@ -184,11 +200,87 @@ impl KeyFrameCore {
                 last_element:       last_element,
                 start:              start_time,
                 end:                end_time,
-                active_brush:       None
+                active_brush:       None,
+                parent_bones:       parent_bones
             })
         }
     }
 
+    ///
+    /// Loads the parents of the bones in a set of elements that are not in the set themselves
+    ///
+    fn load_parent_bones<'a>(core: &'a mut StreamAnimationCore, elements: &'a HashMap<ElementId, ElementWrapper>) -> impl 'a+Future<Output=HashMap<ElementId, BoneElement>> {
+        async move {
+            let mut parent_bones    = HashMap::new();
+            let mut requested       = HashSet::new();
+            let mut bones           = elements.values()
+                .filter_map(|wrapper| match &wrapper.element {
+                    Vector::Bone(bone)  => Some(bone.clone()),
+                    _                   => None
+                })
+                .collect::<Vec<_>>();
+
+            loop {
+                // Find the parent bones that haven't been loaded yet (each bone is only requested once so a bad file can't make us loop forever)
+                let missing_parents = bones.iter()
+                    .filter_map(|bone| bone.bone().parent)
+                    .filter(|parent_id| !elements.contains_key(parent_id))
+                    .filter_map(|parent_id| parent_id.id())
+                    .filter(|parent_id| requested.insert(*parent_id))
+                    .collect::<Vec<_>>();
+
+                if missing_parents.is_empty() {
+                    break;
+                }
+
+                // Request the bones from storage (bones have no dependencies, so they can be resolved immediately)
+                let responses = core.request(missing_parents.into_iter().map(|parent_id| StorageCommand::ReadElement(parent_id)).collect::<Vec<_>>()).await.unwrap_or_else(|| vec![]);
+
+                bones = responses.into_iter()
+                    .filter_map(|response| match response {
+                        StorageResponse::Element(element_id, serialized) => {
+                            ElementWrapper::deserialize_data(ElementId::Assigned(element_id), &serialized)
+                                .and_then(|wrapper| wrapper.resolve(&mut |_| None))
+                        }
+
+                        _ => None
+                    })
+                    .filter_map(|wrapper| match wrapper.element {
+                        Vector::Bone(bone)  => Some(bone),
+                        _                   => None
+                    })
+                    .collect();
+
+                parent_bones.extend(bones.iter().map(|bone| (bone.id(), bone.clone())));
+            }
+
+            parent_bones
+        }
+    }
+
+    ///
+    /// Finds a bone that's in this keyframe or is the parent of a bone in this keyframe
+    ///
+    pub fn bone_with_id(&self, bone_id: ElementId) -> Option<Arc<Bone>> {
+        match self.elements.get(&bone_id).map(|wrapper| &wrapper.element) {
+            Some(Vector::Bone(bone))    => Some(bone.bone()),
+            _                           => self.parent_bones.get(&bone_id).map(|bone| bone.bone())
+        }
+    }
+
+    ///
+    /// Retrieves all of the bones that can pose the elements in this keyframe
+    ///
+    pub fn bones(&self) -> Vec<BoneElement> {
+        self.elements.values()
+            .filter_map(|wrapper| match &wrapper.element {
+                Vector::Bone(bone)  => Some(bone.clone()),
+                _                   => None
+            })
+            .chain(self.parent_bones.values().cloned())
+            .collect()
+    }
+
     ///
     /// Retrieves the vector elements associated with this frame
     ///
@@ -481,7 +573,7 @@ impl KeyFrameCore {
             let mut properties = properties;
             for attachment_id in wrapper.attachments.iter() {
                 if let Some(attach_element) = self.elements.get(&attachment_id) {
-                    properties = attach_element.element.update_properties(Arc::clone(&properties), when);
+                    properties = self.update_properties_for_attachment(&attach_element.element, properties, when);
                 }
             }
 
@@ -492,6 +584,34 @@ impl KeyFrameCore {
         }
     }
 
+    ///
+    /// Updates a set of properties using an element that's attached to another element
+    ///
+    /// Bones pose the elements they're attached to using the rotations of their parents as well as their own rotation
+    ///
+    pub fn update_properties_for_attachment(&self, attachment: &Vector, properties: Arc<VectorProperties>, when: Duration) -> Arc<VectorProperties> {
+        match attachment {
+            Vector::Bone(bone) => {
+                let transformations = skeleton_transformations(bone.id(), when, |bone_id| self.bone_with_id(bone_id));
+
+                if transformations.len() > 0 {
+                    let mut properties      = (*properties).clone();
+                    let mut full_transform  = (*properties.transformations).clone();
+
+                    full_transform.extend(transformations);
+
+                    properties.transformations = Arc::new(full_transform);
+
+                    Arc::new(properties)
+                } else {
+                    properties
+                }
+            }
+
+            other => other.update_properties(properties, when)
+        }
+    }
+
     ///
     /// Returns the commands needed to add an attachment to the specified element
     ///
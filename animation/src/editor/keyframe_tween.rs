@@ -0,0 +1,154 @@
+use super::keyframe_core::*;
+use super::stream_animation_core::*;
+use crate::storage::storage_api::*;
+use crate::traits::*;
+
+use futures::prelude::*;
+
+use std::time::{Duration};
+use std::collections::{HashSet};
+
+impl KeyFrameCore {
+    ///
+    /// Returns the IDs of the top-level elements in this keyframe, in the order that they're drawn
+    ///
+    fn ordered_elements(&self) -> Vec<ElementId> {
+        let mut result          = vec![];
+        let mut visited         = HashSet::new();
+        let mut next_element    = self.initial_element;
+
+        while let Some(element_id) = next_element {
+            // Stop if the ordering loops back on itself
+            if !visited.insert(element_id) { break; }
+
+            let wrapper = match self.elements.get(&element_id) {
+                Some(wrapper)   => wrapper,
+                None            => { break; }
+            };
+
+            result.push(element_id);
+            next_element = wrapper.order_before;
+        }
+
+        result
+    }
+
+    ///
+    /// Returns the brush strokes and the paths that are drawn at the start of this keyframe, in drawing order
+    ///
+    fn tweenable_elements(&self) -> (Vec<ElementId>, Vec<ElementId>) {
+        let mut brush_strokes   = vec![];
+        let mut paths           = vec![];
+
+        for element_id in self.ordered_elements() {
+            let wrapper = &self.elements[&element_id];
+            if wrapper.start_time > self.start { continue; }
+
+            match &wrapper.element {
+                Vector::BrushStroke(_)  => { brush_strokes.push(element_id); }
+                Vector::Path(_)         => { paths.push(element_id); }
+                _                       => { }
+            }
+        }
+
+        (brush_strokes, paths)
+    }
+
+    ///
+    /// Returns true if any elements were drawn on the in-between frames of this keyframe up to the specified time
+    ///
+    /// Drawing on an in-between frame stops the tweening from that frame onwards.
+    ///
+    pub fn has_in_between_elements(&self, when: Duration) -> bool {
+        self.elements.values()
+            .any(|wrapper| wrapper.start_time > self.start && wrapper.start_time <= when)
+    }
+
+    ///
+    /// Moves the brush strokes and paths in this keyframe part of the way towards their shapes in the following keyframe
+    ///
+    /// Elements are matched up by type in the order that they're drawn: elements that have no corresponding element in
+    /// the target keyframe (or paths that can't be matched up with their counterpart) are left as they are.
+    ///
+    pub fn tween_to(&mut self, target: &KeyFrameCore, amount: f64) {
+        let (from_strokes, from_paths)  = self.tweenable_elements();
+        let (to_strokes, to_paths)      = target.tweenable_elements();
+
+        for (from_id, to_id) in from_strokes.into_iter().zip(to_strokes.into_iter()) {
+            let tweened = match (&self.elements[&from_id].element, &target.elements[&to_id].element) {
+                (Vector::BrushStroke(from), Vector::BrushStroke(to))    => from.tween_to(to, amount),
+                _                                                       => { continue; }
+            };
+
+            if let Some(wrapper) = self.elements.get_mut(&from_id) {
+                wrapper.element = Vector::BrushStroke(tweened);
+            }
+        }
+
+        for (from_id, to_id) in from_paths.into_iter().zip(to_paths.into_iter()) {
+            let tweened = match (&self.elements[&from_id].element, &target.elements[&to_id].element) {
+                (Vector::Path(from), Vector::Path(to))  => from.tween_to(to, amount),
+                _                                       => { continue; }
+            };
+
+            if let (Some(tweened), Some(wrapper)) = (tweened, self.elements.get_mut(&from_id)) {
+                wrapper.element = Vector::Path(tweened);
+            }
+        }
+    }
+}
+
+impl StreamAnimationCore {
+    ///
+    /// Loads the keyframe that's displayed at the specified time, tweening its elements towards the following keyframe
+    /// if it's set up to do that
+    ///
+    pub fn load_tweened_keyframe<'a>(&'a mut self, layer_id: u64, when: Duration) -> impl 'a+Future<Output=Option<KeyFrameCore>> {
+        async move {
+            let mut keyframe = self.load_keyframe(layer_id, when).await?;
+
+            // Find where the keyframe starts and ends
+            let keyframes   = self.request(vec![StorageCommand::ReadKeyFrames(layer_id, when..(when + Duration::from_micros(1)))]).await.unwrap_or_else(|| vec![]);
+            let bounds      = keyframes.into_iter()
+                .filter_map(|response| match response {
+                    StorageResponse::KeyFrame(start, end)   => Some((start, end)),
+                    _                                       => None
+                })
+                .next();
+
+            let (start, end) = match bounds {
+                Some(bounds)    => bounds,
+                None            => { return Some(keyframe); }
+            };
+
+            // Frames that are on the keyframe itself or after the last keyframe are never tweened
+            if when <= start || when >= end || end == Duration::from_micros(i64::max_value() as u64) {
+                return Some(keyframe);
+            }
+
+            // Only keyframes that have been marked as tweened are changed
+            let easing = match self.read_layer_properties(layer_id).await.tweens.get(&start) {
+                Some(easing)    => *easing,
+                None            => { return Some(keyframe); }
+            };
+
+            keyframe.start  = start;
+            keyframe.end    = end;
+
+            // Drawing on an in-between frame overrides the tweening
+            if keyframe.has_in_between_elements(when) {
+                return Some(keyframe);
+            }
+
+            // Tween towards the following keyframe
+            if let Some(next_keyframe) = self.load_keyframe(layer_id, end).await {
+                let proportion  = (when - start).as_secs_f64() / (end - start).as_secs_f64();
+                let amount      = easing.ease(proportion);
+
+                keyframe.tween_to(&next_keyframe, amount);
+            }
+
+            Some(keyframe)
+        }
+    }
+}
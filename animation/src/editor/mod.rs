@@ -7,23 +7,30 @@ mod stream_animation_core;
 mod core_path;
 mod core_paint;
 mod core_layer;
+mod core_keyframe;
 mod core_motion;
+mod core_bone;
 mod core_element;
+mod core_symbol;
+mod core_asset;
 mod keyframe_core;
 mod keyframe_raycast;
+mod keyframe_tween;
 mod pending_storage_change;
 mod paint_fill;
 mod element_wrapper;
 mod element_collide;
 mod element_transform;
 mod element_convert_to_path;
+mod element_combine_paths;
 mod stream_layer;
 mod stream_frame;
+mod stream_symbol;
 mod stream_layer_cache;
 
 #[cfg(test)] mod tests;
 
-use self::stream_animation::*;
+pub use self::stream_animation::StreamAnimation;
 use crate::storage::storage_api::*;
 use crate::traits::*;
 
@@ -1,4 +1,5 @@
 use super::element_wrapper::*;
+use crate::serializer::*;
 use crate::storage::storage_api::*;
 
 use std::collections::{HashMap};
@@ -9,7 +10,7 @@ enum ElementChange {
     Wrapper(ElementWrapper),
 
     /// A change that has already been serialized
-    Serialized(String)
+    Serialized(SerializedData)
 }
 
 ///
@@ -88,7 +89,7 @@ impl IntoIterator for PendingStorageChange {
         let update_elements = self.element_changes.into_iter()
             .map(|(element_id, change)| {
                 match change {
-                    ElementChange::Wrapper(wrapper) => StorageCommand::WriteElement(element_id, wrapper.serialize_to_string()),
+                    ElementChange::Wrapper(wrapper) => StorageCommand::WriteElement(element_id, wrapper.serialize_to_data()),
                     ElementChange::Serialized(data) => StorageCommand::WriteElement(element_id, data)
                 }
             });
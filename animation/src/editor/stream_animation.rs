@@ -1,9 +1,11 @@
 use super::stream_layer::*;
+use super::stream_symbol::*;
 use super::element_wrapper::*;
 use super::stream_animation_core::*;
 use crate::storage::storage_api::*;
 use crate::storage::file_properties::*;
 use crate::storage::layer_properties::*;
+use crate::storage::symbol_properties::*;
 use crate::traits::*;
 use crate::serializer::*;
 
@@ -50,6 +52,7 @@ impl StreamAnimation {
             storage_responses:  storage_responses,
             storage_requests:   requests,
             next_element_id:    None,
+            next_asset_id:      None,
             cached_keyframe:    None,
             brush_defn:         None,
             brush_props:        None,
@@ -162,17 +165,23 @@ impl Animation for StreamAnimation {
         self.wait_for_edits();
 
         let layer_responses = self.request_sync(vec![StorageCommand::ReadLayers]).unwrap_or_else(|| vec![]);
-
-        layer_responses
+        let layers          = layer_responses
             .into_iter()
             .map(|response| {
                 match response {
-                    StorageResponse::LayerProperties(id, _) => Some(id),
-                    _                                       => None
+                    StorageResponse::LayerProperties(id, properties)    => Some((id, LayerProperties::deserialize(&mut properties.chars()).unwrap_or_else(|| LayerProperties::default()))),
+                    _                                                   => None
                 }
             })
             .flatten()
-            .collect()
+            .filter(|(_, properties)| {
+                // Layers that belong to symbols are not part of the main animation
+                properties.symbol.is_none()
+            })
+            .collect();
+
+        // Layers in folders follow the folder they're in
+        LayerProperties::hierarchy_order(layers)
     }
 
     ///
@@ -198,6 +207,62 @@ impl Animation for StreamAnimation {
         }
     }
 
+    ///
+    /// Retrieves the IDs of the symbols in this animation
+    ///
+    fn get_symbol_ids(&self) -> Vec<u64> {
+        self.wait_for_edits();
+
+        let symbol_responses = self.request_sync(vec![StorageCommand::ReadSymbols]).unwrap_or_else(|| vec![]);
+
+        symbol_responses
+            .into_iter()
+            .map(|response| {
+                match response {
+                    StorageResponse::Symbol(id, _)  => Some(id),
+                    _                               => None
+                }
+            })
+            .flatten()
+            .collect()
+    }
+
+    ///
+    /// Retrieves the symbol with the specified ID from this animation
+    ///
+    fn get_symbol_with_id(&self, symbol_id: u64) -> Option<Arc<dyn Symbol>> {
+        self.wait_for_edits();
+
+        // Read the properties for the symbol
+        let symbol_responses    = self.request_sync(vec![StorageCommand::ReadSymbols]).unwrap_or_else(|| vec![]);
+        let properties          = symbol_responses.into_iter()
+            .map(|response| {
+                match response {
+                    StorageResponse::Symbol(id, properties) if id == symbol_id  => SymbolProperties::deserialize(&mut properties.chars()),
+                    _                                                           => None
+                }
+            })
+            .flatten()
+            .next()?;
+
+        // Find the layers that belong to this symbol
+        let layer_responses     = self.request_sync(vec![StorageCommand::ReadLayers]).unwrap_or_else(|| vec![]);
+        let layers              = layer_responses.into_iter()
+            .map(|response| {
+                match response {
+                    StorageResponse::LayerProperties(id, properties)    => LayerProperties::deserialize(&mut properties.chars()).map(|properties| (id, properties)),
+                    _                                                   => None
+                }
+            })
+            .flatten()
+            .filter(|(_, properties)| properties.symbol == Some(symbol_id))
+            .collect::<Vec<_>>();
+
+        let layer_ids           = LayerProperties::hierarchy_order(layers);
+
+        Some(Arc::new(StreamSymbol::new(symbol_id, properties, layer_ids)))
+    }
+
     ///
     /// Retrieves the total number of edits that have been performed on this animation
     ///
@@ -268,7 +333,7 @@ impl Animation for StreamAnimation {
                                 // Ignore everything that's not an edit (we have no way to do error handling here)
                                 if let StorageResponse::Edit(_num, serialized_edit) = response {
                                     // Store edits that deserialize successfully on the fetched list
-                                    if let Some(edit) = AnimationEdit::deserialize(&mut serialized_edit.chars()) {
+                                    if let Some(edit) = AnimationEdit::deserialize_data(&serialized_edit) {
                                         fetched.push(edit)
                                     }
                                 }
@@ -284,6 +349,40 @@ impl Animation for StreamAnimation {
         }).fuse().boxed()
     }
 
+    ///
+    /// Retrieves the named snapshots of the edit log that are stored with this animation
+    ///
+    fn get_snapshots(&self) -> Vec<EditLogCheckpoint> {
+        self.wait_for_edits();
+
+        self.request_sync(vec![StorageCommand::ReadSnapshots]).unwrap_or_else(|| vec![])
+            .into_iter()
+            .flat_map(|response| match response {
+                StorageResponse::Snapshot(name, edit_index) => Some(EditLogCheckpoint { name, edit_index }),
+                _                                           => None
+            })
+            .collect()
+    }
+
+    ///
+    /// Retrieves the data for an asset stored with this animation
+    ///
+    fn get_asset(&self, asset_id: u64) -> Option<Arc<Vec<u8>>> {
+        // Create a queue to run the 'read asset' future on
+        let core    = Arc::clone(&self.core);
+        let request = Desync::new(None);
+
+        // Perform the request
+        let _ = request.future(move |result| {
+            async move {
+                *result = core.future(move |core| core.read_asset(asset_id).boxed()).await.unwrap()
+            }.boxed()
+        });
+
+        // Retrieve the result
+        request.sync(|result| result.take())
+    }
+
     ///
     /// Supplies a reference which can be used to find the motions associated with this animation
     ///
@@ -314,6 +413,42 @@ impl EditableAnimation for StreamAnimation {
         request.sync(|result| result.take()).unwrap()
     }
 
+    ///
+    /// Assigns a new unique ID for storing an asset
+    ///
+    fn assign_asset_id(&self) -> u64 {
+        // Create a queue to run the 'assign asset ID' future on
+        let core    = Arc::clone(&self.core);
+        let request = Desync::new(None);
+
+        // Perform the request
+        let _ = request.future(|result| {
+            async move {
+                *result = Some(core.future(|core| core.assign_asset_id().boxed()).await.unwrap())
+            }.boxed()
+        });
+
+        // Retrieve the result
+        request.sync(|result| result.take()).unwrap()
+    }
+
+    ///
+    /// Stores the data for an asset with this animation
+    ///
+    fn write_asset(&self, asset_id: u64, data: Arc<Vec<u8>>) {
+        let core    = Arc::clone(&self.core);
+        let request = Desync::new(());
+
+        // Write the asset on the core and wait for it to be stored
+        let _ = request.future(move |_| {
+            async move {
+                core.future(move |core| async move { core.write_asset(asset_id, &*data).await; }.boxed()).await.ok();
+            }.boxed()
+        });
+
+        request.sync(|_| { });
+    }
+
     ///
     /// Retrieves a sink that can be used to send edits for this animation
     ///
@@ -364,6 +499,51 @@ impl EditableAnimation for StreamAnimation {
         self.idle_sync_requests.desync(move |reqs| { reqs.push(sync_request) });
     }
 
+    ///
+    /// Replaces the edit log for this animation without changing the animation itself
+    ///
+    fn replace_edit_log(&self, num_replaced: usize, edits: Vec<AnimationEdit>, snapshots: Vec<EditLogCheckpoint>) {
+        // Reading the snapshots also waits for any edits that are still being processed to reach the log
+        let old_snapshots   = self.get_snapshots();
+        let num_new_edits   = edits.len();
+
+        let edit_log = edits.iter()
+            .map(|edit| edit.serialize_to_data())
+            .collect();
+
+        // Snapshots of edits that are kept after the replaced edits move along with them
+        let moved_snapshots = old_snapshots.iter()
+            .filter(|snapshot| snapshot.edit_index > num_replaced)
+            .filter(|snapshot| !snapshots.iter().any(|new_snapshot| new_snapshot.name == snapshot.name))
+            .map(|snapshot| EditLogCheckpoint { name: snapshot.name.clone(), edit_index: snapshot.edit_index - num_replaced + num_new_edits })
+            .collect::<Vec<_>>();
+
+        // Replace the log and the snapshots in a single request
+        let mut request = vec![StorageCommand::ReplaceEditLog(num_replaced, edit_log)];
+        request.extend(old_snapshots.into_iter().map(|snapshot| StorageCommand::DeleteSnapshot(snapshot.name)));
+        request.extend(snapshots.into_iter().chain(moved_snapshots).map(|snapshot| StorageCommand::WriteSnapshot(snapshot.name, snapshot.edit_index)));
+
+        self.request_sync(request);
+    }
+
+    ///
+    /// Stores a named snapshot of the animation as it is after the edits that have been performed so far
+    ///
+    fn create_snapshot(&self, name: &str) {
+        let edit_index = self.get_num_edits();
+
+        self.request_sync(vec![StorageCommand::WriteSnapshot(name.to_string(), edit_index)]);
+    }
+
+    ///
+    /// Removes a named snapshot from this animation
+    ///
+    fn delete_snapshot(&self, name: &str) {
+        self.wait_for_edits();
+
+        self.request_sync(vec![StorageCommand::DeleteSnapshot(name.to_string())]);
+    }
+
     ///
     /// Flushes any caches this might have (forces reload from data storage)
     ///
@@ -437,7 +617,7 @@ impl AnimationMotion for StreamAnimation {
                 .map(|response| match response {
                     StorageResponse::Element(id, data) => {
                         // Deserialize the element to get its attachments
-                        let resolver = ElementWrapper::deserialize(ElementId::Assigned(id), &mut data.chars());
+                        let resolver = ElementWrapper::deserialize_data(ElementId::Assigned(id), &data);
                         resolver.and_then(|resolver| resolver.resolve(&mut |_| None))
                     },
                     _ => None
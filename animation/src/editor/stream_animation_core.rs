@@ -5,6 +5,7 @@ use crate::traits::*;
 
 use ::desync::*;
 use flo_stream::*;
+use flo_logging::*;
 
 use futures::future;
 use futures::prelude::*;
@@ -13,6 +14,10 @@ use futures::stream::{BoxStream};
 use std::sync::*;
 use std::time::{Duration};
 
+lazy_static! {
+    static ref STREAM_ANIMATION_LOG: LogPublisher = LogPublisher::new(module_path!());
+}
+
 ///
 /// Performs an asynchronous request on a storage layer for this animation
 ///
@@ -80,6 +85,9 @@ pub (super) struct StreamAnimationCore {
     /// The next element ID to assign (None if we haven't retrieved the element ID yet)
     pub (super) next_element_id: Option<i64>,
 
+    /// The next asset ID to assign (None if we haven't retrieved the asset ID yet)
+    pub (super) next_asset_id: Option<u64>,
+
     /// The keyframe that is currently being edited, if there is one
     pub (super) cached_keyframe: Option<Arc<Desync<KeyFrameCore>>>,
 
@@ -186,6 +194,9 @@ impl StreamAnimationCore {
                 Layer(layer_id, Path(when, PathEdit::BrushProperties(element, properties))) =>
                     Layer(*layer_id, Path(*when, PathEdit::BrushProperties(self.assign_element_id(*element).await, properties.clone()))),
 
+                Layer(layer_id, CreateSymbolInstance(when, element, instance)) =>
+                    Layer(*layer_id, CreateSymbolInstance(*when, self.assign_element_id(*element).await, instance.clone())),
+
                 Element(elements, Group(group_id, group_type)) =>
                     Element(elements.clone(), Group(self.assign_element_id(*group_id).await, *group_type)),
 
@@ -199,6 +210,8 @@ impl StreamAnimationCore {
     ///
     pub fn perform_edits<'a>(&'a mut self, edits: Arc<Vec<AnimationEdit>>) -> impl 'a+Future<Output=()> {
         async move {
+            let _span = STREAM_ANIMATION_LOG.span("perform_edits").with_level(Level::Trace).with_field("num_edits", edits.len());
+
             // Assign IDs to the edits
             let mut mapped_edits    = Vec::with_capacity(edits.len());
             for edit in edits.iter() {
@@ -208,12 +221,7 @@ impl StreamAnimationCore {
 
             // Send the edits to the edit log by serializing them
             let edit_log = edits.iter()
-                .map(|edit| {
-                    let mut serialized = String::new();
-                    edit.serialize(&mut serialized);
-                    serialized
-                })
-                .map(|edit| StorageCommand::WriteEdit(edit))
+                .map(|edit| StorageCommand::WriteEdit(edit.serialize_to_data()))
                 .collect::<Vec<_>>();
 
             self.request(edit_log).await;
@@ -227,9 +235,14 @@ impl StreamAnimationCore {
                     Layer(layer_id, layer_edit)             => { self.layer_edit(*layer_id, layer_edit).await; }
                     Element(element_ids, element_edit)      => { self.element_edit(element_ids, element_edit).await; }
                     Motion(motion_id, motion_edit)          => { self.motion_edit(*motion_id, motion_edit).await; }
+                    Bone(bone_id, bone_edit)                => { self.bone_edit(*bone_id, bone_edit).await; }
                     SetSize(width, height)                  => { self.set_size(*width, *height).await }
                     AddNewLayer(layer_id)                   => { self.add_new_layer(*layer_id).await; }
                     RemoveLayer(layer_id)                   => { self.remove_layer(*layer_id).await; }
+                    AddNewLayerFolder(layer_id)             => { self.add_new_layer_folder(*layer_id).await; }
+                    Symbol(symbol_id, symbol_edit)          => { self.symbol_edit(*symbol_id, symbol_edit).await; }
+                    AddNewSymbol(symbol_id)                 => { self.add_new_symbol(*symbol_id).await; }
+                    RemoveSymbol(symbol_id)                 => { self.remove_symbol(*symbol_id).await; }
                 }
             }
         }
@@ -328,6 +341,7 @@ impl StreamAnimationCore {
     pub fn remove_key_frame<'a>(&'a mut self, layer_id: u64, when: Duration) -> impl 'a+Future<Output=()> { 
         async move {
             self.request_one(StorageCommand::DeleteKeyFrame(layer_id, when)).await;
+            self.update_key_frame_tweens(layer_id, move |tweens| { tweens.remove(&when); }).await;
         } 
     }
 }
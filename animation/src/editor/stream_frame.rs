@@ -116,7 +116,7 @@ impl Frame for StreamFrame {
                         // Apply the properties from each of the attachments in turn
                         for attachment_id in active_attachments.iter() {
                             if let Some(attach_element) = core.elements.get(&attachment_id) {
-                                properties = attach_element.element.update_properties(Arc::clone(&properties), when);
+                                properties = core.update_properties_for_attachment(&attach_element.element, Arc::clone(&properties), when);
                                 properties.render(gc, attach_element.element.clone(), when);
                             }
                         }
@@ -219,4 +219,16 @@ impl Frame for StreamFrame {
             vec![]
         }
     }
+
+    ///
+    /// Retrieves the bones that pose the elements in this frame
+    ///
+    fn bones(&self) -> Vec<BoneElement> {
+        if let Some(core) = self.keyframe_core.as_ref() {
+            core.bones()
+        } else {
+            // No bones
+            vec![]
+        }
+    }
 }
@@ -4,6 +4,7 @@ use super::stream_animation_core::*;
 use crate::storage::storage_api::*;
 use crate::storage::layer_properties::*;
 use crate::traits::*;
+use crate::serializer::*;
 
 use ::desync::*;
 use futures::prelude::*;
@@ -71,9 +72,19 @@ impl Layer for StreamLayer {
     /// The types of edit that are supported by this layer
     ///
     fn supported_edit_types(&self) -> Vec<LayerEditType> {
-        vec![
-            LayerEditType::Vector
-        ]
+        if self.properties.folder {
+            vec![
+                LayerEditType::Folder
+            ]
+        } else if self.properties.reference.is_some() {
+            vec![
+                LayerEditType::Reference
+            ]
+        } else {
+            vec![
+                LayerEditType::Vector
+            ]
+        }
     }
 
     ///
@@ -90,7 +101,14 @@ impl Layer for StreamLayer {
             async move {
                 *frame = core.future(move |core| {
                     async move {
-                        core.load_keyframe(layer_id, time_index).await
+                        let mut keyframe = core.load_tweened_keyframe(layer_id, time_index).await;
+
+                        // Any symbol instances in the keyframe need their content loading so they can be displayed
+                        if let Some(keyframe) = keyframe.as_mut() {
+                            core.load_symbol_content(keyframe, time_index).await;
+                        }
+
+                        keyframe
                     }.boxed()
                 }).await.unwrap_or(None);
             }.boxed()
@@ -203,6 +221,90 @@ impl Layer for StreamLayer {
     fn get_canvas_cache_at_time(&self, time_index: Duration) -> Arc<dyn CanvasCache> {
         Arc::new(StreamLayerCache::new(Arc::clone(&self.core), self.layer_id, time_index))
     }
+
+    ///
+    /// If this is a reference layer, retrieves its settings (None for layers that should be rendered normally)
+    ///
+    fn reference_settings(&self) -> Option<ReferenceLayerSettings> {
+        self.properties.reference.clone()
+    }
+
+    ///
+    /// If the keyframe starting at the specified time is tweened into the following keyframe, the easing that's used
+    ///
+    fn key_frame_tween(&self, key_frame: Duration) -> Option<TweenEasing> {
+        self.properties.tweens.get(&key_frame).cloned()
+    }
+
+    ///
+    /// Retrieves the PNG data for the reference image that's displayed at the specified time
+    ///
+    fn reference_image_at_time(&self, time_index: Duration) -> Option<Arc<Vec<u8>>> {
+        // Only reference layers display images
+        if self.properties.reference.is_none() {
+            return None;
+        }
+
+        // Request the image from the storage: this is stored as the ID of the asset containing the PNG data
+        let image       = self.request_sync(vec![StorageCommand::ReadReferenceImage(self.layer_id, time_index)]);
+        let asset_id    = image.unwrap_or_else(|| vec![])
+            .into_iter()
+            .filter_map(|response| {
+                match response {
+                    StorageResponse::ReferenceImage(_when, image)   => Some(image.chars().next_small_u64()),
+                    _                                               => None
+                }
+            })
+            .nth(0)?;
+
+        // Read the asset to get the image data
+        let asset       = self.request_sync(vec![StorageCommand::ReadAsset(asset_id)]);
+
+        asset.unwrap_or_else(|| vec![])
+            .into_iter()
+            .filter_map(|response| {
+                match response {
+                    StorageResponse::Asset(_asset_id, asset) => {
+                        let mut asset   = asset.chars();
+                        let len         = asset.next_usize();
+                        let png_data    = asset.next_bytes(len);
+
+                        Some(Arc::new(png_data.into_vec()))
+                    }
+
+                    _ => None
+                }
+            })
+            .nth(0)
+    }
+
+    ///
+    /// The ID of the folder that this layer is in (None for layers at the top level)
+    ///
+    fn parent_folder(&self) -> Option<u64> {
+        self.properties.parent
+    }
+
+    ///
+    /// True if this layer has been hidden (layers in hidden folders are also not displayed)
+    ///
+    fn is_hidden(&self) -> bool {
+        self.properties.hidden
+    }
+
+    ///
+    /// True if this layer has been locked against editing (layers in locked folders are also locked)
+    ///
+    fn is_locked(&self) -> bool {
+        self.properties.locked
+    }
+
+    ///
+    /// If this layer is a folder, true if the layers inside it are hidden in the timeline
+    ///
+    fn is_collapsed(&self) -> bool {
+        self.properties.collapsed
+    }
 }
 
 impl VectorLayer for StreamLayer {
@@ -0,0 +1,61 @@
+use crate::storage::symbol_properties::*;
+use crate::traits::*;
+
+use std::time::{Duration};
+
+///
+/// A symbol from a stream animation
+///
+pub struct StreamSymbol {
+    /// The ID of this symbol
+    symbol_id: u64,
+
+    /// The properties for this symbol
+    properties: SymbolProperties,
+
+    /// The layers that belong to this symbol, in drawing order
+    layer_ids: Vec<u64>
+}
+
+impl StreamSymbol {
+    ///
+    /// Creates a new stream symbol from its properties and the list of layers that belong to it
+    ///
+    pub (super) fn new(symbol_id: u64, properties: SymbolProperties, layer_ids: Vec<u64>) -> StreamSymbol {
+        StreamSymbol {
+            symbol_id:  symbol_id,
+            properties: properties,
+            layer_ids:  layer_ids
+        }
+    }
+}
+
+impl Symbol for StreamSymbol {
+    ///
+    /// The ID of this symbol
+    ///
+    fn id(&self) -> u64 {
+        self.symbol_id
+    }
+
+    ///
+    /// The name of this symbol
+    ///
+    fn name(&self) -> String {
+        self.properties.name.clone()
+    }
+
+    ///
+    /// The length of this symbol's timeline
+    ///
+    fn length(&self) -> Duration {
+        self.properties.length
+    }
+
+    ///
+    /// Retrieves the IDs of the layers in this symbol, in the order they're drawn
+    ///
+    fn get_layer_ids(&self) -> Vec<u64> {
+        self.layer_ids.clone()
+    }
+}
@@ -11,6 +11,7 @@ extern crate serde;
 extern crate modifier;
 extern crate futures;
 extern crate itertools;
+extern crate flo_logging;
 
 mod traits;
 mod onion_skin;
@@ -2,6 +2,7 @@ use super::traits::*;
 
 use flo_canvas::*;
 use flo_curves::bezier::path::*;
+use flo_logging::*;
 
 use futures::future::{BoxFuture};
 
@@ -9,6 +10,10 @@ use std::iter;
 use std::sync::*;
 use std::time::Duration;
 
+lazy_static! {
+    static ref ONION_SKIN_LOG: LogPublisher = LogPublisher::new(module_path!());
+}
+
 ///
 /// Computes or retrieves the onion skin for a particular layer at a specified time.specified
 ///
@@ -18,6 +23,10 @@ use std::time::Duration;
 pub fn onion_skin_for_layer(layer: Arc<dyn Layer>, when: Duration) -> CacheProcess<Arc<Vec<Draw>>, BoxFuture<'static, Arc<Vec<Draw>>>> {
     layer.get_canvas_cache_at_time(when)
         .retrieve_or_generate(CacheType::OnionSkinLayer, Box::new(move || {
+            let _span = ONION_SKIN_LOG.span("onion_skin_for_layer")
+                .with_field("layer_id", layer.id())
+                .with_field("when_ms", when.as_millis());
+
             // Fetch the elements for the frame
             let frame                       = layer.get_frame_at_time(when);
             let elements                    = frame.vector_elements().unwrap_or(Box::new(vec![].into_iter()));
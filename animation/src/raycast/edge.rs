@@ -44,6 +44,12 @@ impl RaycastEdge {
             Vector::Transformation(_transform)  => { Box::new(iter::empty()) }
             Vector::Error                       => { Box::new(iter::empty()) }
 
+            // The content of a symbol is drawn separately from the frame it's in
+            Vector::Symbol(_symbol)             => { Box::new(iter::empty()) }
+
+            // Bones are not drawn
+            Vector::Bone(_bone)                 => { Box::new(iter::empty()) }
+
             Vector::Transformed(transform)      => { Self::from_transformed(transform, properties) }
             Vector::BrushStroke(brush_stroke)   => { Self::from_brush_stroke(brush_stroke, properties) }
             Vector::Path(path)                  => { Box::new(Self::from_path_element(path)) }
@@ -0,0 +1,252 @@
+use super::source::*;
+use super::target::*;
+
+use smallvec::*;
+use flo_float_encoder::*;
+
+///
+/// Writes a value in LEB128 format (7 bits at a time, with the top bit set if there are more bytes to follow)
+///
+fn write_leb128(target: &mut Vec<u8>, data: u64) {
+    let mut remaining = data;
+    loop {
+        let lower_bits  = (remaining & 0x7f) as u8;
+        remaining       >>= 7;
+
+        if remaining > 0 {
+            target.push(lower_bits | 0x80);
+        } else {
+            target.push(lower_bits);
+            break;
+        }
+    }
+}
+
+///
+/// Reads a value in LEB128 format from a byte slice
+///
+fn read_leb128(source: &mut &[u8]) -> u64 {
+    let mut result  = 0u64;
+    let mut shift   = 0;
+
+    loop {
+        // Missing bytes are treated as 0 (the same as the character encoding treats missing characters)
+        let byte    = source.first().cloned().unwrap_or(0);
+        if !source.is_empty() { *source = &source[1..]; }
+
+        // Lower 7 bits contain the value
+        if shift < 64 {
+            result  |= ((byte & 0x7f) as u64) << shift;
+        }
+        shift       += 7;
+
+        // If the upper bit is set there are more bytes
+        if (byte & 0x80) == 0 {
+            break;
+        }
+    }
+
+    result
+}
+
+///
+/// The binary data target writes the same values as the `String` target, but stores bytes directly instead of encoding them
+/// as characters. Characters are stored as UTF-8 and sizes as LEB128 values.
+///
+impl AnimationDataTarget for Vec<u8> {
+    fn write_chr(&mut self, chr: char) {
+        let mut buf = [0u8; 4];
+        self.extend_from_slice(chr.encode_utf8(&mut buf).as_bytes());
+    }
+
+    fn write_bytes(&mut self, bytes: &[u8]) {
+        self.extend_from_slice(bytes);
+    }
+
+    fn write_usize(&mut self, data: usize) {
+        write_leb128(self, data as u64);
+    }
+
+    fn write_small_u64(&mut self, data: u64) {
+        write_leb128(self, data);
+    }
+
+    fn write_next_f64_with_encoding(&mut self, encoding: FloatEncoding, last: f64, data: f64) {
+        squish_float_with_encoding(self, encoding, last, data).ok();
+    }
+}
+
+///
+/// Reads data written to a `Vec<u8>` target
+///
+impl AnimationDataSource for &'_ [u8] {
+    fn next_chr(&mut self) -> char {
+        // Characters are UTF-8: the length is determined by the leading byte
+        let len = match self.first() {
+            None                    => { return 'A'; }
+            Some(b) if *b < 0x80    => 1,
+            Some(b) if *b >= 0xf0   => 4,
+            Some(b) if *b >= 0xe0   => 3,
+            Some(_)                 => 2
+        };
+        let len = len.min(self.len());
+
+        let chr = std::str::from_utf8(&self[0..len]).ok()
+            .and_then(|chr| chr.chars().next())
+            .unwrap_or('?');
+        *self   = &self[len..];
+
+        chr
+    }
+
+    fn next_bytes(&mut self, len: usize) -> SmallVec<[u8;8]> {
+        let available   = len.min(self.len());
+        let mut res     = SmallVec::from_slice(&self[0..available]);
+        *self           = &self[available..];
+
+        res.resize(len, 0);
+        res
+    }
+
+    fn next_usize(&mut self) -> usize {
+        read_leb128(self) as usize
+    }
+
+    fn next_small_u64(&mut self) -> u64 {
+        read_leb128(self)
+    }
+
+    fn next_f64_offset(&mut self, last: f64) -> f64 {
+        unsquish_float(self, last).unwrap_or(last)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::time::{Duration};
+
+    #[test]
+    fn bytes_are_not_encoded() {
+        let mut encoded: Vec<u8> = vec![];
+        encoded.write_bytes(&[0u8, 255u8, 42u8]);
+
+        assert!(encoded == vec![0u8, 255u8, 42u8]);
+    }
+
+    #[test]
+    fn decode_bytes() {
+        let bytes       = (0u8..=255u8).collect::<Vec<_>>();
+        let mut encoded: Vec<u8> = vec![];
+
+        encoded.write_bytes(&bytes);
+
+        for end in 0u8..255u8 {
+            let decoded     = (&encoded[..]).next_bytes((end as usize)+1);
+            assert!(decoded == (0u8..=end).collect::<SmallVec<[u8; 8]>>());
+        }
+    }
+
+    #[test]
+    fn decode_chr() {
+        let mut encoded: Vec<u8> = vec![];
+        "A+é€😀".chars().for_each(|chr| encoded.write_chr(chr));
+
+        let mut src = &encoded[..];
+        assert!((0..5).map(|_| src.next_chr()).collect::<String>() == "A+é€😀");
+    }
+
+    #[test]
+    fn decode_usize() {
+        let mut encoded: Vec<u8> = vec![];
+
+        encoded.write_usize(1234);
+        assert!(encoded.len() == 2);
+        assert!((&encoded[..]).next_usize() == 1234);
+    }
+
+    #[test]
+    fn decode_small_u64() {
+        let mut encoded: Vec<u8> = vec![];
+
+        encoded.write_small_u64(0xffff_ffff_ffff_ffff);
+        assert!((&encoded[..]).next_small_u64() == 0xffff_ffff_ffff_ffff);
+    }
+
+    #[test]
+    fn decode_string() {
+        let mut encoded: Vec<u8> = vec![];
+
+        encoded.write_str("Hello, world");
+        assert!((&encoded[..]).next_string() == "Hello, world");
+    }
+
+    #[test]
+    fn decode_f64_offsets() {
+        let mut encoded: Vec<u8> = vec![];
+
+        encoded.write_next_f64(100.0, 101.0);
+        encoded.write_next_f64(101.0, 2000.0);
+
+        let mut src = &encoded[..];
+        assert!((src.next_f64_offset(100.0)-101.0).abs() < 0.01);
+        assert!((src.next_f64_offset(101.0)-2000.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn decode_f64_offsets_with_encodings() {
+        let mut encoded: Vec<u8> = vec![];
+
+        encoded.write_next_f64_with_encoding(FloatEncoding::PRECISION_4096, 100.0, 100.001);
+        encoded.write_next_f64_with_encoding(FloatEncoding::VarInt(16), 100.001, 5000.0001);
+        encoded.write_next_f64_with_encoding(FloatEncoding::Lossless, 5000.0001, 0.1);
+
+        let mut src = &encoded[..];
+        assert!((src.next_f64_offset(100.0)-100.001).abs() < 0.0002);
+        assert!((src.next_f64_offset(100.001)-5000.0001).abs() < 0.00002);
+        assert!(src.next_f64_offset(5000.0001) == 0.1);
+        assert!(src.is_empty());
+    }
+
+    #[test]
+    fn decode_all() {
+        let mut encoded: Vec<u8> = vec![];
+
+        encoded.write_chr('t');
+        encoded.write_usize(42);
+        encoded.write_small_u64(100000);
+        encoded.write_str("Hello");
+        encoded.write_u32(1234);
+        encoded.write_i32(-1234);
+        encoded.write_u64(12345678);
+        encoded.write_i64(-12345678);
+        encoded.write_f32(1.5);
+        encoded.write_f64(-2.5);
+        encoded.write_next_f64(10.0, 12.0);
+        encoded.write_duration(Duration::from_millis(1500));
+
+        let mut src = &encoded[..];
+        assert!(src.next_chr() == 't');
+        assert!(src.next_usize() == 42);
+        assert!(src.next_small_u64() == 100000);
+        assert!(src.next_string() == "Hello");
+        assert!(src.next_u32() == 1234);
+        assert!(src.next_i32() == -1234);
+        assert!(src.next_u64() == 12345678);
+        assert!(src.next_i64() == -12345678);
+        assert!(src.next_f32() == 1.5);
+        assert!(src.next_f64() == -2.5);
+        assert!((src.next_f64_offset(10.0)-12.0).abs() < 0.01);
+        assert!(src.next_duration() == Duration::from_millis(1500));
+        assert!(src.len() == 0);
+    }
+
+    #[test]
+    fn reading_past_the_end_reads_zeros() {
+        let mut src: &[u8] = &[];
+
+        assert!(src.next_chr() == 'A');
+        assert!(src.next_usize() == 0);
+        assert!(src.next_u32() == 0);
+    }
+}
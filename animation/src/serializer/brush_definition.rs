@@ -10,8 +10,9 @@ impl BrushDefinition {
         use self::BrushDefinition::*;
 
         match self {
-            Simple      => { data.write_chr('S'); }
-            Ink(ink)    => { data.write_chr('I'); ink.serialize(data); }
+            Simple              => { data.write_chr('S'); }
+            Ink(ink)            => { data.write_chr('I'); ink.serialize(data); }
+            DynamicInk(ink)     => { data.write_chr('D'); ink.serialize(data); }
         }
     }
 
@@ -22,6 +23,7 @@ impl BrushDefinition {
         match data.next_chr() {
             'S' => { Some(BrushDefinition::Simple) }
             'I' => { InkDefinition::deserialize(data).map(|ink| BrushDefinition::Ink(ink)) }
+            'D' => { DynamicInkDefinition::deserialize(data).map(|ink| BrushDefinition::DynamicInk(ink)) }
             _   => None
         }
     }
@@ -56,6 +58,47 @@ impl InkDefinition {
     }
 }
 
+impl DynamicInkDefinition {
+    ///
+    /// Generates a serialized version of this brush definition on the specified data target
+    ///
+    pub fn serialize<Tgt: AnimationDataTarget>(&self, data: &mut Tgt) {
+        data.write_small_u64(0);        // v0 definition
+
+        data.write_f32(self.min_width);
+        data.write_f32(self.max_width);
+        data.write_f32(self.pressure_opacity);
+        data.write_f32(self.nib_angle);
+        data.write_chr(if self.tilt_nib_angle { 'T' } else { 'F' });
+        data.write_f32(self.calligraphy);
+        data.write_f32(self.velocity_thinning);
+        data.write_f32(self.taper_in);
+        data.write_f32(self.taper_out);
+    }
+
+    ///
+    /// Deserializes a dynamic ink brush definition from the specified data source
+    ///
+    pub fn deserialize<Src: AnimationDataSource>(data: &mut Src) -> Option<DynamicInkDefinition> {
+        match data.next_small_u64() {
+            0 => {
+                Some(DynamicInkDefinition {
+                    min_width:          data.next_f32(),
+                    max_width:          data.next_f32(),
+                    pressure_opacity:   data.next_f32(),
+                    nib_angle:          data.next_f32(),
+                    tilt_nib_angle:     data.next_chr() == 'T',
+                    calligraphy:        data.next_f32(),
+                    velocity_thinning:  data.next_f32(),
+                    taper_in:           data.next_f32(),
+                    taper_out:          data.next_f32()
+                })
+            }
+            _ => { None }
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -72,4 +115,24 @@ mod test {
     fn brush_ink_defn_2() {
         assert!(BrushDefinition::deserialize(&mut "IAAAAg/AAAAAABAAAQAB".chars()) == Some(BrushDefinition::Ink(InkDefinition { min_width: 1.0, max_width: 2.0, scale_up_distance: 3.0 })));
     }
+
+    #[test]
+    fn brush_dynamic_ink_defn() {
+        let definition = DynamicInkDefinition {
+            min_width:          1.0,
+            max_width:          2.0,
+            pressure_opacity:   0.5,
+            nib_angle:          0.75,
+            tilt_nib_angle:     true,
+            calligraphy:        0.25,
+            velocity_thinning:  0.125,
+            taper_in:           10.0,
+            taper_out:          20.0
+        };
+
+        let mut encoded = String::new();
+        BrushDefinition::DynamicInk(definition.clone()).serialize(&mut encoded);
+
+        assert!(BrushDefinition::deserialize(&mut encoded.chars()) == Some(BrushDefinition::DynamicInk(definition)));
+    }
 }
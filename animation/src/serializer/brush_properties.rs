@@ -8,30 +8,98 @@ impl BrushProperties {
     /// Generates a serialized version of these brush properties on the specified data target
     ///
     pub fn serialize<Tgt: AnimationDataTarget>(&self, data: &mut Tgt) {
-        // v0
-        data.write_small_u64(0);
+        // v1
+        data.write_small_u64(1);
 
         data.write_f32(self.size);
         data.write_f32(self.opacity);
         serialize_color(&self.color, data);
+        self.fill.serialize(data);
     }
 
     ///
     /// Deserializes brush properties from a stream
     ///
     pub fn deserialize<Src: AnimationDataSource>(data: &mut Src) -> Option<BrushProperties> {
-        if data.next_small_u64() == 0 {
-            let size    = data.next_f32();
-            let opacity = data.next_f32();
-            let color   = deserialize_color(data);
-
-            color.map(|color| {
-                BrushProperties {
-                    size, opacity, color
-                }
-            })
-        } else {
-            None
+        match data.next_small_u64() {
+            0 => {
+                let size    = data.next_f32();
+                let opacity = data.next_f32();
+                let color   = deserialize_color(data);
+
+                color.map(|color| {
+                    BrushProperties {
+                        size, opacity, color, fill: BrushFill::Solid
+                    }
+                })
+            }
+
+            1 => {
+                let size    = data.next_f32();
+                let opacity = data.next_f32();
+                let color   = deserialize_color(data)?;
+                let fill    = BrushFill::deserialize(data)?;
+
+                Some(BrushProperties {
+                    size, opacity, color, fill
+                })
+            }
+
+            _ => None
+        }
+    }
+}
+
+impl BrushFill {
+    ///
+    /// Generates a serialized version of this brush fill on the specified data target
+    ///
+    pub fn serialize<Tgt: AnimationDataTarget>(&self, data: &mut Tgt) {
+        use self::BrushFill::*;
+
+        match self {
+            Solid                                       => { data.write_chr('S'); }
+
+            LinearGradient { start, end, end_color }    => {
+                data.write_chr('L');
+                data.write_f32(start.0); data.write_f32(start.1);
+                data.write_f32(end.0); data.write_f32(end.1);
+                serialize_color(end_color, data);
+            }
+
+            RadialGradient { center, radius, end_color } => {
+                data.write_chr('R');
+                data.write_f32(center.0); data.write_f32(center.1);
+                data.write_f32(*radius);
+                serialize_color(end_color, data);
+            }
+        }
+    }
+
+    ///
+    /// Deserializes a brush fill from a stream
+    ///
+    pub fn deserialize<Src: AnimationDataSource>(data: &mut Src) -> Option<BrushFill> {
+        match data.next_chr() {
+            'S' => Some(BrushFill::Solid),
+
+            'L' => {
+                let start       = (data.next_f32(), data.next_f32());
+                let end         = (data.next_f32(), data.next_f32());
+                let end_color   = deserialize_color(data)?;
+
+                Some(BrushFill::LinearGradient { start, end, end_color })
+            }
+
+            'R' => {
+                let center      = (data.next_f32(), data.next_f32());
+                let radius      = data.next_f32();
+                let end_color   = deserialize_color(data)?;
+
+                Some(BrushFill::RadialGradient { center, radius, end_color })
+            }
+
+            _ => None
         }
     }
 }
@@ -44,13 +112,31 @@ mod test {
     #[test]
     fn brush_properties_1() {
         let mut encoded = String::new();
-        BrushProperties { size: 20.0, opacity: 1.0, color: Color::Hsluv(0.2, 0.6, 0.4, 1.0) }.serialize(&mut encoded);
+        BrushProperties { size: 20.0, opacity: 1.0, color: Color::Hsluv(0.2, 0.6, 0.4, 1.0), fill: BrushFill::Solid }.serialize(&mut encoded);
 
-        assert!(BrushProperties::deserialize(&mut encoded.chars()) == Some(BrushProperties { size: 20.0, opacity: 1.0, color: Color::Hsluv(0.2, 0.6, 0.4, 1.0) }));
+        assert!(BrushProperties::deserialize(&mut encoded.chars()) == Some(BrushProperties { size: 20.0, opacity: 1.0, color: Color::Hsluv(0.2, 0.6, 0.4, 1.0), fill: BrushFill::Solid }));
     }
 
     #[test]
     fn brush_properties_2() {
-        assert!(BrushProperties::deserialize(&mut "AAAAoBBAAAg/AhzMTmZamZ//P".chars()) == Some(BrushProperties { size: 20.0, opacity: 1.0, color: Color::Hsluv(0.2, 0.6, 0.4, 1.0) }));
+        assert!(BrushProperties::deserialize(&mut "AAAAoBBAAAg/AhzMTmZamZ//P".chars()) == Some(BrushProperties { size: 20.0, opacity: 1.0, color: Color::Hsluv(0.2, 0.6, 0.4, 1.0), fill: BrushFill::Solid }));
+    }
+
+    #[test]
+    fn brush_properties_linear_gradient() {
+        let properties  = BrushProperties { size: 20.0, opacity: 0.5, color: Color::Rgba(1.0, 0.0, 0.0, 1.0), fill: BrushFill::LinearGradient { start: (10.0, 20.0), end: (30.0, 40.0), end_color: Color::Rgba(0.0, 0.0, 1.0, 1.0) } };
+        let mut encoded = String::new();
+        properties.serialize(&mut encoded);
+
+        assert!(BrushProperties::deserialize(&mut encoded.chars()) == Some(properties));
+    }
+
+    #[test]
+    fn brush_properties_radial_gradient() {
+        let properties  = BrushProperties { size: 20.0, opacity: 1.0, color: Color::Rgba(1.0, 1.0, 1.0, 1.0), fill: BrushFill::RadialGradient { center: (100.0, 200.0), radius: 50.0, end_color: Color::Rgba(0.0, 0.0, 0.0, 0.0) } };
+        let mut encoded = String::new();
+        properties.serialize(&mut encoded);
+
+        assert!(BrushProperties::deserialize(&mut encoded.chars()) == Some(properties));
     }
 }
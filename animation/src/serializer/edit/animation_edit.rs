@@ -1,5 +1,6 @@
 use super::super::source::*;
 use super::super::target::*;
+use super::super::serialized_data::*;
 use super::super::super::traits::*;
 
 impl AnimationEdit {
@@ -13,12 +14,26 @@ impl AnimationEdit {
             Layer(layer_id, edit)       => { data.write_chr('L'); data.write_small_u64(*layer_id); edit.serialize(data); },
             Element(elements, edit)     => { data.write_chr('E'); data.write_usize(elements.len()); elements.iter().for_each(|elem| elem.serialize(data)); edit.serialize(data); },
             Motion(element, edit)       => { data.write_chr('M'); element.serialize(data); edit.serialize(data); },
+            Bone(element, edit)         => { data.write_chr('B'); element.serialize(data); edit.serialize(data); },
             SetSize(width, height)      => { data.write_chr('S'); data.write_f64(*width); data.write_f64(*height); },
             AddNewLayer(layer_id)       => { data.write_chr('+'); data.write_small_u64(*layer_id); },
             RemoveLayer(layer_id)       => { data.write_chr('-'); data.write_small_u64(*layer_id); }
+            AddNewLayerFolder(layer_id) => { data.write_chr('['); data.write_small_u64(*layer_id); }
+            Symbol(symbol_id, edit)     => { data.write_chr('Y'); data.write_small_u64(*symbol_id); edit.serialize(data); }
+            AddNewSymbol(symbol_id)     => { data.write_chr('{'); data.write_small_u64(*symbol_id); }
+            RemoveSymbol(symbol_id)     => { data.write_chr('}'); data.write_small_u64(*symbol_id); }
         }
     }
 
+    ///
+    /// Serializes this edit using the binary encoding
+    ///
+    pub fn serialize_to_data(&self) -> SerializedData {
+        let mut data: Vec<u8> = vec![];
+        self.serialize(&mut data);
+        SerializedData::Binary(data)
+    }
+
     ///
     /// Deserializes an animation edit
     ///
@@ -26,9 +41,14 @@ impl AnimationEdit {
         match data.next_chr() {
             'L' => { let layer_id = data.next_small_u64(); LayerEdit::deserialize(data).map(move |edit| AnimationEdit::Layer(layer_id, edit)) }
             'M' => { ElementId::deserialize(data).and_then(|elem| MotionEdit::deserialize(data).map(move |edit| AnimationEdit::Motion(elem, edit))) }
+            'B' => { ElementId::deserialize(data).and_then(|elem| BoneEdit::deserialize(data).map(move |edit| AnimationEdit::Bone(elem, edit))) }
             'S' => { Some(AnimationEdit::SetSize(data.next_f64(), data.next_f64())) }
             '+' => { Some(AnimationEdit::AddNewLayer(data.next_small_u64())) }
             '-' => { Some(AnimationEdit::RemoveLayer(data.next_small_u64())) }
+            '[' => { Some(AnimationEdit::AddNewLayerFolder(data.next_small_u64())) }
+            'Y' => { let symbol_id = data.next_small_u64(); SymbolEdit::deserialize(data).map(move |edit| AnimationEdit::Symbol(symbol_id, edit)) }
+            '{' => { Some(AnimationEdit::AddNewSymbol(data.next_small_u64())) }
+            '}' => { Some(AnimationEdit::RemoveSymbol(data.next_small_u64())) }
 
             'E' => { 
                 let num_elements    = data.next_usize();
@@ -45,6 +65,16 @@ impl AnimationEdit {
             _   => None
         }
     }
+
+    ///
+    /// Deserializes an animation edit from serialized data in either encoding
+    ///
+    pub fn deserialize_data(data: &SerializedData) -> Option<AnimationEdit> {
+        match data {
+            SerializedData::Text(text)      => AnimationEdit::deserialize(&mut text.chars()),
+            SerializedData::Binary(bytes)   => AnimationEdit::deserialize(&mut &bytes[..])
+        }
+    }
 }
 
 ///
@@ -117,6 +147,39 @@ mod test {
         assert!(AnimationEdit::deserialize(&mut encoded.chars()) == Some(AnimationEdit::RemoveLayer(42)));
     }
 
+    #[test]
+    fn add_new_layer_folder() {
+        let mut encoded = String::new();
+        AnimationEdit::AddNewLayerFolder(5).serialize(&mut encoded);
+
+        assert!(AnimationEdit::deserialize(&mut encoded.chars()) == Some(AnimationEdit::AddNewLayerFolder(5)));
+    }
+
+    #[test]
+    fn add_new_symbol() {
+        let mut encoded = String::new();
+        AnimationEdit::AddNewSymbol(3).serialize(&mut encoded);
+
+        assert!(AnimationEdit::deserialize(&mut encoded.chars()) == Some(AnimationEdit::AddNewSymbol(3)));
+    }
+
+    #[test]
+    fn remove_symbol() {
+        let mut encoded = String::new();
+        AnimationEdit::RemoveSymbol(3).serialize(&mut encoded);
+
+        assert!(AnimationEdit::deserialize(&mut encoded.chars()) == Some(AnimationEdit::RemoveSymbol(3)));
+    }
+
+    #[test]
+    fn symbol_edit() {
+        let mut encoded = String::new();
+        let edit        = AnimationEdit::Symbol(3, SymbolEdit::SetLength(Duration::from_millis(2000)));
+        edit.serialize(&mut encoded);
+
+        assert!(AnimationEdit::deserialize(&mut encoded.chars()) == Some(edit));
+    }
+
     #[test]
     fn layer_edit() {
         let mut encoded = String::new();
@@ -143,4 +206,30 @@ mod test {
 
         assert!(AnimationEdit::deserialize(&mut encoded.chars()) == Some(edit));
     }
+
+    #[test]
+    fn bone_edit() {
+        let mut encoded = String::new();
+        let edit        = AnimationEdit::Bone(ElementId::Assigned(42), BoneEdit::SetParent(Some(ElementId::Assigned(43))));
+        edit.serialize(&mut encoded);
+
+        assert!(AnimationEdit::deserialize(&mut encoded.chars()) == Some(edit));
+    }
+
+    #[test]
+    fn binary_element_edit() {
+        let edit        = AnimationEdit::Element(vec![ElementId::Assigned(42), ElementId::Assigned(43), ElementId::Assigned(44)], ElementEdit::Delete);
+        let encoded     = edit.serialize_to_data();
+
+        assert!(AnimationEdit::deserialize_data(&encoded) == Some(edit));
+    }
+
+    #[test]
+    fn text_data_layer_edit() {
+        let mut encoded = String::new();
+        let edit        = AnimationEdit::Layer(1, LayerEdit::AddKeyFrame(Duration::from_millis(1000)));
+        edit.serialize(&mut encoded);
+
+        assert!(AnimationEdit::deserialize_data(&SerializedData::Text(encoded)) == Some(edit));
+    }
 }
@@ -0,0 +1,101 @@
+use super::super::source::*;
+use super::super::target::*;
+use super::super::super::traits::*;
+
+impl BoneEdit {
+    ///
+    /// Generates a serialized version of this edit on the specified data target
+    ///
+    pub fn serialize<Tgt: AnimationDataTarget>(&self, data: &mut Tgt) {
+        use self::BoneEdit::*;
+
+        match self {
+            Create                      => { data.write_chr('+'); }
+            Delete                      => { data.write_chr('-'); }
+            SetParent(None)             => { data.write_chr('p'); }
+            SetParent(Some(parent_id))  => { data.write_chr('P'); parent_id.serialize(data); }
+            SetOrigin(x, y)             => { data.write_chr('O'); data.write_f32(*x); data.write_f32(*y); }
+            SetLength(length)           => { data.write_chr('L'); data.write_f32(*length); }
+            SetAngle(angle)             => { data.write_chr('A'); data.write_f64(*angle); }
+            SetRotation(when, angle)    => { data.write_chr('R'); data.write_duration(*when); data.write_f64(*angle); }
+            RemoveRotation(when)        => { data.write_chr('r'); data.write_duration(*when); }
+        }
+    }
+
+    ///
+    /// Deserializes a bone edit from a data source
+    ///
+    pub fn deserialize<Src: AnimationDataSource>(data: &mut Src) -> Option<BoneEdit> {
+        match data.next_chr() {
+            '+'     => Some(BoneEdit::Create),
+            '-'     => Some(BoneEdit::Delete),
+            'p'     => Some(BoneEdit::SetParent(None)),
+            'P'     => ElementId::deserialize(data).map(|parent_id| BoneEdit::SetParent(Some(parent_id))),
+            'O'     => Some(BoneEdit::SetOrigin(data.next_f32(), data.next_f32())),
+            'L'     => Some(BoneEdit::SetLength(data.next_f32())),
+            'A'     => Some(BoneEdit::SetAngle(data.next_f64())),
+            'R'     => Some(BoneEdit::SetRotation(data.next_duration(), data.next_f64())),
+            'r'     => Some(BoneEdit::RemoveRotation(data.next_duration())),
+
+            _       => None
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::time::{Duration};
+
+    fn check_edit(edit: BoneEdit) {
+        let mut encoded = String::new();
+        edit.serialize(&mut encoded);
+
+        assert!(BoneEdit::deserialize(&mut encoded.chars()) == Some(edit));
+    }
+
+    #[test]
+    fn create() {
+        check_edit(BoneEdit::Create);
+    }
+
+    #[test]
+    fn delete() {
+        check_edit(BoneEdit::Delete);
+    }
+
+    #[test]
+    fn set_parent() {
+        check_edit(BoneEdit::SetParent(Some(ElementId::Assigned(42))));
+    }
+
+    #[test]
+    fn clear_parent() {
+        check_edit(BoneEdit::SetParent(None));
+    }
+
+    #[test]
+    fn set_origin() {
+        check_edit(BoneEdit::SetOrigin(10.0, 11.0));
+    }
+
+    #[test]
+    fn set_length() {
+        check_edit(BoneEdit::SetLength(50.0));
+    }
+
+    #[test]
+    fn set_angle() {
+        check_edit(BoneEdit::SetAngle(1.5));
+    }
+
+    #[test]
+    fn set_rotation() {
+        check_edit(BoneEdit::SetRotation(Duration::from_millis(1500), -0.25));
+    }
+
+    #[test]
+    fn remove_rotation() {
+        check_edit(BoneEdit::RemoveRotation(Duration::from_millis(1500)));
+    }
+}
@@ -21,8 +21,10 @@ impl ElementEdit {
             DetachFromFrame                 => { data.write_chr('D'); }
             CollideWithExistingElements     => { data.write_chr('j'); }
             ConvertToPath                   => { data.write_chr('p'); }
+            CombinePaths(combine)           => { data.write_chr('b'); combine.serialize(data); }
             Group(group_id, group_type)     => { data.write_chr('g'); group_id.serialize(data); group_type.serialize(data); }
             Ungroup                         => { data.write_chr('u'); }
+            SetSymbolInstance(instance)     => { data.write_chr('y'); instance.serialize(data); }
 
             SetControlPoints(points, when)  => { 
                 data.write_chr('c');
@@ -93,6 +95,11 @@ impl ElementEdit {
                 Some(ElementEdit::ConvertToPath)
             }
 
+            'b' => {
+                PathCombine::deserialize(data)
+                    .map(|combine| ElementEdit::CombinePaths(combine))
+            }
+
             'g' => {
                 Some(ElementEdit::Group(ElementId::deserialize(data)?, GroupType::deserialize(data)?))
             }
@@ -101,6 +108,11 @@ impl ElementEdit {
                 Some(ElementEdit::Ungroup)
             }
 
+            'y' => {
+                SymbolInstance::deserialize(data)
+                    .map(|instance| ElementEdit::SetSymbolInstance(instance))
+            }
+
             'C' => {
                 // Obsolete version from older versions of FlowBetween
                 let num_points      = data.next_usize();
@@ -234,6 +246,14 @@ mod test {
         assert!(ElementEdit::deserialize(&mut encoded.chars()) == Some(ElementEdit::ConvertToPath));
     }
 
+    #[test]
+    fn combine_paths() {
+        let mut encoded = String::new();
+        ElementEdit::CombinePaths(PathCombine::Subtract).serialize(&mut encoded);
+
+        assert!(ElementEdit::deserialize(&mut encoded.chars()) == Some(ElementEdit::CombinePaths(PathCombine::Subtract)));
+    }
+
     #[test]
     fn set_control_points() {
         let mut encoded = String::new();
@@ -297,4 +317,21 @@ mod test {
 
         assert!(ElementEdit::deserialize(&mut encoded.chars()) == Some(ElementEdit::Transform(vec![ElementTransform::SetAnchor(6.0, 7.0), ElementTransform::MoveTo(2.0, 3.0)])));
     }
+
+    #[test]
+    fn transform_distribute() {
+        let mut encoded = String::new();
+        ElementEdit::Transform(vec![ElementTransform::DistributeHorizontally, ElementTransform::DistributeVertically]).serialize(&mut encoded);
+
+        assert!(ElementEdit::deserialize(&mut encoded.chars()) == Some(ElementEdit::Transform(vec![ElementTransform::DistributeHorizontally, ElementTransform::DistributeVertically])));
+    }
+
+    #[test]
+    fn set_symbol_instance() {
+        let mut encoded = String::new();
+        let instance    = SymbolInstance { symbol_id: 2, transformations: vec![], time_offset: Duration::from_millis(0), loop_mode: SymbolLoopMode::SingleFrame, playback_rate: 1.0 };
+        ElementEdit::SetSymbolInstance(instance.clone()).serialize(&mut encoded);
+
+        assert!(ElementEdit::deserialize(&mut encoded.chars()) == Some(ElementEdit::SetSymbolInstance(instance)));
+    }
 }
@@ -28,6 +28,16 @@ impl ElementTransform {
                 alignment.serialize(data);
             }
 
+            DistributeHorizontally => {
+                data.write_chr('d');
+                data.write_chr('h');
+            }
+
+            DistributeVertically => {
+                data.write_chr('d');
+                data.write_chr('v');
+            }
+
             FlipHorizontal => {
                 data.write_chr('f');
                 data.write_chr('h');
@@ -73,6 +83,14 @@ impl ElementTransform {
                     .map(|align| ElementTransform::Align(align))
             }
 
+            'd' => {
+                match data.next_chr() {
+                    'h' => Some(ElementTransform::DistributeHorizontally),
+                    'v' => Some(ElementTransform::DistributeVertically),
+                    _ => None
+                }
+            }
+
             'f' => {
                 match data.next_chr() {
                     'h' => Some(ElementTransform::FlipHorizontal),
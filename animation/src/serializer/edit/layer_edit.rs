@@ -16,6 +16,27 @@ impl LayerEdit {
             RemoveKeyFrame(when)    => { data.write_chr('-'); data.write_duration(*when); },
             SetName(name)           => { data.write_chr('N'); data.write_str(name); },
             SetOrdering(ordering)   => { data.write_chr('O'); data.write_u64(*ordering); }
+
+            MoveKeyFrame(from, to)              => { data.write_chr('m'); data.write_duration(*from); data.write_duration(*to); }
+            DuplicateKeyFrame(from, to)         => { data.write_chr('d'); data.write_duration(*from); data.write_duration(*to); }
+            SetKeyFrameTween(when, None)        => { data.write_chr('t'); data.write_duration(*when); }
+            SetKeyFrameTween(when, Some(ease))  => { data.write_chr('T'); data.write_duration(*when); ease.serialize(data); }
+            SetReference(None)                  => { data.write_chr('r'); }
+            SetReference(Some(settings))        => { data.write_chr('R'); settings.serialize(data); }
+            AddReferenceImage(when, asset_id)   => { data.write_chr('I'); data.write_duration(*when); data.write_small_u64(*asset_id); }
+            RemoveReferenceImage(when)          => { data.write_chr('i'); data.write_duration(*when); }
+            SetParent(None)                     => { data.write_chr('f'); }
+            SetParent(Some(parent_id))          => { data.write_chr('F'); data.write_small_u64(*parent_id); }
+            SetHidden(hidden)                   => { data.write_chr(if *hidden { 'H' } else { 'h' }); }
+            SetLocked(locked)                   => { data.write_chr(if *locked { 'L' } else { 'l' }); }
+            SetCollapsed(collapsed)             => { data.write_chr(if *collapsed { 'C' } else { 'c' }); }
+
+            CreateSymbolInstance(when, element_id, instance) => {
+                data.write_chr('y');
+                data.write_duration(*when);
+                element_id.serialize(data);
+                instance.serialize(data);
+            }
         }
     }
 
@@ -36,8 +57,46 @@ impl LayerEdit {
             }
             '+' => { Some(LayerEdit::AddKeyFrame(data.next_duration())) }
             '-' => { Some(LayerEdit::RemoveKeyFrame(data.next_duration())) }
+            'm' => {
+                let from    = data.next_duration();
+                let to      = data.next_duration();
+                Some(LayerEdit::MoveKeyFrame(from, to))
+            }
+            'd' => {
+                let from    = data.next_duration();
+                let to      = data.next_duration();
+                Some(LayerEdit::DuplicateKeyFrame(from, to))
+            }
+            't' => { Some(LayerEdit::SetKeyFrameTween(data.next_duration(), None)) }
+            'T' => {
+                let when    = data.next_duration();
+                TweenEasing::deserialize(data).map(|easing| LayerEdit::SetKeyFrameTween(when, Some(easing)))
+            }
             'N' => { Some(LayerEdit::SetName(data.next_string())) }
             'O' => { Some(LayerEdit::SetOrdering(data.next_u64())) }
+            'r' => { Some(LayerEdit::SetReference(None)) }
+            'R' => { ReferenceLayerSettings::deserialize(data).map(|settings| LayerEdit::SetReference(Some(settings))) }
+            'I' => {
+                let when        = data.next_duration();
+                let asset_id    = data.next_small_u64();
+                Some(LayerEdit::AddReferenceImage(when, asset_id))
+            }
+            'i' => { Some(LayerEdit::RemoveReferenceImage(data.next_duration())) }
+            'f' => { Some(LayerEdit::SetParent(None)) }
+            'F' => { Some(LayerEdit::SetParent(Some(data.next_small_u64()))) }
+            'H' => { Some(LayerEdit::SetHidden(true)) }
+            'h' => { Some(LayerEdit::SetHidden(false)) }
+            'L' => { Some(LayerEdit::SetLocked(true)) }
+            'l' => { Some(LayerEdit::SetLocked(false)) }
+            'C' => { Some(LayerEdit::SetCollapsed(true)) }
+            'c' => { Some(LayerEdit::SetCollapsed(false)) }
+            'y' => {
+                let when        = data.next_duration();
+                let element_id  = ElementId::deserialize(data)?;
+                let instance    = SymbolInstance::deserialize(data)?;
+
+                Some(LayerEdit::CreateSymbolInstance(when, element_id, instance))
+            }
 
             _   => None
         }
@@ -85,6 +144,42 @@ mod test {
         assert!(LayerEdit::deserialize(&mut encoded.chars()) == Some(edit));
     }
 
+    #[test]
+    fn move_key_frame() {
+        let mut encoded = String::new();
+        let edit        = LayerEdit::MoveKeyFrame(Duration::from_millis(1234), Duration::from_millis(500));
+        edit.serialize(&mut encoded);
+
+        assert!(LayerEdit::deserialize(&mut encoded.chars()) == Some(edit));
+    }
+
+    #[test]
+    fn duplicate_key_frame() {
+        let mut encoded = String::new();
+        let edit        = LayerEdit::DuplicateKeyFrame(Duration::from_millis(1234), Duration::from_millis(2000));
+        edit.serialize(&mut encoded);
+
+        assert!(LayerEdit::deserialize(&mut encoded.chars()) == Some(edit));
+    }
+
+    #[test]
+    fn set_key_frame_tween() {
+        let mut encoded = String::new();
+        let edit        = LayerEdit::SetKeyFrameTween(Duration::from_millis(1234), Some(TweenEasing::EaseInOut));
+        edit.serialize(&mut encoded);
+
+        assert!(LayerEdit::deserialize(&mut encoded.chars()) == Some(edit));
+    }
+
+    #[test]
+    fn clear_key_frame_tween() {
+        let mut encoded = String::new();
+        let edit        = LayerEdit::SetKeyFrameTween(Duration::from_millis(1234), None);
+        edit.serialize(&mut encoded);
+
+        assert!(LayerEdit::deserialize(&mut encoded.chars()) == Some(edit));
+    }
+
     #[test]
     fn set_name() {
         let mut encoded = String::new();
@@ -102,4 +197,78 @@ mod test {
 
         assert!(LayerEdit::deserialize(&mut encoded.chars()) == Some(edit));
     }
+
+    #[test]
+    fn set_reference() {
+        let mut encoded = String::new();
+        let edit        = LayerEdit::SetReference(Some(ReferenceLayerSettings { alpha: 0.25, offset: (10.0, 20.0), scale: 1.5, rotation: 0.5 }));
+        edit.serialize(&mut encoded);
+
+        assert!(LayerEdit::deserialize(&mut encoded.chars()) == Some(edit));
+    }
+
+    #[test]
+    fn clear_reference() {
+        let mut encoded = String::new();
+        let edit        = LayerEdit::SetReference(None);
+        edit.serialize(&mut encoded);
+
+        assert!(LayerEdit::deserialize(&mut encoded.chars()) == Some(edit));
+    }
+
+    #[test]
+    fn add_reference_image() {
+        let mut encoded = String::new();
+        let edit        = LayerEdit::AddReferenceImage(Duration::from_millis(1234), 42);
+        edit.serialize(&mut encoded);
+
+        assert!(LayerEdit::deserialize(&mut encoded.chars()) == Some(edit));
+    }
+
+    #[test]
+    fn remove_reference_image() {
+        let mut encoded = String::new();
+        let edit        = LayerEdit::RemoveReferenceImage(Duration::from_millis(1234));
+        edit.serialize(&mut encoded);
+
+        assert!(LayerEdit::deserialize(&mut encoded.chars()) == Some(edit));
+    }
+
+    #[test]
+    fn create_symbol_instance() {
+        let mut encoded = String::new();
+        let instance    = SymbolInstance { symbol_id: 2, transformations: vec![Transformation::Translate(5.0, 6.0)], time_offset: Duration::from_millis(100), loop_mode: SymbolLoopMode::Loop, playback_rate: 1.5 };
+        let edit        = LayerEdit::CreateSymbolInstance(Duration::from_millis(1234), ElementId::Assigned(42), instance);
+        edit.serialize(&mut encoded);
+
+        assert!(LayerEdit::deserialize(&mut encoded.chars()) == Some(edit));
+    }
+
+    #[test]
+    fn set_parent() {
+        let mut encoded = String::new();
+        let edit        = LayerEdit::SetParent(Some(42));
+        edit.serialize(&mut encoded);
+
+        assert!(LayerEdit::deserialize(&mut encoded.chars()) == Some(edit));
+    }
+
+    #[test]
+    fn clear_parent() {
+        let mut encoded = String::new();
+        let edit        = LayerEdit::SetParent(None);
+        edit.serialize(&mut encoded);
+
+        assert!(LayerEdit::deserialize(&mut encoded.chars()) == Some(edit));
+    }
+
+    #[test]
+    fn set_hidden_locked_collapsed() {
+        for edit in vec![LayerEdit::SetHidden(true), LayerEdit::SetHidden(false), LayerEdit::SetLocked(true), LayerEdit::SetLocked(false), LayerEdit::SetCollapsed(true), LayerEdit::SetCollapsed(false)] {
+            let mut encoded = String::new();
+            edit.serialize(&mut encoded);
+
+            assert!(LayerEdit::deserialize(&mut encoded.chars()) == Some(edit));
+        }
+    }
 }
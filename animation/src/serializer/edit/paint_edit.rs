@@ -26,13 +26,7 @@ impl PaintEdit {
                 let mut last_pos = RawPoint::from((0.0, 0.0));
 
                 for point in points.iter() {
-                    data.write_next_f64(last_pos.position.0 as f64, point.position.0 as f64);
-                    data.write_next_f64(last_pos.position.1 as f64, point.position.1 as f64);
-                    data.write_next_f64(last_pos.pressure as f64, point.pressure as f64);
-                    data.write_next_f64(last_pos.tilt.0 as f64, point.tilt.0 as f64);
-                    data.write_next_f64(last_pos.tilt.1 as f64, point.tilt.1 as f64);
-
-                    last_pos = *point;
+                    last_pos = point.serialize_next(&last_pos, data);
                 }
             },
 
@@ -81,11 +75,7 @@ impl PaintEdit {
                             let mut points      = Vec::with_capacity(num_points);
 
                             for _point_num in 0..num_points {
-                                let position    = (data.next_f64_offset(last_pos.position.0 as f64), data.next_f64_offset(last_pos.position.1 as f64));
-                                let pressure    = data.next_f64_offset(last_pos.pressure as f64);
-                                let tilt        = (data.next_f64_offset(last_pos.tilt.0 as f64), data.next_f64_offset(last_pos.tilt.1 as f64));
-
-                                let next_point  = RawPoint { position: (position.0 as f32, position.1 as f32), pressure: pressure as f32, tilt: (tilt.0 as f32, tilt.1 as f32) };
+                                let next_point  = RawPoint::deserialize_next(&last_pos, data);
                                 points.push(next_point);
 
                                 last_pos        = next_point;
@@ -0,0 +1,70 @@
+use super::super::source::*;
+use super::super::target::*;
+use super::super::super::traits::*;
+
+impl PathCombine {
+    ///
+    /// Generates a serialized version of this path combination on the specified data target
+    ///
+    pub fn serialize<Tgt: AnimationDataTarget>(&self, data: &mut Tgt) {
+        use self::PathCombine::*;
+
+        match self {
+            Union       => data.write_chr('U'),
+            Subtract    => data.write_chr('S'),
+            Intersect   => data.write_chr('I'),
+            Exclude     => data.write_chr('X')
+        }
+    }
+
+    ///
+    /// Deserializes a path combination from a data source
+    ///
+    pub fn deserialize<Src: AnimationDataSource>(data: &mut Src) -> Option<PathCombine> {
+        match data.next_chr() {
+            'U'     => Some(PathCombine::Union),
+            'S'     => Some(PathCombine::Subtract),
+            'I'     => Some(PathCombine::Intersect),
+            'X'     => Some(PathCombine::Exclude),
+
+            _       => None
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn union() {
+        let mut encoded = String::new();
+        PathCombine::Union.serialize(&mut encoded);
+
+        assert!(PathCombine::deserialize(&mut encoded.chars()) == Some(PathCombine::Union));
+    }
+
+    #[test]
+    fn subtract() {
+        let mut encoded = String::new();
+        PathCombine::Subtract.serialize(&mut encoded);
+
+        assert!(PathCombine::deserialize(&mut encoded.chars()) == Some(PathCombine::Subtract));
+    }
+
+    #[test]
+    fn intersect() {
+        let mut encoded = String::new();
+        PathCombine::Intersect.serialize(&mut encoded);
+
+        assert!(PathCombine::deserialize(&mut encoded.chars()) == Some(PathCombine::Intersect));
+    }
+
+    #[test]
+    fn exclude() {
+        let mut encoded = String::new();
+        PathCombine::Exclude.serialize(&mut encoded);
+
+        assert!(PathCombine::deserialize(&mut encoded.chars()) == Some(PathCombine::Exclude));
+    }
+}
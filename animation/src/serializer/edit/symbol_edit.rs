@@ -0,0 +1,64 @@
+use super::super::source::*;
+use super::super::target::*;
+use super::super::super::traits::*;
+
+impl SymbolEdit {
+    ///
+    /// Generates a serialized version of this edit on the specified data target
+    ///
+    pub fn serialize<Tgt: AnimationDataTarget>(&self, data: &mut Tgt) {
+        use self::SymbolEdit::*;
+
+        match self {
+            SetName(name)           => { data.write_chr('N'); data.write_str(name); }
+            SetLength(length)       => { data.write_chr('l'); data.write_duration(*length); }
+            AddNewLayer(layer_id)   => { data.write_chr('+'); data.write_small_u64(*layer_id); }
+        }
+    }
+
+    ///
+    /// Deserializes a symbol edit from the supplied data source
+    ///
+    pub fn deserialize<Src: AnimationDataSource>(data: &mut Src) -> Option<SymbolEdit> {
+        match data.next_chr() {
+            'N' => { Some(SymbolEdit::SetName(data.next_string())) }
+            'l' => { Some(SymbolEdit::SetLength(data.next_duration())) }
+            '+' => { Some(SymbolEdit::AddNewLayer(data.next_small_u64())) }
+
+            _   => None
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::time::{Duration};
+
+    #[test]
+    fn set_name() {
+        let mut encoded = String::new();
+        let edit        = SymbolEdit::SetName("Walk cycle".to_string());
+        edit.serialize(&mut encoded);
+
+        assert!(SymbolEdit::deserialize(&mut encoded.chars()) == Some(edit));
+    }
+
+    #[test]
+    fn set_length() {
+        let mut encoded = String::new();
+        let edit        = SymbolEdit::SetLength(Duration::from_millis(2500));
+        edit.serialize(&mut encoded);
+
+        assert!(SymbolEdit::deserialize(&mut encoded.chars()) == Some(edit));
+    }
+
+    #[test]
+    fn add_new_layer() {
+        let mut encoded = String::new();
+        let edit        = SymbolEdit::AddNewLayer(7);
+        edit.serialize(&mut encoded);
+
+        assert!(SymbolEdit::deserialize(&mut encoded.chars()) == Some(edit));
+    }
+}
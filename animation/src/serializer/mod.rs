@@ -1,15 +1,20 @@
 //!
-//! The animation serializer provides a way to convert animation structures to and from a machine-readable ASCII format
+//! The animation serializer provides a way to convert animation structures to and from a machine-readable ASCII format,
+//! or an equivalent binary format
 //! 
 //! Animation structures are basically divided into two: edit log items describe actions that change an animation,
 //! and layer data describes the elements/entities that make up an animation.
 //! 
 //! The custom ASCII format is used for compactness and speed over more verbose formats like JSON, as animations
-//! can contain a lot of data.
+//! can contain a lot of data. The binary format (written to a `Vec<u8>` and read back from a `&[u8]`) encodes the same
+//! values without converting them to characters, so it's smaller again: this is the format used for the edit log and the
+//! elements in storage.
 //!
 
 mod source;
 mod target;
+mod binary;
+mod serialized_data;
 
 mod edit;
 mod color;
@@ -17,14 +22,19 @@ mod vector;
 mod time_path;
 mod cache_type;
 mod element_id;
+mod raw_point;
 mod fill_option;
 mod drawing_style;
 mod path_component;
+mod reference_layer;
+mod tween_easing;
 mod brush_definition;
 mod brush_properties;
 
 pub use self::source::*;
 pub use self::target::*;
+pub use self::serialized_data::*;
+pub use flo_float_encoder::{FloatEncoding};
 
 pub use self::edit::*;
 pub use self::color::*;
@@ -35,5 +45,7 @@ pub use self::element_id::*;
 pub use self::fill_option::*;
 pub use self::drawing_style::*;
 pub use self::path_component::*;
+pub use self::reference_layer::*;
+pub use self::tween_easing::*;
 pub use self::brush_definition::*;
 pub use self::brush_properties::*;
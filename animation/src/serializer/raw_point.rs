@@ -0,0 +1,73 @@
+use super::source::*;
+use super::target::*;
+use super::super::traits::*;
+
+use flo_float_encoder::*;
+
+impl RawPoint {
+    ///
+    /// Generates a serialized version of this raw point relative to a previous point on the specified data target
+    ///
+    pub fn serialize_next<Tgt: AnimationDataTarget>(&self, last: &RawPoint, data: &mut Tgt) -> RawPoint {
+        self.serialize_next_with_encoding(last, FloatEncoding::default(), data)
+    }
+
+    ///
+    /// Generates a serialized version of this raw point relative to a previous point, using a specific encoding for the coordinates
+    ///
+    pub fn serialize_next_with_encoding<Tgt: AnimationDataTarget>(&self, last: &RawPoint, encoding: FloatEncoding, data: &mut Tgt) -> RawPoint {
+        data.write_next_f64_with_encoding(encoding, last.position.0 as f64, self.position.0 as f64);
+        data.write_next_f64_with_encoding(encoding, last.position.1 as f64, self.position.1 as f64);
+        data.write_next_f64_with_encoding(encoding, last.pressure as f64, self.pressure as f64);
+        data.write_next_f64_with_encoding(encoding, last.tilt.0 as f64, self.tilt.0 as f64);
+        data.write_next_f64_with_encoding(encoding, last.tilt.1 as f64, self.tilt.1 as f64);
+
+        *self
+    }
+
+    ///
+    /// Deserializes a raw point relative to a previous point from a data source (this can read points written with any encoding)
+    ///
+    pub fn deserialize_next<Src: AnimationDataSource>(last: &RawPoint, data: &mut Src) -> RawPoint {
+        let position    = (data.next_f64_offset(last.position.0 as f64), data.next_f64_offset(last.position.1 as f64));
+        let pressure    = data.next_f64_offset(last.pressure as f64);
+        let tilt        = (data.next_f64_offset(last.tilt.0 as f64), data.next_f64_offset(last.tilt.1 as f64));
+
+        RawPoint { 
+            position:   (position.0 as f32, position.1 as f32), 
+            pressure:   pressure as f32, 
+            tilt:       (tilt.0 as f32, tilt.1 as f32)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn raw_point_next() {
+        let mut encoded = String::new();
+        let last        = RawPoint { position: (1.0, 2.0), pressure: 0.5, tilt: (0.0, 0.0) };
+        let point       = RawPoint { position: (3.5, 4.25), pressure: 0.75, tilt: (10.0, 20.0) };
+
+        point.serialize_next(&last, &mut encoded);
+
+        let decoded     = RawPoint::deserialize_next(&last, &mut encoded.chars());
+
+        assert!(decoded == point);
+    }
+
+    #[test]
+    fn raw_point_next_lossless() {
+        let mut encoded: Vec<u8> = vec![];
+        let last        = RawPoint { position: (1.0, 2.0), pressure: 0.5, tilt: (0.0, 0.0) };
+        let point       = RawPoint { position: (3.1234567, 4.7654321), pressure: 0.333333, tilt: (10.1, 20.2) };
+
+        point.serialize_next_with_encoding(&last, FloatEncoding::Lossless, &mut encoded);
+
+        let decoded     = RawPoint::deserialize_next(&last, &mut &encoded[..]);
+
+        assert!(decoded == point);
+    }
+}
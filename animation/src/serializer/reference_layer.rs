@@ -0,0 +1,48 @@
+use super::source::*;
+use super::target::*;
+use super::super::traits::*;
+
+impl ReferenceLayerSettings {
+    ///
+    /// Generates a serialized version of these reference layer settings on the specified data target
+    ///
+    pub fn serialize<Tgt: AnimationDataTarget>(&self, data: &mut Tgt) {
+        data.write_f64(self.alpha);
+        data.write_f64(self.offset.0);
+        data.write_f64(self.offset.1);
+        data.write_f64(self.scale);
+        data.write_f64(self.rotation);
+    }
+
+    ///
+    /// Deserializes reference layer settings from a source
+    ///
+    pub fn deserialize<Src: AnimationDataSource>(data: &mut Src) -> Option<ReferenceLayerSettings> {
+        let alpha       = data.next_f64();
+        let offset_x    = data.next_f64();
+        let offset_y    = data.next_f64();
+        let scale       = data.next_f64();
+        let rotation    = data.next_f64();
+
+        Some(ReferenceLayerSettings {
+            alpha:      alpha,
+            offset:     (offset_x, offset_y),
+            scale:      scale,
+            rotation:   rotation
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn reference_settings() {
+        let mut encoded = String::new();
+        let settings    = ReferenceLayerSettings { alpha: 0.25, offset: (10.0, 20.0), scale: 1.5, rotation: 0.5 };
+        settings.serialize(&mut encoded);
+
+        assert!(ReferenceLayerSettings::deserialize(&mut encoded.chars()) == Some(settings));
+    }
+}
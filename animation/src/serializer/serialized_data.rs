@@ -0,0 +1,52 @@
+///
+/// Data that has been written by one of the serialization targets
+///
+/// Data written by a `String` target is stored as text and data written by a `Vec<u8>` target is stored as binary. The two
+/// encodings describe the same values, so storage can contain a mix of the two: the binary encoding is more compact, and the
+/// text encoding is used by older files and anywhere that the data needs to be human-readable.
+///
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum SerializedData {
+    /// Data serialized using the text encoding
+    Text(String),
+
+    /// Data serialized using the binary encoding
+    Binary(Vec<u8>)
+}
+
+impl SerializedData {
+    ///
+    /// Returns the number of bytes used to store this data
+    ///
+    pub fn len(&self) -> usize {
+        match self {
+            SerializedData::Text(text)      => text.len(),
+            SerializedData::Binary(bytes)   => bytes.len()
+        }
+    }
+
+    ///
+    /// True if this data is empty
+    ///
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl From<String> for SerializedData {
+    fn from(text: String) -> SerializedData {
+        SerializedData::Text(text)
+    }
+}
+
+impl From<&str> for SerializedData {
+    fn from(text: &str) -> SerializedData {
+        SerializedData::Text(text.to_string())
+    }
+}
+
+impl From<Vec<u8>> for SerializedData {
+    fn from(bytes: Vec<u8>) -> SerializedData {
+        SerializedData::Binary(bytes)
+    }
+}
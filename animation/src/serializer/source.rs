@@ -328,6 +328,21 @@ mod test {
         assert!(encoded.chars().next_f64_offset(14.0) == 64000.0);
     }
 
+    #[test]
+    fn decode_f64_offset_with_encodings() {
+        let mut encoded = String::new();
+
+        encoded.write_next_f64_with_encoding(FloatEncoding::PRECISION_4096, 14.0, 14.25);
+        encoded.write_next_f64_with_encoding(FloatEncoding::VarInt(8), 14.25, 64000.5);
+        encoded.write_next_f64_with_encoding(FloatEncoding::Lossless, 64000.5, 0.1);
+
+        let mut src = encoded.chars();
+        assert!(src.next_f64_offset(14.0) == 14.25);
+        assert!(src.next_f64_offset(14.25) == 64000.5);
+        assert!(src.next_f64_offset(64000.5) == 0.1);
+        assert!(src.next().is_none());
+    }
+
     #[test]
     fn decode_all() {
         // Encodes everything next to each other so we know the decoder is always left in a valid state afterwards (doesn't read an extra character)
@@ -145,8 +145,16 @@ pub trait AnimationDataTarget {
     /// Writes a f64 value to this target that's relative to a previous value (this uses a more compact format to save space)
     ///
     fn write_next_f64(&mut self, last: f64, data: f64) {
+        self.write_next_f64_with_encoding(FloatEncoding::default(), last, data);
+    }
+
+    ///
+    /// Writes a f64 value to this target that's relative to a previous value, using a specific encoding (which can trade
+    /// space for precision)
+    ///
+    fn write_next_f64_with_encoding(&mut self, encoding: FloatEncoding, last: f64, data: f64) {
         let mut bytes  = vec![];
-        squish_float(&mut bytes, last, data).ok();
+        squish_float_with_encoding(&mut bytes, encoding, last, data).ok();
         self.write_bytes(&bytes);
     }
 
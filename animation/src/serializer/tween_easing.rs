@@ -0,0 +1,47 @@
+use super::source::*;
+use super::target::*;
+use super::super::traits::*;
+
+impl TweenEasing {
+    ///
+    /// Generates a serialized version of this tween easing on the specified data target
+    ///
+    pub fn serialize<Tgt: AnimationDataTarget>(&self, data: &mut Tgt) {
+        use self::TweenEasing::*;
+
+        match self {
+            Linear      => { data.write_chr('l'); }
+            EaseIn      => { data.write_chr('i'); }
+            EaseOut     => { data.write_chr('o'); }
+            EaseInOut   => { data.write_chr('b'); }
+        }
+    }
+
+    ///
+    /// Deserializes a tween easing from a source
+    ///
+    pub fn deserialize<Src: AnimationDataSource>(data: &mut Src) -> Option<TweenEasing> {
+        match data.next_chr() {
+            'l' => Some(TweenEasing::Linear),
+            'i' => Some(TweenEasing::EaseIn),
+            'o' => Some(TweenEasing::EaseOut),
+            'b' => Some(TweenEasing::EaseInOut),
+            _   => None
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn all_easings() {
+        for easing in vec![TweenEasing::Linear, TweenEasing::EaseIn, TweenEasing::EaseOut, TweenEasing::EaseInOut] {
+            let mut encoded = String::new();
+            easing.serialize(&mut encoded);
+
+            assert!(TweenEasing::deserialize(&mut encoded.chars()) == Some(easing));
+        }
+    }
+}
@@ -0,0 +1,116 @@
+use super::super::source::*;
+use super::super::target::*;
+use super::super::super::traits::*;
+
+use std::collections::{BTreeMap};
+
+impl BoneElement {
+    ///
+    /// Generates a serialized version of this bone element on the specified data target
+    ///
+    pub fn serialize<Tgt: AnimationDataTarget>(&self, data: &mut Tgt) {
+        self.bone().serialize(data);
+    }
+
+    ///
+    /// Deserializes a BoneElement from a data source
+    ///
+    pub fn deserialize<Src: AnimationDataSource>(element_id: ElementId, data: &mut Src) -> Option<BoneElement> {
+        Bone::deserialize(data)
+            .map(move |bone| BoneElement::new(element_id, bone))
+    }
+}
+
+impl Bone {
+    ///
+    /// Generates a serialized version of this bone on the specified data target
+    ///
+    pub fn serialize<Tgt: AnimationDataTarget>(&self, data: &mut Tgt) {
+        match self.parent {
+            None            => { data.write_chr('-'); }
+            Some(parent_id) => { data.write_chr('P'); parent_id.serialize(data); }
+        }
+
+        data.write_f32(self.origin.0);
+        data.write_f32(self.origin.1);
+        data.write_f32(self.length);
+        data.write_f64(self.angle);
+
+        data.write_usize(self.rotations.len());
+        for (when, rotation) in self.rotations.iter() {
+            data.write_duration(*when);
+            data.write_f64(*rotation);
+        }
+    }
+
+    ///
+    /// Deserializes a bone from a data source
+    ///
+    pub fn deserialize<Src: AnimationDataSource>(data: &mut Src) -> Option<Bone> {
+        let parent          = match data.next_chr() {
+            '-' => None,
+            'P' => Some(ElementId::deserialize(data)?),
+            _   => { return None; }
+        };
+
+        let origin          = (data.next_f32(), data.next_f32());
+        let length          = data.next_f32();
+        let angle           = data.next_f64();
+
+        let num_rotations   = data.next_usize();
+        let mut rotations   = BTreeMap::new();
+        for _ in 0..num_rotations {
+            let when        = data.next_duration();
+            let rotation    = data.next_f64();
+
+            rotations.insert(when, rotation);
+        }
+
+        Some(Bone {
+            parent:     parent,
+            origin:     origin,
+            length:     length,
+            angle:      angle,
+            rotations:  rotations
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::time::{Duration};
+
+    #[test]
+    fn bone() {
+        let mut bone    = Bone::new();
+        bone.parent     = Some(ElementId::Assigned(3));
+        bone.origin     = (10.0, 20.0);
+        bone.length     = 30.0;
+        bone.angle      = 0.5;
+        bone.rotations.insert(Duration::from_millis(0), 0.25);
+        bone.rotations.insert(Duration::from_millis(1000), -1.0);
+
+        let bone        = BoneElement::new(ElementId::Assigned(1), bone);
+
+        let mut encoded = String::new();
+        bone.serialize(&mut encoded);
+
+        let decoded     = BoneElement::deserialize(ElementId::Assigned(1), &mut encoded.chars());
+        let decoded     = decoded.unwrap();
+
+        assert!(decoded.id() == ElementId::Assigned(1));
+        assert!(*decoded.bone() == *bone.bone());
+    }
+
+    #[test]
+    fn bone_without_parent() {
+        let bone        = BoneElement::new(ElementId::Assigned(1), Bone::new());
+
+        let mut encoded = String::new();
+        bone.serialize(&mut encoded);
+
+        let decoded     = BoneElement::deserialize(ElementId::Assigned(1), &mut encoded.chars());
+        assert!(*decoded.unwrap().bone() == Bone::new());
+    }
+}
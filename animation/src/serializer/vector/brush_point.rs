@@ -2,6 +2,8 @@ use super::super::source::*;
 use super::super::target::*;
 use super::super::super::traits::*;
 
+use flo_float_encoder::*;
+
 impl BrushPoint {
     ///
     /// Generates a serialized version of this brush point on the specified data target
@@ -17,10 +19,17 @@ impl BrushPoint {
     /// Generates a serialized version of this brush point on the specified data target
     ///
     pub fn serialize_next<Tgt: AnimationDataTarget>(&self, last: &BrushPoint, data: &mut Tgt) -> BrushPoint {
-        data.write_next_f64(last.position.0 as f64, self.position.0 as f64); data.write_next_f64(last.position.1 as f64, self.position.1 as f64);
-        data.write_next_f64(last.cp1.0 as f64, self.cp1.0 as f64);           data.write_next_f64(last.cp1.1 as f64, self.cp1.1 as f64);
-        data.write_next_f64(last.cp2.0 as f64, self.cp2.0 as f64);           data.write_next_f64(last.cp2.1 as f64, self.cp2.1 as f64);
-        data.write_next_f64(last.width as f64, self.width as f64);
+        self.serialize_next_with_encoding(last, FloatEncoding::default(), data)
+    }
+
+    ///
+    /// Generates a serialized version of this brush point relative to a previous point, using a specific encoding for the coordinates
+    ///
+    pub fn serialize_next_with_encoding<Tgt: AnimationDataTarget>(&self, last: &BrushPoint, encoding: FloatEncoding, data: &mut Tgt) -> BrushPoint {
+        data.write_next_f64_with_encoding(encoding, last.position.0 as f64, self.position.0 as f64); data.write_next_f64_with_encoding(encoding, last.position.1 as f64, self.position.1 as f64);
+        data.write_next_f64_with_encoding(encoding, last.cp1.0 as f64, self.cp1.0 as f64);           data.write_next_f64_with_encoding(encoding, last.cp1.1 as f64, self.cp1.1 as f64);
+        data.write_next_f64_with_encoding(encoding, last.cp2.0 as f64, self.cp2.0 as f64);           data.write_next_f64_with_encoding(encoding, last.cp2.1 as f64, self.cp2.1 as f64);
+        data.write_next_f64_with_encoding(encoding, last.width as f64, self.width as f64);
 
         self.clone()
     }
@@ -100,4 +109,31 @@ mod test {
 
         assert!(decoded == element);
     }
+
+    #[test]
+    fn brush_point_next_with_precision() {
+        let mut coarse  = String::new();
+        let mut fine    = String::new();
+        let last        = BrushPoint {
+            position:   (1.0, 2.0),
+            cp1:        (3.0, 4.0),
+            cp2:        (5.0, 6.0),
+            width:      7.0
+        };
+        let element     = BrushPoint {
+            position:   (8.001, 9.001),
+            cp1:        (10.001, 11.001),
+            cp2:        (12.001, 13.001),
+            width:      14.001
+        };
+        element.serialize_next_with_encoding(&last, FloatEncoding::PRECISION_256, &mut coarse);
+        element.serialize_next_with_encoding(&last, FloatEncoding::PRECISION_4096, &mut fine);
+
+        let coarse      = BrushPoint::deserialize_next(&last, &mut coarse.chars());
+        let fine        = BrushPoint::deserialize_next(&last, &mut fine.chars());
+
+        assert!((fine.position.0-8.001).abs() < 0.0002);
+        assert!((fine.width-14.001).abs() < 0.0002);
+        assert!((fine.position.0-8.001).abs() < (coarse.position.0-8.001).abs());
+    }
 }
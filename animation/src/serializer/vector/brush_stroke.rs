@@ -2,6 +2,8 @@ use super::super::source::*;
 use super::super::target::*;
 use super::super::super::traits::*;
 
+use flo_float_encoder::*;
+
 use std::sync::*;
 
 impl BrushElement {
@@ -9,6 +11,15 @@ impl BrushElement {
     /// Generates a serialized version of this brush stroke element on the specified data target
     ///
     pub fn serialize<Tgt: AnimationDataTarget>(&self, data: &mut Tgt) {
+        self.serialize_with_encoding(FloatEncoding::default(), data);
+    }
+
+    ///
+    /// Generates a serialized version of this brush stroke element, using a specific encoding for the coordinates of the points
+    ///
+    /// The encoding is stored alongside each value, so `deserialize()` can read the element back whichever encoding is used.
+    ///
+    pub fn serialize_with_encoding<Tgt: AnimationDataTarget>(&self, encoding: FloatEncoding, data: &mut Tgt) {
         // Version 0
         data.write_small_u64(0);
 
@@ -22,7 +33,7 @@ impl BrushElement {
 
         data.write_usize(self.points().len());
         for point in self.points().iter() {
-            last_point = point.serialize_next(&last_point, data);
+            last_point = point.serialize_next_with_encoding(&last_point, encoding, data);
         }
     }
 
@@ -76,4 +87,40 @@ mod test {
 
         assert!(decoded.points() == element.points());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn brush_stroke_binary() {
+        let mut encoded: Vec<u8> = vec![];
+        let element     = BrushElement::new(ElementId::Assigned(1), Arc::new(vec![
+            BrushPoint { position: (1.0, 2.0), cp1: (3.0, 4.0), cp2: (5.0, 6.0), width: 7.0 },
+            BrushPoint { position: (8.0, 9.0), cp1: (10.0, 11.0), cp2: (12.0, 13.0), width: 14.0 },
+            BrushPoint { position: (15.0, 16.0), cp1: (17.0, 18.0), cp2: (19.0, 6.0), width: 20.0 },
+            BrushPoint { position: (1.0, 2.0), cp1: (3.0, 4.0), cp2: (5.0, 6.0), width: 7.0 }
+        ]));
+        element.serialize(&mut encoded);
+
+        let mut text    = String::new();
+        element.serialize(&mut text);
+        assert!(encoded.len() < text.len());
+
+        let decoded     = BrushElement::deserialize(ElementId::Assigned(1), &mut &encoded[..]);
+        let decoded     = decoded.unwrap();
+
+        assert!(decoded.points() == element.points());
+    }
+
+    #[test]
+    fn brush_stroke_lossless() {
+        let mut encoded: Vec<u8> = vec![];
+        let element     = BrushElement::new(ElementId::Assigned(1), Arc::new(vec![
+            BrushPoint { position: (1.1, 2.2), cp1: (3.3, 4.4), cp2: (5.5, 6.6), width: 7.7 },
+            BrushPoint { position: (800.123, 9.001), cp1: (10.01, 11.11), cp2: (12.21, 13.31), width: 0.1 }
+        ]));
+        element.serialize_with_encoding(FloatEncoding::Lossless, &mut encoded);
+
+        let decoded     = BrushElement::deserialize(ElementId::Assigned(1), &mut &encoded[..]);
+        let decoded     = decoded.unwrap();
+
+        assert!(decoded.points() == element.points());
+    }
+}
@@ -4,7 +4,6 @@ use super::super::target::*;
 use super::super::super::traits::*;
 
 use std::sync::*;
-use std::str::{Chars};
 
 impl GroupType {
     ///
@@ -66,7 +65,7 @@ impl GroupElement {
     ///
     /// Deserializes a group from a data source
     ///
-    pub fn deserialize(element_id: ElementId, data: &mut Chars) -> Option<impl ResolveElements<GroupElement>> {
+    pub fn deserialize<Src: AnimationDataSource>(element_id: ElementId, data: &mut Src) -> Option<BoxedResolver<GroupElement>> {
         match data.next_small_u64() {
             0 => {
                 // Type of this group
@@ -107,7 +106,7 @@ impl GroupElement {
                 };
 
                 // Create a resolver for this group
-                Some(ElementResolver(move |mapper| {
+                Some(BoxedResolver::new(move |mapper| {
                     // Resolve the elements
                     let elements = elements.into_iter()
                         .map(|elem_ref| {
@@ -168,4 +167,30 @@ mod test {
         assert!(elements[2].id() == ElementId::Assigned(3));
         assert!(elements[3].id() == ElementId::Unassigned);
     }
+
+    #[test]
+    fn group_binary() {
+        let element1    = Vector::BrushDefinition(BrushDefinitionElement::new(ElementId::Assigned(1), BrushDefinition::Simple, BrushDrawingStyle::Erase));
+        let element2    = Vector::BrushDefinition(BrushDefinitionElement::new(ElementId::Unassigned, BrushDefinition::Simple, BrushDrawingStyle::Erase));
+        let group       = GroupElement::new(ElementId::Assigned(5), GroupType::Added, Arc::new(vec![element1.clone(), element2.clone()]));
+
+        let mut encoded: Vec<u8> = vec![];
+        group.serialize(&mut encoded);
+
+        let decoded     = GroupElement::deserialize(ElementId::Assigned(5), &mut &encoded[..]);
+        let decoded     = decoded.unwrap().resolve(&mut |element_id| {
+            match element_id {
+                ElementId::Assigned(1)  => Some(element1.clone()),
+                _                       => None
+            }
+        });
+        let decoded     = decoded.unwrap();
+
+        assert!(decoded.group_type() == GroupType::Added);
+        assert!(decoded.num_elements() == 2);
+
+        let elements = decoded.elements().collect::<Vec<_>>();
+        assert!(elements[0].id() == ElementId::Assigned(1));
+        assert!(elements[1].id() == ElementId::Unassigned);
+    }
 }
@@ -2,6 +2,8 @@ mod path;
 mod group;
 mod vector;
 mod motion;
+mod symbol;
+mod bone;
 mod transformed;
 mod brush_point;
 mod brush_stroke;
@@ -14,6 +16,8 @@ pub use self::path::*;
 pub use self::group::*;
 pub use self::vector::*;
 pub use self::motion::*;
+pub use self::symbol::*;
+pub use self::bone::*;
 pub use self::transformed::*;
 pub use self::brush_point::*;
 pub use self::brush_stroke::*;
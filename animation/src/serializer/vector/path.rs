@@ -4,7 +4,6 @@ use super::super::target::*;
 use super::super::super::traits::*;
 
 use std::sync::*;
-use std::str::{Chars};
 
 impl PathElement {
     ///
@@ -33,7 +32,7 @@ impl PathElement {
     ///
     /// Deserializes a path element from the data source
     ///
-    pub fn deserialize(element_id: ElementId, data: &mut Chars) -> Option<impl ResolveElements<PathElement>> {
+    pub fn deserialize<Src: AnimationDataSource>(element_id: ElementId, data: &mut Src) -> Option<BoxedResolver<PathElement>> {
         match data.next_small_u64() {
             0 => {
                 // Fetch the brush and properties IDs
@@ -55,7 +54,7 @@ impl PathElement {
                 let path            = Path::deserialize(data)?;
 
                 // Generate the resolver
-                Some(ElementResolver(move |mapper| {
+                Some(BoxedResolver::new(move |mapper| {
                     // Resolve the brush and properties
                     let brush       = brush.or_else(|| mapper(brush_id))?;
                     let properties  = properties.or_else(|| mapper(properties_id))?;
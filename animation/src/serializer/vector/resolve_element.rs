@@ -12,15 +12,26 @@ pub trait ResolveElements<T> {
 }
 
 ///
-/// Basic implementation of the resolve elements trait that resolves via a callback to a closure
+/// Resolver that stores its resolve function in a box
 ///
-pub (crate) struct ElementResolver<TFn: Sized, T>(pub TFn)
-where TFn: FnOnce(&mut dyn FnMut(ElementId) -> Option<Vector>) -> Option<T>;
+/// The deserializers for elements that can contain other elements return this type: as it has no type parameters other
+/// than the type that's being resolved, it doesn't borrow the data source used to deserialize it (which a resolver
+/// returned as `impl ResolveElements` from a function that's generic over the data source would).
+///
+pub struct BoxedResolver<T>(Box<dyn FnOnce(&mut dyn FnMut(ElementId) -> Option<Vector>) -> Option<T>>);
+
+impl<T> BoxedResolver<T> {
+    ///
+    /// Creates a new boxed resolver from a resolve function
+    ///
+    pub (crate) fn new<TFn: 'static+FnOnce(&mut dyn FnMut(ElementId) -> Option<Vector>) -> Option<T>>(resolve: TFn) -> BoxedResolver<T> {
+        BoxedResolver(Box::new(resolve))
+    }
+}
 
-impl<TFn, T> ResolveElements<T> for ElementResolver<TFn, T>
-where TFn: FnOnce(&mut dyn FnMut(ElementId) -> Option<Vector>) -> Option<T> {
+impl<T> ResolveElements<T> for BoxedResolver<T> {
     fn resolve(self, find_element: &mut dyn FnMut(ElementId) -> Option<Vector>) -> Option<T> {
-        let ElementResolver(resolve) = self;
+        let BoxedResolver(resolve) = self;
         resolve(find_element)
     }
 }
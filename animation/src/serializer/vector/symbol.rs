@@ -0,0 +1,127 @@
+use super::super::source::*;
+use super::super::target::*;
+use super::super::super::traits::*;
+
+impl SymbolLoopMode {
+    ///
+    /// Generates a serialized version of this loop mode on the specified data target
+    ///
+    pub fn serialize<Tgt: AnimationDataTarget>(&self, data: &mut Tgt) {
+        use self::SymbolLoopMode::*;
+
+        match self {
+            Loop        => { data.write_chr('l'); }
+            PlayOnce    => { data.write_chr('o'); }
+            SingleFrame => { data.write_chr('s'); }
+        }
+    }
+
+    ///
+    /// Deserializes a loop mode from a data source
+    ///
+    pub fn deserialize<Src: AnimationDataSource>(data: &mut Src) -> Option<SymbolLoopMode> {
+        match data.next_chr() {
+            'l' => Some(SymbolLoopMode::Loop),
+            'o' => Some(SymbolLoopMode::PlayOnce),
+            's' => Some(SymbolLoopMode::SingleFrame),
+            _   => None
+        }
+    }
+}
+
+impl SymbolInstance {
+    ///
+    /// Generates a serialized version of this symbol instance on the specified data target
+    ///
+    pub fn serialize<Tgt: AnimationDataTarget>(&self, data: &mut Tgt) {
+        data.write_small_u64(self.symbol_id);
+
+        data.write_usize(self.transformations.len());
+        self.transformations.iter().for_each(|transform| transform.serialize(data));
+
+        data.write_duration(self.time_offset);
+        self.loop_mode.serialize(data);
+        data.write_f64(self.playback_rate);
+    }
+
+    ///
+    /// Deserializes a symbol instance from a data source
+    ///
+    pub fn deserialize<Src: AnimationDataSource>(data: &mut Src) -> Option<SymbolInstance> {
+        let symbol_id           = data.next_small_u64();
+
+        let num_transforms      = data.next_usize();
+        let transformations     = (0..num_transforms).into_iter()
+            .map(|_| Transformation::deserialize(data))
+            .collect::<Option<Vec<_>>>()?;
+
+        let time_offset         = data.next_duration();
+        let loop_mode           = SymbolLoopMode::deserialize(data)?;
+        let playback_rate       = data.next_f64();
+
+        Some(SymbolInstance {
+            symbol_id:          symbol_id,
+            transformations:    transformations,
+            time_offset:        time_offset,
+            loop_mode:          loop_mode,
+            playback_rate:      playback_rate
+        })
+    }
+}
+
+impl SymbolElement {
+    ///
+    /// Generates a serialized version of this symbol element on the specified data target
+    ///
+    /// (The content of the symbol is stored separately, so only the instance settings are serialized)
+    ///
+    pub fn serialize<Tgt: AnimationDataTarget>(&self, data: &mut Tgt) {
+        self.instance().serialize(data);
+    }
+
+    ///
+    /// Deserializes a symbol element from a data source
+    ///
+    pub fn deserialize<Src: AnimationDataSource>(element_id: ElementId, data: &mut Src) -> Option<SymbolElement> {
+        SymbolInstance::deserialize(data)
+            .map(move |instance| SymbolElement::new(element_id, instance))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use std::time::{Duration};
+
+    #[test]
+    fn symbol_element() {
+        let instance = SymbolInstance {
+            symbol_id:          42,
+            transformations:    vec![Transformation::Translate(10.0, 20.0), Transformation::Rotate(0.5, (1.0, 2.0))],
+            time_offset:        Duration::from_millis(250),
+            loop_mode:          SymbolLoopMode::PlayOnce,
+            playback_rate:      0.5
+        };
+        let element     = SymbolElement::new(ElementId::Assigned(1), instance.clone());
+
+        let mut encoded = String::new();
+        element.serialize(&mut encoded);
+
+        let decoded     = SymbolElement::deserialize(ElementId::Assigned(1), &mut encoded.chars());
+        let decoded     = decoded.unwrap();
+
+        assert!(decoded.id() == ElementId::Assigned(1));
+        assert!(decoded.instance() == &instance);
+    }
+
+    #[test]
+    fn all_loop_modes() {
+        for loop_mode in vec![SymbolLoopMode::Loop, SymbolLoopMode::PlayOnce, SymbolLoopMode::SingleFrame] {
+            let mut encoded = String::new();
+            loop_mode.serialize(&mut encoded);
+
+            assert!(SymbolLoopMode::deserialize(&mut encoded.chars()) == Some(loop_mode));
+        }
+    }
+}
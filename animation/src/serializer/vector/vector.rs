@@ -5,13 +5,6 @@ use super::super::super::traits::*;
 
 use smallvec::*;
 
-use std::str::{Chars};
-
-// Function to turn a resolve function into a boxed resolve function (to get around limitations in Rust's type inference)
-fn box_fn<TFn: 'static+FnOnce(&mut dyn FnMut(ElementId) -> Option<Vector>) -> Option<Vector>>(func: TFn) -> Box<dyn FnOnce(&mut dyn FnMut(ElementId) -> Option<Vector>) -> Option<Vector>> {
-    Box::new(func)
-}
-
 impl Vector {
     ///
     /// Generates a serialized version of this vector element on the specified data target
@@ -29,6 +22,8 @@ impl Vector {
             Path(path)                      => { data.write_chr('p'); path.serialize(data); }
             Motion(motion)                  => { data.write_chr('m'); motion.serialize(data); }
             Group(group)                    => { data.write_chr('g'); group.serialize(data); }
+            Symbol(symbol)                  => { data.write_chr('y'); symbol.serialize(data); }
+            Bone(bone)                      => { data.write_chr('b'); bone.serialize(data); }
             Error                           => { data.write_chr('?'); }
 
             Transformation((id, transform)) => { 
@@ -44,45 +39,51 @@ impl Vector {
     ///
     /// Deserializes a vector element from a data source
     /// 
-    /// We use a concrete type 'Chars' for the data source here and can't use generic types using the AnimationDataSource
-    /// trait: this is because Rust's lifetime checker seems to have a bug and borrows the `data` element for as long
-    /// as the trait exists if we use the generic form due to some kind of interaction with the box_fn function
-    /// (data is only required as long as say `GroupElement::deserialize()` is running but Rust can't see that?)
+    /// The resolver is boxed so that it doesn't borrow the data source (groups contain other vectors, so the resolvers
+    /// returned by this function are stored in the resolvers for other elements)
     ///
-    pub fn deserialize(element_id: ElementId, data: &mut Chars) -> Option<impl ResolveElements<Vector>> {
+    pub fn deserialize<Src: AnimationDataSource>(element_id: ElementId, data: &mut Src) -> Option<BoxedResolver<Vector>> {
         // Deserialize the element
-        let resolve = match data.next_chr() {
+        match data.next_chr() {
             'T' => { unimplemented!("Transformed") }
             'D' => { 
                 BrushDefinitionElement::deserialize(element_id, data)
-                    .map(|defn| box_fn(move |_| Some(Vector::BrushDefinition(defn))))
+                    .map(|defn| BoxedResolver::new(move |_| Some(Vector::BrushDefinition(defn))))
             }
             'P' =>  { 
                 BrushPropertiesElement::deserialize(element_id, data)
-                    .map(|properties| box_fn(move |_| Some(Vector::BrushProperties(properties))))
+                    .map(|properties| BoxedResolver::new(move |_| Some(Vector::BrushProperties(properties))))
             }
             's' => {
                 BrushElement::deserialize(element_id, data)
-                    .map(|brush_stroke| box_fn(move |_| Some(Vector::BrushStroke(brush_stroke))))
+                    .map(|brush_stroke| BoxedResolver::new(move |_| Some(Vector::BrushStroke(brush_stroke))))
             }
             'p' => {
                 let path_resolver = PathElement::deserialize(element_id, data)?;
-                Some(box_fn(move |mapper| {
+                Some(BoxedResolver::new(move |mapper| {
                     let path = path_resolver.resolve(mapper)?;
                     Some(Vector::Path(path))
                 }))
             }
             'm' => { 
                 MotionElement::deserialize(element_id, data)
-                    .map(|motion| box_fn(move |_| Some(Vector::Motion(motion))))
+                    .map(|motion| BoxedResolver::new(move |_| Some(Vector::Motion(motion))))
             }
             'g' => { 
                 let group_resolver = GroupElement::deserialize(element_id, data)?;
-                Some(box_fn(move |mapper| {
+                Some(BoxedResolver::new(move |mapper| {
                     let group = group_resolver.resolve(mapper)?;
                     Some(Vector::Group(group))
                 }))
             }
+            'y' => {
+                SymbolElement::deserialize(element_id, data)
+                    .map(|symbol| BoxedResolver::new(move |_| Some(Vector::Symbol(symbol))))
+            }
+            'b' => {
+                BoneElement::deserialize(element_id, data)
+                    .map(|bone| BoxedResolver::new(move |_| Some(Vector::Bone(bone))))
+            }
             't' => {
                 ElementId::deserialize(data)
                     .and_then(|elem_id| {
@@ -94,20 +95,15 @@ impl Vector {
                         transforms.map(move |transforms| (elem_id, transforms))
                     })
                     .map(|transform| Vector::Transformation(transform))
-                    .map(|vector| box_fn(move |_| Some(vector)))
+                    .map(|vector| BoxedResolver::new(move |_| Some(vector)))
             }
             '?' => {
-                Some(box_fn(move |_mapper| {
+                Some(BoxedResolver::new(move |_mapper| {
                     Some(Vector::Error)
                 }))
             }
 
             _ => None
-        }?;
-
-        // Return a resolver based on the deserialized data
-        Some(ElementResolver(move |mapper| {
-            (resolve)(mapper)
-        }))
+        }
     }
 }
@@ -0,0 +1,156 @@
+use super::super::traits::*;
+
+use std::collections::{HashSet};
+use std::time::{Duration};
+
+///
+/// Describes a structural difference between two animations
+///
+/// 'Missing' items are found in the first animation but not the second, and 'extra' items are found in the second animation
+/// but not the first.
+///
+#[derive(Clone, PartialEq, Debug)]
+pub enum AnimationDifference {
+    /// The animations have different sizes
+    Size((f64, f64), (f64, f64)),
+
+    /// The animations have different lengths
+    Length(Duration, Duration),
+
+    /// The animations have different frame lengths
+    FrameLength(Duration, Duration),
+
+    /// A layer is missing from the second animation
+    MissingLayer(u64),
+
+    /// The second animation has a layer that's not in the first
+    ExtraLayer(u64),
+
+    /// A layer has a different name in each animation
+    LayerName(u64, Option<String>, Option<String>),
+
+    /// A keyframe on a layer is missing from the second animation
+    MissingKeyFrame(u64, Duration),
+
+    /// The second animation has a keyframe that's not in the first
+    ExtraKeyFrame(u64, Duration),
+
+    /// An element in the keyframe at the specified time on a layer is missing from the second animation
+    MissingElement(u64, Duration, ElementId),
+
+    /// The second animation has an element in a keyframe that's not in the first
+    ExtraElement(u64, Duration, ElementId),
+
+    /// An element in a keyframe has different content in each animation
+    ElementDiffers(u64, Duration, ElementId),
+
+    /// The motions attached to an element are different in each animation
+    ElementMotionsDiffer(ElementId, Vec<ElementId>, Vec<ElementId>),
+
+    /// A motion has different content in each animation
+    MotionDiffers(ElementId)
+}
+
+///
+/// Returns the serialized form of the elements in a frame, in the order they appear
+///
+pub (super) fn serialized_elements(frame: &dyn Frame) -> Vec<(ElementId, String)> {
+    frame.vector_elements()
+        .map(|elements| elements.map(|element| {
+            let mut serialized = String::new();
+            element.serialize(&mut serialized);
+
+            (element.id(), serialized)
+        }).collect())
+        .unwrap_or_else(|| vec![])
+}
+
+///
+/// Compares the content of the keyframes of a layer that is in both animations
+///
+fn diff_layer(layer_id: u64, layer_a: &dyn Layer, layer_b: &dyn Layer, motion_a: &dyn AnimationMotion, motion_b: &dyn AnimationMotion, differences: &mut Vec<AnimationDifference>) {
+    use self::AnimationDifference::*;
+
+    if layer_a.name() != layer_b.name() {
+        differences.push(LayerName(layer_id, layer_a.name(), layer_b.name()));
+    }
+
+    // Compare the keyframes
+    let keyframes_a     = layer_a.get_key_frames().collect::<Vec<_>>();
+    let keyframes_b     = layer_b.get_key_frames().collect::<Vec<_>>();
+    let keyframe_set_b  = keyframes_b.iter().cloned().collect::<HashSet<_>>();
+    let keyframe_set_a  = keyframes_a.iter().cloned().collect::<HashSet<_>>();
+
+    keyframes_a.iter().filter(|when| !keyframe_set_b.contains(when)).for_each(|when| differences.push(MissingKeyFrame(layer_id, *when)));
+    keyframes_b.iter().filter(|when| !keyframe_set_a.contains(when)).for_each(|when| differences.push(ExtraKeyFrame(layer_id, *when)));
+
+    // Compare the elements in the keyframes that exist in both layers
+    for when in keyframes_a.iter().filter(|when| keyframe_set_b.contains(when)) {
+        let elements_a  = serialized_elements(&*layer_a.get_frame_at_time(*when));
+        let elements_b  = serialized_elements(&*layer_b.get_frame_at_time(*when));
+        let ids_b       = elements_b.iter().map(|(id, _)| *id).collect::<HashSet<_>>();
+        let ids_a       = elements_a.iter().map(|(id, _)| *id).collect::<HashSet<_>>();
+
+        for (element_id, serialized_a) in elements_a.iter() {
+            if let Some((_, serialized_b)) = elements_b.iter().find(|(id, _)| id == element_id) {
+                if serialized_a != serialized_b {
+                    differences.push(ElementDiffers(layer_id, *when, *element_id));
+                }
+
+                // Compare the motions for this element
+                let motions_a = motion_a.get_motions_for_element(*element_id);
+                let motions_b = motion_b.get_motions_for_element(*element_id);
+
+                if motions_a != motions_b {
+                    differences.push(ElementMotionsDiffer(*element_id, motions_a, motions_b));
+                } else {
+                    for motion_id in motions_a {
+                        if motion_a.get_motion(motion_id) != motion_b.get_motion(motion_id) {
+                            differences.push(MotionDiffers(motion_id));
+                        }
+                    }
+                }
+            }
+        }
+
+        elements_a.iter().filter(|(id, _)| !ids_b.contains(id)).for_each(|(id, _)| differences.push(MissingElement(layer_id, *when, *id)));
+        elements_b.iter().filter(|(id, _)| !ids_a.contains(id)).for_each(|(id, _)| differences.push(ExtraElement(layer_id, *when, *id)));
+    }
+}
+
+///
+/// Compares the structure of two animations (their layers, keyframes, elements and motions) and returns the differences
+///
+pub fn diff_animations<AnimA, AnimB>(animation_a: &AnimA, animation_b: &AnimB) -> Vec<AnimationDifference>
+where   AnimA: ?Sized+Animation,
+        AnimB: ?Sized+Animation {
+    use self::AnimationDifference::*;
+
+    let mut differences = vec![];
+
+    // Compare the animation properties
+    if animation_a.size() != animation_b.size()                 { differences.push(Size(animation_a.size(), animation_b.size())); }
+    if animation_a.duration() != animation_b.duration()         { differences.push(Length(animation_a.duration(), animation_b.duration())); }
+    if animation_a.frame_length() != animation_b.frame_length() { differences.push(FrameLength(animation_a.frame_length(), animation_b.frame_length())); }
+
+    // Compare the layers
+    let layers_a    = animation_a.get_layer_ids();
+    let layers_b    = animation_b.get_layer_ids();
+
+    layers_a.iter().filter(|layer_id| !layers_b.contains(layer_id)).for_each(|layer_id| differences.push(MissingLayer(*layer_id)));
+    layers_b.iter().filter(|layer_id| !layers_a.contains(layer_id)).for_each(|layer_id| differences.push(ExtraLayer(*layer_id)));
+
+    for layer_id in layers_a.iter().filter(|layer_id| layers_b.contains(layer_id)) {
+        let layer_a = animation_a.get_layer_with_id(*layer_id);
+        let layer_b = animation_b.get_layer_with_id(*layer_id);
+
+        match (layer_a, layer_b) {
+            (Some(layer_a), Some(layer_b))  => diff_layer(*layer_id, &*layer_a, &*layer_b, animation_a.motion(), animation_b.motion(), &mut differences),
+            (Some(_), None)                 => differences.push(MissingLayer(*layer_id)),
+            (None, Some(_))                 => differences.push(ExtraLayer(*layer_id)),
+            (None, None)                    => { }
+        }
+    }
+
+    differences
+}
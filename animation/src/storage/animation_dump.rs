@@ -0,0 +1,467 @@
+use super::compaction::*;
+use super::file_properties::*;
+use super::super::traits::*;
+
+use std::sync::*;
+use std::collections::{HashMap, HashSet, BTreeMap};
+use std::time::{Duration};
+
+///
+/// The parts of an animation that are described by its edit log but can't be read back from the animation itself
+///
+struct EditLogDetails {
+    /// The points that were used to draw each brush stroke (the animation only stores the curve that was fitted to them)
+    brush_stroke_points: HashMap<ElementId, Arc<Vec<RawPoint>>>,
+
+    /// The elements that have had their control points moved since they were created
+    moved_control_points: HashSet<ElementId>,
+
+    /// The asset IDs of the reference images on each layer
+    reference_images: HashMap<u64, BTreeMap<Duration, u64>>
+}
+
+impl EditLogDetails {
+    ///
+    /// Reads the details from an edit log
+    ///
+    fn from_edits(edits: &[AnimationEdit]) -> EditLogDetails {
+        use self::AnimationEdit::*;
+        use self::LayerEdit::*;
+
+        let mut details = EditLogDetails {
+            brush_stroke_points:    HashMap::new(),
+            moved_control_points:   HashSet::new(),
+            reference_images:       HashMap::new()
+        };
+
+        for edit in edits.iter() {
+            match edit {
+                Layer(_, Paint(_, PaintEdit::BrushStroke(element_id, points)))  => { details.brush_stroke_points.insert(*element_id, Arc::clone(points)); }
+                Layer(layer_id, AddReferenceImage(when, asset_id))              => { details.reference_images.entry(*layer_id).or_insert_with(|| BTreeMap::new()).insert(*when, *asset_id); }
+                Layer(layer_id, RemoveReferenceImage(when))                     => { details.reference_images.get_mut(layer_id).map(|images| images.remove(when)); }
+                RemoveLayer(layer_id)                                           => { details.reference_images.remove(layer_id); }
+
+                Element(elements, ElementEdit::SetControlPoints(_, _))          => { details.moved_control_points.extend(elements.iter().cloned()); }
+
+                _                                                               => { }
+            }
+        }
+
+        details
+    }
+}
+
+///
+/// Returns the highest element ID used by an edit log
+///
+fn max_element_id(edits: &[AnimationEdit]) -> i64 {
+    use self::AnimationEdit::*;
+
+    edits.iter()
+        .flat_map(|edit| {
+            let mut element_ids = match edit {
+                Element(elements, ElementEdit::Group(group_id, _))  => elements.iter().cloned().chain(Some(*group_id)).collect(),
+                Element(elements, _)                                => elements.clone(),
+                Motion(motion_id, _)                                => vec![*motion_id],
+                Bone(bone_id, _)                                    => vec![*bone_id],
+                _                                                   => vec![]
+            };
+
+            element_ids.extend(created_element(edit).map(|(_, element_id)| element_id));
+            element_ids
+        })
+        .flat_map(|element_id| element_id.id())
+        .max()
+        .unwrap_or(0)
+}
+
+///
+/// Generates the edits that recreate the current state of an animation
+///
+struct AnimationDump<'a> {
+    /// The details read from the edit log
+    log: EditLogDetails,
+
+    /// The edits generated so far
+    edits: Vec<AnimationEdit>,
+
+    /// The edits that transform elements, which go at the end of the dump
+    transforms: Vec<AnimationEdit>,
+
+    /// The IDs of the elements that have been created by the dump so far
+    created: HashSet<ElementId>,
+
+    /// The IDs (in the original animation) of the paint brush and properties that are currently selected
+    paint_brush: (Option<ElementId>, Option<ElementId>),
+
+    /// The IDs (in the original animation) of the path brush and properties that are currently selected
+    path_brush: (Option<ElementId>, Option<ElementId>),
+
+    /// Assigns the IDs of brushes that have to be selected a second time
+    assign_element_id: &'a mut dyn FnMut() -> ElementId,
+
+    /// The motions in the animation being dumped
+    motion: &'a dyn AnimationMotion
+}
+
+///
+/// Converts the transformations attached to an element back into the edit that produces them
+///
+fn element_transforms(transformations: &[Transformation]) -> Vec<ElementTransform> {
+    use self::ElementTransform::*;
+
+    transformations.iter()
+        .flat_map(|transformation| match transformation {
+            Transformation::Translate(x, y)         => vec![SetAnchor(0.0, 0.0), MoveTo(*x, *y)],
+            Transformation::FlipHoriz(x, y)         => vec![SetAnchor(*x, *y), FlipHorizontal],
+            Transformation::FlipVert(x, y)          => vec![SetAnchor(*x, *y), FlipVertical],
+            Transformation::Scale(xr, yr, (x, y))   => vec![SetAnchor(*x, *y), Scale(*xr, *yr)],
+            Transformation::Rotate(angle, (x, y))   => vec![SetAnchor(*x, *y), Rotate(*angle)],
+
+            // Matrix transformations aren't generated by any edit
+            Transformation::Matrix(_)               => vec![]
+        })
+        .collect()
+}
+
+impl<'a> AnimationDump<'a> {
+    ///
+    /// Returns the ID to use for a brush element that's being selected: the original ID the first time, and a new ID if the
+    /// brush has to be selected again later on
+    ///
+    /// New IDs are assigned here rather than when the dump is replayed, as the animation would otherwise give out IDs that
+    /// are used by later edits in the dump.
+    ///
+    fn brush_element_id(&mut self, original_id: ElementId) -> ElementId {
+        if original_id.is_assigned() && self.created.insert(original_id) {
+            original_id
+        } else {
+            (self.assign_element_id)()
+        }
+    }
+
+    ///
+    /// Selects the brush used by a brush stroke element
+    ///
+    fn select_paint_brush(&mut self, layer_id: u64, when: Duration, frame: &dyn Frame, element_id: ElementId) {
+        for (attachment_id, _) in frame.attached_elements(element_id) {
+            match frame.element_with_id(attachment_id) {
+                Some(Vector::BrushDefinition(defn)) => {
+                    if self.paint_brush.0 != Some(attachment_id) {
+                        let brush_id = self.brush_element_id(attachment_id);
+                        self.edits.push(AnimationEdit::Layer(layer_id, LayerEdit::Paint(when, PaintEdit::SelectBrush(brush_id, defn.definition().clone(), defn.drawing_style()))));
+                        self.paint_brush.0 = Some(attachment_id);
+                    }
+                }
+
+                Some(Vector::BrushProperties(props)) => {
+                    if self.paint_brush.1 != Some(attachment_id) {
+                        let props_id = self.brush_element_id(attachment_id);
+                        self.edits.push(AnimationEdit::Layer(layer_id, LayerEdit::Paint(when, PaintEdit::BrushProperties(props_id, props.brush_properties().clone()))));
+                        self.paint_brush.1 = Some(attachment_id);
+                    }
+                }
+
+                _ => { }
+            }
+        }
+    }
+
+    ///
+    /// Selects the brush used by a path element
+    ///
+    fn select_path_brush(&mut self, layer_id: u64, when: Duration, path: &PathElement) {
+        let defn    = path.brush();
+        let props   = path.properties();
+
+        if self.path_brush.0 != Some(defn.id()) {
+            let brush_id = self.brush_element_id(defn.id());
+            self.edits.push(AnimationEdit::Layer(layer_id, LayerEdit::Path(when, PathEdit::SelectBrush(brush_id, defn.definition().clone(), defn.drawing_style()))));
+            self.path_brush.0 = Some(defn.id());
+        }
+
+        if self.path_brush.1 != Some(props.id()) {
+            let props_id = self.brush_element_id(props.id());
+            self.edits.push(AnimationEdit::Layer(layer_id, LayerEdit::Path(when, PathEdit::BrushProperties(props_id, props.brush_properties().clone()))));
+            self.path_brush.1 = Some(props.id());
+        }
+    }
+
+    ///
+    /// Writes out the edits that create a motion, if it hasn't been created already
+    ///
+    fn create_motion(&mut self, motion_id: ElementId) {
+        if !self.created.insert(motion_id) {
+            return;
+        }
+
+        let motion = match self.motion.get_motion(motion_id) { Some(motion) => motion, None => { return; } };
+
+        self.edits.push(AnimationEdit::Motion(motion_id, MotionEdit::Create));
+
+        match motion {
+            Motion::None                    => { }
+            Motion::Translate(translate)    => {
+                self.edits.push(AnimationEdit::Motion(motion_id, MotionEdit::SetType(MotionType::Translate)));
+                self.edits.push(AnimationEdit::Motion(motion_id, MotionEdit::SetOrigin(translate.origin.0, translate.origin.1)));
+                self.edits.push(AnimationEdit::Motion(motion_id, MotionEdit::SetPath(translate.translate.clone())));
+            }
+            other                           => {
+                self.edits.push(AnimationEdit::Motion(motion_id, MotionEdit::SetType(other.motion_type())));
+            }
+        }
+    }
+
+    ///
+    /// Writes out the edits that recreate the motions, bones and transformations attached to an element
+    ///
+    fn dump_attachments(&mut self, frame: &dyn Frame, element_id: ElementId) {
+        for (attachment_id, attachment_type) in frame.attached_elements(element_id) {
+            match attachment_type {
+                VectorType::Motion          => {
+                    self.create_motion(attachment_id);
+                    self.edits.push(AnimationEdit::Element(vec![element_id], ElementEdit::AddAttachment(attachment_id)));
+                }
+
+                VectorType::Bone            => {
+                    self.edits.push(AnimationEdit::Element(vec![element_id], ElementEdit::AddAttachment(attachment_id)));
+                }
+
+                VectorType::Transformation  => {
+                    if let Some(Vector::Transformation((_, transformations))) = frame.element_with_id(attachment_id) {
+                        let transforms = element_transforms(&transformations);
+
+                        if transforms.len() > 0 {
+                            self.transforms.push(AnimationEdit::Element(vec![element_id], ElementEdit::Transform(transforms)));
+                        }
+                    }
+                }
+
+                _                           => { }
+            }
+        }
+    }
+
+    ///
+    /// Writes out the edits that create an element in a keyframe, returning its ID if it was created
+    ///
+    fn dump_element(&mut self, layer_id: u64, when: Duration, frame: &dyn Frame, element: &Vector) -> Option<ElementId> {
+        let element_id = element.id();
+
+        match element {
+            Vector::Transformed(transformed)    => { return self.dump_element(layer_id, when, frame, &*transformed.without_transformations()); }
+
+            Vector::BrushStroke(brush_stroke)   => {
+                self.select_paint_brush(layer_id, when, frame, element_id);
+
+                // The curve is fitted to the original points again, then moved to where the control points are now
+                let points = self.log.brush_stroke_points.get(&element_id).cloned();
+                let points = points.unwrap_or_else(|| Arc::new(brush_stroke.points().iter().map(|point| RawPoint::from(point.position)).collect()));
+
+                self.edits.push(AnimationEdit::Layer(layer_id, LayerEdit::Paint(when, PaintEdit::BrushStroke(element_id, points))));
+
+                if !self.log.brush_stroke_points.contains_key(&element_id) || self.log.moved_control_points.contains(&element_id) {
+                    let control_points = brush_stroke.control_points(&VectorProperties::default()).into_iter().map(|cp| cp.position()).collect();
+                    self.edits.push(AnimationEdit::Element(vec![element_id], ElementEdit::SetControlPoints(control_points, when)));
+                }
+            }
+
+            Vector::Path(path)                  => {
+                self.select_path_brush(layer_id, when, path);
+                self.edits.push(AnimationEdit::Layer(layer_id, LayerEdit::Path(when, PathEdit::CreatePath(element_id, Arc::clone(&path.path().elements)))));
+            }
+
+            Vector::Group(group)                => {
+                let grouped_elements = group.elements()
+                    .flat_map(|grouped_element| self.dump_element(layer_id, when, frame, grouped_element))
+                    .collect::<Vec<_>>();
+
+                self.edits.push(AnimationEdit::Element(grouped_elements, ElementEdit::Group(element_id, group.group_type())));
+            }
+
+            Vector::Symbol(symbol)              => {
+                self.edits.push(AnimationEdit::Layer(layer_id, LayerEdit::CreateSymbolInstance(when, element_id, symbol.instance().clone())));
+            }
+
+            // Other elements are only used as attachments, and are written out along with the elements they're attached to
+            _                                   => { return None; }
+        }
+
+        self.dump_attachments(frame, element_id);
+
+        Some(element_id)
+    }
+
+    ///
+    /// Writes out the bones used by the animation
+    ///
+    fn dump_bones(&mut self, bones: BTreeMap<ElementId, Arc<Bone>>) {
+        // Create all of the bones before connecting them so the parents are always available
+        for (bone_id, bone) in bones.iter() {
+            self.created.insert(*bone_id);
+
+            self.edits.push(AnimationEdit::Bone(*bone_id, BoneEdit::Create));
+            self.edits.push(AnimationEdit::Bone(*bone_id, BoneEdit::SetOrigin(bone.origin.0, bone.origin.1)));
+            self.edits.push(AnimationEdit::Bone(*bone_id, BoneEdit::SetLength(bone.length)));
+            self.edits.push(AnimationEdit::Bone(*bone_id, BoneEdit::SetAngle(bone.angle)));
+
+            for (when, rotation) in bone.rotations.iter() {
+                self.edits.push(AnimationEdit::Bone(*bone_id, BoneEdit::SetRotation(*when, *rotation)));
+            }
+        }
+
+        for (bone_id, bone) in bones.iter() {
+            if bone.parent.is_some() {
+                self.edits.push(AnimationEdit::Bone(*bone_id, BoneEdit::SetParent(bone.parent)));
+            }
+        }
+    }
+
+    ///
+    /// Writes out the properties of a layer
+    ///
+    fn dump_layer_properties(&mut self, layer_id: u64, layer: &dyn Layer) {
+        let name = layer.name().unwrap_or_else(|| String::new());
+
+        if name != ""                                       { self.edits.push(AnimationEdit::Layer(layer_id, LayerEdit::SetName(name))); }
+        if let Some(reference) = layer.reference_settings() { self.edits.push(AnimationEdit::Layer(layer_id, LayerEdit::SetReference(Some(reference)))); }
+        if layer.is_hidden()                                { self.edits.push(AnimationEdit::Layer(layer_id, LayerEdit::SetHidden(true))); }
+        if layer.is_locked()                                { self.edits.push(AnimationEdit::Layer(layer_id, LayerEdit::SetLocked(true))); }
+        if layer.is_collapsed()                             { self.edits.push(AnimationEdit::Layer(layer_id, LayerEdit::SetCollapsed(true))); }
+    }
+
+    ///
+    /// Writes out the keyframes, elements and reference images of a layer
+    ///
+    fn dump_layer_content(&mut self, layer_id: u64, layer: &dyn Layer) {
+        for when in layer.get_key_frames() {
+            self.edits.push(AnimationEdit::Layer(layer_id, LayerEdit::AddKeyFrame(when)));
+
+            if let Some(tween) = layer.key_frame_tween(when) {
+                self.edits.push(AnimationEdit::Layer(layer_id, LayerEdit::SetKeyFrameTween(when, Some(tween))));
+            }
+
+            // Brushes are selected again in each keyframe so the elements are always attached to brushes in the same keyframe
+            self.paint_brush    = (None, None);
+            self.path_brush     = (None, None);
+
+            let frame           = layer.get_frame_at_time(when);
+            let elements        = frame.vector_elements().map(|elements| elements.collect::<Vec<_>>()).unwrap_or_else(|| vec![]);
+
+            for element in elements.iter() {
+                self.dump_element(layer_id, when, &*frame, element);
+            }
+        }
+
+        let reference_images = self.log.reference_images.get(&layer_id).cloned().unwrap_or_else(|| BTreeMap::new());
+        for (when, asset_id) in reference_images {
+            self.edits.push(AnimationEdit::Layer(layer_id, LayerEdit::AddReferenceImage(when, asset_id)));
+        }
+    }
+}
+
+///
+/// Generates a set of edits that recreates the current state of an animation
+///
+/// The edit log of the animation is needed as well as the animation itself: brush strokes are recreated from the points
+/// they were drawn with and reference images from the assets they were imported as, which the animation doesn't store.
+/// Everything else is read from the animation. The edits can be serialized with `serialize_animation_as_edits()`.
+///
+/// Any new elements in the dump are given IDs after the highest one used in the edit log.
+///
+pub fn dump_animation_as_edits<Anim: ?Sized+Animation>(animation: &Anim, edit_log: &[AnimationEdit]) -> Vec<AnimationEdit> {
+    let mut last_element_id = max_element_id(edit_log);
+
+    dump_animation_with_element_ids(animation, edit_log, move || {
+        last_element_id += 1;
+        ElementId::Assigned(last_element_id)
+    })
+}
+
+///
+/// As for `dump_animation_as_edits()`, except the IDs for any new elements are assigned by a function
+///
+/// The transformations are performed at the end of the dump: these each create a new element when they're replayed, with
+/// an ID following the highest one in the animation at that point.
+///
+pub fn dump_animation_with_element_ids<Anim: ?Sized+Animation, AssignIdFn: FnMut() -> ElementId>(animation: &Anim, edit_log: &[AnimationEdit], mut assign_element_id: AssignIdFn) -> Vec<AnimationEdit> {
+    let mut dump        = AnimationDump {
+        log:                EditLogDetails::from_edits(edit_log),
+        edits:              vec![],
+        transforms:         vec![],
+        created:            HashSet::new(),
+        paint_brush:        (None, None),
+        path_brush:         (None, None),
+        assign_element_id:  &mut assign_element_id,
+        motion:             animation.motion()
+    };
+
+    // Animations start at the default size
+    let (width, height) = animation.size();
+    if (width, height) != FileProperties::default().size {
+        dump.edits.push(AnimationEdit::SetSize(width, height));
+    }
+
+    // Read the layers, including the ones that belong to symbols
+    let layer_ids       = animation.get_layer_ids();
+    let symbols         = animation.get_symbol_ids().into_iter()
+        .flat_map(|symbol_id| animation.get_symbol_with_id(symbol_id))
+        .collect::<Vec<_>>();
+    let all_layers      = layer_ids.iter().cloned()
+        .chain(symbols.iter().flat_map(|symbol| symbol.get_layer_ids()))
+        .flat_map(|layer_id| animation.get_layer_with_id(layer_id).map(|layer| (layer_id, layer)))
+        .collect::<Vec<_>>();
+
+    // Bones are shared between keyframes, so they're all created first
+    let mut bones = BTreeMap::new();
+    for (_, layer) in all_layers.iter() {
+        for when in layer.get_key_frames() {
+            for bone in layer.get_frame_at_time(when).bones() {
+                bones.insert(bone.id(), bone.bone());
+            }
+        }
+    }
+    dump.dump_bones(bones);
+
+    // Symbols are created before the layers, which can contain instances of them
+    for symbol in symbols.iter() {
+        let symbol_id = symbol.id();
+
+        dump.edits.push(AnimationEdit::AddNewSymbol(symbol_id));
+        dump.edits.push(AnimationEdit::Symbol(symbol_id, SymbolEdit::SetName(symbol.name())));
+        dump.edits.push(AnimationEdit::Symbol(symbol_id, SymbolEdit::SetLength(symbol.length())));
+
+        for layer_id in symbol.get_layer_ids() {
+            dump.edits.push(AnimationEdit::Symbol(symbol_id, SymbolEdit::AddNewLayer(layer_id)));
+        }
+    }
+
+    // Create the layers in order, then put them in their folders
+    for layer_id in layer_ids.iter() {
+        let layer = match animation.get_layer_with_id(*layer_id) { Some(layer) => layer, None => { continue; } };
+
+        if layer.supported_edit_types().contains(&LayerEditType::Folder) {
+            dump.edits.push(AnimationEdit::AddNewLayerFolder(*layer_id));
+        } else {
+            dump.edits.push(AnimationEdit::AddNewLayer(*layer_id));
+        }
+    }
+
+    for (layer_id, layer) in all_layers.iter() {
+        if let Some(parent_id) = layer.parent_folder() {
+            dump.edits.push(AnimationEdit::Layer(*layer_id, LayerEdit::SetParent(Some(parent_id))));
+        }
+    }
+
+    // Write out the properties and content of each layer
+    for (layer_id, layer) in all_layers.iter() {
+        dump.dump_layer_properties(*layer_id, &**layer);
+        dump.dump_layer_content(*layer_id, &**layer);
+    }
+
+    // Transformations are written last: they create new elements, so this ensures their IDs can't clash with an element from the dump
+    let AnimationDump { mut edits, transforms, .. } = dump;
+    edits.extend(transforms);
+
+    edits
+}
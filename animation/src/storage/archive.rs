@@ -0,0 +1,389 @@
+use super::compaction::*;
+use super::animation_diff::*;
+use super::super::traits::*;
+use super::super::serializer::*;
+
+use futures::prelude::*;
+
+use std::fmt;
+use std::sync::*;
+use std::collections::{HashMap, HashSet};
+use std::fmt::{Display, Formatter};
+use std::time::{Duration};
+
+///
+/// The text that starts the header line of an archive
+///
+pub const ARCHIVE_MAGIC: &str = "FLO-ARCHIVE";
+
+///
+/// The version of the archive format written by this version of FlowBetween
+///
+pub const ARCHIVE_VERSION: usize = 1;
+
+///
+/// An element as it appeared in a keyframe of an archived animation
+///
+#[derive(Clone, PartialEq, Debug)]
+pub struct ArchivedElement {
+    /// The layer containing the element
+    pub layer_id: u64,
+
+    /// The time of the keyframe containing the element
+    pub keyframe: Duration,
+
+    /// The ID of the element
+    pub element_id: ElementId,
+
+    /// The element in serialized form
+    pub serialized: String
+}
+
+///
+/// A self-contained copy of an animation that can be written to a single file
+///
+/// An archive is a text file. The first line is the header, `FLO-ARCHIVE <version>`, where the version is a decimal number
+/// (currently 1). Every other line is a record: the first character identifies the type of record and the rest of the line
+/// contains its data in the format used by `AnimationDataTarget`. Blank lines are ignored, and readers should ignore any
+/// records they don't recognise so that new record types can be added without changing the version. The records are:
+///
+/// * `N` - the name of the animation (a string)
+/// * `P` - the animation properties: width and height (f64s), then the duration and frame length
+/// * `L` - a layer: its ID (a small u64), then `+` followed by its name or `-` if it has no name
+/// * `K` - an element in a keyframe: the layer ID, the keyframe time, the element ID, then the serialized element
+/// * `S` - a named snapshot: its name, then the number of edits that it contains
+/// * `A` - an asset used by the edit log: its ID (a small u64), then the length of its data (a usize) and the data
+/// * `E` - an edit from the edit log (a serialized `AnimationEdit`), in the order they should be performed
+///
+/// The edit log is the authoritative description of the animation: the properties, layers and elements are there so the
+/// archive can be inspected without replaying it, and so that an imported animation can be checked against the original.
+/// Reference images and any other assets used by the animation are stored once each, in the `A` records, and are referred
+/// to by ID from the edits that use them.
+///
+#[derive(Clone, PartialEq, Debug)]
+pub struct AnimationArchive {
+    /// The name of the archived animation, if it has one
+    pub name: Option<String>,
+
+    /// The size of the animation
+    pub size: (f64, f64),
+
+    /// The length of the animation
+    pub duration: Duration,
+
+    /// The length of a frame in the animation
+    pub frame_length: Duration,
+
+    /// The IDs and names of the layers in the animation
+    pub layers: Vec<(u64, Option<String>)>,
+
+    /// The elements in the keyframes of the animation
+    pub elements: Vec<ArchivedElement>,
+
+    /// The named snapshots of the animation
+    pub snapshots: Vec<EditLogCheckpoint>,
+
+    /// The assets used by the edit log
+    pub assets: Vec<(u64, Arc<Vec<u8>>)>,
+
+    /// The edit log for the animation
+    pub edits: Vec<AnimationEdit>
+}
+
+///
+/// Errors that can occur while reading an archive
+///
+#[derive(Clone, PartialEq, Debug)]
+pub enum ArchiveError {
+    /// The data does not start with an archive header
+    MissingHeader,
+
+    /// The archive was written with a newer version of the format than this version of FlowBetween can read
+    UnsupportedVersion(usize),
+
+    /// The record on the specified line could not be read
+    BadRecord(usize)
+}
+
+impl Display for ArchiveError {
+    fn fmt(&self, fmt: &mut Formatter) -> Result<(), fmt::Error> {
+        use self::ArchiveError::*;
+
+        match self {
+            MissingHeader               => write!(fmt, "Not a FlowBetween archive"),
+            UnsupportedVersion(version) => write!(fmt, "Archive version {} is not supported (the latest supported version is {})", version, ARCHIVE_VERSION),
+            BadRecord(line)             => write!(fmt, "{}: could not read archive record", line)
+        }
+    }
+}
+
+impl AnimationArchive {
+    ///
+    /// Creates an archive from an existing animation
+    ///
+    /// If `compact` is true, the edit log is compacted before it's stored in the archive (the snapshots of the animation are preserved)
+    ///
+    pub fn from_animation<'a, Anim: ?Sized+Animation>(animation: &'a Anim, name: Option<String>, compact: bool) -> impl 'a+Future<Output=AnimationArchive> {
+        async move {
+            // Read the edit log and the snapshots
+            let num_edits   = animation.get_num_edits();
+            let edits       = animation.read_edit_log(0..num_edits).collect::<Vec<_>>().await;
+            let snapshots   = animation.get_snapshots();
+
+            let (edits, snapshots) = if compact {
+                let compacted = compact_animation_edits(animation, &edits, &snapshots);
+                (compacted.edits, compacted.checkpoints)
+            } else {
+                (edits, snapshots)
+            };
+
+            // Read the assets used by the edits
+            let mut asset_ids   = HashSet::new();
+            let mut assets      = vec![];
+
+            for asset_id in edits.iter().flat_map(|edit| edit.asset_id()) {
+                if asset_ids.insert(asset_id) {
+                    if let Some(asset) = animation.get_asset(asset_id) {
+                        assets.push((asset_id, asset));
+                    }
+                }
+            }
+
+            // Read the layers and the elements in their keyframes
+            let mut layers      = vec![];
+            let mut elements    = vec![];
+
+            for layer_id in animation.get_layer_ids() {
+                let layer = match animation.get_layer_with_id(layer_id) { Some(layer) => layer, None => continue };
+
+                layers.push((layer_id, layer.name()));
+
+                for keyframe in layer.get_key_frames() {
+                    let frame = layer.get_frame_at_time(keyframe);
+
+                    elements.extend(serialized_elements(&*frame).into_iter()
+                        .map(|(element_id, serialized)| ArchivedElement { layer_id, keyframe, element_id, serialized }));
+                }
+            }
+
+            AnimationArchive {
+                name:           name,
+                size:           animation.size(),
+                duration:       animation.duration(),
+                frame_length:   animation.frame_length(),
+                layers:         layers,
+                elements:       elements,
+                snapshots:      snapshots,
+                assets:         assets,
+                edits:          edits
+            }
+        }
+    }
+
+    ///
+    /// Writes this archive to a string
+    ///
+    pub fn serialize(&self, data: &mut String) {
+        // Header
+        format!("{} {}", ARCHIVE_MAGIC, ARCHIVE_VERSION).chars().for_each(|chr| data.write_chr(chr));
+        data.write_chr('\n');
+
+        // Name and properties
+        if let Some(name) = &self.name {
+            data.write_chr('N');
+            data.write_str(name);
+            data.write_chr('\n');
+        }
+
+        data.write_chr('P');
+        data.write_f64(self.size.0);
+        data.write_f64(self.size.1);
+        data.write_duration(self.duration);
+        data.write_duration(self.frame_length);
+        data.write_chr('\n');
+
+        // Layers
+        for (layer_id, name) in self.layers.iter() {
+            data.write_chr('L');
+            data.write_small_u64(*layer_id);
+            match name {
+                Some(name)  => { data.write_chr('+'); data.write_str(name); }
+                None        => { data.write_chr('-'); }
+            }
+            data.write_chr('\n');
+        }
+
+        // Elements
+        for element in self.elements.iter() {
+            data.write_chr('K');
+            data.write_small_u64(element.layer_id);
+            data.write_duration(element.keyframe);
+            element.element_id.serialize(data);
+            element.serialized.chars().for_each(|chr| data.write_chr(chr));
+            data.write_chr('\n');
+        }
+
+        // Snapshots
+        for snapshot in self.snapshots.iter() {
+            data.write_chr('S');
+            data.write_str(&snapshot.name);
+            data.write_usize(snapshot.edit_index);
+            data.write_chr('\n');
+        }
+
+        // Assets
+        for (asset_id, asset) in self.assets.iter() {
+            data.write_chr('A');
+            data.write_small_u64(*asset_id);
+            data.write_usize(asset.len());
+            data.write_bytes(asset);
+            data.write_chr('\n');
+        }
+
+        // Edit log
+        for edit in self.edits.iter() {
+            data.write_chr('E');
+            edit.serialize(data);
+            data.write_chr('\n');
+        }
+    }
+
+    ///
+    /// Reads an archive from its serialized form
+    ///
+    pub fn deserialize(data: &str) -> Result<AnimationArchive, ArchiveError> {
+        let mut lines = data.lines().enumerate();
+
+        // Check the header
+        let header  = lines.next().map(|(_, header)| header).ok_or(ArchiveError::MissingHeader)?;
+        let mut header_parts = header.trim().split(' ');
+
+        if header_parts.next() != Some(ARCHIVE_MAGIC) { return Err(ArchiveError::MissingHeader); }
+        let version = header_parts.next().and_then(|version| version.parse::<usize>().ok()).ok_or(ArchiveError::MissingHeader)?;
+        if version > ARCHIVE_VERSION { return Err(ArchiveError::UnsupportedVersion(version)); }
+
+        // Read the records
+        let mut archive = AnimationArchive {
+            name:           None,
+            size:           (0.0, 0.0),
+            duration:       Duration::from_millis(0),
+            frame_length:   Duration::from_millis(0),
+            layers:         vec![],
+            elements:       vec![],
+            snapshots:      vec![],
+            assets:         vec![],
+            edits:          vec![]
+        };
+
+        for (line_num, line) in lines {
+            let mut record = line.chars();
+
+            match record.next() {
+                None        => { }
+                Some('N')   => { archive.name = Some(record.next_string()); }
+
+                Some('P')   => {
+                    archive.size            = (record.next_f64(), record.next_f64());
+                    archive.duration        = record.next_duration();
+                    archive.frame_length    = record.next_duration();
+                }
+
+                Some('L')   => {
+                    let layer_id    = record.next_small_u64();
+                    let name        = match record.next_chr() {
+                        '+' => Some(record.next_string()),
+                        '-' => None,
+                        _   => return Err(ArchiveError::BadRecord(line_num+1))
+                    };
+
+                    archive.layers.push((layer_id, name));
+                }
+
+                Some('K')   => {
+                    let layer_id    = record.next_small_u64();
+                    let keyframe    = record.next_duration();
+                    let element_id  = ElementId::deserialize(&mut record).ok_or(ArchiveError::BadRecord(line_num+1))?;
+                    let serialized  = record.as_str().to_string();
+
+                    archive.elements.push(ArchivedElement { layer_id, keyframe, element_id, serialized });
+                }
+
+                Some('S')   => {
+                    let name        = record.next_string();
+                    let edit_index  = record.next_usize();
+
+                    archive.snapshots.push(EditLogCheckpoint { name, edit_index });
+                }
+
+                Some('A')   => {
+                    let asset_id    = record.next_small_u64();
+                    let len         = record.next_usize();
+                    let asset       = record.next_bytes(len);
+
+                    archive.assets.push((asset_id, Arc::new(asset.into_vec())));
+                }
+
+                Some('E')   => {
+                    let edit = AnimationEdit::deserialize(&mut record).ok_or(ArchiveError::BadRecord(line_num+1))?;
+                    archive.edits.push(edit);
+                }
+
+                // Unknown records are skipped
+                Some(_)     => { }
+            }
+        }
+
+        Ok(archive)
+    }
+
+    ///
+    /// Performs the edits in this archive on an animation, which should usually be empty, and recreates its snapshots
+    ///
+    pub fn restore<Target: ?Sized+EditableAnimation>(&self, target: &Target) {
+        // The assets need to be available before the edits that use them are performed
+        for (asset_id, asset) in self.assets.iter() {
+            target.write_asset(*asset_id, Arc::clone(asset));
+        }
+
+        let mut snapshots = self.snapshots.clone();
+        snapshots.sort_by_key(|snapshot| snapshot.edit_index);
+
+        // Perform the edits, stopping to create each snapshot as its position in the edit log is reached
+        let mut next_edit = 0;
+        for snapshot in snapshots {
+            let edit_index = snapshot.edit_index.min(self.edits.len());
+
+            if edit_index > next_edit {
+                target.perform_edits(self.edits[next_edit..edit_index].to_vec());
+                next_edit = edit_index;
+            }
+
+            target.create_snapshot(&snapshot.name);
+        }
+
+        if next_edit < self.edits.len() {
+            target.perform_edits(self.edits[next_edit..].to_vec());
+        }
+    }
+
+    ///
+    /// Returns the elements from the archive that are missing or different in an animation (eg, after restoring the archive)
+    ///
+    pub fn find_mismatched_elements<Anim: ?Sized+Animation>(&self, animation: &Anim) -> Vec<ArchivedElement> {
+        // Cache of the elements in each keyframe of the animation
+        let mut keyframes = HashMap::new();
+
+        self.elements.iter()
+            .filter(|element| {
+                let elements = keyframes.entry((element.layer_id, element.keyframe))
+                    .or_insert_with(|| {
+                        let frame = animation.get_layer_with_id(element.layer_id).map(|layer| layer.get_frame_at_time(element.keyframe));
+                        frame.map(|frame| serialized_elements(&*frame)).unwrap_or_else(|| vec![])
+                    });
+
+                !elements.iter().any(|(element_id, serialized)| *element_id == element.element_id && *serialized == element.serialized)
+            })
+            .cloned()
+            .collect()
+    }
+}
@@ -0,0 +1,472 @@
+use super::animation_dump::*;
+use super::super::traits::*;
+
+use futures::prelude::*;
+
+use std::collections::{HashMap, HashSet};
+
+///
+/// The result of compacting an edit log
+///
+#[derive(Clone, PartialEq, Debug)]
+pub struct CompactedEditLog {
+    /// The edits in the compacted log
+    pub edits: Vec<AnimationEdit>,
+
+    /// The checkpoints with their indexes updated to refer to the compacted log
+    pub checkpoints: Vec<EditLogCheckpoint>
+}
+
+///
+/// Returns the layer and ID of the element created by an edit, if it creates a new element in a frame
+///
+pub (super) fn created_element(edit: &AnimationEdit) -> Option<(u64, ElementId)> {
+    use self::AnimationEdit::*;
+    use self::LayerEdit::*;
+
+    match edit {
+        Layer(layer_id, Paint(_, PaintEdit::BrushStroke(element_id, _)))    => Some((*layer_id, *element_id)),
+        Layer(layer_id, Paint(_, PaintEdit::Fill(element_id, _, _)))        => Some((*layer_id, *element_id)),
+        Layer(layer_id, Path(_, PathEdit::CreatePath(element_id, _)))       => Some((*layer_id, *element_id)),
+        Layer(layer_id, CreateSymbolInstance(_, element_id, _))             => Some((*layer_id, *element_id)),
+        Layer(layer_id, Paint(_, PaintEdit::SelectBrush(element_id, _, _))) => Some((*layer_id, *element_id)),
+        Layer(layer_id, Paint(_, PaintEdit::BrushProperties(element_id, _)))=> Some((*layer_id, *element_id)),
+        Layer(layer_id, Path(_, PathEdit::SelectBrush(element_id, _, _)))   => Some((*layer_id, *element_id)),
+        Layer(layer_id, Path(_, PathEdit::BrushProperties(element_id, _)))  => Some((*layer_id, *element_id)),
+        _                                                                   => None
+    }
+}
+
+///
+/// Returns the element IDs referenced by an element edit other than the elements it's being applied to
+///
+fn element_edit_references(edit: &ElementEdit) -> Option<ElementId> {
+    use self::ElementEdit::*;
+
+    match edit {
+        AddAttachment(element_id)               => Some(*element_id),
+        RemoveAttachment(element_id)            => Some(*element_id),
+        Order(ElementOrdering::Before(before))  => Some(*before),
+        Group(group_id, _)                      => Some(*group_id),
+        _                                       => None
+    }
+}
+
+///
+/// True if a deleted element can be removed from the log when the specified edit was performed between its creation
+/// and its deletion
+///
+fn can_skip_over(edit: &AnimationEdit, element_id: ElementId, element_layer: u64, element_layers: &HashMap<ElementId, u64>) -> bool {
+    use self::AnimationEdit::*;
+    use self::LayerEdit::*;
+
+    match edit {
+        // Edits to other layers can't interact with the element
+        Layer(layer_id, _) if *layer_id != element_layer            => true,
+
+        // Changing the brush or the layer properties doesn't interact with existing elements
+        Layer(_, Paint(_, PaintEdit::SelectBrush(_, _, _)))         |
+        Layer(_, Paint(_, PaintEdit::BrushProperties(_, _)))        |
+        Layer(_, Path(_, PathEdit::SelectBrush(_, _, _)))           |
+        Layer(_, Path(_, PathEdit::BrushProperties(_, _)))          |
+        Layer(_, SetName(_))                                        |
+        Layer(_, SetOrdering(_))                                    |
+        Layer(_, SetParent(_))                                      |
+        Layer(_, SetHidden(_))                                      |
+        Layer(_, SetLocked(_))                                      |
+        Layer(_, SetCollapsed(_))                                   |
+        Layer(_, SetKeyFrameTween(_, _))                            |
+        Layer(_, SetReference(_))                                   |
+        Layer(_, AddReferenceImage(_, _))                           |
+        Layer(_, RemoveReferenceImage(_))                           => true,
+
+        // Anything else on the same layer (new elements, fills, keyframe changes) might depend on the element
+        Layer(_, _)                                                 => false,
+
+        // Edits to just this element are discarded along with it, provided they don't combine it with anything else
+        Element(elements, edit) if elements == &vec![element_id]    => {
+            match edit {
+                ElementEdit::SetControlPoints(_, _) |
+                ElementEdit::SetPath(_)             |
+                ElementEdit::Order(_)               |
+                ElementEdit::AddAttachment(_)       |
+                ElementEdit::RemoveAttachment(_)    |
+                ElementEdit::Transform(_)           |
+                ElementEdit::SetSymbolInstance(_)   |
+                ElementEdit::Delete                 => true,
+                _                                   => false
+            }
+        }
+
+        // Edits to other elements are fine provided that they don't refer to this one and can't combine with it
+        Element(elements, edit)                                     => {
+            if elements.contains(&element_id) || element_edit_references(edit) == Some(element_id) {
+                false
+            } else if edit == &ElementEdit::CollideWithExistingElements {
+                elements.iter().all(|other| element_layers.get(other).map(|layer| *layer != element_layer).unwrap_or(false))
+            } else {
+                true
+            }
+        }
+
+        // Motions, bones, the animation size, symbol settings and adding other layers don't interact with elements
+        Motion(_, _)                                                => true,
+        Bone(_, _)                                                  => true,
+        SetSize(_, _)                                               => true,
+        AddNewLayer(_)                                              => true,
+        AddNewLayerFolder(_)                                        => true,
+        RemoveLayer(layer_id)                                       => *layer_id != element_layer,
+        AddNewSymbol(_)                                             => true,
+        Symbol(_, _)                                                => true,
+
+        // Removing a symbol removes its layers, which might include the element's layer
+        RemoveSymbol(_)                                             => false
+    }
+}
+
+///
+/// Removes elements that were created and then deleted within a set of edits, provided that nothing else could
+/// have depended on them
+///
+fn remove_deleted_elements(edits: &mut Vec<Option<AnimationEdit>>) {
+    // Find where each element was created
+    let mut created_at      = HashMap::new();
+    let mut element_layers  = HashMap::new();
+
+    for (index, edit) in edits.iter().enumerate() {
+        if let Some((layer_id, element_id)) = edit.as_ref().and_then(created_element) {
+            if element_id.is_assigned() && !created_at.contains_key(&element_id) {
+                created_at.insert(element_id, index);
+                element_layers.insert(element_id, layer_id);
+            }
+        }
+    }
+
+    // Look for deletions
+    for delete_index in 0..edits.len() {
+        let deleted_elements = match &edits[delete_index] {
+            Some(AnimationEdit::Element(elements, ElementEdit::Delete)) => elements.clone(),
+            _                                                           => { continue; }
+        };
+
+        // Find the elements that can be removed from the log entirely
+        let mut removed = HashSet::new();
+
+        for element_id in deleted_elements.iter() {
+            let create_index    = match created_at.get(element_id) { Some(index) => *index, None => { continue; } };
+            let layer_id        = element_layers[element_id];
+
+            if create_index > delete_index || edits[create_index].is_none() {
+                continue;
+            }
+
+            let can_remove      = edits[(create_index+1)..delete_index].iter()
+                .flat_map(|edit| edit.iter())
+                .all(|edit| can_skip_over(edit, *element_id, layer_id, &element_layers));
+
+            if can_remove {
+                removed.insert(*element_id);
+            }
+        }
+
+        if removed.len() == 0 {
+            continue;
+        }
+
+        // Remove the edits that create or alter the removed elements
+        for index in 0..delete_index {
+            let remove_edit = match &edits[index] {
+                Some(AnimationEdit::Element(elements, _))   => elements.len() == 1 && removed.contains(&elements[0]),
+                Some(edit)                                  => created_element(edit).map(|(_, element_id)| removed.contains(&element_id)).unwrap_or(false),
+                None                                        => false
+            };
+
+            if remove_edit {
+                edits[index] = None;
+            }
+        }
+
+        // Remove the elements from the delete edit
+        let remaining_elements = deleted_elements.into_iter().filter(|element_id| !removed.contains(element_id)).collect::<Vec<_>>();
+        edits[delete_index] = if remaining_elements.len() == 0 {
+            None
+        } else {
+            Some(AnimationEdit::Element(remaining_elements, ElementEdit::Delete))
+        };
+    }
+}
+
+///
+/// Identifies edits that set a property to an absolute value, which are superseded by later edits with the same key
+///
+#[derive(Clone, PartialEq, Eq, Hash)]
+enum PropertyKey {
+    Size,
+    LayerName(u64),
+    LayerReference(u64),
+    LayerHidden(u64),
+    LayerLocked(u64),
+    LayerCollapsed(u64),
+    PaintBrush(u64, std::time::Duration),
+    PaintBrushProperties(u64, std::time::Duration),
+    PathBrush(u64, std::time::Duration),
+    PathBrushProperties(u64, std::time::Duration),
+    ControlPoints(Vec<ElementId>, std::time::Duration),
+    ElementPath(Vec<ElementId>),
+    MotionType(ElementId),
+    MotionOrigin(ElementId),
+    MotionPath(ElementId)
+}
+
+///
+/// Returns the property that an edit sets, if it sets a property that's overwritten by subsequent edits of the same type
+///
+fn property_key(edit: &AnimationEdit) -> Option<PropertyKey> {
+    use self::AnimationEdit::*;
+    use self::LayerEdit::*;
+
+    match edit {
+        SetSize(_, _)                                                   => Some(PropertyKey::Size),
+        Layer(layer_id, SetName(_))                                     => Some(PropertyKey::LayerName(*layer_id)),
+        Layer(layer_id, SetReference(_))                                => Some(PropertyKey::LayerReference(*layer_id)),
+        Layer(layer_id, SetHidden(_))                                   => Some(PropertyKey::LayerHidden(*layer_id)),
+        Layer(layer_id, SetLocked(_))                                   => Some(PropertyKey::LayerLocked(*layer_id)),
+        Layer(layer_id, SetCollapsed(_))                                => Some(PropertyKey::LayerCollapsed(*layer_id)),
+        Layer(layer_id, Paint(when, PaintEdit::SelectBrush(_, _, _)))   => Some(PropertyKey::PaintBrush(*layer_id, *when)),
+        Layer(layer_id, Paint(when, PaintEdit::BrushProperties(_, _)))  => Some(PropertyKey::PaintBrushProperties(*layer_id, *when)),
+        Layer(layer_id, Path(when, PathEdit::SelectBrush(_, _, _)))     => Some(PropertyKey::PathBrush(*layer_id, *when)),
+        Layer(layer_id, Path(when, PathEdit::BrushProperties(_, _)))    => Some(PropertyKey::PathBrushProperties(*layer_id, *when)),
+        Element(elements, ElementEdit::SetControlPoints(_, when))       => Some(PropertyKey::ControlPoints(elements.clone(), *when)),
+        Element(elements, ElementEdit::SetPath(_))                      => Some(PropertyKey::ElementPath(elements.clone())),
+        Motion(motion_id, MotionEdit::SetType(_))                       => Some(PropertyKey::MotionType(*motion_id)),
+        Motion(motion_id, MotionEdit::SetOrigin(_, _))                  => Some(PropertyKey::MotionOrigin(*motion_id)),
+        Motion(motion_id, MotionEdit::SetPath(_))                       => Some(PropertyKey::MotionPath(*motion_id)),
+        _                                                               => None
+    }
+}
+
+///
+/// True if an edit might read the value of a property (so an earlier value of the property must be kept)
+///
+fn depends_on_property(edit: &AnimationEdit, key: &PropertyKey) -> bool {
+    use self::AnimationEdit::*;
+    use self::LayerEdit::*;
+    use self::PropertyKey::*;
+
+    match key {
+        // Nothing reads the size or the layer properties
+        Size                            |
+        LayerName(_)                    |
+        LayerReference(_)               |
+        LayerHidden(_)                  |
+        LayerLocked(_)                  |
+        LayerCollapsed(_)               => false,
+
+        // Brush settings are used by the edits that create elements. The brush elements are stored in the keyframe, but
+        // new elements are attached to the most recently selected brush on any layer, so edits on every layer depend on them
+        PaintBrush(_, _)                |
+        PaintBrushProperties(_, _)      => match edit { Layer(_, Paint(_, PaintEdit::BrushStroke(_, _))) | Layer(_, Paint(_, PaintEdit::Fill(_, _, _))) => true, _ => false },
+        PathBrush(_, _)                 |
+        PathBrushProperties(_, _)       => match edit { Layer(_, Path(_, PathEdit::CreatePath(_, _))) => true, _ => false },
+
+        // The shape of an element is used by anything that creates new elements or refers to the element
+        ControlPoints(elements, _)      |
+        ElementPath(elements)           => match edit {
+            Layer(_, Paint(_, _))       |
+            Layer(_, Path(_, _))        => created_element(edit).is_some(),
+            Layer(_, _)                 => false,
+            Element(other_elements, other_edit) => {
+                other_edits_element(elements, other_elements, other_edit)
+            }
+            Motion(_, _)                => true,
+            Bone(_, _)                  => true,
+            _                           => false
+        },
+
+        // Motions affect any element edit (as those can depend on where the elements are at a particular time)
+        MotionType(_)                   |
+        MotionOrigin(_)                 |
+        MotionPath(_)                   => match edit { Element(_, _) => true, _ => false }
+    }
+}
+
+///
+/// True if an element edit refers to any of a set of elements
+///
+fn other_edits_element(elements: &Vec<ElementId>, other_elements: &Vec<ElementId>, other_edit: &ElementEdit) -> bool {
+    if other_edit == &ElementEdit::CollideWithExistingElements {
+        return true;
+    }
+
+    other_elements.iter().any(|other| elements.contains(other))
+        || element_edit_references(other_edit).map(|other| elements.contains(&other)).unwrap_or(false)
+}
+
+///
+/// Removes edits that set a property that is set again later on before anything can depend on its value
+///
+fn remove_superseded_properties(edits: &mut Vec<Option<AnimationEdit>>) {
+    // The index of the last edit to set each property that's not been used by anything yet
+    let mut unused_properties: HashMap<PropertyKey, usize> = HashMap::new();
+
+    for index in 0..edits.len() {
+        let edit = match &edits[index] { Some(edit) => edit.clone(), None => { continue; } };
+
+        // Any property this edit reads is now in use, so its value can't be discarded. This includes the elements created
+        // by an edit that sets a property (such as the brush definition elements)
+        unused_properties.retain(|key, previous_index| {
+            let created = edits[*previous_index].as_ref().and_then(created_element);
+            let refers  = match (&edit, created) {
+                (AnimationEdit::Element(elements, element_edit), Some((_, element_id))) => other_edits_element(&vec![element_id], elements, element_edit),
+                _                                                                       => false
+            };
+
+            !refers && !depends_on_property(&edit, key)
+        });
+
+        // If this edit sets a property that was previously set and not used since, then the earlier edit isn't needed
+        if let Some(key) = property_key(&edit) {
+            if let Some(previous_index) = unused_properties.insert(key, index) {
+                edits[previous_index] = None;
+            }
+        }
+    }
+}
+
+///
+/// Compacts a single section of the edit log
+///
+fn compact_section(edits: &[AnimationEdit]) -> Vec<AnimationEdit> {
+    let mut edits = edits.iter().cloned().map(Some).collect::<Vec<_>>();
+
+    remove_deleted_elements(&mut edits);
+    remove_superseded_properties(&mut edits);
+
+    edits.into_iter().flatten().collect()
+}
+
+///
+/// Rewrites an edit log into a shorter one that produces the same animation
+///
+/// Elements that were drawn and then deleted are removed from the log, along with edits that set a value that was
+/// overwritten before anything used it (for example, the intermediate positions while a control point is dragged).
+/// Replaying up to any of the checkpoints in the compacted log produces the same result as it did with the original
+/// log.
+///
+/// This works on the edits alone. When the animation is available, `compact_animation_edits()` can replace the log
+/// with a dump of the animation instead, which is usually much shorter.
+///
+pub fn compact_edit_log(edits: &[AnimationEdit], checkpoints: &[EditLogCheckpoint]) -> CompactedEditLog {
+    // Split the log into sections at each of the checkpoints
+    let mut boundaries = checkpoints.iter()
+        .map(|checkpoint| checkpoint.edit_index.min(edits.len()))
+        .collect::<Vec<_>>();
+    boundaries.push(0);
+    boundaries.push(edits.len());
+    boundaries.sort();
+    boundaries.dedup();
+
+    // Compact each section, and record where the boundaries are in the new log
+    let mut compacted       = vec![];
+    let mut new_boundaries  = HashMap::new();
+    new_boundaries.insert(0, 0);
+
+    for section in boundaries.windows(2) {
+        compacted.extend(compact_section(&edits[section[0]..section[1]]));
+        new_boundaries.insert(section[1], compacted.len());
+    }
+
+    // Update the checkpoints
+    let checkpoints = checkpoints.iter()
+        .map(|checkpoint| EditLogCheckpoint {
+            name:       checkpoint.name.clone(),
+            edit_index: new_boundaries[&checkpoint.edit_index.min(edits.len())]
+        })
+        .collect();
+
+    CompactedEditLog {
+        edits:          compacted,
+        checkpoints:    checkpoints
+    }
+}
+
+///
+/// Compacts the edit log of an animation, which must produce the animation in its current state
+///
+/// The log is replaced by a dump of the animation (see `dump_animation_as_edits()`), which contains only the edits needed
+/// to recreate what's in the animation now. A dump can't recreate any earlier states, so if any of the checkpoints are
+/// before the end of the log, the log is compacted section by section with `compact_edit_log()` instead.
+///
+pub fn compact_animation_edits<Anim: ?Sized+Animation>(animation: &Anim, edits: &[AnimationEdit], checkpoints: &[EditLogCheckpoint]) -> CompactedEditLog {
+    if has_earlier_checkpoints(edits, checkpoints) {
+        return compact_edit_log(edits, checkpoints);
+    }
+
+    compacted_to_dump(dump_animation_as_edits(animation, edits), checkpoints)
+}
+
+///
+/// True if a checkpoint is before the end of an edit log (so the log can't be replaced by a dump of the animation)
+///
+fn has_earlier_checkpoints(edits: &[AnimationEdit], checkpoints: &[EditLogCheckpoint]) -> bool {
+    checkpoints.iter().any(|checkpoint| checkpoint.edit_index < edits.len())
+}
+
+///
+/// Returns the compacted version of a log that's been replaced by a dump
+///
+fn compacted_to_dump(dump: Vec<AnimationEdit>, checkpoints: &[EditLogCheckpoint]) -> CompactedEditLog {
+    // All of the checkpoints are at the end of the log, so they all move to the end of the dump
+    let checkpoints = checkpoints.iter()
+        .map(|checkpoint| EditLogCheckpoint {
+            name:       checkpoint.name.clone(),
+            edit_index: dump.len()
+        })
+        .collect();
+
+    CompactedEditLog {
+        edits:          dump,
+        checkpoints:    checkpoints
+    }
+}
+
+///
+/// Compacts the edit log of an animation in place, returning the compacted log
+///
+/// The compacted log produces the same animation as the original, so the existing animation data is left as it is and
+/// only the log itself is replaced. The snapshots stored with the animation are always preserved, in addition to any
+/// checkpoints passed in here. Edits that are made while the log is being compacted are kept after the compacted edits.
+///
+pub fn compact_animation_edit_log<'a, Anim>(animation: &'a Anim, checkpoints: Vec<EditLogCheckpoint>) -> impl 'a+Future<Output=CompactedEditLog>
+where Anim: ?Sized+EditableAnimation {
+    async move {
+        // Read the existing edit log
+        let num_edits       = animation.get_num_edits();
+        let edits           = animation.read_edit_log(0..num_edits).collect::<Vec<_>>().await;
+
+        // The stored snapshots are kept along with the requested checkpoints
+        let snapshots       = animation.get_snapshots();
+        let num_snapshots   = snapshots.len();
+        let checkpoints     = snapshots.into_iter().chain(checkpoints).collect::<Vec<_>>();
+
+        // Compact it and write it back to the animation
+        let compacted       = if has_earlier_checkpoints(&edits, &checkpoints) {
+            compact_edit_log(&edits, &checkpoints)
+        } else {
+            // Any new elements in the dump are given IDs by the animation so they can't clash with the elements from edits made later on
+            let dump = dump_animation_with_element_ids(animation, &edits, || animation.assign_element_id());
+
+            // Each transformation in the dump creates a new element when it's replayed, so the IDs for these need to be reserved too
+            for edit in dump.iter() {
+                if let AnimationEdit::Element(_, ElementEdit::Transform(_)) = edit {
+                    animation.assign_element_id();
+                }
+            }
+
+            compacted_to_dump(dump, &checkpoints)
+        };
+        animation.replace_edit_log(num_edits, compacted.edits.clone(), compacted.checkpoints[0..num_snapshots].to_vec());
+
+        compacted
+    }
+}
@@ -1,4 +1,5 @@
 use super::storage_api::*;
+use super::super::serializer::{SerializedData};
 
 use ::desync::*;
 
@@ -46,7 +47,10 @@ struct InMemoryLayerStorage {
     keyframes: Vec<InMemoryKeyFrameStorage>,
 
     /// The cached items for this layer
-    cache: Vec<InMemoryLayerCache>
+    cache: Vec<InMemoryLayerCache>,
+
+    /// The reference images for this layer, ordered by the time they're displayed from
+    reference_images: Vec<(Duration, String)>
 }
 
 ///
@@ -65,16 +69,25 @@ struct InMemoryStorageCore {
     animation_properties: Option<String>,
 
     /// The edit log
-    edit_log: Vec<String>,
+    edit_log: Vec<SerializedData>,
+
+    /// The named snapshots of the edit log
+    snapshots: Vec<(String, usize)>,
 
     /// The definitions for each element
-    elements: HashMap<i64, String>,
+    elements: HashMap<i64, SerializedData>,
 
     /// The keyframes that an element is attached to
     element_attachments: HashMap<i64, Vec<ElementAttachment>>,
 
     /// The layers
-    layers: HashMap<u64, InMemoryLayerStorage>
+    layers: HashMap<u64, InMemoryLayerStorage>,
+
+    /// The properties for each symbol
+    symbols: HashMap<u64, String>,
+
+    /// The assets used by the animation
+    assets: HashMap<u64, String>
 }
 
 ///
@@ -94,9 +107,12 @@ impl InMemoryStorage {
         let core = InMemoryStorageCore {
             animation_properties:   None,
             edit_log:               vec![],
+            snapshots:              vec![],
             elements:               HashMap::new(),
             layers:                 HashMap::new(),
-            element_attachments:    HashMap::new()
+            element_attachments:    HashMap::new(),
+            symbols:                HashMap::new(),
+            assets:                 HashMap::new()
         };
 
         // And the storage
@@ -196,6 +212,33 @@ impl InMemoryStorageCore {
                     response.push(StorageResponse::Updated); 
                 }
 
+                ReplaceEditLog(num_replaced, edits)                 => {
+                    let num_replaced    = num_replaced.min(self.edit_log.len());
+                    let later_edits     = self.edit_log.split_off(num_replaced);
+
+                    self.edit_log       = edits;
+                    self.edit_log.extend(later_edits);
+                    response.push(StorageResponse::Updated);
+                }
+
+                WriteSnapshot(name, edit_index)                     => {
+                    self.snapshots.retain(|(existing, _)| existing != &name);
+                    self.snapshots.push((name, edit_index));
+                    response.push(StorageResponse::Updated);
+                }
+
+                DeleteSnapshot(name)                                => {
+                    self.snapshots.retain(|(existing, _)| existing != &name);
+                    response.push(StorageResponse::Updated);
+                }
+
+                ReadSnapshots                                       => {
+                    let mut snapshots = self.snapshots.clone();
+                    snapshots.sort_by(|(name_a, index_a), (name_b, index_b)| index_a.cmp(index_b).then(name_a.cmp(name_b)));
+
+                    response.extend(snapshots.into_iter().map(|(name, edit_index)| StorageResponse::Snapshot(name, edit_index)));
+                }
+
                 ReadHighestUnusedElementId                          => { 
                     response.push(StorageResponse::HighestUnusedElementId(self.elements.keys().cloned().max().unwrap_or(-1)+1)); 
                 }
@@ -475,6 +518,93 @@ impl InMemoryStorageCore {
                         response.push(StorageResponse::NotFound);
                     }
                 }
+
+                WriteReferenceImage(layer_id, when, image)          => {
+                    if let Some(layer) = self.layers.get_mut(&layer_id) {
+                        // Replace or insert the image at this time
+                        match layer.reference_images.binary_search_by(|(image_time, _)| image_time.cmp(&when)) {
+                            Ok(index)   => layer.reference_images[index].1 = image,
+                            Err(index)  => layer.reference_images.insert(index, (when, image))
+                        }
+
+                        response.push(StorageResponse::Updated);
+                    } else {
+                        // Layer not present
+                        response.push(StorageResponse::NotFound);
+                    }
+                }
+
+                DeleteReferenceImage(layer_id, when)                => {
+                    if let Some(layer) = self.layers.get_mut(&layer_id) {
+                        match layer.reference_images.binary_search_by(|(image_time, _)| image_time.cmp(&when)) {
+                            Ok(index)   => {
+                                layer.reference_images.remove(index);
+                                response.push(StorageResponse::Updated);
+                            }
+                            Err(_index) => response.push(StorageResponse::NotFound)
+                        }
+                    } else {
+                        // Layer not present
+                        response.push(StorageResponse::NotFound);
+                    }
+                }
+
+                ReadReferenceImage(layer_id, when)                  => {
+                    if let Some(layer) = self.layers.get(&layer_id) {
+                        // The image displayed at a particular time is the one at or before that time
+                        let index = match layer.reference_images.binary_search_by(|(image_time, _)| image_time.cmp(&when)) {
+                            Ok(index)   => Some(index),
+                            Err(index)  => if index > 0 { Some(index-1) } else { None }
+                        };
+
+                        match index {
+                            Some(index) => {
+                                let (image_time, image) = &layer.reference_images[index];
+                                response.push(StorageResponse::ReferenceImage(*image_time, image.clone()));
+                            }
+                            None        => response.push(StorageResponse::NotFound)
+                        }
+                    } else {
+                        // Layer not present
+                        response.push(StorageResponse::NotFound);
+                    }
+                }
+
+                WriteAsset(asset_id, asset)                         => {
+                    self.assets.insert(asset_id, asset);
+                    response.push(StorageResponse::Updated);
+                }
+
+                ReadAsset(asset_id)                                 => {
+                    match self.assets.get(&asset_id) {
+                        Some(asset) => response.push(StorageResponse::Asset(asset_id, asset.clone())),
+                        None        => response.push(StorageResponse::NotFound)
+                    }
+                }
+
+                ReadHighestUnusedAssetId                            => {
+                    response.push(StorageResponse::HighestUnusedAssetId(self.assets.keys().cloned().max().map(|asset_id| asset_id+1).unwrap_or(0)));
+                }
+
+                WriteSymbol(symbol_id, properties)                  => {
+                    self.symbols.insert(symbol_id, properties);
+                    response.push(StorageResponse::Updated);
+                }
+
+                DeleteSymbol(symbol_id)                             => {
+                    if self.symbols.remove(&symbol_id).is_some() {
+                        response.push(StorageResponse::Updated);
+                    } else {
+                        response.push(StorageResponse::NotFound);
+                    }
+                }
+
+                ReadSymbols                                         => {
+                    let mut symbol_ids = self.symbols.keys().cloned().collect::<Vec<_>>();
+                    symbol_ids.sort();
+
+                    response.extend(symbol_ids.into_iter().map(|symbol_id| StorageResponse::Symbol(symbol_id, self.symbols[&symbol_id].clone())));
+                }
             }
         }
 
@@ -488,9 +618,10 @@ impl InMemoryLayerStorage {
     ///
     pub fn new(properties: String) -> InMemoryLayerStorage {
         InMemoryLayerStorage {
-            properties: properties,
-            keyframes:  vec![],
-            cache:      vec![]
+            properties:         properties,
+            keyframes:          vec![],
+            cache:              vec![],
+            reference_images:   vec![]
         }
     }
 }
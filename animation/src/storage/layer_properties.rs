@@ -1,6 +1,9 @@
+use super::super::traits::*;
 use super::super::serializer::*;
 
 use std::i64;
+use std::time::{Duration};
+use std::collections::{BTreeMap, HashMap, HashSet};
 
 ///
 /// Storage/serialization structure used to represent the properties of a layer
@@ -10,7 +13,31 @@ pub struct LayerProperties {
     pub name: String,
 
     /// The ordering of this layer, relative to other layers
-    pub ordering: i64
+    pub ordering: i64,
+
+    /// If this is a reference layer, the settings used to display its reference images
+    pub reference: Option<ReferenceLayerSettings>,
+
+    /// The keyframes (by start time) that are tweened into the following keyframe, and the easing to use
+    pub tweens: BTreeMap<Duration, TweenEasing>,
+
+    /// If this layer belongs to a symbol rather than the main animation, the ID of that symbol
+    pub symbol: Option<u64>,
+
+    /// True if this layer is a folder used to group other layers
+    pub folder: bool,
+
+    /// The ID of the folder that this layer is in (None if it's at the top level)
+    pub parent: Option<u64>,
+
+    /// True if this layer (and any layers in it, if it's a folder) should not be displayed
+    pub hidden: bool,
+
+    /// True if this layer (and any layers in it, if it's a folder) should not be edited
+    pub locked: bool,
+
+    /// If this is a folder, true if the layers in it should be hidden in the timeline
+    pub collapsed: bool
 }
 
 
@@ -18,7 +45,15 @@ impl Default for LayerProperties {
     fn default() -> LayerProperties {
         LayerProperties {
             name:       "".to_string(),
-            ordering:   i64::max_value()
+            ordering:   i64::max_value(),
+            reference:  None,
+            tweens:     BTreeMap::new(),
+            symbol:     None,
+            folder:     false,
+            parent:     None,
+            hidden:     false,
+            locked:     false,
+            collapsed:  false
         }
     }
 }
@@ -28,11 +63,37 @@ impl LayerProperties {
     /// Serializes these file properties to a target
     ///
     pub fn serialize<Tgt: AnimationDataTarget>(&self, data: &mut Tgt) {
-        // Version 0 of the properties
-        data.write_small_u64(0);
+        // Version 4 of the properties
+        data.write_small_u64(4);
 
         data.write_str(&self.name);
         data.write_i64(self.ordering);
+
+        match &self.reference {
+            None            => { data.write_chr('V'); }
+            Some(settings)  => { data.write_chr('R'); settings.serialize(data); }
+        }
+
+        data.write_usize(self.tweens.len());
+        for (when, easing) in self.tweens.iter() {
+            data.write_duration(*when);
+            easing.serialize(data);
+        }
+
+        match self.symbol {
+            None            => { data.write_chr('-'); }
+            Some(symbol_id) => { data.write_chr('S'); data.write_small_u64(symbol_id); }
+        }
+
+        match self.parent {
+            None            => { data.write_chr('-'); }
+            Some(parent_id) => { data.write_chr('P'); data.write_small_u64(parent_id); }
+        }
+
+        data.write_chr(if self.folder { 'F' } else { '-' });
+        data.write_chr(if self.hidden { 'H' } else { '-' });
+        data.write_chr(if self.locked { 'L' } else { '-' });
+        data.write_chr(if self.collapsed { 'C' } else { '-' });
     }
 
     ///
@@ -49,7 +110,247 @@ impl LayerProperties {
                 Some(result)
             }
 
+            1 => {
+                result.name         = data.next_string();
+                result.ordering     = data.next_i64();
+                result.reference    = match data.next_chr() {
+                    'V' => None,
+                    'R' => Some(ReferenceLayerSettings::deserialize(data)?),
+                    _   => { return None; }
+                };
+
+                Some(result)
+            }
+
+            2 => {
+                result.name         = data.next_string();
+                result.ordering     = data.next_i64();
+                result.reference    = match data.next_chr() {
+                    'V' => None,
+                    'R' => Some(ReferenceLayerSettings::deserialize(data)?),
+                    _   => { return None; }
+                };
+
+                let num_tweens      = data.next_usize();
+                for _ in 0..num_tweens {
+                    let when    = data.next_duration();
+                    let easing  = TweenEasing::deserialize(data)?;
+
+                    result.tweens.insert(when, easing);
+                }
+
+                Some(result)
+            }
+
+            3 => {
+                result.name         = data.next_string();
+                result.ordering     = data.next_i64();
+                result.reference    = match data.next_chr() {
+                    'V' => None,
+                    'R' => Some(ReferenceLayerSettings::deserialize(data)?),
+                    _   => { return None; }
+                };
+
+                let num_tweens      = data.next_usize();
+                for _ in 0..num_tweens {
+                    let when    = data.next_duration();
+                    let easing  = TweenEasing::deserialize(data)?;
+
+                    result.tweens.insert(when, easing);
+                }
+
+                result.symbol       = match data.next_chr() {
+                    '-' => None,
+                    'S' => Some(data.next_small_u64()),
+                    _   => { return None; }
+                };
+
+                Some(result)
+            }
+
+            4 => {
+                result.name         = data.next_string();
+                result.ordering     = data.next_i64();
+                result.reference    = match data.next_chr() {
+                    'V' => None,
+                    'R' => Some(ReferenceLayerSettings::deserialize(data)?),
+                    _   => { return None; }
+                };
+
+                let num_tweens      = data.next_usize();
+                for _ in 0..num_tweens {
+                    let when    = data.next_duration();
+                    let easing  = TweenEasing::deserialize(data)?;
+
+                    result.tweens.insert(when, easing);
+                }
+
+                result.symbol       = match data.next_chr() {
+                    '-' => None,
+                    'S' => Some(data.next_small_u64()),
+                    _   => { return None; }
+                };
+                result.parent       = match data.next_chr() {
+                    '-' => None,
+                    'P' => Some(data.next_small_u64()),
+                    _   => { return None; }
+                };
+
+                result.folder       = data.next_chr() == 'F';
+                result.hidden       = data.next_chr() == 'H';
+                result.locked       = data.next_chr() == 'L';
+                result.collapsed    = data.next_chr() == 'C';
+
+                Some(result)
+            }
+
             _ => None
         }
     }
+
+    ///
+    /// Orders a set of layers so that they're sorted by their ordering, with the layers in each folder immediately
+    /// following the folder itself
+    ///
+    /// Layers whose parent isn't in the list are treated as being at the top level.
+    ///
+    pub fn hierarchy_order(layers: Vec<(u64, LayerProperties)>) -> Vec<u64> {
+        let mut layers  = layers;
+        layers.sort_by_key(|(layer_id, properties)| (properties.ordering, *layer_id));
+
+        // Find the children of each layer
+        let layer_ids   = layers.iter().map(|(layer_id, _)| *layer_id).collect::<HashSet<_>>();
+        let mut roots   = vec![];
+        let mut children: HashMap<u64, Vec<u64>> = HashMap::new();
+
+        for (layer_id, properties) in layers.iter() {
+            match properties.parent {
+                Some(parent_id) if layer_ids.contains(&parent_id) && parent_id != *layer_id => { children.entry(parent_id).or_insert_with(|| vec![]).push(*layer_id); }
+                _                                                                           => { roots.push(*layer_id); }
+            }
+        }
+
+        // Visit the layers depth-first
+        let mut result  = vec![];
+        let mut visited = HashSet::new();
+        let mut pending = roots.into_iter().rev().collect::<Vec<_>>();
+
+        while let Some(layer_id) = pending.pop() {
+            if !visited.insert(layer_id) { continue; }
+
+            result.push(layer_id);
+            if let Some(layer_children) = children.get(&layer_id) {
+                pending.extend(layer_children.iter().rev().cloned());
+            }
+        }
+
+        // Layers that are in a loop of parents are never reached from the top level, so they go at the end
+        for (layer_id, _) in layers.iter() {
+            if visited.insert(*layer_id) {
+                result.push(*layer_id);
+            }
+        }
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn vector_layer_properties() {
+        let mut encoded = String::new();
+        let properties  = LayerProperties { name: "Layer".to_string(), ordering: 42, reference: None, tweens: BTreeMap::new(), symbol: None, folder: false, parent: None, hidden: false, locked: false, collapsed: false };
+        properties.serialize(&mut encoded);
+
+        let decoded     = LayerProperties::deserialize(&mut encoded.chars()).unwrap();
+        assert!(decoded.name == "Layer");
+        assert!(decoded.ordering == 42);
+        assert!(decoded.reference == None);
+    }
+
+    #[test]
+    fn reference_layer_properties() {
+        let mut encoded = String::new();
+        let settings    = ReferenceLayerSettings { alpha: 0.25, offset: (10.0, 20.0), scale: 1.5, rotation: 0.5 };
+        let properties  = LayerProperties { name: "Reference".to_string(), ordering: 1, reference: Some(settings.clone()), tweens: BTreeMap::new(), symbol: None, folder: false, parent: None, hidden: false, locked: false, collapsed: false };
+        properties.serialize(&mut encoded);
+
+        let decoded     = LayerProperties::deserialize(&mut encoded.chars()).unwrap();
+        assert!(decoded.name == "Reference");
+        assert!(decoded.ordering == 1);
+        assert!(decoded.reference == Some(settings));
+    }
+
+    #[test]
+    fn tweened_layer_properties() {
+        let mut encoded = String::new();
+        let mut tweens  = BTreeMap::new();
+        tweens.insert(Duration::from_millis(0), TweenEasing::Linear);
+        tweens.insert(Duration::from_millis(500), TweenEasing::EaseInOut);
+
+        let properties  = LayerProperties { name: "Tweened".to_string(), ordering: 2, reference: None, tweens: tweens.clone(), symbol: None, folder: false, parent: None, hidden: false, locked: false, collapsed: false };
+        properties.serialize(&mut encoded);
+
+        let decoded     = LayerProperties::deserialize(&mut encoded.chars()).unwrap();
+        assert!(decoded.name == "Tweened");
+        assert!(decoded.tweens == tweens);
+    }
+
+    #[test]
+    fn version_0_properties() {
+        let mut encoded = String::new();
+        encoded.write_small_u64(0);
+        encoded.write_str("Old layer");
+        encoded.write_i64(3);
+
+        let decoded     = LayerProperties::deserialize(&mut encoded.chars()).unwrap();
+        assert!(decoded.name == "Old layer");
+        assert!(decoded.ordering == 3);
+        assert!(decoded.reference == None);
+    }
+
+    #[test]
+    fn symbol_layer_properties() {
+        let mut encoded = String::new();
+        let properties  = LayerProperties { name: "Symbol layer".to_string(), ordering: 3, reference: None, tweens: BTreeMap::new(), symbol: Some(7), folder: false, parent: None, hidden: false, locked: false, collapsed: false };
+        properties.serialize(&mut encoded);
+
+        let decoded     = LayerProperties::deserialize(&mut encoded.chars()).unwrap();
+        assert!(decoded.name == "Symbol layer");
+        assert!(decoded.symbol == Some(7));
+    }
+
+    #[test]
+    fn folder_layer_properties() {
+        let mut encoded = String::new();
+        let properties  = LayerProperties { name: "Folder".to_string(), ordering: 4, reference: None, tweens: BTreeMap::new(), symbol: None, folder: true, parent: Some(2), hidden: true, locked: false, collapsed: true };
+        properties.serialize(&mut encoded);
+
+        let decoded     = LayerProperties::deserialize(&mut encoded.chars()).unwrap();
+        assert!(decoded.name == "Folder");
+        assert!(decoded.folder);
+        assert!(decoded.parent == Some(2));
+        assert!(decoded.hidden);
+        assert!(!decoded.locked);
+        assert!(decoded.collapsed);
+    }
+
+    #[test]
+    fn children_follow_their_folder() {
+        let layer       = |ordering, parent| LayerProperties { ordering: ordering, parent: parent, ..LayerProperties::default() };
+        let layers      = vec![(1, layer(0, None)), (2, layer(1, None)), (3, layer(0, Some(2))), (4, layer(2, None)), (5, layer(1, Some(2)))];
+
+        assert!(LayerProperties::hierarchy_order(layers) == vec![1, 2, 3, 5, 4]);
+    }
+
+    #[test]
+    fn parent_loops_are_still_ordered() {
+        let layer       = |ordering, parent| LayerProperties { ordering: ordering, parent: parent, ..LayerProperties::default() };
+        let layers      = vec![(1, layer(0, None)), (2, layer(1, Some(3))), (3, layer(2, Some(2)))];
+
+        assert!(LayerProperties::hierarchy_order(layers) == vec![1, 2, 3]);
+    }
 }
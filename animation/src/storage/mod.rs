@@ -20,9 +20,15 @@
 
 pub (super) mod file_properties;
 pub (super) mod layer_properties;
+pub (super) mod symbol_properties;
 pub (super) mod storage_api;
 pub (super) mod in_memory_storage;
 pub (super) mod animation_loader;
+pub (super) mod replay;
+pub (super) mod animation_diff;
+pub (super) mod compaction;
+pub (super) mod animation_dump;
+pub (super) mod archive;
 
 #[cfg(test)] mod tests;
 
@@ -30,3 +36,8 @@ pub use super::editor::*;
 pub use self::storage_api::*;
 pub use self::in_memory_storage::*;
 pub use self::animation_loader::*;
+pub use self::replay::*;
+pub use self::animation_diff::*;
+pub use self::compaction::*;
+pub use self::animation_dump::*;
+pub use self::archive::*;
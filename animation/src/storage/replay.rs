@@ -0,0 +1,59 @@
+use super::super::traits::*;
+
+use futures::prelude::*;
+
+use std::collections::{HashSet};
+
+/// The number of edits that are sent to the target animation at once while replaying an edit log
+const REPLAY_BATCH_SIZE: usize = 256;
+
+///
+/// Replays the edit log of one animation into another animation
+///
+/// The target animation should usually be empty (for example, an animation created from a new `InMemoryStorage`). If `until`
+/// is specified, only the edits before that index are replayed, which makes it possible to create a snapshot of what the
+/// animation looked like at an earlier point in its history. Any assets used by the edits are copied to the target before
+/// the edits that use them. The result is the number of edits that were replayed.
+///
+pub fn replay_edit_log<'a, Source, Target>(source: &'a Source, target: &'a Target, until: Option<usize>) -> impl 'a+Future<Output=usize>
+where   Source: ?Sized+Animation,
+        Target: ?Sized+EditableAnimation {
+    async move {
+        // Work out how many edits to replay
+        let num_edits       = source.get_num_edits();
+        let num_edits       = until.map(|until| until.min(num_edits)).unwrap_or(num_edits);
+
+        // Stream the edits from the source into the target in batches
+        let mut edit_log    = source.read_edit_log(0..num_edits);
+        let mut batch       = Vec::with_capacity(REPLAY_BATCH_SIZE);
+        let mut replayed    = 0;
+        let mut assets      = HashSet::new();
+
+        while let Some(edit) = edit_log.next().await {
+            // Copy the assets the first time they're used
+            if let Some(asset_id) = edit.asset_id() {
+                if assets.insert(asset_id) {
+                    if let Some(asset) = source.get_asset(asset_id) {
+                        target.write_asset(asset_id, asset);
+                    }
+                }
+            }
+
+            batch.push(edit);
+
+            if batch.len() >= REPLAY_BATCH_SIZE {
+                replayed += batch.len();
+                target.perform_edits(batch);
+                batch = Vec::with_capacity(REPLAY_BATCH_SIZE);
+            }
+        }
+
+        // Send the remaining edits
+        replayed += batch.len();
+        if batch.len() > 0 {
+            target.perform_edits(batch);
+        }
+
+        replayed
+    }
+}
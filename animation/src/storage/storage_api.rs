@@ -1,3 +1,5 @@
+use super::super::serializer::{SerializedData};
+
 use std::ops::{Range};
 use std::time::{Duration};
 
@@ -13,7 +15,20 @@ pub enum StorageCommand {
     ReadAnimationProperties,
 
     /// Appends a serialized edit to the edit log
-    WriteEdit(String),
+    WriteEdit(SerializedData),
+
+    /// Replaces the specified number of edits at the start of the edit log with a new set of serialized edits (which must
+    /// produce the same animation). Any edits after the replaced ones are kept, following on from the new edits.
+    ReplaceEditLog(usize, Vec<SerializedData>),
+
+    /// Stores a named snapshot of the edit log (the name, and the number of edits that make up the snapshot)
+    WriteSnapshot(String, usize),
+
+    /// Removes the snapshot with the specified name
+    DeleteSnapshot(String),
+
+    /// Reads all of the named snapshots of the edit log
+    ReadSnapshots,
 
     /// Retrieves the highest unused element ID (this ID and any higher are guaranteed to be unassigned)
     ReadHighestUnusedElementId,
@@ -25,7 +40,7 @@ pub enum StorageCommand {
     ReadEdits(Range<usize>),
 
     /// Writes the serialized value of an element
-    WriteElement(i64, String),
+    WriteElement(i64, SerializedData),
 
     /// Reads the previously serialized value of an element
     ReadElement(i64),
@@ -76,7 +91,34 @@ pub enum StorageCommand {
     DeleteLayerCache(u64, Duration, String),
 
     /// Reads from the layer cache (parameters are layer id, cache time and key)
-    ReadLayerCache(u64, Duration, String)
+    ReadLayerCache(u64, Duration, String),
+
+    /// Writes a serialized reference image for a layer (parameters are layer id, the time the image is displayed from and the image data)
+    WriteReferenceImage(u64, Duration, String),
+
+    /// Removes the reference image displayed from the specified time
+    DeleteReferenceImage(u64, Duration),
+
+    /// Reads the reference image that is displayed at a particular time on a layer
+    ReadReferenceImage(u64, Duration),
+
+    /// Writes the serialized data for an asset (such as an image used by a reference layer)
+    WriteAsset(u64, String),
+
+    /// Reads the serialized data for an asset
+    ReadAsset(u64),
+
+    /// Retrieves the highest unused asset ID (this ID and any higher are guaranteed to be unassigned)
+    ReadHighestUnusedAssetId,
+
+    /// Sets the serialized properties for a symbol (adding the symbol if it doesn't already exist)
+    WriteSymbol(u64, String),
+
+    /// Removes the symbol with the specified ID (its layers are stored and deleted separately)
+    DeleteSymbol(u64),
+
+    /// Reads all of the symbols stored in this API (as SymbolProperties)
+    ReadSymbols
 }
 
 ///
@@ -106,7 +148,10 @@ pub enum StorageResponse {
     HighestUnusedElementId(i64),
 
     /// An edit requested when reading the edit log
-    Edit(usize, String),
+    Edit(usize, SerializedData),
+
+    /// A named snapshot of the edit log, and the number of edits that make it up
+    Snapshot(String, usize),
 
     /// Start of a read from a keyframe. The two times here are the start and end time of the keyframe from the start of the animation
     KeyFrame(Duration, Duration),
@@ -115,7 +160,7 @@ pub enum StorageResponse {
     NotInAFrame(Duration),
 
     /// The serialized version of the element that was requested
-    Element(i64, String),
+    Element(i64, SerializedData),
 
     /// Returns the (layer, keyframe) pairs that a particular element is attached to
     ElementAttachments(i64, Vec<(u64, Duration)>),
@@ -123,6 +168,18 @@ pub enum StorageResponse {
     /// Returns the contents of the requested layer cache
     LayerCache(String),
 
+    /// Returns the time a reference image is displayed from and its serialized data
+    ReferenceImage(Duration, String),
+
+    /// The serialized data for an asset
+    Asset(u64, String),
+
+    /// The highest unused asset ID (0 if there are no assets stored yet)
+    HighestUnusedAssetId(u64),
+
+    /// The serialized properties for a symbol
+    Symbol(u64, String),
+
     /// The storage subsystem encountered an error
     Error(StorageError, String)
 }
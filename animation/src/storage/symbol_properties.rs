@@ -0,0 +1,70 @@
+use super::super::serializer::*;
+
+use std::time::{Duration};
+
+///
+/// Storage/serialization structure used to represent the properties of a symbol
+///
+#[derive(Clone, PartialEq, Debug)]
+pub struct SymbolProperties {
+    /// The name of the symbol
+    pub name: String,
+
+    /// The length of the symbol's timeline
+    pub length: Duration
+}
+
+impl Default for SymbolProperties {
+    fn default() -> SymbolProperties {
+        // Default is an unnamed symbol that's one second long
+        SymbolProperties {
+            name:   "".to_string(),
+            length: Duration::from_millis(1000)
+        }
+    }
+}
+
+impl SymbolProperties {
+    ///
+    /// Serializes these symbol properties to a target
+    ///
+    pub fn serialize<Tgt: AnimationDataTarget>(&self, data: &mut Tgt) {
+        // Version 0 of the properties
+        data.write_small_u64(0);
+
+        data.write_str(&self.name);
+        data.write_duration(self.length);
+    }
+
+    ///
+    /// Deserializes symbol properties from a source
+    ///
+    pub fn deserialize<Src: AnimationDataSource>(data: &mut Src) -> Option<SymbolProperties> {
+        let mut result = SymbolProperties::default();
+
+        match data.next_small_u64() {
+            0 => {
+                result.name     = data.next_string();
+                result.length   = data.next_duration();
+
+                Some(result)
+            }
+
+            _ => None
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn symbol_properties() {
+        let mut encoded = String::new();
+        let properties  = SymbolProperties { name: "Walk cycle".to_string(), length: Duration::from_millis(2500) };
+        properties.serialize(&mut encoded);
+
+        assert!(SymbolProperties::deserialize(&mut encoded.chars()) == Some(properties));
+    }
+}
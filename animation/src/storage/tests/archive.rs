@@ -0,0 +1,78 @@
+use super::*;
+
+use futures::executor;
+
+use std::sync::*;
+use std::time::Duration;
+
+#[test]
+fn archive_contains_animation_data() {
+    let source  = create_source_animation();
+    let archive = executor::block_on(AnimationArchive::from_animation(&source, Some("Test".to_string()), false));
+
+    assert!(archive.size == (1920.0, 1080.0));
+    assert!(archive.layers == vec![(1, Some("Test layer".to_string()))]);
+    assert!(archive.elements.len() == source.get_layer_with_id(1).unwrap().get_frame_at_time(Duration::from_millis(0)).vector_elements().unwrap().count());
+    assert!(archive.snapshots == vec![EditLogCheckpoint { name: "One line".to_string(), edit_index: 6 }]);
+    assert!(archive.edits.len() == source.get_num_edits());
+}
+
+#[test]
+fn serialize_and_deserialize_archive() {
+    let source      = create_source_animation();
+    let archive     = executor::block_on(AnimationArchive::from_animation(&source, Some("Test".to_string()), false));
+
+    let mut serialized = String::new();
+    archive.serialize(&mut serialized);
+
+    assert!(serialized.starts_with("FLO-ARCHIVE 1\n"));
+    assert!(AnimationArchive::deserialize(&serialized) == Ok(archive));
+}
+
+#[test]
+fn restored_archive_matches_original() {
+    let source      = create_source_animation();
+    let archive     = executor::block_on(AnimationArchive::from_animation(&source, None, true));
+    let target      = create_animation();
+
+    archive.restore(&target);
+
+    assert!(diff_animations(&source, &target) == vec![]);
+    assert!(archive.find_mismatched_elements(&target) == vec![]);
+    assert!(target.get_snapshots() == archive.snapshots);
+}
+
+#[test]
+fn archive_stores_reference_images_once() {
+    let source  = create_animation();
+    let image   = source.assign_asset_id();
+    source.write_asset(image, Arc::new(vec![1, 2, 3, 4]));
+
+    source.perform_edits(vec![
+        AnimationEdit::AddNewLayer(1),
+        AnimationEdit::Layer(1, LayerEdit::SetReference(Some(ReferenceLayerSettings::default()))),
+        AnimationEdit::Layer(1, LayerEdit::AddReferenceImage(Duration::from_millis(0), image)),
+        AnimationEdit::Layer(1, LayerEdit::AddReferenceImage(Duration::from_millis(40), image))
+    ]);
+
+    let archive     = executor::block_on(AnimationArchive::from_animation(&source, None, false));
+    assert!(archive.assets == vec![(image, Arc::new(vec![1, 2, 3, 4]))]);
+
+    let mut serialized = String::new();
+    archive.serialize(&mut serialized);
+
+    let target      = create_animation();
+    AnimationArchive::deserialize(&serialized).unwrap().restore(&target);
+
+    assert!(target.get_layer_with_id(1).unwrap().reference_image_at_time(Duration::from_millis(60)) == Some(Arc::new(vec![1, 2, 3, 4])));
+}
+
+#[test]
+fn reject_newer_archive_version() {
+    assert!(AnimationArchive::deserialize("FLO-ARCHIVE 9999\n") == Err(ArchiveError::UnsupportedVersion(9999)));
+}
+
+#[test]
+fn reject_missing_header() {
+    assert!(AnimationArchive::deserialize("EAAA\n") == Err(ArchiveError::MissingHeader));
+}
@@ -0,0 +1,184 @@
+use super::*;
+
+use std::sync::*;
+use std::time::Duration;
+
+///
+/// Creates an animation with a layer (1) containing a brush stroke (50)
+///
+fn create_layer_with_stroke() -> impl EditableAnimation {
+    let anim = create_animation();
+
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewLayer(1),
+        AnimationEdit::Layer(1, LayerEdit::AddKeyFrame(Duration::from_millis(0))),
+        AnimationEdit::Layer(1, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::SelectBrush(
+                ElementId::Unassigned,
+                BrushDefinition::Ink(InkDefinition::default()),
+                BrushDrawingStyle::Draw
+            )
+        )),
+        AnimationEdit::Layer(1, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::
+            BrushProperties(ElementId::Unassigned, BrushProperties::new()))),
+        AnimationEdit::Layer(1, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::BrushStroke(ElementId::Assigned(50), Arc::new(vec![
+                RawPoint::from((10.0, 10.0)),
+                RawPoint::from((20.0, 5.0))
+            ]))))
+    ]);
+
+    anim
+}
+
+///
+/// Retrieves the transformations applied to the brush stroke at a particular time
+///
+fn stroke_transformations<Anim: EditableAnimation>(anim: &Anim, when: Duration) -> Vec<Transformation> {
+    let layer       = anim.get_layer_with_id(1).unwrap();
+    let frame       = layer.get_frame_at_time(when);
+    let stroke      = frame.element_with_id(ElementId::Assigned(50)).unwrap();
+    let properties  = frame.apply_properties_for_element(&stroke, Arc::new(VectorProperties::default()));
+
+    (*properties.transformations).clone()
+}
+
+#[test]
+fn bone_rotates_bound_element() {
+    let anim = create_layer_with_stroke();
+
+    anim.perform_edits(vec![
+        AnimationEdit::Bone(ElementId::Assigned(100), BoneEdit::Create),
+        AnimationEdit::Bone(ElementId::Assigned(100), BoneEdit::SetOrigin(10.0, 20.0)),
+        AnimationEdit::Bone(ElementId::Assigned(100), BoneEdit::SetRotation(Duration::from_millis(0), 1.0)),
+        AnimationEdit::Element(vec![ElementId::Assigned(50)], ElementEdit::AddAttachment(ElementId::Assigned(100)))
+    ]);
+
+    assert!(stroke_transformations(&anim, Duration::from_millis(0)) == vec![Transformation::Rotate(1.0, (10.0, 20.0))]);
+}
+
+#[test]
+fn bone_rotation_is_interpolated() {
+    let anim = create_layer_with_stroke();
+
+    anim.perform_edits(vec![
+        AnimationEdit::Bone(ElementId::Assigned(100), BoneEdit::Create),
+        AnimationEdit::Bone(ElementId::Assigned(100), BoneEdit::SetRotation(Duration::from_millis(0), 0.0)),
+        AnimationEdit::Bone(ElementId::Assigned(100), BoneEdit::SetRotation(Duration::from_millis(1000), 1.0)),
+        AnimationEdit::Element(vec![ElementId::Assigned(50)], ElementEdit::AddAttachment(ElementId::Assigned(100)))
+    ]);
+
+    assert!(stroke_transformations(&anim, Duration::from_millis(0)).len() == 0);
+
+    let halfway = stroke_transformations(&anim, Duration::from_millis(500));
+    assert!(halfway.len() == 1);
+    match halfway[0] {
+        Transformation::Rotate(angle, origin)   => assert!((angle-0.5).abs() < 0.0001 && origin == (0.0, 0.0)),
+        _                                       => assert!(false)
+    }
+
+    assert!(stroke_transformations(&anim, Duration::from_millis(2000)) == vec![Transformation::Rotate(1.0, (0.0, 0.0))]);
+}
+
+#[test]
+fn removed_rotation_no_longer_poses_element() {
+    let anim = create_layer_with_stroke();
+
+    anim.perform_edits(vec![
+        AnimationEdit::Bone(ElementId::Assigned(100), BoneEdit::Create),
+        AnimationEdit::Bone(ElementId::Assigned(100), BoneEdit::SetRotation(Duration::from_millis(0), 1.0)),
+        AnimationEdit::Element(vec![ElementId::Assigned(50)], ElementEdit::AddAttachment(ElementId::Assigned(100))),
+        AnimationEdit::Bone(ElementId::Assigned(100), BoneEdit::RemoveRotation(Duration::from_millis(0)))
+    ]);
+
+    assert!(stroke_transformations(&anim, Duration::from_millis(0)).len() == 0);
+}
+
+#[test]
+fn parent_bone_moves_child() {
+    let anim = create_layer_with_stroke();
+
+    // The parent bone (100) isn't attached to anything in the frame, so it has to be loaded via the child bone (101)
+    anim.perform_edits(vec![
+        AnimationEdit::Bone(ElementId::Assigned(100), BoneEdit::Create),
+        AnimationEdit::Bone(ElementId::Assigned(100), BoneEdit::SetLength(10.0)),
+        AnimationEdit::Bone(ElementId::Assigned(100), BoneEdit::SetRotation(Duration::from_millis(0), 1.0)),
+        AnimationEdit::Bone(ElementId::Assigned(101), BoneEdit::Create),
+        AnimationEdit::Bone(ElementId::Assigned(101), BoneEdit::SetOrigin(10.0, 0.0)),
+        AnimationEdit::Bone(ElementId::Assigned(101), BoneEdit::SetParent(Some(ElementId::Assigned(100)))),
+        AnimationEdit::Bone(ElementId::Assigned(101), BoneEdit::SetRotation(Duration::from_millis(0), 0.5)),
+        AnimationEdit::Element(vec![ElementId::Assigned(50)], ElementEdit::AddAttachment(ElementId::Assigned(101)))
+    ]);
+
+    assert!(stroke_transformations(&anim, Duration::from_millis(0)) == vec![
+        Transformation::Rotate(0.5, (10.0, 0.0)),
+        Transformation::Rotate(1.0, (0.0, 0.0))
+    ]);
+
+    let layer       = anim.get_layer_with_id(1).unwrap();
+    let frame       = layer.get_frame_at_time(Duration::from_millis(0));
+    let mut bones   = frame.bones().into_iter().map(|bone| bone.id()).collect::<Vec<_>>();
+    bones.sort_by_key(|bone_id| bone_id.id());
+
+    assert!(bones == vec![ElementId::Assigned(100), ElementId::Assigned(101)]);
+}
+
+#[test]
+fn cannot_connect_bones_in_a_loop() {
+    let anim = create_layer_with_stroke();
+
+    anim.perform_edits(vec![
+        AnimationEdit::Bone(ElementId::Assigned(100), BoneEdit::Create),
+        AnimationEdit::Bone(ElementId::Assigned(101), BoneEdit::Create),
+        AnimationEdit::Bone(ElementId::Assigned(101), BoneEdit::SetParent(Some(ElementId::Assigned(100)))),
+        AnimationEdit::Bone(ElementId::Assigned(100), BoneEdit::SetParent(Some(ElementId::Assigned(101)))),
+        AnimationEdit::Bone(ElementId::Assigned(100), BoneEdit::SetParent(Some(ElementId::Assigned(100)))),
+        AnimationEdit::Element(vec![ElementId::Assigned(50)], ElementEdit::AddAttachment(ElementId::Assigned(101)))
+    ]);
+
+    let layer       = anim.get_layer_with_id(1).unwrap();
+    let frame       = layer.get_frame_at_time(Duration::from_millis(0));
+    let bones       = frame.bones();
+
+    let parent      = bones.iter().filter(|bone| bone.id() == ElementId::Assigned(100)).nth(0).unwrap();
+    let child       = bones.iter().filter(|bone| bone.id() == ElementId::Assigned(101)).nth(0).unwrap();
+
+    assert!(parent.bone().parent == None);
+    assert!(child.bone().parent == Some(ElementId::Assigned(100)));
+}
+
+#[test]
+fn cannot_connect_to_missing_bone() {
+    let anim = create_layer_with_stroke();
+
+    anim.perform_edits(vec![
+        AnimationEdit::Bone(ElementId::Assigned(100), BoneEdit::Create),
+        AnimationEdit::Bone(ElementId::Assigned(100), BoneEdit::SetParent(Some(ElementId::Assigned(50)))),
+        AnimationEdit::Bone(ElementId::Assigned(100), BoneEdit::SetParent(Some(ElementId::Assigned(200)))),
+        AnimationEdit::Element(vec![ElementId::Assigned(50)], ElementEdit::AddAttachment(ElementId::Assigned(100)))
+    ]);
+
+    let layer       = anim.get_layer_with_id(1).unwrap();
+    let frame       = layer.get_frame_at_time(Duration::from_millis(0));
+    let bones       = frame.bones();
+
+    assert!(bones.len() == 1);
+    assert!(bones[0].bone().parent == None);
+}
+
+#[test]
+fn create_bone_action_binds_elements() {
+    let anim    = create_layer_with_stroke();
+    let edits   = BoneEditAction::CreateBone(None, (10.0, 10.0), (10.0, 30.0), vec![ElementId::Assigned(50)]).to_animation_edits(&anim);
+    anim.perform_edits(edits);
+
+    let layer       = anim.get_layer_with_id(1).unwrap();
+    let frame       = layer.get_frame_at_time(Duration::from_millis(0));
+    let bones       = frame.bones();
+
+    assert!(bones.len() == 1);
+    assert!(frame.attached_elements(ElementId::Assigned(50)) == vec![(bones[0].id(), VectorType::Bone)]);
+
+    let bone        = bones[0].bone();
+    assert!(bone.origin == (10.0, 10.0));
+    assert!((bone.length - 20.0).abs() < 0.001);
+    assert!((bone.angle - std::f64::consts::PI/2.0).abs() < 0.001);
+}
@@ -0,0 +1,200 @@
+use super::*;
+
+use flo_curves::*;
+use flo_curves::bezier::path::*;
+
+use std::sync::*;
+use std::time::Duration;
+
+///
+/// Creates the components of a 10x10 square with its bottom-left corner at the specified x position
+///
+fn square_at(x: f64) -> Arc<Vec<PathComponent>> {
+    Arc::new(vec![
+        PathComponent::Move(PathPoint::new(x, 0.0)),
+        PathComponent::Line(PathPoint::new(x+10.0, 0.0)),
+        PathComponent::Line(PathPoint::new(x+10.0, 10.0)),
+        PathComponent::Line(PathPoint::new(x, 10.0)),
+        PathComponent::Close
+    ])
+}
+
+///
+/// Creates the edits to add layer 24 with a keyframe at 300ms and select a brush for paths and paint strokes
+///
+fn setup_layer() -> Vec<AnimationEdit> {
+    use self::LayerEdit::*;
+
+    vec![
+        AnimationEdit::AddNewLayer(24),
+        AnimationEdit::Layer(24, LayerEdit::AddKeyFrame(Duration::from_millis(300))),
+        AnimationEdit::Layer(24, Path(Duration::from_millis(300),
+            PathEdit::SelectBrush(ElementId::Unassigned, BrushDefinition::Ink(InkDefinition::default()), BrushDrawingStyle::Draw))),
+        AnimationEdit::Layer(24, Path(Duration::from_millis(300),
+            PathEdit::BrushProperties(ElementId::Unassigned, BrushProperties::new()))),
+        AnimationEdit::Layer(24, Paint(Duration::from_millis(300),
+            PaintEdit::SelectBrush(ElementId::Unassigned, BrushDefinition::Ink(InkDefinition::default()), BrushDrawingStyle::Draw))),
+        AnimationEdit::Layer(24, Paint(Duration::from_millis(300),
+            PaintEdit::BrushProperties(ElementId::Unassigned, BrushProperties::new())))
+    ]
+}
+
+///
+/// Creates an animation with two overlapping squares (elements 100 and 101) and combines them using the specified operation
+///
+fn combine_squares(combine: PathCombine) -> impl EditableAnimation {
+    use self::LayerEdit::*;
+
+    let anim = create_animation();
+
+    anim.perform_edits(setup_layer());
+    anim.perform_edits(vec![
+        AnimationEdit::Layer(24, Path(Duration::from_millis(300),
+            PathEdit::CreatePath(ElementId::Assigned(100), square_at(0.0)))),
+        AnimationEdit::Layer(24, Path(Duration::from_millis(300),
+            PathEdit::CreatePath(ElementId::Assigned(101), square_at(5.0)))),
+
+        AnimationEdit::Element(vec![ElementId::Assigned(100), ElementId::Assigned(101)], ElementEdit::CombinePaths(combine))
+    ]);
+
+    anim
+}
+
+///
+/// Reads the combined path from an animation generated by combine_squares
+///
+fn combined_path(anim: &impl EditableAnimation) -> Path {
+    let layer       = anim.get_layer_with_id(24).unwrap();
+    let frame       = layer.get_frame_at_time(Duration::from_millis(300));
+    let elements    = frame.vector_elements().unwrap().collect::<Vec<_>>();
+
+    // The second square should have been merged into the first
+    let ids         = elements.iter().map(|element| element.id().id().unwrap()).collect::<Vec<_>>();
+    assert!(ids == vec![100]);
+
+    match &elements[0] {
+        Vector::Path(path)  => path.path().clone(),
+        _                   => { assert!(false); unimplemented!() }
+    }
+}
+
+///
+/// Reads the path for an element from a frame
+///
+fn element_path(frame: &Arc<dyn Frame>, element_id: i64) -> Path {
+    match frame.element_with_id(ElementId::Assigned(element_id)) {
+        Some(Vector::Path(path))    => path.path().clone(),
+        _                           => { assert!(false); unimplemented!() }
+    }
+}
+
+///
+/// Checks that a path has the specified bounding box
+///
+fn assert_bounds(path: &Path, min: (f64, f64), max: (f64, f64)) {
+    let bounds = path.bounding_box::<Bounds<_>>();
+
+    assert!((bounds.min().x()-min.0).abs() < 0.01);
+    assert!((bounds.min().y()-min.1).abs() < 0.01);
+    assert!((bounds.max().x()-max.0).abs() < 0.01);
+    assert!((bounds.max().y()-max.1).abs() < 0.01);
+}
+
+#[test]
+fn union_squares() {
+    let anim = combine_squares(PathCombine::Union);
+    let path = combined_path(&anim);
+
+    assert!(path.to_subpaths().len() == 1);
+    assert_bounds(&path, (0.0, 0.0), (15.0, 10.0));
+}
+
+#[test]
+fn subtract_squares() {
+    let anim = combine_squares(PathCombine::Subtract);
+    let path = combined_path(&anim);
+
+    assert!(path.to_subpaths().len() == 1);
+    assert_bounds(&path, (0.0, 0.0), (5.0, 10.0));
+}
+
+#[test]
+fn intersect_squares() {
+    let anim = combine_squares(PathCombine::Intersect);
+    let path = combined_path(&anim);
+
+    assert!(path.to_subpaths().len() == 1);
+    assert_bounds(&path, (5.0, 0.0), (10.0, 10.0));
+}
+
+#[test]
+fn exclude_squares() {
+    let anim = combine_squares(PathCombine::Exclude);
+    let path = combined_path(&anim);
+
+    assert!(path.to_subpaths().len() == 2);
+    assert_bounds(&path, (0.0, 0.0), (15.0, 10.0));
+}
+
+#[test]
+fn union_brush_stroke_with_square() {
+    use self::LayerEdit::*;
+
+    let anim = create_animation();
+
+    // A horizontal brush stroke through the middle of a square
+    anim.perform_edits(setup_layer());
+    anim.perform_edits(vec![
+        AnimationEdit::Layer(24, Paint(Duration::from_millis(300), PaintEdit::BrushStroke(ElementId::Assigned(100), Arc::new(vec![
+            RawPoint::from((0.0, 5.0)),
+            RawPoint::from((10.0, 5.0)),
+            RawPoint::from((20.0, 5.0))
+        ])))),
+        AnimationEdit::Layer(24, Path(Duration::from_millis(300),
+            PathEdit::CreatePath(ElementId::Assigned(101), square_at(5.0)))),
+
+        AnimationEdit::Element(vec![ElementId::Assigned(100), ElementId::Assigned(101)], ElementEdit::CombinePaths(PathCombine::Union))
+    ]);
+
+    // The brush stroke is replaced by a path covering both the stroke and the square
+    let path    = combined_path(&anim);
+    let bounds  = path.bounding_box::<Bounds<_>>();
+
+    assert!(bounds.min().x() < 1.0);
+    assert!(bounds.max().x() > 19.0);
+    assert!((bounds.min().y()-0.0).abs() < 0.01);
+    assert!((bounds.max().y()-10.0).abs() < 0.01);
+}
+
+#[test]
+fn elements_outside_the_first_keyframe_are_left_alone() {
+    use self::LayerEdit::*;
+
+    let anim = create_animation();
+
+    // Squares 100 and 101 are in the keyframe at 300ms, and square 102 is in the keyframe at 600ms
+    anim.perform_edits(setup_layer());
+    anim.perform_edits(vec![
+        AnimationEdit::Layer(24, LayerEdit::AddKeyFrame(Duration::from_millis(600))),
+        AnimationEdit::Layer(24, Path(Duration::from_millis(300),
+            PathEdit::CreatePath(ElementId::Assigned(100), square_at(0.0)))),
+        AnimationEdit::Layer(24, Path(Duration::from_millis(300),
+            PathEdit::CreatePath(ElementId::Assigned(101), square_at(5.0)))),
+        AnimationEdit::Layer(24, Path(Duration::from_millis(600),
+            PathEdit::CreatePath(ElementId::Assigned(102), square_at(10.0)))),
+
+        AnimationEdit::Element(vec![ElementId::Assigned(100), ElementId::Assigned(101), ElementId::Assigned(102)], ElementEdit::CombinePaths(PathCombine::Union))
+    ]);
+
+    // Only the squares in the first keyframe are combined
+    let path    = combined_path(&anim);
+    assert_bounds(&path, (0.0, 0.0), (15.0, 10.0));
+
+    // The square in the other keyframe is unchanged
+    let layer   = anim.get_layer_with_id(24).unwrap();
+    let frame   = layer.get_frame_at_time(Duration::from_millis(600));
+    let ids     = frame.vector_elements().unwrap().map(|element| element.id().id().unwrap()).collect::<Vec<_>>();
+
+    assert!(ids == vec![102]);
+    assert_bounds(&element_path(&frame, 102), (10.0, 0.0), (20.0, 10.0));
+}
@@ -0,0 +1,212 @@
+use super::*;
+
+use futures::executor;
+
+use std::sync::*;
+use std::time::Duration;
+
+fn select_brush(brush_id: i64) -> Vec<AnimationEdit> {
+    vec![
+        AnimationEdit::Layer(1, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::SelectBrush(ElementId::Assigned(brush_id), BrushDefinition::Ink(InkDefinition::default()), BrushDrawingStyle::Draw))),
+        AnimationEdit::Layer(1, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::BrushProperties(ElementId::Assigned(brush_id+1), BrushProperties::new()))),
+    ]
+}
+
+fn brush_stroke(element_id: i64, from: (f32, f32), to: (f32, f32)) -> AnimationEdit {
+    AnimationEdit::Layer(1, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::BrushStroke(ElementId::Assigned(element_id), Arc::new(vec![
+        RawPoint::from(from),
+        RawPoint::from(to)
+    ]))))
+}
+
+fn create_layer() -> Vec<AnimationEdit> {
+    vec![
+        AnimationEdit::AddNewLayer(1),
+        AnimationEdit::Layer(1, LayerEdit::AddKeyFrame(Duration::from_millis(0)))
+    ]
+}
+
+fn is_brush_stroke(edit: &AnimationEdit, element_id: i64) -> bool {
+    match edit {
+        AnimationEdit::Layer(_, LayerEdit::Paint(_, PaintEdit::BrushStroke(id, _))) => *id == ElementId::Assigned(element_id),
+        _                                                                           => false
+    }
+}
+
+#[test]
+fn remove_deleted_element() {
+    let mut edits = create_layer();
+    edits.extend(select_brush(10));
+    edits.push(brush_stroke(100, (10.0, 10.0), (20.0, 5.0)));
+    edits.push(brush_stroke(101, (30.0, 10.0), (40.0, 5.0)));
+    edits.push(AnimationEdit::Element(vec![ElementId::Assigned(101)], ElementEdit::Delete));
+
+    let compacted = compact_edit_log(&edits, &vec![]);
+
+    assert!(compacted.edits.len() == 5);
+    assert!(compacted.edits.iter().any(|edit| is_brush_stroke(edit, 100)));
+    assert!(!compacted.edits.iter().any(|edit| is_brush_stroke(edit, 101)));
+}
+
+#[test]
+fn keep_deleted_element_if_something_was_drawn_afterwards() {
+    let mut edits = create_layer();
+    edits.extend(select_brush(10));
+    edits.push(brush_stroke(100, (10.0, 10.0), (20.0, 5.0)));
+    edits.push(brush_stroke(101, (30.0, 10.0), (40.0, 5.0)));
+    edits.push(AnimationEdit::Element(vec![ElementId::Assigned(100)], ElementEdit::Delete));
+
+    let compacted = compact_edit_log(&edits, &vec![]);
+
+    assert!(compacted.edits == edits);
+}
+
+#[test]
+fn remove_superseded_control_points() {
+    let element     = vec![ElementId::Assigned(100)];
+    let mut edits   = create_layer();
+    edits.extend(select_brush(10));
+    edits.push(brush_stroke(100, (10.0, 10.0), (20.0, 5.0)));
+    edits.push(AnimationEdit::Element(element.clone(), ElementEdit::SetControlPoints(vec![(10.0, 10.0), (12.0, 12.0), (18.0, 6.0), (21.0, 5.0)], Duration::from_millis(0))));
+    edits.push(AnimationEdit::Element(element.clone(), ElementEdit::SetControlPoints(vec![(10.0, 10.0), (13.0, 12.0), (18.0, 6.0), (22.0, 5.0)], Duration::from_millis(0))));
+    edits.push(AnimationEdit::Element(element.clone(), ElementEdit::SetControlPoints(vec![(10.0, 10.0), (14.0, 12.0), (18.0, 6.0), (23.0, 5.0)], Duration::from_millis(0))));
+
+    let compacted = compact_edit_log(&edits, &vec![]);
+
+    assert!(compacted.edits.len() == 6);
+    assert!(compacted.edits.last() == edits.last());
+}
+
+#[test]
+fn keep_brush_used_by_stroke() {
+    let mut edits = create_layer();
+    edits.extend(select_brush(10));
+    edits.push(brush_stroke(100, (10.0, 10.0), (20.0, 5.0)));
+    edits.extend(select_brush(20));
+    edits.extend(select_brush(30));
+    edits.push(brush_stroke(101, (30.0, 10.0), (40.0, 5.0)));
+
+    let compacted = compact_edit_log(&edits, &vec![]);
+
+    assert!(compacted.edits.len() == edits.len()-2);
+    assert!(compacted.edits[2..5] == edits[2..5]);
+}
+
+#[test]
+fn checkpoints_are_preserved() {
+    let mut edits = create_layer();
+    edits.extend(select_brush(10));
+    edits.push(brush_stroke(100, (10.0, 10.0), (20.0, 5.0)));
+    edits.push(brush_stroke(101, (30.0, 10.0), (40.0, 5.0)));
+    edits.push(AnimationEdit::Element(vec![ElementId::Assigned(101)], ElementEdit::Delete));
+    edits.push(AnimationEdit::Layer(1, LayerEdit::SetName("Name 1".to_string())));
+    edits.push(AnimationEdit::Layer(1, LayerEdit::SetName("Name 2".to_string())));
+
+    let checkpoints = vec![EditLogCheckpoint { name: "Before delete".to_string(), edit_index: 6 }, EditLogCheckpoint { name: "End".to_string(), edit_index: 9 }];
+    let compacted   = compact_edit_log(&edits, &checkpoints);
+
+    // The deleted element has to be kept as it's part of the first checkpoint, but the first name can be removed
+    assert!(compacted.edits.len() == 8);
+    assert!(compacted.edits[0..7] == edits[0..7]);
+    assert!(compacted.checkpoints == vec![EditLogCheckpoint { name: "Before delete".to_string(), edit_index: 6 }, EditLogCheckpoint { name: "End".to_string(), edit_index: 8 }]);
+}
+
+#[test]
+fn compacted_animation_is_unchanged() {
+    let animation   = create_animation();
+    let mut edits   = create_layer();
+    edits.extend(select_brush(10));
+    edits.push(brush_stroke(100, (10.0, 10.0), (20.0, 5.0)));
+    edits.push(brush_stroke(101, (30.0, 10.0), (40.0, 5.0)));
+    edits.push(AnimationEdit::Element(vec![ElementId::Assigned(101)], ElementEdit::Delete));
+    animation.perform_edits(edits);
+
+    let compacted   = executor::block_on(compact_animation_edit_log(&animation, vec![]));
+    assert!(compacted.edits.len() == 5);
+    assert!(animation.get_num_edits() == 5);
+
+    // Replaying the compacted log should produce the same animation
+    let replayed    = create_animation();
+    executor::block_on(replay_edit_log(&animation, &replayed, None));
+
+    assert!(diff_animations(&animation, &replayed) == vec![]);
+}
+
+#[test]
+fn brushes_are_kept_for_each_layer() {
+    let ink_on_layer = |layer_id: u64, brush_id: i64, scale_up_distance: f32| vec![
+        AnimationEdit::Layer(layer_id, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::SelectBrush(ElementId::Assigned(brush_id), BrushDefinition::Ink(InkDefinition { scale_up_distance, ..InkDefinition::default() }), BrushDrawingStyle::Draw))),
+        AnimationEdit::Layer(layer_id, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::BrushProperties(ElementId::Assigned(brush_id+1), BrushProperties::new()))),
+    ];
+
+    // Select a brush on layer 1, then a different one on layer 2 before drawing on layer 1 with the first brush
+    let mut edits = create_layer();
+    edits.push(AnimationEdit::AddNewLayer(2));
+    edits.push(AnimationEdit::Layer(2, LayerEdit::AddKeyFrame(Duration::from_millis(0))));
+    edits.extend(ink_on_layer(1, 10, 5.0));
+    edits.extend(ink_on_layer(2, 20, 80.0));
+    edits.push(AnimationEdit::Layer(1, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::BrushStroke(ElementId::Assigned(100), Arc::new(vec![
+        RawPoint::from((10.0, 10.0)),
+        RawPoint::from((15.0, 8.0)),
+        RawPoint::from((20.0, 5.0)),
+        RawPoint::from((30.0, 2.0))
+    ])))));
+
+    let compacted   = compact_edit_log(&edits, &vec![]);
+
+    // Nothing can be removed: the brush on layer 1 is still in use
+    assert!(compacted.edits == edits);
+
+    // Replaying the compacted log should produce the same animation as the original one
+    let original    = create_animation();
+    let replayed    = create_animation();
+    original.perform_edits(edits);
+    replayed.perform_edits(compacted.edits);
+
+    assert!(diff_animations(&original, &replayed) == vec![]);
+}
+
+#[test]
+fn heavily_edited_animation_compacts_to_its_dump() {
+    let animation   = create_animation();
+    let mut edits   = create_layer();
+    edits.extend(select_brush(10));
+
+    // Draw a lot of strokes, renaming the layer after each one
+    for stroke in 0..50 {
+        let x = (stroke as f32) * 10.0;
+
+        edits.push(brush_stroke(100+stroke, (x, 10.0), (x+10.0, 5.0)));
+        edits.push(AnimationEdit::Layer(1, LayerEdit::SetName(format!("Layer {}", stroke))));
+    }
+
+    // Delete most of them, then drag the control points of the rest around
+    for stroke in 0..45 {
+        edits.push(AnimationEdit::Element(vec![ElementId::Assigned(100+stroke)], ElementEdit::Delete));
+    }
+
+    for offset in 0..20 {
+        for stroke in 45..50 {
+            let x = (stroke as f32) * 10.0;
+            let y = offset as f32;
+
+            edits.push(AnimationEdit::Element(vec![ElementId::Assigned(100+stroke)], ElementEdit::SetControlPoints(vec![(x, 10.0), (x+2.0, 12.0+y), (x+8.0, 6.0+y), (x+11.0, 5.0)], Duration::from_millis(0))));
+        }
+    }
+
+    animation.perform_edits(edits.clone());
+
+    // The compacted log is the dump of the animation: the layer, its name, the brush and the five strokes with their final control points
+    let dump        = dump_animation_as_edits(&animation, &edits);
+    let compacted   = executor::block_on(compact_animation_edit_log(&animation, vec![]));
+
+    assert!(dump.len() == 15);
+    assert!(compacted.edits == dump);
+    assert!(animation.get_num_edits() == dump.len());
+
+    // Replaying the compacted log should produce the same animation
+    let replayed    = create_animation();
+    executor::block_on(replay_edit_log(&animation, &replayed, None));
+
+    assert!(diff_animations(&animation, &replayed) == vec![]);
+}
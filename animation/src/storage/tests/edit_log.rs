@@ -121,7 +121,7 @@ fn read_brush_strokes_from_edit_log() {
             )
         )),
         AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::
-            BrushProperties(ElementId::Unassigned, BrushProperties { color: Color::Rgba(0.5, 0.2, 0.7, 1.0), opacity: 1.0, size: 32.0 }))),
+            BrushProperties(ElementId::Unassigned, BrushProperties { color: Color::Rgba(0.5, 0.2, 0.7, 1.0), opacity: 1.0, size: 32.0, fill: BrushFill::Solid }))),
         AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::BrushStroke(ElementId::Unassigned, Arc::new(vec![
                     RawPoint::from((10.0, 10.0)),
                     RawPoint::from((20.0, 5.0))
@@ -155,7 +155,7 @@ fn read_brush_strokes_from_edit_log() {
     assert!(match edits[3] {
         AnimationEdit::Layer(2, LayerEdit::Paint(_when, PaintEdit::
             BrushProperties(ElementId::Assigned(_element_id), ref brush_properties)))
-                => brush_properties == &BrushProperties { color: Color::Rgba(0.5, 0.2, 0.7, 1.0), opacity: 1.0, size: 32.0 },
+                => brush_properties == &BrushProperties { color: Color::Rgba(0.5, 0.2, 0.7, 1.0), opacity: 1.0, size: 32.0, fill: BrushFill::Solid },
             _ => false
     });
     assert!(match edits[6] {
@@ -181,7 +181,7 @@ fn read_element_delete_from_edit_log() {
                 BrushDrawingStyle::Draw
             )
         )),
-        AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::BrushProperties(ElementId::Unassigned, BrushProperties { color: Color::Rgba(0.5, 0.2, 0.7, 1.0), opacity: 1.0, size: 32.0 }))),
+        AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::BrushProperties(ElementId::Unassigned, BrushProperties { color: Color::Rgba(0.5, 0.2, 0.7, 1.0), opacity: 1.0, size: 32.0, fill: BrushFill::Solid }))),
         AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::BrushStroke(ElementId::Assigned(126), Arc::new(vec![
                     RawPoint::from((10.0, 10.0)),
                     RawPoint::from((20.0, 5.0))
@@ -219,7 +219,7 @@ fn will_assign_element_ids() {
                 BrushDrawingStyle::Draw
             )
         )),
-        AnimationEdit::Layer(0, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::BrushProperties(ElementId::Unassigned, BrushProperties { color: Color::Rgba(0.5, 0.2, 0.7, 1.0), opacity: 1.0, size: 32.0 }))),
+        AnimationEdit::Layer(0, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::BrushProperties(ElementId::Unassigned, BrushProperties { color: Color::Rgba(0.5, 0.2, 0.7, 1.0), opacity: 1.0, size: 32.0, fill: BrushFill::Solid }))),
         AnimationEdit::Layer(0, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::BrushStroke(ElementId::Unassigned, Arc::new(vec![
                 RawPoint::from((10.0, 10.0)),
                 RawPoint::from((20.0, 5.0))
@@ -238,3 +238,64 @@ fn will_assign_element_ids() {
     // Element ID should be assigned
     assert!(match &paint_edit[0] { &AnimationEdit::Layer(0, LayerEdit::Paint(_, PaintEdit::BrushStroke(ElementId::Assigned(_), _))) => true, _ => false });
 }
+
+#[test]
+fn create_and_delete_snapshots() {
+    let anim = create_animation();
+
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewLayer(2),
+        AnimationEdit::Layer(2, LayerEdit::AddKeyFrame(Duration::from_millis(0))),
+    ]);
+    anim.create_snapshot("First");
+
+    anim.perform_edits(vec![
+        AnimationEdit::Layer(2, LayerEdit::SetName("Renamed".to_string())),
+    ]);
+    anim.create_snapshot("Second");
+    anim.create_snapshot("Removed");
+    anim.delete_snapshot("Removed");
+
+    assert!(anim.get_snapshots() == vec![
+        EditLogCheckpoint { name: "First".to_string(), edit_index: 2 },
+        EditLogCheckpoint { name: "Second".to_string(), edit_index: 3 }
+    ]);
+}
+
+#[test]
+fn replace_edit_log_replaces_snapshots() {
+    let anim = create_animation();
+
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewLayer(2),
+        AnimationEdit::Layer(2, LayerEdit::AddKeyFrame(Duration::from_millis(0))),
+    ]);
+    anim.create_snapshot("Old");
+
+    anim.replace_edit_log(2, vec![AnimationEdit::AddNewLayer(2)], vec![EditLogCheckpoint { name: "New".to_string(), edit_index: 1 }]);
+
+    assert!(anim.get_num_edits() == 1);
+    assert!(anim.get_snapshots() == vec![EditLogCheckpoint { name: "New".to_string(), edit_index: 1 }]);
+}
+
+#[test]
+fn replace_edit_log_keeps_later_edits() {
+    let anim = create_animation();
+
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewLayer(2),
+        AnimationEdit::Layer(2, LayerEdit::SetName("Old".to_string())),
+        AnimationEdit::Layer(2, LayerEdit::AddKeyFrame(Duration::from_millis(0))),
+    ]);
+    anim.create_snapshot("Later");
+
+    // Replace only the first two edits: the keyframe edit was made after the log was read so should be kept
+    anim.replace_edit_log(2, vec![AnimationEdit::AddNewLayer(2)], vec![]);
+
+    let edit_log        = anim.read_edit_log(0..anim.get_num_edits());
+    let edit_log        = edit_log.collect();
+    let edits: Vec<_>   = executor::block_on(edit_log);
+
+    assert!(edits == vec![AnimationEdit::AddNewLayer(2), AnimationEdit::Layer(2, LayerEdit::AddKeyFrame(Duration::from_millis(0)))]);
+    assert!(anim.get_snapshots() == vec![EditLogCheckpoint { name: "Later".to_string(), edit_index: 2 }]);
+}
@@ -0,0 +1,135 @@
+use super::*;
+
+#[test]
+fn add_layer_folder() {
+    let anim = create_animation();
+
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewLayerFolder(2)
+    ]);
+
+    let folder = anim.get_layer_with_id(2).unwrap();
+    assert!(folder.supported_edit_types() == vec![LayerEditType::Folder]);
+    assert!(folder.parent_folder() == None);
+}
+
+#[test]
+fn folder_contents_follow_folder() {
+    let anim = create_animation();
+
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewLayer(1),
+        AnimationEdit::AddNewLayerFolder(2),
+        AnimationEdit::AddNewLayer(3),
+        AnimationEdit::AddNewLayer(4),
+        AnimationEdit::Layer(4, LayerEdit::SetParent(Some(2)))
+    ]);
+
+    assert!(anim.get_layer_ids() == vec![1, 2, 4, 3]);
+    assert!(anim.get_layer_with_id(4).unwrap().parent_folder() == Some(2));
+}
+
+#[test]
+fn reorder_layers_inside_folder() {
+    let anim = create_animation();
+
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewLayerFolder(1),
+        AnimationEdit::AddNewLayer(2),
+        AnimationEdit::AddNewLayer(3),
+        AnimationEdit::AddNewLayer(4),
+        AnimationEdit::Layer(2, LayerEdit::SetParent(Some(1))),
+        AnimationEdit::Layer(3, LayerEdit::SetParent(Some(1))),
+        AnimationEdit::Layer(3, LayerEdit::SetOrdering(2))
+    ]);
+
+    assert!(anim.get_layer_ids() == vec![1, 3, 2, 4]);
+}
+
+#[test]
+fn move_layer_out_of_folder() {
+    let anim = create_animation();
+
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewLayerFolder(1),
+        AnimationEdit::AddNewLayer(2),
+        AnimationEdit::Layer(2, LayerEdit::SetParent(Some(1))),
+        AnimationEdit::Layer(2, LayerEdit::SetParent(None))
+    ]);
+
+    assert!(anim.get_layer_with_id(2).unwrap().parent_folder() == None);
+}
+
+#[test]
+fn cannot_move_layer_into_normal_layer() {
+    let anim = create_animation();
+
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewLayer(1),
+        AnimationEdit::AddNewLayer(2),
+        AnimationEdit::Layer(2, LayerEdit::SetParent(Some(1)))
+    ]);
+
+    assert!(anim.get_layer_with_id(2).unwrap().parent_folder() == None);
+}
+
+#[test]
+fn cannot_move_folder_inside_itself() {
+    let anim = create_animation();
+
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewLayerFolder(1),
+        AnimationEdit::AddNewLayerFolder(2),
+        AnimationEdit::Layer(2, LayerEdit::SetParent(Some(1))),
+        AnimationEdit::Layer(1, LayerEdit::SetParent(Some(2))),
+        AnimationEdit::Layer(1, LayerEdit::SetParent(Some(1)))
+    ]);
+
+    assert!(anim.get_layer_with_id(1).unwrap().parent_folder() == None);
+    assert!(anim.get_layer_with_id(2).unwrap().parent_folder() == Some(1));
+    assert!(anim.get_layer_ids() == vec![1, 2]);
+}
+
+#[test]
+fn hide_lock_and_collapse() {
+    let anim = create_animation();
+
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewLayerFolder(1),
+        AnimationEdit::Layer(1, LayerEdit::SetHidden(true)),
+        AnimationEdit::Layer(1, LayerEdit::SetLocked(true)),
+        AnimationEdit::Layer(1, LayerEdit::SetCollapsed(true))
+    ]);
+
+    let folder = anim.get_layer_with_id(1).unwrap();
+    assert!(folder.is_hidden());
+    assert!(folder.is_locked());
+    assert!(folder.is_collapsed());
+
+    anim.perform_edits(vec![
+        AnimationEdit::Layer(1, LayerEdit::SetHidden(false)),
+        AnimationEdit::Layer(1, LayerEdit::SetLocked(false))
+    ]);
+
+    let folder = anim.get_layer_with_id(1).unwrap();
+    assert!(!folder.is_hidden());
+    assert!(!folder.is_locked());
+    assert!(folder.is_collapsed());
+}
+
+#[test]
+fn removing_folder_moves_contents_to_its_parent() {
+    let anim = create_animation();
+
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewLayerFolder(1),
+        AnimationEdit::AddNewLayerFolder(2),
+        AnimationEdit::AddNewLayer(3),
+        AnimationEdit::Layer(2, LayerEdit::SetParent(Some(1))),
+        AnimationEdit::Layer(3, LayerEdit::SetParent(Some(2))),
+        AnimationEdit::RemoveLayer(2)
+    ]);
+
+    assert!(anim.get_layer_ids() == vec![1, 3]);
+    assert!(anim.get_layer_with_id(3).unwrap().parent_folder() == Some(1));
+}
@@ -203,6 +203,99 @@ fn remove_keyframe() {
 
 }
 
+#[test]
+fn move_keyframe() {
+    let anim = create_animation();
+
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewLayer(2),
+        AnimationEdit::Layer(2, LayerEdit::AddKeyFrame(Duration::from_millis(0))),
+        AnimationEdit::Layer(2, LayerEdit::AddKeyFrame(Duration::from_millis(250))),
+        AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(300), PaintEdit::SelectBrush(
+                ElementId::Unassigned,
+                BrushDefinition::Ink(InkDefinition::default()),
+                BrushDrawingStyle::Draw
+            )
+        )),
+        AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(300), PaintEdit::
+            BrushProperties(ElementId::Unassigned, BrushProperties::new()))),
+        AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(300), PaintEdit::BrushStroke(ElementId::Assigned(50), Arc::new(vec![
+                    RawPoint::from((10.0, 10.0)),
+                    RawPoint::from((20.0, 5.0))
+                ])))),
+        AnimationEdit::Layer(2, LayerEdit::MoveKeyFrame(Duration::from_millis(250), Duration::from_millis(1000)))
+    ]);
+
+    let layer       = anim.get_layer_with_id(2).unwrap();
+    let keyframes   = layer.get_key_frames_during_time(Duration::from_millis(0)..Duration::from_millis(2000)).collect::<Vec<_>>();
+    assert!(keyframes == vec![Duration::from_millis(0), Duration::from_millis(1000)]);
+
+    // The brush stroke should have moved to the new keyframe, keeping its position relative to the start of the keyframe
+    let frame       = layer.get_frame_at_time(Duration::from_millis(500));
+    let elements    = frame.vector_elements().unwrap().collect::<Vec<_>>();
+    assert!(!elements.iter().any(|element| element.id() == ElementId::Assigned(50)));
+
+    let frame       = layer.get_frame_at_time(Duration::from_millis(1050));
+    let elements    = frame.vector_elements().unwrap().collect::<Vec<_>>();
+    assert!(elements.iter().any(|element| element.id() == ElementId::Assigned(50)));
+}
+
+#[test]
+fn cannot_move_keyframe_over_existing_keyframe() {
+    let anim = create_animation();
+
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewLayer(2),
+        AnimationEdit::Layer(2, LayerEdit::AddKeyFrame(Duration::from_millis(250))),
+        AnimationEdit::Layer(2, LayerEdit::AddKeyFrame(Duration::from_millis(500))),
+        AnimationEdit::Layer(2, LayerEdit::MoveKeyFrame(Duration::from_millis(250), Duration::from_millis(500)))
+    ]);
+
+    let layer       = anim.get_layer_with_id(2).unwrap();
+    let keyframes   = layer.get_key_frames_during_time(Duration::from_millis(0)..Duration::from_millis(1000)).collect::<Vec<_>>();
+    assert!(keyframes == vec![Duration::from_millis(250), Duration::from_millis(500)]);
+}
+
+#[test]
+fn duplicate_keyframe() {
+    let anim = create_animation();
+
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewLayer(2),
+        AnimationEdit::Layer(2, LayerEdit::AddKeyFrame(Duration::from_millis(0))),
+        AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(100), PaintEdit::SelectBrush(
+                ElementId::Unassigned,
+                BrushDefinition::Ink(InkDefinition::default()),
+                BrushDrawingStyle::Draw
+            )
+        )),
+        AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(100), PaintEdit::
+            BrushProperties(ElementId::Unassigned, BrushProperties::new()))),
+        AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(100), PaintEdit::BrushStroke(ElementId::Assigned(50), Arc::new(vec![
+                    RawPoint::from((10.0, 10.0)),
+                    RawPoint::from((20.0, 5.0))
+                ])))),
+        AnimationEdit::Layer(2, LayerEdit::DuplicateKeyFrame(Duration::from_millis(0), Duration::from_millis(500)))
+    ]);
+
+    let layer       = anim.get_layer_with_id(2).unwrap();
+    let keyframes   = layer.get_key_frames_during_time(Duration::from_millis(0)..Duration::from_millis(1000)).collect::<Vec<_>>();
+    assert!(keyframes == vec![Duration::from_millis(0), Duration::from_millis(500)]);
+
+    // Original keyframe is unchanged
+    let frame       = layer.get_frame_at_time(Duration::from_millis(200));
+    let elements    = frame.vector_elements().unwrap().collect::<Vec<_>>();
+    assert!(elements.iter().any(|element| element.id() == ElementId::Assigned(50)));
+
+    // New keyframe has a copy of the brush stroke with a new ID
+    let frame       = layer.get_frame_at_time(Duration::from_millis(600));
+    let elements    = frame.vector_elements().unwrap().collect::<Vec<_>>();
+    let strokes     = elements.iter().filter(|element| match element { Vector::BrushStroke(_) => true, _ => false }).collect::<Vec<_>>();
+    assert!(strokes.len() == 1);
+    assert!(strokes[0].id() != ElementId::Assigned(50));
+    assert!(strokes[0].id().is_assigned());
+}
+
 #[test]
 fn retrieve_keyframe() {
     let anim = create_animation();
@@ -334,3 +427,70 @@ fn get_active_brush() {
 
     assert!(active_brush.is_some());
 }
+
+#[test]
+fn set_reference_layer() {
+    let anim = create_animation();
+
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewLayer(2),
+        AnimationEdit::Layer(2, LayerEdit::SetReference(Some(ReferenceLayerSettings::default())))
+    ]);
+
+    let layer = anim.get_layer_with_id(2).unwrap();
+
+    assert!(layer.reference_settings() == Some(ReferenceLayerSettings::default()));
+    assert!(layer.supported_edit_types() == vec![LayerEditType::Reference]);
+}
+
+#[test]
+fn vector_layer_is_not_reference() {
+    let anim = create_animation();
+
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewLayer(2),
+        AnimationEdit::Layer(2, LayerEdit::SetReference(Some(ReferenceLayerSettings::default()))),
+        AnimationEdit::Layer(2, LayerEdit::SetReference(None))
+    ]);
+
+    let layer = anim.get_layer_with_id(2).unwrap();
+
+    assert!(layer.reference_settings() == None);
+    assert!(layer.supported_edit_types() == vec![LayerEditType::Vector]);
+}
+
+#[test]
+fn reference_image_sequence() {
+    let anim = create_animation();
+
+    let image1 = anim.assign_asset_id();
+    anim.write_asset(image1, Arc::new(vec![1, 2, 3]));
+    let image2 = anim.assign_asset_id();
+    anim.write_asset(image2, Arc::new(vec![4, 5, 6, 7]));
+    let image3 = anim.assign_asset_id();
+    anim.write_asset(image3, Arc::new(vec![8]));
+
+    assert!(image1 != image2 && image2 != image3 && image1 != image3);
+
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewLayer(2),
+        AnimationEdit::Layer(2, LayerEdit::SetReference(Some(ReferenceLayerSettings::default()))),
+        AnimationEdit::Layer(2, LayerEdit::AddReferenceImage(Duration::from_millis(0), image1)),
+        AnimationEdit::Layer(2, LayerEdit::AddReferenceImage(Duration::from_millis(40), image2)),
+        AnimationEdit::Layer(2, LayerEdit::AddReferenceImage(Duration::from_millis(80), image3))
+    ]);
+
+    let layer = anim.get_layer_with_id(2).unwrap();
+
+    assert!(layer.reference_image_at_time(Duration::from_millis(0)) == Some(Arc::new(vec![1, 2, 3])));
+    assert!(layer.reference_image_at_time(Duration::from_millis(60)) == Some(Arc::new(vec![4, 5, 6, 7])));
+    assert!(layer.reference_image_at_time(Duration::from_millis(100)) == Some(Arc::new(vec![8])));
+
+    anim.perform_edits(vec![
+        AnimationEdit::Layer(2, LayerEdit::RemoveReferenceImage(Duration::from_millis(40)))
+    ]);
+
+    let layer = anim.get_layer_with_id(2).unwrap();
+
+    assert!(layer.reference_image_at_time(Duration::from_millis(60)) == Some(Arc::new(vec![1, 2, 3])));
+}
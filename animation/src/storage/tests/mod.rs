@@ -3,6 +3,9 @@ use crate::editor::*;
 use crate::storage::*;
 use futures::*;
 
+use std::sync::*;
+use std::time::{Duration};
+
 mod animation_properties;
 mod layers;
 mod edit_log;
@@ -11,8 +14,16 @@ mod motion;
 mod path;
 mod caching;
 mod collide_paths;
+mod combine_paths;
 mod grouping;
 mod transformation;
+mod replay;
+mod compaction;
+mod archive;
+mod tweening;
+mod symbols;
+mod folders;
+mod bones;
 
 ///
 /// Creates an in-memory animaton for the tests
@@ -24,6 +35,38 @@ pub fn create_animation() -> impl EditableAnimation {
     animation
 }
 
+///
+/// Draws a line on layer 1 of an animation, selecting a new brush first
+///
+pub fn draw_line(anim: &impl EditableAnimation, from: (f32, f32), to: (f32, f32)) {
+    anim.perform_edits(vec![
+        AnimationEdit::Layer(1, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::SelectBrush(ElementId::Unassigned, BrushDefinition::Ink(InkDefinition::default()), BrushDrawingStyle::Draw))),
+        AnimationEdit::Layer(1, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::BrushProperties(ElementId::Unassigned, BrushProperties::new()))),
+        AnimationEdit::Layer(1, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::BrushStroke(ElementId::Unassigned, Arc::new(vec![
+            RawPoint::from(from),
+            RawPoint::from(to)
+        ]))))
+    ]);
+}
+
+///
+/// Creates an animation with a named layer containing two lines, with a snapshot taken after the first line
+///
+pub fn create_source_animation() -> impl EditableAnimation {
+    let anim = create_animation();
+
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewLayer(1),
+        AnimationEdit::Layer(1, LayerEdit::SetName("Test layer".to_string())),
+        AnimationEdit::Layer(1, LayerEdit::AddKeyFrame(Duration::from_millis(0))),
+    ]);
+    draw_line(&anim, (10.0, 10.0), (20.0, 5.0));
+    anim.create_snapshot("One line");
+    draw_line(&anim, (30.0, 10.0), (40.0, 5.0));
+
+    anim
+}
+
 ///
 /// Deserializes some edits and runs them on the animation. The edit string can be generated
 /// by the diagnostics command line tool.
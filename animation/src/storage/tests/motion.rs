@@ -261,3 +261,102 @@ fn delete_motion_element() {
     let attached = anim.motion().get_elements_for_motion(ElementId::Assigned(100));
     assert!(attached == vec![]);
 }
+
+#[test]
+fn move_keyframe_moves_motion() {
+    let anim = create_animation();
+
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewLayer(2),
+        AnimationEdit::Layer(2, LayerEdit::AddKeyFrame(Duration::from_millis(0))),
+        AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::SelectBrush(
+                ElementId::Unassigned,
+                BrushDefinition::Ink(InkDefinition::default()),
+                BrushDrawingStyle::Draw
+            )
+        )),
+        AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::
+            BrushProperties(ElementId::Unassigned, BrushProperties::new()))),
+        AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::BrushStroke(ElementId::Assigned(50), Arc::new(vec![
+                    RawPoint::from((10.0, 10.0)),
+                    RawPoint::from((20.0, 5.0))
+                ])))),
+
+        AnimationEdit::Motion(ElementId::Assigned(100), MotionEdit::Create),
+        AnimationEdit::Motion(ElementId::Assigned(100), MotionEdit::SetType(MotionType::Translate)),
+        AnimationEdit::Motion(ElementId::Assigned(100), MotionEdit::SetOrigin(50.0, 60.0)),
+        AnimationEdit::Motion(ElementId::Assigned(100), MotionEdit::SetPath(TimeCurve::new(TimePoint::new(200.0, 200.0, Duration::from_millis(442)), TimePoint::new(300.0, 200.0, Duration::from_millis(642))))),
+        AnimationEdit::Element(vec![ElementId::Assigned(50)], ElementEdit::AddAttachment(ElementId::Assigned(100))),
+
+        AnimationEdit::Layer(2, LayerEdit::MoveKeyFrame(Duration::from_millis(0), Duration::from_millis(1000)))
+    ]);
+
+    let attached = anim.motion().get_motions_for_element(ElementId::Assigned(50));
+    assert!(attached == vec![ElementId::Assigned(100)]);
+
+    if let Some(Motion::Translate(translate)) = anim.motion().get_motion(ElementId::Assigned(100)) {
+        assert!(translate.translate.points[0].point == TimePoint::new(200.0, 200.0, Duration::from_millis(1442)));
+        assert!(translate.translate.points[1].point == TimePoint::new(300.0, 200.0, Duration::from_millis(1642)));
+    } else {
+        assert!(false)
+    }
+}
+
+#[test]
+fn edit_motion_path_via_control_points() {
+    let anim = create_animation();
+
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewLayer(2),
+        AnimationEdit::Layer(2, LayerEdit::AddKeyFrame(Duration::from_millis(0))),
+        AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::SelectBrush(
+                ElementId::Unassigned,
+                BrushDefinition::Ink(InkDefinition::default()),
+                BrushDrawingStyle::Draw
+            )
+        )),
+        AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::
+            BrushProperties(ElementId::Unassigned, BrushProperties::new()))),
+        AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::BrushStroke(ElementId::Assigned(50), Arc::new(vec![
+                    RawPoint::from((10.0, 10.0)),
+                    RawPoint::from((20.0, 5.0))
+                ])))),
+
+        AnimationEdit::Motion(ElementId::Assigned(100), MotionEdit::Create),
+        AnimationEdit::Motion(ElementId::Assigned(100), MotionEdit::SetType(MotionType::Translate)),
+        AnimationEdit::Motion(ElementId::Assigned(100), MotionEdit::SetOrigin(50.0, 60.0)),
+        AnimationEdit::Motion(ElementId::Assigned(100), MotionEdit::SetPath(TimeCurve::new(TimePoint::new(200.0, 200.0, Duration::from_millis(442)), TimePoint::new(300.0, 200.0, Duration::from_millis(642))))),
+        AnimationEdit::Element(vec![ElementId::Assigned(50)], ElementEdit::AddAttachment(ElementId::Assigned(100)))
+    ]);
+
+    // The motion element's control points are the points along its path
+    let layer           = anim.get_layer_with_id(2).unwrap();
+    let frame           = layer.get_frame_at_time(Duration::from_millis(442));
+    let motion          = frame.element_with_id(ElementId::Assigned(100)).unwrap();
+    let properties      = VectorProperties::default();
+    let control_points  = motion.control_points(&properties);
+
+    assert!(control_points.len() == 4);
+    assert!(control_points[0] == ControlPoint::BezierPoint(200.0, 200.0));
+    assert!(control_points[3] == ControlPoint::BezierPoint(300.0, 200.0));
+
+    // Moving the final point should move where the path ends
+    let new_positions   = vec![control_points[0].position(), control_points[1].position(), control_points[2].position(), (300.0, 250.0)];
+    let adjusted        = motion.with_adjusted_control_points(new_positions, &properties);
+
+    if let Vector::Motion(adjusted) = adjusted {
+        if let Motion::Translate(translate) = &*adjusted.motion() {
+            anim.perform_edits(vec![AnimationEdit::Motion(ElementId::Assigned(100), MotionEdit::SetPath(translate.translate.clone()))]);
+        } else {
+            assert!(false)
+        }
+    } else {
+        assert!(false)
+    }
+
+    if let Some(Motion::Translate(translate)) = anim.motion().get_motion(ElementId::Assigned(100)) {
+        assert!(translate.translate.points[1].point == TimePoint::new(300.0, 250.0, Duration::from_millis(642)));
+    } else {
+        assert!(false)
+    }
+}
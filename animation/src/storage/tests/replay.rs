@@ -0,0 +1,49 @@
+use super::*;
+
+use futures::executor;
+
+#[test]
+fn replayed_animation_matches_original() {
+    let source      = create_source_animation();
+    let target      = create_animation();
+
+    let num_edits   = executor::block_on(replay_edit_log(&source, &target, None));
+
+    assert!(num_edits == source.get_num_edits());
+    assert!(diff_animations(&source, &target) == vec![]);
+}
+
+#[test]
+fn replay_can_stop_early() {
+    let source      = create_source_animation();
+    let target      = create_animation();
+
+    let num_edits   = executor::block_on(replay_edit_log(&source, &target, Some(3)));
+
+    assert!(num_edits == 3);
+    assert!(target.get_num_edits() == 3);
+    assert!(target.get_layer_with_id(1).and_then(|layer| layer.name()) == Some("Test layer".to_string()));
+    assert!(diff_animations(&source, &target) != vec![]);
+}
+
+#[test]
+fn diff_finds_missing_layer() {
+    let source      = create_source_animation();
+    let target      = create_animation();
+
+    assert!(diff_animations(&source, &target) == vec![AnimationDifference::MissingLayer(1)]);
+    assert!(diff_animations(&target, &source) == vec![AnimationDifference::ExtraLayer(1)]);
+}
+
+#[test]
+fn diff_finds_extra_element() {
+    let source      = create_source_animation();
+    let target      = create_animation();
+
+    executor::block_on(replay_edit_log(&source, &target, None));
+    draw_line(&target, (50.0, 10.0), (60.0, 5.0));
+
+    let differences = diff_animations(&source, &target);
+    assert!(differences.len() == 1);
+    assert!(match differences[0] { AnimationDifference::ExtraElement(1, _, _) => true, _ => false });
+}
@@ -0,0 +1,212 @@
+use super::*;
+
+use std::sync::*;
+use std::time::Duration;
+
+///
+/// Creates the edits to paint a brush stroke on a layer
+///
+fn paint_stroke(layer_id: u64, when: Duration, element_id: i64) -> Vec<AnimationEdit> {
+    vec![
+        AnimationEdit::Layer(layer_id, LayerEdit::Paint(when, PaintEdit::SelectBrush(
+                ElementId::Unassigned,
+                BrushDefinition::Ink(InkDefinition::default()),
+                BrushDrawingStyle::Draw
+            )
+        )),
+        AnimationEdit::Layer(layer_id, LayerEdit::Paint(when, PaintEdit::
+            BrushProperties(ElementId::Unassigned, BrushProperties::new()))),
+        AnimationEdit::Layer(layer_id, LayerEdit::Paint(when, PaintEdit::BrushStroke(ElementId::Assigned(element_id), Arc::new(vec![
+                RawPoint::from((10.0, 10.0)),
+                RawPoint::from((20.0, 5.0)),
+                RawPoint::from((30.0, 10.0))
+            ]))))
+    ]
+}
+
+///
+/// Creates an animation with a main layer (1) and a symbol (1) with a single layer (2) that contains a brush stroke (100)
+///
+fn create_symbol() -> impl EditableAnimation {
+    let anim = create_animation();
+
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewLayer(1),
+        AnimationEdit::Layer(1, LayerEdit::AddKeyFrame(Duration::from_millis(0))),
+        AnimationEdit::AddNewSymbol(1),
+        AnimationEdit::Symbol(1, SymbolEdit::AddNewLayer(2)),
+        AnimationEdit::Layer(2, LayerEdit::AddKeyFrame(Duration::from_millis(0)))
+    ]);
+    anim.perform_edits(paint_stroke(2, Duration::from_millis(0), 100));
+
+    anim
+}
+
+///
+/// Retrieves the symbol element with the specified ID from a frame
+///
+fn symbol_element(frame: &Arc<dyn Frame>, element_id: i64) -> Option<SymbolElement> {
+    match frame.element_with_id(ElementId::Assigned(element_id)) {
+        Some(Vector::Symbol(symbol))    => Some(symbol),
+        _                               => None
+    }
+}
+
+///
+/// Retrieves the IDs of the brush strokes in the content of a symbol element
+///
+fn content_ids(symbol: &SymbolElement) -> Vec<i64> {
+    symbol.content().iter()
+        .flat_map(|frame| frame.vector_elements().into_iter().flatten())
+        .filter(|element| match element {
+            Vector::BrushStroke(_)  => true,
+            _                       => false
+        })
+        .filter_map(|element| element.id().id())
+        .collect()
+}
+
+#[test]
+fn symbol_layers_are_not_animation_layers() {
+    let anim    = create_symbol();
+
+    assert!(anim.get_layer_ids() == vec![1]);
+    assert!(anim.get_symbol_ids() == vec![1]);
+
+    let symbol  = anim.get_symbol_with_id(1).unwrap();
+    assert!(symbol.get_layer_ids() == vec![2]);
+    assert!(anim.get_layer_with_id(2).is_some());
+}
+
+#[test]
+fn set_symbol_name_and_length() {
+    let anim = create_symbol();
+
+    anim.perform_edits(vec![
+        AnimationEdit::Symbol(1, SymbolEdit::SetName("Walk cycle".to_string())),
+        AnimationEdit::Symbol(1, SymbolEdit::SetLength(Duration::from_millis(2500)))
+    ]);
+
+    let symbol = anim.get_symbol_with_id(1).unwrap();
+    assert!(symbol.name() == "Walk cycle".to_string());
+    assert!(symbol.length() == Duration::from_millis(2500));
+}
+
+#[test]
+fn instance_displays_symbol_content() {
+    let anim = create_symbol();
+
+    anim.perform_edits(vec![
+        AnimationEdit::Layer(1, LayerEdit::CreateSymbolInstance(Duration::from_millis(0), ElementId::Assigned(200), SymbolInstance::new(1)))
+    ]);
+
+    let layer   = anim.get_layer_with_id(1).unwrap();
+    let frame   = layer.get_frame_at_time(Duration::from_millis(0));
+    let symbol  = symbol_element(&frame, 200).unwrap();
+
+    assert!(symbol.instance().symbol_id == 1);
+    assert!(content_ids(&symbol) == vec![100]);
+}
+
+#[test]
+fn editing_symbol_updates_every_instance() {
+    let anim = create_symbol();
+
+    anim.perform_edits(vec![
+        AnimationEdit::Layer(1, LayerEdit::CreateSymbolInstance(Duration::from_millis(0), ElementId::Assigned(200), SymbolInstance::new(1))),
+        AnimationEdit::Layer(1, LayerEdit::CreateSymbolInstance(Duration::from_millis(0), ElementId::Assigned(201), SymbolInstance::new(1)))
+    ]);
+    anim.perform_edits(paint_stroke(2, Duration::from_millis(0), 101));
+
+    let layer   = anim.get_layer_with_id(1).unwrap();
+    let frame   = layer.get_frame_at_time(Duration::from_millis(0));
+
+    let mut first_content   = content_ids(&symbol_element(&frame, 200).unwrap());
+    let mut second_content  = content_ids(&symbol_element(&frame, 201).unwrap());
+    first_content.sort();
+    second_content.sort();
+
+    assert!(first_content == vec![100, 101]);
+    assert!(second_content == vec![100, 101]);
+}
+
+#[test]
+fn instance_time_offset_picks_symbol_frame() {
+    let anim = create_symbol();
+
+    anim.perform_edits(vec![
+        AnimationEdit::Layer(2, LayerEdit::AddKeyFrame(Duration::from_millis(500)))
+    ]);
+    anim.perform_edits(paint_stroke(2, Duration::from_millis(500), 101));
+
+    let mut instance        = SymbolInstance::new(1);
+    instance.loop_mode      = SymbolLoopMode::SingleFrame;
+    instance.time_offset    = Duration::from_millis(600);
+
+    anim.perform_edits(vec![
+        AnimationEdit::Layer(1, LayerEdit::CreateSymbolInstance(Duration::from_millis(0), ElementId::Assigned(200), instance))
+    ]);
+
+    let layer   = anim.get_layer_with_id(1).unwrap();
+    let frame   = layer.get_frame_at_time(Duration::from_millis(0));
+    let symbol  = symbol_element(&frame, 200).unwrap();
+
+    assert!(content_ids(&symbol) == vec![101]);
+}
+
+#[test]
+fn set_symbol_instance() {
+    let anim = create_symbol();
+
+    anim.perform_edits(vec![
+        AnimationEdit::Layer(1, LayerEdit::CreateSymbolInstance(Duration::from_millis(0), ElementId::Assigned(200), SymbolInstance::new(1)))
+    ]);
+
+    let mut instance        = SymbolInstance::new(1);
+    instance.playback_rate  = 0.5;
+    instance.loop_mode      = SymbolLoopMode::PlayOnce;
+
+    anim.perform_edits(vec![
+        AnimationEdit::Element(vec![ElementId::Assigned(200)], ElementEdit::SetSymbolInstance(instance.clone()))
+    ]);
+
+    let layer   = anim.get_layer_with_id(1).unwrap();
+    let frame   = layer.get_frame_at_time(Duration::from_millis(0));
+    let symbol  = symbol_element(&frame, 200).unwrap();
+
+    assert!(symbol.instance() == &instance);
+}
+
+#[test]
+fn remove_symbol_removes_its_layers() {
+    let anim = create_symbol();
+
+    anim.perform_edits(vec![
+        AnimationEdit::RemoveSymbol(1)
+    ]);
+
+    assert!(anim.get_symbol_ids().is_empty());
+    assert!(anim.get_symbol_with_id(1).is_none());
+    assert!(anim.get_layer_with_id(2).is_none());
+    assert!(anim.get_layer_ids() == vec![1]);
+}
+
+#[test]
+fn symbol_containing_itself_is_displayed_once() {
+    let anim = create_symbol();
+
+    anim.perform_edits(vec![
+        AnimationEdit::Layer(2, LayerEdit::CreateSymbolInstance(Duration::from_millis(0), ElementId::Assigned(300), SymbolInstance::new(1))),
+        AnimationEdit::Layer(1, LayerEdit::CreateSymbolInstance(Duration::from_millis(0), ElementId::Assigned(200), SymbolInstance::new(1)))
+    ]);
+
+    let layer   = anim.get_layer_with_id(1).unwrap();
+    let frame   = layer.get_frame_at_time(Duration::from_millis(0));
+    let symbol  = symbol_element(&frame, 200).unwrap();
+
+    assert!(content_ids(&symbol) == vec![100]);
+
+    // The instance inside the symbol is an instance of the symbol that's being loaded, so it has no content
+    let nested  = symbol.content().iter().filter_map(|frame| symbol_element(frame, 300)).next().unwrap();
+    assert!(nested.content().is_empty());
+}
@@ -238,3 +238,58 @@ fn align_paths_center() {
         assert!(false);
     }
 }
+
+#[test]
+fn distribute_paths_horizontally() {
+    use self::LayerEdit::*;
+
+    let anim = create_animation();
+
+    let square_at = |x: f64| Arc::new(vec![
+        PathComponent::Move(PathPoint::new(x, 0.0)),
+        PathComponent::Line(PathPoint::new(x+10.0, 0.0)),
+        PathComponent::Line(PathPoint::new(x+10.0, 10.0)),
+        PathComponent::Line(PathPoint::new(x, 10.0)),
+        PathComponent::Close
+    ]);
+
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewLayer(24),
+        AnimationEdit::Layer(24, LayerEdit::AddKeyFrame(Duration::from_millis(300))),
+        AnimationEdit::Layer(24, Path(Duration::from_millis(300),
+            PathEdit::SelectBrush(ElementId::Unassigned, BrushDefinition::Ink(InkDefinition::default()), BrushDrawingStyle::Draw))),
+        AnimationEdit::Layer(24, Path(Duration::from_millis(300),
+            PathEdit::BrushProperties(ElementId::Unassigned, BrushProperties::new()))),
+
+        AnimationEdit::Layer(24, Path(Duration::from_millis(300),
+            PathEdit::CreatePath(ElementId::Assigned(100), square_at(0.0)))),
+        AnimationEdit::Layer(24, Path(Duration::from_millis(300),
+            PathEdit::CreatePath(ElementId::Assigned(101), square_at(15.0)))),
+        AnimationEdit::Layer(24, Path(Duration::from_millis(300),
+            PathEdit::CreatePath(ElementId::Assigned(102), square_at(50.0)))),
+
+        AnimationEdit::Element(vec![ElementId::Assigned(100), ElementId::Assigned(101), ElementId::Assigned(102)], ElementEdit::Transform(vec![ElementTransform::DistributeHorizontally]))
+    ]);
+
+    let layer               = anim.get_layer_with_id(24).unwrap();
+    let frame               = layer.get_frame_at_time(Duration::from_millis(300));
+
+    let attachments         = frame.attached_elements(ElementId::Assigned(101));
+
+    // The middle element should move so the gaps on either side of it are the same size
+    assert!(attachments.len() == 1);
+    let attached_element    =  frame.element_with_id(attachments[0].0).unwrap();
+
+    if let Vector::Transformation((_, transform)) = attached_element {
+        assert!(transform.len() == 1);
+
+        if let Transformation::Translate(x, y) = transform[0] {
+            assert!((x- 10.0).abs() < 0.001);
+            assert!((y- 0.0).abs() < 0.001);
+        } else {
+            assert!(false);
+        }
+    } else {
+        assert!(false);
+    }
+}
@@ -0,0 +1,120 @@
+use super::*;
+
+use std::sync::*;
+use std::time::Duration;
+
+///
+/// Creates an animation with two keyframes each containing a brush stroke, where the second is 100 units below the first
+///
+fn create_two_keyframes() -> impl EditableAnimation {
+    let anim = create_animation();
+
+    let paint_stroke = |when: Duration, element_id: i64, offset: f32| {
+        vec![
+            AnimationEdit::Layer(1, LayerEdit::Paint(when, PaintEdit::SelectBrush(
+                    ElementId::Unassigned,
+                    BrushDefinition::Ink(InkDefinition::default()),
+                    BrushDrawingStyle::Draw
+                )
+            )),
+            AnimationEdit::Layer(1, LayerEdit::Paint(when, PaintEdit::
+                BrushProperties(ElementId::Unassigned, BrushProperties::new()))),
+            AnimationEdit::Layer(1, LayerEdit::Paint(when, PaintEdit::BrushStroke(ElementId::Assigned(element_id), Arc::new(vec![
+                    RawPoint::from((10.0, 10.0 + offset)),
+                    RawPoint::from((20.0, 5.0 + offset)),
+                    RawPoint::from((30.0, 10.0 + offset))
+                ]))))
+        ]
+    };
+
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewLayer(1),
+        AnimationEdit::Layer(1, LayerEdit::AddKeyFrame(Duration::from_millis(0))),
+        AnimationEdit::Layer(1, LayerEdit::AddKeyFrame(Duration::from_millis(1000)))
+    ]);
+    anim.perform_edits(paint_stroke(Duration::from_millis(0), 100, 0.0));
+    anim.perform_edits(paint_stroke(Duration::from_millis(1000), 101, 100.0));
+
+    anim
+}
+
+///
+/// Retrieves the points in the brush stroke with the specified ID from a frame
+///
+fn stroke_points(frame: &Arc<dyn Frame>, element_id: i64) -> Vec<BrushPoint> {
+    match frame.element_with_id(ElementId::Assigned(element_id)) {
+        Some(Vector::BrushStroke(stroke))   => (*stroke.points()).clone(),
+        _                                   => vec![]
+    }
+}
+
+#[test]
+fn untweened_keyframe_is_unchanged() {
+    let anim        = create_two_keyframes();
+    let layer       = anim.get_layer_with_id(1).unwrap();
+
+    let start       = stroke_points(&layer.get_frame_at_time(Duration::from_millis(0)), 100);
+    let in_between  = stroke_points(&layer.get_frame_at_time(Duration::from_millis(500)), 100);
+
+    assert!(!start.is_empty());
+    assert!(layer.key_frame_tween(Duration::from_millis(0)).is_none());
+    assert!(start == in_between);
+}
+
+#[test]
+fn tween_brush_stroke_between_keyframes() {
+    let anim = create_two_keyframes();
+
+    anim.perform_edits(vec![
+        AnimationEdit::Layer(1, LayerEdit::SetKeyFrameTween(Duration::from_millis(0), Some(TweenEasing::Linear)))
+    ]);
+
+    let layer       = anim.get_layer_with_id(1).unwrap();
+    assert!(layer.key_frame_tween(Duration::from_millis(0)) == Some(TweenEasing::Linear));
+
+    let start       = stroke_points(&layer.get_frame_at_time(Duration::from_millis(0)), 100);
+    let in_between  = stroke_points(&layer.get_frame_at_time(Duration::from_millis(500)), 100);
+
+    // The strokes are the same shape, so the tweened stroke should be half-way between them
+    assert!(!start.is_empty());
+    assert!(start.len() == in_between.len());
+    for (start_point, tween_point) in start.iter().zip(in_between.iter()) {
+        assert!((tween_point.position.0 - start_point.position.0).abs() < 0.1);
+        assert!((tween_point.position.1 - (start_point.position.1 + 50.0)).abs() < 0.1);
+    }
+}
+
+#[test]
+fn drawing_on_in_between_frame_stops_tween() {
+    let anim = create_two_keyframes();
+
+    anim.perform_edits(vec![
+        AnimationEdit::Layer(1, LayerEdit::SetKeyFrameTween(Duration::from_millis(0), Some(TweenEasing::Linear))),
+        AnimationEdit::Layer(1, LayerEdit::Paint(Duration::from_millis(600), PaintEdit::BrushStroke(ElementId::Assigned(102), Arc::new(vec![
+                RawPoint::from((50.0, 50.0)),
+                RawPoint::from((60.0, 60.0))
+            ]))))
+    ]);
+
+    let layer       = anim.get_layer_with_id(1).unwrap();
+    let start       = stroke_points(&layer.get_frame_at_time(Duration::from_millis(0)), 100);
+    let before      = stroke_points(&layer.get_frame_at_time(Duration::from_millis(500)), 100);
+    let after       = stroke_points(&layer.get_frame_at_time(Duration::from_millis(700)), 100);
+
+    assert!(start != before);
+    assert!(start == after);
+}
+
+#[test]
+fn tween_moves_with_keyframe() {
+    let anim = create_two_keyframes();
+
+    anim.perform_edits(vec![
+        AnimationEdit::Layer(1, LayerEdit::SetKeyFrameTween(Duration::from_millis(0), Some(TweenEasing::EaseIn))),
+        AnimationEdit::Layer(1, LayerEdit::MoveKeyFrame(Duration::from_millis(0), Duration::from_millis(200)))
+    ]);
+
+    let layer = anim.get_layer_with_id(1).unwrap();
+    assert!(layer.key_frame_tween(Duration::from_millis(0)).is_none());
+    assert!(layer.key_frame_tween(Duration::from_millis(200)) == Some(TweenEasing::EaseIn));
+}
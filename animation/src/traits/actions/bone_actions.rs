@@ -0,0 +1,57 @@
+use super::edit_action::*;
+use super::super::edit::*;
+use super::super::animation::*;
+
+///
+/// Edit actions that build skeletons out of bones
+///
+#[derive(Clone, PartialEq, Debug)]
+pub enum BoneEditAction {
+    /// Adds a new bone that runs from one point to another, and binds a set of elements to it
+    ///
+    /// The new bone is connected to the parent bone if there is one. The points should be in the coordinates of the
+    /// unposed skeleton.
+    CreateBone(Option<ElementId>, (f32, f32), (f32, f32), Vec<ElementId>)
+}
+
+impl EditAction for BoneEditAction {
+    ///
+    /// Converts this edit action into a set of animation edits for a particular animation
+    ///
+    fn to_animation_edits<Anim: EditableAnimation>(&self, animation: &Anim) -> Vec<AnimationEdit> {
+        use self::BoneEditAction::*;
+
+        match self {
+            CreateBone(parent, from, to, elements)  => create_bone_edit(animation, *parent, *from, *to, elements)
+        }
+    }
+}
+
+///
+/// Generates the edits to create a new bone
+///
+fn create_bone_edit<Anim: EditableAnimation>(animation: &Anim, parent: Option<ElementId>, from: (f32, f32), to: (f32, f32), elements: &Vec<ElementId>) -> Vec<AnimationEdit> {
+    let bone_id         = animation.assign_element_id();
+    let (dx, dy)        = ((to.0 - from.0) as f64, (to.1 - from.1) as f64);
+
+    // The bone points from the 'from' point to the 'to' point
+    let create_bone     = vec![
+        BoneEdit::Create,
+        BoneEdit::SetOrigin(from.0, from.1),
+        BoneEdit::SetLength((dx*dx + dy*dy).sqrt() as f32),
+        BoneEdit::SetAngle(dy.atan2(dx))
+    ];
+    let set_parent      = parent.map(|parent| BoneEdit::SetParent(Some(parent)));
+
+    // Bind the elements to the new bone
+    let create_bone     = create_bone.into_iter().chain(set_parent).map(|bone_edit| AnimationEdit::Bone(bone_id, bone_edit));
+    let bind_elements   = if elements.len() > 0 {
+        Some(AnimationEdit::Element(elements.clone(), ElementEdit::AddAttachment(bone_id)))
+    } else {
+        None
+    };
+
+    create_bone
+        .chain(bind_elements)
+        .collect()
+}
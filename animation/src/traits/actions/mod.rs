@@ -5,6 +5,8 @@
 
 mod edit_action;
 mod motion_actions;
+mod bone_actions;
 
 pub use self::edit_action::*;
 pub use self::motion_actions::*;
+pub use self::bone_actions::*;
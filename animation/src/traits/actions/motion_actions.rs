@@ -197,15 +197,24 @@ mod test {
             fn frame_length(&self) -> Duration { unimplemented!() }
             fn get_layer_ids(&self) -> Vec<u64> { unimplemented!() }
             fn get_layer_with_id<'a>(&'a self, _layer_id: u64) -> Option<Arc<dyn Layer>> { unimplemented!() }
+            fn get_symbol_ids(&self) -> Vec<u64> { unimplemented!() }
+            fn get_symbol_with_id(&self, _symbol_id: u64) -> Option<Arc<dyn Symbol>> { unimplemented!() }
             fn get_num_edits(&self) -> usize { unimplemented!() }
             fn read_edit_log<'a>(&'a self, _range: Range<usize>) -> BoxStream<'a, AnimationEdit> { unimplemented!() }
+            fn get_snapshots(&self) -> Vec<EditLogCheckpoint> { unimplemented!() }
+            fn get_asset(&self, _asset_id: u64) -> Option<Arc<Vec<u8>>> { unimplemented!() }
             fn motion<'a>(&'a self) -> &'a dyn AnimationMotion { self }
         }
 
         impl EditableAnimation for TestAnimation {
             fn edit(&self) -> Publisher<Arc<Vec<AnimationEdit>>> { unimplemented!() }
             fn perform_edits(&self, _edits: Vec<AnimationEdit>) { unimplemented!() }
+            fn replace_edit_log(&self, _num_replaced: usize, _edits: Vec<AnimationEdit>, _snapshots: Vec<EditLogCheckpoint>) { unimplemented!() }
+            fn create_snapshot(&self, _name: &str) { unimplemented!() }
+            fn delete_snapshot(&self, _name: &str) { unimplemented!() }
             fn flush_caches(&self) { unimplemented!() }
+            fn assign_asset_id(&self) -> u64 { unimplemented!() }
+            fn write_asset(&self, _asset_id: u64, _data: Arc<Vec<u8>>) { unimplemented!() }
 
             fn assign_element_id(&self) -> ElementId {
                 ElementId::Assigned(42)
@@ -258,15 +267,24 @@ mod test {
             fn frame_length(&self) -> Duration { unimplemented!() }
             fn get_layer_ids(&self) -> Vec<u64> { unimplemented!() }
             fn get_layer_with_id<'a>(&'a self, _layer_id: u64) -> Option<Arc<dyn Layer>> { unimplemented!() }
+            fn get_symbol_ids(&self) -> Vec<u64> { unimplemented!() }
+            fn get_symbol_with_id(&self, _symbol_id: u64) -> Option<Arc<dyn Symbol>> { unimplemented!() }
             fn get_num_edits(&self) -> usize { unimplemented!() }
             fn read_edit_log<'a>(&'a self, _range: Range<usize>) -> BoxStream<'a, AnimationEdit> { unimplemented!() }
+            fn get_snapshots(&self) -> Vec<EditLogCheckpoint> { unimplemented!() }
+            fn get_asset(&self, _asset_id: u64) -> Option<Arc<Vec<u8>>> { unimplemented!() }
             fn motion<'a>(&'a self) -> &'a dyn AnimationMotion { self }
         }
 
         impl EditableAnimation for TestAnimation {
             fn edit(&self) -> Publisher<Arc<Vec<AnimationEdit>>> { unimplemented!() }
             fn perform_edits(&self, _edits: Vec<AnimationEdit>) { unimplemented!() }
+            fn replace_edit_log(&self, _num_replaced: usize, _edits: Vec<AnimationEdit>, _snapshots: Vec<EditLogCheckpoint>) { unimplemented!() }
+            fn create_snapshot(&self, _name: &str) { unimplemented!() }
+            fn delete_snapshot(&self, _name: &str) { unimplemented!() }
             fn flush_caches(&self) { unimplemented!() }
+            fn assign_asset_id(&self) -> u64 { unimplemented!() }
+            fn write_asset(&self, _asset_id: u64, _data: Arc<Vec<u8>>) { unimplemented!() }
 
             fn assign_element_id(&self) -> ElementId {
                 ElementId::Assigned(43)
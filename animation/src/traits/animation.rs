@@ -1,6 +1,8 @@
 use super::edit::*;
 use super::layer::*;
+use super::symbol::*;
 use super::animation_motion::*;
+use super::edit_log_checkpoint::*;
 
 use flo_stream::*;
 
@@ -32,13 +34,25 @@ pub trait Animation : Send+Sync {
     ///
     /// Retrieves the IDs of the layers in this object
     ///
+    /// This doesn't include the layers that belong to symbols (which can be found via `get_symbol_with_id`)
+    ///
     fn get_layer_ids(&self) -> Vec<u64>;
 
     ///
-    /// Retrieves the layer with the specified ID from this animation
+    /// Retrieves the layer with the specified ID from this animation (which can be a layer belonging to a symbol)
     ///
     fn get_layer_with_id(&self, layer_id: u64) -> Option<Arc<dyn Layer>>;
 
+    ///
+    /// Retrieves the IDs of the symbols in this animation
+    ///
+    fn get_symbol_ids(&self) -> Vec<u64>;
+
+    ///
+    /// Retrieves the symbol with the specified ID from this animation
+    ///
+    fn get_symbol_with_id(&self, symbol_id: u64) -> Option<Arc<dyn Symbol>>;
+
     ///
     /// Retrieves the total number of items that have been performed on this animation
     ///
@@ -49,6 +63,16 @@ pub trait Animation : Send+Sync {
     ///
     fn read_edit_log<'a>(&'a self, range: Range<usize>) -> BoxStream<'a, AnimationEdit>;
 
+    ///
+    /// Retrieves the named snapshots of the edit log that are stored with this animation
+    ///
+    fn get_snapshots(&self) -> Vec<EditLogCheckpoint>;
+
+    ///
+    /// Retrieves the data for an asset stored with this animation (such as the PNG data for a reference image)
+    ///
+    fn get_asset(&self, asset_id: u64) -> Option<Arc<Vec<u8>>>;
+
     ///
     /// Supplies a reference which can be used to find the motions associated with this animation
     ///
@@ -66,6 +90,20 @@ pub trait EditableAnimation : Animation+Send+Sync {
     ///
     fn assign_element_id(&self) -> ElementId;
 
+    ///
+    /// Assigns a new unique ID for storing an asset
+    ///
+    fn assign_asset_id(&self) -> u64;
+
+    ///
+    /// Stores the data for an asset with this animation
+    ///
+    /// Assets are stored outside of the edit log so large items such as images are only stored once: edits
+    /// such as `LayerEdit::AddReferenceImage` refer to them by ID. The asset should be written before any
+    /// edits that use it are performed.
+    ///
+    fn write_asset(&self, asset_id: u64, data: Arc<Vec<u8>>);
+
     ///
     /// Retrieves a sink that can be used to send edits for this animation
     ///
@@ -81,6 +119,28 @@ pub trait EditableAnimation : Animation+Send+Sync {
     ///
     fn perform_edits(&self, edits: Vec<AnimationEdit>);
 
+    ///
+    /// Replaces the first `num_replaced` edits in the edit log for this animation without changing the animation itself
+    ///
+    /// The new edits must produce the same animation as the edits they replace: this is used to compact the log. Any
+    /// edits after the replaced ones (for example, edits made while the replacement was being generated) are kept after
+    /// the new edits. The snapshots stored with the animation are replaced with the specified set, which should refer
+    /// to the new log, except for any snapshots of the kept edits, which are moved to match their new position.
+    ///
+    fn replace_edit_log(&self, num_replaced: usize, edits: Vec<AnimationEdit>, snapshots: Vec<EditLogCheckpoint>);
+
+    ///
+    /// Stores a named snapshot of the animation as it is after the edits that have been performed so far
+    ///
+    /// Any existing snapshot with the same name is replaced.
+    ///
+    fn create_snapshot(&self, name: &str);
+
+    ///
+    /// Removes a named snapshot from this animation
+    ///
+    fn delete_snapshot(&self, name: &str);
+
     ///
     /// Flushes any caches this might have (forces reload from data storage)
     ///
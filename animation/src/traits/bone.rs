@@ -0,0 +1,211 @@
+use super::edit::*;
+use super::vector::*;
+
+use std::sync::*;
+use std::collections::{BTreeMap, HashSet};
+use std::time::{Duration};
+
+///
+/// A bone in a 2D skeleton, used to pose cut-out animations
+///
+/// Bones are posed by rotating them around their origin. Rotating a bone also moves any bones that have it
+/// as their parent. Elements are bound rigidly to a bone by attaching the bone to them.
+///
+#[derive(Clone, PartialEq, Debug)]
+pub struct Bone {
+    /// The bone that this bone is connected to, if there is one
+    pub parent: Option<ElementId>,
+
+    /// The point that this bone rotates around (in the coordinates of the unposed skeleton)
+    pub origin: (f32, f32),
+
+    /// The length of this bone
+    pub length: f32,
+
+    /// The direction this bone points in when it's not posed, in radians
+    pub angle: f64,
+
+    /// The rotation of this bone relative to its rest position at particular times, in radians
+    pub rotations: BTreeMap<Duration, f64>
+}
+
+impl Bone {
+    ///
+    /// Creates a new bone with no parent, at the origin and with no length
+    ///
+    pub fn new() -> Bone {
+        Bone {
+            parent:     None,
+            origin:     (0.0, 0.0),
+            length:     0.0,
+            angle:      0.0,
+            rotations:  BTreeMap::new()
+        }
+    }
+
+    ///
+    /// The position of the end of this bone when it's not posed
+    ///
+    pub fn tip(&self) -> (f32, f32) {
+        let (x, y)  = self.origin;
+        let length  = self.length as f64;
+
+        (x + (self.angle.cos()*length) as f32, y + (self.angle.sin()*length) as f32)
+    }
+
+    ///
+    /// Retrieves the rotation of this bone at a particular time
+    ///
+    /// Rotations are interpolated linearly between keyframes, and hold their value before the first and after the last keyframe.
+    ///
+    pub fn rotation_at(&self, when: Duration) -> f64 {
+        let before  = self.rotations.range(..=when).next_back();
+        let after   = self.rotations.range(when..).next();
+
+        match (before, after) {
+            (None, None)                                    => 0.0,
+            (Some((_, angle)), None)                        |
+            (None, Some((_, angle)))                        => *angle,
+            (Some((start, from)), Some((end, to)))          => {
+                if start == end {
+                    *from
+                } else {
+                    let ratio = ((when - *start).as_micros() as f64) / ((*end - *start).as_micros() as f64);
+                    from + (to - from) * ratio
+                }
+            }
+        }
+    }
+
+    ///
+    /// Returns the transformation that poses this bone at a particular time (or None if the bone is at rest)
+    ///
+    pub fn transformation(&self, when: Duration) -> Option<Transformation> {
+        let rotation = self.rotation_at(when);
+
+        if rotation == 0.0 {
+            None
+        } else {
+            Some(Transformation::Rotate(rotation, (self.origin.0 as f64, self.origin.1 as f64)))
+        }
+    }
+
+    ///
+    /// Creates a copy of this bone with the times of its rotations moved by the specified number of milliseconds
+    ///
+    pub fn with_time_offset(&self, offset_millis: f64) -> Bone {
+        let rotations = self.rotations.iter()
+            .map(|(when, rotation)| {
+                let when_micros = (when.as_micros() as f64) + offset_millis * 1000.0;
+                (Duration::from_micros(when_micros.max(0.0) as u64), *rotation)
+            })
+            .collect();
+
+        Bone {
+            parent:     self.parent,
+            origin:     self.origin,
+            length:     self.length,
+            angle:      self.angle,
+            rotations:  rotations
+        }
+    }
+}
+
+///
+/// Returns the transformations that pose something bound to a bone, in the order they should be applied
+///
+/// The bone's own rotation is applied first, followed by the rotations of each of its parents in turn (this is
+/// forward kinematics: the origin of a child bone is carried around by the rotation of its parent). Parents are
+/// found using the `find_bone` function, and the chain stops at any bone that can't be found.
+///
+pub fn skeleton_transformations<FindBone>(bone_id: ElementId, when: Duration, find_bone: FindBone) -> Vec<Transformation>
+where FindBone: Fn(ElementId) -> Option<Arc<Bone>> {
+    let mut transformations = vec![];
+    let mut visited         = HashSet::new();
+    let mut next_bone       = Some(bone_id);
+
+    while let Some(bone_id) = next_bone {
+        // Stop if a bad file has made a loop of bones
+        if !visited.insert(bone_id) {
+            break;
+        }
+
+        let bone = match find_bone(bone_id) {
+            Some(bone)  => bone,
+            None        => { break; }
+        };
+
+        transformations.extend(bone.transformation(when));
+        next_bone = bone.parent;
+    }
+
+    transformations
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use std::collections::{HashMap};
+
+    #[test]
+    fn interpolate_rotation() {
+        let mut bone = Bone::new();
+        bone.rotations.insert(Duration::from_millis(1000), 1.0);
+        bone.rotations.insert(Duration::from_millis(3000), 2.0);
+
+        assert!(bone.rotation_at(Duration::from_millis(0)) == 1.0);
+        assert!(bone.rotation_at(Duration::from_millis(1000)) == 1.0);
+        assert!((bone.rotation_at(Duration::from_millis(2000)) - 1.5).abs() < 0.0001);
+        assert!(bone.rotation_at(Duration::from_millis(3000)) == 2.0);
+        assert!(bone.rotation_at(Duration::from_millis(5000)) == 2.0);
+    }
+
+    #[test]
+    fn unposed_bone_has_no_transformation() {
+        let bone = Bone::new();
+
+        assert!(bone.rotation_at(Duration::from_millis(1000)) == 0.0);
+        assert!(bone.transformation(Duration::from_millis(1000)).is_none());
+    }
+
+    #[test]
+    fn child_rotation_comes_before_parent() {
+        let mut parent          = Bone::new();
+        parent.rotations.insert(Duration::from_millis(0), 1.0);
+
+        let mut child           = Bone::new();
+        child.parent            = Some(ElementId::Assigned(1));
+        child.origin            = (10.0, 0.0);
+        child.rotations.insert(Duration::from_millis(0), 0.5);
+
+        let mut bones           = HashMap::new();
+        bones.insert(ElementId::Assigned(1), Arc::new(parent));
+        bones.insert(ElementId::Assigned(2), Arc::new(child));
+
+        let transformations     = skeleton_transformations(ElementId::Assigned(2), Duration::from_millis(0), |bone_id| bones.get(&bone_id).cloned());
+
+        assert!(transformations == vec![Transformation::Rotate(0.5, (10.0, 0.0)), Transformation::Rotate(1.0, (0.0, 0.0))]);
+    }
+
+    #[test]
+    fn bone_loops_stop() {
+        let mut bone            = Bone::new();
+        bone.parent             = Some(ElementId::Assigned(1));
+        bone.rotations.insert(Duration::from_millis(0), 1.0);
+
+        let bone                = Arc::new(bone);
+        let transformations     = skeleton_transformations(ElementId::Assigned(1), Duration::from_millis(0), |_| Some(Arc::clone(&bone)));
+
+        assert!(transformations.len() == 1);
+    }
+
+    #[test]
+    fn move_rotations_in_time() {
+        let mut bone = Bone::new();
+        bone.rotations.insert(Duration::from_millis(1000), 1.0);
+
+        let moved = bone.with_time_offset(500.0);
+        assert!(moved.rotations.keys().cloned().collect::<Vec<_>>() == vec![Duration::from_millis(1500)]);
+    }
+}
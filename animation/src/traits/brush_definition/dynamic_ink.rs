@@ -0,0 +1,54 @@
+use std::f32;
+
+///
+/// Dynamic ink brushes are solid lines whose width and opacity respond to how the pen is used.
+/// The actual behaviour is implemented by the `DynamicInkBrush` structure.
+///
+#[derive(Clone, Serialize, Deserialize, PartialEq, Debug)]
+pub struct DynamicInkDefinition {
+    /// Width at pressure 0%
+    pub min_width: f32,
+
+    /// Width at pressure 100%
+    pub max_width: f32,
+
+    /// How much light pressure reduces the opacity of the stroke (0 = pressure has no effect on opacity, 1 = zero pressure is transparent)
+    pub pressure_opacity: f32,
+
+    /// The angle of the nib in radians, used when tilt is not available or is not being used
+    pub nib_angle: f32,
+
+    /// True if the tilt of the pen should set the angle of the nib
+    pub tilt_nib_angle: bool,
+
+    /// How flat the nib is (0 = round nib, with the same width in all directions, 1 = flat nib, which is thinnest when moving along the nib angle)
+    pub calligraphy: f32,
+
+    /// How much the stroke thins when the pen is moving quickly (0 = no thinning, 1 = fast strokes thin to the minimum width)
+    pub velocity_thinning: f32,
+
+    /// Distance to scale up at the start of the brush stroke
+    pub taper_in: f32,
+
+    /// Distance to scale down at the end of the brush stroke
+    pub taper_out: f32
+}
+
+impl DynamicInkDefinition {
+    ///
+    /// Creates the default dynamic ink definition
+    ///
+    pub fn default() -> DynamicInkDefinition {
+        DynamicInkDefinition {
+            min_width:          0.25,
+            max_width:          5.0,
+            pressure_opacity:   0.0,
+            nib_angle:          f32::consts::PI / 4.0,
+            tilt_nib_angle:     false,
+            calligraphy:        0.0,
+            velocity_thinning:  0.0,
+            taper_in:           40.0,
+            taper_out:          0.0
+        }
+    }
+}
@@ -1,6 +1,8 @@
 mod ink;
+mod dynamic_ink;
 
 pub use self::ink::*;
+pub use self::dynamic_ink::*;
 
 ///
 /// Stores the definition of a particular brush
@@ -11,5 +13,8 @@ pub enum BrushDefinition {
     Simple,
 
     /// An ink brush with a particular definition
-    Ink(InkDefinition)
+    Ink(InkDefinition),
+
+    /// An ink brush whose width and opacity respond to pressure, tilt and the speed of the pen
+    DynamicInk(DynamicInkDefinition)
 }
@@ -1,5 +1,25 @@
 use flo_canvas::*;
 
+use std::hash::{Hash, Hasher};
+use std::collections::hash_map::DefaultHasher;
+
+///
+/// How the area covered by a brush stroke is filled
+///
+/// Gradients run from the brush colour to the end colour. Coordinates are in the same space as the brush stroke.
+///
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum BrushFill {
+    /// Fills with the brush colour
+    Solid,
+
+    /// Fills with a gradient that changes along the line between two points
+    LinearGradient { start: (f32, f32), end: (f32, f32), end_color: Color },
+
+    /// Fills with a gradient that changes outwards from a center point
+    RadialGradient { center: (f32, f32), radius: f32, end_color: Color }
+}
+
 ///
 /// Standard properties for a brush stroke
 ///
@@ -15,7 +35,10 @@ pub struct BrushProperties {
     pub opacity: f32,
 
     /// The colour of the brush stroke
-    pub color: Color
+    pub color: Color,
+
+    /// How the brush stroke is filled
+    pub fill: BrushFill
 }
 
 impl BrushProperties {
@@ -26,7 +49,60 @@ impl BrushProperties {
         BrushProperties {
             size:       5.0,
             opacity:    1.0,
-            color:      Color::Rgba(0.0, 0.0, 0.0, 1.0)
+            color:      Color::Rgba(0.0, 0.0, 0.0, 1.0),
+            fill:       BrushFill::Solid
+        }
+    }
+}
+
+impl BrushFill {
+    ///
+    /// Returns the drawing instructions to set up this fill for a brush, with the specified start colour
+    ///
+    pub fn to_drawing(&self, start_color: Color, opacity: f32) -> Vec<Draw> {
+        match self {
+            BrushFill::Solid                                        => vec![Draw::FillColor(start_color.with_alpha(opacity))],
+
+            BrushFill::LinearGradient { start, end, end_color }     => {
+                let start_color = start_color.with_alpha(opacity);
+                let end_color   = end_color.with_alpha(end_color.to_rgba_components().3*opacity);
+                let gradient_id = Self::gradient_id(start_color, end_color);
+
+                vec![
+                    Draw::Gradient(gradient_id, GradientOp::Create(start_color)),
+                    Draw::Gradient(gradient_id, GradientOp::AddStop(1.0, end_color)),
+                    Draw::FillGradient(gradient_id, *start, *end)
+                ]
+            }
+
+            BrushFill::RadialGradient { center, radius, end_color } => {
+                let start_color = start_color.with_alpha(opacity);
+                let end_color   = end_color.with_alpha(end_color.to_rgba_components().3*opacity);
+                let gradient_id = Self::gradient_id(start_color, end_color);
+
+                vec![
+                    Draw::Gradient(gradient_id, GradientOp::Create(start_color)),
+                    Draw::Gradient(gradient_id, GradientOp::AddStop(1.0, end_color)),
+                    Draw::FillRadialGradient(gradient_id, *center, *radius)
+                ]
+            }
+        }
+    }
+
+    ///
+    /// Allocates the ID for a brush gradient between two colours
+    ///
+    /// The ID is derived from the colours, so brush strokes with the same gradient share a definition and strokes with
+    /// different gradients don't replace each other's definitions.
+    ///
+    fn gradient_id(start_color: Color, end_color: Color) -> GradientId {
+        let mut hasher = DefaultHasher::new();
+
+        for color in [start_color, end_color].iter() {
+            let (r, g, b, a) = color.to_rgba_components();
+            [r, g, b, a].iter().for_each(|component| component.to_bits().hash(&mut hasher));
         }
+
+        GradientId(hasher.finish())
     }
 }
@@ -2,6 +2,8 @@ use super::element_id::*;
 use super::layer_edit::*;
 use super::motion_edit::*;
 use super::element_edit::*;
+use super::symbol_edit::*;
+use super::bone_edit::*;
 
 ///
 /// Represents an edit to an animation object
@@ -18,6 +20,10 @@ pub enum AnimationEdit {
     /// Motions have element IDs so can be treated as elements but are not attached to a layer
     Motion(ElementId, MotionEdit),
 
+    /// Edit to a bone in a skeleton
+    /// Bones have element IDs and are bound to elements by attaching them, but like motions are not attached to a layer themselves
+    Bone(ElementId, BoneEdit),
+
     /// Sets the canvas size for this animation
     SetSize(f64, f64),
 
@@ -26,7 +32,23 @@ pub enum AnimationEdit {
     AddNewLayer(u64),
 
     /// Removes the layer with the specified ID
-    RemoveLayer(u64)
+    /// If the layer is a folder, the layers inside it are moved into its parent
+    RemoveLayer(u64),
+
+    /// Adds a new layer folder (a layer that groups other layers) and assigns it the specified ID
+    /// Has no effect if a layer with that ID already exists
+    AddNewLayerFolder(u64),
+
+    /// Edit to an existing symbol
+    Symbol(u64, SymbolEdit),
+
+    /// Adds a new symbol (a separately stored animation with its own layers) and assigns it the specified ID
+    /// Has no effect if a symbol with that ID already exists
+    AddNewSymbol(u64),
+
+    /// Removes the symbol with the specified ID, along with all of its layers
+    /// Any instances of the symbol that are left will display nothing
+    RemoveSymbol(u64)
 }
 
 impl AnimationEdit {
@@ -42,4 +64,14 @@ impl AnimationEdit {
             other                       => other
         }
     }
+
+    ///
+    /// The ID of the asset that this edit uses, if it refers to one
+    ///
+    pub fn asset_id(&self) -> Option<u64> {
+        match self {
+            AnimationEdit::Layer(_, layer_edit) => layer_edit.asset_id(),
+            _                                   => None
+        }
+    }
 }
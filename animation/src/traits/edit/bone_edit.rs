@@ -0,0 +1,37 @@
+use super::element_id::*;
+
+use std::time::{Duration};
+
+///
+/// Represents an edit to a bone in a skeleton
+///
+#[derive(Clone, PartialEq, Debug)]
+pub enum BoneEdit {
+    /// Creates a new bone with this element ID
+    ///
+    /// A new bone has no parent, has its origin at 0,0, has no length and is not posed
+    Create,
+
+    /// Deletes the bone with this ID
+    Delete,
+
+    /// Connects this bone to a parent bone (or disconnects it if the parent is None)
+    ///
+    /// The parent must be an existing bone, and can't be this bone or one of its children
+    SetParent(Option<ElementId>),
+
+    /// Changes the point that this bone rotates around
+    SetOrigin(f32, f32),
+
+    /// Sets the length of this bone
+    SetLength(f32),
+
+    /// Sets the direction the bone points in when it's at rest, in radians
+    SetAngle(f64),
+
+    /// Sets the rotation of the bone (in radians, relative to its rest position) at the specified time
+    SetRotation(Duration, f64),
+
+    /// Removes the rotation set at the specified time
+    RemoveRotation(Duration)
+}
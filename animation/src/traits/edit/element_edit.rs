@@ -1,7 +1,9 @@
 use super::element_id::*;
 use super::element_transform::*;
+use super::path_combine::*;
 use crate::traits::path::*;
 use crate::traits::group_type::*;
+use crate::traits::vector::{SymbolInstance};
 
 use std::sync::*;
 use std::time::{Duration};
@@ -65,6 +67,15 @@ pub enum ElementEdit {
     /// Converts this element to a path
     ConvertToPath,
 
+    /// Converts the elements to paths and combines them into a single path element
+    ///
+    /// The ID of the combined element is the same as the ID of the first element in the set, and it keeps the properties
+    /// of that element. The remaining elements are removed from the frame.
+    CombinePaths(PathCombine),
+
     /// Applies one or more transformations to the elements
-    Transform(Vec<ElementTransform>)
+    Transform(Vec<ElementTransform>),
+
+    /// Changes how symbol instances are displayed (has no effect on elements that aren't symbol instances)
+    SetSymbolInstance(SymbolInstance)
 }
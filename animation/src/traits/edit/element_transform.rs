@@ -14,6 +14,12 @@ pub enum ElementTransform {
     /// Aligns several elements to the anchor point (or the overall bounding box)
     Align(ElementAlign),
 
+    /// Moves the elements horizontally so that there's an equal space between each of them, keeping the leftmost and rightmost elements in place
+    DistributeHorizontally,
+
+    /// Moves the elements vertically so that there's an equal space between each of them, keeping the topmost and bottommost elements in place
+    DistributeVertically,
+
     /// Flips the element horizontally around the anchor
     FlipHorizontal,
 
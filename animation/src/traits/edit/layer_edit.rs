@@ -1,4 +1,7 @@
 use super::frame_edit::*;
+use super::element_id::*;
+use super::super::vector::{SymbolInstance};
+use super::super::layer::*;
 
 use std::time::Duration;
 
@@ -11,7 +14,14 @@ use std::time::Duration;
 
 #[derive(Clone, PartialEq, Debug)]
 pub enum LayerEditType {
-    Vector
+    /// Layer contains vector elements
+    Vector,
+
+    /// Layer displays reference images
+    Reference,
+
+    /// Layer is a folder that groups other layers
+    Folder
 }
 
 ///
@@ -34,11 +44,60 @@ pub enum LayerEdit {
     /// Removes a keyframe previously added at a particular duration
     RemoveKeyFrame(Duration),
 
+    /// Moves the keyframe that starts at the first time so that it starts at the second time
+    ///
+    /// The elements in the keyframe keep their position relative to the start of the keyframe, and any
+    /// motions attached to them are shifted by the same amount. Nothing happens if there's already a
+    /// keyframe at the target time.
+    MoveKeyFrame(Duration, Duration),
+
+    /// Copies the keyframe that starts at the first time to a new keyframe starting at the second time
+    ///
+    /// The copied elements (and their motions) are assigned new IDs. Nothing happens if there's already
+    /// a keyframe at the target time.
+    DuplicateKeyFrame(Duration, Duration),
+
+    /// Sets whether or not the keyframe that starts at the specified time is tweened into the following keyframe
+    ///
+    /// When tweening is turned on, the frames between the two keyframes interpolate the paths and brush strokes
+    /// in the first keyframe into the matching ones in the second, using the specified easing. Set to None to
+    /// stop tweening.
+    SetKeyFrameTween(Duration, Option<TweenEasing>),
+
     /// Changes the name of this layer
     SetName(String),
 
     /// Sets this layer so that it is ordered behind the specified layer
-    SetOrdering(u64)
+    SetOrdering(u64),
+
+    /// Moves this layer into the specified folder (or to the top level if the folder is None)
+    ///
+    /// Nothing happens if the target isn't a folder, or if it's this layer or one of the layers it contains.
+    SetParent(Option<u64>),
+
+    /// Sets whether or not this layer is hidden (hiding a folder hides all of the layers inside it)
+    SetHidden(bool),
+
+    /// Sets whether or not this layer is locked against editing (locking a folder locks all of the layers inside it)
+    SetLocked(bool),
+
+    /// Sets whether or not the layers inside this folder are hidden in the timeline
+    SetCollapsed(bool),
+
+    /// Turns this layer into a reference layer with the specified settings (or back into a normal layer if the settings are None)
+    SetReference(Option<ReferenceLayerSettings>),
+
+    /// Sets the PNG image that a reference layer displays from a particular point in time
+    ///
+    /// The image is the ID of an asset that has been stored using `EditableAnimation::write_asset`. An image is
+    /// displayed until the time of the next image, so image sequences can be built by adding an image for every frame.
+    AddReferenceImage(Duration, u64),
+
+    /// Removes the reference image that was added at the specified time
+    RemoveReferenceImage(Duration),
+
+    /// Adds an instance of a symbol to the keyframe at the specified time
+    CreateSymbolInstance(Duration, ElementId, SymbolInstance)
 }
 
 impl LayerEdit {
@@ -50,8 +109,19 @@ impl LayerEdit {
         use self::LayerEdit::*;
 
         match self {
-            Paint(when, paint_edit) => Paint(when, paint_edit.assign_element_id(assign_element_id)),
-            other                   => other
+            Paint(when, paint_edit)                                     => Paint(when, paint_edit.assign_element_id(assign_element_id)),
+            CreateSymbolInstance(when, ElementId::Unassigned, instance) => CreateSymbolInstance(when, ElementId::Assigned(assign_element_id()), instance),
+            other                                                       => other
+        }
+    }
+
+    ///
+    /// The ID of the asset that this edit uses, if it refers to one
+    ///
+    pub fn asset_id(&self) -> Option<u64> {
+        match self {
+            LayerEdit::AddReferenceImage(_, asset_id)   => Some(*asset_id),
+            _                                           => None
         }
     }
 }
@@ -5,7 +5,10 @@ mod frame_edit;
 mod element_edit;
 mod element_align;
 mod element_transform;
+mod path_combine;
 mod motion_edit;
+mod symbol_edit;
+mod bone_edit;
 
 pub use self::element_id::*;
 pub use self::animation_edit::*;
@@ -14,4 +17,7 @@ pub use self::frame_edit::*;
 pub use self::element_edit::*;
 pub use self::element_align::*;
 pub use self::element_transform::*;
+pub use self::path_combine::*;
 pub use self::motion_edit::*;
+pub use self::symbol_edit::*;
+pub use self::bone_edit::*;
@@ -0,0 +1,20 @@
+///
+/// Ways that the paths of a set of elements can be combined into a single path
+///
+/// The first element supplies the properties of the resulting path, and operations are applied in the order
+/// that the elements are supplied in.
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PathCombine {
+    /// The result covers the area covered by any of the elements
+    Union,
+
+    /// The later elements are cut out of the first element
+    Subtract,
+
+    /// The result covers only the area covered by every element
+    Intersect,
+
+    /// The result covers the area covered by an odd number of elements (the union with the overlapping regions removed)
+    Exclude
+}
@@ -0,0 +1,19 @@
+use std::time::Duration;
+
+///
+/// Represents an edit to a symbol (a separately stored animation that can be displayed in other layers)
+///
+#[derive(Clone, PartialEq, Debug)]
+pub enum SymbolEdit {
+    /// Changes the name of this symbol
+    SetName(String),
+
+    /// Sets the length of this symbol's timeline (instances that loop start again after this time)
+    SetLength(Duration),
+
+    /// Adds a new layer to this symbol and assigns it the specified ID
+    ///
+    /// Layer IDs are shared with the main animation, so this has no effect if a layer with that ID already exists.
+    /// Symbol layers are edited and removed in the same way as any other layer.
+    AddNewLayer(u64)
+}
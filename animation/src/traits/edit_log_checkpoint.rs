@@ -0,0 +1,15 @@
+///
+/// A named point in the edit log of an animation
+///
+/// The edit index is the number of edits that make up the checkpoint: replaying this many edits will recreate the
+/// animation as it was when the checkpoint was made. Checkpoints are stored with an animation as its named snapshots,
+/// and are preserved when the edit log is compacted.
+///
+#[derive(Clone, PartialEq, Debug)]
+pub struct EditLogCheckpoint {
+    /// The name of this checkpoint
+    pub name: String,
+
+    /// The number of edits that have been performed at this checkpoint
+    pub edit_index: usize
+}
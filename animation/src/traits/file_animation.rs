@@ -12,4 +12,22 @@ pub trait FileAnimation : Send+Sync {
     /// Opens an animation from a file on disk
     ///
     fn open(&self, path: &Path) -> Self::NewAnimation;
+
+    ///
+    /// Creates a new animation at the `target` path from the first `until` edits of the animation at the `source` path
+    ///
+    /// Returns None if the animation could not be created or if this type of file does not support branching
+    ///
+    fn fork(&self, _source: &Path, _target: &Path, _until: usize) -> Option<Self::NewAnimation> {
+        None
+    }
+
+    ///
+    /// Creates a new animation at the specified path with no edits (`open` creates new animations with a default set of layers)
+    ///
+    /// Returns None if the animation could not be created or if this type of file does not support creating empty animations
+    ///
+    fn create_empty(&self, _path: &Path) -> Option<Self::NewAnimation> {
+        None
+    }
 }
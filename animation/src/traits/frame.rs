@@ -41,6 +41,11 @@ pub trait Frame : Send+Sync {
     /// (Element data can be retrieved via element_with_id)
     ///
     fn attached_elements(&self, id: ElementId) -> Vec<(ElementId, VectorType)>;
+
+    ///
+    /// Retrieves the bones that pose the elements in this frame (including the parents of any bones attached to the elements)
+    ///
+    fn bones(&self) -> Vec<BoneElement>;
 }
 
 impl Frame for Arc<dyn Frame> {
@@ -75,4 +80,9 @@ impl Frame for Arc<dyn Frame> {
     /// (Element data can be retrieved via element_with_id)
     ///
     fn attached_elements(&self, id: ElementId) -> Vec<(ElementId, VectorType)> { (**self).attached_elements(id) }
+
+    ///
+    /// Retrieves the bones that pose the elements in this frame (including the parents of any bones attached to the elements)
+    ///
+    #[inline] fn bones(&self) -> Vec<BoneElement> { (**self).bones() }
 }
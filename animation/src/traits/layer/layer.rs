@@ -1,4 +1,6 @@
 use super::vector::*;
+use super::reference::*;
+use super::tween::*;
 use super::super::edit::*;
 use super::super::frame::*;
 use super::super::cache::*;
@@ -58,4 +60,39 @@ pub trait Layer : Send+Sync {
     /// Retrieves the canvas cache at the specified time
     ///
     fn get_canvas_cache_at_time(&self, time_index: Duration) -> Arc<dyn CanvasCache>;
+
+    ///
+    /// If this is a reference layer, retrieves its settings (None for layers that should be rendered normally)
+    ///
+    fn reference_settings(&self) -> Option<ReferenceLayerSettings>;
+
+    ///
+    /// If the keyframe starting at the specified time is tweened into the following keyframe, the easing that's used
+    ///
+    fn key_frame_tween(&self, key_frame: Duration) -> Option<TweenEasing>;
+
+    ///
+    /// Retrieves the PNG data for the reference image that's displayed at the specified time
+    ///
+    fn reference_image_at_time(&self, time_index: Duration) -> Option<Arc<Vec<u8>>>;
+
+    ///
+    /// The ID of the folder that this layer is in (None for layers at the top level)
+    ///
+    fn parent_folder(&self) -> Option<u64>;
+
+    ///
+    /// True if this layer has been hidden (layers in hidden folders are also not displayed)
+    ///
+    fn is_hidden(&self) -> bool;
+
+    ///
+    /// True if this layer has been locked against editing (layers in locked folders are also locked)
+    ///
+    fn is_locked(&self) -> bool;
+
+    ///
+    /// If this layer is a folder, true if the layers inside it are hidden in the timeline
+    ///
+    fn is_collapsed(&self) -> bool;
 }
@@ -1,5 +1,9 @@
 mod layer;
 mod vector;
+mod reference;
+mod tween;
 
 pub use self::layer::*;
 pub use self::vector::*;
+pub use self::reference::*;
+pub use self::tween::*;
@@ -0,0 +1,51 @@
+use flo_canvas::*;
+
+///
+/// Settings for a reference layer
+///
+/// Reference layers display an image (or a sequence of images) underneath the vector layers for
+/// rotoscoping. They're only displayed while editing and are never included when exporting the
+/// animation.
+///
+#[derive(Clone, PartialEq, Debug)]
+pub struct ReferenceLayerSettings {
+    /// The opacity of the reference images (0.0-1.0)
+    pub alpha: f64,
+
+    /// The offset to apply to the reference images
+    pub offset: (f64, f64),
+
+    /// The scale factor to apply to the reference images
+    pub scale: f64,
+
+    /// The rotation (in radians) to apply to the reference images
+    pub rotation: f64
+}
+
+impl Default for ReferenceLayerSettings {
+    fn default() -> ReferenceLayerSettings {
+        ReferenceLayerSettings {
+            alpha:      0.5,
+            offset:     (0.0, 0.0),
+            scale:      1.0,
+            rotation:   0.0
+        }
+    }
+}
+
+impl ReferenceLayerSettings {
+    ///
+    /// Returns the canvas transform to apply to a reference image with the specified size
+    ///
+    /// Images are drawn with their bottom-left corner at the origin: they are rotated and scaled around their center.
+    ///
+    pub fn image_transform(&self, (width, height): (f64, f64)) -> Transform2D {
+        let (cx, cy)    = ((width/2.0) as f32, (height/2.0) as f32);
+        let (dx, dy)    = (self.offset.0 as f32, self.offset.1 as f32);
+
+        Transform2D::translate(dx+cx, dy+cy)
+            * Transform2D::rotate(self.rotation as f32)
+            * Transform2D::scale(self.scale as f32, self.scale as f32)
+            * Transform2D::translate(-cx, -cy)
+    }
+}
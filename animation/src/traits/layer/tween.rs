@@ -0,0 +1,64 @@
+///
+/// Describes how the elements in a keyframe are tweened into the elements in the following keyframe
+///
+/// The easing maps the proportion of the time between the two keyframes that has passed onto the
+/// proportion of the way that the elements have moved from their initial shape to their final shape.
+///
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum TweenEasing {
+    /// The shapes change at a constant rate
+    Linear,
+
+    /// The shapes start changing slowly and speed up
+    EaseIn,
+
+    /// The shapes start changing quickly and slow down
+    EaseOut,
+
+    /// The shapes start and finish changing slowly
+    EaseInOut
+}
+
+impl Default for TweenEasing {
+    fn default() -> TweenEasing {
+        TweenEasing::Linear
+    }
+}
+
+impl TweenEasing {
+    ///
+    /// Maps a time (0.0-1.0) between two keyframes onto the amount (0.0-1.0) the shapes should be tweened by
+    ///
+    pub fn ease(&self, t: f64) -> f64 {
+        use self::TweenEasing::*;
+
+        let t = t.max(0.0).min(1.0);
+
+        match self {
+            Linear      => t,
+            EaseIn      => t * t,
+            EaseOut     => 1.0 - (1.0-t) * (1.0-t),
+            EaseInOut   => t * t * (3.0 - 2.0*t)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn easings_start_and_end_at_keyframes() {
+        for easing in vec![TweenEasing::Linear, TweenEasing::EaseIn, TweenEasing::EaseOut, TweenEasing::EaseInOut] {
+            assert!(easing.ease(0.0) == 0.0);
+            assert!(easing.ease(1.0) == 1.0);
+        }
+    }
+
+    #[test]
+    fn ease_in_starts_slowly() {
+        assert!(TweenEasing::EaseIn.ease(0.25) < TweenEasing::Linear.ease(0.25));
+        assert!(TweenEasing::EaseOut.ease(0.25) > TweenEasing::Linear.ease(0.25));
+        assert!((TweenEasing::EaseInOut.ease(0.5) - 0.5).abs() < 0.0001);
+    }
+}
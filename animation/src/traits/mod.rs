@@ -5,6 +5,8 @@ mod animation_motion;
 mod file_animation;
 mod frame;
 mod layer;
+mod symbol;
+mod bone;
 mod raw_point;
 mod brush;
 mod brush_properties;
@@ -18,6 +20,7 @@ mod cache;
 mod combine_result;
 mod group_type;
 mod fill_option;
+mod edit_log_checkpoint;
 
 pub use self::edit::*;
 pub use self::actions::*;
@@ -26,6 +29,8 @@ pub use self::animation_motion::*;
 pub use self::file_animation::*;
 pub use self::frame::*;
 pub use self::layer::*;
+pub use self::symbol::*;
+pub use self::bone::*;
 pub use self::raw_point::*;
 pub use self::brush::*;
 pub use self::brush_properties::*;
@@ -39,3 +44,4 @@ pub use self::cache::*;
 pub use self::combine_result::*;
 pub use self::group_type::*;
 pub use self::fill_option::*;
+pub use self::edit_log_checkpoint::*;
@@ -80,6 +80,24 @@ impl Motion {
         }
     }
 
+    ///
+    /// Returns a copy of this motion with all of its times moved by the specified number of milliseconds
+    ///
+    pub fn with_time_offset(&self, offset_millis: f32) -> Motion {
+        use self::Motion::*;
+
+        match self {
+            None                    => None,
+            Reverse(motion)         => Reverse(Arc::new(motion.with_time_offset(offset_millis))),
+            Translate(translate)    => {
+                let mut translate = translate.clone();
+                translate.set_path(translate.translate.with_time_offset(offset_millis));
+
+                Translate(translate)
+            }
+        }
+    }
+
     ///
     /// Changes this to the reverse motion of itself
     ///
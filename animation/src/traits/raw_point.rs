@@ -30,17 +30,24 @@ impl From<(f32, f32)> for RawPoint {
 /// Writes a set of raw points to a stream
 ///
 pub fn write_raw_points<Target: Write>(tgt: &mut Target, points: &[RawPoint]) -> Result<(), Error> {
+    write_raw_points_with_encoding(tgt, FloatEncoding::default(), points)
+}
+
+///
+/// Writes a set of raw points to a stream using a specific encoding for the coordinates (`read_raw_points()` can read any encoding)
+///
+pub fn write_raw_points_with_encoding<Target: Write>(tgt: &mut Target, encoding: FloatEncoding, points: &[RawPoint]) -> Result<(), Error> {
     let mut last_position   = (0.0, 0.0);
     let mut last_pressure   = 0.0;
     let mut last_tilt       = (0.0, 0.0);
 
     for point in points {
         // Squish the points into the stream (we rely on the way that points are usually close together to reduce the amount of data we need to write to the stream)
-        squish_float(tgt, last_position.0 as f64, point.position.0 as f64)?;
-        squish_float(tgt, last_position.1 as f64, point.position.1 as f64)?;
-        squish_float(tgt, last_pressure as f64, point.pressure as f64)?;
-        squish_float(tgt, last_tilt.0 as f64, point.tilt.0 as f64)?;
-        squish_float(tgt, last_tilt.1 as f64, point.tilt.1 as f64)?;
+        squish_float_with_encoding(tgt, encoding, last_position.0 as f64, point.position.0 as f64)?;
+        squish_float_with_encoding(tgt, encoding, last_position.1 as f64, point.position.1 as f64)?;
+        squish_float_with_encoding(tgt, encoding, last_pressure as f64, point.pressure as f64)?;
+        squish_float_with_encoding(tgt, encoding, last_tilt.0 as f64, point.tilt.0 as f64)?;
+        squish_float_with_encoding(tgt, encoding, last_tilt.1 as f64, point.tilt.1 as f64)?;
 
         // Store the previous positions (for squishing)
         last_position   = point.position;
@@ -112,4 +119,20 @@ mod test {
         assert!((read_points[2].tilt.1-11.0).abs() < 0.01);
         assert!((read_points[3].pressure-0.3).abs() < 0.01);
     }
+
+    #[test]
+    fn encode_decode_lossless_raw_points() {
+        let mut tgt = vec![];
+        let points  = vec![
+            RawPoint { position: (2.0, 2.0), pressure: 0.5, tilt: (0.0, 0.0) },
+            RawPoint { position: (2.001, 2.4), pressure: 0.55, tilt: (20.0, 0.0) },
+            RawPoint { position: (2000.7, 4.2), pressure: 0.51, tilt: (20.0, 11.0) },
+        ];
+
+        write_raw_points_with_encoding(&mut tgt, FloatEncoding::Lossless, &points).unwrap();
+
+        let mut src: &[u8] = &tgt;
+        let read_points = read_raw_points(&mut src).unwrap();
+        assert!(read_points == points);
+    }
 }
@@ -0,0 +1,29 @@
+use std::time::Duration;
+
+///
+/// A symbol is a separately stored animation with its own layers and timeline, that can be displayed
+/// in other layers using symbol instance elements
+///
+pub trait Symbol : Send+Sync {
+    ///
+    /// The ID of this symbol
+    ///
+    fn id(&self) -> u64;
+
+    ///
+    /// The name of this symbol
+    ///
+    fn name(&self) -> String;
+
+    ///
+    /// The length of this symbol's timeline
+    ///
+    fn length(&self) -> Duration;
+
+    ///
+    /// Retrieves the IDs of the layers in this symbol, in the order they're drawn
+    ///
+    /// The layers themselves can be retrieved using `get_layer_with_id` on the animation the symbol belongs to
+    ///
+    fn get_layer_ids(&self) -> Vec<u64>;
+}
@@ -0,0 +1,447 @@
+use super::time_curve::*;
+use super::time_point::*;
+use super::time_control_point::*;
+
+///
+/// Preset ways that an element can move between two points on a time curve
+///
+/// Easing changes the timing of a motion without changing the path that it follows (the overshoot easing continues
+/// the path beyond its end point before returning)
+///
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Easing {
+    /// Moves at a constant speed
+    Linear,
+
+    /// Starts slowly and speeds up
+    EaseIn,
+
+    /// Starts quickly and slows down
+    EaseOut,
+
+    /// Starts and finishes slowly
+    EaseInOut,
+
+    /// Moves past the end point and then returns to it
+    Overshoot,
+
+    /// Bounces off the end point several times before coming to a rest
+    Bounce
+}
+
+impl Easing {
+    ///
+    /// Returns all of the easing presets
+    ///
+    pub fn all() -> Vec<Easing> {
+        vec![Easing::Linear, Easing::EaseIn, Easing::EaseOut, Easing::EaseInOut, Easing::Overshoot, Easing::Bounce]
+    }
+
+    ///
+    /// Returns a name for this easing preset
+    ///
+    pub fn name(&self) -> &'static str {
+        match self {
+            Easing::Linear      => "Linear",
+            Easing::EaseIn      => "Ease In",
+            Easing::EaseOut     => "Ease Out",
+            Easing::EaseInOut   => "Ease In/Out",
+            Easing::Overshoot   => "Overshoot",
+            Easing::Bounce      => "Bounce"
+        }
+    }
+
+    ///
+    /// Given the proportion of the time that has passed (0.0-1.0), returns the proportion of the way along the path that
+    /// an element should have moved
+    ///
+    pub fn value_at(&self, t: f64) -> f64 {
+        match self {
+            Easing::Linear      => t,
+            Easing::EaseIn      => t*t,
+            Easing::EaseOut     => 1.0 - (1.0-t)*(1.0-t),
+            Easing::EaseInOut   => if t < 0.5 { 2.0*t*t } else { 1.0 - 2.0*(1.0-t)*(1.0-t) },
+
+            Easing::Overshoot   => {
+                let c1 = 1.70158;
+                let c3 = c1 + 1.0;
+
+                1.0 + c3*(t-1.0).powi(3) + c1*(t-1.0).powi(2)
+            }
+
+            Easing::Bounce      => {
+                let n1 = 7.5625;
+                let d1 = 2.75;
+
+                if t < 1.0/d1 {
+                    n1*t*t
+                } else if t < 2.0/d1 {
+                    let t = t - 1.5/d1;
+                    n1*t*t + 0.75
+                } else if t < 2.5/d1 {
+                    let t = t - 2.25/d1;
+                    n1*t*t + 0.9375
+                } else {
+                    let t = t - 2.625/d1;
+                    n1*t*t + 0.984375
+                }
+            }
+        }
+    }
+
+    ///
+    /// For the easings that can be represented by changing the timing of the handles of a single curve section, returns
+    /// the easing values for the start and end of that section
+    ///
+    fn handle_easing(&self) -> Option<(f32, f32)> {
+        match self {
+            Easing::Linear      => Some((0.0, 0.0)),
+            Easing::EaseIn      => Some((1.0, 0.0)),
+            Easing::EaseOut     => Some((0.0, 1.0)),
+            Easing::EaseInOut   => Some((0.5, 0.5)),
+            Easing::Overshoot   => None,
+            Easing::Bounce      => None
+        }
+    }
+
+    ///
+    /// The times (as a proportion of the length of a section) where new points are generated for easings that need
+    /// more than one curve section
+    ///
+    fn knots(&self) -> Vec<f64> {
+        match self {
+            Easing::Bounce  => vec![0.0, 0.5/2.75, 1.0/2.75, 1.5/2.75, 2.0/2.75, 2.25/2.75, 2.5/2.75, 2.625/2.75, 1.0],
+            _               => vec![0.0, 0.2, 0.4, 0.6, 0.8, 1.0]
+        }
+    }
+}
+
+impl TimeCurve {
+    ///
+    /// Generates a time curve with the timing of the section starting at the specified point replaced by an easing preset
+    ///
+    /// The spatial path of the section is preserved. Simple easings are applied by changing the timing of the section's
+    /// handles, and the more complex ones replace the section with several new sections.
+    ///
+    pub fn with_section_easing(&self, section: usize, easing: Easing) -> TimeCurve {
+        if section+1 >= self.points.len() {
+            return self.clone();
+        }
+
+        match easing.handle_easing() {
+            Some((ease_start, ease_end))    => {
+                let mut new_points      = self.points.clone();
+                let start_millis        = new_points[section].point.milliseconds();
+                let end_millis          = new_points[section+1].point.milliseconds();
+                let section_millis      = end_millis - start_millis;
+
+                new_points[section].future.2    = start_millis + Self::handle_millis_for_easing(ease_start, section_millis);
+                new_points[section+1].past.2    = end_millis - Self::handle_millis_for_easing(ease_end, section_millis);
+
+                TimeCurve { points: new_points }
+            }
+
+            None                            => self.with_sampled_easing(section, easing)
+        }
+    }
+
+    ///
+    /// Generates a time curve with every section replaced with an easing preset
+    ///
+    /// Any sections that were split up by an earlier overshoot or bounce preset are joined back together before the
+    /// new preset is applied, so applying a preset replaces the last one rather than easing the curve again.
+    ///
+    pub fn with_easing_preset(&self, easing: Easing) -> TimeCurve {
+        let original        = self.without_sampled_easings();
+        let num_sections    = original.points.len().saturating_sub(1);
+        let mut curve       = original;
+
+        // Work backwards so that sections that are split don't change the index of the sections that are still to be eased
+        for section in (0..num_sections).rev() {
+            curve = curve.with_section_easing(section, easing);
+        }
+
+        curve
+    }
+
+    ///
+    /// Joins together any runs of sections that were generated by `with_sampled_easing`
+    ///
+    fn without_sampled_easings(&self) -> TimeCurve {
+        if self.points.is_empty() {
+            return self.clone();
+        }
+
+        let mut new_points  = vec![self.points[0]];
+        let mut index       = 0;
+
+        while index+1 < self.points.len() {
+            match self.sampled_section_at(index) {
+                Some((num_sections, future, past))  => {
+                    // Restore the handles of the section that was eased
+                    let mut end_point = self.points[index+num_sections];
+                    end_point.past    = past;
+
+                    new_points.last_mut().unwrap().future = future;
+                    new_points.push(end_point);
+
+                    index += num_sections;
+                }
+
+                None                                => {
+                    new_points.push(self.points[index+1]);
+                    index += 1;
+                }
+            }
+        }
+
+        TimeCurve { points: new_points }
+    }
+
+    ///
+    /// If the points starting at the specified index were generated by `with_sampled_easing`, returns the number of
+    /// sections that were generated and the handles of the section that they replaced
+    ///
+    fn sampled_section_at(&self, index: usize) -> Option<(usize, TimePoint, TimePoint)> {
+        Easing::all().into_iter()
+            .filter(|easing| easing.handle_easing().is_none())
+            .filter_map(|easing| {
+                let knots           = easing.knots();
+                let num_sections    = knots.len()-1;
+                if index + num_sections >= self.points.len() { return None; }
+
+                let points          = &self.points[index..=(index+num_sections)];
+                let start           = points[0].point;
+                let end             = points[num_sections].point;
+                let start_millis    = start.milliseconds() as f64;
+                let section_millis  = end.milliseconds() as f64 - start_millis;
+                if section_millis <= 0.0 { return None; }
+
+                // The points must be at the times of the knots for the easing
+                let time_tolerance  = (section_millis * 1e-4).max(0.01);
+                let at_knots        = points.iter().zip(knots.iter())
+                    .all(|(point, u)| ((point.point.milliseconds() as f64) - (start_millis + section_millis*u)).abs() < time_tolerance);
+                if !at_knots { return None; }
+
+                // Find the handles of the original section by fitting the points between the ends to its bezier curve
+                let (p0, p3)        = (start.coords(), end.coords());
+                let samples         = knots[1..num_sections].iter().zip(points[1..num_sections].iter())
+                    .map(|(u, point)| {
+                        let p           = easing.value_at(*u);
+                        let q           = 1.0-p;
+                        let (x, y)      = point.point.coords();
+                        let (x, y)      = (x as f64 - (p0.0 as f64)*q*q*q - (p3.0 as f64)*p*p*p, y as f64 - (p0.1 as f64)*q*q*q - (p3.1 as f64)*p*p*p);
+
+                        (3.0*q*q*p, 3.0*q*p*p, (x, y))
+                    })
+                    .collect::<Vec<_>>();
+
+                let (a11, a12, a22) = samples.iter().fold((0.0, 0.0, 0.0), |(a11, a12, a22), (b1, b2, _)| (a11 + b1*b1, a12 + b1*b2, a22 + b2*b2));
+                let (r1, r2)        = samples.iter().fold(((0.0, 0.0), (0.0, 0.0)), |(r1, r2), (b1, b2, (x, y))| ((r1.0 + b1*x, r1.1 + b1*y), (r2.0 + b2*x, r2.1 + b2*y)));
+                let determinant     = a11*a22 - a12*a12;
+                if determinant.abs() < 1e-9 { return None; }
+
+                let p1              = ((a22*r1.0 - a12*r2.0)/determinant, (a22*r1.1 - a12*r2.1)/determinant);
+                let p2              = ((a11*r2.0 - a12*r1.0)/determinant, (a11*r2.1 - a12*r1.1)/determinant);
+
+                // Every point must lie on the fitted curve (otherwise the curve has been edited since the easing was applied)
+                let on_curve        = samples.iter()
+                    .all(|(b1, b2, (x, y))| {
+                        let (dx, dy) = (x - b1*p1.0 - b2*p2.0, y - b1*p1.1 - b2*p2.1);
+                        (dx*dx + dy*dy) < (0.01*0.01)
+                    });
+                if !on_curve { return None; }
+
+                let future          = TimePoint(p1.0 as f32, p1.1 as f32, (start_millis + section_millis/3.0) as f32);
+                let past            = TimePoint(p2.0 as f32, p2.1 as f32, (start_millis + section_millis*2.0/3.0) as f32);
+
+                Some((num_sections, future, past))
+            })
+            .nth(0)
+    }
+
+    ///
+    /// Replaces a section with a set of sections that follow the same spatial path with the timing of an easing function
+    ///
+    fn with_sampled_easing(&self, section: usize, easing: Easing) -> TimeCurve {
+        let start           = self.points[section];
+        let end             = self.points[section+1];
+        let start_millis    = start.point.milliseconds() as f64;
+        let section_millis  = end.point.milliseconds() as f64 - start_millis;
+
+        // The spatial path is the section's bezier curve, which is extended beyond its ends for easings that overshoot
+        let (p0, p1, p2, p3) = (start.point.coords(), start.future.coords(), end.past.coords(), end.point.coords());
+        let spatial         = |p: f64| {
+            let q       = 1.0-p;
+            let (a, b, c, d) = (q*q*q, 3.0*q*q*p, 3.0*q*p*p, p*p*p);
+
+            ((p0.0 as f64)*a + (p1.0 as f64)*b + (p2.0 as f64)*c + (p3.0 as f64)*d,
+             (p0.1 as f64)*a + (p1.1 as f64)*b + (p2.1 as f64)*c + (p3.1 as f64)*d)
+        };
+
+        // Velocity along the path for a particular proportion of the time (the easing function can change direction at a knot, so the direction matters)
+        let velocity        = |u: f64, forward: bool| {
+            let h           = 1e-4;
+            let (u1, u2)    = if forward { (u, u+h) } else { (u-h, u) };
+            let (x1, y1)    = spatial(easing.value_at(u1));
+            let (x2, y2)    = spatial(easing.value_at(u2));
+
+            ((x2-x1)/h, (y2-y1)/h)
+        };
+
+        // Generate a control point for each knot
+        let knots           = easing.knots();
+        let last_knot       = knots.len()-1;
+        let mut new_points  = vec![];
+
+        for (index, u) in knots.iter().enumerate() {
+            let u           = *u;
+            let millis      = start_millis + section_millis*u;
+            let (x, y)      = spatial(easing.value_at(u));
+            let point       = TimePoint(x as f32, y as f32, millis as f32);
+
+            let past        = if index > 0 {
+                let du          = u - knots[index-1];
+                let (dx, dy)    = velocity(u, false);
+                TimePoint((x - dx*du/3.0) as f32, (y - dy*du/3.0) as f32, (millis - section_millis*du/3.0) as f32)
+            } else {
+                start.past
+            };
+
+            let future      = if index < last_knot {
+                let du          = knots[index+1] - u;
+                let (dx, dy)    = velocity(u, true);
+                TimePoint((x + dx*du/3.0) as f32, (y + dy*du/3.0) as f32, (millis + section_millis*du/3.0) as f32)
+            } else {
+                end.future
+            };
+
+            // The start and end points stay exactly where they were
+            let point       = if index == 0 { start.point } else if index == last_knot { end.point } else { point };
+
+            new_points.push(TimeControlPoint::new(past, point, future));
+        }
+
+        // Replace the section with the new points
+        let mut points = self.points.clone();
+        points.splice(section..(section+2), new_points);
+
+        TimeCurve { points: points }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn easings_start_and_end_at_the_end_points() {
+        for easing in Easing::all() {
+            assert!(easing.value_at(0.0).abs() < 0.0001);
+            assert!((easing.value_at(1.0)-1.0).abs() < 0.0001);
+        }
+    }
+
+    #[test]
+    fn linear_easing_keeps_the_path() {
+        let curve       = TimeCurve::new(TimePoint(0.0, 0.0, 0.0), TimePoint(300.0, 0.0, 300.0));
+        let eased_curve = curve.with_easing_preset(Easing::Linear);
+
+        assert!(eased_curve.is_close_to(&curve));
+    }
+
+    #[test]
+    fn ease_in_starts_slowly() {
+        let curve       = TimeCurve::new(TimePoint(0.0, 0.0, 0.0), TimePoint(300.0, 0.0, 300.0));
+        let eased_curve = curve.with_easing_preset(Easing::EaseIn);
+
+        assert!(eased_curve.points.len() == 2);
+        assert!(eased_curve.point_at_time(30.0).unwrap().0 < 30.0);
+        assert!(eased_curve.point_at_time(150.0).unwrap().0 < 150.0);
+    }
+
+    #[test]
+    fn ease_out_finishes_slowly() {
+        let curve       = TimeCurve::new(TimePoint(0.0, 0.0, 0.0), TimePoint(300.0, 0.0, 300.0));
+        let eased_curve = curve.with_easing_preset(Easing::EaseOut);
+
+        assert!(eased_curve.point_at_time(30.0).unwrap().0 > 30.0);
+        assert!(eased_curve.point_at_time(150.0).unwrap().0 > 150.0);
+    }
+
+    #[test]
+    fn overshoot_goes_past_the_end() {
+        let curve       = TimeCurve::new(TimePoint(0.0, 0.0, 0.0), TimePoint(100.0, 0.0, 1000.0));
+        let eased_curve = curve.with_easing_preset(Easing::Overshoot);
+
+        assert!(eased_curve.points[0].point == curve.points[0].point);
+        assert!(eased_curve.points.last().unwrap().point == curve.points[1].point);
+        assert!(eased_curve.point_at_time(600.0).unwrap().0 > 100.0);
+        assert!((eased_curve.point_at_time(1000.0).unwrap().0 - 100.0).abs() < 0.1);
+    }
+
+    #[test]
+    fn bounce_returns_towards_the_start() {
+        let curve       = TimeCurve::new(TimePoint(0.0, 0.0, 0.0), TimePoint(100.0, 0.0, 1000.0));
+        let eased_curve = curve.with_easing_preset(Easing::Bounce);
+
+        let first_bounce    = eased_curve.point_at_time(1000.0/2.75).unwrap().0;
+        let between_bounces = eased_curve.point_at_time(1500.0/2.75).unwrap().0;
+
+        assert!((first_bounce - 100.0).abs() < 1.0);
+        assert!((between_bounces - 75.0).abs() < 1.0);
+        assert!(eased_curve.points.last().unwrap().point == curve.points[1].point);
+    }
+
+    #[test]
+    fn easing_one_section_leaves_the_others_alone() {
+        let curve       = TimeCurve::new(TimePoint(0.0, 0.0, 0.0), TimePoint(100.0, 0.0, 1000.0));
+        let curve       = curve.set_point_at_time(std::time::Duration::from_millis(2000), (200.0, 0.0));
+        let eased_curve = curve.with_section_easing(1, Easing::Bounce);
+
+        assert!(eased_curve.points[0] == curve.points[0]);
+        assert!(eased_curve.points[1].point == curve.points[1].point);
+        assert!(eased_curve.points[1].past == curve.points[1].past);
+        assert!(eased_curve.points.len() == 1 + Easing::Bounce.knots().len());
+    }
+
+    #[test]
+    fn presets_in_sequence_ease_the_original_sections() {
+        let curve       = TimeCurve::new(TimePoint(0.0, 0.0, 0.0), TimePoint(100.0, 0.0, 1000.0));
+
+        let bounce      = curve.with_easing_preset(Easing::Bounce);
+        let ease_in     = bounce.with_easing_preset(Easing::EaseIn);
+        let overshoot   = ease_in.with_easing_preset(Easing::Overshoot);
+
+        assert!(ease_in.points.len() == 2);
+        assert!(ease_in.is_close_to(&curve.with_easing_preset(Easing::EaseIn)));
+        assert!(overshoot.is_close_to(&curve.with_easing_preset(Easing::Overshoot)));
+        assert!(overshoot.with_easing_preset(Easing::Linear).is_close_to(&curve));
+    }
+
+    #[test]
+    fn applying_a_preset_twice_is_the_same_as_applying_it_once() {
+        let curve       = TimeCurve::new(TimePoint(0.0, 0.0, 0.0), TimePoint(100.0, 0.0, 1000.0));
+        let curve       = curve.set_point_at_time(std::time::Duration::from_millis(2000), (200.0, 50.0));
+
+        for easing in Easing::all() {
+            let once    = curve.with_easing_preset(easing);
+            let twice   = once.with_easing_preset(easing);
+
+            assert!(twice.is_close_to(&once));
+        }
+    }
+
+    #[test]
+    fn presets_keep_a_curved_path() {
+        let curve       = TimeCurve::new(TimePoint(0.0, 0.0, 0.0), TimePoint(100.0, 0.0, 1000.0));
+        let curve       = curve.set_point_at_time(std::time::Duration::from_millis(2000), (200.0, 50.0));
+
+        let bounce      = curve.with_easing_preset(Easing::Bounce);
+        let overshoot   = bounce.with_easing_preset(Easing::Overshoot);
+        let ease_out    = overshoot.with_easing_preset(Easing::EaseOut);
+
+        assert!(overshoot.is_close_to(&curve.with_easing_preset(Easing::Overshoot)));
+        assert!(ease_out.is_close_to(&curve.with_easing_preset(Easing::EaseOut)));
+    }
+}
@@ -139,6 +139,252 @@ impl TimeCurve {
 
         }
     }
+
+    ///
+    /// Generates a time curve with every point moved by the specified number of milliseconds
+    ///
+    pub fn with_time_offset(&self, offset_millis: f32) -> TimeCurve {
+        let offset      = TimePoint(0.0, 0.0, offset_millis);
+        let new_points  = self.points.iter()
+            .map(|point| TimeControlPoint::new(point.past + offset, point.point + offset, point.future + offset))
+            .collect();
+
+        TimeCurve { points: new_points }
+    }
+
+    ///
+    /// Returns the editable points of this curve
+    ///
+    /// Points are returned in the order they appear along the curve: the first point, its future handle, the past handle of
+    /// the next point, that point and so on. Every third point (starting from 0) is a point on the curve. The past handle of
+    /// the first point and the future handle of the last point have no effect, so they're not returned.
+    ///
+    pub fn control_points(&self) -> Vec<TimePoint> {
+        let num_points = self.points.len();
+
+        self.points.iter()
+            .enumerate()
+            .flat_map(|(index, point)| {
+                let past    = if index > 0 { Some(point.past) } else { None };
+                let future  = if index < num_points-1 { Some(point.future) } else { None };
+
+                past.into_iter()
+                    .chain(Some(point.point))
+                    .chain(future)
+            })
+            .collect()
+    }
+
+    ///
+    /// Generates a time curve with its control points (in the order returned by `control_points()`) moved to new positions
+    ///
+    /// The times of the points are left unchanged.
+    ///
+    pub fn with_control_point_positions(&self, new_positions: Vec<(f32, f32)>) -> TimeCurve {
+        if new_positions.len() != self.control_points().len() {
+            return self.clone();
+        }
+
+        let num_points      = self.points.len();
+        let mut positions   = new_positions.into_iter();
+        let mut new_points  = vec![];
+
+        for (index, point) in self.points.iter().enumerate() {
+            let mut new_point = *point;
+
+            if index > 0 {
+                let (x, y)          = positions.next().unwrap();
+                new_point.past      = TimePoint(x, y, point.past.milliseconds());
+            }
+
+            let (x, y)              = positions.next().unwrap();
+            new_point.point         = TimePoint(x, y, point.point.milliseconds());
+
+            if index < num_points-1 {
+                let (x, y)          = positions.next().unwrap();
+                new_point.future    = TimePoint(x, y, point.future.milliseconds());
+            }
+
+            // The handles that aren't editable follow the point
+            let (dx, dy)        = (new_point.point.0-point.point.0, new_point.point.1-point.point.1);
+            if index == 0               { new_point.past    = point.past + TimePoint(dx, dy, 0.0); }
+            if index == num_points-1    { new_point.future  = point.future + TimePoint(dx, dy, 0.0); }
+
+            new_points.push(new_point);
+        }
+
+        TimeCurve { points: new_points }
+    }
+
+    ///
+    /// Returns the easing in to and out of the point at the specified index
+    ///
+    /// Easing values range from 0.0 (the element moves at a constant speed) to 1.0 (the element slows down as much as
+    /// possible as it approaches or leaves the point)
+    ///
+    pub fn easing_at(&self, index: usize) -> (f32, f32) {
+        if index >= self.points.len() {
+            return (0.0, 0.0);
+        }
+
+        let point       = &self.points[index];
+        let ease_in     = if index > 0 {
+            Self::easing_for_handle(point.point.milliseconds() - point.past.milliseconds(), point.point.milliseconds() - self.points[index-1].point.milliseconds())
+        } else {
+            0.0
+        };
+        let ease_out    = if index+1 < self.points.len() {
+            Self::easing_for_handle(point.future.milliseconds() - point.point.milliseconds(), self.points[index+1].point.milliseconds() - point.point.milliseconds())
+        } else {
+            0.0
+        };
+
+        (ease_in, ease_out)
+    }
+
+    ///
+    /// Generates a time curve with the easing in to and out of the point at the specified index changed
+    ///
+    /// The easing is set by changing the times of the point's handles: a handle a third of the way to the next point
+    /// produces no easing, and a handle all the way to the next point produces the maximum amount.
+    ///
+    pub fn with_easing(&self, index: usize, ease_in: f32, ease_out: f32) -> TimeCurve {
+        if index >= self.points.len() {
+            return self.clone();
+        }
+
+        let mut new_points  = self.points.clone();
+        let point_millis    = new_points[index].point.milliseconds();
+
+        if index > 0 {
+            let section_millis          = point_millis - new_points[index-1].point.milliseconds();
+            new_points[index].past.2    = point_millis - Self::handle_millis_for_easing(ease_in, section_millis);
+        }
+
+        if index+1 < new_points.len() {
+            let section_millis          = new_points[index+1].point.milliseconds() - point_millis;
+            new_points[index].future.2  = point_millis + Self::handle_millis_for_easing(ease_out, section_millis);
+        }
+
+        TimeCurve { points: new_points }
+    }
+
+    ///
+    /// Generates a time curve with the point at the specified index moved to a new time
+    ///
+    /// The point can't be moved past the points on either side of it. The handles for the sections on either side of
+    /// the point are re-timed so that the easing into and out of the point stays the same.
+    ///
+    pub fn with_point_time(&self, index: usize, millis: f32) -> TimeCurve {
+        let num_points = self.points.len();
+        if index >= num_points {
+            return self.clone();
+        }
+
+        // Limit the new time to the section between the neighbouring points
+        let min_millis  = if index > 0 { self.points[index-1].point.milliseconds() + MIN_TIME_MILLISECONDS } else { f32::MIN };
+        let max_millis  = if index+1 < num_points { self.points[index+1].point.milliseconds() - MIN_TIME_MILLISECONDS } else { f32::MAX };
+        if min_millis > max_millis {
+            return self.clone();
+        }
+
+        let millis      = millis.max(min_millis).min(max_millis);
+
+        // Remember where the handles next to the point are as a proportion of their sections
+        let first       = if index > 0 { index-1 } else { 0 };
+        let last        = if index+1 < num_points { index+1 } else { index };
+        let proportions = (first..=last).map(|point_index| self.handle_proportions(point_index)).collect::<Vec<_>>();
+
+        // Move the point
+        let mut curve   = self.clone();
+        let (x, y)      = curve.points[index].point.coords();
+        curve.points[index].move_to(x, y, millis);
+
+        // Restore the handles
+        for (point_index, (past, future)) in (first..=last).zip(proportions) {
+            let point_millis = curve.points[point_index].point.milliseconds();
+
+            if point_index > 0 {
+                let section_millis                  = point_millis - curve.points[point_index-1].point.milliseconds();
+                curve.points[point_index].past.2    = point_millis - section_millis*past;
+            }
+
+            if point_index+1 < num_points {
+                let section_millis                  = curve.points[point_index+1].point.milliseconds() - point_millis;
+                curve.points[point_index].future.2  = point_millis + section_millis*future;
+            }
+        }
+
+        curve
+    }
+
+    ///
+    /// Generates a time curve with the time of one of the handles of a point changed
+    ///
+    /// Handles are kept within the section that they control, so the curve always moves forward in time.
+    ///
+    pub fn with_handle_time(&self, index: usize, is_future: bool, millis: f32) -> TimeCurve {
+        let num_points = self.points.len();
+        if index >= num_points {
+            return self.clone();
+        }
+
+        let mut curve       = self.clone();
+        let point_millis    = curve.points[index].point.milliseconds();
+
+        if is_future && index+1 < num_points {
+            let next_millis                 = curve.points[index+1].point.milliseconds();
+            curve.points[index].future.2    = millis.max(point_millis).min(next_millis);
+        } else if !is_future && index > 0 {
+            let previous_millis             = curve.points[index-1].point.milliseconds();
+            curve.points[index].past.2      = millis.max(previous_millis).min(point_millis);
+        }
+
+        curve
+    }
+
+    ///
+    /// Returns how far in time the past and future handles of a point are from the point, as a proportion of the sections they're in
+    ///
+    fn handle_proportions(&self, index: usize) -> (f32, f32) {
+        let point_millis    = self.points[index].point.milliseconds();
+        let proportion      = |handle_millis: f32, section_millis: f32| {
+            if section_millis > 0.0 { handle_millis / section_millis } else { 1.0/3.0 }
+        };
+
+        let past            = if index > 0 {
+            proportion(point_millis - self.points[index].past.milliseconds(), point_millis - self.points[index-1].point.milliseconds())
+        } else {
+            1.0/3.0
+        };
+        let future          = if index+1 < self.points.len() {
+            proportion(self.points[index].future.milliseconds() - point_millis, self.points[index+1].point.milliseconds() - point_millis)
+        } else {
+            1.0/3.0
+        };
+
+        (past, future)
+    }
+
+    ///
+    /// Works out the easing value for a handle that's a certain time away from its point
+    ///
+    fn easing_for_handle(handle_millis: f32, section_millis: f32) -> f32 {
+        if section_millis <= 0.0 {
+            0.0
+        } else {
+            let proportion = handle_millis / section_millis;
+            ((proportion - (1.0/3.0)) * 1.5).max(0.0).min(1.0)
+        }
+    }
+
+    ///
+    /// Works out how far in time a handle should be from its point to produce a particular easing value
+    ///
+    pub (super) fn handle_millis_for_easing(easing: f32, section_millis: f32) -> f32 {
+        let easing = easing.max(0.0).min(1.0);
+        section_millis * ((1.0/3.0) + easing*(2.0/3.0))
+    }
 }
 
 #[cfg(test)]
@@ -201,6 +447,109 @@ mod test {
         assert!(moved_curve.points[1].point == TimePoint(50.0, 50.0, 50.0));
     }
 
+    #[test]
+    fn offset_moves_all_points() {
+        let curve       = TimeCurve::new(TimePoint(40.0, 40.0, 20.0), TimePoint(50.0, 50.0, 100.0));
+        let moved_curve = curve.with_time_offset(-10.0);
+
+        assert!(moved_curve.points.len() == 2);
+        assert!(moved_curve.points[0].point == TimePoint(40.0, 40.0, 10.0));
+        assert!(moved_curve.points[0].future == curve.points[0].future - TimePoint(0.0, 0.0, 10.0));
+        assert!(moved_curve.points[1].point == TimePoint(50.0, 50.0, 90.0));
+        assert!(moved_curve.points[1].past == curve.points[1].past - TimePoint(0.0, 0.0, 10.0));
+    }
+
+    #[test]
+    fn control_points_skip_unused_handles() {
+        let curve           = TimeCurve::new(TimePoint(40.0, 40.0, 20.0), TimePoint(50.0, 50.0, 100.0));
+        let control_points  = curve.control_points();
+
+        assert!(control_points.len() == 4);
+        assert!(control_points[0] == curve.points[0].point);
+        assert!(control_points[1] == curve.points[0].future);
+        assert!(control_points[2] == curve.points[1].past);
+        assert!(control_points[3] == curve.points[1].point);
+    }
+
+    #[test]
+    fn moving_control_points_preserves_time() {
+        let curve       = TimeCurve::new(TimePoint(40.0, 40.0, 20.0), TimePoint(50.0, 50.0, 100.0));
+        let moved_curve = curve.with_control_point_positions(vec![(0.0, 0.0), (10.0, 0.0), (20.0, 0.0), (30.0, 0.0)]);
+
+        assert!(moved_curve.points[0].point == TimePoint(0.0, 0.0, 20.0));
+        assert!(moved_curve.points[0].future == TimePoint(10.0, 0.0, curve.points[0].future.milliseconds()));
+        assert!(moved_curve.points[1].past == TimePoint(20.0, 0.0, curve.points[1].past.milliseconds()));
+        assert!(moved_curve.points[1].point == TimePoint(30.0, 0.0, 100.0));
+    }
+
+    #[test]
+    fn new_curve_has_no_easing() {
+        let curve               = TimeCurve::new(TimePoint(40.0, 40.0, 0.0), TimePoint(50.0, 50.0, 300.0));
+        let (ease_in, ease_out) = curve.easing_at(0);
+
+        assert!(ease_in.abs() < 0.001);
+        assert!(ease_out.abs() < 0.001);
+    }
+
+    #[test]
+    fn set_easing() {
+        let curve               = TimeCurve::new(TimePoint(40.0, 40.0, 0.0), TimePoint(50.0, 50.0, 300.0));
+        let eased_curve         = curve.with_easing(0, 0.0, 0.5);
+        let eased_curve         = eased_curve.with_easing(1, 1.0, 0.0);
+
+        let (_, ease_out)       = eased_curve.easing_at(0);
+        let (ease_in, _)        = eased_curve.easing_at(1);
+
+        assert!((ease_out-0.5).abs() < 0.001);
+        assert!((ease_in-1.0).abs() < 0.001);
+        assert!((eased_curve.points[0].future.milliseconds()-200.0).abs() < 0.01);
+        assert!((eased_curve.points[1].past.milliseconds()-0.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn eased_curve_starts_slowly() {
+        let curve       = TimeCurve::new(TimePoint(0.0, 0.0, 0.0), TimePoint(300.0, 0.0, 300.0));
+        let eased_curve = curve.with_easing(0, 0.0, 1.0);
+
+        let linear_pos  = curve.point_at_time(30.0).unwrap();
+        let eased_pos   = eased_curve.point_at_time(30.0).unwrap();
+
+        assert!(eased_pos.0 < linear_pos.0);
+    }
+
+    #[test]
+    fn retiming_point_keeps_easing() {
+        let curve       = TimeCurve::new(TimePoint(0.0, 0.0, 0.0), TimePoint(100.0, 0.0, 300.0));
+        let curve       = curve.set_point_at_time(Duration::from_millis(600), (200.0, 0.0));
+        let curve       = curve.with_easing(1, 1.0, 0.5);
+        let retimed     = curve.with_point_time(1, 400.0);
+
+        let (ease_in, ease_out) = retimed.easing_at(1);
+
+        assert!(retimed.points[1].point == TimePoint(100.0, 0.0, 400.0));
+        assert!((ease_in-1.0).abs() < 0.001);
+        assert!((ease_out-0.5).abs() < 0.001);
+    }
+
+    #[test]
+    fn retimed_point_stays_between_neighbours() {
+        let curve       = TimeCurve::new(TimePoint(0.0, 0.0, 0.0), TimePoint(100.0, 0.0, 300.0));
+        let curve       = curve.set_point_at_time(Duration::from_millis(600), (200.0, 0.0));
+        let retimed     = curve.with_point_time(1, 900.0);
+
+        assert!(retimed.points[1].point.milliseconds() < 600.0);
+    }
+
+    #[test]
+    fn handle_time_stays_within_section() {
+        let curve       = TimeCurve::new(TimePoint(0.0, 0.0, 0.0), TimePoint(100.0, 0.0, 300.0));
+        let retimed     = curve.with_handle_time(0, true, 200.0);
+        let clamped     = curve.with_handle_time(0, true, 500.0);
+
+        assert!(retimed.points[0].future.milliseconds() == 200.0);
+        assert!(clamped.points[0].future.milliseconds() == 300.0);
+    }
+
     #[test]
     fn moving_instant_start_point_changes_both_start_and_end_point() {
         let curve       = TimeCurve::new(TimePoint(40.0, 40.0, 40.0), TimePoint(40.0, 40.0, 40.0));
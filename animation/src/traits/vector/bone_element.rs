@@ -0,0 +1,111 @@
+use super::vector::*;
+use super::properties::*;
+use super::control_point::*;
+use super::vector_element::*;
+use super::path_conversion_options::*;
+use super::super::edit::*;
+use super::super::path::*;
+use super::super::bone::*;
+
+use flo_canvas::*;
+
+use std::sync::*;
+use std::time::Duration;
+
+///
+/// The bone element describes a bone in a skeleton as a vector element
+///
+#[derive(Clone, Debug)]
+pub struct BoneElement {
+    id:     ElementId,
+    bone:   Arc<Bone>
+}
+
+impl BoneElement {
+    ///
+    /// Creates a new bone element
+    ///
+    pub fn new(id: ElementId, bone: Bone) -> BoneElement {
+        BoneElement {
+            id:     id,
+            bone:   Arc::new(bone)
+        }
+    }
+
+    ///
+    /// Retrieves the bone represented by this element
+    ///
+    pub fn bone(&self) -> Arc<Bone> {
+        Arc::clone(&self.bone)
+    }
+}
+
+impl VectorElement for BoneElement {
+    ///
+    /// The ID of this element
+    ///
+    fn id(&self) -> ElementId {
+        self.id
+    }
+
+    ///
+    /// Modifies this element to have a new ID
+    ///
+    fn set_id(&mut self, new_id: ElementId) {
+        self.id = new_id
+    }
+
+    ///
+    /// Retrieves the paths for this element, if there are any
+    ///
+    fn to_path(&self, _properties: &VectorProperties, _options: PathConversion) -> Option<Vec<Path>> {
+        // Not a path element
+        None
+    }
+
+    ///
+    /// Renders this vector element
+    ///
+    fn render(&self, _gc: &mut dyn GraphicsPrimitives, _properties: &VectorProperties, _when: Duration) {
+        // Bones are only displayed while they're being edited
+    }
+
+    ///
+    /// Returns the properties to use for future elements
+    ///
+    /// This only applies the rotation of this bone: the rotations of its parents are applied by the frame, which can look them up
+    ///
+    fn update_properties(&self, properties: Arc<VectorProperties>, when: Duration) -> Arc<VectorProperties> {
+        if let Some(transform) = self.bone.transformation(when) {
+            // Add the rotation to the properties
+            let mut properties      = (*properties).clone();
+            let mut full_transform  = (*properties.transformations).clone();
+
+            full_transform.push(transform);
+
+            properties.transformations = Arc::new(full_transform);
+
+            Arc::new(properties)
+        } else {
+            // Bone is at rest
+            properties
+        }
+    }
+
+    ///
+    /// Fetches the control points for this element
+    ///
+    fn control_points(&self, _properties: &VectorProperties) -> Vec<ControlPoint> {
+        // Bones are posed with the bone tool rather than through their control points
+        vec![]
+    }
+
+    ///
+    /// Creates a new vector element from this one with the control points updated to the specified set of new values
+    ///
+    /// The vector here specifies the updated position for each control point in control_points
+    ///
+    fn with_adjusted_control_points(&self, _new_positions: Vec<(f32, f32)>, _properties: &VectorProperties) -> Vector {
+        Vector::Bone(self.clone())
+    }
+}
@@ -0,0 +1,372 @@
+use super::brush_element::*;
+use super::path_element::*;
+use super::vector_element::*;
+use super::super::path::*;
+use super::super::brush::*;
+
+use std::sync::*;
+
+///
+/// A bezier curve section used when tweening between two elements (the start point is the end of the previous section)
+///
+#[derive(Clone, Copy, PartialEq, Debug)]
+struct TweenSection {
+    cp1:    (f64, f64),
+    cp2:    (f64, f64),
+    end:    (f64, f64),
+    width:  f64
+}
+
+///
+/// A list of sections starting at a particular point
+///
+#[derive(Clone, PartialEq, Debug)]
+struct TweenCurve {
+    start:          (f64, f64),
+    start_width:    f64,
+    sections:       Vec<TweenSection>,
+    closed:         bool
+}
+
+#[inline]
+fn lerp_point(a: (f64, f64), b: (f64, f64), amount: f64) -> (f64, f64) {
+    (a.0 + (b.0-a.0)*amount, a.1 + (b.1-a.1)*amount)
+}
+
+#[inline]
+fn mid_point(a: (f64, f64), b: (f64, f64)) -> (f64, f64) {
+    lerp_point(a, b, 0.5)
+}
+
+#[inline]
+fn distance(a: (f64, f64), b: (f64, f64)) -> f64 {
+    ((b.0-a.0)*(b.0-a.0) + (b.1-a.1)*(b.1-a.1)).sqrt()
+}
+
+impl TweenCurve {
+    ///
+    /// Creates a curve with no sections
+    ///
+    fn new(start: (f64, f64), start_width: f64) -> TweenCurve {
+        TweenCurve {
+            start:          start,
+            start_width:    start_width,
+            sections:       vec![],
+            closed:         false
+        }
+    }
+
+    ///
+    /// Adds a straight line to the end of this curve
+    ///
+    fn line_to(&mut self, end: (f64, f64), width: f64) {
+        let start = self.sections.last().map(|section| section.end).unwrap_or(self.start);
+
+        self.sections.push(TweenSection {
+            cp1:    lerp_point(start, end, 1.0/3.0),
+            cp2:    lerp_point(start, end, 2.0/3.0),
+            end:    end,
+            width:  width
+        });
+    }
+
+    ///
+    /// Splits the longest section of this curve in half (so the curve has one more section without changing its shape)
+    ///
+    fn split_longest_section(&mut self) {
+        if self.sections.is_empty() {
+            // A curve that's just a point is extended by adding a zero-length section
+            let (start, width) = (self.start, self.start_width);
+            self.sections.push(TweenSection { cp1: start, cp2: start, end: start, width: width });
+            return;
+        }
+
+        // Find the section with the longest chord
+        let mut longest_index   = 0;
+        let mut longest_length  = -1.0;
+        let mut start           = self.start;
+        let mut longest_start   = self.start;
+        let mut longest_width   = self.start_width;
+        let mut start_width     = self.start_width;
+
+        for (index, section) in self.sections.iter().enumerate() {
+            let length = distance(start, section.end);
+
+            if length > longest_length {
+                longest_index   = index;
+                longest_length  = length;
+                longest_start   = start;
+                longest_width   = start_width;
+            }
+
+            start       = section.end;
+            start_width = section.width;
+        }
+
+        // Divide it at its midpoint using de Casteljau's algorithm
+        let section     = self.sections[longest_index];
+        let ab          = mid_point(longest_start, section.cp1);
+        let bc          = mid_point(section.cp1, section.cp2);
+        let cd          = mid_point(section.cp2, section.end);
+        let abc         = mid_point(ab, bc);
+        let bcd         = mid_point(bc, cd);
+        let mid         = mid_point(abc, bcd);
+
+        let first_half  = TweenSection { cp1: ab, cp2: abc, end: mid, width: (longest_width + section.width)/2.0 };
+        let second_half = TweenSection { cp1: bcd, cp2: cd, end: section.end, width: section.width };
+
+        self.sections[longest_index] = second_half;
+        self.sections.insert(longest_index, first_half);
+    }
+
+    ///
+    /// Interpolates between this curve and another one, which must have the same number of sections
+    ///
+    fn lerp(&self, target: &TweenCurve, amount: f64) -> TweenCurve {
+        TweenCurve {
+            start:          lerp_point(self.start, target.start, amount),
+            start_width:    self.start_width + (target.start_width-self.start_width)*amount,
+            sections:       self.sections.iter().zip(target.sections.iter())
+                .map(|(from, to)| TweenSection {
+                    cp1:    lerp_point(from.cp1, to.cp1, amount),
+                    cp2:    lerp_point(from.cp2, to.cp2, amount),
+                    end:    lerp_point(from.end, to.end, amount),
+                    width:  from.width + (to.width-from.width)*amount
+                })
+                .collect(),
+            closed:         self.closed
+        }
+    }
+
+    ///
+    /// Interpolates between two curves, subdividing them so that their sections correspond
+    ///
+    fn tween(mut from: TweenCurve, mut to: TweenCurve, amount: f64) -> TweenCurve {
+        while from.sections.len() < to.sections.len() { from.split_longest_section(); }
+        while to.sections.len() < from.sections.len() { to.split_longest_section(); }
+
+        from.lerp(&to, amount)
+    }
+
+    ///
+    /// Creates a tween curve from a set of brush points
+    ///
+    fn from_brush_points(points: &[BrushPoint]) -> TweenCurve {
+        let to_f64      = |(x, y): (f32, f32)| (x as f64, y as f64);
+        let first       = points.get(0).map(|point| (to_f64(point.position), point.width as f64)).unwrap_or(((0.0, 0.0), 0.0));
+        let mut curve   = TweenCurve::new(first.0, first.1);
+
+        curve.sections  = points.iter().skip(1)
+            .map(|point| TweenSection {
+                cp1:    to_f64(point.cp1),
+                cp2:    to_f64(point.cp2),
+                end:    to_f64(point.position),
+                width:  point.width as f64
+            })
+            .collect();
+
+        curve
+    }
+
+    ///
+    /// Converts this curve to a set of brush points
+    ///
+    fn to_brush_points(&self) -> Vec<BrushPoint> {
+        let to_f32      = |(x, y): (f64, f64)| (x as f32, y as f32);
+        let start       = to_f32(self.start);
+        let first_point = BrushPoint { position: start, cp1: start, cp2: start, width: self.start_width as f32 };
+
+        Some(first_point).into_iter()
+            .chain(self.sections.iter().map(|section| BrushPoint {
+                position:   to_f32(section.end),
+                cp1:        to_f32(section.cp1),
+                cp2:        to_f32(section.cp2),
+                width:      section.width as f32
+            }))
+            .collect()
+    }
+
+    ///
+    /// Splits a path into the curves that make up its subpaths (or None if the path can't be tweened)
+    ///
+    fn from_path(path: &Path) -> Option<Vec<TweenCurve>> {
+        let mut curves  = vec![];
+        let mut current = None;
+
+        for component in path.elements_ref() {
+            match component {
+                PathComponent::Move(point)              => {
+                    curves.extend(current.take());
+                    current = Some(TweenCurve::new(point.position, 0.0));
+                }
+
+                PathComponent::Line(point)              => {
+                    current.as_mut()?.line_to(point.position, 0.0);
+                }
+
+                PathComponent::Bezier(point, cp1, cp2)  => {
+                    current.as_mut()?.sections.push(TweenSection { cp1: cp1.position, cp2: cp2.position, end: point.position, width: 0.0 });
+                }
+
+                PathComponent::Close                    => {
+                    let mut curve   = current.take()?;
+                    curve.closed    = true;
+                    curves.push(curve);
+                }
+            }
+        }
+
+        curves.extend(current.take());
+        Some(curves)
+    }
+
+    ///
+    /// Converts a set of curves back into a path
+    ///
+    fn to_path(curves: &[TweenCurve]) -> Path {
+        let to_point = |(x, y): (f64, f64)| PathPoint { position: (x, y) };
+
+        Path::from_elements(curves.iter()
+            .flat_map(|curve| {
+                Some(PathComponent::Move(to_point(curve.start))).into_iter()
+                    .chain(curve.sections.iter().map(move |section| PathComponent::Bezier(to_point(section.end), to_point(section.cp1), to_point(section.cp2))))
+                    .chain(if curve.closed { Some(PathComponent::Close) } else { None })
+            }))
+    }
+}
+
+impl BrushElement {
+    ///
+    /// Creates the brush stroke that's part of the way between this stroke and a target stroke
+    ///
+    /// An amount of 0.0 is this stroke and 1.0 is the target. The points of the strokes are matched up in order,
+    /// with the stroke with fewer points being subdivided until they match. The result has the ID of this element.
+    ///
+    pub fn tween_to(&self, target: &BrushElement, amount: f64) -> BrushElement {
+        let from    = TweenCurve::from_brush_points(&self.points());
+        let to      = TweenCurve::from_brush_points(&target.points());
+        let tweened = TweenCurve::tween(from, to, amount);
+
+        BrushElement::new(self.id(), Arc::new(tweened.to_brush_points()))
+    }
+}
+
+impl PathElement {
+    ///
+    /// Creates the path that's part of the way between this path and a target path
+    ///
+    /// An amount of 0.0 is this path and 1.0 is the target. Paths can only be tweened if they have the same number of
+    /// subpaths and the subpaths are open or closed in the same way: None is returned if they don't match. The result
+    /// uses the ID and brush of this element.
+    ///
+    pub fn tween_to(&self, target: &PathElement, amount: f64) -> Option<PathElement> {
+        let from    = TweenCurve::from_path(self.path())?;
+        let to      = TweenCurve::from_path(target.path())?;
+
+        if from.len() != to.len()                                                   { return None; }
+        if from.iter().zip(to.iter()).any(|(from, to)| from.closed != to.closed)    { return None; }
+
+        let tweened = from.into_iter().zip(to.into_iter())
+            .map(|(from, to)| TweenCurve::tween(from, to, amount))
+            .collect::<Vec<_>>();
+
+        Some(PathElement::new(self.id(), TweenCurve::to_path(&tweened), self.brush(), self.properties()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::traits::*;
+
+    fn close_to(a: (f32, f32), b: (f32, f32)) -> bool {
+        (a.0-b.0).abs() < 0.01 && (a.1-b.1).abs() < 0.01
+    }
+
+    fn line_stroke(id: i64, points: Vec<(f32, f32)>) -> BrushElement {
+        let mut last    = points[0];
+        let points      = points.into_iter()
+            .map(|point| {
+                let cp1 = (last.0 + (point.0-last.0)/3.0, last.1 + (point.1-last.1)/3.0);
+                let cp2 = (last.0 + (point.0-last.0)*2.0/3.0, last.1 + (point.1-last.1)*2.0/3.0);
+                last    = point;
+
+                BrushPoint { position: point, cp1: cp1, cp2: cp2, width: 1.0 }
+            })
+            .collect::<Vec<_>>();
+
+        BrushElement::new(ElementId::Assigned(id), Arc::new(points))
+    }
+
+    fn path_element(id: i64, path: Path) -> PathElement {
+        let brush       = Arc::new(BrushDefinitionElement::new(ElementId::Unassigned, BrushDefinition::Simple, BrushDrawingStyle::Draw));
+        let properties  = Arc::new(BrushPropertiesElement::new(ElementId::Unassigned, BrushProperties::new()));
+
+        PathElement::new(ElementId::Assigned(id), path, brush, properties)
+    }
+
+    #[test]
+    fn tween_brush_strokes_with_same_points() {
+        let from    = line_stroke(1, vec![(0.0, 0.0), (10.0, 0.0)]);
+        let to      = line_stroke(2, vec![(0.0, 10.0), (10.0, 10.0)]);
+
+        let tweened = from.tween_to(&to, 0.5);
+        let points  = tweened.points();
+
+        assert!(tweened.id() == ElementId::Assigned(1));
+        assert!(points.len() == 2);
+        assert!(close_to(points[0].position, (0.0, 5.0)));
+        assert!(close_to(points[1].position, (10.0, 5.0)));
+    }
+
+    #[test]
+    fn tween_brush_strokes_with_different_points() {
+        let from    = line_stroke(1, vec![(0.0, 0.0), (10.0, 0.0)]);
+        let to      = line_stroke(2, vec![(0.0, 10.0), (5.0, 10.0), (10.0, 10.0)]);
+
+        let tweened = from.tween_to(&to, 0.5);
+        let points  = tweened.points();
+
+        assert!(points.len() == 3);
+        assert!(close_to(points[1].position, (5.0, 5.0)));
+        assert!(close_to(points[2].position, (10.0, 5.0)));
+    }
+
+    #[test]
+    fn tween_closed_paths() {
+        let square  = |size: f32| Path::from_elements(vec![
+            PathComponent::Move(PathPoint::new(0.0, 0.0)),
+            PathComponent::Line(PathPoint::new(size, 0.0)),
+            PathComponent::Line(PathPoint::new(size, size)),
+            PathComponent::Line(PathPoint::new(0.0, size)),
+            PathComponent::Close
+        ]);
+        let from    = path_element(1, square(10.0));
+        let to      = path_element(2, square(20.0));
+
+        let tweened     = from.tween_to(&to, 0.5).unwrap();
+        let components  = tweened.path().elements().collect::<Vec<_>>();
+
+        assert!(components.len() == 5);
+        assert!(components[4] == PathComponent::Close);
+        match components[2] {
+            PathComponent::Bezier(point, _, _)  => assert!(point == PathPoint::new(15.0, 15.0)),
+            _                                   => assert!(false)
+        }
+    }
+
+    #[test]
+    fn cannot_tween_open_path_to_closed_path() {
+        let open    = path_element(1, Path::from_elements(vec![
+            PathComponent::Move(PathPoint::new(0.0, 0.0)),
+            PathComponent::Line(PathPoint::new(10.0, 0.0))
+        ]));
+        let closed  = path_element(2, Path::from_elements(vec![
+            PathComponent::Move(PathPoint::new(0.0, 0.0)),
+            PathComponent::Line(PathPoint::new(10.0, 0.0)),
+            PathComponent::Close
+        ]));
+
+        assert!(open.tween_to(&closed, 0.5).is_none());
+    }
+}
@@ -13,6 +13,9 @@ mod transformed_vector;
 mod path_conversion_options;
 mod brush_properties_element;
 mod brush_definition_element;
+mod element_tween;
+mod symbol_element;
+mod bone_element;
 
 pub use self::vector::*;
 pub use self::properties::*;
@@ -29,3 +32,6 @@ pub use self::transformed_vector::*;
 pub use self::path_conversion_options::*;
 pub use self::brush_properties_element::*;
 pub use self::brush_definition_element::*;
+pub use self::element_tween::*;
+pub use self::symbol_element::*;
+pub use self::bone_element::*;
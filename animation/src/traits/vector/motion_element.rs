@@ -6,6 +6,7 @@ use super::path_conversion_options::*;
 use super::super::edit::*;
 use super::super::path::*;
 use super::super::motion::*;
+use super::super::time_path::*;
 
 use flo_canvas::*;
 
@@ -97,8 +98,25 @@ impl VectorElement for MotionElement {
     /// Fetches the control points for this element
     ///
     fn control_points(&self, _properties: &VectorProperties) -> Vec<ControlPoint> {
-        // TODO: can return control points to make this motion editable here
-        vec![]
+        match &*self.motion {
+            Motion::Translate(translate) => {
+                // The control points are the points along the path the origin follows, and their handles
+                translate.translate.control_points()
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, TimePoint(x, y, _))| {
+                        if index%3 == 0 {
+                            ControlPoint::BezierPoint(x, y)
+                        } else {
+                            ControlPoint::BezierControlPoint(x, y)
+                        }
+                    })
+                    .collect()
+            }
+
+            // Other types of motion have no control points
+            _ => vec![]
+        }
     }
 
     ///
@@ -106,8 +124,17 @@ impl VectorElement for MotionElement {
     ///
     /// The vector here specifies the updated position for each control point in control_points
     ///
-    fn with_adjusted_control_points(&self, _new_positions: Vec<(f32, f32)>, _properties: &VectorProperties) -> Vector {
-        // TODO: can update time points from control points if we want
-        Vector::Motion(self.clone())
+    fn with_adjusted_control_points(&self, new_positions: Vec<(f32, f32)>, _properties: &VectorProperties) -> Vector {
+        match &*self.motion {
+            Motion::Translate(translate) => {
+                // Move the points in the path, leaving their times as they were
+                let mut translate = translate.clone();
+                translate.set_path(translate.translate.with_control_point_positions(new_positions));
+
+                Vector::Motion(MotionElement::new(self.id, Motion::Translate(translate)))
+            }
+
+            _ => Vector::Motion(self.clone())
+        }
     }
 }
@@ -0,0 +1,299 @@
+use super::vector::*;
+use super::properties::*;
+use super::control_point::*;
+use super::vector_element::*;
+use super::transformation::*;
+use super::path_conversion_options::*;
+use super::super::edit::*;
+use super::super::path::*;
+use super::super::frame::*;
+
+use flo_canvas::*;
+
+use std::fmt;
+use std::sync::*;
+use std::time::Duration;
+
+///
+/// Describes what an instance of a symbol displays once it has played to the end of the symbol's timeline
+///
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum SymbolLoopMode {
+    /// The symbol starts again from the beginning
+    Loop,
+
+    /// The symbol stays on its final frame
+    PlayOnce,
+
+    /// The symbol doesn't play, and always displays the frame at the time offset
+    SingleFrame
+}
+
+///
+/// The settings for an instance of a symbol
+///
+#[derive(Clone, PartialEq, Debug)]
+pub struct SymbolInstance {
+    /// The ID of the symbol that is displayed by this instance
+    pub symbol_id: u64,
+
+    /// The transformations applied to the symbol when it's displayed
+    pub transformations: Vec<Transformation>,
+
+    /// The time in the symbol's timeline that is displayed when this instance first appears
+    pub time_offset: Duration,
+
+    /// What this instance displays when it has played to the end of the symbol
+    pub loop_mode: SymbolLoopMode,
+
+    /// The speed that the symbol plays at (1.0 is the same speed as the animation the instance is in)
+    pub playback_rate: f64
+}
+
+///
+/// Vector element that displays an instance of a symbol (a separately stored animation with its own layers)
+///
+#[derive(Clone)]
+pub struct SymbolElement {
+    /// The ID of this element
+    id: ElementId,
+
+    /// How the symbol is displayed
+    instance: SymbolInstance,
+
+    /// The frames from each of the symbol's layers, if they have been loaded
+    content: Arc<Vec<Arc<dyn Frame>>>
+}
+
+impl SymbolInstance {
+    ///
+    /// Creates a new instance of a symbol with no transformations, that plays at normal speed and loops
+    ///
+    pub fn new(symbol_id: u64) -> SymbolInstance {
+        SymbolInstance {
+            symbol_id:          symbol_id,
+            transformations:    vec![],
+            time_offset:        Duration::from_millis(0),
+            loop_mode:          SymbolLoopMode::Loop,
+            playback_rate:      1.0
+        }
+    }
+
+    ///
+    /// Works out the time in the symbol's timeline to display for a given time since the instance first appeared
+    ///
+    pub fn symbol_time(&self, time_since_start: Duration, symbol_length: Duration) -> Duration {
+        let offset_micros   = self.time_offset.as_micros() as f64;
+        let length_micros   = symbol_length.as_micros() as f64;
+        let played_micros   = (time_since_start.as_micros() as f64) * self.playback_rate.max(0.0);
+
+        let symbol_micros   = match self.loop_mode {
+            SymbolLoopMode::SingleFrame => offset_micros,
+            SymbolLoopMode::PlayOnce    => offset_micros + played_micros,
+            SymbolLoopMode::Loop        => {
+                if length_micros > 0.0 {
+                    (offset_micros + played_micros) % length_micros
+                } else {
+                    0.0
+                }
+            }
+        };
+
+        // Times are limited to the last frame of the symbol
+        let symbol_micros   = symbol_micros.min((length_micros - 1.0).max(0.0));
+
+        Duration::from_micros(symbol_micros.floor() as u64)
+    }
+}
+
+impl SymbolElement {
+    ///
+    /// Creates a new symbol element (with no content loaded)
+    ///
+    pub fn new(id: ElementId, instance: SymbolInstance) -> SymbolElement {
+        SymbolElement {
+            id:         id,
+            instance:   instance,
+            content:    Arc::new(vec![])
+        }
+    }
+
+    ///
+    /// The settings for this instance of the symbol
+    ///
+    pub fn instance(&self) -> &SymbolInstance {
+        &self.instance
+    }
+
+    ///
+    /// Creates a copy of this element with new instance settings
+    ///
+    pub fn with_instance(&self, instance: SymbolInstance) -> SymbolElement {
+        SymbolElement {
+            id:         self.id,
+            instance:   instance,
+            content:    Arc::clone(&self.content)
+        }
+    }
+
+    ///
+    /// The frames from the symbol's layers that are displayed by this element
+    ///
+    pub fn content(&self) -> Arc<Vec<Arc<dyn Frame>>> {
+        Arc::clone(&self.content)
+    }
+
+    ///
+    /// Creates a copy of this element that displays the specified frames from the symbol's layers
+    ///
+    pub fn with_content(&self, content: Vec<Arc<dyn Frame>>) -> SymbolElement {
+        SymbolElement {
+            id:         self.id,
+            instance:   self.instance.clone(),
+            content:    Arc::new(content)
+        }
+    }
+
+    ///
+    /// Returns the transformations to apply to the content of this symbol, in the order they should be applied
+    ///
+    fn all_transformations<'a>(&'a self, properties: &'a VectorProperties) -> impl 'a+DoubleEndedIterator<Item=&'a Transformation> {
+        self.instance.transformations.iter()
+            .chain(properties.transformations.iter())
+    }
+}
+
+impl fmt::Debug for SymbolElement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SymbolElement")
+            .field("id", &self.id)
+            .field("instance", &self.instance)
+            .field("content", &format!("{} frames", self.content.len()))
+            .finish()
+    }
+}
+
+impl VectorElement for SymbolElement {
+    ///
+    /// The ID of this element
+    ///
+    fn id(&self) -> ElementId {
+        self.id
+    }
+
+    ///
+    /// Modifies this element to have a new ID
+    ///
+    fn set_id(&mut self, new_id: ElementId) {
+        self.id = new_id
+    }
+
+    ///
+    /// Retrieves the paths for this element, if there are any
+    ///
+    fn to_path(&self, properties: &VectorProperties, options: PathConversion) -> Option<Vec<Path>> {
+        let mut paths = vec![];
+
+        // Fetch the paths for the elements in each of the frames making up the symbol
+        for frame in self.content.iter() {
+            for element in frame.vector_elements().into_iter().flatten() {
+                let element_properties = frame.apply_properties_for_element(&element, Arc::new(VectorProperties::default()));
+                paths.extend(element.to_path(&element_properties, options).into_iter().flatten());
+            }
+        }
+
+        if paths.is_empty() {
+            return None;
+        }
+
+        // Position them where the instance is
+        for transform in self.all_transformations(properties) {
+            for path in paths.iter_mut() {
+                *path = transform.transform_path(path);
+            }
+        }
+
+        Some(paths)
+    }
+
+    ///
+    /// Renders this vector element
+    ///
+    fn render(&self, gc: &mut dyn GraphicsPrimitives, properties: &VectorProperties, _when: Duration) {
+        if self.content.is_empty() {
+            return;
+        }
+
+        gc.push_state();
+
+        // Transformations are multiplied into the canvas transform, so the last one to be applied must be set first
+        for transform in self.all_transformations(properties).rev() {
+            gc.transform(transform.clone().into());
+        }
+
+        for frame in self.content.iter() {
+            frame.render_to(gc);
+        }
+
+        gc.pop_state();
+    }
+
+    ///
+    /// Fetches the control points for this element
+    ///
+    fn control_points(&self, _properties: &VectorProperties) -> Vec<ControlPoint> {
+        // Symbols are edited by entering them rather than through their control points
+        vec![]
+    }
+
+    ///
+    /// Creates a new vector element from this one with the control points updated to the specified set of new values
+    ///
+    /// The vector here specifies the updated position for each control point in control_points
+    ///
+    fn with_adjusted_control_points(&self, _new_positions: Vec<(f32, f32)>, _properties: &VectorProperties) -> Vector {
+        Vector::Symbol(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn loop_wraps_around() {
+        let instance = SymbolInstance::new(1);
+
+        assert!(instance.symbol_time(Duration::from_millis(500), Duration::from_millis(2000)) == Duration::from_millis(500));
+        assert!(instance.symbol_time(Duration::from_millis(2500), Duration::from_millis(2000)) == Duration::from_millis(500));
+    }
+
+    #[test]
+    fn play_once_holds_last_frame() {
+        let mut instance    = SymbolInstance::new(1);
+        instance.loop_mode  = SymbolLoopMode::PlayOnce;
+
+        assert!(instance.symbol_time(Duration::from_millis(500), Duration::from_millis(2000)) == Duration::from_millis(500));
+        assert!(instance.symbol_time(Duration::from_millis(5000), Duration::from_millis(2000)) < Duration::from_millis(2000));
+        assert!(instance.symbol_time(Duration::from_millis(5000), Duration::from_millis(2000)) > Duration::from_millis(1999));
+    }
+
+    #[test]
+    fn single_frame_uses_offset() {
+        let mut instance        = SymbolInstance::new(1);
+        instance.loop_mode      = SymbolLoopMode::SingleFrame;
+        instance.time_offset    = Duration::from_millis(250);
+
+        assert!(instance.symbol_time(Duration::from_millis(0), Duration::from_millis(2000)) == Duration::from_millis(250));
+        assert!(instance.symbol_time(Duration::from_millis(1500), Duration::from_millis(2000)) == Duration::from_millis(250));
+    }
+
+    #[test]
+    fn playback_rate_and_offset() {
+        let mut instance        = SymbolInstance::new(1);
+        instance.playback_rate  = 2.0;
+        instance.time_offset    = Duration::from_millis(100);
+
+        assert!(instance.symbol_time(Duration::from_millis(300), Duration::from_millis(2000)) == Duration::from_millis(700));
+    }
+}
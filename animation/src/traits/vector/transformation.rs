@@ -51,13 +51,15 @@ impl Transformation {
         let (ox, oy) = (origin.x(), origin.y());
 
         match transform {
-            ElementTransform::SetAnchor(_, _)   => Transformation::Translate(0.0, 0.0),
-            ElementTransform::Align(_)          => Transformation::Translate(0.0, 0.0),
-            ElementTransform::FlipHorizontal    => Transformation::FlipHoriz(ox, oy),
-            ElementTransform::FlipVertical      => Transformation::FlipVert(ox, oy),
-            ElementTransform::Scale(sx, sy)     => Transformation::Scale(sx, sy, (ox, oy)),
-            ElementTransform::Rotate(theta)     => Transformation::Rotate(theta, (ox, oy)),
-            ElementTransform::MoveTo(x, y)      => Transformation::Translate(x+ox, y+oy),
+            ElementTransform::SetAnchor(_, _)        => Transformation::Translate(0.0, 0.0),
+            ElementTransform::Align(_)               => Transformation::Translate(0.0, 0.0),
+            ElementTransform::DistributeHorizontally => Transformation::Translate(0.0, 0.0),
+            ElementTransform::DistributeVertically   => Transformation::Translate(0.0, 0.0),
+            ElementTransform::FlipHorizontal         => Transformation::FlipHoriz(ox, oy),
+            ElementTransform::FlipVertical           => Transformation::FlipVert(ox, oy),
+            ElementTransform::Scale(sx, sy)          => Transformation::Scale(sx, sy, (ox, oy)),
+            ElementTransform::Rotate(theta)          => Transformation::Rotate(theta, (ox, oy)),
+            ElementTransform::MoveTo(x, y)           => Transformation::Translate(x+ox, y+oy),
         }
     }
 
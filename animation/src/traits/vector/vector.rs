@@ -3,6 +3,8 @@ use super::brush_element::*;
 use super::group_element::*;
 use super::error_element::*;
 use super::motion_element::*;
+use super::symbol_element::*;
+use super::bone_element::*;
 use super::vector_element::*;
 use super::transformation::*;
 use super::transformed_vector::*;
@@ -44,6 +46,12 @@ pub enum Vector {
     /// Attached to an element to indicate a transformation that should be applied to it when rendering
     Transformation((ElementId, SmallVec<[Transformation; 2]>)),
 
+    /// An instance of a symbol (a separately stored animation with its own layers)
+    Symbol(SymbolElement),
+
+    /// Element describing a bone in a skeleton
+    Bone(BoneElement),
+
     /// Element exists but could not be loaded from the file
     Error
 }
@@ -114,6 +122,18 @@ impl Vector {
             _ => self.clone()
         }
     }
+
+    ///
+    /// Creates an updated vector element with new symbol instance settings
+    ///
+    pub fn with_symbol_instance(&self, instance: SymbolInstance) -> Vector {
+        match self {
+            Vector::Symbol(symbol_element) => Vector::Symbol(symbol_element.with_instance(instance)),
+
+            // Element is unchanged if it's not a symbol instance
+            _ => self.clone()
+        }
+    }
 }
 
 impl DerefMut for Vector {
@@ -132,6 +152,8 @@ impl DerefMut for Vector {
             Motion(elem)                    => elem,
             Group(elem)                     => elem,
             Transformation(elem)            => elem,
+            Symbol(elem)                    => elem,
+            Bone(elem)                      => elem,
             Error                           => panic!("Cannot edit an error element")
         }
     }
@@ -155,6 +177,8 @@ impl Deref for Vector {
             Motion(elem)                    => elem,
             Group(elem)                     => elem,
             Transformation(transform)       => transform,
+            Symbol(elem)                    => elem,
+            Bone(elem)                      => elem,
             Error                           => &*ERROR_ELEMENT
         }
     }
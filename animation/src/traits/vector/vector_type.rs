@@ -26,6 +26,12 @@ pub enum VectorType {
     /// A property describing a transformation that can be applied to another element
     Transformation,
 
+    /// An instance of a symbol
+    Symbol,
+
+    /// A bone in a skeleton
+    Bone,
+
     /// Element that exists but could not be loaded
     Error
 }
@@ -43,6 +49,8 @@ impl From<&Vector> for VectorType {
             Motion(_)                       => VectorType::Motion,
             Group(_)                        => VectorType::Group,
             Transformation(_)               => VectorType::Transformation,
+            Symbol(_)                       => VectorType::Symbol,
+            Bone(_)                         => VectorType::Bone,
             Error                           => VectorType::Error
         }
     }
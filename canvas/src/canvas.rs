@@ -77,6 +77,16 @@ impl CanvasCore {
         }
     }
 
+    ///
+    /// True if a drawing command defines a texture (these are kept when the canvas is cleared)
+    ///
+    fn is_texture_command(draw: &Draw) -> bool {
+        match draw {
+            Draw::Texture(_, _) => true,
+            _                   => false
+        }
+    }
+
     ///
     /// Removes all of the drawing for the specified layer
     ///
@@ -93,6 +103,8 @@ impl CanvasCore {
                 match drawing {
                     &(_, Draw::ClearCanvas)         => true,
                     &(_, Draw::LayerBlend(_, _))    => true,
+                    &(_, Draw::Gradient(_, _))      => true,
+                    &(_, Draw::Texture(_, _))       => true,
                     &(layer, _)                     => layer != layer_id
                 }
             })
@@ -114,20 +126,35 @@ impl CanvasCore {
         to_draw.iter().for_each(|draw| {
             match draw {
                 &Draw::ClearCanvas => {
-                    // Clearing the canvas empties the command list and updates the clear count
-                    self.drawing_since_last_clear   = vec![];
+                    // Clearing the canvas empties the command list and updates the clear count (textures are kept until they're freed)
+                    let textures                    = self.drawing_since_last_clear.drain(..).filter(|(_, draw)| Self::is_texture_command(draw)).collect::<Vec<_>>();
                     self.current_layer              = 0;
                     clear_pending                   = true;
 
-                    new_drawing = vec![];
+                    new_drawing.retain(Self::is_texture_command);
 
                     // Start the new drawing with the 'clear' command
-                    self.drawing_since_last_clear.push((0, *draw));
+                    self.drawing_since_last_clear.push((0, draw.clone()));
+                    self.drawing_since_last_clear.extend(textures);
+                },
+
+                &Draw::Texture(texture_id, TextureOp::Create(_, _, _)) |
+                &Draw::Texture(texture_id, TextureOp::Free) => {
+                    // Creating or freeing a texture replaces anything that was done to it before (including drawing it, as the old texture no longer exists to be drawn)
+                    self.drawing_since_last_clear.retain(|(_, draw)| match draw {
+                        Draw::Texture(existing_id, _)           => *existing_id != texture_id,
+                        Draw::DrawTexture(existing_id, _, _, _) => *existing_id != texture_id,
+                        _                                       => true
+                    });
+
+                    if let &Draw::Texture(_, TextureOp::Create(_, _, _)) = draw {
+                        self.drawing_since_last_clear.push((self.current_layer, draw.clone()));
+                    }
                 },
 
                 &Draw::Restore => {
                     // Have to push the restore in case it can't be cleared
-                    self.drawing_since_last_clear.push((self.current_layer, *draw));
+                    self.drawing_since_last_clear.push((self.current_layer, draw.clone()));
 
                     // On a 'restore' command we clear out everything since the 'store' if we can (so we don't build a backlog)
                     self.rewind_to_last_store();
@@ -140,13 +167,13 @@ impl CanvasCore {
                         self.drawing_since_last_clear.pop();
                     } else {
                         // Something else: the free becomes part of the drawing log (this is often inefficient)
-                        self.drawing_since_last_clear.push((self.current_layer, *draw));
+                        self.drawing_since_last_clear.push((self.current_layer, draw.clone()));
                     }
                 },
 
                 &Draw::Layer(new_layer) => {
                     self.current_layer = new_layer;
-                    self.drawing_since_last_clear.push((new_layer, *draw));
+                    self.drawing_since_last_clear.push((new_layer, draw.clone()));
                 },
 
                 &Draw::ClearLayer => {
@@ -157,11 +184,11 @@ impl CanvasCore {
                 },
 
                 // Default is to add to the current drawing
-                _ => self.drawing_since_last_clear.push((self.current_layer, *draw))
+                _ => self.drawing_since_last_clear.push((self.current_layer, draw.clone()))
             }
 
             // Send everything to the streams
-            new_drawing.push(*draw);
+            new_drawing.push(draw.clone());
         });
 
         // Send the new drawing commands to the streams
@@ -169,7 +196,7 @@ impl CanvasCore {
 
         for stream_index in 0..self.pending_streams.len() {
             // Send commands to this stream
-            if !self.pending_streams[stream_index].send_drawing(new_drawing.iter().map(|draw| *draw), clear_pending) {
+            if !self.pending_streams[stream_index].send_drawing(new_drawing.iter().cloned(), clear_pending) {
                 // If it returns false then the stream has been dropped and we should remove it from this object
                 to_remove.push(stream_index);
             }
@@ -235,7 +262,7 @@ impl Canvas {
         let add_stream = Arc::clone(&new_stream);
         self.core.sync(move |core| {
             // Send the data we've received since the last clear
-            add_stream.send_drawing(core.drawing_since_last_clear.iter().map(|&(_, draw)| draw), true);
+            add_stream.send_drawing(core.drawing_since_last_clear.iter().map(|(_, draw)| draw.clone()), true);
 
             // Store the stream in the core so future notifications get sent there
             core.pending_streams.push(add_stream);
@@ -249,7 +276,7 @@ impl Canvas {
     /// Retrieves the list of drawing actions in this canvas
     ///
     pub fn get_drawing(&self) -> Vec<Draw> {
-        self.core.sync(|core| core.drawing_since_last_clear.iter().map(|&(_, draw)| draw.clone()).collect())
+        self.core.sync(|core| core.drawing_since_last_clear.iter().map(|(_, draw)| draw.clone()).collect())
     }
 }
 
@@ -291,6 +318,11 @@ impl<'a> GraphicsContext for CoreContext<'a> {
     fn dash_offset(&mut self, offset: f32)                      { self.pending.push(Draw::DashOffset(offset)); }
     fn fill_color(&mut self, col: Color)                        { self.pending.push(Draw::FillColor(col)); }
     fn stroke_color(&mut self, col: Color)                      { self.pending.push(Draw::StrokeColor(col)); }
+    fn gradient(&mut self, gradient_id: GradientId, op: GradientOp) { self.pending.push(Draw::Gradient(gradient_id, op)); }
+    fn fill_gradient(&mut self, gradient_id: GradientId, x1: f32, y1: f32, x2: f32, y2: f32) { self.pending.push(Draw::FillGradient(gradient_id, (x1, y1), (x2, y2))); }
+    fn fill_radial_gradient(&mut self, gradient_id: GradientId, center_x: f32, center_y: f32, radius: f32) { self.pending.push(Draw::FillRadialGradient(gradient_id, (center_x, center_y), radius)); }
+    fn texture(&mut self, texture_id: TextureId, op: TextureOp)  { self.pending.push(Draw::Texture(texture_id, op)); }
+    fn draw_texture(&mut self, texture_id: TextureId, x1: f32, y1: f32, x2: f32, y2: f32, alpha: f32) { self.pending.push(Draw::DrawTexture(texture_id, (x1, y1), (x2, y2), alpha)); }
     fn blend_mode(&mut self, mode: BlendMode)                   { self.pending.push(Draw::BlendMode(mode)); }
     fn identity_transform(&mut self)                            { self.pending.push(Draw::IdentityTransform); }
     fn canvas_height(&mut self, height: f32)                    { self.pending.push(Draw::CanvasHeight(height)); }
@@ -388,9 +420,12 @@ impl CanvasStream {
     fn send_drawing<DrawIter: Iterator<Item=Draw>> (&self, drawing: DrawIter, clear_pending: bool) -> bool {
         let mut core = self.core.lock().unwrap();
 
-        // Clear out any pending commands if they're hidden by a clear
+        // Clear out any pending commands if they're hidden by a clear (textures stay defined until they're freed)
         if clear_pending {
-            core.queue.clear();
+            core.queue.retain(|draw| match draw {
+                Draw::Texture(_, _) => true,
+                _                   => false
+            });
         }
 
         // Push the drawing commands
@@ -802,4 +837,59 @@ mod test {
             assert!(stream.next().await == Some(Draw::Fill));
         });
     }
+
+    #[test]
+    fn textures_are_kept_when_canvas_is_cleared() {
+        let canvas      = Canvas::new();
+        let bytes       = Arc::new(vec![255, 255, 255, 255]);
+
+        // Define two textures, free one of them and then clear the canvas
+        canvas.draw(|gc| {
+            gc.texture(TextureId(1), TextureOp::Create(1, 1, TextureFormat::Rgba));
+            gc.texture(TextureId(1), TextureOp::SetBytes(0, 0, 1, 1, bytes.clone()));
+            gc.texture(TextureId(2), TextureOp::Create(1, 1, TextureFormat::Rgba));
+            gc.texture(TextureId(2), TextureOp::Free);
+
+            gc.new_path();
+            gc.clear_canvas();
+
+            gc.draw_texture(TextureId(1), 0.0, 0.0, 1.0, 1.0, 1.0);
+        });
+
+        // The texture that was not freed should still be defined after the clear
+        let mut stream  = canvas.stream();
+
+        executor::block_on(async {
+            assert!(stream.next().await == Some(Draw::ClearCanvas));
+            assert!(stream.next().await == Some(Draw::Texture(TextureId(1), TextureOp::Create(1, 1, TextureFormat::Rgba))));
+            assert!(stream.next().await == Some(Draw::Texture(TextureId(1), TextureOp::SetBytes(0, 0, 1, 1, bytes.clone()))));
+            assert!(stream.next().await == Some(Draw::DrawTexture(TextureId(1), (0.0, 0.0), (1.0, 1.0), 1.0)));
+        });
+    }
+
+    #[test]
+    fn freeing_texture_removes_drawing_that_uses_it() {
+        let canvas      = Canvas::new();
+
+        // Draw two textures, then free one of them
+        canvas.draw(|gc| {
+            gc.texture(TextureId(1), TextureOp::Create(1, 1, TextureFormat::Rgba));
+            gc.texture(TextureId(2), TextureOp::Create(1, 1, TextureFormat::Rgba));
+            gc.draw_texture(TextureId(1), 0.0, 0.0, 1.0, 1.0, 1.0);
+            gc.draw_texture(TextureId(2), 0.0, 0.0, 1.0, 1.0, 1.0);
+            gc.texture(TextureId(2), TextureOp::Free);
+
+            gc.new_path();
+        });
+
+        // Only the texture that's still defined should be drawn
+        let mut stream  = canvas.stream();
+
+        executor::block_on(async {
+            assert!(stream.next().await == Some(Draw::ClearCanvas));
+            assert!(stream.next().await == Some(Draw::Texture(TextureId(1), TextureOp::Create(1, 1, TextureFormat::Rgba))));
+            assert!(stream.next().await == Some(Draw::DrawTexture(TextureId(1), (0.0, 0.0), (1.0, 1.0), 1.0)));
+            assert!(stream.next().await == Some(Draw::NewPath));
+        });
+    }
 }
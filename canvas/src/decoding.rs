@@ -7,6 +7,7 @@ use futures::stream;
 use futures::task::{Poll};
 
 use std::mem;
+use std::sync::*;
 use std::str::*;
 use std::result::Result;
 
@@ -47,6 +48,22 @@ enum DecoderState {
 
     ColorStroke(String),                // 'Cs' (r, g, b, a)
     ColorFill(String),                  // 'Cf' (r, g, b, a)
+    ColorFillGradientId(String),        // 'Cg' (id)
+    ColorFillGradient(GradientId, String), // 'Cg' (id) (x1, y1, x2, y2)
+    ColorFillRadialId(String),          // 'Cr' (id)
+    ColorFillRadial(GradientId, String), // 'Cr' (id) (x, y, radius)
+
+    Gradient(String),                   // 'G' (id)
+    GradientOperation(GradientId),      // 'G' (id) (op)
+    GradientCreate(GradientId, String), // 'G' (id) 'n' (r, g, b, a)
+    GradientAddStop(GradientId, String), // 'G' (id) 's' (pos, r, g, b, a)
+
+    Texture(String),                    // 'B' (id)
+    TextureOperation(TextureId),        // 'B' (id) (op)
+    TextureCreate(TextureId, String),   // 'B' (id) 'N' (w, h, format)
+    TextureSetBytesHeader(TextureId, String), // 'B' (id) 'D' (x, y, w, h, len)
+    TextureSetBytes(TextureId, (u32, u32, u32, u32), usize, String), // 'B' (id) 'D' (x, y, w, h, len) (bytes)
+    TextureDraw(TextureId, String),     // 'B' (id) 'd' (x1, y1, x2, y2, alpha)
 
     BlendMode(String),                  // 'M' (mode)
 
@@ -140,6 +157,22 @@ impl CanvasDecoder {
 
             ColorStroke(param)              => Self::decode_color_stroke(next_chr, param)?,
             ColorFill(param)                => Self::decode_color_fill(next_chr, param)?,
+            ColorFillGradientId(param)      => Self::decode_color_fill_gradient_id(next_chr, param)?,
+            ColorFillGradient(id, param)    => Self::decode_color_fill_gradient(next_chr, id, param)?,
+            ColorFillRadialId(param)        => Self::decode_color_fill_radial_id(next_chr, param)?,
+            ColorFillRadial(id, param)      => Self::decode_color_fill_radial(next_chr, id, param)?,
+
+            Gradient(param)                 => Self::decode_gradient(next_chr, param)?,
+            GradientOperation(id)           => Self::decode_gradient_op(next_chr, id)?,
+            GradientCreate(id, param)       => Self::decode_gradient_create(next_chr, id, param)?,
+            GradientAddStop(id, param)      => Self::decode_gradient_add_stop(next_chr, id, param)?,
+
+            Texture(param)                  => Self::decode_texture(next_chr, param)?,
+            TextureOperation(id)            => Self::decode_texture_op(next_chr, id)?,
+            TextureCreate(id, param)        => Self::decode_texture_create(next_chr, id, param)?,
+            TextureSetBytesHeader(id, param) => Self::decode_texture_set_bytes_header(next_chr, id, param)?,
+            TextureSetBytes(id, region, len, param) => Self::decode_texture_set_bytes(next_chr, id, region, len, param)?,
+            TextureDraw(id, param)          => Self::decode_texture_draw(next_chr, id, param)?,
 
             BlendMode(param)                => Self::decode_blend_mode(next_chr, param)?,
 
@@ -179,6 +212,8 @@ impl CanvasDecoder {
             's' => Ok((DecoderState::Sprite, None)),
             'T' => Ok((DecoderState::Transform, None)),
             'Z' => Ok((DecoderState::State, None)),
+            'G' => Ok((DecoderState::Gradient(String::new()), None)),
+            'B' => Ok((DecoderState::Texture(String::new()), None)),
 
             // Single character commands
             '.' => Ok((DecoderState::None, Some(Draw::ClosePath))),
@@ -242,6 +277,8 @@ impl CanvasDecoder {
         match next_chr {
             's'     => Ok((DecoderState::ColorStroke(String::new()), None)),
             'f'     => Ok((DecoderState::ColorFill(String::new()), None)),
+            'g'     => Ok((DecoderState::ColorFillGradientId(String::new()), None)),
+            'r'     => Ok((DecoderState::ColorFillRadialId(String::new()), None)),
 
             _       => Err(DecoderError::InvalidCharacter(next_chr))
         }
@@ -446,6 +483,195 @@ impl CanvasDecoder {
         }
     }
 
+    #[inline] fn decode_color_fill_gradient_id(next_chr: char, param: String) -> Result<(DecoderState, Option<Draw>), DecoderError> {
+        match Self::decode_gradient_id(next_chr, param)? {
+            PartialResult::FullMatch(gradient_id)   => Ok((DecoderState::ColorFillGradient(gradient_id, String::new()), None)),
+            PartialResult::MatchMore(param)         => Ok((DecoderState::ColorFillGradientId(param), None))
+        }
+    }
+
+    #[inline] fn decode_color_fill_gradient(next_chr: char, gradient_id: GradientId, mut param: String) -> Result<(DecoderState, Option<Draw>), DecoderError> {
+        if param.len() < 23 {
+            param.push(next_chr);
+            Ok((DecoderState::ColorFillGradient(gradient_id, param), None))
+        } else {
+            param.push(next_chr);
+
+            let mut param   = param.chars();
+            let x1          = Self::decode_f32(&mut param)?;
+            let y1          = Self::decode_f32(&mut param)?;
+            let x2          = Self::decode_f32(&mut param)?;
+            let y2          = Self::decode_f32(&mut param)?;
+
+            Ok((DecoderState::None, Some(Draw::FillGradient(gradient_id, (x1, y1), (x2, y2)))))
+        }
+    }
+
+    #[inline] fn decode_color_fill_radial_id(next_chr: char, param: String) -> Result<(DecoderState, Option<Draw>), DecoderError> {
+        match Self::decode_gradient_id(next_chr, param)? {
+            PartialResult::FullMatch(gradient_id)   => Ok((DecoderState::ColorFillRadial(gradient_id, String::new()), None)),
+            PartialResult::MatchMore(param)         => Ok((DecoderState::ColorFillRadialId(param), None))
+        }
+    }
+
+    #[inline] fn decode_color_fill_radial(next_chr: char, gradient_id: GradientId, mut param: String) -> Result<(DecoderState, Option<Draw>), DecoderError> {
+        if param.len() < 17 {
+            param.push(next_chr);
+            Ok((DecoderState::ColorFillRadial(gradient_id, param), None))
+        } else {
+            param.push(next_chr);
+
+            let mut param   = param.chars();
+            let x           = Self::decode_f32(&mut param)?;
+            let y           = Self::decode_f32(&mut param)?;
+            let radius      = Self::decode_f32(&mut param)?;
+
+            Ok((DecoderState::None, Some(Draw::FillRadialGradient(gradient_id, (x, y), radius))))
+        }
+    }
+
+    #[inline] fn decode_gradient(next_chr: char, param: String) -> Result<(DecoderState, Option<Draw>), DecoderError> {
+        match Self::decode_gradient_id(next_chr, param)? {
+            PartialResult::FullMatch(gradient_id)   => Ok((DecoderState::GradientOperation(gradient_id), None)),
+            PartialResult::MatchMore(param)         => Ok((DecoderState::Gradient(param), None))
+        }
+    }
+
+    #[inline] fn decode_gradient_op(next_chr: char, gradient_id: GradientId) -> Result<(DecoderState, Option<Draw>), DecoderError> {
+        // Matched 'G' (id) so far
+        match next_chr {
+            'n'     => Ok((DecoderState::GradientCreate(gradient_id, String::new()), None)),
+            's'     => Ok((DecoderState::GradientAddStop(gradient_id, String::new()), None)),
+
+            _       => Err(DecoderError::InvalidCharacter(next_chr))
+        }
+    }
+
+    #[inline] fn decode_gradient_create(next_chr: char, gradient_id: GradientId, mut param: String) -> Result<(DecoderState, Option<Draw>), DecoderError> {
+        if param.len() < 24 {
+            param.push(next_chr);
+            Ok((DecoderState::GradientCreate(gradient_id, param), None))
+        } else {
+            param.push(next_chr);
+
+            let mut param   = param.chars();
+            let color       = Self::decode_color_only(&mut param)?;
+
+            Ok((DecoderState::None, Some(Draw::Gradient(gradient_id, GradientOp::Create(color)))))
+        }
+    }
+
+    #[inline] fn decode_gradient_add_stop(next_chr: char, gradient_id: GradientId, mut param: String) -> Result<(DecoderState, Option<Draw>), DecoderError> {
+        if param.len() < 30 {
+            param.push(next_chr);
+            Ok((DecoderState::GradientAddStop(gradient_id, param), None))
+        } else {
+            param.push(next_chr);
+
+            let mut param   = param.chars();
+            let pos         = Self::decode_f32(&mut param)?;
+            let color       = Self::decode_color_only(&mut param)?;
+
+            Ok((DecoderState::None, Some(Draw::Gradient(gradient_id, GradientOp::AddStop(pos, color)))))
+        }
+    }
+
+    #[inline] fn decode_texture(next_chr: char, param: String) -> Result<(DecoderState, Option<Draw>), DecoderError> {
+        match Self::decode_texture_id(next_chr, param)? {
+            PartialResult::FullMatch(texture_id)    => Ok((DecoderState::TextureOperation(texture_id), None)),
+            PartialResult::MatchMore(param)         => Ok((DecoderState::Texture(param), None))
+        }
+    }
+
+    #[inline] fn decode_texture_op(next_chr: char, texture_id: TextureId) -> Result<(DecoderState, Option<Draw>), DecoderError> {
+        // Matched 'B' (id) so far
+        match next_chr {
+            'N'     => Ok((DecoderState::TextureCreate(texture_id, String::new()), None)),
+            'D'     => Ok((DecoderState::TextureSetBytesHeader(texture_id, String::new()), None)),
+            'X'     => Ok((DecoderState::None, Some(Draw::Texture(texture_id, TextureOp::Free)))),
+            'd'     => Ok((DecoderState::TextureDraw(texture_id, String::new()), None)),
+
+            _       => Err(DecoderError::InvalidCharacter(next_chr))
+        }
+    }
+
+    #[inline] fn decode_texture_create(next_chr: char, texture_id: TextureId, mut param: String) -> Result<(DecoderState, Option<Draw>), DecoderError> {
+        if param.len() < 12 {
+            param.push(next_chr);
+            Ok((DecoderState::TextureCreate(texture_id, param), None))
+        } else {
+            param.push(next_chr);
+
+            let mut param   = param.chars();
+            let width       = Self::decode_u32(&mut param)?;
+            let height      = Self::decode_u32(&mut param)?;
+            let format      = match param.next() {
+                Some('r')   => TextureFormat::Rgba,
+                Some(other) => Err(DecoderError::InvalidCharacter(other))?,
+                None        => Err(DecoderError::MissingCharacter)?
+            };
+
+            Ok((DecoderState::None, Some(Draw::Texture(texture_id, TextureOp::Create(width, height, format)))))
+        }
+    }
+
+    #[inline] fn decode_texture_set_bytes_header(next_chr: char, texture_id: TextureId, mut param: String) -> Result<(DecoderState, Option<Draw>), DecoderError> {
+        if param.len() < 29 {
+            param.push(next_chr);
+            Ok((DecoderState::TextureSetBytesHeader(texture_id, param), None))
+        } else {
+            param.push(next_chr);
+
+            let mut param   = param.chars();
+            let x           = Self::decode_u32(&mut param)?;
+            let y           = Self::decode_u32(&mut param)?;
+            let width       = Self::decode_u32(&mut param)?;
+            let height      = Self::decode_u32(&mut param)?;
+            let len         = Self::decode_u32(&mut param)? as usize;
+
+            if len == 0 {
+                // No bytes follow the header
+                Ok((DecoderState::None, Some(Draw::Texture(texture_id, TextureOp::SetBytes(x, y, width, height, Arc::new(vec![]))))))
+            } else {
+                Ok((DecoderState::TextureSetBytes(texture_id, (x, y, width, height), len, String::new()), None))
+            }
+        }
+    }
+
+    #[inline] fn decode_texture_set_bytes(next_chr: char, texture_id: TextureId, region: (u32, u32, u32, u32), len: usize, mut param: String) -> Result<(DecoderState, Option<Draw>), DecoderError> {
+        // Bytes are encoded in groups of 3, with 4 characters per group
+        let encoded_len = ((len+2)/3)*4;
+
+        param.push(next_chr);
+
+        if param.len() < encoded_len {
+            Ok((DecoderState::TextureSetBytes(texture_id, region, len, param), None))
+        } else {
+            let (x, y, width, height)   = region;
+            let bytes                   = Self::decode_bytes(&mut param.chars(), len)?;
+
+            Ok((DecoderState::None, Some(Draw::Texture(texture_id, TextureOp::SetBytes(x, y, width, height, Arc::new(bytes))))))
+        }
+    }
+
+    #[inline] fn decode_texture_draw(next_chr: char, texture_id: TextureId, mut param: String) -> Result<(DecoderState, Option<Draw>), DecoderError> {
+        if param.len() < 29 {
+            param.push(next_chr);
+            Ok((DecoderState::TextureDraw(texture_id, param), None))
+        } else {
+            param.push(next_chr);
+
+            let mut param   = param.chars();
+            let x1          = Self::decode_f32(&mut param)?;
+            let y1          = Self::decode_f32(&mut param)?;
+            let x2          = Self::decode_f32(&mut param)?;
+            let y2          = Self::decode_f32(&mut param)?;
+            let alpha       = Self::decode_f32(&mut param)?;
+
+            Ok((DecoderState::None, Some(Draw::DrawTexture(texture_id, (x1, y1), (x2, y2), alpha))))
+        }
+    }
+
     #[inline] fn decode_blend_mode(next_chr: char, mut param: String) -> Result<(DecoderState, Option<Draw>), DecoderError> {
         if param.len() < 1 {
             param.push(next_chr);
@@ -648,9 +874,26 @@ impl CanvasDecoder {
     }
 
     ///
-    /// Consumes characters until we have a sprite ID
+    /// Consumes 25 characters to decode a colour
     ///
-    fn decode_sprite_id(next_chr: char, mut param: String) -> Result<PartialResult<SpriteId>, DecoderError> {
+    fn decode_color_only(param: &mut Chars) -> Result<Color, DecoderError> {
+        let col_type    = param.next();
+        let r           = Self::decode_f32(param)?;
+        let g           = Self::decode_f32(param)?;
+        let b           = Self::decode_f32(param)?;
+        let a           = Self::decode_f32(param)?;
+
+        if col_type != Some('R') {
+            Err(DecoderError::UnknownColorType)?;
+        }
+
+        Ok(Color::Rgba(r, g, b, a))
+    }
+
+    ///
+    /// Consumes characters until we have a u64 in 'truncated' format
+    ///
+    fn decode_truncated_u64(next_chr: char, mut param: String) -> Result<PartialResult<u64>, DecoderError> {
         // Add the next character
         param.push(next_chr);
 
@@ -663,12 +906,66 @@ impl CanvasDecoder {
                 result |= (Self::decode_base64(chr)? & !0x20) as u64;
             }
 
-            Ok(PartialResult::FullMatch(SpriteId(result)))
+            Ok(PartialResult::FullMatch(result))
         } else {
             Ok(PartialResult::MatchMore(param))
         }
     }
 
+    ///
+    /// Consumes characters until we have a sprite ID
+    ///
+    fn decode_sprite_id(next_chr: char, param: String) -> Result<PartialResult<SpriteId>, DecoderError> {
+        match Self::decode_truncated_u64(next_chr, param)? {
+            PartialResult::FullMatch(sprite_id) => Ok(PartialResult::FullMatch(SpriteId(sprite_id))),
+            PartialResult::MatchMore(param)     => Ok(PartialResult::MatchMore(param))
+        }
+    }
+
+    ///
+    /// Consumes characters until we have a gradient ID
+    ///
+    fn decode_gradient_id(next_chr: char, param: String) -> Result<PartialResult<GradientId>, DecoderError> {
+        match Self::decode_truncated_u64(next_chr, param)? {
+            PartialResult::FullMatch(gradient_id)   => Ok(PartialResult::FullMatch(GradientId(gradient_id))),
+            PartialResult::MatchMore(param)         => Ok(PartialResult::MatchMore(param))
+        }
+    }
+
+    ///
+    /// Consumes characters until we have a texture ID
+    ///
+    fn decode_texture_id(next_chr: char, param: String) -> Result<PartialResult<TextureId>, DecoderError> {
+        match Self::decode_truncated_u64(next_chr, param)? {
+            PartialResult::FullMatch(texture_id)    => Ok(PartialResult::FullMatch(TextureId(texture_id))),
+            PartialResult::MatchMore(param)         => Ok(PartialResult::MatchMore(param))
+        }
+    }
+
+    ///
+    /// Decodes a set of bytes encoded in groups of 3 (4 characters per group)
+    ///
+    fn decode_bytes(chrs: &mut Chars, len: usize) -> Result<Vec<u8>, DecoderError> {
+        let mut result = Vec::with_capacity(len);
+
+        while result.len() < len {
+            let mut value = 0u32;
+
+            for idx in 0..4 {
+                let next_chr    = chrs.next().ok_or(DecoderError::MissingCharacter)?;
+                value           |= (Self::decode_base64(next_chr)? as u32) << (idx*6);
+            }
+
+            for idx in 0..3 {
+                if result.len() < len {
+                    result.push(((value >> (idx*8)) & 0xff) as u8);
+                }
+            }
+        }
+
+        Ok(result)
+    }
+
     ///
     /// Consumes 6 characters to decode a f32
     ///
@@ -907,6 +1204,49 @@ mod test {
         check_round_trip_single(Draw::FillColor(Color::Rgba(0.2, 0.3, 0.4, 0.5)));
     }
 
+    #[test]
+    fn decode_create_gradient() {
+        check_round_trip_single(Draw::Gradient(GradientId(0), GradientOp::Create(Color::Rgba(0.1, 0.2, 0.3, 0.4))));
+        check_round_trip_single(Draw::Gradient(GradientId(1300), GradientOp::Create(Color::Rgba(0.1, 0.2, 0.3, 0.4))));
+    }
+
+    #[test]
+    fn decode_gradient_stop() {
+        check_round_trip_single(Draw::Gradient(GradientId(42), GradientOp::AddStop(0.5, Color::Rgba(0.4, 0.3, 0.2, 0.1))));
+    }
+
+    #[test]
+    fn decode_fill_gradient() {
+        check_round_trip_single(Draw::FillGradient(GradientId(1000000000), (1.0, 2.0), (3.0, 4.0)));
+    }
+
+    #[test]
+    fn decode_fill_radial_gradient() {
+        check_round_trip_single(Draw::FillRadialGradient(GradientId(10), (5.0, 6.0), 7.0));
+    }
+
+    #[test]
+    fn decode_create_texture() {
+        check_round_trip_single(Draw::Texture(TextureId(1), TextureOp::Create(300, 200, TextureFormat::Rgba)));
+    }
+
+    #[test]
+    fn decode_texture_bytes() {
+        check_round_trip_single(Draw::Texture(TextureId(1), TextureOp::SetBytes(2, 3, 1, 1, Arc::new(vec![255, 0, 128, 255]))));
+        check_round_trip_single(Draw::Texture(TextureId(1), TextureOp::SetBytes(0, 0, 0, 0, Arc::new(vec![]))));
+        check_round_trip_single(Draw::Texture(TextureId(1), TextureOp::SetBytes(0, 0, 3, 1, Arc::new((0..12).collect()))));
+    }
+
+    #[test]
+    fn decode_free_texture() {
+        check_round_trip_single(Draw::Texture(TextureId(1000), TextureOp::Free));
+    }
+
+    #[test]
+    fn decode_draw_texture() {
+        check_round_trip_single(Draw::DrawTexture(TextureId(1), (10.0, 20.0), (30.0, 40.0), 0.5));
+    }
+
     #[test]
     fn decode_blend_mode() {
         check_round_trip_single(Draw::BlendMode(BlendMode::Lighten));
@@ -1065,6 +1405,14 @@ mod test {
             Draw::DashOffset(13.0),
             Draw::StrokeColor(Color::Rgba(0.1, 0.2, 0.3, 0.4)),
             Draw::FillColor(Color::Rgba(0.2, 0.3, 0.4, 0.5)),
+            Draw::Gradient(GradientId(3), GradientOp::Create(Color::Rgba(0.1, 0.2, 0.3, 0.4))),
+            Draw::Gradient(GradientId(3), GradientOp::AddStop(0.5, Color::Rgba(0.4, 0.3, 0.2, 0.1))),
+            Draw::FillGradient(GradientId(3), (1.0, 2.0), (3.0, 4.0)),
+            Draw::FillRadialGradient(GradientId(3), (5.0, 6.0), 7.0),
+            Draw::Texture(TextureId(4), TextureOp::Create(2, 2, TextureFormat::Rgba)),
+            Draw::Texture(TextureId(4), TextureOp::SetBytes(0, 0, 1, 1, Arc::new(vec![1, 2, 3, 4]))),
+            Draw::DrawTexture(TextureId(4), (1.0, 2.0), (3.0, 4.0), 1.0),
+            Draw::Texture(TextureId(4), TextureOp::Free),
             Draw::BlendMode(BlendMode::Lighten),
             Draw::IdentityTransform,
             Draw::CanvasHeight(81.0),
@@ -1107,6 +1455,14 @@ mod test {
             Draw::DashOffset(13.0),
             Draw::StrokeColor(Color::Rgba(0.1, 0.2, 0.3, 0.4)),
             Draw::FillColor(Color::Rgba(0.2, 0.3, 0.4, 0.5)),
+            Draw::Gradient(GradientId(3), GradientOp::Create(Color::Rgba(0.1, 0.2, 0.3, 0.4))),
+            Draw::Gradient(GradientId(3), GradientOp::AddStop(0.5, Color::Rgba(0.4, 0.3, 0.2, 0.1))),
+            Draw::FillGradient(GradientId(3), (1.0, 2.0), (3.0, 4.0)),
+            Draw::FillRadialGradient(GradientId(3), (5.0, 6.0), 7.0),
+            Draw::Texture(TextureId(4), TextureOp::Create(2, 2, TextureFormat::Rgba)),
+            Draw::Texture(TextureId(4), TextureOp::SetBytes(0, 0, 1, 1, Arc::new(vec![1, 2, 3, 4]))),
+            Draw::DrawTexture(TextureId(4), (1.0, 2.0), (3.0, 4.0), 1.0),
+            Draw::Texture(TextureId(4), TextureOp::Free),
             Draw::BlendMode(BlendMode::Lighten),
             Draw::IdentityTransform,
             Draw::CanvasHeight(81.0),
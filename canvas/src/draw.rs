@@ -5,6 +5,8 @@
 use super::transform2d::*;
 use super::color::*;
 
+use std::sync::*;
+
 ///
 /// Possible way to join lines
 ///
@@ -59,6 +61,61 @@ pub enum BlendMode {
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct SpriteId(pub u64);
 
+///
+/// Identifier of a canvas gradient
+///
+/// Gradients are defined as a series of colour stops and can then be used to set the fill of future paths.
+/// The stops are read when the gradient is selected as a fill, so a gradient can be redefined without
+/// affecting any fills that used its previous definition.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct GradientId(pub u64);
+
+///
+/// Operations that can be performed on a gradient
+///
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum GradientOp {
+    /// Clears the gradient and sets the colour at its start (position 0.0)
+    Create(Color),
+
+    /// Adds a new colour stop at the specified position (0.0 is the start of the gradient, 1.0 is the end)
+    AddStop(f32, Color)
+}
+
+///
+/// Identifier of a canvas texture
+///
+/// Textures are bitmap images that can be drawn on the canvas. They are shared between all the layers and sprites
+/// of a canvas.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct TextureId(pub u64);
+
+///
+/// Format of the pixel data in a texture
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum TextureFormat {
+    /// 4 bytes per pixel, in the order red, green, blue, alpha (alpha is not premultiplied)
+    Rgba
+}
+
+///
+/// Operations that can be performed on a texture
+///
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum TextureOp {
+    /// Creates (or replaces) a texture with the specified width and height in pixels
+    Create(u32, u32, TextureFormat),
+
+    /// Sets the pixels for the region x, y, width, height of a texture (rows are ordered from the top of the image)
+    SetBytes(u32, u32, u32, u32, Arc<Vec<u8>>),
+
+    /// Releases the resources used by a texture
+    Free
+}
+
 ///
 /// Transformation to apply to a canvas 'sprite'
 ///
@@ -83,7 +140,7 @@ pub enum SpriteTransform {
 ///
 /// Instructions for drawing to a canvas
 ///
-#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
 pub enum Draw {
     /// Begins a new path
     NewPath,
@@ -133,6 +190,22 @@ pub enum Draw {
     /// Set the line color
     StrokeColor(Color),
 
+    /// Updates the definition of a gradient
+    Gradient(GradientId, GradientOp),
+
+    /// Fills future paths with a linear gradient running from the start point to the end point
+    FillGradient(GradientId, (f32, f32), (f32, f32)),
+
+    /// Fills future paths with a radial gradient with the specified center point and radius
+    FillRadialGradient(GradientId, (f32, f32), f32),
+
+    /// Performs an operation on a texture
+    Texture(TextureId, TextureOp),
+
+    /// Draws a texture in the rectangle between two points using the current transform. The first row of the texture
+    /// is drawn at the first y coordinate. The final parameter is the alpha value to use when drawing the texture.
+    DrawTexture(TextureId, (f32, f32), (f32, f32), f32),
+
     /// Set how future renderings are blended with one another
     BlendMode(BlendMode),
 
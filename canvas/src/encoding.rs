@@ -170,25 +170,105 @@ impl CanvasEncoding<String> for Transform2D {
     }
 }
 
+///
+/// Encodes a u64 in 'truncated' format: 5 bits per character, with the 6th bit set if there are more characters to follow
+///
+fn encode_truncated_u64(value: u64, append_to: &mut String) {
+    let mut value = value;
+
+    for _ in 0..13 {
+        let five_bits = (value & 0x1f) as usize;
+        let remaining = value >> 5;
+
+        if remaining != 0 {
+            let next_char = ENCODING_CHAR_SET[five_bits | 0x20];
+            append_to.push(next_char);
+        } else {
+            let next_char = ENCODING_CHAR_SET[five_bits];
+            append_to.push(next_char);
+            break;
+        }
+
+        value = remaining;
+    }
+}
+
 impl CanvasEncoding<String> for SpriteId {
     #[inline]
     fn encode_canvas(&self, append_to: &mut String) {
-        let SpriteId(mut sprite_id) = self;
-
-        for _ in 0..13 {
-            let five_bits = (sprite_id & 0x1f) as usize;
-            let remaining = sprite_id >> 5;
-
-            if remaining != 0 {
-                let next_char = ENCODING_CHAR_SET[five_bits | 0x20];
-                append_to.push(next_char);
-            } else {
-                let next_char = ENCODING_CHAR_SET[five_bits];
-                append_to.push(next_char);
-                break;
-            }
+        let SpriteId(sprite_id) = self;
+        encode_truncated_u64(*sprite_id, append_to);
+    }
+}
+
+impl CanvasEncoding<String> for GradientId {
+    #[inline]
+    fn encode_canvas(&self, append_to: &mut String) {
+        let GradientId(gradient_id) = self;
+        encode_truncated_u64(*gradient_id, append_to);
+    }
+}
+
+impl CanvasEncoding<String> for GradientOp {
+    fn encode_canvas(&self, append_to: &mut String) {
+        use self::GradientOp::*;
+
+        match self {
+            Create(color)           => ('n', *color).encode_canvas(append_to),
+            AddStop(pos, color)     => ('s', *pos, *color).encode_canvas(append_to)
+        }
+    }
+}
+
+impl CanvasEncoding<String> for TextureId {
+    #[inline]
+    fn encode_canvas(&self, append_to: &mut String) {
+        let TextureId(texture_id) = self;
+        encode_truncated_u64(*texture_id, append_to);
+    }
+}
+
+///
+/// Encodes a set of bytes: each group of 3 bytes is encoded as 4 characters (the final group is padded with 0s if needed)
+///
+fn encode_bytes(bytes: &[u8], append_to: &mut String) {
+    for group in bytes.chunks(3) {
+        let mut value = 0u32;
+
+        for (idx, byte) in group.iter().enumerate() {
+            value |= (*byte as u32) << (idx*8);
+        }
 
-            sprite_id = remaining;
+        for _ in 0..4 {
+            append_to.push(ENCODING_CHAR_SET[(value & 0x3f) as usize]);
+            value >>= 6;
+        }
+    }
+}
+
+impl CanvasEncoding<String> for TextureFormat {
+    fn encode_canvas(&self, append_to: &mut String) {
+        use self::TextureFormat::*;
+
+        match self {
+            Rgba => 'r'
+        }.encode_canvas(append_to)
+    }
+}
+
+impl CanvasEncoding<String> for TextureOp {
+    fn encode_canvas(&self, append_to: &mut String) {
+        use self::TextureOp::*;
+
+        match self {
+            Create(width, height, format)           => ('N', *width, *height, *format).encode_canvas(append_to),
+            Free                                    => 'X'.encode_canvas(append_to),
+
+            SetBytes(x, y, width, height, bytes)    => {
+                ('D', *x, *y, *width, *height).encode_canvas(append_to);
+                (bytes.len() as u32).encode_canvas(append_to);
+                encode_bytes(&*bytes, append_to);
+            }
         }
     }
 }
@@ -228,6 +308,11 @@ impl CanvasEncoding<String> for Draw {
             &DashOffset(offset)                     => ('D', 'o', offset).encode_canvas(append_to),
             &StrokeColor(col)                       => ('C', 's', col).encode_canvas(append_to),
             &FillColor(col)                         => ('C', 'f', col).encode_canvas(append_to),
+            &Gradient(gradient_id, op)              => ('G', gradient_id, op).encode_canvas(append_to),
+            &FillGradient(gradient_id, start, end)  => ('C', 'g', gradient_id, start, end).encode_canvas(append_to),
+            &FillRadialGradient(gradient_id, center, radius) => ('C', 'r', gradient_id, center, radius).encode_canvas(append_to),
+            &Texture(texture_id, ref op)            => { ('B', texture_id).encode_canvas(append_to); op.encode_canvas(append_to); },
+            &DrawTexture(texture_id, min, max, alpha) => ('B', texture_id, 'd', (min, max), alpha).encode_canvas(append_to),
             &BlendMode(mode)                        => ('M', mode).encode_canvas(append_to),
             &IdentityTransform                      => ('T', 'i').encode_canvas(append_to),
             &CanvasHeight(height)                   => ('T', 'h', height).encode_canvas(append_to),
@@ -262,6 +347,8 @@ impl CanvasEncoding<String> for Vec<Draw> {
 mod test {
     use super::*;
 
+    use std::sync::*;
+
     #[test]
     fn can_encode_u32() {
         let test_number: u32 = 0xabcd1234;
@@ -321,6 +408,16 @@ mod test {
     #[test]
     fn can_encode_fillcolor() { assert!(&encode_draw(Draw::FillColor(Color::Rgba(1.0, 1.0, 1.0, 1.0))) == "CfRAAAg/AAAAg/AAAAg/AAAAg/A") }
     #[test]
+    fn can_encode_create_gradient() { assert!(&encode_draw(Draw::Gradient(GradientId(1), GradientOp::Create(Color::Rgba(1.0, 1.0, 1.0, 1.0)))) == "GBnRAAAg/AAAAg/AAAAg/AAAAg/A") }
+    #[test]
+    fn can_encode_fill_gradient() { assert!(&encode_draw(Draw::FillGradient(GradientId(1), (20.0, 20.0), (20.0, 20.0))) == "CgBAAAoBBAAAoBBAAAoBBAAAoBB") }
+    #[test]
+    fn can_encode_create_texture() { assert!(&encode_draw(Draw::Texture(TextureId(1), TextureOp::Create(2, 2, TextureFormat::Rgba))) == "BBNCAAAAACAAAAAr") }
+    #[test]
+    fn can_encode_texture_bytes() { assert!(&encode_draw(Draw::Texture(TextureId(1), TextureOp::SetBytes(0, 0, 1, 1, Arc::new(vec![255, 0, 0, 255])))) == "BBDAAAAAAAAAAAABAAAAABAAAAAEAAAAA/DAA/DAA") }
+    #[test]
+    fn can_encode_draw_texture() { assert!(&encode_draw(Draw::DrawTexture(TextureId(1), (10.0, 20.0), (30.0, 40.0), 0.5)) == "BBdAAAIBBAAAoBBAAA8BBAAAICBAAAA/A") }
+    #[test]
     fn can_encode_blendmode() { assert!(&encode_draw(Draw::BlendMode(BlendMode::SourceOver)) == "MSV") }
     #[test]
     fn can_encode_identity_transform() { assert!(&encode_draw(Draw::IdentityTransform) == "Ti") }
@@ -28,6 +28,11 @@ pub trait GraphicsContext {
     fn dash_offset(&mut self, offset: f32);
     fn fill_color(&mut self, col: Color);
     fn stroke_color(&mut self, col: Color);
+    fn gradient(&mut self, gradient_id: GradientId, op: GradientOp);
+    fn fill_gradient(&mut self, gradient_id: GradientId, x1: f32, y1: f32, x2: f32, y2: f32);
+    fn fill_radial_gradient(&mut self, gradient_id: GradientId, center_x: f32, center_y: f32, radius: f32);
+    fn texture(&mut self, texture_id: TextureId, op: TextureOp);
+    fn draw_texture(&mut self, texture_id: TextureId, x1: f32, y1: f32, x2: f32, y2: f32, alpha: f32);
     fn blend_mode(&mut self, mode: BlendMode);
     fn identity_transform(&mut self);
     fn canvas_height(&mut self, height: f32);
@@ -69,6 +74,11 @@ pub trait GraphicsContext {
             DashOffset(dash_offset)                     => self.dash_offset(dash_offset),
             FillColor(col)                              => self.fill_color(col),
             StrokeColor(col)                            => self.stroke_color(col),
+            Gradient(gradient_id, op)                   => self.gradient(gradient_id, op),
+            FillGradient(id, (x1, y1), (x2, y2))        => self.fill_gradient(id, x1, y1, x2, y2),
+            FillRadialGradient(id, (x, y), radius)      => self.fill_radial_gradient(id, x, y, radius),
+            Texture(texture_id, op)                     => self.texture(texture_id, op),
+            DrawTexture(id, (x1, y1), (x2, y2), alpha)  => self.draw_texture(id, x1, y1, x2, y2, alpha),
             BlendMode(blendmode)                        => self.blend_mode(blendmode),
             IdentityTransform                           => self.identity_transform(),
             CanvasHeight(height)                        => self.canvas_height(height),
@@ -201,6 +211,11 @@ impl GraphicsContext for Vec<Draw> {
     #[inline] fn dash_offset(&mut self, offset: f32)                                    { self.push(Draw::DashOffset(offset)); }
     #[inline] fn fill_color(&mut self, col: Color)                                      { self.push(Draw::FillColor(col)); }
     #[inline] fn stroke_color(&mut self, col: Color)                                    { self.push(Draw::StrokeColor(col)); }
+    #[inline] fn gradient(&mut self, gradient_id: GradientId, op: GradientOp)           { self.push(Draw::Gradient(gradient_id, op)); }
+    #[inline] fn fill_gradient(&mut self, gradient_id: GradientId, x1: f32, y1: f32, x2: f32, y2: f32) { self.push(Draw::FillGradient(gradient_id, (x1, y1), (x2, y2))); }
+    #[inline] fn fill_radial_gradient(&mut self, gradient_id: GradientId, center_x: f32, center_y: f32, radius: f32) { self.push(Draw::FillRadialGradient(gradient_id, (center_x, center_y), radius)); }
+    #[inline] fn texture(&mut self, texture_id: TextureId, op: TextureOp)               { self.push(Draw::Texture(texture_id, op)); }
+    #[inline] fn draw_texture(&mut self, texture_id: TextureId, x1: f32, y1: f32, x2: f32, y2: f32, alpha: f32) { self.push(Draw::DrawTexture(texture_id, (x1, y1), (x2, y2), alpha)); }
     #[inline] fn blend_mode(&mut self, mode: BlendMode)                                 { self.push(Draw::BlendMode(mode)); }
     #[inline] fn identity_transform(&mut self)                                          { self.push(Draw::IdentityTransform); }
     #[inline] fn canvas_height(&mut self, height: f32)                                  { self.push(Draw::CanvasHeight(height)); }
@@ -2,6 +2,7 @@ use super::state::*;
 use super::storage_descriptor::*;
 
 use flo_animation::*;
+use flo_animation::storage::*;
 
 ///
 /// Command that can be issued to a FlowBetween instance
@@ -63,5 +64,41 @@ pub enum FloCommand {
     ListElements,
 
     /// Writes out debugging SVG files for raycasting a particular element
-    RayCastToSvg(ElementId)
+    RayCastToSvg(ElementId),
+
+    /// Checks the input animation for any inconsistencies between the edit log and the rest of the file
+    CheckIntegrity,
+
+    /// Rebuilds the input animation from its edit log
+    RepairAnimation,
+
+    /// Replays the edit log of the input animation into the output animation, stopping before the specified edit if there is one
+    ReplayEdits(Option<usize>),
+
+    /// Lists the structural differences between the input and the output animations
+    CompareAnimations,
+
+    /// Replays the edit log of the input animation into a new animation and lists any differences from the original
+    VerifyEditLog,
+
+    /// Compacts the edits in the edit buffer, preserving the specified checkpoints
+    CompactEdits(Vec<EditLogCheckpoint>),
+
+    /// Compacts the edit log of the input animation in place, preserving the specified checkpoints
+    CompactAnimation(Vec<EditLogCheckpoint>),
+
+    /// Lists the named snapshots of the input animation
+    ListSnapshots,
+
+    /// Creates a named snapshot of the input animation as it is now
+    CreateSnapshot(String),
+
+    /// Creates a new animation in the catalog from the named snapshot of the input animation, and makes it the output animation
+    ForkSnapshot(String),
+
+    /// Writes the input animation to an archive file at the specified path, compacting the edit log if the flag is set
+    ExportArchive(String, bool),
+
+    /// Creates a new animation in the catalog from the archive file at the specified path, and makes it the output animation
+    ImportArchive(String)
 }
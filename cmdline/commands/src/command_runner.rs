@@ -70,6 +70,18 @@ fn run_command<'a>(command: FloCommand, output: &'a mut Publisher<FloCommandOutp
             FloCommand::SelectFrame(layer, when)        => { select_frame(output, state, layer, when).await; }
             FloCommand::ListElements                    => { list_elements(output, state).await; }
             FloCommand::RayCastToSvg(element_id)        => { raycast_to_svg(output, state, element_id).await?; }
+            FloCommand::CheckIntegrity                  => { check_integrity(output, state).await?; }
+            FloCommand::RepairAnimation                 => { repair_animation(output, state).await?; }
+            FloCommand::ReplayEdits(until)              => { replay_edits(output, state, until).await?; }
+            FloCommand::CompareAnimations               => { compare_animations(output, state).await?; }
+            FloCommand::VerifyEditLog                   => { verify_edit_log(output, state).await?; }
+            FloCommand::CompactEdits(ref keep)          => { compact_edits(output, state, keep.clone()).await?; }
+            FloCommand::CompactAnimation(ref keep)      => { compact_animation(output, state, keep.clone()).await?; }
+            FloCommand::ListSnapshots                   => { list_snapshots(output, state).await; }
+            FloCommand::CreateSnapshot(ref name)        => { create_snapshot(output, state, name.clone()).await; }
+            FloCommand::ForkSnapshot(ref name)          => { fork_snapshot(output, state, name.clone()).await?; }
+            FloCommand::ExportArchive(ref to, compact)  => { export_archive(output, state, to.clone(), compact).await?; }
+            FloCommand::ImportArchive(ref path)         => { import_archive(output, state, path.clone()).await?; }
         }
 
         // Finish the command
@@ -21,7 +21,22 @@ pub enum CommandError {
     NoFrameSelected,
 
     /// The element ID was not found
-    ElementNotFound(ElementId)
+    ElementNotFound(ElementId),
+
+    /// The integrity of an animation could not be checked
+    CouldNotCheckIntegrity(String),
+
+    /// An animation could not be rebuilt from its edit log
+    CouldNotRepairAnimation(String),
+
+    /// The input animation has no snapshot with the specified name
+    SnapshotNotFound(String),
+
+    /// An archive could not be written
+    CouldNotWriteArchive(String),
+
+    /// An archive could not be read
+    CouldNotReadArchive(String)
 }
 
 impl Display for CommandError {
@@ -33,7 +48,12 @@ impl Display for CommandError {
             CouldNotCreateAnimation(name)   => write!(fmt, "Coult not create animation '{}'", name),
             CannotParseEdit(line, edit)     => write!(fmt, "{}: cannot parse edit '{}'", line, edit),
             NoFrameSelected                 => write!(fmt, "A frame must be selected for this operation"),
-            ElementNotFound(id)             => write!(fmt, "Element {} was not found", id.id().map(|id| id.to_string()).unwrap_or("<unassigned>".to_string()))
+            ElementNotFound(id)             => write!(fmt, "Element {} was not found", id.id().map(|id| id.to_string()).unwrap_or("<unassigned>".to_string())),
+            CouldNotCheckIntegrity(msg)     => write!(fmt, "Could not check integrity: {}", msg),
+            CouldNotRepairAnimation(msg)    => write!(fmt, "Could not repair animation: {}", msg),
+            SnapshotNotFound(name)          => write!(fmt, "Snapshot '{}' was not found", name),
+            CouldNotWriteArchive(msg)       => write!(fmt, "Could not write archive: {}", msg),
+            CouldNotReadArchive(msg)        => write!(fmt, "Could not read archive: {}", msg)
         }
     }
 }
@@ -89,6 +89,20 @@ impl CommandState {
         Arc::clone(&self.0.input_animation.1)
     }
 
+    ///
+    /// Retrieves the current input animation for this state as an editable animation
+    ///
+    pub fn editable_input_animation(&self) -> Arc<dyn EditableAnimation> {
+        Arc::clone(&self.0.input_animation.2)
+    }
+
+    ///
+    /// Retrieves where the current input animation is stored
+    ///
+    pub fn input_descriptor(&self) -> StorageDescriptor {
+        self.0.input_animation.0.clone()
+    }
+
     ///
     /// Retrieves the current output animation for this state
     ///
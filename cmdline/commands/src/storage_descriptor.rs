@@ -46,7 +46,16 @@ impl StorageDescriptor {
     /// Opens the animation that this storage descriptor references, using the specified file manager
     ///
     pub fn open_animation(&self, file_manager: &Arc<dyn FileManager>) -> Option<Arc<impl EditableAnimation>> {
-        let storage = match self {
+        let storage     = self.open_storage(file_manager);
+        let animation   = storage.map(|storage| Arc::new(create_animation_editor(move |commands| storage.get_responses(commands).boxed())));
+        animation
+    }
+
+    ///
+    /// Opens the storage for the animation that this storage descriptor references
+    ///
+    pub fn open_storage(&self, file_manager: &Arc<dyn FileManager>) -> Option<SqliteAnimationStorage> {
+        match self {
             StorageDescriptor::InMemory                 => SqliteAnimationStorage::new_in_memory().ok(),
             StorageDescriptor::File(filename)           => SqliteAnimationStorage::open_file(&PathBuf::from(filename)).ok(),
 
@@ -71,10 +80,7 @@ impl StorageDescriptor {
 
                 result
             }
-        };
-
-        let animation   = storage.map(|storage| Arc::new(create_animation_editor(move |commands| storage.get_responses(commands).boxed())));
-        animation
+        }
     }
 
     ///
@@ -0,0 +1,78 @@
+use crate::state::*;
+use crate::error::*;
+use crate::output::*;
+use crate::storage_descriptor::*;
+
+use flo_stream::*;
+use flo_animation::storage::*;
+use flo_sqlite_storage::*;
+
+use futures::prelude::*;
+
+use std::fs;
+use std::sync::*;
+use std::path::{Path};
+
+///
+/// The export_archive command: writes the input animation to a single-file archive
+///
+pub fn export_archive<'a>(output: &'a mut Publisher<FloCommandOutput>, state: &'a mut CommandState, path: String, compact: bool) -> impl Future<Output=Result<(), CommandError>>+Send+'a {
+    async move {
+        let descriptor  = state.input_descriptor();
+        let animation   = state.input_animation();
+
+        // Create the archive
+        output.publish(FloCommandOutput::StartTask("Archive animation".to_string())).await;
+        let archive     = AnimationArchive::from_animation(&*animation, Some(format!("{}", descriptor)), compact).await;
+        output.publish(FloCommandOutput::FinishTask).await;
+
+        // Write it out
+        let mut archive_data = String::new();
+        archive.serialize(&mut archive_data);
+        fs::write(&path, archive_data).map_err(|err| CommandError::CouldNotWriteArchive(format!("{}: {}", path, err)))?;
+
+        output.publish(FloCommandOutput::Message(format!("Wrote '{}' to '{}' ({} edits, {} elements)", descriptor, path, archive.edits.len(), archive.elements.len()))).await;
+
+        Ok(())
+    }
+}
+
+///
+/// The import_archive command: creates a new animation in the catalog from an archive, and makes it the output animation
+///
+pub fn import_archive<'a>(output: &'a mut Publisher<FloCommandOutput>, state: &'a mut CommandState, path: String) -> impl Future<Output=Result<(), CommandError>>+Send+'a {
+    async move {
+        use FloCommandOutput::*;
+
+        // Read the archive
+        let archive_data    = fs::read_to_string(&path).map_err(|err| CommandError::CouldNotReadArchive(format!("{}: {}", path, err)))?;
+        let archive         = AnimationArchive::deserialize(&archive_data).map_err(|err| CommandError::CouldNotReadArchive(format!("{}: {}", path, err)))?;
+
+        // Create a new animation in the catalog with an unused name
+        let catalog         = state.file_manager();
+        let name            = archive.name.clone()
+            .or_else(|| Path::new(&path).file_stem().map(|stem| stem.to_string_lossy().to_string()))
+            .unwrap_or_else(|| "Imported animation".to_string());
+        let name            = catalog.unused_display_name(&name);
+        let new_path        = catalog.create_new_path();
+        let storage         = SqliteAnimationStorage::new_with_file(new_path.as_path()).map_err(|_| CommandError::CouldNotCreateAnimation(name.clone()))?;
+        let animation       = create_animation_editor(move |commands| storage.get_responses(commands).boxed());
+        catalog.set_display_name_for_path(new_path.as_path(), name.clone());
+
+        // Replay the archive into it
+        output.publish(StartTask("Import archive".to_string())).await;
+        archive.restore(&animation);
+        output.publish(FinishTask).await;
+
+        // Check that the imported animation matches the elements that were archived
+        let mismatched      = archive.find_mismatched_elements(&animation);
+        for element in mismatched.iter() {
+            output.publish(Error(format!("Element {:?} in the keyframe at {}ms on layer {} does not match the archive", element.element_id, element.keyframe.as_millis(), element.layer_id))).await;
+        }
+
+        *state = state.set_output_animation(StorageDescriptor::CatalogName(name.clone()), Arc::new(animation));
+        output.publish(Message(format!("Imported '{}' as '{}' ({} edits)", path, name, archive.edits.len()))).await;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,54 @@
+use crate::state::*;
+use crate::error::*;
+use crate::output::*;
+
+use flo_stream::*;
+use flo_animation::storage::*;
+
+use futures::prelude::*;
+
+///
+/// Publishes the result of compacting an edit log
+///
+async fn publish_compacted(output: &mut Publisher<FloCommandOutput>, original_length: usize, compacted: &CompactedEditLog) {
+    use FloCommandOutput::*;
+
+    output.publish(Message(format!("Compacted {} edits to {}", original_length, compacted.edits.len()))).await;
+
+    for checkpoint in compacted.checkpoints.iter() {
+        output.publish(Message(format!("  Checkpoint '{}' is now at edit {}", checkpoint.name, checkpoint.edit_index))).await;
+    }
+}
+
+///
+/// The compact_edits command compacts the edits in the edit buffer
+///
+pub fn compact_edits<'a>(output: &'a mut Publisher<FloCommandOutput>, state: &'a mut CommandState, checkpoints: Vec<EditLogCheckpoint>) -> impl Future<Output=Result<(), CommandError>>+Send+'a {
+    async move {
+        let original_length = state.edit_buffer().len();
+        let compacted       = compact_edit_log(state.edit_buffer(), &checkpoints);
+
+        publish_compacted(output, original_length, &compacted).await;
+        *state = state.set_edit_buffer(compacted.edits);
+
+        Ok(())
+    }
+}
+
+///
+/// The compact_animation command compacts the edit log of the input animation in place
+///
+pub fn compact_animation<'a>(output: &'a mut Publisher<FloCommandOutput>, state: &'a mut CommandState, checkpoints: Vec<EditLogCheckpoint>) -> impl Future<Output=Result<(), CommandError>>+Send+'a {
+    async move {
+        let animation       = state.editable_input_animation();
+        let original_length = animation.get_num_edits();
+
+        output.publish(FloCommandOutput::StartTask("Compact edit log".to_string())).await;
+        let compacted       = compact_animation_edit_log(&*animation, checkpoints).await;
+        output.publish(FloCommandOutput::FinishTask).await;
+
+        publish_compacted(output, original_length, &compacted).await;
+
+        Ok(())
+    }
+}
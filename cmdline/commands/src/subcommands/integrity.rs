@@ -0,0 +1,71 @@
+use crate::state::*;
+use crate::error::*;
+use crate::output::*;
+use crate::storage_descriptor::*;
+
+use flo_stream::*;
+use flo_sqlite_storage::*;
+
+use futures::prelude::*;
+
+///
+/// Opens the storage for the input animation (the in-memory animation has no storage we can reopen)
+///
+pub (super) fn open_input_storage(state: &CommandState) -> Option<SqliteAnimationStorage> {
+    match state.input_descriptor() {
+        StorageDescriptor::InMemory => None,
+        descriptor                  => descriptor.open_storage(&state.file_manager())
+    }
+}
+
+///
+/// The check_integrity command: looks for inconsistencies in the input animation
+///
+pub fn check_integrity<'a>(output: &'a mut Publisher<FloCommandOutput>, state: &'a mut CommandState) -> impl Future<Output=Result<(), CommandError>>+Send+'a {
+    async move {
+        use FloCommandOutput::*;
+
+        let descriptor  = state.input_descriptor();
+        let storage     = open_input_storage(state).ok_or_else(|| CommandError::CouldNotOpenAnimation(format!("{}", descriptor)))?;
+        let problems    = storage.check_integrity().map_err(|err| CommandError::CouldNotCheckIntegrity(err.to_string()))?;
+
+        // Report the problems that were found
+        for problem in problems.iter() {
+            output.publish(Error(problem.clone())).await;
+        }
+
+        if problems.len() == 0 {
+            output.publish(Message(format!("No problems found in '{}'", descriptor))).await;
+        } else {
+            output.publish(Message(format!("{} problems found in '{}'", problems.len(), descriptor))).await;
+        }
+
+        Ok(())
+    }
+}
+
+///
+/// The repair_animation command: rebuilds the input animation from its edit log
+///
+pub fn repair_animation<'a>(output: &'a mut Publisher<FloCommandOutput>, state: &'a mut CommandState) -> impl Future<Output=Result<(), CommandError>>+Send+'a {
+    async move {
+        use FloCommandOutput::*;
+
+        let descriptor  = state.input_descriptor();
+        let storage     = open_input_storage(state).ok_or_else(|| CommandError::CouldNotOpenAnimation(format!("{}", descriptor)))?;
+
+        // Rebuild the animation
+        output.publish(StartTask("Replay edit log".to_string())).await;
+        let num_edits   = storage.rebuild_from_edit_log();
+        output.publish(FinishTask).await;
+
+        let num_edits   = num_edits.map_err(|(_err, msg)| CommandError::CouldNotRepairAnimation(msg))?;
+        output.publish(Message(format!("Rebuilt '{}' from {} edits", descriptor, num_edits))).await;
+
+        // Reload the input animation so it reflects the rebuilt data
+        *state = state.load_input_file(descriptor.clone())
+            .ok_or_else(move || CommandError::CouldNotOpenAnimation(format!("{}", descriptor)))?;
+
+        Ok(())
+    }
+}
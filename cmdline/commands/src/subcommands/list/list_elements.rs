@@ -21,6 +21,8 @@ fn describe_vector(vec: &Vector) -> String {
         Path(path)                      => { format!("Path, {} elements", path.path().elements().count()) }
         Motion(_motion)                 => { format!("Motion description") }
         Transformation(_transform)      => { format!("Transformation description") }
+        Symbol(symbol)                  => { format!("Instance of symbol {}", symbol.instance().symbol_id) }
+        Bone(bone)                      => { format!("Bone, length {}", bone.bone().length) }
         Error                           => { format!("Error :-(") }
 
         Group(group)                    => { 
@@ -1,6 +1,11 @@
 mod list;
+mod archive;
 mod edits;
+mod compact;
 mod elements;
+mod integrity;
+mod replay;
+mod snapshots;
 mod read_from;
 mod dump_catalog;
 mod select_frame;
@@ -8,8 +13,13 @@ mod write_to_catalog;
 mod set_catalog_folder;
 
 pub (super) use self::list::*;
+pub (super) use self::archive::*;
 pub (super) use self::edits::*;
+pub (super) use self::compact::*;
 pub (super) use self::elements::*;
+pub (super) use self::integrity::*;
+pub (super) use self::replay::*;
+pub (super) use self::snapshots::*;
 pub (super) use self::read_from::*;
 pub (super) use self::dump_catalog::*;
 pub (super) use self::select_frame::*;
@@ -0,0 +1,80 @@
+use crate::state::*;
+use crate::error::*;
+use crate::output::*;
+
+use flo_stream::*;
+use flo_animation::*;
+use flo_animation::storage::*;
+
+use futures::prelude::*;
+
+use std::sync::*;
+
+///
+/// Publishes a list of differences between two animations
+///
+async fn publish_differences(output: &mut Publisher<FloCommandOutput>, differences: Vec<AnimationDifference>) {
+    use FloCommandOutput::*;
+
+    for difference in differences.iter() {
+        output.publish(Error(format!("{:?}", difference))).await;
+    }
+
+    if differences.len() == 0 {
+        output.publish(Message("No differences found".to_string())).await;
+    } else {
+        output.publish(Message(format!("{} differences found", differences.len()))).await;
+    }
+}
+
+///
+/// The replay_edits command replays the edit log of the input animation into the output animation, optionally stopping
+/// before a particular edit
+///
+pub fn replay_edits<'a>(output: &'a mut Publisher<FloCommandOutput>, state: &'a mut CommandState, until: Option<usize>) -> impl Future<Output=Result<(), CommandError>>+Send+'a {
+    async move {
+        let input       = state.input_animation();
+        let target      = state.output_animation();
+
+        output.publish(FloCommandOutput::StartTask("Replay edit log".to_string())).await;
+        let num_edits   = replay_edit_log(&*input, &*target, until).await;
+        output.publish(FloCommandOutput::FinishTask).await;
+
+        output.publish(FloCommandOutput::Message(format!("Replayed {} edits to the output animation", num_edits))).await;
+
+        Ok(())
+    }
+}
+
+///
+/// The compare_animations command lists the differences between the input and the output animations
+///
+pub fn compare_animations<'a>(output: &'a mut Publisher<FloCommandOutput>, state: &'a mut CommandState) -> impl Future<Output=Result<(), CommandError>>+Send+'a {
+    async move {
+        let differences = diff_animations(&*state.input_animation(), &*state.output_animation());
+        publish_differences(output, differences).await;
+
+        Ok(())
+    }
+}
+
+///
+/// The verify_edit_log command replays the edit log of the input animation into an in-memory animation and lists any
+/// differences between the two
+///
+pub fn verify_edit_log<'a>(output: &'a mut Publisher<FloCommandOutput>, state: &'a mut CommandState) -> impl Future<Output=Result<(), CommandError>>+Send+'a {
+    async move {
+        let input       = state.input_animation();
+        let storage     = InMemoryStorage::new();
+        let target      = Arc::new(create_animation_editor(move |commands| storage.get_responses(commands).boxed()));
+
+        output.publish(FloCommandOutput::StartTask("Replay edit log".to_string())).await;
+        let num_edits   = replay_edit_log(&*input, &*target, None).await;
+        output.publish(FloCommandOutput::FinishTask).await;
+
+        output.publish(FloCommandOutput::Message(format!("Replayed {} edits", num_edits))).await;
+        publish_differences(output, diff_animations(&*input, &*target)).await;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,85 @@
+use super::integrity::*;
+use crate::state::*;
+use crate::error::*;
+use crate::output::*;
+use crate::storage_descriptor::*;
+
+use flo_stream::*;
+use flo_animation::*;
+use flo_animation::storage::*;
+
+use futures::prelude::*;
+use std::sync::*;
+
+///
+/// The list_snapshots command: writes out the named snapshots of the input animation
+///
+pub fn list_snapshots<'a>(output: &'a mut Publisher<FloCommandOutput>, state: &'a mut CommandState) -> impl Future<Output=()>+Send+'a {
+    async move {
+        let snapshots = state.input_animation().get_snapshots();
+
+        for snapshot in snapshots.iter() {
+            output.publish(FloCommandOutput::Message(format!("{}\t{} edits", snapshot.name, snapshot.edit_index))).await;
+        }
+
+        if snapshots.len() == 0 {
+            output.publish(FloCommandOutput::Message(format!("'{}' has no snapshots", state.input_descriptor()))).await;
+        }
+    }
+}
+
+///
+/// The create_snapshot command: names the current state of the input animation
+///
+pub fn create_snapshot<'a>(output: &'a mut Publisher<FloCommandOutput>, state: &'a mut CommandState, name: String) -> impl Future<Output=()>+Send+'a {
+    async move {
+        let animation   = state.editable_input_animation();
+        let num_edits   = animation.get_num_edits();
+
+        animation.create_snapshot(&name);
+
+        output.publish(FloCommandOutput::Message(format!("Created snapshot '{}' at edit {}", name, num_edits))).await;
+    }
+}
+
+///
+/// The fork_snapshot command: creates a new animation in the catalog from a snapshot of the input animation
+///
+pub fn fork_snapshot<'a>(output: &'a mut Publisher<FloCommandOutput>, state: &'a mut CommandState, name: String) -> impl Future<Output=Result<(), CommandError>>+Send+'a {
+    async move {
+        // Find the snapshot to fork from
+        let descriptor  = state.input_descriptor();
+        let snapshot    = state.input_animation().get_snapshots()
+            .into_iter()
+            .filter(|snapshot| snapshot.name == name)
+            .nth(0)
+            .ok_or_else(|| CommandError::SnapshotNotFound(name.clone()))?;
+
+        // Copy the edits from the input animation into a new file in the catalog
+        let storage     = open_input_storage(state).ok_or_else(|| CommandError::CouldNotOpenAnimation(format!("{}", descriptor)))?;
+        let new_name    = format!("{} ({})", descriptor, snapshot.name);
+        let catalog     = state.file_manager();
+        let new_path    = catalog.create_new_path();
+
+        output.publish(FloCommandOutput::StartTask("Fork snapshot".to_string())).await;
+        let forked      = storage.fork(new_path.as_path(), snapshot.edit_index);
+        output.publish(FloCommandOutput::FinishTask).await;
+
+        let forked      = match forked {
+            Ok(forked)      => forked,
+            Err((_, msg))   => {
+                catalog.delete_path(new_path.as_path());
+                return Err(CommandError::CouldNotCreateAnimation(format!("{}: {}", new_name, msg)));
+            }
+        };
+        catalog.set_display_name_for_path(new_path.as_path(), new_name.clone());
+
+        // Make the forked animation the output animation
+        let animation   = create_animation_editor(move |commands| forked.get_responses(commands).boxed());
+        *state          = state.set_output_animation(StorageDescriptor::CatalogName(new_name.clone()), Arc::new(animation));
+
+        output.publish(FloCommandOutput::Message(format!("Created '{}' from {} edits", new_name, snapshot.edit_index))).await;
+
+        Ok(())
+    }
+}
@@ -1,5 +1,6 @@
 use flo_commands::*;
 use flo_animation::*;
+use flo_animation::storage::*;
 
 use tokio::prelude::*;
 use tokio::io::{stdin, stderr};
@@ -12,6 +13,17 @@ use self::console::*;
 
 use std::str::{FromStr};
 
+///
+/// Parses a checkpoint parameter (of the form <name>:<edit_index>)
+///
+fn parse_checkpoint(checkpoint: &str) -> Option<EditLogCheckpoint> {
+    let sep_pos     = checkpoint.rfind(':')?;
+    let name        = checkpoint[0..sep_pos].to_string();
+    let edit_index  = usize::from_str(&checkpoint[sep_pos+1..checkpoint.len()]).ok()?;
+
+    Some(EditLogCheckpoint { name, edit_index })
+}
+
 #[tokio::main]
 async fn main() {
     // Fetch the parameters
@@ -74,6 +86,65 @@ async fn main() {
             .about("Reads a file (or standard input if no file is specified) containing serialized edits and writes them to the output animation"))
         .subcommand(SubCommand::with_name("dump-all-catalog-edits")
             .about("Writes out the entire catalog as a set of edit logs"))
+        .subcommand(SubCommand::with_name("check-integrity")
+            .about("Checks the input animation for data that is inconsistent with its edit log"))
+        .subcommand(SubCommand::with_name("repair")
+            .about("Rebuilds the input animation from its edit log"))
+        .subcommand(SubCommand::with_name("replay")
+            .about("Replays the edit log of the input animation into the output animation")
+            .arg(Arg::with_name("until")
+                .long("until")
+                .takes_value(true)
+                .help("Stops replaying before the specified edit")))
+        .subcommand(SubCommand::with_name("diff")
+            .about("Lists the differences between the input animation and the output animation"))
+        .subcommand(SubCommand::with_name("verify")
+            .about("Checks that replaying the edit log of the input animation recreates the same animation"))
+        .subcommand(SubCommand::with_name("compact")
+            .about("Compacts the edit log of the input animation in place")
+            .arg(Arg::with_name("checkpoint")
+                .long("checkpoint")
+                .takes_value(true)
+                .multiple(true)
+                .number_of_values(1)
+                .help("Preserves the animation as it was after a particular edit (eg: --checkpoint \"client review:120\")")))
+        .subcommand(SubCommand::with_name("compact-edits")
+            .about("Writes a compacted version of the edit log of the input animation to the output animation")
+            .arg(Arg::with_name("checkpoint")
+                .long("checkpoint")
+                .takes_value(true)
+                .multiple(true)
+                .number_of_values(1)
+                .help("Preserves the animation as it was after a particular edit (eg: --checkpoint \"client review:120\")")))
+        .subcommand(SubCommand::with_name("list-snapshots")
+            .about("Lists the named snapshots of the input animation"))
+        .subcommand(SubCommand::with_name("snapshot")
+            .about("Creates a named snapshot of the input animation")
+            .arg(Arg::with_name("NAME")
+                .help("The name of the snapshot")
+                .required(true)
+                .index(1)))
+        .subcommand(SubCommand::with_name("fork-snapshot")
+            .about("Creates a new animation in the catalog from a named snapshot of the input animation")
+            .arg(Arg::with_name("NAME")
+                .help("The name of the snapshot to create the new animation from")
+                .required(true)
+                .index(1)))
+        .subcommand(SubCommand::with_name("export")
+            .about("Writes the input animation to a portable .flo archive")
+            .arg(Arg::with_name("FILE")
+                .help("The archive file to write")
+                .required(true)
+                .index(1))
+            .arg(Arg::with_name("compact")
+                .long("compact")
+                .help("Compacts the edit log before writing it to the archive")))
+        .subcommand(SubCommand::with_name("import")
+            .about("Imports a .flo archive into the catalog and makes it the output animation")
+            .arg(Arg::with_name("FILE")
+                .help("The archive file to read")
+                .required(true)
+                .index(1)))
         .subcommand(SubCommand::with_name("debug-raycasting")
             .about("Writes out a series of SVG files showing the raycasting used for a particular element")
             .arg(Arg::with_name("ELEMENT")
@@ -168,6 +239,84 @@ async fn main() {
             input.push(FloCommand::DumpCatalogAsEdits);
         }
 
+        // Integrity commands
+        if let Some(_) = params.subcommand_matches("check-integrity") {
+            input.push(FloCommand::CheckIntegrity);
+        }
+
+        if let Some(_) = params.subcommand_matches("repair") {
+            input.push(FloCommand::RepairAnimation);
+            input.push(FloCommand::CheckIntegrity);
+        }
+
+        // Replay commands
+        if let Some(replay) = params.subcommand_matches("replay") {
+            let until = match replay.value_of("until").map(|until| usize::from_str(until)) {
+                None            => None,
+                Some(Ok(until)) => Some(until),
+                Some(Err(_))    => {
+                    stderr().write(format!("'{}' is not a valid edit number\n\n", replay.value_of("until").unwrap_or("-")).as_bytes()).await.unwrap();
+                    return;
+                }
+            };
+
+            input.push(FloCommand::ReplayEdits(until));
+        }
+
+        if let Some(_) = params.subcommand_matches("diff") {
+            input.push(FloCommand::CompareAnimations);
+        }
+
+        if let Some(_) = params.subcommand_matches("verify") {
+            input.push(FloCommand::VerifyEditLog);
+        }
+
+        // Compaction commands
+        for compact_command in ["compact", "compact-edits"].iter() {
+            if let Some(compact) = params.subcommand_matches(compact_command) {
+                // Parse the checkpoints
+                let mut checkpoints = vec![];
+                for checkpoint in compact.values_of("checkpoint").into_iter().flatten() {
+                    if let Some(checkpoint) = parse_checkpoint(checkpoint) {
+                        checkpoints.push(checkpoint);
+                    } else {
+                        stderr().write(format!("'{}' is not a valid checkpoint. The parameter must be of the format <name>:<edit_index>\n\n", checkpoint).as_bytes()).await.unwrap();
+                        return;
+                    }
+                }
+
+                if *compact_command == "compact" {
+                    input.push(FloCommand::CompactAnimation(checkpoints));
+                } else {
+                    input.push(FloCommand::ReadAllEdits);
+                    input.push(FloCommand::CompactEdits(checkpoints));
+                    input.push(FloCommand::WriteAllEdits);
+                }
+            }
+        }
+
+        // Snapshot commands
+        if let Some(_) = params.subcommand_matches("list-snapshots") {
+            input.push(FloCommand::ListSnapshots);
+        }
+
+        if let Some(snapshot) = params.subcommand_matches("snapshot") {
+            input.push(FloCommand::CreateSnapshot(snapshot.value_of("NAME").unwrap().to_string()));
+        }
+
+        if let Some(fork) = params.subcommand_matches("fork-snapshot") {
+            input.push(FloCommand::ForkSnapshot(fork.value_of("NAME").unwrap().to_string()));
+        }
+
+        // Archive commands
+        if let Some(export) = params.subcommand_matches("export") {
+            input.push(FloCommand::ExportArchive(export.value_of("FILE").unwrap().to_string(), export.is_present("compact")));
+        }
+
+        if let Some(import) = params.subcommand_matches("import") {
+            input.push(FloCommand::ImportArchive(import.value_of("FILE").unwrap().to_string()));
+        }
+
         // Serialize edits command
         if let Some(_) = params.subcommand_matches("serialize-edits") {
             input.push(FloCommand::ReadAllEdits);
@@ -4,10 +4,18 @@ use flo_ui::*;
 use flo_canvas::*;
 use flo_binding::*;
 use flo_animation::*;
+use flo_logging::*;
 
 use std::sync::*;
 use std::collections::HashMap;
 
+lazy_static! {
+    static ref CANVAS_RENDERER_LOG: LogPublisher = LogPublisher::new(module_path!());
+}
+
+/// The first texture ID used for reference images (this range is reserved so they don't collide with other textures on the canvas)
+const REFERENCE_TEXTURE_BASE: u64 = 0x1_0000_0000;
+
 ///
 /// Represents a layer in the current frame
 ///
@@ -25,6 +33,20 @@ struct FrameLayer {
     active_properties:  Option<BrushProperties>
 }
 
+///
+/// Represents a reference layer in the current frame
+///
+struct ReferenceLayer {
+    /// The settings for this reference layer
+    settings:           ReferenceLayerSettings,
+
+    /// The PNG data that the texture for this layer was loaded from
+    png_data:           Option<Arc<Vec<u8>>>,
+
+    /// The texture containing the image for this layer (texture ID, width and height)
+    texture:            Option<(TextureId, u32, u32)>
+}
+
 ///
 /// Represents a layer containing an overlay
 ///
@@ -46,6 +68,18 @@ pub struct CanvasRenderer {
     /// The layers in the current frame
     frame_layers: HashMap<u64, FrameLayer>,
 
+    /// The reference layers that have been loaded, and the textures that were uploaded for them
+    reference_layers: HashMap<u64, ReferenceLayer>,
+
+    /// The reference layers in the current frame, in timeline order (these are drawn beneath the frame layers)
+    reference_order: Vec<u64>,
+
+    /// Texture operations that need to be sent to the canvas before the reference layers are next drawn
+    pending_textures: Vec<(TextureId, TextureOp)>,
+
+    /// The ID to use for the next reference texture
+    next_texture_id: u64,
+
     /// The over layers in the current frame
     overlay_layers: HashMap<u32, OverlayLayer>,
 
@@ -73,6 +107,10 @@ impl CanvasRenderer {
     pub fn new() -> CanvasRenderer {
         CanvasRenderer {
             frame_layers:       HashMap::new(),
+            reference_layers:   HashMap::new(),
+            reference_order:    vec![],
+            pending_textures:   vec![],
+            next_texture_id:    REFERENCE_TEXTURE_BASE,
             overlay_layers:     HashMap::new(),
             annotated_layer:    None
         }
@@ -81,8 +119,11 @@ impl CanvasRenderer {
     ///
     /// Clears all layers from this renderer
     ///
+    /// The textures for reference layers are kept so they don't need to be uploaded again if the layers are reloaded
+    ///
     pub fn clear(&mut self) {
-        self.frame_layers = HashMap::new();
+        self.frame_layers       = HashMap::new();
+        self.reference_order    = vec![];
     }
 
     ///
@@ -181,6 +222,12 @@ impl CanvasRenderer {
     /// Loads a particular frame from a layer into this renderer
     ///
     pub fn load_frame(&mut self, model: FrameLayerModel) {
+        // Reference layers are displayed as images rather than as frames
+        if let Some(reference) = model.reference.get() {
+            self.load_reference(model.layer_id, reference);
+            return;
+        }
+
         // Load the frame data (we don't necessarily form a binding here)
         let frame = model.frame.get();
 
@@ -205,10 +252,54 @@ impl CanvasRenderer {
         }
     }
 
+    ///
+    /// Loads the image for a reference layer into this renderer
+    ///
+    fn load_reference(&mut self, animation_layer_id: u64, reference: ReferenceFrame) {
+        self.reference_order.push(animation_layer_id);
+
+        // If the image is unchanged, the texture that was uploaded before can be re-used
+        if let Some(existing) = self.reference_layers.get_mut(&animation_layer_id) {
+            if existing.png_data == reference.png_data {
+                existing.settings = reference.settings;
+                return;
+            }
+        }
+
+        // Free the texture for the old image
+        if let Some((texture_id, _, _)) = self.reference_layers.remove(&animation_layer_id).and_then(|old_layer| old_layer.texture) {
+            self.pending_textures.push((texture_id, TextureOp::Free));
+        }
+
+        // Decode the PNG data for the image
+        let image = reference.png_data.as_ref()
+            .and_then(|png_data| Image::Png(Arc::new(InMemoryImageData::from((**png_data).clone()))).to_rgba_data());
+
+        // Upload it to a new texture the next time the canvas is drawn
+        let texture = image.map(|(width, height, rgba)| {
+            let texture_id          = TextureId(self.next_texture_id);
+            self.next_texture_id    += 1;
+
+            self.pending_textures.push((texture_id, TextureOp::Create(width, height, TextureFormat::Rgba)));
+            self.pending_textures.push((texture_id, TextureOp::SetBytes(0, 0, width, height, Arc::new(rgba))));
+
+            (texture_id, width, height)
+        });
+
+        self.reference_layers.insert(animation_layer_id, ReferenceLayer {
+            settings:   reference.settings,
+            png_data:   reference.png_data,
+            texture:    texture
+        });
+    }
+
     ///
     /// Clears a canvas and sets it up for rendering
     ///
-    fn clear_canvas(&mut self, canvas: &BindingCanvas, (width, height): (f64, f64)) {
+    /// The view transform is applied after the animation is centered on the canvas: the canvas backends map input
+    /// through the inverse of the canvas transform, so paint actions will arrive in animation coordinates.
+    ///
+    fn clear_canvas(&mut self, canvas: &BindingCanvas, (width, height): (f64, f64), view_transform: Transform2D) {
         // Clearing the canvas also removes any 'annotations' that might have been performed
         self.annotated_layer = None;
 
@@ -216,6 +307,7 @@ impl CanvasRenderer {
             gc.clear_canvas();
             gc.canvas_height((height*1.05) as f32);
             gc.center_region(0.0,0.0, width as f32, height as f32);
+            gc.transform(view_transform);
         });
     }
 
@@ -249,12 +341,61 @@ impl CanvasRenderer {
     }
 
     ///
-    /// Draws the current set of frame layers to the specified canvas
+    /// Draws the reference layers to a context
+    ///
+    /// Reference images go on the background layer so they're always beneath the frame layers. Textures are only
+    /// uploaded when the image for a layer changes, as they're kept when the canvas is cleared.
+    ///
+    fn draw_reference_layers(&mut self, gc: &mut dyn GraphicsPrimitives) {
+        gc.layer(0);
+
+        // Free the textures for any reference layers that are no longer in the frame
+        let reference_order     = &self.reference_order;
+        let removed_layers      = self.reference_layers.keys()
+            .filter(|layer_id| !reference_order.contains(layer_id))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        for layer_id in removed_layers {
+            if let Some((texture_id, _, _)) = self.reference_layers.remove(&layer_id).and_then(|old_layer| old_layer.texture) {
+                self.pending_textures.push((texture_id, TextureOp::Free));
+            }
+        }
+
+        // Upload any textures that have changed since the last time the canvas was drawn
+        for (texture_id, op) in self.pending_textures.drain(..) {
+            gc.texture(texture_id, op);
+        }
+
+        // Draw the layers in the order they appear in the timeline
+        for layer_id in self.reference_order.iter() {
+            let reference = if let Some(reference) = self.reference_layers.get(layer_id) { reference } else { continue; };
+
+            if let Some((texture_id, width, height)) = reference.texture {
+                let (w, h) = (width as f32, height as f32);
+
+                // The first row of the image is at the top (the canvas y axis points upwards)
+                gc.push_state();
+                gc.transform(reference.settings.image_transform((width as f64, height as f64)));
+                gc.draw_texture(texture_id, 0.0, h, w, 0.0, reference.settings.alpha as f32);
+                gc.pop_state();
+            }
+        }
+    }
+
+    ///
+    /// Draws the current set of frame layers to the specified canvas, using the specified view transform
     ///
-    pub fn draw_frame_layers(&mut self, canvas: &BindingCanvas, size: (f64, f64)) {
+    pub fn draw_frame_layers(&mut self, canvas: &BindingCanvas, size: (f64, f64), view_transform: Transform2D) {
+        // Redrawing happens frequently, so this only updates the metrics unless trace spans are turned on
+        let _span = CANVAS_RENDERER_LOG.span("draw_frame_layers")
+            .with_level(Level::Trace)
+            .with_field("num_layers", self.frame_layers.len());
+
         // Clear the canvas and redraw the background
-        self.clear_canvas(canvas, size);
+        self.clear_canvas(canvas, size, view_transform);
         canvas.draw(|gc| self.draw_background(gc, size));
+        canvas.draw(|gc| self.draw_reference_layers(gc));
 
         // Draw the active set of layers
         canvas.draw(move |gc| {
@@ -272,6 +413,8 @@ impl CanvasRenderer {
     /// Overlay operations will clear any annotation that might have been added.
     ///
     pub fn draw_overlays(&mut self, canvas: &BindingCanvas) {
+        let _span = CANVAS_RENDERER_LOG.span("draw_overlays").with_level(Level::Trace);
+
         // Overlays screw with the annotation: make sure it's cleared
         self.clear_annotation(canvas);
 
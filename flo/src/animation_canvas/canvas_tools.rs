@@ -186,12 +186,13 @@ impl<Anim: 'static+Animation+EditableAnimation> CanvasTools<Anim> {
         for action in actions {
             match action {
                 ToolAction::Data(data)              => self.tool_runner.set_tool_data(data),
-                ToolAction::Edit(edit)              => animation_edits.push(edit),
+                ToolAction::Edit(edit)              => if !self.edits_locked_layer(&edit) { animation_edits.push(edit) },
                 ToolAction::BrushPreview(preview)   => self.process_brush_preview(canvas, renderer, preview),
                 ToolAction::Overlay(overlay)        => self.process_overlay(canvas, renderer, overlay),
                 ToolAction::Select(element)         => self.animation.selection().select(element),
                 ToolAction::ClearSelection          => self.animation.selection().clear_selection(),
-                ToolAction::InvalidateFrame         => self.animation.timeline().invalidate_canvas()
+                ToolAction::InvalidateFrame         => self.animation.timeline().invalidate_canvas(),
+                ToolAction::View(view_action)       => self.animation.viewport().perform(view_action)
             }
         }
 
@@ -211,6 +212,17 @@ impl<Anim: 'static+Animation+EditableAnimation> CanvasTools<Anim> {
         }
     }
 
+    ///
+    /// True if an edit changes the content of a layer that's been locked (or is in a locked folder)
+    ///
+    fn edits_locked_layer(&self, edit: &AnimationEdit) -> bool {
+        match edit {
+            AnimationEdit::Layer(layer_id, _)       => self.animation.timeline().is_layer_locked(*layer_id),
+            AnimationEdit::Element(element_ids, _)  => self.animation.timeline().are_elements_locked(element_ids),
+            _                                       => false
+        }
+    }
+
     ///
     /// True if we need to update the brush definition before drawing
     ///
@@ -229,6 +241,15 @@ impl<Anim: 'static+Animation+EditableAnimation> CanvasTools<Anim> {
         properties.as_ref() != Some(&self.brush_properties)
     }
 
+    ///
+    /// True if the layer that the brush preview is being drawn on is locked
+    ///
+    fn preview_layer_locked(&self) -> bool {
+        self.preview_layer
+            .map(|layer_id| self.animation.timeline().is_layer_locked(layer_id))
+            .unwrap_or(false)
+    }
+
     ///
     /// Processes a brush preview action
     ///
@@ -248,8 +269,8 @@ impl<Anim: 'static+Animation+EditableAnimation> CanvasTools<Anim> {
             BrushPreviewAction::BrushDefinition(defn, style)    => { self.brush_definition = (defn.clone(), style); self.preview.as_mut().map(move |preview| preview.select_brush(&defn, style)); },
             BrushPreviewAction::BrushProperties(props)          => { self.brush_properties = props; self.preview.as_mut().map(move |preview| preview.set_brush_properties(&props)); },
             BrushPreviewAction::AddPoint(point)                 => { self.preview.as_mut().map(move |preview| preview.continue_brush_stroke(point)); },
-            BrushPreviewAction::Commit                          => { self.commit_brush_preview(canvas, renderer) },
-            BrushPreviewAction::CommitAsPath                    => { self.commit_brush_preview_as_path(canvas, renderer) }
+            BrushPreviewAction::Commit                          => { if !self.preview_layer_locked() { self.commit_brush_preview(canvas, renderer) } else { self.preview = None; } },
+            BrushPreviewAction::CommitAsPath                    => { if !self.preview_layer_locked() { self.commit_brush_preview_as_path(canvas, renderer) } else { self.preview = None; } }
             BrushPreviewAction::CombineCollidingElements        => { self.combine_colliding_elements() }
         }
     }
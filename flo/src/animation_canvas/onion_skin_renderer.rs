@@ -52,8 +52,7 @@ impl OnionSkinRenderer {
                         let color   = color.with_alpha(opacity as f32);
 
                         iter::once(Draw::NewPath)
-                            .chain(drawing.iter()
-                                .map(|draw| *draw))
+                            .chain(drawing.iter().cloned())
                             .chain(vec![
                                 Draw::FillColor(color),
                                 Draw::Fill,
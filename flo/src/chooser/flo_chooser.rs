@@ -3,11 +3,19 @@ use super::super::model::*;
 use super::super::editor::*;
 
 use flo_animation::*;
+use flo_animation::storage::*;
 use flo_ui_files::*;
 use flo_ui_files::ui::*;
 use flo_ui_files::sqlite::*;
 
+use futures::executor;
+
+use std::fs;
 use std::sync::*;
+use std::path::{Path, PathBuf};
+
+/// The file extension used for exported animations
+const ARCHIVE_EXTENSION: &str = "flo";
 
 ///
 /// The default file chooser for FlowBetween
@@ -18,7 +26,10 @@ where Loader::NewAnimation: 'static+EditableAnimation {
     file_manager: Arc<SqliteFileManager>,
 
     /// The shared open file store for this animation
-    file_store: Arc<OpenFileStore<FloSharedModel<Loader>>>
+    file_store: Arc<OpenFileStore<FloSharedModel<Loader>>>,
+
+    /// The loader used to read the versions of an animation and to create new animations from them
+    loader: Arc<Loader>
 }
 
 impl<Loader: 'static+FileAnimation> FloChooser<Loader>
@@ -31,12 +42,13 @@ where Loader::NewAnimation: 'static+EditableAnimation {
         let file_manager = Arc::new(SqliteFileManager::new(APP_NAME, DEFAULT_USER_FOLDER));
 
         // Create the file store
-        let file_store = Arc::new(OpenFileStore::new(loader));
+        let file_store = Arc::new(OpenFileStore::new(Arc::clone(&loader)));
 
         // Put everything together
         FloChooser {
             file_manager:   file_manager,
-            file_store:     file_store
+            file_store:     file_store,
+            loader:         loader
         }
     }
 }
@@ -62,4 +74,118 @@ where Loader::NewAnimation: 'static+EditableAnimation  {
     fn get_file_store(&self) -> Arc<OpenFileStore<FloSharedModel<Loader>>> {
         Arc::clone(&self.file_store)
     }
+
+    ///
+    /// Retrieves the named snapshots of the animation at the specified path
+    ///
+    fn get_file_versions(&self, path: &Path) -> Vec<FileVersion> {
+        // Files that haven't been written yet have no versions (opening them would create them)
+        if !path.exists() {
+            return vec![];
+        }
+
+        self.loader.open(path)
+            .get_snapshots()
+            .into_iter()
+            .map(|snapshot| FileVersion { name: snapshot.name, edit_index: snapshot.edit_index })
+            .collect()
+    }
+
+    ///
+    /// Creates a new animation from a snapshot of an existing one
+    ///
+    fn fork_file_version(&self, path: &Path, version: &FileVersion) -> Option<PathBuf> {
+        // Create a new file to contain the version
+        let new_path    = self.file_manager.create_new_path();
+        let forked      = self.loader.fork(path, new_path.as_path(), version.edit_index);
+
+        if forked.is_none() {
+            self.file_manager.delete_path(new_path.as_path());
+            return None;
+        }
+
+        // Name it after the original file and the version
+        let file_name   = self.file_manager.display_name_for_path(path).unwrap_or_else(|| "Untitled".to_string());
+        self.file_manager.set_display_name_for_path(new_path.as_path(), format!("{} ({})", file_name, version.name));
+
+        Some(new_path)
+    }
+
+    ///
+    /// Writes an animation to an archive in the exchange folder
+    ///
+    fn export_file(&self, path: &Path, compact: bool) -> Option<PathBuf> {
+        if !path.exists() {
+            return None;
+        }
+
+        // Archive the animation, compacting its edit log if requested
+        let name        = self.file_manager.display_name_for_path(path).unwrap_or_else(|| "Untitled".to_string());
+        let animation   = self.loader.open(path);
+        let archive     = executor::block_on(AnimationArchive::from_animation(&animation, Some(name.clone()), compact));
+
+        let mut archive_data = String::new();
+        archive.serialize(&mut archive_data);
+
+        // Pick a filename in the exchange folder that's not already in use
+        let exchange_folder     = self.file_manager.exchange_folder();
+        let file_name           = name.chars().map(|chr| if chr.is_alphanumeric() || chr == ' ' || chr == '-' || chr == '_' { chr } else { '_' }).collect::<String>();
+        let mut archive_path    = exchange_folder.join(format!("{}.{}", file_name, ARCHIVE_EXTENSION));
+        let mut file_index      = 0;
+
+        while archive_path.exists() {
+            file_index      += 1;
+            archive_path    = exchange_folder.join(format!("{} ({}).{}", file_name, file_index, ARCHIVE_EXTENSION));
+        }
+
+        fs::write(archive_path.as_path(), archive_data).ok()?;
+
+        Some(archive_path)
+    }
+
+    ///
+    /// Returns the archives in the exchange folder
+    ///
+    fn get_importable_files(&self) -> Vec<PathBuf> {
+        let exchange_folder = self.file_manager.exchange_folder();
+        let mut archives    = fs::read_dir(exchange_folder).into_iter()
+            .flatten()
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().map(|extension| extension == ARCHIVE_EXTENSION).unwrap_or(false))
+            .collect::<Vec<_>>();
+
+        archives.sort();
+        archives
+    }
+
+    ///
+    /// Imports an archive as a new animation
+    ///
+    fn import_file(&self, archive_path: &Path) -> Option<PathBuf> {
+        // Read the archive
+        let archive_data    = fs::read_to_string(archive_path).ok()?;
+        let archive         = AnimationArchive::deserialize(&archive_data).ok()?;
+
+        // Create an empty animation to import the archive into
+        let new_path        = self.file_manager.create_new_path();
+        let animation       = match self.loader.create_empty(new_path.as_path()) {
+            Some(animation) => animation,
+            None            => {
+                self.file_manager.delete_path(new_path.as_path());
+                return None;
+            }
+        };
+
+        archive.restore(&animation);
+
+        // Give the new file a name that's not already in use
+        let name            = archive.name.clone()
+            .or_else(|| archive_path.file_stem().map(|stem| stem.to_string_lossy().to_string()))
+            .unwrap_or_else(|| "Imported file".to_string());
+        let name            = self.file_manager.unused_display_name(&name);
+        self.file_manager.set_display_name_for_path(new_path.as_path(), name);
+
+        Some(new_path)
+    }
 }
@@ -14,6 +14,11 @@ use std::time::Duration;
 
 const MAIN_CANVAS: &str     = "main";
 const PAINT_ACTION: &str    = "Paint";
+const SCROLL_ACTION: &str   = "ScrollWheel";
+const PINCH_ACTION: &str    = "Pinch";
+
+/// The number of pixels the scroll wheel needs to move to double (or halve) the zoom factor
+const SCROLL_ZOOM_DISTANCE: f32 = 256.0;
 
 ///
 /// The core of the canvas
@@ -32,7 +37,10 @@ struct CanvasCore<Anim: Animation+EditableAnimation> {
     last_paint_device: Option<PaintDevice>,
 
     /// The time of the current frame
-    current_time: Duration
+    current_time: Duration,
+
+    /// The view transform that was used when the canvas was last drawn
+    current_view_transform: Transform2D
 }
 
 ///
@@ -76,7 +84,8 @@ impl<Anim: Animation+EditableAnimation+'static> CanvasController<Anim> {
                 canvas_tools:               canvas_tools,
                 last_paint_device:          None,
                 current_time:               Duration::new(0, 0),
-                current_invalidation_count: 0
+                current_invalidation_count: 0,
+                current_view_transform:     Transform2D::identity()
             });
         let core                = Arc::new(core);
 
@@ -152,6 +161,10 @@ impl<Anim: Animation+EditableAnimation+'static> CanvasController<Anim> {
                             (ActionTrigger::Paint(PaintDevice::Eraser),                     PAINT_ACTION),
                             (ActionTrigger::Paint(PaintDevice::Mouse(MouseButton::Left)),   PAINT_ACTION)
                         ))
+                        .with((
+                            (ActionTrigger::ScrollWheel,                                    SCROLL_ACTION),
+                            (ActionTrigger::Pinch,                                          PINCH_ACTION)
+                        ))
                 ])
         });
 
@@ -196,16 +209,41 @@ impl<Anim: Animation+EditableAnimation+'static> CanvasController<Anim> {
     /// Draws the current set of frame layers
     ///
     fn draw_frame_layers(&self) {
-        let canvas  = self.canvases.get_named_resource(MAIN_CANVAS).unwrap();
-        let size    = self.anim_model.size();
+        let canvas          = self.canvases.get_named_resource(MAIN_CANVAS).unwrap();
+        let size            = self.anim_model.size();
+        let view_transform  = self.anim_model.viewport().transform.get();
 
         // Draw the active set of layers
         self.core.sync(move |core| {
-            core.renderer.draw_frame_layers(&*canvas, size);
+            core.current_view_transform = view_transform;
+
+            core.renderer.draw_frame_layers(&*canvas, size, view_transform);
             core.renderer.draw_overlays(&*canvas);
         });
     }
 
+    ///
+    /// Zooms the view when the scroll wheel is moved over the canvas
+    ///
+    fn scroll_wheel(&self, position: (f32, f32), (_delta_x, delta_y): (f32, f32)) {
+        // Scrolling up zooms in around the pointer
+        let factor = 2.0f32.powf(-delta_y / SCROLL_ZOOM_DISTANCE);
+        self.anim_model.viewport().perform(ViewportAction::Zoom(position, factor));
+    }
+
+    ///
+    /// Zooms and rotates the view in response to a pinch gesture
+    ///
+    fn pinch(&self, center: (f32, f32), scale: f32, rotation: f32) {
+        let viewport = self.anim_model.viewport();
+
+        // The gesture rotation is on the screen: it runs the opposite way in animation coordinates if the view is mirrored
+        let rotation = if viewport.flipped.get() { -rotation } else { rotation };
+
+        if scale != 1.0     { viewport.perform(ViewportAction::Zoom(center, scale)); }
+        if rotation != 0.0  { viewport.perform(ViewportAction::Rotate(center, rotation)); }
+    }
+
     ///
     /// Performs a series of painting actions on the canvas
     ///
@@ -280,6 +318,9 @@ impl<Anim: Animation+EditableAnimation+'static> Controller for CanvasController<
             // If the selected frame has changed, regenerate the canvas
             self.update_layers_to_frame_at_time(target_time);
             self.draw_frame_layers();
+        } else if self.core.sync(|core| core.current_view_transform) != self.anim_model.viewport().transform.get() {
+            // Only the view has changed: redraw the existing layers with the new transform
+            self.draw_frame_layers();
         }
     }
 
@@ -288,6 +329,8 @@ impl<Anim: Animation+EditableAnimation+'static> Controller for CanvasController<
 
         match (action_id, action_parameter) {
             (PAINT_ACTION, &Paint(ref device, ref painting))    => self.paint(device, painting),
+            (SCROLL_ACTION, &ScrollWheel(position, delta))      => self.scroll_wheel(position, delta),
+            (PINCH_ACTION, &Pinch(center, scale, rotation))     => self.pinch(center, scale, rotation),
             _                                                   => ()
         };
     }
@@ -23,17 +23,23 @@ pub struct ControlBarController<Anim: 'static+Animation+EditableAnimation> {
     frame_controls: Arc<FrameControlsController<Anim>>,
 
     /// The images for this controller
-    images: Arc<ResourceManager<Image>>
+    images: Arc<ResourceManager<Image>>,
+
+    /// Whether or not the log panel is visible
+    show_logs: Binding<bool>,
+
+    /// Whether or not the graph editor panel is visible
+    show_graph: Binding<bool>
 }
 
 impl<Anim: 'static+Animation+EditableAnimation> ControlBarController<Anim> {
     ///
     /// Creates a new control bar controller
     ///
-    pub fn new(model: &FloModel<Anim>) -> ControlBarController<Anim> {
+    pub fn new(model: &FloModel<Anim>, show_logs: Binding<bool>, show_graph: Binding<bool>) -> ControlBarController<Anim> {
         // Create the UI
         let images              = Arc::new(Self::images());
-        let ui                  = Self::ui(Arc::clone(&images));
+        let ui                  = Self::ui(Arc::clone(&images), BindRef::from(show_logs.clone()), BindRef::from(show_graph.clone()));
 
         // Create the subcontrollers
         let keyframe_controls   = KeyFrameControlsController::new(model);
@@ -47,7 +53,9 @@ impl<Anim: 'static+Animation+EditableAnimation> ControlBarController<Anim> {
             ui:                 ui,
             keyframe_controls:  keyframe_controls,
             frame_controls:     frame_controls,
-            images:             images
+            images:             images,
+            show_logs:          show_logs,
+            show_graph:         show_graph
         }
     }
 
@@ -63,36 +71,56 @@ impl<Anim: 'static+Animation+EditableAnimation> ControlBarController<Anim> {
     ///
     /// Creates the UI for this controller
     ///
-    fn ui(_images: Arc<ResourceManager<Image>>) -> BindRef<Control> {
+    fn ui(_images: Arc<ResourceManager<Image>>, show_logs: BindRef<bool>, show_graph: BindRef<bool>) -> BindRef<Control> {
         // Create the UI itself
-        let ui = Control::container()
-            .with(Bounds::fill_all())
-            .with(ControlAttribute::Padding((0, 2), (0, 2)))
-            .with(vec![
-                Control::empty()
-                    .with(Bounds::next_horiz(6.0)),
-
-                Control::container()
-                    .with_controller("FrameControls")
-                    .with(Bounds::next_horiz(22.0*6.0+80.0)),
-
-                Control::empty()
-                    .with(Bounds::next_horiz(3.0)),
-                Control::empty()
-                    .with(Appearance::Background(TIMESCALE_LAYERS))
-                    .with(Bounds::next_horiz(1.0)),
-
-                Control::empty()
-                    .with(Bounds::stretch_horiz(1.0)),
-                Control::container()
-                    .with_controller("KeyFrameControls")
-                    .with(Bounds::next_horiz(188.0)),
-                Control::empty()
-                    .with(Bounds::next_horiz(32.0))
-            ]);
+        let ui = computed(move || {
+            let logs_background     = if show_logs.get() { LOG_FILTER_SELECTED } else { TIMESCALE_BACKGROUND };
+            let graph_background    = if show_graph.get() { LOG_FILTER_SELECTED } else { TIMESCALE_BACKGROUND };
+
+            Control::container()
+                .with(Bounds::fill_all())
+                .with(ControlAttribute::Padding((0, 2), (0, 2)))
+                .with(vec![
+                    Control::empty()
+                        .with(Bounds::next_horiz(6.0)),
+
+                    Control::container()
+                        .with_controller("FrameControls")
+                        .with(Bounds::next_horiz(22.0*6.0+80.0)),
+
+                    Control::empty()
+                        .with(Bounds::next_horiz(3.0)),
+                    Control::empty()
+                        .with(Appearance::Background(TIMESCALE_LAYERS))
+                        .with(Bounds::next_horiz(1.0)),
+
+                    Control::empty()
+                        .with(Bounds::stretch_horiz(1.0)),
+                    Control::container()
+                        .with_controller("KeyFrameControls")
+                        .with(Bounds::next_horiz(232.0)),
+                    Control::empty()
+                        .with(Bounds::next_horiz(8.0)),
+                    Control::label()
+                        .with("Graph")
+                        .with(TextAlign::Center)
+                        .with(Appearance::Background(graph_background))
+                        .with((ActionTrigger::Click, "ToggleGraph"))
+                        .with(Bounds::next_horiz(44.0)),
+                    Control::empty()
+                        .with(Bounds::next_horiz(4.0)),
+                    Control::label()
+                        .with("Logs")
+                        .with(TextAlign::Center)
+                        .with(Appearance::Background(logs_background))
+                        .with((ActionTrigger::Click, "ToggleLogs"))
+                        .with(Bounds::next_horiz(40.0)),
+                    Control::empty()
+                        .with(Bounds::next_horiz(8.0))
+                ])
+        });
 
         // Create the binding
-        let ui = bind(ui);
         BindRef::from(ui)
     }
 }
@@ -114,4 +142,13 @@ impl<Anim: 'static+Animation+EditableAnimation> Controller for ControlBarControl
     fn get_image_resources(&self) -> Option<Arc<ResourceManager<Image>>> {
         Some(Arc::clone(&self.images))
     }
+
+    fn action(&self, action_id: &str, _action_parameter: &ActionParameter) {
+        match action_id {
+            "ToggleLogs"    => self.show_logs.set(!self.show_logs.get()),
+            "ToggleGraph"   => self.show_graph.set(!self.show_graph.get()),
+
+            _               => { }
+        }
+    }
 }
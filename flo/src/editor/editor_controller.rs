@@ -3,6 +3,8 @@ use super::canvas_controller::*;
 use super::toolbox_controller::*;
 use super::timeline_controller::*;
 use super::controlbar_controller::*;
+use super::log_controller::*;
+use super::graph_editor_controller::*;
 use super::super::model::*;
 use super::super::style::*;
 
@@ -23,7 +25,9 @@ enum SubController {
     Menu,
     ControlBar,
     Timeline,
-    Toolbox
+    Toolbox,
+    Logs,
+    GraphEditor
 }
 
 ///
@@ -34,7 +38,7 @@ pub struct EditorController<Anim: FileAnimation> {
     anim: PhantomData<Anim>,
 
     /// The main editor UI
-    ui: Binding<Control>,
+    ui: BindRef<Control>,
 
     /// The subcontrollers for this editor
     subcontrollers: HashMap<SubController, Arc<dyn Controller>>
@@ -59,9 +63,13 @@ where Loader::NewAnimation: 'static+EditableAnimation {
         let menu        = Arc::new(MenuController::new(&animation));
         let timeline    = Arc::new(TimelineController::new(&animation));
         let toolbox     = Arc::new(ToolboxController::new(&animation));
-        let control_bar = Arc::new(ControlBarController::new(&animation));
+        let show_logs   = bind(false);
+        let logs        = Arc::new(LogController::new(BindRef::new(&show_logs)));
+        let show_graph  = bind(false);
+        let graph       = Arc::new(GraphEditorController::new(&animation));
+        let control_bar = Arc::new(ControlBarController::new(&animation, show_logs.clone(), show_graph.clone()));
 
-        let ui          = bind(Self::ui());
+        let ui          = BindRef::from(computed(move || Self::ui(show_logs.get(), show_graph.get())));
         let mut subcontrollers: HashMap<SubController, Arc<dyn Controller>> = HashMap::new();
 
         subcontrollers.insert(SubController::Canvas,        canvas);
@@ -69,6 +77,8 @@ where Loader::NewAnimation: 'static+EditableAnimation {
         subcontrollers.insert(SubController::Timeline,      timeline);
         subcontrollers.insert(SubController::Toolbox,       toolbox);
         subcontrollers.insert(SubController::ControlBar,    control_bar);
+        subcontrollers.insert(SubController::Logs,          logs);
+        subcontrollers.insert(SubController::GraphEditor,   graph);
 
         EditorController {
             anim:           PhantomData,
@@ -153,10 +163,28 @@ where Loader::NewAnimation: 'static+EditableAnimation {
             .with_controller(&serde_json::to_string(&SubController::ControlBar).unwrap())
     }
 
+    ///
+    /// Creates the log panel control
+    ///
+    pub fn logs() -> Control {
+        Control::container()
+            .with(Bounds::next_horiz(LOG_PANEL_WIDTH))
+            .with_controller(&serde_json::to_string(&SubController::Logs).unwrap())
+    }
+
+    ///
+    /// Creates the graph editor panel control
+    ///
+    pub fn graph_editor() -> Control {
+        Control::container()
+            .with(Bounds::next_horiz(GRAPH_PANEL_WIDTH))
+            .with_controller(&serde_json::to_string(&SubController::GraphEditor).unwrap())
+    }
+
     ///
     /// Creates the UI tree for this controller
     ///
-    pub fn ui() -> Control {
+    pub fn ui(show_logs: bool, show_graph: bool) -> Control {
         use self::Position::*;
 
         let menu_bar    = Self::menu_bar();
@@ -165,12 +193,25 @@ where Loader::NewAnimation: 'static+EditableAnimation {
         let canvas      = Self::canvas();
         let control_bar = Self::control_bar();
 
+        // The graph editor and log panels are docked to the right of the canvas when they're visible
+        let mut main_area = vec![toolbar, canvas];
+
+        let side_panels = vec![(show_graph, Self::graph_editor()), (show_logs, Self::logs())];
+        for (is_visible, panel) in side_panels {
+            if is_visible {
+                main_area.push(Control::empty()
+                    .with(Bounds::next_horiz(1.0))
+                    .with(Appearance::Background(TIMESCALE_BORDER)));
+                main_area.push(panel);
+            }
+        }
+
         Control::container()
             .with(Bounds::fill_all())
             .with(vec![
                 menu_bar,
                 Control::container()
-                    .with((vec![toolbar, canvas],
+                    .with((main_area,
                         Bounds { x1: Start, y1: After, x2: End, y2: Stretch(1.0) })),
                 Control::empty()
                     .with(Bounds::next_vert(1.0))
@@ -186,7 +227,7 @@ where Loader::NewAnimation: 'static+EditableAnimation {
 impl<Loader: 'static+FileAnimation> Controller for EditorController<Loader>
 where Loader::NewAnimation: 'static+EditableAnimation {
     fn ui(&self) -> BindRef<Control> {
-        BindRef::new(&self.ui)
+        BindRef::clone(&self.ui)
     }
 
     fn get_subcontroller(&self, id: &str) -> Option<Arc<dyn Controller>> {
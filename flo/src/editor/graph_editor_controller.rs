@@ -0,0 +1,483 @@
+use super::super::style::*;
+use super::super::model::*;
+
+use flo_ui::*;
+use flo_canvas::*;
+use flo_stream::*;
+use flo_binding::*;
+use flo_animation::*;
+
+use ::desync::*;
+
+use std::sync::*;
+
+/// Action when the user drags on the graph
+const DRAG_GRAPH: &str = "DragGraph";
+
+/// The width of the graph panel when it's docked in the editor
+pub const GRAPH_PANEL_WIDTH: f32 = 400.0;
+
+/// The height of the graph canvas
+const GRAPH_HEIGHT: f32 = 240.0;
+
+/// The space left around the edges of the graph
+const GRAPH_MARGIN: f32 = 16.0;
+
+/// The height of the lane at the bottom of the graph that shows the timing of the control points
+const TIMING_LANE_HEIGHT: f32 = 24.0;
+
+/// The height of the row of easing presets
+const PRESET_ROW_HEIGHT: f32 = 24.0;
+
+/// The distance (in pixels) within which a marker in the timing lane can be picked up
+const MARKER_HIT_DISTANCE: f32 = 6.0;
+
+/// The number of samples used to plot each curve
+const GRAPH_SAMPLES: usize = 100;
+
+///
+/// A marker in the timing lane of the graph
+///
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum TimingMarker {
+    /// The control point at the specified index
+    Point(usize),
+
+    /// One of the handles of the control point at the specified index (true for the future handle)
+    Handle(usize, bool)
+}
+
+///
+/// Describes a drag that's in progress on the graph
+///
+#[derive(Clone, PartialEq, Debug)]
+struct GraphDrag {
+    /// The motion that's being edited
+    motion_id: ElementId,
+
+    /// The curve for the motion when the drag started
+    curve: TimeCurve,
+
+    /// The marker that's being dragged
+    marker: TimingMarker,
+
+    /// The time of the marker when the drag started
+    initial_millis: f32,
+
+    /// The number of milliseconds each pixel represented when the drag started
+    millis_per_pixel: f32
+}
+
+///
+/// The graph editor displays the x and y values of the selected motions against time, and allows their timing to be edited
+///
+pub struct GraphEditorController<Anim: Animation> {
+    /// The UI for this controller
+    ui: BindRef<Control>,
+
+    /// The canvases for the graph
+    canvases: Arc<ResourceManager<BindingCanvas>>,
+
+    /// The motions attached to the selected elements
+    selected_motions: BindRef<Arc<Vec<(ElementId, TimeCurve)>>>,
+
+    /// The drag that's currently in progress
+    drag: Binding<Option<GraphDrag>>,
+
+    /// The curve that's being edited by the current drag (displayed in place of the motion's actual curve)
+    editing: Binding<Option<(ElementId, TimeCurve)>>,
+
+    /// The animation editing stream where this will send updates
+    edit: Desync<Publisher<Arc<Vec<AnimationEdit>>>>,
+
+    /// The timeline model for the animation
+    timeline: TimelineModel<Anim>
+}
+
+impl<Anim: 'static+Animation+EditableAnimation> GraphEditorController<Anim> {
+    ///
+    /// Creates a new graph editor controller
+    ///
+    pub fn new(flo_model: &FloModel<Anim>) -> GraphEditorController<Anim> {
+        let selected_motions    = flo_model.selection().selected_motions.clone();
+        let editing             = bind(None);
+        let canvases            = Arc::new(ResourceManager::new());
+
+        let graph               = Self::graph_canvas(selected_motions.clone(), BindRef::new(&editing));
+        let graph               = canvases.register(graph);
+        let ui                  = Self::ui(selected_motions.clone(), graph);
+
+        GraphEditorController {
+            ui:                 ui,
+            canvases:           canvases,
+            selected_motions:   selected_motions,
+            drag:               bind(None),
+            editing:            editing,
+            edit:               Desync::new(flo_model.edit()),
+            timeline:           flo_model.timeline().clone()
+        }
+    }
+
+    ///
+    /// Returns the motions to display, with the motion being edited replaced by its preview
+    ///
+    fn visible_motions(selected_motions: &Vec<(ElementId, TimeCurve)>, editing: &Option<(ElementId, TimeCurve)>) -> Vec<(ElementId, TimeCurve)> {
+        selected_motions.iter()
+            .map(|(motion_id, curve)| {
+                match editing {
+                    Some((editing_id, editing_curve)) if editing_id == motion_id    => (*motion_id, editing_curve.clone()),
+                    _                                                               => (*motion_id, curve.clone())
+                }
+            })
+            .collect()
+    }
+
+    ///
+    /// Finds the range of times covered by a set of motions
+    ///
+    fn time_range(motions: &Vec<(ElementId, TimeCurve)>) -> (f32, f32) {
+        let mut min_millis = f32::MAX;
+        let mut max_millis = f32::MIN;
+
+        for (_, curve) in motions.iter() {
+            for point in curve.points.iter() {
+                min_millis = min_millis.min(point.point.milliseconds());
+                max_millis = max_millis.max(point.point.milliseconds());
+            }
+        }
+
+        if min_millis > max_millis {
+            (0.0, 1000.0)
+        } else if max_millis - min_millis < 1.0 {
+            (min_millis, min_millis + 1.0)
+        } else {
+            (min_millis, max_millis)
+        }
+    }
+
+    ///
+    /// Samples the x and y values of a curve over its length
+    ///
+    fn sample_curve(curve: &TimeCurve) -> Vec<(f32, f32, f32)> {
+        let first   = curve.points.first().map(|point| point.point.milliseconds());
+        let last    = curve.points.last().map(|point| point.point.milliseconds());
+
+        match (first, last) {
+            (Some(first), Some(last)) => {
+                (0..=GRAPH_SAMPLES)
+                    .map(|sample| first + (last - first) * (sample as f32) / (GRAPH_SAMPLES as f32))
+                    .filter_map(|millis| curve.point_at_time(millis).map(|point| (millis, point.0, point.1)))
+                    .collect()
+            }
+
+            _ => vec![]
+        }
+    }
+
+    ///
+    /// Returns the x position in the graph of a particular time
+    ///
+    fn millis_to_x(millis: f32, time_range: (f32, f32)) -> f32 {
+        let (min_millis, max_millis) = time_range;
+
+        GRAPH_MARGIN + (millis - min_millis) / (max_millis - min_millis) * (GRAPH_PANEL_WIDTH - GRAPH_MARGIN*2.0)
+    }
+
+    ///
+    /// Returns the markers in the timing lane for a curve, along with their times
+    ///
+    fn timing_markers(curve: &TimeCurve) -> Vec<(TimingMarker, f32)> {
+        let last_index  = curve.points.len().saturating_sub(1);
+        let mut markers = vec![];
+
+        for (index, point) in curve.points.iter().enumerate() {
+            // Points take priority over handles when they're in the same place, so they're returned first
+            markers.push((TimingMarker::Point(index), point.point.milliseconds()));
+        }
+
+        for (index, point) in curve.points.iter().enumerate() {
+            if index > 0            { markers.push((TimingMarker::Handle(index, false), point.past.milliseconds())); }
+            if index < last_index   { markers.push((TimingMarker::Handle(index, true), point.future.milliseconds())); }
+        }
+
+        markers
+    }
+
+    ///
+    /// Creates the canvas that draws the graph
+    ///
+    fn graph_canvas(selected_motions: BindRef<Arc<Vec<(ElementId, TimeCurve)>>>, editing: BindRef<Option<(ElementId, TimeCurve)>>) -> BindingCanvas {
+        BindingCanvas::with_drawing(move |gc| {
+            let motions     = Self::visible_motions(&*selected_motions.get(), &editing.get());
+            let time_range  = Self::time_range(&motions);
+
+            // Use pixel coordinates, with the origin at the top-left
+            gc.canvas_height(-GRAPH_HEIGHT);
+            gc.center_region(0.0, 0.0, GRAPH_PANEL_WIDTH, GRAPH_HEIGHT);
+
+            // Work out the range of values covered by the curves
+            let samples     = motions.iter().map(|(_, curve)| Self::sample_curve(curve)).collect::<Vec<_>>();
+            let min_value   = samples.iter().flatten().fold(f32::MAX, |min, (_, x, y)| min.min(*x).min(*y));
+            let max_value   = samples.iter().flatten().fold(f32::MIN, |max, (_, x, y)| max.max(*x).max(*y));
+            let (min_value, max_value) = if min_value > max_value { (0.0, 1.0) } else if max_value - min_value < 1.0 { (min_value - 0.5, max_value + 0.5) } else { (min_value, max_value) };
+
+            let graph_top    = GRAPH_MARGIN;
+            let graph_bottom = GRAPH_HEIGHT - TIMING_LANE_HEIGHT - GRAPH_MARGIN;
+            let value_to_y   = |value: f32| graph_bottom - (value - min_value) / (max_value - min_value) * (graph_bottom - graph_top);
+
+            // Draw the axes
+            gc.line_width_pixels(1.0);
+            gc.stroke_color(GRAPH_AXIS);
+            gc.new_path();
+            gc.move_to(GRAPH_MARGIN, graph_top);
+            gc.line_to(GRAPH_MARGIN, graph_bottom);
+            gc.line_to(GRAPH_PANEL_WIDTH - GRAPH_MARGIN, graph_bottom);
+            gc.stroke();
+
+            // Plot the x and y values of each curve
+            for curve_samples in samples.iter() {
+                for (component, color) in [GRAPH_X_VALUE, GRAPH_Y_VALUE].iter().enumerate() {
+                    gc.stroke_color(*color);
+                    gc.new_path();
+
+                    for (index, (millis, x_value, y_value)) in curve_samples.iter().enumerate() {
+                        let value   = if component == 0 { *x_value } else { *y_value };
+                        let x       = Self::millis_to_x(*millis, time_range);
+                        let y       = value_to_y(value);
+
+                        if index == 0 { gc.move_to(x, y); } else { gc.line_to(x, y); }
+                    }
+
+                    gc.stroke();
+                }
+            }
+
+            // Draw the timing lane
+            let lane_top    = GRAPH_HEIGHT - TIMING_LANE_HEIGHT;
+            let lane_y      = lane_top + TIMING_LANE_HEIGHT/2.0;
+
+            gc.fill_color(GRAPH_TIMING_LANE);
+            gc.new_path();
+            gc.rect(0.0, lane_top, GRAPH_PANEL_WIDTH, GRAPH_HEIGHT);
+            gc.fill();
+
+            for (_, curve) in motions.iter() {
+                // Lines joining each point to its handles
+                gc.stroke_color(CP_LINES);
+                gc.new_path();
+                for point in curve.points.iter() {
+                    gc.move_to(Self::millis_to_x(point.past.milliseconds(), time_range), lane_y);
+                    gc.line_to(Self::millis_to_x(point.future.milliseconds(), time_range), lane_y);
+                }
+                gc.stroke();
+
+                for (marker, millis) in Self::timing_markers(curve) {
+                    let x = Self::millis_to_x(millis, time_range);
+
+                    gc.new_path();
+                    match marker {
+                        TimingMarker::Point(_)      => {
+                            gc.rect(x-3.0, lane_y-3.0, x+3.0, lane_y+3.0);
+                            gc.fill_color(CP_BEZIER);
+                        }
+
+                        TimingMarker::Handle(_, _)  => {
+                            gc.move_to(x, lane_y-4.0);
+                            gc.line_to(x+4.0, lane_y);
+                            gc.line_to(x, lane_y+4.0);
+                            gc.line_to(x-4.0, lane_y);
+                            gc.close_path();
+                            gc.fill_color(CP_BEZIER_CP);
+                        }
+                    }
+                    gc.fill();
+                }
+            }
+        })
+    }
+
+    ///
+    /// Creates the row of easing presets
+    ///
+    fn preset_row() -> Control {
+        let presets     = Easing::all();
+        let label_width = GRAPH_PANEL_WIDTH / (presets.len() as f32);
+
+        Control::container()
+            .with(Bounds::next_vert(PRESET_ROW_HEIGHT))
+            .with(presets.iter()
+                .enumerate()
+                .map(|(index, easing)| {
+                    Control::label()
+                        .with(easing.name())
+                        .with(TextAlign::Center)
+                        .with((ActionTrigger::Click, format!("Ease-{}", index)))
+                        .with(Bounds::next_horiz(label_width))
+                })
+                .collect::<Vec<_>>())
+    }
+
+    ///
+    /// Creates the UI for this controller
+    ///
+    fn ui(selected_motions: BindRef<Arc<Vec<(ElementId, TimeCurve)>>>, graph: Resource<BindingCanvas>) -> BindRef<Control> {
+        let ui = computed(move || {
+            let has_motions = selected_motions.get().len() > 0;
+
+            let contents    = if has_motions {
+                vec![
+                    Self::preset_row(),
+                    Control::empty()
+                        .with(Bounds::next_vert(1.0))
+                        .with(Appearance::Background(TIMESCALE_BORDER)),
+                    Control::canvas()
+                        .with(graph.clone())
+                        .with(Bounds::next_vert(GRAPH_HEIGHT))
+                        .with((ActionTrigger::Drag, DRAG_GRAPH))
+                ]
+            } else {
+                vec![
+                    Control::label()
+                        .with("Select an element with a motion path to edit its timing")
+                        .with(TextAlign::Center)
+                        .with(Bounds::next_vert(PRESET_ROW_HEIGHT))
+                ]
+            };
+
+            Control::container()
+                .with(Bounds::fill_all())
+                .with(Appearance::Background(GRAPH_BACKGROUND))
+                .with(Font::Size(11.0))
+                .with(contents)
+        });
+
+        BindRef::from(ui)
+    }
+
+    ///
+    /// Finds the marker in the timing lane nearest to the specified position
+    ///
+    fn drag_at_position(&self, x: f32, y: f32) -> Option<GraphDrag> {
+        if y < GRAPH_HEIGHT - TIMING_LANE_HEIGHT {
+            return None;
+        }
+
+        let motions     = self.selected_motions.get();
+        let time_range  = Self::time_range(&*motions);
+        let mut nearest = None;
+        let mut nearest_distance = MARKER_HIT_DISTANCE;
+
+        for (motion_id, curve) in motions.iter() {
+            for (marker, millis) in Self::timing_markers(curve) {
+                let distance = (Self::millis_to_x(millis, time_range) - x).abs();
+
+                if distance < nearest_distance {
+                    nearest_distance    = distance;
+                    nearest             = Some(GraphDrag {
+                        motion_id:          *motion_id,
+                        curve:              curve.clone(),
+                        marker:             marker,
+                        initial_millis:     millis,
+                        millis_per_pixel:   (time_range.1 - time_range.0) / (GRAPH_PANEL_WIDTH - GRAPH_MARGIN*2.0)
+                    });
+                }
+            }
+        }
+
+        nearest
+    }
+
+    ///
+    /// Returns the curve that results from dragging a marker by a particular distance
+    ///
+    fn dragged_curve(drag: &GraphDrag, distance: f32) -> TimeCurve {
+        let millis = drag.initial_millis + distance * drag.millis_per_pixel;
+
+        match drag.marker {
+            TimingMarker::Point(index)              => drag.curve.with_point_time(index, millis),
+            TimingMarker::Handle(index, is_future)  => drag.curve.with_handle_time(index, is_future, millis)
+        }
+    }
+
+    ///
+    /// Sends a set of edits to the animation
+    ///
+    fn publish_edits(&self, edits: Vec<AnimationEdit>) {
+        if edits.len() == 0 {
+            return;
+        }
+
+        let _ = self.edit.future(move |animation| animation.publish(Arc::new(edits)));
+        self.edit.sync(|_| { });
+        self.timeline.invalidate_canvas();
+    }
+
+    ///
+    /// Applies an easing preset to all of the selected motions
+    ///
+    fn apply_easing(&self, easing: Easing) {
+        // Applying a preset replaces any preset that was applied earlier
+        let edits = self.selected_motions.get()
+            .iter()
+            .map(|(motion_id, curve)| AnimationEdit::Motion(*motion_id, MotionEdit::SetPath(curve.with_easing_preset(easing))))
+            .collect();
+
+        self.publish_edits(edits);
+    }
+}
+
+impl<Anim: 'static+Animation+EditableAnimation> Controller for GraphEditorController<Anim> {
+    fn ui(&self) -> BindRef<Control> {
+        BindRef::clone(&self.ui)
+    }
+
+    fn get_canvas_resources(&self) -> Option<Arc<ResourceManager<BindingCanvas>>> {
+        Some(Arc::clone(&self.canvases))
+    }
+
+    fn action(&self, action_id: &str, action_parameter: &ActionParameter) {
+        use self::ActionParameter::*;
+
+        match (action_id, action_parameter) {
+            (DRAG_GRAPH, &Drag(DragAction::Start, (start_x, start_y), _, _)) => {
+                self.drag.set(self.drag_at_position(start_x, start_y));
+                self.editing.set(None);
+            },
+
+            (DRAG_GRAPH, &Drag(DragAction::Drag, (start_x, _start_y), (x, _y), _)) => {
+                if let Some(drag) = self.drag.get() {
+                    self.editing.set(Some((drag.motion_id, Self::dragged_curve(&drag, x - start_x))));
+                }
+            },
+
+            (DRAG_GRAPH, &Drag(DragAction::Finish, (start_x, _start_y), (x, _y), _)) => {
+                if let Some(drag) = self.drag.get() {
+                    let new_curve = Self::dragged_curve(&drag, x - start_x);
+
+                    if new_curve != drag.curve {
+                        self.publish_edits(vec![AnimationEdit::Motion(drag.motion_id, MotionEdit::SetPath(new_curve))]);
+                    }
+                }
+
+                self.drag.set(None);
+                self.editing.set(None);
+            },
+
+            (DRAG_GRAPH, &Drag(DragAction::Cancel, _, _, _)) => {
+                self.drag.set(None);
+                self.editing.set(None);
+            },
+
+            _ => {
+                // 'Ease-x' applies easing preset 'x' to the selected motions
+                if action_id.starts_with("Ease-") {
+                    let (_, easing_index) = action_id.split_at("Ease-".len());
+
+                    if let Some(easing) = easing_index.parse::<usize>().ok().and_then(|index| Easing::all().get(index).cloned()) {
+                        self.apply_easing(easing);
+                    }
+                }
+            }
+        }
+    }
+}
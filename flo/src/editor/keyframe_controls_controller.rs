@@ -10,6 +10,7 @@ use ::desync::*;
 
 use std::sync::*;
 use std::time::Duration;
+use std::collections::HashSet;
 
 ///
 /// Provides the buttons for controlling the keyframes
@@ -62,12 +63,34 @@ impl<Anim: 'static+Animation+EditableAnimation> KeyFrameControlsController<Anim>
         let keyframe_selected       = frame.keyframe_selected.clone();
         let prev_next_1             = frame.previous_and_next_keyframe.clone();
         let prev_next_2             = frame.previous_and_next_keyframe.clone();
+        let prev_next_3             = frame.previous_and_next_keyframe.clone();
+        let selected_keyframes      = timeline.selected_keyframes.clone();
+        let tween_model             = model.clone();
+        let tween_update_count      = model.frame_update_count();
+        let tween_layer             = timeline.selected_layer.clone();
+        let tween_time              = timeline.current_time.clone();
+        let tween_keyframe_selected = frame.keyframe_selected.clone();
+        let tween_prev_next         = frame.previous_and_next_keyframe.clone();
 
         view_model.set_computed("CreateKeyFrameOnDrawSelected", move || PropertyValue::Bool(create_keyframe_on_draw.get()));
         view_model.set_computed("ShowOnionSkinsSelected",       move || PropertyValue::Bool(show_onion_skins.get()));
         view_model.set_computed("CanCreateKeyFrame",            move || PropertyValue::Bool(selected_layer.get().is_some() && !keyframe_selected.get()));
         view_model.set_computed("CanMoveToPreviousKeyFrame",    move || PropertyValue::Bool(prev_next_1.get().0.is_some()));
         view_model.set_computed("CanMoveToNextKeyFrame",        move || PropertyValue::Bool(prev_next_2.get().1.is_some()));
+        view_model.set_computed("CanDeleteKeyFrames",           move || PropertyValue::Bool(!selected_keyframes.get().is_empty()));
+        view_model.set_computed("CanTweenKeyFrame",             move || PropertyValue::Bool(prev_next_3.get().1.is_some()));
+        view_model.set_computed("TweenKeyFrameSelected",        move || {
+            // Update whenever the animation is edited
+            tween_update_count.get();
+
+            let key_frame = Self::current_key_frame(tween_keyframe_selected.get(), tween_time.get(), tween_prev_next.get());
+            let tweened   = match (tween_layer.get(), key_frame) {
+                (Some(layer_id), Some(key_frame))   => tween_model.get_layer_with_id(layer_id).and_then(|layer| layer.key_frame_tween(key_frame)).is_some(),
+                _                                   => false
+            };
+
+            PropertyValue::Bool(tweened)
+        });
 
         // The edit sink lets us send edits to the animation (in particular, the 'new keyframe' edits)
         let edit_sink       = model.edit();
@@ -100,6 +123,8 @@ impl<Anim: 'static+Animation+EditableAnimation> KeyFrameControlsController<Anim>
         let next_key_frame      = images.get_named_resource("next_key_frame").unwrap();
         let onion_skins         = images.get_named_resource("onion_skins").unwrap();
         let previous_key_frame  = images.get_named_resource("previous_key_frame").unwrap();
+        let delete_key_frame    = images.get_named_resource("delete_key_frame").unwrap();
+        let tween_key_frame     = images.get_named_resource("tween_key_frame").unwrap();
 
         // Get the parts of the model we want to use
 
@@ -110,6 +135,8 @@ impl<Anim: 'static+Animation+EditableAnimation> KeyFrameControlsController<Anim>
             let next_key_frame      = next_key_frame.clone();
             let onion_skins         = onion_skins.clone();
             let previous_key_frame  = previous_key_frame.clone();
+            let delete_key_frame    = delete_key_frame.clone();
+            let tween_key_frame     = tween_key_frame.clone();
 
             Control::container()
                 .with(vec![
@@ -143,6 +170,23 @@ impl<Anim: 'static+Animation+EditableAnimation> KeyFrameControlsController<Anim>
                                 .with((ActionTrigger::Click, "CreateKeyFrame"))
                                 .with(Bounds::next_horiz(22.0)),
 
+                            Control::button()
+                                .with(vec![Control::empty().with(delete_key_frame).with(TextAlign::Center).with(Bounds::fill_all())])
+                                .with(ControlAttribute::Padding((4, 4), (4, 4)))
+                                .with(State::Enabled(Property::bound("CanDeleteKeyFrames")))
+                                .with(Hover::Tooltip("Delete the selected keyframes".to_string()))
+                                .with((ActionTrigger::Click, "DeleteSelectedKeyFrames"))
+                                .with(Bounds::next_horiz(22.0)),
+
+                            Control::button()
+                                .with(vec![Control::empty().with(tween_key_frame).with(TextAlign::Center).with(Bounds::fill_all())])
+                                .with(ControlAttribute::Padding((4, 4), (4, 4)))
+                                .with(State::Selected(Property::bound("TweenKeyFrameSelected")))
+                                .with(State::Enabled(Property::bound("CanTweenKeyFrame")))
+                                .with(Hover::Tooltip("Tween this keyframe into the next keyframe".to_string()))
+                                .with((ActionTrigger::Click, "ToggleTweenKeyFrame"))
+                                .with(Bounds::next_horiz(22.0)),
+
                             Control::button()
                                 .with(vec![Control::empty().with(onion_skins).with(TextAlign::Center).with(Bounds::fill_all())])
                                 .with(ControlAttribute::Padding((4, 4), (4, 4)))
@@ -169,7 +213,7 @@ impl<Anim: 'static+Animation+EditableAnimation> KeyFrameControlsController<Anim>
                                 .with((ActionTrigger::Click, "MoveToNextKeyFrame"))
                                 .with(Bounds::next_horiz(22.0)),
                         ])
-                        .with(Bounds::next_horiz(22.0*5.0))
+                        .with(Bounds::next_horiz(22.0*7.0))
 
                 ])
                 .with(Bounds::fill_all())
@@ -190,15 +234,30 @@ impl<Anim: 'static+Animation+EditableAnimation> KeyFrameControlsController<Anim>
         let next_key_frame      = images.register(svg_static(include_bytes!("../../svg/keyframes/next_key_frame.svg")));
         let onion_skins         = images.register(svg_static(include_bytes!("../../svg/keyframes/onion_skins.svg")));
         let previous_key_frame  = images.register(svg_static(include_bytes!("../../svg/keyframes/previous_key_frame.svg")));
+        let delete_key_frame    = images.register(svg_static(include_bytes!("../../svg/keyframes/delete_key_frame.svg")));
+        let tween_key_frame     = images.register(svg_static(include_bytes!("../../svg/keyframes/tween_key_frame.svg")));
 
         images.assign_name(&new_key_frame,      "new_key_frame");
         images.assign_name(&new_on_paint,       "new_on_paint");
         images.assign_name(&next_key_frame,     "next_key_frame");
         images.assign_name(&onion_skins,        "onion_skins");
         images.assign_name(&previous_key_frame, "previous_key_frame");
+        images.assign_name(&delete_key_frame,   "delete_key_frame");
+        images.assign_name(&tween_key_frame,    "tween_key_frame");
 
         images
     }
+
+    ///
+    /// Returns the start time of the keyframe that's displayed at the current time, if there is one
+    ///
+    fn current_key_frame(keyframe_selected: bool, current_time: Duration, previous_and_next: (Option<Duration>, Option<Duration>)) -> Option<Duration> {
+        if keyframe_selected {
+            Some(current_time)
+        } else {
+            previous_and_next.0
+        }
+    }
 }
 
 impl<Anim: 'static+Animation+EditableAnimation> Controller for KeyFrameControlsController<Anim> {
@@ -272,6 +331,43 @@ impl<Anim: 'static+Animation+EditableAnimation> Controller for KeyFrameControlsC
                 }
             },
 
+            "ToggleTweenKeyFrame" => {
+                let selected_layer  = self.selected_layer.get();
+                let key_frame       = Self::current_key_frame(self.frame.keyframe_selected.get(), self.current_time.get(), self.frame.previous_and_next_keyframe.get());
+
+                if let (Some(selected_layer), Some(key_frame)) = (selected_layer, key_frame) {
+                    // Turn tweening off if it's on, or on with the default easing if it's off
+                    let tweened = self.debug_model.get_layer_with_id(selected_layer).and_then(|layer| layer.key_frame_tween(key_frame)).is_some();
+                    let easing  = if tweened { None } else { Some(TweenEasing::default()) };
+
+                    let _ = self.edit_sink.future(move |edit_sink| edit_sink.publish(Arc::new(vec![
+                        AnimationEdit::Layer(selected_layer, LayerEdit::SetKeyFrameTween(key_frame, easing))
+                    ])));
+                    self.edit_sink.sync(|_| { });
+
+                    self.timeline.invalidate_canvas();
+                }
+            },
+
+            "DeleteSelectedKeyFrames" => {
+                // Remove all of the keyframes that are selected in the timeline
+                let selected_keyframes  = self.timeline.selected_keyframes.get();
+                let mut selected        = selected_keyframes.iter().cloned().collect::<Vec<_>>();
+                selected.sort();
+
+                if !selected.is_empty() {
+                    let _ = self.edit_sink.future(move |edit_sink| edit_sink.publish(Arc::new(selected.into_iter()
+                        .map(|(layer_id, when)| AnimationEdit::Layer(layer_id, LayerEdit::RemoveKeyFrame(when)))
+                        .collect::<Vec<_>>())));
+                    self.edit_sink.sync(|_| { });
+
+                    // Clear the selection and update the timeline
+                    self.timeline.selected_keyframes.set(Arc::new(HashSet::new()));
+                    self.timeline.invalidate_canvas();
+                    self.timeline.update_keyframe_bindings();
+                }
+            },
+
             _ => { }
         }
     }
@@ -0,0 +1,434 @@
+use super::super::style::*;
+
+use flo_ui::*;
+use flo_canvas::{Color};
+use flo_binding::*;
+use flo_logging::*;
+
+use ::desync::*;
+use futures::future;
+
+use std::sync::*;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// The maximum number of messages that the log controller will remember
+const MAX_LOG_MESSAGES: usize = 1000;
+
+/// The maximum number of messages that are displayed at once (the most recent messages are shown)
+const MAX_VISIBLE_MESSAGES: usize = 200;
+
+/// The height of a row in the log
+const LOG_ROW_HEIGHT: f32 = 18.0;
+
+/// The height of the filter bar at the top of the log
+const LOG_FILTER_HEIGHT: f32 = 24.0;
+
+/// The width of the log panel when it's docked in the editor
+pub const LOG_PANEL_WIDTH: f32 = 400.0;
+
+/// The levels that can be chosen in the filter bar, along with their labels
+const LOG_LEVELS: [(Level, &str); 5] = [
+    (Level::Error,  "Error"),
+    (Level::Warn,   "Warn"),
+    (Level::Info,   "Info"),
+    (Level::Debug,  "Debug"),
+    (Level::Trace,  "Trace")
+];
+
+///
+/// The log controller displays the log messages generated by the application
+///
+/// This subscribes to the core log only while the log panel is visible. While it's subscribed, messages that would
+/// otherwise be sent to stderr are sent here instead.
+///
+pub struct LogController {
+    /// The UI for this controller
+    ui: BindRef<Control>,
+
+    /// The messages received so far, along with an identifier for each message (oldest first)
+    messages: Arc<Mutex<VecDeque<(u64, LogMsg)>>>,
+
+    /// Updated whenever the list of messages changes
+    messages_changed: Binding<u64>,
+
+    /// The least serious level of message to display
+    max_level: Binding<Level>,
+
+    /// The target to display messages for (or the empty string to display all targets)
+    target: Binding<String>,
+
+    /// The messages whose fields are being displayed
+    expanded: Binding<HashSet<u64>>,
+
+    /// The visible messages as JSON lines, along with the number of messages, once 'Copy' has been pressed
+    copy_json: Binding<Option<(usize, Arc<String>)>>,
+
+    /// Receives the log messages while the log panel is visible
+    _subscription: Arc<Desync<Option<Arc<Desync<u64>>>>>
+}
+
+impl LogController {
+    ///
+    /// Creates a new log controller
+    ///
+    pub fn new(show_logs: BindRef<bool>) -> LogController {
+        let messages            = Arc::new(Mutex::new(VecDeque::new()));
+        let messages_changed    = bind(0);
+        let max_level           = bind(Level::Info);
+        let target              = bind(String::new());
+        let expanded            = bind(HashSet::new());
+        let copy_json           = bind(None);
+
+        let subscription        = Self::subscribe_while_visible(show_logs, Arc::clone(&messages), messages_changed.clone());
+        let ui                  = Self::ui(Arc::clone(&messages), messages_changed.clone(), max_level.clone(), target.clone(), expanded.clone(), copy_json.clone());
+
+        LogController {
+            ui:                 ui,
+            messages:           messages,
+            messages_changed:   messages_changed,
+            max_level:          max_level,
+            target:             target,
+            expanded:           expanded,
+            copy_json:          copy_json,
+            _subscription:      subscription
+        }
+    }
+
+    ///
+    /// Subscribes to the log messages when the log panel is shown, and unsubscribes when it's hidden
+    ///
+    /// Subscribing to the log stops messages from going to the default sink (stderr), so this only happens while the messages
+    /// can be seen.
+    ///
+    fn subscribe_while_visible(show_logs: BindRef<bool>, messages: Arc<Mutex<VecDeque<(u64, LogMsg)>>>, messages_changed: Binding<u64>) -> Arc<Desync<Option<Arc<Desync<u64>>>>> {
+        let subscription = Arc::new(Desync::new(None));
+
+        pipe_in(Arc::clone(&subscription), follow(show_logs), move |receiver, is_visible| {
+            if !is_visible {
+                // Dropping the receiver ends the subscription
+                *receiver = None;
+            } else if receiver.is_none() {
+                *receiver = Some(Self::receive_messages(Arc::clone(&messages), messages_changed.clone()));
+            }
+
+            Box::pin(future::ready(()))
+        });
+
+        subscription
+    }
+
+    ///
+    /// Adds the messages from the core log to the list of messages as they arrive
+    ///
+    fn receive_messages(messages: Arc<Mutex<VecDeque<(u64, LogMsg)>>>, messages_changed: Binding<u64>) -> Arc<Desync<u64>> {
+        // Carry on numbering from the last message that was received
+        let next_id     = messages.lock().unwrap().back().map(|(id, _)| id+1).unwrap_or(0);
+        let receiver    = Arc::new(Desync::new(next_id));
+
+        pipe_in(Arc::clone(&receiver), subscribe_to_logs(), move |next_id, msg| {
+            // Assign an ID to the message
+            let id          = *next_id;
+            *next_id        += 1;
+
+            // Add to the end of the list of messages, removing the oldest messages if there are too many
+            {
+                let mut messages = messages.lock().unwrap();
+
+                messages.push_back((id, msg));
+                while messages.len() > MAX_LOG_MESSAGES {
+                    messages.pop_front();
+                }
+            }
+
+            Self::notify_changed(&messages_changed);
+
+            Box::pin(future::ready(()))
+        });
+
+        receiver
+    }
+
+    ///
+    /// Notifies the UI that the list of messages has changed
+    ///
+    fn notify_changed(messages_changed: &Binding<u64>) {
+        messages_changed.set(messages_changed.get().wrapping_add(1));
+    }
+
+    ///
+    /// Returns the colour to use for a message of a particular level
+    ///
+    fn level_color(level: Level) -> Color {
+        match level {
+            Level::Error    => LOG_ERROR,
+            Level::Warn     => LOG_WARNING,
+            Level::Info     => LOG_INFO,
+            Level::Debug    => LOG_DEBUG,
+            Level::Trace    => LOG_DEBUG
+        }
+    }
+
+    ///
+    /// Returns the most recent messages that match the filter, oldest first
+    ///
+    fn visible_messages<'a>(messages: &'a VecDeque<(u64, LogMsg)>, max_level: Level, target: &str) -> Vec<&'a (u64, LogMsg)> {
+        let mut filter  = LogFilter::new().with_max_level(max_level);
+        if !target.is_empty() {
+            filter = filter.with_target(target);
+        }
+
+        let mut visible = messages.iter()
+            .rev()
+            .filter(|(_, msg)| filter.matches(msg))
+            .take(MAX_VISIBLE_MESSAGES)
+            .collect::<Vec<_>>();
+        visible.reverse();
+
+        visible
+    }
+
+    ///
+    /// Creates the control that copies the visible messages
+    ///
+    /// Generating the JSON for every message is too slow to do whenever a message arrives, so pressing 'Copy' first
+    /// prepares the text, and the button then changes to one that copies it to the clipboard.
+    ///
+    fn copy_control(copy_json: &Option<(usize, Arc<String>)>) -> Control {
+        match copy_json {
+            None                    => {
+                Control::label()
+                    .with("Copy")
+                    .with(TextAlign::Center)
+                    .with((ActionTrigger::Click, "PrepareCopy"))
+                    .with(Bounds::next_horiz(60.0))
+            }
+
+            Some((count, json))     => {
+                Control::label()
+                    .with(format!("Copy {}", count))
+                    .with(TextAlign::Center)
+                    .with(Appearance::Background(LOG_FILTER_SELECTED))
+                    .with(Hint::CopyOnClick((**json).clone()))
+                    .with((ActionTrigger::Click, "FinishCopy"))
+                    .with(Bounds::next_horiz(60.0))
+            }
+        }
+    }
+
+    ///
+    /// Creates the filter bar for the log
+    ///
+    fn filter_bar(max_level: Level, target: &str, copy_json: &Option<(usize, Arc<String>)>) -> Control {
+        // One button for each level, followed by the target filter and the other actions
+        let mut controls = LOG_LEVELS.iter()
+            .map(|(level, label)| {
+                let background = if *level == max_level { LOG_FILTER_SELECTED } else { LOG_BACKGROUND };
+
+                Control::label()
+                    .with(*label)
+                    .with(TextAlign::Center)
+                    .with(Appearance::Background(background))
+                    .with(Appearance::Foreground(Self::level_color(*level)))
+                    .with((ActionTrigger::Click, format!("SetLevel-{}", label)))
+                    .with(Bounds::next_horiz(40.0))
+            })
+            .collect::<Vec<_>>();
+
+        controls.extend(vec![
+            Control::empty()
+                .with(Bounds::next_horiz(4.0)),
+            Control::text_box()
+                .with(target)
+                .with(Bounds::stretch_horiz(1.0))
+                .with((ActionTrigger::SetValue, "SetTarget")),
+            Control::empty()
+                .with(Bounds::next_horiz(4.0)),
+            Self::copy_control(copy_json),
+            Control::label()
+                .with("Clear")
+                .with(TextAlign::Center)
+                .with((ActionTrigger::Click, "Clear"))
+                .with(Bounds::next_horiz(40.0))
+        ]);
+
+        Control::container()
+            .with(Bounds::next_vert(LOG_FILTER_HEIGHT))
+            .with(ControlAttribute::Padding((4, 3), (4, 3)))
+            .with(controls)
+    }
+
+    ///
+    /// Creates the controls for a single log message
+    ///
+    fn message_controls(id: u64, msg: &LogMsg, is_expanded: bool) -> Vec<Control> {
+        let target      = msg.field_value("target").unwrap_or("");
+        let summary     = format!("{}: {}", target, msg.message());
+
+        let mut controls = vec![
+            Control::label()
+                .with(summary)
+                .with(TextAlign::Left)
+                .with(Appearance::Foreground(Self::level_color(msg.level())))
+                .with((ActionTrigger::Click, format!("Toggle-{}", id)))
+                .with(Bounds::next_vert(LOG_ROW_HEIGHT))
+        ];
+
+        if is_expanded {
+            // Expanded messages show their fields and can be copied
+            let mut fields = msg.fields();
+            fields.sort();
+
+            for (field_name, field_value) in fields {
+                controls.push(Control::label()
+                    .with(format!("    {} = {}", field_name, field_value))
+                    .with(TextAlign::Left)
+                    .with(Appearance::Background(LOG_EXPANDED_BACKGROUND))
+                    .with(Bounds::next_vert(LOG_ROW_HEIGHT)));
+            }
+
+            controls.push(Control::container()
+                .with(Appearance::Background(LOG_EXPANDED_BACKGROUND))
+                .with(Bounds::next_vert(LOG_ROW_HEIGHT))
+                .with(vec![
+                    Control::empty()
+                        .with(Bounds::stretch_horiz(1.0)),
+                    Control::label()
+                        .with("Copy")
+                        .with(TextAlign::Center)
+                        .with(Hint::CopyOnClick(msg.to_json(None)))
+                        .with(Bounds::next_horiz(40.0))
+                ]));
+        }
+
+        controls
+    }
+
+    ///
+    /// Creates the UI for this controller
+    ///
+    fn ui(messages: Arc<Mutex<VecDeque<(u64, LogMsg)>>>, messages_changed: Binding<u64>, max_level: Binding<Level>, target: Binding<String>, expanded: Binding<HashSet<u64>>, copy_json: Binding<Option<(usize, Arc<String>)>>) -> BindRef<Control> {
+        // The controls generated for each message are kept so that only new rows need to be created when a message arrives
+        let row_cache: Mutex<HashMap<(u64, bool), Vec<Control>>> = Mutex::new(HashMap::new());
+
+        let ui = computed(move || {
+            messages_changed.get();
+
+            let max_level   = max_level.get();
+            let target      = target.get();
+            let expanded    = expanded.get();
+            let copy_json   = copy_json.get();
+
+            // Generate the controls for the visible messages, re-using the rows that were generated last time
+            let mut new_cache           = HashMap::new();
+            let mut message_controls    = vec![];
+
+            {
+                let messages        = messages.lock().unwrap();
+                let mut row_cache   = row_cache.lock().unwrap();
+
+                for (id, msg) in Self::visible_messages(&messages, max_level, &target) {
+                    let key     = (*id, expanded.contains(id));
+                    let rows    = row_cache.remove(&key)
+                        .unwrap_or_else(|| Self::message_controls(*id, msg, key.1));
+
+                    message_controls.extend(rows.iter().cloned());
+                    new_cache.insert(key, rows);
+                }
+
+                // Rows for messages that are no longer visible are discarded
+                *row_cache = new_cache;
+            }
+
+            let height = (message_controls.len() as f32) * LOG_ROW_HEIGHT;
+
+            Control::container()
+                .with(Bounds::fill_all())
+                .with(Appearance::Background(LOG_BACKGROUND))
+                .with(Font::Size(11.0))
+                .with(vec![
+                    Self::filter_bar(max_level, &target, &copy_json),
+                    Control::empty()
+                        .with(Bounds::next_vert(1.0))
+                        .with(Appearance::Background(TIMESCALE_BORDER)),
+                    Control::scrolling_container()
+                        .with(Bounds::stretch_vert(1.0))
+                        .with(Scroll::MinimumContentSize(LOG_PANEL_WIDTH, height))
+                        .with(Scroll::VerticalScrollBar(ScrollBarVisibility::OnlyIfNeeded))
+                        .with(Scroll::HorizontalScrollBar(ScrollBarVisibility::Never))
+                        .with(ControlAttribute::Padding((4, 2), (4, 2)))
+                        .with(message_controls)
+                ])
+        });
+
+        BindRef::from(ui)
+    }
+}
+
+impl Controller for LogController {
+    fn ui(&self) -> BindRef<Control> {
+        BindRef::clone(&self.ui)
+    }
+
+    fn action(&self, action_id: &str, action_parameter: &ActionParameter) {
+        match action_id {
+            "SetTarget" => {
+                if let ActionParameter::Value(PropertyValue::String(new_target)) = action_parameter {
+                    self.target.set(new_target.trim().to_string());
+                    self.copy_json.set(None);
+                }
+            }
+
+            "Clear" => {
+                self.messages.lock().unwrap().clear();
+                self.expanded.set(HashSet::new());
+                self.copy_json.set(None);
+                Self::notify_changed(&self.messages_changed);
+            }
+
+            "PrepareCopy" => {
+                // The messages that are visible are copied as JSON lines
+                let json = {
+                    let messages    = self.messages.lock().unwrap();
+                    let visible     = Self::visible_messages(&messages, self.max_level.get(), &self.target.get());
+
+                    let json        = visible.iter()
+                        .map(|(_, msg)| msg.to_json(None))
+                        .collect::<Vec<_>>()
+                        .join("\n");
+
+                    (visible.len(), Arc::new(json))
+                };
+
+                self.copy_json.set(Some(json));
+            }
+
+            "FinishCopy" => {
+                self.copy_json.set(None);
+            }
+
+            _ => {
+                // 'SetLevel-x' should set the level filter, 'Toggle-x' should expand or collapse message 'x'
+                if action_id.starts_with("SetLevel-") {
+                    let (_, level_name) = action_id.split_at("SetLevel-".len());
+
+                    if let Some((level, _)) = LOG_LEVELS.iter().find(|(_, label)| *label == level_name) {
+                        self.max_level.set(*level);
+                        self.copy_json.set(None);
+                    }
+                } else if action_id.starts_with("Toggle-") {
+                    let (_, message_id) = action_id.split_at("Toggle-".len());
+
+                    if let Ok(message_id) = message_id.parse::<u64>() {
+                        let mut expanded = self.expanded.get();
+
+                        if !expanded.remove(&message_id) {
+                            expanded.insert(message_id);
+                        }
+
+                        self.expanded.set(expanded);
+                    }
+                }
+            }
+        }
+    }
+}
@@ -6,6 +6,10 @@ use super::super::tools::*;
 use flo_ui::*;
 use flo_binding::*;
 use flo_animation::*;
+use flo_animation::storage::*;
+use ::desync::*;
+
+use futures::prelude::*;
 
 use std::sync::*;
 use std::collections::HashMap;
@@ -18,7 +22,10 @@ pub struct MenuController<Anim: Animation> {
     ui:                 BindRef<Control>,
     tool_controllers:   Mutex<HashMap<String, Arc<dyn Controller>>>,
 
-    empty_menu:         Arc<EmptyMenuController>
+    empty_menu:         Arc<EmptyMenuController>,
+
+    /// Queue used to compact the edit log for the animation in the background
+    compact_queue:      Desync<()>
 }
 
 impl<Anim: 'static+Animation> MenuController<Anim> {
@@ -38,7 +45,9 @@ impl<Anim: 'static+Animation> MenuController<Anim> {
             ui:                 BindRef::from(ui),
             tool_controllers:   Mutex::new(HashMap::new()),
 
-            empty_menu:         empty_menu
+            empty_menu:         empty_menu,
+
+            compact_queue:      Desync::new(())
         }
     }
 
@@ -71,6 +80,23 @@ impl<Anim: 'static+Animation> MenuController<Anim> {
                         .with(Bounds::stretch_horiz(1.0))
                         .with(Font::Size(12.0))
                         .with_controller(&tool_controller),
+
+                    Control::button()
+                        .with(vec![Control::label().with("Save").with(TextAlign::Center).with(Bounds::fill_all())])
+                        .with(Font::Size(12.0))
+                        .with(Hover::Tooltip("Save the animation, compacting its edit history".to_string()))
+                        .with((ActionTrigger::Click, "SaveAndCompact"))
+                        .with(Bounds::next_horiz(48.0)),
+                    Control::empty()
+                        .with(Bounds::next_horiz(4.0)),
+                    Control::button()
+                        .with(vec![Control::label().with("Save version").with(TextAlign::Center).with(Bounds::fill_all())])
+                        .with(Font::Size(12.0))
+                        .with(Hover::Tooltip("Save a named version of the animation that can be reopened from the file chooser".to_string()))
+                        .with((ActionTrigger::Click, "SaveVersion"))
+                        .with(Bounds::next_horiz(96.0)),
+                    Control::empty()
+                        .with(Bounds::next_horiz(6.0)),
                 ])
                 .with(Appearance::Background(MENU_BACKGROUND))
         }))
@@ -94,6 +120,38 @@ impl<Anim: EditableAnimation+Animation+'static> Controller for MenuController<An
         BindRef::clone(&self.ui)
     }
 
+    fn action(&self, action_id: &str, _action_parameter: &ActionParameter) {
+        match action_id {
+            "SaveAndCompact" => {
+                // Edits are written as they're made, so saving just needs to rewrite the edit log into its most compact form
+                let animation = Arc::clone(&self.anim_model);
+
+                let _ = self.compact_queue.future(move |_| {
+                    async move {
+                        compact_animation_edit_log(&*animation, vec![]).await;
+                    }.boxed()
+                });
+            }
+
+            "SaveVersion" => {
+                // Versions are named snapshots of the edit log (queued so they're created after any compaction that's in progress)
+                let animation = Arc::clone(&self.anim_model);
+
+                self.compact_queue.desync(move |_| {
+                    let snapshots           = animation.get_snapshots();
+                    let mut version_num     = snapshots.len() + 1;
+                    while snapshots.iter().any(|snapshot| snapshot.name == format!("Version {}", version_num)) {
+                        version_num += 1;
+                    }
+
+                    animation.create_snapshot(&format!("Version {}", version_num));
+                });
+            }
+
+            _ => { }
+        }
+    }
+
     fn get_subcontroller(&self, id: &str) -> Option<Arc<dyn Controller>> {
         use std::collections::hash_map::Entry::*;
 
@@ -8,9 +8,13 @@ mod controlbar_controller;
 mod frame_controls_controller;
 mod keyframe_controls_controller;
 mod toolbox_controller;
+mod log_controller;
+mod graph_editor_controller;
 
 pub use self::editor_controller::*;
 pub use self::canvas_controller::*;
 pub use self::menu_controller::*;
 pub use self::timeline_controller::*;
 pub use self::toolbox_controller::*;
+pub use self::log_controller::*;
+pub use self::graph_editor_controller::*;
@@ -5,12 +5,15 @@ use super::super::model::*;
 
 use flo_ui::*;
 use flo_canvas::*;
+use flo_stream::*;
 use flo_binding::*;
 use flo_animation::*;
 
+use ::desync::*;
+
 use std::sync::*;
 use std::time::Duration;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 /// Side of the onion skin indicator
 enum Onion {
@@ -30,6 +33,9 @@ const DRAG_ONION_FRAMES_AFTER: &str = "DragOnionAfter";
 /// Action when the user clicks/drags on the scale away from the 'time' indicator
 const CLICK_AND_DRAG_TIMELINE_POSITION: &str = "ClickTime";
 
+/// Action when the user clicks/drags on the keyframes
+const DRAG_KEYFRAMES: &str = "DragKeyFrames";
+
 /// Action when the virtual scroll position changes
 const SCROLL_TIMELINE: &str     = "Scroll";
 
@@ -76,6 +82,15 @@ pub struct TimelineController<Anim: Animation> {
     /// The setting of frames_before/frames_after when the drag on the onion skin start/end indicators started
     drag_start_frames:          Binding<usize>,
 
+    /// The keyframe (layer ID and time) where the current keyframe drag started, and whether or not it was already selected
+    drag_keyframe:              Binding<Option<((u64, Duration), bool)>>,
+
+    /// The number of frames the selected keyframes have been dragged by
+    keyframe_drag_offset:       Binding<i64>,
+
+    /// The animation editing stream where this will send keyframe updates
+    edit:                       Desync<Publisher<Arc<Vec<AnimationEdit>>>>,
+
     /// A virtual control that draws the timeline scale
     virtual_scale:              VirtualCanvas,
 
@@ -106,7 +121,8 @@ impl<Anim: 'static+Animation+EditableAnimation> TimelineController<Anim> {
         let virtual_scale = VirtualCanvas::new(Arc::clone(&canvases), Self::draw_scale);
 
         // This draws the keyframes
-        let create_keyframe_canvas  = Self::create_draw_keyframes_fn(anim_model.timeline());
+        let keyframe_drag_offset    = bind(0);
+        let create_keyframe_canvas  = Self::create_draw_keyframes_fn(anim_model.timeline(), BindRef::new(&keyframe_drag_offset));
         let virtual_keyframes       = VirtualCanvas::new(Arc::clone(&canvases), move |x, y| (create_keyframe_canvas)(x, y));
 
         // Viewmodel specifies a few dynamic things
@@ -166,7 +182,7 @@ impl<Anim: 'static+Animation+EditableAnimation> TimelineController<Anim> {
 
         let duration                    = BindRef::new(&anim_model.timeline().duration);
         let frame_duration              = BindRef::new(&anim_model.timeline().frame_duration);
-        let layers                      = BindRef::new(&anim_model.timeline().layers);
+        let layers                      = BindRef::new(&anim_model.timeline().visible_layers);
 
         let virtual_scale_control       = virtual_scale.control();
         let virtual_keyframes_control   = virtual_keyframes.control();
@@ -181,6 +197,9 @@ impl<Anim: 'static+Animation+EditableAnimation> TimelineController<Anim> {
             virtual_keyframes:          virtual_keyframes,
             drag_start_time:            bind(Duration::from_millis(0)),
             drag_start_frames:          bind(0),
+            drag_keyframe:              bind(None),
+            keyframe_drag_offset:       keyframe_drag_offset,
+            edit:                       Desync::new(anim_model.edit()),
             canvases:                   canvases,
             layer_list_controller:      Arc::new(layer_list_controller),
             layer_controls_controller:  Arc::new(layer_controls_controller),
@@ -331,6 +350,7 @@ impl<Anim: 'static+Animation+EditableAnimation> TimelineController<Anim> {
                         .with(vec![
                             virtual_keyframes_control.get()
                         ])
+                        .with((ActionTrigger::Drag, DRAG_KEYFRAMES))
                         .with(ControlAttribute::ZIndex(2)),
                     Control::canvas()           // Selected frame indicator (upper part, arrow indicator)
                         .with(timescale_indicator)
@@ -361,7 +381,9 @@ impl<Anim: 'static+Animation+EditableAnimation> TimelineController<Anim> {
     ///
     /// Creates the function for drawing the keyframes
     ///
-    fn create_draw_keyframes_fn(timeline: &TimelineModel<Anim>) -> impl Fn(f32, f32) -> Box<dyn Fn(&mut dyn GraphicsPrimitives) -> ()+Send+Sync>+Send+Sync {
+    /// Selected keyframes are highlighted, and while they're being dragged, an outline is drawn where they'll end up
+    ///
+    fn create_draw_keyframes_fn(timeline: &TimelineModel<Anim>, drag_offset: BindRef<i64>) -> impl Fn(f32, f32) -> Box<dyn Fn(&mut dyn GraphicsPrimitives) -> ()+Send+Sync>+Send+Sync {
         let timeline    = timeline.clone();
 
         move |x, y| {
@@ -376,12 +398,17 @@ impl<Anim: 'static+Animation+EditableAnimation> TimelineController<Anim> {
             let start_tick  = start_tick.max(0.0) as u32;
             let end_tick    = end_tick.max(0.0) as u32;
             let keyframes   = timeline.get_keyframe_binding(start_tick..end_tick);
-            let layers      = BindRef::new(&timeline.layers);
+            let layers      = BindRef::new(&timeline.visible_layers);
+            let selected    = BindRef::new(&timeline.selected_keyframes);
+            let frame_len   = BindRef::new(&timeline.frame_duration);
+            let drag_offset = drag_offset.clone();
 
             // Generate the drawing function for this part of the canvas
             Box::new(move |gc| {
                 let layers      = layers.get();
                 let keyframes   = keyframes.get();
+                let selected    = selected.get();
+                let drag_offset = drag_offset.get();
 
                 let last_layer  = last_layer.min(layers.len());
                 let end_tick    = end_tick;
@@ -428,7 +455,6 @@ impl<Anim: 'static+Animation+EditableAnimation> TimelineController<Anim> {
                 gc.stroke();
 
                 // Draw the keyframes that are in this region
-                gc.fill_color(TIMESCALE_KEYFRAME);
                 for keyframe in keyframes.iter() {
                     // Fetch where this frame occurs
                     let frame       = keyframe.frame;
@@ -444,12 +470,43 @@ impl<Anim: 'static+Animation+EditableAnimation> TimelineController<Anim> {
                             let ypos = (*layer_index as f32) * TIMELINE_LAYER_HEIGHT;
 
                             // Draw the frame marker
+                            if selected.contains(&(layer_id, keyframe.when)) {
+                                gc.fill_color(TIMESCALE_KEYFRAME_SELECTED);
+                            } else {
+                                gc.fill_color(TIMESCALE_KEYFRAME);
+                            }
+
                             gc.new_path();
                             gc.circle(xpos + TICK_LENGTH/2.0, ypos + TIMELINE_LAYER_HEIGHT/2.0, TICK_LENGTH/2.0 - 0.5);
                             gc.fill();
                         }
                     }
                 }
+
+                // Outline where the selected keyframes will be moved to if they're being dragged
+                if drag_offset != 0 {
+                    let frame_duration_ns = Self::duration_to_ns(frame_len.get());
+
+                    gc.stroke_color(TIMESCALE_KEYFRAME_SELECTED);
+                    gc.line_width(1.0);
+
+                    for (layer_id, when) in selected.iter() {
+                        let frame = (Self::duration_to_ns(*when) + frame_duration_ns/2) / frame_duration_ns;
+                        let frame = frame + drag_offset;
+
+                        if let Some(layer_index) = index_for_layer.get(layer_id) {
+                            if frame >= (start_tick as i64) && frame < (end_tick as i64) && layer_index >= &first_layer && layer_index < &last_layer {
+                                let xpos = (frame as f32) * TICK_LENGTH;
+                                let xpos = xpos + LAYER_PANEL_WIDTH;
+                                let ypos = (*layer_index as f32) * TIMELINE_LAYER_HEIGHT;
+
+                                gc.new_path();
+                                gc.circle(xpos + TICK_LENGTH/2.0, ypos + TIMELINE_LAYER_HEIGHT/2.0, TICK_LENGTH/2.0 - 1.0);
+                                gc.stroke();
+                            }
+                        }
+                    }
+                }
             })
         }
     }
@@ -651,6 +708,116 @@ impl<Anim: 'static+Animation+EditableAnimation> TimelineController<Anim> {
 
         time_ns
     }
+
+    ///
+    /// Finds the keyframe (as a layer ID and a time) at a position in the keyframes area of the timeline
+    ///
+    fn keyframe_at_position(&self, xpos: f32, ypos: f32) -> Option<(u64, Duration)> {
+        let timeline    = self.anim_model.timeline();
+
+        // Work out the frame and layer that the position is in
+        let frame       = ((xpos - LAYER_PANEL_WIDTH) / TICK_LENGTH).floor();
+        let layer_index = (ypos / TIMELINE_LAYER_HEIGHT).floor();
+
+        if frame < 0.0 || layer_index < 0.0 { return None; }
+
+        let frame       = frame as u32;
+        let layers      = timeline.visible_layers.get();
+        let layer_id    = layers.get(layer_index as usize)?.id;
+
+        // Search for a keyframe on this frame
+        let keyframes   = timeline.get_keyframe_binding(frame..(frame+1)).get();
+
+        keyframes.into_iter()
+            .find(|keyframe| keyframe.layer_id == layer_id && keyframe.frame == frame)
+            .map(|keyframe| (keyframe.layer_id, keyframe.when))
+    }
+
+    ///
+    /// Returns the start times of the keyframes in a layer that are between the earliest and latest of a set of times
+    ///
+    fn keyframe_times<TimeIter: Iterator<Item=Duration>>(&self, layer_id: u64, times: TimeIter) -> HashSet<Duration> {
+        let times = times.collect::<Vec<_>>();
+        let start = times.iter().min().cloned().unwrap_or(Duration::from_millis(0));
+        let end   = times.iter().max().cloned().unwrap_or(Duration::from_millis(0)) + Duration::from_micros(1);
+
+        self.anim_model.get_layer_with_id(layer_id)
+            .map(|layer| layer.get_key_frames_during_time(start..end).filter(|when| *when >= start).collect())
+            .unwrap_or_else(|| HashSet::new())
+    }
+
+    ///
+    /// Moves (or copies, if `copy_keyframes` is true) the selected keyframes by a number of frames
+    ///
+    fn move_selected_keyframes(&self, frame_offset: i64, copy_keyframes: bool) {
+        let timeline            = self.anim_model.timeline();
+        let offset_ns           = frame_offset * Self::duration_to_ns(timeline.frame_duration.get());
+
+        // Move the keyframes furthest in the direction of travel first so that they don't collide with each other
+        let mut selected        = timeline.selected_keyframes.get().iter().cloned().collect::<Vec<_>>();
+        selected.sort_by_key(|(layer_id, when)| (*when, *layer_id));
+        if frame_offset > 0 { selected.reverse(); }
+
+        // Keyframes can't be moved to before the start of the animation
+        let moves               = selected.into_iter()
+            .filter_map(|(layer_id, when)| {
+                let new_time_ns = Self::duration_to_ns(when) + offset_ns;
+
+                if new_time_ns < 0 {
+                    None
+                } else {
+                    Some((layer_id, when, Self::ns_to_duration(new_time_ns)))
+                }
+            })
+            .collect::<Vec<_>>();
+
+        // The animation won't move a keyframe on top of another one, so work out which of the keyframes will actually move
+        let mut move_times      = HashMap::new();
+        moves.iter().for_each(|(layer_id, from, to)| move_times.entry(*layer_id).or_insert_with(|| vec![]).extend(vec![*from, *to]));
+
+        let mut keyframe_times  = move_times.into_iter()
+            .map(|(layer_id, times)| (layer_id, self.keyframe_times(layer_id, times.into_iter())))
+            .collect::<HashMap<_, _>>();
+        let moves               = moves.into_iter()
+            .filter(|(layer_id, from, to)| {
+                let times = keyframe_times.get_mut(layer_id).unwrap();
+
+                if !times.contains(from) || times.contains(to) {
+                    false
+                } else {
+                    if !copy_keyframes { times.remove(from); }
+                    times.insert(*to);
+                    true
+                }
+            })
+            .collect::<Vec<_>>();
+
+        if moves.is_empty() { return; }
+
+        // Send the edits to the animation
+        let edits = moves.iter()
+            .map(|(layer_id, from, to)| {
+                if copy_keyframes {
+                    AnimationEdit::Layer(*layer_id, LayerEdit::DuplicateKeyFrame(*from, *to))
+                } else {
+                    AnimationEdit::Layer(*layer_id, LayerEdit::MoveKeyFrame(*from, *to))
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let _ = self.edit.future(move |edit| edit.publish(Arc::new(edits)));
+        self.edit.sync(|_| { });
+
+        // The keyframes at their new positions replace the keyframes that were moved in the selection
+        let mut selected = (*timeline.selected_keyframes.get()).clone();
+        moves.iter().for_each(|(layer_id, from, _)| { selected.remove(&(*layer_id, *from)); });
+        moves.iter().for_each(|(layer_id, _, to)| { selected.insert((*layer_id, *to)); });
+        timeline.selected_keyframes.set(Arc::new(selected));
+
+        // Update the model
+        timeline.update_keyframe_bindings();
+        timeline.invalidate_canvas();
+    }
 }
 
 impl<Anim: EditableAnimation+Animation+'static> Controller for TimelineController<Anim> {
@@ -674,7 +841,7 @@ impl<Anim: EditableAnimation+Animation+'static> Controller for TimelineControlle
                 self.virtual_keyframes.virtual_scroll((VIRTUAL_WIDTH, VIRTUAL_HEIGHT), (virtual_x, y), (width+2, height));
             },
 
-            (CLICK_AND_DRAG_TIMELINE_POSITION, &Drag(DragAction::Start, (start_x, _start_y), _)) => {
+            (CLICK_AND_DRAG_TIMELINE_POSITION, &Drag(DragAction::Start, (start_x, _start_y), _, _)) => {
                 // Clicking on the scale moves the time to where the user clicked initially
                 let time_ns = self.xpos_to_ns(start_x - LAYER_PANEL_WIDTH - (TICK_LENGTH/2.0));
                 let time    = Self::ns_to_duration(time_ns);
@@ -683,13 +850,13 @@ impl<Anim: EditableAnimation+Animation+'static> Controller for TimelineControlle
                 self.drag_start_time.set(time);
             },
 
-            (DRAG_TIMELINE_POSITION, &Drag(DragAction::Start, _, _)) => {
+            (DRAG_TIMELINE_POSITION, &Drag(DragAction::Start, _, _, _)) => {
                 // Remember the start time when a drag begins
                 self.drag_start_time.set(self.anim_model.timeline().current_time.get());
             },
 
-            (DRAG_TIMELINE_POSITION, &Drag(_drag_type, (start_x, _start_y), (x, _y)))
-            | (CLICK_AND_DRAG_TIMELINE_POSITION, &Drag(_drag_type, (start_x, _start_y), (x, _y))) => {
+            (DRAG_TIMELINE_POSITION, &Drag(_drag_type, (start_x, _start_y), (x, _y), _))
+            | (CLICK_AND_DRAG_TIMELINE_POSITION, &Drag(_drag_type, (start_x, _start_y), (x, _y), _)) => {
                 // Get the frame duration and start time in nanoseconds
                 let timeline            = self.anim_model.timeline();
                 let start_time          = self.drag_start_time.get();
@@ -707,16 +874,68 @@ impl<Anim: EditableAnimation+Animation+'static> Controller for TimelineControlle
                 timeline.current_time.set(new_time);
             },
 
-            (DRAG_ONION_FRAMES_AFTER, &Drag(DragAction::Start, _, _)) => {
+            (DRAG_KEYFRAMES, &Drag(DragAction::Start, (start_x, start_y), _, _)) => {
+                // Clicking on a keyframe adds it to the selection, and clicking anywhere else clears the selection
+                let keyframe            = self.keyframe_at_position(start_x, start_y);
+                let selected_keyframes  = self.anim_model.timeline().selected_keyframes.clone();
+
+                if let Some(keyframe) = keyframe {
+                    let mut selected    = (*selected_keyframes.get()).clone();
+                    let was_selected    = !selected.insert(keyframe);
+
+                    selected_keyframes.set(Arc::new(selected));
+                    self.drag_keyframe.set(Some((keyframe, was_selected)));
+                } else {
+                    selected_keyframes.set(Arc::new(HashSet::new()));
+                    self.drag_keyframe.set(None);
+                }
+
+                self.keyframe_drag_offset.set(0);
+            },
+
+            (DRAG_KEYFRAMES, &Drag(DragAction::Drag, (start_x, _start_y), (x, _y), _)) => {
+                // Dragging moves the selected keyframes by whole frames
+                if self.drag_keyframe.get().is_some() {
+                    self.keyframe_drag_offset.set(((x - start_x) / TICK_LENGTH).round() as i64);
+                }
+            },
+
+            (DRAG_KEYFRAMES, &Drag(DragAction::Finish, (start_x, _start_y), (x, _y), modifiers)) => {
+                if let Some((keyframe, was_selected)) = self.drag_keyframe.get() {
+                    let frame_offset = ((x - start_x) / TICK_LENGTH).round() as i64;
+
+                    if frame_offset != 0 {
+                        // Move the keyframes to their new location (alt-dragging copies them instead)
+                        self.move_selected_keyframes(frame_offset, modifiers.alt);
+                    } else if was_selected {
+                        // Clicking on an already selected keyframe deselects it
+                        let selected_keyframes  = self.anim_model.timeline().selected_keyframes.clone();
+                        let mut selected        = (*selected_keyframes.get()).clone();
+                        selected.remove(&keyframe);
+
+                        selected_keyframes.set(Arc::new(selected));
+                    }
+                }
+
+                self.drag_keyframe.set(None);
+                self.keyframe_drag_offset.set(0);
+            },
+
+            (DRAG_KEYFRAMES, &Drag(DragAction::Cancel, _, _, _)) => {
+                self.drag_keyframe.set(None);
+                self.keyframe_drag_offset.set(0);
+            },
+
+            (DRAG_ONION_FRAMES_AFTER, &Drag(DragAction::Start, _, _, _)) => {
                 self.drag_start_frames.set(self.anim_model.onion_skin().frames_after.get());
             },
 
-            (DRAG_ONION_FRAMES_BEFORE, &Drag(DragAction::Start, _, _)) => {
+            (DRAG_ONION_FRAMES_BEFORE, &Drag(DragAction::Start, _, _, _)) => {
                 self.drag_start_frames.set(self.anim_model.onion_skin().frames_before.get());
             },
 
-            (DRAG_ONION_FRAMES_AFTER, &Drag(_drag_type, (start_x, _start_y), (x, _y)))
-            | (DRAG_ONION_FRAMES_BEFORE, &Drag(_drag_type, (start_x, _start_y), (x, _y))) => {
+            (DRAG_ONION_FRAMES_AFTER, &Drag(_drag_type, (start_x, _start_y), (x, _y), _))
+            | (DRAG_ONION_FRAMES_BEFORE, &Drag(_drag_type, (start_x, _start_y), (x, _y), _)) => {
                 // Work out the difference in frames
                 let is_before       = action_id == DRAG_ONION_FRAMES_BEFORE;
                 let initial_frames  = self.drag_start_frames.get();
@@ -63,8 +63,28 @@ impl<Anim: 'static+Animation+EditableAnimation> TimelineLayerControlsController<
                                 .with(Bounds::stretch_horiz(1.0)),
                             Control::container()
                                 .with(Hint::Class("button-group".to_string()))
-                                .with(Bounds::next_horiz(36.0))
+                                .with(Bounds::next_horiz(72.0))
                                 .with(vec![
+                                    Control::button()
+                                        .with(Bounds::next_horiz(18.0))
+                                        .with((ActionTrigger::Click, "AddReferenceLayer"))
+                                        .with(Hover::Tooltip("Add a new reference layer for displaying images".to_string()))
+                                        .with(vec![
+                                            Control::label()
+                                                .with(Bounds::fill_all())
+                                                .with(TextAlign::Center)
+                                                .with("\u{25a7}")
+                                        ]),
+                                    Control::button()
+                                        .with(Bounds::next_horiz(18.0))
+                                        .with((ActionTrigger::Click, "AddNewLayerFolder"))
+                                        .with(Hover::Tooltip("Add a new layer folder".to_string()))
+                                        .with(vec![
+                                            Control::label()
+                                                .with(Bounds::fill_all())
+                                                .with(TextAlign::Center)
+                                                .with("\u{25a4}")
+                                        ]),
                                     Control::button()
                                         .with(Bounds::next_horiz(18.0))
                                         .with((ActionTrigger::Click, "AddNewLayer"))
@@ -95,6 +115,19 @@ impl<Anim: 'static+Animation+EditableAnimation> TimelineLayerControlsController<
         // Turn into a bindref
         BindRef::from(ui)
     }
+
+    ///
+    /// The folder that a new layer should be created in (the selected folder, or the folder containing the selected layer)
+    ///
+    fn folder_for_new_layer(&self) -> Option<u64> {
+        let selected_layer_id = self.timeline.selected_layer.get()?;
+
+        self.timeline.layers.get()
+            .into_iter()
+            .filter(|layer| layer.id == selected_layer_id)
+            .nth(0)
+            .and_then(|layer| if layer.is_folder { Some(layer.id) } else { layer.parent })
+    }
 }
 
 impl<Anim: 'static+Animation+EditableAnimation> Controller for TimelineLayerControlsController<Anim> {
@@ -104,16 +137,33 @@ impl<Anim: 'static+Animation+EditableAnimation> Controller for TimelineLayerCont
 
     fn action(&self, action_id: &str, _action_parameter: &ActionParameter) {
         match action_id {
-            "AddNewLayer" => {
-                // Pick a layer ID for the new layer
-                let new_layer_id = self.animation.get_layer_ids().into_iter().max().unwrap_or(0) + 1;
+            "AddNewLayer" | "AddReferenceLayer" => {
+                // Pick a layer ID for the new layer (symbol layers share IDs with the main animation)
+                let new_layer_id    = self.timeline.unused_layer_id();
+                let is_reference    = action_id == "AddReferenceLayer";
+                let name            = if is_reference { format!("Reference {}", new_layer_id+1) } else { format!("Layer {}", new_layer_id+1) };
+
+                // The layer is added to the symbol if one is being edited
+                let add_layer       = match self.timeline.editing_symbol.get() {
+                    None            => AnimationEdit::AddNewLayer(new_layer_id),
+                    Some(symbol_id) => AnimationEdit::Symbol(symbol_id, SymbolEdit::AddNewLayer(new_layer_id))
+                };
+
+                // New layers go in the same folder as the selected layer
+                let mut edits       = vec![
+                    add_layer,
+                    AnimationEdit::Layer(new_layer_id, LayerEdit::SetName(name))
+                ];
+                edits.extend(self.folder_for_new_layer().map(|folder_id| AnimationEdit::Layer(new_layer_id, LayerEdit::SetParent(Some(folder_id)))));
+
+                // Reference layers display images imported from the layer list instead of vector content
+                if is_reference {
+                    edits.push(AnimationEdit::Layer(new_layer_id, LayerEdit::SetReference(Some(ReferenceLayerSettings::default()))));
+                }
 
                 // Send to the animation
                 let _ = self.edit.future(move |animation| {
-                    animation.publish(Arc::new(vec![
-                        AnimationEdit::AddNewLayer(new_layer_id),
-                        AnimationEdit::Layer(new_layer_id, LayerEdit::SetName(format!("Layer {}", new_layer_id+1)))
-                    ]))
+                    animation.publish(Arc::new(edits))
                 });
                 self.edit.sync(|_| {});
 
@@ -125,12 +175,35 @@ impl<Anim: 'static+Animation+EditableAnimation> Controller for TimelineLayerCont
                 self.timeline.invalidate_canvas();
             },
 
+            "AddNewLayerFolder" => {
+                // Folders can only be added to the main animation
+                if self.timeline.editing_symbol.get().is_some() {
+                    return;
+                }
+
+                let new_folder_id   = self.timeline.unused_layer_id();
+                let mut edits       = vec![
+                    AnimationEdit::AddNewLayerFolder(new_folder_id),
+                    AnimationEdit::Layer(new_folder_id, LayerEdit::SetName(format!("Folder {}", new_folder_id+1)))
+                ];
+                edits.extend(self.folder_for_new_layer().map(|folder_id| AnimationEdit::Layer(new_folder_id, LayerEdit::SetParent(Some(folder_id)))));
+
+                // Send to the animation
+                let _ = self.edit.future(move |animation| {
+                    animation.publish(Arc::new(edits))
+                });
+                self.edit.sync(|_| {});
+
+                // Select the new folder
+                self.timeline.selected_layer.set(Some(new_folder_id));
+            },
+
             "RemoveLayer" => {
                 // This will remove the selected layer
                 let layer_to_remove = self.timeline.selected_layer.get();
 
                 // Check that the layer actually exists
-                let layer_ids = self.animation.get_layer_ids();
+                let layer_ids = timeline_layer_ids(&*self.animation, self.timeline.editing_symbol.get());
                 if layer_ids.iter().any(|layer_id| Some(*layer_id) == layer_to_remove) {
                     let layer_to_remove = layer_to_remove.unwrap();
 
@@ -6,10 +6,20 @@ use flo_ui::*;
 use flo_stream::*;
 use flo_binding::*;
 use flo_animation::*;
+use flo_logging::*;
 
 use ::desync::*;
 
+use std::fs;
 use std::sync::*;
+use std::time::{Duration};
+
+/// The amount each level of folders is indented by in the layer list
+const LAYER_INDENT: f32 = 10.0;
+
+lazy_static! {
+    static ref LAYER_LIST_LOG: LogPublisher = LogPublisher::new(module_path!());
+}
 
 ///
 /// Controller class that displays and edits the layer names
@@ -21,11 +31,26 @@ pub struct TimelineLayerListController {
     /// Where the edits are sent
     edit_sink: Desync<Publisher<Arc<Vec<AnimationEdit>>>>,
 
+    /// The animation being edited (used to store the images imported into reference layers)
+    animation: Arc<dyn EditableAnimation>,
+
+    /// The current time in the timeline (imported reference images are displayed from this time)
+    current_time: BindRef<Duration>,
+
     /// The currently selected layer
     selected_layer_id: Binding<Option<u64>>,
 
     /// The layer whose name is being edited
-    editing_layer_id: Binding<Option<u64>>
+    editing_layer_id: Binding<Option<u64>>,
+
+    /// The reference layer that an image is being imported into
+    importing_layer_id: Binding<Option<u64>>,
+
+    /// All of the layers in the timeline
+    layers: BindRef<Vec<LayerModel>>,
+
+    /// The layers that have a row in the layer list
+    visible_layers: BindRef<Vec<LayerModel>>
 }
 
 impl TimelineLayerListController {
@@ -36,39 +61,96 @@ impl TimelineLayerListController {
         // Create the UI from the model
         let selected_layer_id   = model.timeline().selected_layer.clone();
         let editing_layer_id    = bind(None);
-        let ui                  = Self::ui(model, BindRef::from(editing_layer_id.clone()));
+        let importing_layer_id  = bind(None);
+        let ui                  = Self::ui(model, BindRef::from(editing_layer_id.clone()), BindRef::from(importing_layer_id.clone()));
 
         let edit_sink           = model.edit();
+        let animation           = Arc::new(model.clone());
+        let current_time        = BindRef::from(model.timeline().current_time.clone());
+        let layers              = model.timeline().layers.clone();
+        let visible_layers      = model.timeline().visible_layers.clone();
 
         TimelineLayerListController {
             ui:                 ui,
             edit_sink:          Desync::new(edit_sink),
+            animation:          animation,
+            current_time:       current_time,
             selected_layer_id:  selected_layer_id,
-            editing_layer_id:   editing_layer_id
+            editing_layer_id:   editing_layer_id,
+            importing_layer_id: importing_layer_id,
+            layers:             layers,
+            visible_layers:     visible_layers
         }
     }
 
     ///
     /// Creates a control from a layer model
     ///
-    fn layer_label(model: &LayerModel, selected_layer_id: Option<u64>, editing_layer_id: Option<u64>) -> Control {
+    fn layer_label(model: &LayerModel, selected_layer_id: Option<u64>, editing_layer_id: Option<u64>, importing_layer_id: Option<u64>) -> Control {
         let name        = model.name.get();
         let layer_id    = model.id;
 
-        let is_selected = Some(layer_id) == selected_layer_id;
-        let is_editing  = Some(layer_id) == editing_layer_id;
+        let is_selected     = Some(layer_id) == selected_layer_id;
+        let is_editing      = Some(layer_id) == editing_layer_id;
+        let is_importing    = Some(layer_id) == importing_layer_id;
         let background  = if is_selected { TIMELINE_SELECTED_LAYER } else { TIMELINE_BACKGROUND };
 
+        // Folders have a button to show or hide their contents
+        let folder_toggle = if model.is_folder {
+            Control::label()
+                .with(if model.collapsed { "\u{25b8}" } else { "\u{25be}" })
+                .with(TextAlign::Center)
+                .with(Bounds::next_horiz(12.0))
+                .with((ActionTrigger::Click, format!("ToggleCollapsed-{}", layer_id)))
+        } else {
+            Control::empty()
+                .with(Bounds::next_horiz(12.0))
+        };
+
+        // Reference layers have a button to import the image displayed at the current time
+        let import_image = if model.is_reference {
+            Control::label()
+                .with("\u{25a7}")
+                .with(TextAlign::Center)
+                .with(Bounds::next_horiz(16.0))
+                .with(Hover::Tooltip("Import a PNG image to display from the current frame".to_string()))
+                .with((ActionTrigger::Click, format!("ImportReferenceImage-{}", layer_id)))
+        } else {
+            Control::empty()
+                .with(Bounds::next_horiz(16.0))
+        };
+
+        // Layers that are hidden or locked because of the folder they're in are displayed differently from the layers that were hidden or locked directly
+        let hidden_label    = if model.hidden { "\u{25cb}" } else if model.effectively_hidden { "\u{25cc}" } else { "\u{25cf}" };
+        let locked_label    = if model.locked { "\u{25a3}" } else if model.effectively_locked { "\u{25a9}" } else { "\u{25a1}" };
+
         Control::container()
             .with(Bounds::next_vert(TIMELINE_LAYER_HEIGHT-1.0))
             .with(ControlAttribute::Padding((4, 1), (1, 1)))
             .with(Appearance::Background(background))
             .with(vec![
+                Control::label()
+                    .with("\u{2261}")
+                    .with(TextAlign::Center)
+                    .with(Bounds::next_horiz(12.0))
+                    .with(Hover::Tooltip("Drag to reorder or move into a folder".to_string()))
+                    .with((ActionTrigger::Drag, format!("DragLayer-{}", layer_id))),
                 Control::empty()
-                    .with(Bounds::next_horiz(20.0)),
+                    .with(Bounds::next_horiz(LAYER_INDENT * (model.depth as f32))),
+                folder_toggle,
                 Control::empty()
                     .with(Bounds::next_horiz(2.0)),
-                if is_editing {
+                if is_importing {
+                    // The path of the image to import is typed into the place where the name would be
+                    Control::text_box()
+                        .with("")
+                        .with(Bounds::stretch_horiz(1.0))
+                        .with(Hover::Tooltip("Path of the PNG image to import".to_string()))
+                        .with(State::FocusPriority(Property::from(128.0)))
+                        .with((ActionTrigger::CancelEdit, "CancelImportingImage"))
+                        .with((ActionTrigger::Dismiss, "CancelImportingImage"))
+                        .with((ActionTrigger::SetValue, "ImportImage"))
+                } else if is_editing {
                     Control::text_box()
                         .with(name)
                         .with(Bounds::stretch_horiz(1.0))
@@ -84,16 +166,142 @@ impl TimelineLayerListController {
                             ActionTrigger::Click,
                             if is_selected { format!("EditLayer-{}", layer_id) } else { format!("SelectLayer-{}", layer_id) }
                         ))
-                }
+                },
+                import_image,
+                Control::label()
+                    .with(hidden_label)
+                    .with(TextAlign::Center)
+                    .with(Bounds::next_horiz(16.0))
+                    .with(Hover::Tooltip(if model.hidden { "Show layer".to_string() } else { "Hide layer".to_string() }))
+                    .with((ActionTrigger::Click, format!("ToggleHidden-{}", layer_id))),
+                Control::label()
+                    .with(locked_label)
+                    .with(TextAlign::Center)
+                    .with(Bounds::next_horiz(16.0))
+                    .with(Hover::Tooltip(if model.locked { "Unlock layer".to_string() } else { "Lock layer".to_string() }))
+                    .with((ActionTrigger::Click, format!("ToggleLocked-{}", layer_id)))
             ])
     }
 
+    ///
+    /// Finds the ID of the layer in an action name like 'SelectLayer-x'
+    ///
+    fn layer_id_for_action(action_id: &str, prefix: &str) -> Option<u64> {
+        if action_id.starts_with(prefix) {
+            let (_, layer_id) = action_id.split_at(prefix.len());
+            u64::from_str_radix(layer_id, 10).ok()
+        } else {
+            None
+        }
+    }
+
+    ///
+    /// Sends some edits to the animation
+    ///
+    fn publish_edits(&self, edits: Vec<AnimationEdit>) {
+        let _ = self.edit_sink.future(move |edit_sink| {
+            edit_sink.publish(Arc::new(edits))
+        });
+    }
+
+    ///
+    /// Imports the PNG image at the specified path into a reference layer, displaying it from the current time
+    ///
+    fn import_reference_image(&self, layer_id: u64, path: &str) {
+        // Read the image and make sure that it's a PNG file that can be displayed
+        let png_data = match fs::read(path) {
+            Ok(png_data)    => Arc::new(png_data),
+            Err(err)        => { LAYER_LIST_LOG.log((Level::Warn, format!("Could not read reference image `{}`: {}", path, err))); return; }
+        };
+
+        if Image::Png(Arc::new(InMemoryImageData::from((*png_data).clone()))).to_rgba_data().is_none() {
+            LAYER_LIST_LOG.log((Level::Warn, format!("`{}` is not a PNG image", path)));
+            return;
+        }
+
+        // Store the image as an asset (so it's only stored once no matter how many times it's displayed), then display it on the layer
+        let asset_id = self.animation.assign_asset_id();
+        self.animation.write_asset(asset_id, png_data);
+
+        self.publish_edits(vec![AnimationEdit::Layer(layer_id, LayerEdit::AddReferenceImage(self.current_time.get(), asset_id))]);
+    }
+
+    ///
+    /// Returns true if a layer is inside the specified folder (or in a folder inside it)
+    ///
+    fn is_inside_folder(layers: &Vec<LayerModel>, layer_id: u64, folder_id: u64) -> bool {
+        let mut parent_id   = layers.iter().filter(|layer| layer.id == layer_id).nth(0).and_then(|layer| layer.parent);
+        let mut depth       = 0;
+
+        while let Some(check_id) = parent_id {
+            if check_id == folder_id    { return true; }
+            if depth > layers.len()     { return false; }
+
+            parent_id   = layers.iter().filter(|layer| layer.id == check_id).nth(0).and_then(|layer| layer.parent);
+            depth       += 1;
+        }
+
+        false
+    }
+
+    ///
+    /// Generates the edits to move a layer that has been dragged up or down by a number of rows in the layer list
+    ///
+    /// Dropping a layer on a folder moves it into the folder: dropping it on any other layer moves it next to that layer,
+    /// in the same folder.
+    ///
+    fn drop_layer_edits(&self, layer_id: u64, row_offset: i64) -> Vec<AnimationEdit> {
+        let layers          = self.layers.get();
+        let visible_layers  = self.visible_layers.get();
+
+        // Find the row the layer was dropped on
+        let from_row        = match visible_layers.iter().position(|layer| layer.id == layer_id) {
+            Some(row)   => row as i64,
+            None        => { return vec![]; }
+        };
+        let to_row          = (from_row + row_offset).max(0).min(visible_layers.len() as i64 - 1);
+        if to_row == from_row {
+            return vec![];
+        }
+
+        let dragged         = &visible_layers[from_row as usize];
+        let target          = &visible_layers[to_row as usize];
+
+        // A folder can't be moved inside itself
+        if Self::is_inside_folder(&layers, target.id, layer_id) {
+            return vec![];
+        }
+
+        if target.is_folder {
+            // Move into the folder
+            if dragged.parent == Some(target.id) {
+                vec![]
+            } else {
+                vec![AnimationEdit::Layer(layer_id, LayerEdit::SetParent(Some(target.id)))]
+            }
+        } else {
+            // Move into the same folder as the target layer
+            let mut edits = vec![];
+            if dragged.parent != target.parent {
+                edits.push(AnimationEdit::Layer(layer_id, LayerEdit::SetParent(target.parent)));
+            }
+
+            // Order behind the target layer when moving up, or in front of it when moving down
+            edits.push(AnimationEdit::Layer(layer_id, LayerEdit::SetOrdering(target.id)));
+            if to_row > from_row {
+                edits.push(AnimationEdit::Layer(target.id, LayerEdit::SetOrdering(layer_id)));
+            }
+
+            edits
+        }
+    }
+
     ///
     /// Creates the UI binding from the model
     ///
-    fn ui<Anim: 'static+Animation>(model: &FloModel<Anim>, editing_layer_id: BindRef<Option<u64>>) -> BindRef<Control> {
+    fn ui<Anim: 'static+Animation>(model: &FloModel<Anim>, editing_layer_id: BindRef<Option<u64>>, importing_layer_id: BindRef<Option<u64>>) -> BindRef<Control> {
         // Extract the bindings we're going to use from the model
-        let layers          = model.timeline().layers.clone();
+        let layers          = model.timeline().visible_layers.clone();
         let selected_layer  = model.timeline().selected_layer.clone();
 
         // Generate the UI
@@ -102,12 +310,13 @@ impl TimelineLayerListController {
             let layers          = layers.get();
             let selected_layer  = selected_layer.get();
             let editing_layer   = editing_layer_id.get();
+            let importing_layer = importing_layer_id.get();
 
             // Each layer creates a control
             let layer_controls = layers.into_iter()
                 .flat_map(|layer_model| {
                     // The layer is a simple label
-                    let label = Self::layer_label(&layer_model, selected_layer, editing_layer);
+                    let label = Self::layer_label(&layer_model, selected_layer, editing_layer, importing_layer);
 
                     // Each layer is followed by a divider
                     let divider = Control::empty()
@@ -153,22 +362,47 @@ impl Controller for TimelineLayerListController {
                 self.editing_layer_id.set(None)
             },
 
+            "CancelImportingImage" => self.importing_layer_id.set(None),
+
+            "ImportImage" => {
+                if let (ActionParameter::Value(PropertyValue::String(path)), Some(layer_id)) = (action_parameter, self.importing_layer_id.get()) {
+                    self.import_reference_image(layer_id, path);
+                }
+
+                self.importing_layer_id.set(None)
+            },
+
             _ => {
                 // 'SelectLayer-x' should select layer 'x'. 'EditLayer-x' should edit layer 'x'
-                if action_id.starts_with("SelectLayer-") {
-                    // Get the layer ID that should be selected
-                    let (_, layer_id)   = action_id.split_at("SelectLayer-".len());
-                    let layer_id        = u64::from_str_radix(layer_id, 10).unwrap();
-
+                if let Some(layer_id) = Self::layer_id_for_action(action_id, "SelectLayer-") {
                     // Update the model
                     self.selected_layer_id.set(Some(layer_id));
-                } else if action_id.starts_with("EditLayer-") {
-                    // Get the layer ID that should be edited
-                    let (_, layer_id)   = action_id.split_at("EditLayer-".len());
-                    let layer_id        = u64::from_str_radix(layer_id, 10).unwrap();
-
+                } else if let Some(layer_id) = Self::layer_id_for_action(action_id, "EditLayer-") {
                     // Update the model
                     self.editing_layer_id.set(Some(layer_id));
+                } else if let Some(layer_id) = Self::layer_id_for_action(action_id, "DragLayer-") {
+                    // Layers are moved when the drag finishes
+                    if let ActionParameter::Drag(DragAction::Finish, (_start_x, start_y), (_x, y), _) = action_parameter {
+                        let row_offset  = ((y - start_y) / TIMELINE_LAYER_HEIGHT).round() as i64;
+                        let edits       = self.drop_layer_edits(layer_id, row_offset);
+
+                        if !edits.is_empty() {
+                            self.publish_edits(edits);
+                        }
+                    }
+                } else if let Some(layer_id) = Self::layer_id_for_action(action_id, "ToggleCollapsed-") {
+                    let collapsed = self.layers.get().into_iter().any(|layer| layer.id == layer_id && layer.collapsed);
+                    self.publish_edits(vec![AnimationEdit::Layer(layer_id, LayerEdit::SetCollapsed(!collapsed))]);
+                } else if let Some(layer_id) = Self::layer_id_for_action(action_id, "ToggleHidden-") {
+                    let hidden = self.layers.get().into_iter().any(|layer| layer.id == layer_id && layer.hidden);
+                    self.publish_edits(vec![AnimationEdit::Layer(layer_id, LayerEdit::SetHidden(!hidden))]);
+                } else if let Some(layer_id) = Self::layer_id_for_action(action_id, "ImportReferenceImage-") {
+                    // Ask for the path of the image to import
+                    self.editing_layer_id.set(None);
+                    self.importing_layer_id.set(Some(layer_id));
+                } else if let Some(layer_id) = Self::layer_id_for_action(action_id, "ToggleLocked-") {
+                    let locked = self.layers.get().into_iter().any(|layer| layer.id == layer_id && layer.locked);
+                    self.publish_edits(vec![AnimationEdit::Layer(layer_id, LayerEdit::SetLocked(!locked))]);
                 }
             }
         }
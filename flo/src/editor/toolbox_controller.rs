@@ -100,6 +100,8 @@ impl<Anim: 'static+EditableAnimation+Animation> ToolboxController<Anim> {
         // Load the tool images
         let select      = images.register(svg_static(include_bytes!("../../svg/tools/select.svg")));
         let adjust      = images.register(svg_static(include_bytes!("../../svg/tools/adjust.svg")));
+        let motion_path = images.register(svg_static(include_bytes!("../../svg/tools/motion_path.svg")));
+        let skeleton    = images.register(svg_static(include_bytes!("../../svg/tools/skeleton.svg")));
         let pan         = images.register(svg_static(include_bytes!("../../svg/tools/pan.svg")));
 
         let pencil      = images.register(svg_static(include_bytes!("../../svg/tools/pencil.svg")));
@@ -110,6 +112,8 @@ impl<Anim: 'static+EditableAnimation+Animation> ToolboxController<Anim> {
         // Assign names to them
         images.assign_name(&select, "select");
         images.assign_name(&adjust, "adjust");
+        images.assign_name(&motion_path, "motion_path");
+        images.assign_name(&skeleton, "skeleton");
         images.assign_name(&pan, "pan");
 
         images.assign_name(&pencil, "pencil");
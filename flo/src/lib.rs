@@ -6,6 +6,7 @@ extern crate flo_stream;
 extern crate flo_canvas;
 extern crate flo_binding;
 extern crate flo_ui_files;
+extern crate flo_logging;
 extern crate flo_animation;
 
 extern crate desync;
@@ -208,7 +208,8 @@ impl EraserMenuController {
             let brush_properties = BrushProperties {
                 size:       size.get(),
                 opacity:    opacity.get(),
-                color:      Color::Rgba(0.0, 0.0, 0.0, 1.0)
+                color:      Color::Rgba(0.0, 0.0, 0.0, 1.0),
+                fill:       BrushFill::Solid
             };
 
             let points = brush.brush_points_for_raw_points(&points);
@@ -20,6 +20,8 @@ pub struct InkMenuController {
     opacity:            Binding<f32>,
     modification_mode:  Binding<BrushModificationMode>,
     representation:     Binding<BrushRepresentation>,
+    dynamics_enabled:   Binding<bool>,
+    dynamics:           Binding<DynamicInkDefinition>,
     brush_panel_open:   Binding<bool>,
 
     canvases:           Arc<ResourceManager<BindingCanvas>>,
@@ -64,7 +66,7 @@ impl InkMenuController {
     ///
     /// Creates a new ink menu controller
     ///
-    pub fn new(size: &Binding<f32>, opacity: &Binding<f32>, colour: &Binding<Color>, modification_mode: &Binding<BrushModificationMode>, representation: &Binding<BrushRepresentation>) -> InkMenuController {
+    pub fn new(size: &Binding<f32>, opacity: &Binding<f32>, colour: &Binding<Color>, modification_mode: &Binding<BrushModificationMode>, representation: &Binding<BrushRepresentation>, dynamics_enabled: &Binding<bool>, dynamics: &Binding<DynamicInkDefinition>, brush_definition: &BindRef<BrushDefinition>) -> InkMenuController {
         // Set up the view model
         let view_model = Arc::new(DynamicViewModel::new());
 
@@ -80,6 +82,20 @@ impl InkMenuController {
         let edit_brush_properties   = brush_panel_open.clone();
         view_model.set_computed("EditBrushProperties", move || PropertyValue::Bool(edit_brush_properties.get()));
 
+        // The dynamics settings are edited with sliders in the brush properties popup
+        let vm_dynamics = dynamics.clone();
+        view_model.set_computed("PressureOpacity", move || PropertyValue::Float(vm_dynamics.get().pressure_opacity as f64));
+        let vm_dynamics = dynamics.clone();
+        view_model.set_computed("NibAngle", move || PropertyValue::Float(vm_dynamics.get().nib_angle.to_degrees() as f64));
+        let vm_dynamics = dynamics.clone();
+        view_model.set_computed("Calligraphy", move || PropertyValue::Float(vm_dynamics.get().calligraphy as f64));
+        let vm_dynamics = dynamics.clone();
+        view_model.set_computed("VelocityThinning", move || PropertyValue::Float(vm_dynamics.get().velocity_thinning as f64));
+        let vm_dynamics = dynamics.clone();
+        view_model.set_computed("TaperIn", move || PropertyValue::Float(vm_dynamics.get().taper_in as f64));
+        let vm_dynamics = dynamics.clone();
+        view_model.set_computed("TaperOut", move || PropertyValue::Float(vm_dynamics.get().taper_out as f64));
+
         // Create the colour picker popup
         let color_picker_open   = Binding::new(false);
         let color_picker        = ColorPickerController::new(colour);
@@ -96,7 +112,7 @@ impl InkMenuController {
         // Create the canvases
         let canvases                = Arc::new(ResourceManager::new());
 
-        let brush_preview           = Self::brush_preview(size, opacity, colour);
+        let brush_preview           = Self::brush_preview(size, opacity, colour, brush_definition);
         let brush_preview           = canvases.register(brush_preview);
         canvases.assign_name(&brush_preview, "BrushPreview");
 
@@ -121,7 +137,7 @@ impl InkMenuController {
         canvases.assign_name(&colour_preview, "ColourPreview");
 
         // Generate the UI
-        let ui = Self::ui(&canvases, &images, &brush_panel_open, modification_mode, representation);
+        let ui = Self::ui(&canvases, &images, &brush_panel_open, modification_mode, representation, dynamics_enabled, dynamics);
 
         // Finalize the control
         InkMenuController {
@@ -129,6 +145,8 @@ impl InkMenuController {
             opacity:            opacity.clone(),
             modification_mode:  modification_mode.clone(),
             representation:     representation.clone(),
+            dynamics_enabled:   dynamics_enabled.clone(),
+            dynamics:           dynamics.clone(),
             brush_panel_open:   brush_panel_open,
 
             canvases:           canvases,
@@ -141,14 +159,86 @@ impl InkMenuController {
         }
     }
 
+    ///
+    /// Creates a toggle button for the brush properties popup
+    ///
+    fn settings_toggle(text: &str, selected: bool, action: &str) -> Control {
+        Control::button()
+            .with(vec![Control::label().with(text).with(Font::Size(11.0)).with(TextAlign::Center).with(Bounds::fill_all())])
+            .with(State::Selected(Property::Bool(selected)))
+            .with((ActionTrigger::Click, action))
+            .with(Bounds::next_vert(22.0))
+    }
+
+    ///
+    /// Creates a slider for one of the dynamics settings in the brush properties popup
+    ///
+    fn dynamics_slider(text: &str, property: &str, max_value: f64, action: &str) -> Control {
+        Control::container()
+            .with(Bounds::next_vert(22.0))
+            .with(vec![
+                Control::label()
+                    .with(text)
+                    .with(Font::Size(11.0))
+                    .with(TextAlign::Right)
+                    .with(Bounds::next_horiz(88.0)),
+                Control::empty()
+                    .with(Bounds::next_horiz(6.0)),
+                Control::slider()
+                    .with(State::Range((0.0.to_property(), max_value.to_property())))
+                    .with(State::Value(Property::Bind(property.to_string())))
+                    .with(Bounds::fill_horiz())
+                    .with((ActionTrigger::EditValue, action.to_string()))
+                    .with((ActionTrigger::SetValue, action.to_string()))
+            ])
+    }
+
+    ///
+    /// Creates the controls for the dynamics settings in the brush properties popup
+    ///
+    fn dynamics_controls(dynamics_enabled: bool, tilt_nib_angle: bool) -> Vec<Control> {
+        let mut controls = vec![
+            Control::empty()
+                .with(Bounds::next_vert(3.0)),
+            Control::empty()
+                .with(Appearance::Background(MENU_BACKGROUND_ALT))
+                .with(Bounds::next_vert(2.0)),
+            Control::empty()
+                .with(Bounds::next_vert(3.0)),
+            Self::settings_toggle("Pressure & tilt dynamics", dynamics_enabled, "ToggleDynamics"),
+            Control::empty()
+                .with(Bounds::next_vert(3.0))
+        ];
+
+        if dynamics_enabled {
+            controls.extend(vec![
+                Self::dynamics_slider("Pressure opacity:", "PressureOpacity", 1.0, "SetPressureOpacity"),
+                Self::dynamics_slider("Calligraphy:", "Calligraphy", 1.0, "SetCalligraphy"),
+                Self::dynamics_slider("Nib angle:", "NibAngle", 180.0, "SetNibAngle"),
+                Self::dynamics_slider("Speed thinning:", "VelocityThinning", 1.0, "SetVelocityThinning"),
+                Self::dynamics_slider("Taper in:", "TaperIn", 100.0, "SetTaperIn"),
+                Self::dynamics_slider("Taper out:", "TaperOut", 100.0, "SetTaperOut"),
+                Control::empty()
+                    .with(Bounds::next_vert(3.0)),
+                Self::settings_toggle("Tilt sets nib angle", tilt_nib_angle, "ToggleTiltNibAngle"),
+                Control::empty()
+                    .with(Bounds::next_vert(3.0))
+            ]);
+        }
+
+        controls
+    }
+
     ///
     /// Creates the UI for the ink menu bar
     ///
-    fn ui(canvases: &ResourceManager<BindingCanvas>, images: &ResourceManager<Image>, brush_panel_open: &Binding<bool>, modification_mode: &Binding<BrushModificationMode>, representation: &Binding<BrushRepresentation>) -> BindRef<Control> {
+    fn ui(canvases: &ResourceManager<BindingCanvas>, images: &ResourceManager<Image>, brush_panel_open: &Binding<bool>, modification_mode: &Binding<BrushModificationMode>, representation: &Binding<BrushRepresentation>, dynamics_enabled: &Binding<bool>, dynamics: &Binding<DynamicInkDefinition>) -> BindRef<Control> {
         // Model
         let modification_mode           = modification_mode.clone();
         let representation              = representation.clone();
         let brush_panel_open            = brush_panel_open.clone();
+        let dynamics_enabled            = dynamics_enabled.clone();
+        let dynamics                    = dynamics.clone();
 
         // Fetch the image resources
         let brush_settings_background   = images.get_named_resource("brush_settings");
@@ -174,6 +264,11 @@ impl InkMenuController {
             let modification_mode   = modification_mode.get();
             let representation      = representation.get();
             let brush_panel_open    = brush_panel_open.get();
+            let dynamics_enabled    = dynamics_enabled.get();
+            let tilt_nib_angle      = dynamics.get().tilt_nib_angle;
+
+            // The settings popup grows when the dynamics settings are displayed
+            let settings_height     = if dynamics_enabled { 131 + 33 + 6*22 + 28 } else { 131 + 33 };
 
             let modification_icon   = match modification_mode {
                 BrushModificationMode::Additive     => additive_mode.clone(),
@@ -308,7 +403,7 @@ impl InkMenuController {
                                 .with(if brush_panel_open { vec![
                                     Control::popup()
                                         .with(Popup::Direction(PopupDirection::Below))
-                                        .with(Popup::Size(220, 131))
+                                        .with(Popup::Size(240, settings_height))
                                         .with(Popup::Offset(14))
                                         .with(ControlAttribute::ZIndex(1000))
                                         .with(Popup::IsOpen(Property::Bind("EditBrushProperties".to_string())))
@@ -374,7 +469,7 @@ impl InkMenuController {
                                                         ]),
                                                     Control::empty()
                                                         .with(Bounds::next_vert(3.0)),
-                                                ])
+                                                ].into_iter().chain(Self::dynamics_controls(dynamics_enabled, tilt_nib_angle)).collect::<Vec<_>>())
                                         ]),
                                 ] } else { vec![] }),
                             Control::empty()
@@ -395,6 +490,15 @@ impl InkMenuController {
         BindRef::from(ui)
     }
 
+    ///
+    /// Updates the settings for the dynamic ink brush
+    ///
+    fn update_dynamics<UpdateFn: FnOnce(&mut DynamicInkDefinition) -> ()>(&self, update: UpdateFn) {
+        let mut dynamics = self.dynamics.get();
+        update(&mut dynamics);
+        self.dynamics.set(dynamics);
+    }
+
     ///
     /// Creates the size preview canvas
     ///
@@ -459,10 +563,11 @@ impl InkMenuController {
     ///
     /// Creates the brush preview canvas
     ///
-    pub fn brush_preview(size: &Binding<f32>, opacity: &Binding<f32>, color: &Binding<Color>) -> BindingCanvas {
-        let size    = size.clone();
-        let opacity = opacity.clone();
-        let color   = color.clone();
+    pub fn brush_preview(size: &Binding<f32>, opacity: &Binding<f32>, color: &Binding<Color>, brush_definition: &BindRef<BrushDefinition>) -> BindingCanvas {
+        let size                = size.clone();
+        let opacity             = opacity.clone();
+        let color               = color.clone();
+        let brush_definition    = brush_definition.clone();
 
         let control_height  = 32.0 - 6.0;
         let control_width   = 64.0;
@@ -479,8 +584,8 @@ impl InkMenuController {
             gc.rect(-control_width/2.0, -control_height/2.0, control_width/2.0, control_height/2.0);
             gc.fill();
 
-            // Create an ink brush
-            let brush = InkBrush::new(&InkDefinition::default(), BrushDrawingStyle::Draw);
+            // Create the brush that the ink tool is using
+            let brush = create_brush_from_definition(&brush_definition.get(), BrushDrawingStyle::Draw);
 
             // Render a test brush stroke
             let mut points = vec![];
@@ -499,7 +604,8 @@ impl InkMenuController {
             let brush_properties = BrushProperties {
                 size:       size.get(),
                 opacity:    opacity.get(),
-                color:      color.get()
+                color:      color.get(),
+                fill:       BrushFill::Solid
             };
 
             let points = brush.brush_points_for_raw_points(&points);
@@ -577,6 +683,23 @@ impl Controller for InkMenuController {
                 self.brush_panel_open.set(false);
             },
 
+            ("ToggleDynamics", _) => {
+                self.dynamics_enabled.set(!self.dynamics_enabled.get());
+            },
+
+            ("ToggleTiltNibAngle", _) => {
+                let mut dynamics        = self.dynamics.get();
+                dynamics.tilt_nib_angle = !dynamics.tilt_nib_angle;
+                self.dynamics.set(dynamics);
+            },
+
+            ("SetPressureOpacity", &Value(PropertyValue::Float(value)))     => { self.update_dynamics(|dynamics| dynamics.pressure_opacity = value as f32); },
+            ("SetCalligraphy", &Value(PropertyValue::Float(value)))         => { self.update_dynamics(|dynamics| dynamics.calligraphy = value as f32); },
+            ("SetNibAngle", &Value(PropertyValue::Float(value)))            => { self.update_dynamics(|dynamics| dynamics.nib_angle = (value as f32).to_radians()); },
+            ("SetVelocityThinning", &Value(PropertyValue::Float(value)))    => { self.update_dynamics(|dynamics| dynamics.velocity_thinning = value as f32); },
+            ("SetTaperIn", &Value(PropertyValue::Float(value)))             => { self.update_dynamics(|dynamics| dynamics.taper_in = value as f32); },
+            ("SetTaperOut", &Value(PropertyValue::Float(value)))            => { self.update_dynamics(|dynamics| dynamics.taper_out = value as f32); },
+
             ("NextModificationMode", _) => {
                 self.modification_mode.set(match self.modification_mode.get() {
                     BrushModificationMode::Additive     => BrushModificationMode::Individual,
@@ -0,0 +1,199 @@
+use super::controls;
+use super::super::model::*;
+
+use flo_ui::*;
+use flo_binding::*;
+use flo_animation::*;
+
+use std::sync::*;
+use std::f32;
+
+/// The amount the zoom buttons change the zoom by
+const ZOOM_STEP: f32 = 1.25;
+
+/// The amount the rotation buttons rotate the view by
+const ROTATE_STEP: f32 = f32::consts::PI / 12.0;
+
+///
+/// The menu controller for the pan tool
+///
+pub struct PanMenuController {
+    /// The viewport that this will update
+    viewport: ViewportModel,
+
+    /// The size of the animation (we zoom and rotate around the center)
+    size: BindRef<(f64, f64)>,
+
+    /// The images for the pan menu
+    images: Arc<ResourceManager<Image>>,
+
+    // The UI for this control
+    ui: BindRef<Control>
+}
+
+impl PanMenuController {
+    ///
+    /// Creates a new pan menu controller
+    ///
+    pub fn new<Anim: 'static+Animation>(flo_model: &FloModel<Anim>) -> PanMenuController {
+        let images      = Self::images();
+        let viewport    = flo_model.viewport().clone();
+        let ui          = Self::ui(&images, &viewport);
+
+        PanMenuController {
+            viewport:   viewport,
+            size:       flo_model.size.clone(),
+            images:     Arc::new(images),
+            ui:         ui
+        }
+    }
+
+    ///
+    /// Creates the images for this controller
+    ///
+    fn images() -> ResourceManager<Image> {
+        let images      = ResourceManager::new();
+
+        let flip_horiz  = images.register(svg_static(include_bytes!("../../svg/selection_controls/flip_horizontal.svg")));
+        images.assign_name(&flip_horiz, "FlipHorizontal");
+
+        images
+    }
+
+    ///
+    /// Creates a text button for the pan menu
+    ///
+    fn button(text: &str, action: &str, width: f32) -> Control {
+        Control::button()
+            .with(vec![Control::label().with(text).with(TextAlign::Center).with(Bounds::fill_all())])
+            .with(Font::Size(12.0))
+            .with((ActionTrigger::Click, action))
+            .with(Bounds::next_horiz(width))
+    }
+
+    ///
+    /// Creates the UI for the pan menu controller
+    ///
+    fn ui(images: &ResourceManager<Image>, viewport: &ViewportModel) -> BindRef<Control> {
+        let flip_horiz  = images.get_named_resource("FlipHorizontal");
+        let zoom        = viewport.zoom.clone();
+        let rotation    = viewport.rotation.clone();
+        let flipped     = viewport.flipped.clone();
+
+        let ui = computed(move || {
+            let zoom_text       = format!("{:.0}%", zoom.get()*100.0);
+            let rotation_text   = format!("{:.0}°", rotation.get().to_degrees());
+            let flipped         = flipped.get();
+
+            Control::container()
+                .with(Bounds::fill_all())
+                .with(ControlAttribute::Padding((0, 3), (0, 3)))
+                .with(vec![
+                    controls::divider(),
+
+                    Control::label()
+                        .with("View:")
+                        .with(FontWeight::Light)
+                        .with(TextAlign::Right)
+                        .with(Font::Size(14.0))
+                        .with(Bounds::next_horiz(48.0)),
+
+                    Control::empty()
+                        .with(Bounds::next_horiz(4.0)),
+
+                    Control::label()
+                        .with(zoom_text)
+                        .with(FontWeight::Light)
+                        .with(TextAlign::Right)
+                        .with(Font::Size(12.0))
+                        .with(Bounds::next_horiz(40.0)),
+                    Control::empty()
+                        .with(Bounds::next_horiz(4.0)),
+                    Control::container()
+                        .with(Hint::Class("button-group".to_string()))
+                        .with(ControlAttribute::Padding((0,2), (0,2)))
+                        .with(Bounds::next_horiz(24.0*2.0))
+                        .with(vec![
+                            Self::button("-", "ZoomOut", 24.0),
+                            Self::button("+", "ZoomIn", 24.0)
+                        ]),
+
+                    controls::divider(),
+
+                    Control::label()
+                        .with(rotation_text)
+                        .with(FontWeight::Light)
+                        .with(TextAlign::Right)
+                        .with(Font::Size(12.0))
+                        .with(Bounds::next_horiz(32.0)),
+                    Control::empty()
+                        .with(Bounds::next_horiz(4.0)),
+                    Control::container()
+                        .with(Hint::Class("button-group".to_string()))
+                        .with(ControlAttribute::Padding((0,2), (0,2)))
+                        .with(Bounds::next_horiz(24.0*2.0))
+                        .with(vec![
+                            Self::button("⟲", "RotateLeft", 24.0),
+                            Self::button("⟳", "RotateRight", 24.0)
+                        ]),
+
+                    controls::divider(),
+
+                    Control::container()
+                        .with(Hint::Class("button-group".to_string()))
+                        .with(ControlAttribute::Padding((0,2), (0,2)))
+                        .with(Bounds::next_horiz(28.0))
+                        .with(vec![
+                            Control::button()
+                                .with(vec![Control::empty().with(flip_horiz.clone()).with(TextAlign::Center).with(Bounds::fill_all())])
+                                .with(State::Selected(Property::Bool(flipped)))
+                                .with((ActionTrigger::Click, "FlipHorizontal"))
+                                .with(Bounds::next_horiz(28.0))
+                                .with(ControlAttribute::Padding((4, 1), (4, 3)))
+                        ]),
+
+                    Control::empty()
+                        .with(Bounds::next_horiz(4.0)),
+                    Control::container()
+                        .with(Hint::Class("button-group".to_string()))
+                        .with(ControlAttribute::Padding((0,2), (0,2)))
+                        .with(Bounds::next_horiz(48.0))
+                        .with(vec![
+                            Self::button("Reset", "ResetView", 48.0)
+                        ])
+                ])
+        });
+
+        BindRef::from(ui)
+    }
+}
+
+impl Controller for PanMenuController {
+    fn ui(&self) -> BindRef<Control> {
+        self.ui.clone()
+    }
+
+    fn get_image_resources(&self) -> Option<Arc<ResourceManager<Image>>> {
+        Some(Arc::clone(&self.images))
+    }
+
+    fn action(&self, action_id: &str, _action_parameter: &ActionParameter) {
+        // View operations from the menu are centered on the middle of the animation
+        let (width, height) = self.size.get();
+        let center          = ((width/2.0) as f32, (height/2.0) as f32);
+
+        // Rotations are reversed when the view is mirrored so that the buttons always turn the view in the same direction
+        let rotate_step     = if self.viewport.flipped.get() { -ROTATE_STEP } else { ROTATE_STEP };
+
+        match action_id {
+            "ZoomIn"            => self.viewport.perform(ViewportAction::Zoom(center, ZOOM_STEP)),
+            "ZoomOut"           => self.viewport.perform(ViewportAction::Zoom(center, 1.0/ZOOM_STEP)),
+            "RotateLeft"        => self.viewport.perform(ViewportAction::Rotate(center, rotate_step)),
+            "RotateRight"       => self.viewport.perform(ViewportAction::Rotate(center, -rotate_step)),
+            "FlipHorizontal"    => self.viewport.perform(ViewportAction::FlipHorizontal(center)),
+            "ResetView"         => self.viewport.perform(ViewportAction::Reset),
+
+            _                   => { }
+        }
+    }
+}
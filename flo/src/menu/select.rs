@@ -10,7 +10,106 @@ use flo_animation::*;
 use ::desync::*;
 
 use std::sync::*;
-use std::collections::HashSet;
+use std::collections::{HashSet, HashMap};
+use std::time::Duration;
+
+/// Height of a row in the transform inspector
+const TRANSFORM_ROW_HEIGHT: f32 = 22.0;
+
+/// Size of one of the buttons used to choose the anchor point in the transform inspector
+const ANCHOR_BUTTON_SIZE: f32 = 16.0;
+
+///
+/// The point on the selection's bounding box that the transform inspector positions the selection by and transforms it around
+///
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum TransformAnchor {
+    TopLeft,    Top,    TopRight,
+    Left,       Center, Right,
+    BottomLeft, Bottom, BottomRight
+}
+
+impl TransformAnchor {
+    ///
+    /// Returns all of the anchor points, in rows from the top-left
+    ///
+    fn all() -> Vec<TransformAnchor> {
+        use self::TransformAnchor::*;
+
+        vec![
+            TopLeft,    Top,    TopRight,
+            Left,       Center, Right,
+            BottomLeft, Bottom, BottomRight
+        ]
+    }
+
+    ///
+    /// Returns the position of this anchor on a bounding box
+    ///
+    fn point_in(&self, bounds: &Rect) -> (f64, f64) {
+        use self::TransformAnchor::*;
+
+        let (x1, y1, x2, y2)    = (bounds.x1 as f64, bounds.y1 as f64, bounds.x2 as f64, bounds.y2 as f64);
+        let (mid_x, mid_y)      = ((x1+x2)/2.0, (y1+y2)/2.0);
+
+        // The y axis points upwards, so the top of the bounding box is y2
+        match self {
+            TopLeft     => (x1, y2),
+            Top         => (mid_x, y2),
+            TopRight    => (x2, y2),
+            Left        => (x1, mid_y),
+            Center      => (mid_x, mid_y),
+            Right       => (x2, mid_y),
+            BottomLeft  => (x1, y1),
+            Bottom      => (mid_x, y1),
+            BottomRight => (x2, y1)
+        }
+    }
+}
+
+///
+/// Returns the rotation (in degrees) and the scale factors applied by a set of transformations
+///
+fn rotation_and_scale<'a, TransformIter: IntoIterator<Item=&'a Transformation>>(transformations: TransformIter) -> (f64, f64, f64) {
+    let mut rotation    = 0.0f64;
+    let mut scale       = (1.0f64, 1.0f64);
+
+    for transformation in transformations {
+        match transformation {
+            Transformation::Rotate(theta, _)        => { rotation += *theta; }
+            Transformation::Scale(sx, sy, _)        => { scale = (scale.0*sx, scale.1*sy); }
+            _                                       => { }
+        }
+    }
+
+    (rotation.to_degrees(), scale.0, scale.1)
+}
+
+///
+/// Returns the transformations that result from setting one of the values in the transform inspector
+///
+/// The transform is the rotation (in degrees) and scale factors of the selection, which are unknown when several elements
+/// are selected. Values entered for an unknown rotation or scale are relative to the current state of the selection.
+///
+fn transform_for_value(action_id: &str, value: f64, anchor: TransformAnchor, bounds: Rect, transform: Option<(f64, f64, f64)>) -> Option<Vec<ElementTransform>> {
+    let (rotation, scale_x, scale_y)    = transform.unwrap_or((0.0, 1.0, 1.0));
+    let (anchor_x, anchor_y)            = anchor.point_in(&bounds);
+    let (width, height)                 = (bounds.width() as f64, bounds.height() as f64);
+
+    let transform = match action_id {
+        "SetX"          => ElementTransform::MoveTo(value, anchor_y),
+        "SetY"          => ElementTransform::MoveTo(anchor_x, value),
+        "SetWidth"      => if width > 0.0 && value > 0.0 { ElementTransform::Scale(value/width, 1.0) } else { return None },
+        "SetHeight"     => if height > 0.0 && value > 0.0 { ElementTransform::Scale(1.0, value/height) } else { return None },
+        "SetRotation"   => ElementTransform::Rotate((value - rotation).to_radians()),
+        "SetScaleX"     => if scale_x != 0.0 && value != 0.0 { ElementTransform::Scale(value/100.0/scale_x, 1.0) } else { return None },
+        "SetScaleY"     => if scale_y != 0.0 && value != 0.0 { ElementTransform::Scale(1.0, value/100.0/scale_y) } else { return None },
+
+        _               => return None
+    };
+
+    Some(vec![ElementTransform::SetAnchor(anchor_x, anchor_y), transform])
+}
 
 ///
 /// The menu controller for the selection tool
@@ -22,6 +121,9 @@ pub struct SelectMenuController<Anim: Animation> {
     /// Currently selected elements, in order
     selection_in_order: BindRef<Arc<Vec<ElementId>>>,
 
+    /// The elements in the current frame
+    elements: BindRef<Arc<Vec<(Vector, Arc<VectorProperties>)>>>,
+
     /// The images for the selection menu
     images: Arc<ResourceManager<Image>>,
 
@@ -31,6 +133,30 @@ pub struct SelectMenuController<Anim: Animation> {
     /// The timeline model for the animation
     timeline: TimelineModel<Anim>,
 
+    /// Whether or not the select tool snaps to the grid
+    snap_to_grid: Binding<bool>,
+
+    /// The spacing of the grid used for snapping
+    grid_size: Binding<f32>,
+
+    /// Whether or not the select tool snaps to smart guides
+    smart_guides: Binding<bool>,
+
+    /// The view model for this controller
+    view_model: Arc<DynamicViewModel>,
+
+    /// Whether or not the transform inspector is open
+    transform_open: Binding<bool>,
+
+    /// The anchor point used by the transform inspector
+    anchor: Binding<TransformAnchor>,
+
+    /// The bounding box of the selected elements
+    selection_bounds: BindRef<Option<Rect>>,
+
+    /// The rotation (in degrees) and scale factors of the selection (if there's a single element selected)
+    selection_transform: BindRef<Option<(f64, f64, f64)>>,
+
     // The UI for this control
     ui: BindRef<Control>
 }
@@ -41,10 +167,23 @@ impl<Anim: 'static+EditableAnimation+Animation> SelectMenuController<Anim> {
     ///
     pub fn new(flo_model: &FloModel<Anim>, tool_model: &SelectToolModel) -> SelectMenuController<Anim> {
         let images              = Self::images();
-        let ui                  = Self::ui(&images, tool_model);
         let edit                = Desync::new(flo_model.edit());
         let selected            = flo_model.selection().selected_elements.clone();
+
+        // State for the transform inspector
+        let view_model          = Arc::new(DynamicViewModel::new());
+        let transform_open      = bind(false);
+        let anchor              = bind(TransformAnchor::Center);
+        let selection_bounds    = Self::selection_bounds(selected.clone(), flo_model.frame().bounding_boxes.clone());
+        let selection_transform = Self::selection_transform(selected.clone(), flo_model.frame().frame.clone());
+
+        let vm_transform_open   = transform_open.clone();
+        view_model.set_computed("EditTransform", move || PropertyValue::Bool(vm_transform_open.get()));
+
+        let inspector           = (transform_open.clone(), anchor.clone(), selection_bounds.clone(), selection_transform.clone());
+        let ui                  = Self::ui(&images, tool_model, BindRef::new(&flo_model.timeline().editing_symbol), inspector);
         let selection_in_order  = flo_model.selection().selection_in_order.clone();
+        let elements            = flo_model.frame().elements.clone();
         let timeline            = flo_model.timeline().clone();
 
         SelectMenuController {
@@ -53,10 +192,146 @@ impl<Anim: 'static+EditableAnimation+Animation> SelectMenuController<Anim> {
             images:             Arc::new(images),
             selected:           selected,
             selection_in_order: selection_in_order,
-            timeline:           timeline
+            elements:           elements,
+            timeline:           timeline,
+            snap_to_grid:       tool_model.snap_to_grid.clone(),
+            grid_size:          tool_model.grid_size.clone(),
+            smart_guides:       tool_model.smart_guides.clone(),
+            view_model:         view_model,
+            transform_open:     transform_open,
+            anchor:             anchor,
+            selection_bounds:   selection_bounds,
+            selection_transform: selection_transform
         }
     }
 
+    ///
+    /// Creates a binding that tracks the overall bounding box of the selected elements
+    ///
+    fn selection_bounds(selected: BindRef<Arc<HashSet<ElementId>>>, bounding_boxes: BindRef<Arc<HashMap<ElementId, Rect>>>) -> BindRef<Option<Rect>> {
+        let bounds = computed(move || {
+            let selected        = selected.get();
+            let bounding_boxes  = bounding_boxes.get();
+
+            selected.iter()
+                .filter_map(|element_id| bounding_boxes.get(element_id))
+                .fold(None, |overall: Option<Rect>, bounds| Some(overall.map(|overall| overall.union(*bounds)).unwrap_or(*bounds)))
+        });
+
+        BindRef::from(bounds)
+    }
+
+    ///
+    /// Creates a binding that tracks the rotation (in degrees) and the scale factors of the selection
+    ///
+    /// These are read from the transformations attached to the element when there's a single element selected. The
+    /// elements in a multiple selection can each have a different rotation and scale, so there's no value for them.
+    ///
+    fn selection_transform(selected: BindRef<Arc<HashSet<ElementId>>>, frame: BindRef<Option<Arc<dyn Frame>>>) -> BindRef<Option<(f64, f64, f64)>> {
+        let transform = computed(move || {
+            let selected = selected.get();
+
+            if let (Some(frame), 1) = (frame.get(), selected.len()) {
+                let element_id      = *selected.iter().nth(0).unwrap();
+                let transformations = frame.attached_elements(element_id)
+                    .into_iter()
+                    .filter(|(_, attachment_type)| *attachment_type == VectorType::Transformation)
+                    .filter_map(|(attachment_id, _)| match frame.element_with_id(attachment_id) {
+                        Some(Vector::Transformation((_, transformations)))  => Some(transformations),
+                        _                                                   => None
+                    })
+                    .collect::<Vec<_>>();
+
+                Some(rotation_and_scale(transformations.iter().flat_map(|transformations| transformations.iter())))
+            } else {
+                None
+            }
+        });
+
+        BindRef::from(transform)
+    }
+
+    ///
+    /// Creates a row in the transform inspector
+    ///
+    fn transform_field(label: &str, value: Option<f64>, action: &str) -> Control {
+        Control::container()
+            .with(Bounds::next_vert(TRANSFORM_ROW_HEIGHT))
+            .with(ControlAttribute::Padding((0, 1), (0, 1)))
+            .with(vec![
+                Control::label()
+                    .with(label)
+                    .with(Font::Size(11.0))
+                    .with(TextAlign::Right)
+                    .with(Bounds::next_horiz(64.0)),
+                Control::empty()
+                    .with(Bounds::next_horiz(6.0)),
+                Control::text_box()
+                    .with(value.map(|value| format!("{:.1}", value)).unwrap_or_default())
+                    .with(Font::Size(11.0))
+                    .with(Bounds::stretch_horiz(1.0))
+                    .with((ActionTrigger::SetValue, action.to_string()))
+            ])
+    }
+
+    ///
+    /// Creates the contents of the transform inspector popup
+    ///
+    fn transform_inspector(anchor: TransformAnchor, bounds: Rect, transform: Option<(f64, f64, f64)>) -> Vec<Control> {
+        let (anchor_x, anchor_y) = anchor.point_in(&bounds);
+
+        // The anchor is chosen from a 3x3 grid of buttons
+        let anchor_rows = TransformAnchor::all()
+            .chunks(3)
+            .enumerate()
+            .map(|(row_index, row)| {
+                Control::container()
+                    .with(Bounds::next_vert(ANCHOR_BUTTON_SIZE))
+                    .with(row.iter()
+                        .enumerate()
+                        .map(|(col_index, row_anchor)| {
+                            Control::button()
+                                .with(State::Selected(Property::Bool(*row_anchor == anchor)))
+                                .with((ActionTrigger::Click, format!("SetAnchor-{}", row_index*3 + col_index)))
+                                .with(Bounds::next_horiz(ANCHOR_BUTTON_SIZE))
+                        })
+                        .collect::<Vec<_>>())
+            })
+            .collect::<Vec<_>>();
+
+        vec![
+            Control::label()
+                .with("Transform")
+                .with(Font::Size(13.0))
+                .with(Font::Weight(FontWeight::Light))
+                .with(TextAlign::Center)
+                .with(Bounds::next_vert(24.0)),
+            Control::container()
+                .with(Bounds::next_vert(ANCHOR_BUTTON_SIZE*3.0))
+                .with(vec![
+                    Control::label()
+                        .with("Anchor")
+                        .with(Font::Size(11.0))
+                        .with(TextAlign::Right)
+                        .with(Bounds::next_horiz(64.0)),
+                    Control::empty()
+                        .with(Bounds::next_horiz(6.0)),
+                    Control::container()
+                        .with(Bounds::next_horiz(ANCHOR_BUTTON_SIZE*3.0))
+                        .with(anchor_rows)
+                ]),
+            Control::empty()
+                .with(Bounds::next_vert(4.0)),
+            Self::transform_field("X", Some(anchor_x), "SetX"),
+            Self::transform_field("Y", Some(anchor_y), "SetY"),
+            Self::transform_field("Width", Some(bounds.width() as f64), "SetWidth"),
+            Self::transform_field("Height", Some(bounds.height() as f64), "SetHeight"),
+            Self::transform_field("Rotation", transform.map(|(rotation, _, _)| rotation), "SetRotation"),
+            Self::transform_field("Scale X %", transform.map(|(_, scale_x, _)| scale_x * 100.0), "SetScaleX"),
+            Self::transform_field("Scale Y %", transform.map(|(_, _, scale_y)| scale_y * 100.0), "SetScaleY")
+        ]
+    }
+
     ///
     /// Creates the images for this controller
     ///
@@ -75,6 +350,9 @@ impl<Anim: 'static+EditableAnimation+Animation> SelectMenuController<Anim> {
         let align_middle    = images.register(svg_static(include_bytes!("../../svg/selection_controls/align_middle.svg")));
         let align_bottom    = images.register(svg_static(include_bytes!("../../svg/selection_controls/align_bottom.svg")));
 
+        let distribute_h    = images.register(svg_static(include_bytes!("../../svg/selection_controls/distribute_horizontal.svg")));
+        let distribute_v    = images.register(svg_static(include_bytes!("../../svg/selection_controls/distribute_vertical.svg")));
+
         let flip_horiz      = images.register(svg_static(include_bytes!("../../svg/selection_controls/flip_horizontal.svg")));
         let flip_vert       = images.register(svg_static(include_bytes!("../../svg/selection_controls/flip_vertical.svg")));
 
@@ -83,6 +361,7 @@ impl<Anim: 'static+EditableAnimation+Animation> SelectMenuController<Anim> {
         let path_add        = images.register(svg_static(include_bytes!("../../svg/selection_controls/add.svg")));
         let path_subtract   = images.register(svg_static(include_bytes!("../../svg/selection_controls/subtract.svg")));
         let path_intersect  = images.register(svg_static(include_bytes!("../../svg/selection_controls/intersect.svg")));
+        let path_exclude    = images.register(svg_static(include_bytes!("../../svg/selection_controls/exclude.svg")));
 
         images.assign_name(&order_to_back, "OrderToBack");
         images.assign_name(&order_behind, "OrderBehind");
@@ -96,6 +375,9 @@ impl<Anim: 'static+EditableAnimation+Animation> SelectMenuController<Anim> {
         images.assign_name(&align_middle, "AlignMiddle");
         images.assign_name(&align_bottom, "AlignBottom");
 
+        images.assign_name(&distribute_h, "DistributeHorizontally");
+        images.assign_name(&distribute_v, "DistributeVertically");
+
         images.assign_name(&flip_horiz, "FlipHorizontal");
         images.assign_name(&flip_vert, "FlipVertical");
 
@@ -104,6 +386,7 @@ impl<Anim: 'static+EditableAnimation+Animation> SelectMenuController<Anim> {
         images.assign_name(&path_add, "PathAdd");
         images.assign_name(&path_subtract, "PathSubtract");
         images.assign_name(&path_intersect, "PathIntersect");
+        images.assign_name(&path_exclude, "PathExclude");
 
         images
     }
@@ -111,7 +394,7 @@ impl<Anim: 'static+EditableAnimation+Animation> SelectMenuController<Anim> {
     ///
     /// Creates the UI for the select menu controller
     ///
-    fn ui(images: &ResourceManager<Image>, tool_model: &SelectToolModel) -> BindRef<Control> {
+    fn ui(images: &ResourceManager<Image>, tool_model: &SelectToolModel, editing_symbol: BindRef<Option<u64>>, inspector: (Binding<bool>, Binding<TransformAnchor>, BindRef<Option<Rect>>, BindRef<Option<(f64, f64, f64)>>)) -> BindRef<Control> {
         // Fetch the images
         let order_to_back       = images.get_named_resource("OrderToBack");
         let order_behind        = images.get_named_resource("OrderBehind");
@@ -124,6 +407,9 @@ impl<Anim: 'static+EditableAnimation+Animation> SelectMenuController<Anim> {
         let align_top           = images.get_named_resource("AlignTop");
         let align_middle        = images.get_named_resource("AlignMiddle");
         let align_bottom        = images.get_named_resource("AlignBottom");
+
+        let distribute_h        = images.get_named_resource("DistributeHorizontally");
+        let distribute_v        = images.get_named_resource("DistributeVertically");
         
         let flip_horiz          = images.get_named_resource("FlipHorizontal");
         let flip_vert           = images.get_named_resource("FlipVertical");
@@ -133,10 +419,16 @@ impl<Anim: 'static+EditableAnimation+Animation> SelectMenuController<Anim> {
         let path_add            = images.get_named_resource("PathAdd");
         let path_subtract       = images.get_named_resource("PathSubtract");
         let path_intersect      = images.get_named_resource("PathIntersect");
+        let path_exclude        = images.get_named_resource("PathExclude");
 
         // Parts of the model
         let anything_selected   = tool_model.anything_selected.clone();
         let num_selected        = tool_model.num_elements_selected.clone();
+        let snap_to_grid        = tool_model.snap_to_grid.clone();
+        let grid_size           = tool_model.grid_size.clone();
+        let smart_guides        = tool_model.smart_guides.clone();
+
+        let (transform_open, anchor, selection_bounds, selection_transform) = inspector;
 
         let ui              =
             computed(move || {
@@ -150,6 +442,7 @@ impl<Anim: 'static+EditableAnimation+Animation> SelectMenuController<Anim> {
 
                 let anything_selected   = anything_selected.get();
                 let multi_select        = num_selected > 1;
+                let editing_symbol      = editing_symbol.get().is_some();
 
                 // Pick the control sets based on the selection
                 let order_controls = if anything_selected { 
@@ -262,6 +555,27 @@ impl<Anim: 'static+EditableAnimation+Animation> SelectMenuController<Anim> {
                                     .with(Bounds::next_horiz(28.0))
                                     .with(ControlAttribute::Padding((1, 1), (7, 3)))
                             ]),
+                        Control::empty()
+                            .with(Bounds::next_horiz(4.0)),
+                        Control::container()
+                            .with(Hint::Class("button-group".to_string()))
+                            .with(ControlAttribute::Padding((0,2), (0,2)))
+                            .with(Font::Size(9.0))
+                            .with(Bounds::next_horiz(28.0*2.0))
+                            .with(vec![
+                                Control::button()
+                                    .with(vec![Control::empty().with(distribute_h.clone()).with(TextAlign::Center).with(Bounds::fill_all())])
+                                    .with(Font::Size(10.0))
+                                    .with((ActionTrigger::Click, "DistributeHorizontally"))
+                                    .with(Bounds::next_horiz(28.0))
+                                    .with(ControlAttribute::Padding((7, 1), (1, 3))),
+                                Control::button()
+                                    .with(vec![Control::empty().with(distribute_v.clone()).with(TextAlign::Center).with(Bounds::fill_all())])
+                                    .with(Font::Size(10.0))
+                                    .with((ActionTrigger::Click, "DistributeVertically"))
+                                    .with(Bounds::next_horiz(28.0))
+                                    .with(ControlAttribute::Padding((1, 1), (7, 3)))
+                            ]),
                     ]
                 } else {
                     vec![]
@@ -312,7 +626,7 @@ impl<Anim: 'static+EditableAnimation+Animation> SelectMenuController<Anim> {
                             .with(Hint::Class("button-group".to_string()))
                             .with(ControlAttribute::Padding((0,2), (0,2)))
                             .with(Font::Size(9.0))
-                            .with(Bounds::next_horiz(22.0*2.0 + 28.0*2.0))
+                            .with(Bounds::next_horiz(22.0*3.0 + 28.0*2.0))
                             .with(vec![
                                 Control::button()
                                     .with(vec![Control::empty().with(group.clone()).with(TextAlign::Center).with(Bounds::fill_all())])
@@ -336,6 +650,12 @@ impl<Anim: 'static+EditableAnimation+Animation> SelectMenuController<Anim> {
                                     .with(vec![Control::empty().with(path_intersect.clone()).with(TextAlign::Center).with(Bounds::fill_all())])
                                     .with(Font::Size(10.0))
                                     .with((ActionTrigger::Click, "PathIntersect"))
+                                    .with(Bounds::next_horiz(22.0))
+                                    .with(ControlAttribute::Padding((0, 0), (0, 2))),
+                                Control::button()
+                                    .with(vec![Control::empty().with(path_exclude.clone()).with(TextAlign::Center).with(Bounds::fill_all())])
+                                    .with(Font::Size(10.0))
+                                    .with((ActionTrigger::Click, "PathExclude"))
                                     .with(Bounds::next_horiz(28.0))
                                     .with(ControlAttribute::Padding((0, 0), (6, 2)))
                             ])
@@ -344,11 +664,154 @@ impl<Anim: 'static+EditableAnimation+Animation> SelectMenuController<Anim> {
                     vec![]
                 };
 
+                // Symbols can be created at any time, entered when a single element is selected and exited while one is being edited
+                let symbol_buttons = vec![
+                        Some(Control::button()
+                            .with(vec![Control::label().with("New").with(TextAlign::Center).with(Bounds::fill_all())])
+                            .with(Font::Size(10.0))
+                            .with(Hover::Tooltip("Create a new symbol and start editing it".to_string()))
+                            .with((ActionTrigger::Click, "NewSymbol"))
+                            .with(Bounds::next_horiz(40.0))),
+                        if num_selected == 1 {
+                            Some(Control::button()
+                                .with(vec![Control::label().with("Enter").with(TextAlign::Center).with(Bounds::fill_all())])
+                                .with(Font::Size(10.0))
+                                .with(Hover::Tooltip("Edit the symbol displayed by the selected instance".to_string()))
+                                .with((ActionTrigger::Click, "EnterSymbol"))
+                                .with(Bounds::next_horiz(40.0)))
+                        } else {
+                            None
+                        },
+                        if editing_symbol {
+                            Some(Control::button()
+                                .with(vec![Control::label().with("Exit").with(TextAlign::Center).with(Bounds::fill_all())])
+                                .with(Font::Size(10.0))
+                                .with(Hover::Tooltip("Stop editing the symbol and return to the main animation".to_string()))
+                                .with((ActionTrigger::Click, "ExitSymbol"))
+                                .with(Bounds::next_horiz(40.0)))
+                        } else {
+                            None
+                        }
+                    ].into_iter()
+                    .flatten()
+                    .collect::<Vec<_>>();
+
+                let symbol_controls = vec![
+                    controls::divider(),
+
+                    Control::label()
+                        .with("Symbol:")
+                        .with(TextAlign::Right)
+                        .with(Font::Size(13.0))
+                        .with(Bounds::next_horiz(48.0)),
+                    Control::empty()
+                        .with(Bounds::next_horiz(4.0)),
+                    Control::container()
+                        .with(Hint::Class("button-group".to_string()))
+                        .with(ControlAttribute::Padding((0,2), (0,2)))
+                        .with(Font::Size(9.0))
+                        .with(Bounds::next_horiz(40.0 * (symbol_buttons.len() as f32)))
+                        .with(symbol_buttons)
+                ];
+
+                // The transform inspector lets the user edit the position, size, rotation and scale of the selection as numbers
+                let transform_controls = match (anything_selected, selection_bounds.get()) {
+                    (true, Some(bounds)) => {
+                        let transform_open  = transform_open.get();
+                        let popup           = if transform_open {
+                            vec![
+                                Control::popup()
+                                    .with(Popup::Direction(PopupDirection::Below))
+                                    .with(Popup::Size(200, (24.0 + ANCHOR_BUTTON_SIZE*3.0 + 4.0 + TRANSFORM_ROW_HEIGHT*7.0 + 12.0) as u32))
+                                    .with(Popup::Offset(14))
+                                    .with(ControlAttribute::ZIndex(1000))
+                                    .with(Popup::IsOpen(Property::Bind("EditTransform".to_string())))
+                                    .with((ActionTrigger::Dismiss, "HideTransformPopup"))
+                                    .with(vec![
+                                        Control::container()
+                                            .with(Bounds::fill_all())
+                                            .with(ControlAttribute::Padding((8, 4), (12, 8)))
+                                            .with(Self::transform_inspector(anchor.get(), bounds, selection_transform.get()))
+                                    ])
+                            ]
+                        } else {
+                            vec![]
+                        };
+
+                        vec![
+                            controls::divider(),
+
+                            Control::empty()
+                                .with(Bounds::next_horiz(4.0)),
+                            Control::container()
+                                .with(Hint::Class("button-group".to_string()))
+                                .with(ControlAttribute::Padding((0,2), (0,2)))
+                                .with(Font::Size(9.0))
+                                .with(Bounds::next_horiz(64.0))
+                                .with(vec![
+                                    Control::button()
+                                        .with(vec![Control::label().with("Transform").with(TextAlign::Center).with(Bounds::fill_all())].into_iter().chain(popup).collect::<Vec<_>>())
+                                        .with(Font::Size(10.0))
+                                        .with(State::Selected(Property::Bool(transform_open)))
+                                        .with(if !transform_open { (ActionTrigger::Click, "ShowTransformPopup") } else { (ActionTrigger::Click, "HideTransformPopup") })
+                                        .with(Bounds::next_horiz(64.0))
+                                ])
+                        ]
+                    }
+
+                    _ => vec![]
+                };
+
+                // Snapping is used when dragging or scaling the selection
+                let snap_controls = vec![
+                    controls::divider(),
+
+                    Control::label()
+                        .with("Snap:")
+                        .with(TextAlign::Right)
+                        .with(Font::Size(13.0))
+                        .with(Bounds::next_horiz(48.0)),
+                    Control::empty()
+                        .with(Bounds::next_horiz(4.0)),
+                    Control::container()
+                        .with(Hint::Class("button-group".to_string()))
+                        .with(ControlAttribute::Padding((0,2), (0,2)))
+                        .with(Font::Size(9.0))
+                        .with(Bounds::next_horiz(44.0*2.0))
+                        .with(vec![
+                            Control::button()
+                                .with(vec![Control::label().with("Grid").with(TextAlign::Center).with(Bounds::fill_all())])
+                                .with(Font::Size(10.0))
+                                .with(State::Selected(Property::Bool(snap_to_grid.get())))
+                                .with(Hover::Tooltip("Snap to the grid when moving or scaling the selection".to_string()))
+                                .with((ActionTrigger::Click, "ToggleSnapToGrid"))
+                                .with(Bounds::next_horiz(44.0)),
+                            Control::button()
+                                .with(vec![Control::label().with("Guides").with(TextAlign::Center).with(Bounds::fill_all())])
+                                .with(Font::Size(10.0))
+                                .with(State::Selected(Property::Bool(smart_guides.get())))
+                                .with(Hover::Tooltip("Snap to the edges and centres of other elements and the canvas".to_string()))
+                                .with((ActionTrigger::Click, "ToggleSmartGuides"))
+                                .with(Bounds::next_horiz(44.0))
+                        ]),
+                    Control::empty()
+                        .with(Bounds::next_horiz(4.0)),
+                    Control::text_box()
+                        .with(format!("{}", grid_size.get()))
+                        .with(Font::Size(11.0))
+                        .with(Hover::Tooltip("Grid spacing".to_string()))
+                        .with((ActionTrigger::SetValue, "SetGridSize"))
+                        .with(Bounds::next_horiz(36.0))
+                ];
+
                 // Extra controls to display when there's a selection to edit
                 let selection_controls = order_controls.into_iter()
                     .chain(align_controls)
                     .chain(flip_controls)
-                    .chain(group_controls);
+                    .chain(transform_controls)
+                    .chain(group_controls)
+                    .chain(symbol_controls)
+                    .chain(snap_controls);
 
                 // Build the control
                 Control::container()
@@ -392,8 +855,52 @@ impl<Anim: 'static+EditableAnimation+Animation> Controller for SelectMenuControl
         Some(Arc::clone(&self.images))
     }
 
-    fn action(&self, action_id: &str, _action_parameter: &ActionParameter) {
+    fn get_viewmodel(&self) -> Option<Arc<dyn ViewModel>> {
+        Some(self.view_model.clone())
+    }
+
+    fn action(&self, action_id: &str, action_parameter: &ActionParameter) {
         match action_id {
+            // Transform inspector
+            "ShowTransformPopup"    => self.transform_open.set(true),
+            "HideTransformPopup"    => self.transform_open.set(false),
+
+            "SetX" | "SetY" | "SetWidth" | "SetHeight" |
+            "SetRotation" | "SetScaleX" | "SetScaleY" => {
+                let value       = match action_parameter {
+                    ActionParameter::Value(PropertyValue::String(value))    => value.trim().parse::<f64>().ok(),
+                    _                                                       => None
+                };
+                let bounds      = self.selection_bounds.get();
+                let transform   = value.and_then(|value| bounds.and_then(|bounds| transform_for_value(action_id, value, self.anchor.get(), bounds, self.selection_transform.get())));
+
+                if let Some(transform) = transform {
+                    let selection   = self.selection_in_order.get();
+
+                    let _           = self.edit.future(move |animation| {
+                        animation.publish(Arc::new(vec![AnimationEdit::Element(selection.iter().cloned().collect(), 
+                            ElementEdit::Transform(transform))]))
+                    });
+                    self.edit.sync(|_| { });
+                    self.timeline.invalidate_canvas();
+                }
+            }
+
+            // Snapping
+            "ToggleSnapToGrid"  => self.snap_to_grid.set(!self.snap_to_grid.get()),
+            "ToggleSmartGuides" => self.smart_guides.set(!self.smart_guides.get()),
+
+            "SetGridSize"       => {
+                if let ActionParameter::Value(PropertyValue::String(grid_size)) = action_parameter {
+                    if let Ok(grid_size) = grid_size.trim().parse::<f32>() {
+                        if grid_size > 0.0 {
+                            self.grid_size.set(grid_size);
+                            self.snap_to_grid.set(true);
+                        }
+                    }
+                }
+            }
+
             // Ordering
             "MoveToFront" | "MoveForwards" | "MoveBackwards" | "MoveToBack" => {
                 let selection                       = self.selection_in_order.get();
@@ -450,6 +957,39 @@ impl<Anim: 'static+EditableAnimation+Animation> Controller for SelectMenuControl
                 self.timeline.invalidate_canvas();
             }
 
+            // Distributing with equal spacing
+            "DistributeHorizontally" | "DistributeVertically" => {
+                let selection   = self.selection_in_order.get();
+                let distribute  = if action_id == "DistributeHorizontally" { ElementTransform::DistributeHorizontally } else { ElementTransform::DistributeVertically };
+
+                let _           = self.edit.future(move |animation| {
+                    animation.publish(Arc::new(vec![AnimationEdit::Element(selection.iter().cloned().collect(), 
+                        ElementEdit::Transform(vec![distribute]))]))
+                });
+                self.edit.sync(|_| { });
+                self.timeline.invalidate_canvas();
+            }
+
+            // Path arithmetic
+            "PathAdd" | "PathSubtract" | "PathIntersect" | "PathExclude" => {
+                let selection   = self.selection_in_order.get();
+                let combine     = match action_id {
+                    "PathAdd"       => PathCombine::Union,
+                    "PathSubtract"  => PathCombine::Subtract,
+                    "PathIntersect" => PathCombine::Intersect,
+                    "PathExclude"   => PathCombine::Exclude,
+
+                    _               => PathCombine::Union
+                };
+
+                let _           = self.edit.future(move |animation| {
+                    animation.publish(Arc::new(vec![AnimationEdit::Element(selection.iter().cloned().collect(), 
+                        ElementEdit::CombinePaths(combine))]))
+                });
+                self.edit.sync(|_| { });
+                self.timeline.invalidate_canvas();
+            }
+
             // Flipping about an axis
             "FlipHorizontal" => {
                 let selection   = self.selection_in_order.get();
@@ -473,7 +1013,156 @@ impl<Anim: 'static+EditableAnimation+Animation> Controller for SelectMenuControl
                 self.timeline.invalidate_canvas();
             }
 
-            _ => { }
+            // Symbols
+            "NewSymbol" => {
+                let symbol_id       = self.timeline.unused_symbol_id();
+                let layer_id        = self.timeline.unused_layer_id();
+                let when            = self.timeline.current_time.get();
+                let instance_layer  = self.timeline.selected_layer.get();
+
+                // Create the symbol with a single layer that has a keyframe at the start
+                let mut edits = vec![
+                    AnimationEdit::AddNewSymbol(symbol_id),
+                    AnimationEdit::Symbol(symbol_id, SymbolEdit::SetName(format!("Symbol {}", symbol_id))),
+                    AnimationEdit::Symbol(symbol_id, SymbolEdit::AddNewLayer(layer_id)),
+                    AnimationEdit::Layer(layer_id, LayerEdit::SetName("Layer 1".to_string())),
+                    AnimationEdit::Layer(layer_id, LayerEdit::AddKeyFrame(Duration::from_millis(0)))
+                ];
+
+                // Display an instance of the symbol in the selected layer
+                if let Some(instance_layer) = instance_layer {
+                    edits.push(AnimationEdit::Layer(instance_layer, LayerEdit::CreateSymbolInstance(when, ElementId::Unassigned, SymbolInstance::new(symbol_id))));
+                }
+
+                let _ = self.edit.future(move |animation| {
+                    animation.publish(Arc::new(edits))
+                });
+                self.edit.sync(|_| { });
+
+                // Start editing the new symbol
+                self.timeline.enter_symbol(symbol_id);
+                self.timeline.update_keyframe_bindings();
+                self.timeline.invalidate_canvas();
+            }
+
+            "EnterSymbol" => {
+                // Find the symbol displayed by the selected element
+                let selected_id = self.selection_in_order.get().iter().cloned().nth(0);
+                let symbol_id   = self.elements.get().iter()
+                    .filter(|(element, _)| Some(element.id()) == selected_id)
+                    .filter_map(|(element, _)| match element {
+                        Vector::Symbol(symbol)  => Some(symbol.instance().symbol_id),
+                        _                       => None
+                    })
+                    .nth(0);
+
+                if let Some(symbol_id) = symbol_id {
+                    self.timeline.enter_symbol(symbol_id);
+                    self.timeline.update_keyframe_bindings();
+                    self.timeline.invalidate_canvas();
+                }
+            }
+
+            "ExitSymbol" => {
+                self.timeline.exit_symbol();
+                self.timeline.update_keyframe_bindings();
+                self.timeline.invalidate_canvas();
+            }
+
+            _ => {
+                // 'SetAnchor-x' chooses the anchor point for the transform inspector
+                if action_id.starts_with("SetAnchor-") {
+                    let (_, anchor_index) = action_id.split_at("SetAnchor-".len());
+
+                    if let Some(anchor) = anchor_index.parse::<usize>().ok().and_then(|index| TransformAnchor::all().get(index).cloned()) {
+                        self.anchor.set(anchor);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use std::f64;
+
+    fn bounds() -> Rect {
+        Rect::with_points(10.0, 20.0, 110.0, 70.0)
+    }
+
+    fn rotation_in(transform: &Option<Vec<ElementTransform>>) -> f64 {
+        match transform.as_ref().map(|transform| transform[1]) {
+            Some(ElementTransform::Rotate(theta))   => theta,
+            _                                       => panic!("Not a rotation: {:?}", transform)
         }
     }
+
+    #[test]
+    fn anchor_points_are_on_the_bounds() {
+        assert!(TransformAnchor::TopLeft.point_in(&bounds()) == (10.0, 70.0));
+        assert!(TransformAnchor::Center.point_in(&bounds()) == (60.0, 45.0));
+        assert!(TransformAnchor::BottomRight.point_in(&bounds()) == (110.0, 20.0));
+    }
+
+    #[test]
+    fn set_x_moves_the_anchor() {
+        let transform = transform_for_value("SetX", 200.0, TransformAnchor::TopLeft, bounds(), None);
+
+        assert!(transform == Some(vec![ElementTransform::SetAnchor(10.0, 70.0), ElementTransform::MoveTo(200.0, 70.0)]));
+    }
+
+    #[test]
+    fn set_y_moves_the_anchor() {
+        let transform = transform_for_value("SetY", 100.0, TransformAnchor::Center, bounds(), None);
+
+        assert!(transform == Some(vec![ElementTransform::SetAnchor(60.0, 45.0), ElementTransform::MoveTo(60.0, 100.0)]));
+    }
+
+    #[test]
+    fn set_width_scales_around_the_anchor() {
+        let transform = transform_for_value("SetWidth", 200.0, TransformAnchor::BottomRight, bounds(), Some((0.0, 1.0, 1.0)));
+
+        assert!(transform == Some(vec![ElementTransform::SetAnchor(110.0, 20.0), ElementTransform::Scale(2.0, 1.0)]));
+    }
+
+    #[test]
+    fn zero_width_is_ignored() {
+        let transform = transform_for_value("SetWidth", 0.0, TransformAnchor::Center, bounds(), None);
+
+        assert!(transform == None);
+    }
+
+    #[test]
+    fn set_scale_is_relative_to_the_current_scale() {
+        let transform = transform_for_value("SetScaleX", 100.0, TransformAnchor::Center, bounds(), Some((0.0, 2.0, 1.0)));
+
+        assert!(transform == Some(vec![ElementTransform::SetAnchor(60.0, 45.0), ElementTransform::Scale(0.5, 1.0)]));
+    }
+
+    #[test]
+    fn set_rotation_is_relative_to_the_current_rotation() {
+        let transform = transform_for_value("SetRotation", 90.0, TransformAnchor::Center, bounds(), Some((30.0, 1.0, 1.0)));
+
+        assert!((rotation_in(&transform) - 60.0f64.to_radians()).abs() < 1e-6);
+    }
+
+    #[test]
+    fn set_rotation_for_multiple_elements_rotates_by_the_value() {
+        let transform = transform_for_value("SetRotation", 45.0, TransformAnchor::Center, bounds(), None);
+
+        assert!((rotation_in(&transform) - 45.0f64.to_radians()).abs() < 1e-6);
+    }
+
+    #[test]
+    fn rotation_and_scale_combine_transformations() {
+        let transformations                 = vec![Transformation::Rotate(f64::consts::PI/2.0, (0.0, 0.0)), Transformation::Scale(2.0, 3.0, (0.0, 0.0)), Transformation::Rotate(f64::consts::PI/2.0, (0.0, 0.0))];
+        let (rotation, scale_x, scale_y)    = rotation_and_scale(transformations.iter());
+
+        assert!((rotation - 180.0).abs() < 1e-6);
+        assert!(scale_x == 2.0);
+        assert!(scale_y == 3.0);
+    }
 }
@@ -3,6 +3,7 @@ use super::frame::*;
 use super::timeline::*;
 use super::selection::*;
 use super::onion_skin::*;
+use super::viewport::*;
 
 use flo_stream::*;
 use flo_binding::*;
@@ -37,6 +38,9 @@ pub struct FloModel<Anim: Animation> {
     /// The onion skin model
     onion_skin: OnionSkinModel<Anim>,
 
+    /// The viewport model
+    viewport: ViewportModel,
+
     /// The size of the animation
     pub size: BindRef<(f64, f64)>,
 
@@ -60,9 +64,10 @@ impl<Anim: EditableAnimation+Animation+'static> FloModel<Anim> {
         let tools               = ToolModel::new();
         let timeline            = TimelineModel::new(Arc::clone(&animation), edit_publisher.subscribe());
         let frame_edit_counter  = bind(0);
-        let frame               = FrameModel::new(Arc::clone(&animation), edit_publisher.subscribe(), BindRef::new(&timeline.current_time), BindRef::new(&frame_edit_counter), BindRef::new(&timeline.selected_layer));
+        let frame               = FrameModel::new(Arc::clone(&animation), edit_publisher.subscribe(), BindRef::new(&timeline.current_time), BindRef::new(&frame_edit_counter), BindRef::new(&timeline.selected_layer), timeline.layers.clone());
         let selection           = SelectionModel::new(&frame, &timeline);
         let onion_skin          = OnionSkinModel::new(Arc::clone(&animation), &timeline);
+        let viewport            = ViewportModel::new();
 
         let size_binding        = bind(animation.size());
         let edit_publisher      = Arc::new(Desync::new(edit_publisher));
@@ -75,6 +80,7 @@ impl<Anim: EditableAnimation+Animation+'static> FloModel<Anim> {
             frame:              frame,
             selection:          selection,
             onion_skin:         onion_skin,
+            viewport:           viewport,
 
             size:               BindRef::from(size_binding.clone()),
             size_binding:       size_binding,
@@ -124,16 +130,41 @@ impl<Anim: EditableAnimation+Animation+'static> FloModel<Anim> {
                 },
 
                 AddNewLayer(_)              |
+                AddNewLayerFolder(_)        |
                 RemoveLayer(_)              |
                 Element(_, _)               |
                 Motion(_, _)                |
+                Bone(_, _)                  |
                 Layer(_, Path(_, _))        |
                 Layer(_, Paint(_, _))       => {
                     advance_edit_counter = true;
                 }
 
-                Layer(_, AddKeyFrame(_))    |
-                Layer(_, RemoveKeyFrame(_)) => {
+                Layer(_, AddKeyFrame(_))            |
+                Layer(_, RemoveKeyFrame(_))         |
+                Layer(_, MoveKeyFrame(_, _))        |
+                Layer(_, DuplicateKeyFrame(_, _))   |
+                Layer(_, SetKeyFrameTween(_, _))    => {
+                    advance_edit_counter = true;
+                },
+
+                AddNewSymbol(_)                             |
+                Symbol(_, _)                                |
+                Layer(_, CreateSymbolInstance(_, _, _))     => {
+                    advance_edit_counter = true;
+                },
+
+                RemoveSymbol(symbol_id) => {
+                    // Stop editing a symbol if it's removed
+                    if timeline.editing_symbol.get() == Some(*symbol_id) {
+                        timeline.exit_symbol();
+                    }
+                    advance_edit_counter = true;
+                },
+
+                Layer(_, SetReference(_))           |
+                Layer(_, AddReferenceImage(_, _))   |
+                Layer(_, RemoveReferenceImage(_))   => {
                     advance_edit_counter = true;
                 },
 
@@ -144,8 +175,13 @@ impl<Anim: EditableAnimation+Animation+'static> FloModel<Anim> {
                     advance_edit_counter = true;
                 },
 
-                Layer(layer_id, SetOrdering(at_index)) => {
-                    unimplemented!("Cannot update model with layer ordering yet")
+                Layer(_, SetOrdering(_))            |
+                Layer(_, SetParent(_))              |
+                Layer(_, SetHidden(_))              |
+                Layer(_, SetLocked(_))              |
+                Layer(_, SetCollapsed(_))           => {
+                    // The order and visibility of the layers affects how the frame is drawn
+                    advance_edit_counter = true;
                 }
             }
         }
@@ -209,6 +245,13 @@ impl<Anim: Animation+'static> FloModel<Anim> {
         &self.onion_skin
     }
 
+    ///
+    /// Retrieves the viewport model for the main canvas
+    ///
+    pub fn viewport(&self) -> &ViewportModel {
+        &self.viewport
+    }
+
     ///
     /// Retrieves the frame update binding for this animation
     ///
@@ -235,6 +278,7 @@ impl<Anim: Animation> Clone for FloModel<Anim> {
             frame:              self.frame.clone(),
             selection:          self.selection.clone(),
             onion_skin:         self.onion_skin.clone(),
+            viewport:           self.viewport.clone(),
 
             size:               self.size.clone(),
             size_binding:       self.size_binding.clone(),
@@ -280,6 +324,20 @@ impl<Anim: Animation> Animation for FloModel<Anim> {
         self.animation.get_layer_with_id(layer_id)
     }
 
+    ///
+    /// Retrieves the IDs of the symbols in this animation
+    ///
+    fn get_symbol_ids(&self) -> Vec<u64> {
+        self.animation.get_symbol_ids()
+    }
+
+    ///
+    /// Retrieves the symbol with the specified ID from this animation
+    ///
+    fn get_symbol_with_id(&self, symbol_id: u64) -> Option<Arc<dyn Symbol>> {
+        self.animation.get_symbol_with_id(symbol_id)
+    }
+
     ///
     /// Retrieves the total number of items that have been performed on this animation
     ///
@@ -294,6 +352,20 @@ impl<Anim: Animation> Animation for FloModel<Anim> {
         self.animation.read_edit_log(range)
     }
 
+    ///
+    /// Retrieves the named snapshots of the edit log that are stored with this animation
+    ///
+    fn get_snapshots(&self) -> Vec<EditLogCheckpoint> {
+        self.animation.get_snapshots()
+    }
+
+    ///
+    /// Retrieves the data for an asset stored with this animation
+    ///
+    fn get_asset(&self, asset_id: u64) -> Option<Arc<Vec<u8>>> {
+        self.animation.get_asset(asset_id)
+    }
+
     ///
     /// Supplies a reference which can be used to find the motions associated with this animation
     ///
@@ -350,6 +422,41 @@ impl<Anim: 'static+Animation+EditableAnimation> EditableAnimation for FloModel<A
         self.animation.assign_element_id()
     }
 
+    ///
+    /// Assigns a new unique ID for storing an asset
+    ///
+    fn assign_asset_id(&self) -> u64 {
+        self.animation.assign_asset_id()
+    }
+
+    ///
+    /// Stores the data for an asset with this animation
+    ///
+    fn write_asset(&self, asset_id: u64, data: Arc<Vec<u8>>) {
+        self.animation.write_asset(asset_id, data)
+    }
+
+    ///
+    /// Replaces the edit log for this animation without changing the animation itself
+    ///
+    fn replace_edit_log(&self, num_replaced: usize, edits: Vec<AnimationEdit>, snapshots: Vec<EditLogCheckpoint>) {
+        self.animation.replace_edit_log(num_replaced, edits, snapshots)
+    }
+
+    ///
+    /// Stores a named snapshot of the animation as it is after the edits that have been performed so far
+    ///
+    fn create_snapshot(&self, name: &str) {
+        self.animation.create_snapshot(name)
+    }
+
+    ///
+    /// Removes a named snapshot from this animation
+    ///
+    fn delete_snapshot(&self, name: &str) {
+        self.animation.delete_snapshot(name)
+    }
+
     ///
     /// Flushes any caches this might have (forces reload from data storage)
     ///
@@ -386,4 +493,47 @@ mod test {
         assert!(model.size()        == (800.0, 600.0));
         assert!(model.size.get()    == (800.0, 600.0));
     }
+
+    #[test]
+    fn elements_on_locked_layers_are_locked() {
+        let in_memory_store = InMemoryStorage::new();
+        let animation       = create_animation_editor(move |commands| in_memory_store.get_responses(commands).boxed());
+        let model           = FloModel::new(animation);
+
+        let square          = Arc::new(vec![
+            PathComponent::Move(PathPoint::new(0.0, 0.0)),
+            PathComponent::Line(PathPoint::new(10.0, 0.0)),
+            PathComponent::Line(PathPoint::new(10.0, 10.0)),
+            PathComponent::Close
+        ]);
+        let create_square   = |layer_id, element_id| {
+            vec![
+                AnimationEdit::Layer(layer_id, LayerEdit::AddKeyFrame(Duration::from_millis(0))),
+                AnimationEdit::Layer(layer_id, LayerEdit::Path(Duration::from_millis(0),
+                    PathEdit::SelectBrush(ElementId::Unassigned, BrushDefinition::Ink(InkDefinition::default()), BrushDrawingStyle::Draw))),
+                AnimationEdit::Layer(layer_id, LayerEdit::Path(Duration::from_millis(0),
+                    PathEdit::BrushProperties(ElementId::Unassigned, BrushProperties::new()))),
+                AnimationEdit::Layer(layer_id, LayerEdit::Path(Duration::from_millis(0),
+                    PathEdit::CreatePath(ElementId::Assigned(element_id), Arc::clone(&square))))
+            ]
+        };
+
+        // Element 100 is on layer 1, which is locked, and element 101 is on layer 2, which isn't
+        executor::block_on(async {
+            let mut edits = vec![AnimationEdit::AddNewLayer(1), AnimationEdit::AddNewLayer(2)];
+            edits.extend(create_square(1, 100));
+            edits.extend(create_square(2, 101));
+            edits.push(AnimationEdit::Layer(1, LayerEdit::SetLocked(true)));
+
+            let mut edit_log = model.edit();
+            edit_log.publish(Arc::new(edits)).await;
+            edit_log.when_empty().await;
+            model.when_complete().await;
+        });
+
+        // Any edit that includes element 100 would change the locked layer
+        assert!(model.timeline().are_elements_locked(&[ElementId::Assigned(100)]));
+        assert!(!model.timeline().are_elements_locked(&[ElementId::Assigned(101)]));
+        assert!(model.timeline().are_elements_locked(&[ElementId::Assigned(101), ElementId::Assigned(100)]));
+    }
 }
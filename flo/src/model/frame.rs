@@ -1,3 +1,5 @@
+use super::layer::*;
+
 use flo_stream::*;
 use flo_binding::*;
 use flo_animation::*;
@@ -43,6 +45,21 @@ pub struct FrameLayerModel {
 
     /// The current frmae for this layer
     pub frame: BindRef<Option<Arc<dyn Frame>>>,
+
+    /// If this is a reference layer, the settings and image that it's displaying in the current frame
+    pub reference: BindRef<Option<ReferenceFrame>>
+}
+
+///
+/// The image displayed by a reference layer in the current frame
+///
+#[derive(Clone)]
+pub struct ReferenceFrame {
+    /// The settings for the reference layer
+    pub settings: ReferenceLayerSettings,
+
+    /// The PNG data for the image being displayed (or None if there's no image at this time)
+    pub png_data: Option<Arc<Vec<u8>>>
 }
 
 ///
@@ -80,7 +97,7 @@ impl FrameModel {
     /// invalidated; the value has no meaning, so any value (for example, the
     /// length of the edit log)
     ///
-    pub fn new<Anim: Animation+'static>(animation: Arc<Anim>, edits: Subscriber<Arc<Vec<AnimationEdit>>>, when: BindRef<Duration>, animation_update: BindRef<u64>, selected_layer: BindRef<Option<u64>>) -> FrameModel {
+    pub fn new<Anim: Animation+'static>(animation: Arc<Anim>, edits: Subscriber<Arc<Vec<AnimationEdit>>>, when: BindRef<Duration>, animation_update: BindRef<u64>, selected_layer: BindRef<Option<u64>>, timeline_layers: BindRef<Vec<LayerModel>>) -> FrameModel {
         // Create the bindings for the current frame state
         let keyframe_selected           = Self::keyframe_selected(Arc::clone(&animation), edits.resubscribe(), when.clone(), selected_layer.clone());
         let previous_and_next_keyframe  = Self::previous_next_keyframes(Arc::clone(&animation), edits.resubscribe(), when.clone(), selected_layer.clone());
@@ -90,15 +107,20 @@ impl FrameModel {
 
         // Create a computed list of layers (because updates are lazy, this will
         // only update when it's actually read)
-        let layers = computed(move || {
+        let drawn_layers    = timeline_layers.clone();
+        let layers          = computed(move || {
             // Claim the frames
             let mut frames = frames.lock().unwrap();
 
             // We bind to the update so this invalidates whenever the update list changes
             animation_update.get();
 
-            // Refresh the frames from the animation
-            let layer_ids = animation.get_layer_ids();
+            // Refresh the frames from the layers in the timeline (folders have no content, and hidden layers aren't drawn)
+            let layer_ids = drawn_layers.get()
+                .into_iter()
+                .filter(|layer| !layer.is_folder && !layer.effectively_hidden)
+                .map(|layer| layer.id)
+                .collect::<Vec<_>>();
 
             // Remove layers that aren't in use any more
             let deleted_layers: Vec<_> = layer_ids
@@ -117,6 +139,9 @@ impl FrameModel {
                     Entry::Vacant(vacant) => {
                         // Create a new bindnig
                         let layer_id            = *layer_id;
+                        let reference_when      = BindRef::clone(&when);
+                        let reference_animation = Arc::clone(&animation);
+                        let reference_update    = animation_update.clone();
                         let when                = BindRef::clone(&when);
                         let frame_animation     = Arc::clone(&animation);
                         let animation_update    = animation_update.clone();
@@ -133,12 +158,28 @@ impl FrameModel {
                                 .map(|layer| layer.get_frame_at_time(when))
                         });
 
+                        // Reference layers also have an image for the current frame
+                        let reference_binding   = ComputedBinding::new_in_context(move || {
+                            // Binds to the animation update and the time in the same way as the frame
+                            reference_update.get();
+                            let when = reference_when.get();
+
+                            // Reference settings and image are read from the layer
+                            let layer       = reference_animation.get_layer_with_id(layer_id)?;
+                            let settings    = layer.reference_settings()?;
+                            let png_data    = layer.reference_image_at_time(when);
+
+                            Some(ReferenceFrame { settings, png_data })
+                        });
+
                         // Add a frame layer model for this frame
                         let frame           = BindRef::new(&frame_binding);
+                        let reference       = BindRef::new(&reference_binding);
 
                         vacant.insert(FrameLayerModel {
                             layer_id:       layer_id,
                             frame:          frame,
+                            reference:      reference
                         });
                     }
                 }
@@ -152,7 +193,7 @@ impl FrameModel {
         });
 
         // The current frame tracks the frame the user has got selected from the set of layers
-        let frame                   = Self::current_frame(selected_layer, layers.clone());
+        let frame                   = Self::current_frame(selected_layer, layers.clone(), timeline_layers);
         let elements                = Self::element_properties(frame.clone());
         let bounding_boxes          = Self::bounding_boxes(elements.clone());
 
@@ -173,13 +214,23 @@ impl FrameModel {
     ///
     /// Returns a binding for the selected frame
     ///
-    fn current_frame<SelectedLayer: 'static+Bound<Option<u64>>, LayerModel: 'static+Bound<Vec<FrameLayerModel>>>(selected_layer: SelectedLayer, layers: LayerModel) -> BindRef<Option<Arc<dyn Frame>>> {
+    fn current_frame<SelectedLayer: 'static+Bound<Option<u64>>, Layers: 'static+Bound<Vec<FrameLayerModel>>>(selected_layer: SelectedLayer, layers: Layers, timeline_layers: BindRef<Vec<LayerModel>>) -> BindRef<Option<Arc<dyn Frame>>> {
         BindRef::new(&computed(move || {
-            let selected_layer_id = selected_layer.get();
+            let selected_layer_id   = selected_layer.get();
+            let selected_locked     = timeline_layers.get()
+                .into_iter()
+                .any(|layer| Some(layer.id) == selected_layer_id && (layer.effectively_locked || layer.is_folder));
+
+            // Locked layers can't be edited, so the tools don't see their content (folders have no content)
+            if selected_locked {
+                return None;
+            }
 
+            // Reference layers have no frame that the tools can interact with
             layers.get()
                 .into_iter()
                 .filter(|layer| Some(layer.layer_id) == selected_layer_id)
+                .filter(|layer| layer.reference.get().is_none())
                 .filter_map(|layer| layer.frame.get())
                 .nth(0)
         }))
@@ -191,7 +242,9 @@ impl FrameModel {
     fn is_key_frame_update(layer_id: u64, edit: &AnimationEdit) -> bool {
         match edit {
             AnimationEdit::Layer(edit_layer_id, LayerEdit::AddKeyFrame(_)) |
-            AnimationEdit::Layer(edit_layer_id, LayerEdit::RemoveKeyFrame(_)) => edit_layer_id == &layer_id,
+            AnimationEdit::Layer(edit_layer_id, LayerEdit::RemoveKeyFrame(_)) |
+            AnimationEdit::Layer(edit_layer_id, LayerEdit::MoveKeyFrame(_, _)) |
+            AnimationEdit::Layer(edit_layer_id, LayerEdit::DuplicateKeyFrame(_, _)) => edit_layer_id == &layer_id,
             _ => false
         }
     }
@@ -1,6 +1,8 @@
 use flo_binding::*;
 use flo_animation::*;
 
+use std::collections::{HashMap};
+
 ///
 /// Viewmodel for a layer
 ///
@@ -10,20 +12,98 @@ pub struct LayerModel {
     pub id: u64,
 
     /// The name of this layer
-    pub name: Binding<String>
+    pub name: Binding<String>,
+
+    /// The ID of the folder that this layer is in
+    pub parent: Option<u64>,
+
+    /// The number of folders this layer is inside
+    pub depth: usize,
+
+    /// True if this layer is a folder
+    pub is_folder: bool,
+
+    /// True if this is a reference layer (which displays images instead of vector content)
+    pub is_reference: bool,
+
+    /// True if this layer has been hidden
+    pub hidden: bool,
+
+    /// True if this layer has been locked
+    pub locked: bool,
+
+    /// True if this is a folder whose contents are hidden in the timeline
+    pub collapsed: bool,
+
+    /// True if this layer or any of the folders it's in is hidden
+    pub effectively_hidden: bool,
+
+    /// True if this layer or any of the folders it's in is locked
+    pub effectively_locked: bool,
+
+    /// True if any of the folders this layer is in are collapsed
+    pub in_collapsed_folder: bool
 }
 
 impl PartialEq for LayerModel {
     fn eq(&self, other: &LayerModel) -> bool {
-        other.id == self.id
+        other.id                            == self.id
+            && other.parent                 == self.parent
+            && other.depth                  == self.depth
+            && other.is_folder              == self.is_folder
+            && other.is_reference           == self.is_reference
+            && other.hidden                 == self.hidden
+            && other.locked                 == self.locked
+            && other.collapsed              == self.collapsed
+            && other.effectively_hidden     == self.effectively_hidden
+            && other.effectively_locked     == self.effectively_locked
+            && other.in_collapsed_folder    == self.in_collapsed_folder
     }
 }
 
 impl LayerModel {
     pub fn new<'a>(layer: &'a dyn Layer) -> LayerModel {
+        let edit_types      = layer.supported_edit_types();
+        let is_folder       = edit_types.contains(&LayerEditType::Folder);
+        let is_reference    = edit_types.contains(&LayerEditType::Reference);
+
         LayerModel {
-            id:     layer.id(),
-            name:   bind(layer.name().unwrap_or_else(|| format!("Layer {}", layer.id())))
+            id:                     layer.id(),
+            name:                   bind(layer.name().unwrap_or_else(|| if is_folder { format!("Folder {}", layer.id()) } else { format!("Layer {}", layer.id()) })),
+            parent:                 layer.parent_folder(),
+            depth:                  0,
+            is_folder:              is_folder,
+            is_reference:           is_reference,
+            hidden:                 layer.is_hidden(),
+            locked:                 layer.is_locked(),
+            collapsed:              layer.is_collapsed(),
+            effectively_hidden:     layer.is_hidden(),
+            effectively_locked:     layer.is_locked(),
+            in_collapsed_folder:    false
+        }
+    }
+
+    ///
+    /// Updates the depth and the inherited hidden, locked and collapsed states of a set of layers
+    ///
+    /// The layers must be in the order returned by the animation, where folders always appear before the layers inside them.
+    ///
+    pub fn update_hierarchy(layers: &mut Vec<LayerModel>) {
+        let mut folders: HashMap<u64, (usize, bool, bool, bool)> = HashMap::new();
+
+        for layer in layers.iter_mut() {
+            let (depth, parent_hidden, parent_locked, parent_collapsed) = layer.parent
+                .and_then(|parent_id| folders.get(&parent_id).cloned())
+                .unwrap_or((0, false, false, false));
+
+            layer.depth                 = depth;
+            layer.effectively_hidden    = layer.hidden || parent_hidden;
+            layer.effectively_locked    = layer.locked || parent_locked;
+            layer.in_collapsed_folder   = parent_collapsed;
+
+            if layer.is_folder {
+                folders.insert(layer.id, (depth+1, layer.effectively_hidden, layer.effectively_locked, parent_collapsed || layer.collapsed));
+            }
         }
     }
 }
@@ -18,6 +18,9 @@ pub struct SelectionModel {
     /// The selected elements as they are ordered in the current frame (selected elements not in the current frame are excluded)
     pub selection_in_order: BindRef<Arc<Vec<ElementId>>>,
 
+    /// The IDs and paths of the translation motions attached to the selected elements in the current frame
+    pub selected_motions: BindRef<Arc<Vec<(ElementId, TimeCurve)>>>,
+
     /// The binding for the selected element (used when updating)
     selected_elements_binding: Binding<Arc<HashSet<ElementId>>>
 }
@@ -31,11 +34,13 @@ impl SelectionModel {
         let selected_elements_binding   = bind(Arc::new(HashSet::new()));
         let selected_elements           = BindRef::new(&selected_elements_binding);
         let selection_in_order          = Self::selection_in_order(selected_elements.clone(), frame_model, timeline_model);
+        let selected_motions            = Self::selected_motions(selected_elements.clone(), frame_model);
 
         SelectionModel {
             selected_elements:          selected_elements,
             selected_elements_binding:  selected_elements_binding,
-            selection_in_order:         selection_in_order
+            selection_in_order:         selection_in_order,
+            selected_motions:           selected_motions
         }
     }
 
@@ -73,6 +78,42 @@ impl SelectionModel {
         BindRef::new(&in_order)
     }
 
+    ///
+    /// Creates a binding of the translation motions attached to the selected elements
+    ///
+    fn selected_motions(selection: BindRef<Arc<HashSet<ElementId>>>, frame_model: &FrameModel) -> BindRef<Arc<Vec<(ElementId, TimeCurve)>>> {
+        let frame = frame_model.frame.clone();
+
+        let motions = computed(move || {
+            let mut motions = vec![];
+
+            if let Some(frame) = frame.get() {
+                let selection       = selection.get();
+                let mut seen        = HashSet::new();
+
+                // A motion can be attached to more than one element, so we only return each motion once
+                for element_id in selection.iter() {
+                    for (attachment_id, attachment_type) in frame.attached_elements(*element_id) {
+                        if attachment_type != VectorType::Motion || !seen.insert(attachment_id) {
+                            continue;
+                        }
+
+                        if let Some(Vector::Motion(motion)) = frame.element_with_id(attachment_id) {
+                            if let Motion::Translate(translate) = &*motion.motion() {
+                                motions.push((attachment_id, translate.translate.clone()));
+                            }
+                        }
+                    }
+                }
+            }
+
+            motions.sort_by_key(|(motion_id, _)| motion_id.id());
+            Arc::new(motions)
+        });
+
+        BindRef::new(&motions)
+    }
+
     ///
     /// Adds a particular element to the selection
     ///
@@ -32,9 +32,24 @@ pub struct TimelineModel<Anim: Animation> {
     /// The layers in the timeline
     pub layers: BindRef<Vec<LayerModel>>,
 
+    /// The layers that have a row in the timeline (layers inside collapsed folders are left out)
+    pub visible_layers: BindRef<Vec<LayerModel>>,
+
+    /// The symbol whose layers are being edited (or None if the layers of the main animation are being edited)
+    pub editing_symbol: Binding<Option<u64>>,
+
+    /// The length of the main animation's timeline
+    animation_duration: Duration,
+
+    /// The selected layer and time in the main animation to go back to when we stop editing a symbol
+    return_from_symbol: Arc<Mutex<Option<(Option<u64>, Duration)>>>,
+
     /// The ID of the layer currently selected for editing
     pub selected_layer: Binding<Option<u64>>,
 
+    /// The keyframes that are selected in the timeline (as layer IDs and start times)
+    pub selected_keyframes: Binding<Arc<HashSet<(u64, Duration)>>>,
+
     /// The number of times the canvas has been invalidated
     pub canvas_invalidation_count: Binding<u64>,
 
@@ -50,7 +65,12 @@ impl<Anim: Animation> Clone for TimelineModel<Anim> {
             frame_duration:             Binding::clone(&self.frame_duration),
             duration:                   Binding::clone(&self.duration),
             layers:                     BindRef::clone(&self.layers),
+            visible_layers:             BindRef::clone(&self.visible_layers),
+            editing_symbol:             Binding::clone(&self.editing_symbol),
+            animation_duration:         self.animation_duration,
+            return_from_symbol:         Arc::clone(&self.return_from_symbol),
             selected_layer:             Binding::clone(&self.selected_layer),
+            selected_keyframes:         Binding::clone(&self.selected_keyframes),
             canvas_invalidation_count:  Binding::clone(&self.canvas_invalidation_count),
             keyframes:                  Arc::clone(&self.keyframes)
         }
@@ -65,8 +85,12 @@ impl<Anim: Animation+'static> TimelineModel<Anim> {
     where EditStream: 'static+Send+Unpin+Stream<Item=Arc<Vec<AnimationEdit>>> {
         let edits = get_timeline_updates(edits);
 
+        // The main animation is edited to start with
+        let editing_symbol = bind(None);
+
         // Create the layers binding
-        let layers = Self::layers_binding(&animation, edits);
+        let layers = Self::layers_binding(&animation, &editing_symbol, edits);
+        let visible_layers = Self::visible_layers_binding(&layers);
 
         // Initial selected layer is the first in the list
         let selected_layer = animation.get_layer_ids().into_iter().nth(0);
@@ -82,18 +106,23 @@ impl<Anim: Animation+'static> TimelineModel<Anim> {
             duration:                   bind(duration),
             frame_duration:             bind(frame_duration),
             layers:                     layers,
+            visible_layers:             visible_layers,
+            editing_symbol:             editing_symbol,
+            animation_duration:         duration,
+            return_from_symbol:         Arc::new(Mutex::new(None)),
             selected_layer:             bind(selected_layer),
+            selected_keyframes:         bind(Arc::new(HashSet::new())),
             canvas_invalidation_count:  bind(0),
             keyframes:                  Arc::new(Mutex::new(HashMap::new()))
         }
     }
 
     ///
-    /// Retrieves the layers for an animation
+    /// Retrieves the layers for an animation (or for one of its symbols)
     ///
-    fn get_layers(animation: &Arc<Anim>) -> Vec<LayerModel> {
+    fn get_layers(animation: &Arc<Anim>, editing_symbol: Option<u64>) -> Vec<LayerModel> {
         // Load the layers from the animation
-        let layer_ids   = animation.get_layer_ids();
+        let layer_ids   = timeline_layer_ids(&**animation, editing_symbol);
         let mut layers  = vec![];
 
         for id in layer_ids {
@@ -103,22 +132,27 @@ impl<Anim: Animation+'static> TimelineModel<Anim> {
             }
         }
 
+        LayerModel::update_hierarchy(&mut layers);
+
         layers
     }
 
     ///
     /// Returns a binding for the layers in an animation
     ///
-    fn layers_binding<EditStream>(animation: &Arc<Anim>, edits: EditStream) -> BindRef<Vec<LayerModel>>
+    fn layers_binding<EditStream>(animation: &Arc<Anim>, editing_symbol: &Binding<Option<u64>>, edits: EditStream) -> BindRef<Vec<LayerModel>>
     where EditStream: 'static+Send+Unpin+Stream<Item=TimelineModelUpdate> {
         // The animation is used to create the initial layer models in the binding
-        let animation = Arc::clone(animation);
+        let animation       = Arc::clone(animation);
+        let editing_symbol  = Binding::clone(editing_symbol);
 
-        // Create a stream filtered to only layer edits
-        let layer_edits = edits.filter(|edit| future::ready(edit.is_layer_operation()));
+        // Create a stream filtered to only layer edits, which also reloads the layers whenever we start or stop editing a symbol
+        let layer_edits     = edits.filter(|edit| future::ready(edit.is_layer_operation()));
+        let symbol_changes  = follow(editing_symbol.clone()).map(|symbol_id| TimelineModelUpdate::EditSymbol(symbol_id));
+        let layer_edits     = Box::pin(stream::select(layer_edits, symbol_changes));
 
         // Get the initial set of layers
-        let layers = Self::get_layers(&animation);
+        let layers = Self::get_layers(&animation, editing_symbol.get());
 
         // Create a stream binding to update them
         let layers = bind_stream(layer_edits, layers, move |layers, edit| {
@@ -129,18 +163,38 @@ impl<Anim: Animation+'static> TimelineModel<Anim> {
 
             match edit {
                 TimelineModelUpdate::AddNewLayer(layer_id) => {
-                    // Create a new layer model
-                    let layer = animation.get_layer_with_id(layer_id);
+                    // Layers added to the main animation only appear when we're not editing a symbol
+                    if editing_symbol.get().is_none() {
+                        // Create a new layer model
+                        let layer = animation.get_layer_with_id(layer_id);
+
+                        if let Some(layer) = layer {
+                            let model = LayerModel::new(&*layer);
+                            layers.push(model);
+                        }
+                    }
+                },
 
-                    if let Some(layer) = layer {
-                        let model = LayerModel::new(&*layer);
-                        layers.push(model);
+                AddNewSymbolLayer(symbol_id, layer_id) => {
+                    // Layers added to a symbol only appear while that symbol is being edited
+                    if editing_symbol.get() == Some(symbol_id) {
+                        let layer = animation.get_layer_with_id(layer_id);
+
+                        if let Some(layer) = layer {
+                            layers.push(LayerModel::new(&*layer));
+                        }
                     }
                 },
 
-                RemoveLayer(layer_id) => {
-                    // Remove the layer(s?) with the old ID
-                    layers.retain(|model| model.id != layer_id)
+                EditSymbol(symbol_id) => {
+                    // Switching between the main animation and a symbol replaces all of the layers
+                    layers = Self::get_layers(&animation, symbol_id);
+                },
+
+                RemoveLayer(_layer_id) | RefreshLayers => {
+                    // Removing a folder moves the layers inside it, and the other changes can move or hide any of the layers
+                    // in a folder, so the whole hierarchy is reloaded
+                    layers = Self::get_layers(&animation, editing_symbol.get());
                 },
 
                 _ => { }
@@ -154,6 +208,20 @@ impl<Anim: Animation+'static> TimelineModel<Anim> {
         BindRef::from(layers)
     }
 
+    ///
+    /// Returns a binding for the layers that have a row in the timeline
+    ///
+    fn visible_layers_binding(layers: &BindRef<Vec<LayerModel>>) -> BindRef<Vec<LayerModel>> {
+        let layers = layers.clone();
+
+        BindRef::from(computed(move || {
+            layers.get()
+                .into_iter()
+                .filter(|layer| !layer.in_collapsed_folder)
+                .collect::<Vec<_>>()
+        }))
+    }
+
     ///
     /// Updates all of the existing keyframe bindings
     ///
@@ -172,6 +240,93 @@ impl<Anim: Animation+'static> TimelineModel<Anim> {
         }
     }
 
+    ///
+    /// Starts editing the layers of a symbol in place of the layers of the main animation
+    ///
+    pub fn enter_symbol(&self, symbol_id: u64) {
+        let symbol = match self.animation.get_symbol_with_id(symbol_id) {
+            Some(symbol)    => symbol,
+            None            => { return; }
+        };
+
+        // Remember where we were in the main animation
+        {
+            let mut return_from_symbol = self.return_from_symbol.lock().unwrap();
+            if self.editing_symbol.get().is_none() {
+                *return_from_symbol = Some((self.selected_layer.get(), self.current_time.get()));
+            }
+        }
+
+        // The timeline displays the symbol's layers from the start of the symbol
+        self.editing_symbol.set(Some(symbol_id));
+        self.duration.set(symbol.length());
+        self.current_time.set(Duration::from_millis(0));
+        self.selected_layer.set(symbol.get_layer_ids().into_iter().nth(0));
+        self.selected_keyframes.set(Arc::new(HashSet::new()));
+    }
+
+    ///
+    /// Stops editing a symbol and returns to editing the main animation
+    ///
+    pub fn exit_symbol(&self) {
+        if self.editing_symbol.get().is_none() {
+            return;
+        }
+
+        // Go back to where we were when we started editing the symbol
+        let (selected_layer, current_time) = self.return_from_symbol.lock().unwrap().take()
+            .unwrap_or((None, Duration::from_millis(0)));
+
+        self.editing_symbol.set(None);
+        self.duration.set(self.animation_duration);
+        self.current_time.set(current_time);
+        self.selected_layer.set(selected_layer);
+        self.selected_keyframes.set(Arc::new(HashSet::new()));
+    }
+
+    ///
+    /// Picks an ID for a new layer that isn't used by the main animation or by any of its symbols
+    ///
+    pub fn unused_layer_id(&self) -> u64 {
+        let main_layers     = self.animation.get_layer_ids().into_iter();
+        let symbol_layers   = self.animation.get_symbol_ids().into_iter()
+            .filter_map(|symbol_id| self.animation.get_symbol_with_id(symbol_id))
+            .flat_map(|symbol| symbol.get_layer_ids().into_iter());
+
+        main_layers.chain(symbol_layers).max().unwrap_or(0) + 1
+    }
+
+    ///
+    /// Returns true if the content of the specified layer can't be edited (because it or one of the folders it's in
+    /// is locked, or because it's a folder or a reference layer and has no vector content of its own)
+    ///
+    pub fn is_layer_locked(&self, layer_id: u64) -> bool {
+        self.layers.get().iter()
+            .any(|layer| layer.id == layer_id && (layer.effectively_locked || layer.is_folder || layer.is_reference))
+    }
+
+    ///
+    /// Returns true if any of the specified elements are in a layer whose content can't be edited at the current time
+    ///
+    pub fn are_elements_locked(&self, element_ids: &[ElementId]) -> bool {
+        let when = self.current_time.get();
+
+        self.layers.get().into_iter()
+            .filter(|layer| self.is_layer_locked(layer.id))
+            .filter_map(|layer| self.animation.get_layer_with_id(layer.id))
+            .any(|layer| {
+                let frame = layer.get_frame_at_time(when);
+                element_ids.iter().any(|element_id| frame.element_with_id(*element_id).is_some())
+            })
+    }
+
+    ///
+    /// Picks an ID for a new symbol
+    ///
+    pub fn unused_symbol_id(&self) -> u64 {
+        self.animation.get_symbol_ids().into_iter().max().unwrap_or(0) + 1
+    }
+
     ///
     /// Causes the canvas to be invalidated
     ///
@@ -186,7 +341,7 @@ impl<Anim: Animation+'static> TimelineModel<Anim> {
     fn get_keyframe_model(&self, frames: &Range<u32>) -> Vec<KeyFrameModel> {
         let frame_duration      = self.frame_duration.get();
         let when                = (frame_duration*frames.start)..(frame_duration*frames.end);
-        let layers              = self.animation.get_layer_ids();
+        let layers              = timeline_layer_ids(&*self.animation, self.editing_symbol.get());
 
         let keyframe_model  = layers.into_iter()
             .map(|layer_id|     self.animation.get_layer_with_id(layer_id))
@@ -262,3 +417,15 @@ impl<Anim: Animation+'static> TimelineModel<Anim> {
         dead_times.into_iter().for_each(|dead_time| { keyframes.remove(&dead_time); });
     }
 }
+
+///
+/// Retrieves the IDs of the layers that are displayed in the timeline while editing a symbol (or the main animation if the symbol is None)
+///
+pub fn timeline_layer_ids<Anim: Animation+?Sized>(animation: &Anim, editing_symbol: Option<u64>) -> Vec<u64> {
+    match editing_symbol {
+        None            => animation.get_layer_ids(),
+        Some(symbol_id) => animation.get_symbol_with_id(symbol_id)
+            .map(|symbol| symbol.get_layer_ids())
+            .unwrap_or_else(|| vec![])
+    }
+}
@@ -11,7 +11,10 @@ use std::time::Duration;
 ///
 pub enum TimelineModelUpdate {
     AddNewLayer(u64),
+    AddNewSymbolLayer(u64, u64),
     RemoveLayer(u64),
+    EditSymbol(Option<u64>),
+    RefreshLayers,
     AddKeyFrame(u64, Duration),
     RemoveKeyFrame(u64, Duration)
 }
@@ -24,10 +27,13 @@ impl TimelineModelUpdate {
         use self::TimelineModelUpdate::*;
 
         match self {
-            AddNewLayer(_)  |
-            RemoveLayer(_)  =>  true,
+            AddNewLayer(_)          |
+            AddNewSymbolLayer(_, _) |
+            RemoveLayer(_)          |
+            EditSymbol(_)           |
+            RefreshLayers           =>  true,
 
-            _               => false
+            _                       => false
         }
     }
 }
@@ -42,14 +48,24 @@ pub fn get_timeline_updates<EditStream: Stream<Item=Arc<Vec<AnimationEdit>>>>(ed
             use self::AnimationEdit::*;
 
             animation_edits.iter()
-                .filter_map(|animation_edit| {
+                .flat_map(|animation_edit| {
                     match animation_edit {
-                        AddNewLayer(layer_id)                   => Some(TimelineModelUpdate::AddNewLayer(*layer_id)),
-                        RemoveLayer(layer_id)                   => Some(TimelineModelUpdate::RemoveLayer(*layer_id)),
-                        Layer(layer_id, AddKeyFrame(when))      => Some(TimelineModelUpdate::AddKeyFrame(*layer_id, *when)),
-                        Layer(layer_id, RemoveKeyFrame(when))   => Some(TimelineModelUpdate::RemoveKeyFrame(*layer_id, *when)),
+                        AddNewLayer(layer_id)                                   => vec![TimelineModelUpdate::AddNewLayer(*layer_id)],
+                        RemoveLayer(layer_id)                                   => vec![TimelineModelUpdate::RemoveLayer(*layer_id)],
+                        AddNewLayerFolder(_)                                    => vec![TimelineModelUpdate::RefreshLayers],
+                        Symbol(symbol_id, SymbolEdit::AddNewLayer(layer_id))    => vec![TimelineModelUpdate::AddNewSymbolLayer(*symbol_id, *layer_id)],
+                        Layer(layer_id, AddKeyFrame(when))                      => vec![TimelineModelUpdate::AddKeyFrame(*layer_id, *when)],
+                        Layer(layer_id, RemoveKeyFrame(when))                   => vec![TimelineModelUpdate::RemoveKeyFrame(*layer_id, *when)],
+                        Layer(layer_id, MoveKeyFrame(from, to))                 => vec![TimelineModelUpdate::RemoveKeyFrame(*layer_id, *from), TimelineModelUpdate::AddKeyFrame(*layer_id, *to)],
+                        Layer(layer_id, DuplicateKeyFrame(_, to))               => vec![TimelineModelUpdate::AddKeyFrame(*layer_id, *to)],
+                        Layer(_, SetOrdering(_))                                |
+                        Layer(_, SetParent(_))                                  |
+                        Layer(_, SetHidden(_))                                  |
+                        Layer(_, SetLocked(_))                                  |
+                        Layer(_, SetCollapsed(_))                               |
+                        Layer(_, SetReference(_))                               => vec![TimelineModelUpdate::RefreshLayers],
 
-                        _                                       => None
+                        _                                                       => vec![]
                     }
                 })
                 .collect::<Vec<_>>()
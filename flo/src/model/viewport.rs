@@ -0,0 +1,198 @@
+use flo_canvas::*;
+use flo_binding::*;
+
+///
+/// Operations that change how the canvas is viewed
+///
+/// Coordinates are in animation coordinates (ie, the coordinates of a tool input on the canvas)
+///
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ViewportAction {
+    /// Moves the view so that the first point ends up where the second point currently is
+    Pan((f32, f32), (f32, f32)),
+
+    /// Zooms the view by a factor, keeping the specified point in the same place
+    Zoom((f32, f32), f32),
+
+    /// Rotates the view by an angle in radians around a point
+    Rotate((f32, f32), f32),
+
+    /// Mirrors the view horizontally around a point
+    FlipHorizontal((f32, f32)),
+
+    /// Resets the view back to the default
+    Reset
+}
+
+///
+/// The viewport model describes how the animation is displayed on the main canvas
+///
+/// Changing the viewport only changes the view: it never generates any edits to the animation itself.
+///
+#[derive(Clone)]
+pub struct ViewportModel {
+    /// The transformation to apply to the animation on the canvas
+    pub transform: BindRef<Transform2D>,
+
+    /// The zoom factor of the current view
+    pub zoom: BindRef<f32>,
+
+    /// The rotation of the current view in radians
+    pub rotation: BindRef<f32>,
+
+    /// True if the view is mirrored horizontally
+    pub flipped: BindRef<bool>,
+
+    /// The binding where the transform is stored
+    transform_binding: Binding<Transform2D>
+}
+
+impl ViewportModel {
+    ///
+    /// Creates a new viewport model with the default view
+    ///
+    pub fn new() -> ViewportModel {
+        let transform_binding   = bind(Transform2D::identity());
+        let transform           = BindRef::from(transform_binding.clone());
+
+        let zoom                = Self::zoom(transform.clone());
+        let rotation            = Self::rotation(transform.clone());
+        let flipped             = Self::flipped(transform.clone());
+
+        ViewportModel {
+            transform:          transform,
+            zoom:               zoom,
+            rotation:           rotation,
+            flipped:            flipped,
+            transform_binding:  transform_binding
+        }
+    }
+
+    ///
+    /// Creates a binding for the zoom factor of the transform
+    ///
+    fn zoom(transform: BindRef<Transform2D>) -> BindRef<f32> {
+        BindRef::from(computed(move || {
+            let Transform2D(matrix) = transform.get();
+            let determinant         = matrix[0][0]*matrix[1][1] - matrix[0][1]*matrix[1][0];
+
+            determinant.abs().sqrt()
+        }))
+    }
+
+    ///
+    /// Creates a binding for the rotation of the transform
+    ///
+    fn rotation(transform: BindRef<Transform2D>) -> BindRef<f32> {
+        BindRef::from(computed(move || {
+            let Transform2D(matrix) = transform.get();
+            matrix[1][0].atan2(matrix[1][1])
+        }))
+    }
+
+    ///
+    /// Creates a binding indicating if the transform is mirrored
+    ///
+    fn flipped(transform: BindRef<Transform2D>) -> BindRef<bool> {
+        BindRef::from(computed(move || {
+            let Transform2D(matrix) = transform.get();
+            let determinant         = matrix[0][0]*matrix[1][1] - matrix[0][1]*matrix[1][0];
+
+            determinant < 0.0
+        }))
+    }
+
+    ///
+    /// Returns the transform that changes the view around the specified point
+    ///
+    fn around_point((x, y): (f32, f32), transform: Transform2D) -> Transform2D {
+        Transform2D::translate(x, y) * transform * Transform2D::translate(-x, -y)
+    }
+
+    ///
+    /// Updates the viewport with an action
+    ///
+    pub fn perform(&self, action: ViewportAction) {
+        use self::ViewportAction::*;
+
+        let current = self.transform_binding.get();
+
+        // The new transform is applied before the existing one, as the action coordinates are in animation coordinates
+        let new_transform = match action {
+            Pan((x1, y1), (x2, y2))     => current * Transform2D::translate(x2-x1, y2-y1),
+            Zoom(center, factor)        => if factor > 0.0 { current * Self::around_point(center, Transform2D::scale(factor, factor)) } else { current },
+            Rotate(center, radians)     => current * Self::around_point(center, Transform2D::rotate(radians)),
+            FlipHorizontal(center)      => current * Self::around_point(center, Transform2D::scale(-1.0, 1.0)),
+            Reset                       => Transform2D::identity()
+        };
+
+        self.transform_binding.set(new_transform);
+    }
+
+    ///
+    /// Converts a point on the canvas back to animation coordinates
+    ///
+    pub fn to_animation_coordinates(&self, (x, y): (f32, f32)) -> (f32, f32) {
+        let Transform2D(matrix) = self.transform.get().invert().unwrap_or_else(|| Transform2D::identity());
+
+        (matrix[0][0]*x + matrix[0][1]*y + matrix[0][2], matrix[1][0]*x + matrix[1][1]*y + matrix[1][2])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn near((x1, y1): (f32, f32), (x2, y2): (f32, f32)) -> bool {
+        (x1-x2).abs() < 0.001 && (y1-y2).abs() < 0.001
+    }
+
+    #[test]
+    fn zoom_keeps_center_in_place() {
+        let viewport = ViewportModel::new();
+        viewport.perform(ViewportAction::Zoom((100.0, 200.0), 2.0));
+
+        assert!(near(viewport.to_animation_coordinates((100.0, 200.0)), (100.0, 200.0)));
+        assert!(near(viewport.to_animation_coordinates((120.0, 200.0)), (110.0, 200.0)));
+        assert!((viewport.zoom.get() - 2.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn rotate_keeps_center_in_place() {
+        let viewport = ViewportModel::new();
+        viewport.perform(ViewportAction::Rotate((100.0, 200.0), 0.5));
+
+        assert!(near(viewport.to_animation_coordinates((100.0, 200.0)), (100.0, 200.0)));
+        assert!((viewport.rotation.get() - 0.5).abs() < 0.001);
+    }
+
+    #[test]
+    fn flip_is_mirrored() {
+        let viewport = ViewportModel::new();
+        viewport.perform(ViewportAction::FlipHorizontal((100.0, 0.0)));
+
+        assert!(viewport.flipped.get());
+        assert!(near(viewport.to_animation_coordinates((110.0, 5.0)), (90.0, 5.0)));
+
+        viewport.perform(ViewportAction::FlipHorizontal((100.0, 0.0)));
+        assert!(!viewport.flipped.get());
+    }
+
+    #[test]
+    fn pan_moves_point() {
+        let viewport = ViewportModel::new();
+        viewport.perform(ViewportAction::Pan((10.0, 10.0), (30.0, 40.0)));
+
+        assert!(near(viewport.to_animation_coordinates((30.0, 40.0)), (10.0, 10.0)));
+    }
+
+    #[test]
+    fn reset_view() {
+        let viewport = ViewportModel::new();
+        viewport.perform(ViewportAction::Zoom((100.0, 200.0), 2.0));
+        viewport.perform(ViewportAction::Rotate((100.0, 200.0), 0.5));
+        viewport.perform(ViewportAction::Reset);
+
+        assert!(viewport.transform.get() == Transform2D::identity());
+    }
+}
@@ -43,7 +43,16 @@ pub struct InkModel {
     pub modification_mode: Binding<BrushModificationMode>,
 
     /// The way new brush strokes are represented
-    pub representation: Binding<BrushRepresentation>
+    pub representation: Binding<BrushRepresentation>,
+
+    /// True if the brush should respond to pressure, tilt and velocity using the dynamics settings
+    pub dynamics_enabled: Binding<bool>,
+
+    /// The settings for the dynamic ink brush
+    pub dynamics: Binding<DynamicInkDefinition>,
+
+    /// The definition of the brush that this model will draw with
+    pub brush_definition: BindRef<BrushDefinition>
 }
 
 ///
@@ -70,8 +79,11 @@ impl InkModel {
         let color               = bind(Color::Hsluv(0.0, 100.0, 0.0, 1.0));
         let modification_mode   = bind(BrushModificationMode::Individual);
         let representation      = bind(BrushRepresentation::BrushStroke);
+        let dynamics_enabled    = bind(false);
+        let dynamics            = bind(DynamicInkDefinition::default());
 
         let brush_properties    = Self::brush_properties(size.clone(), opacity.clone(), color.clone());
+        let brush_definition    = Self::brush_definition(dynamics_enabled.clone(), dynamics.clone());
 
         InkModel {
             size:               size,
@@ -79,10 +91,28 @@ impl InkModel {
             color:              color,
             brush_properties:   brush_properties,
             modification_mode:  modification_mode,
-            representation:     representation
+            representation:     representation,
+            dynamics_enabled:   dynamics_enabled,
+            dynamics:           dynamics,
+            brush_definition:   brush_definition
         }
     }
 
+    ///
+    /// Creates the brush definition from the model bindings
+    ///
+    fn brush_definition(dynamics_enabled: Binding<bool>, dynamics: Binding<DynamicInkDefinition>) -> BindRef<BrushDefinition> {
+        let brush_definition = computed(move || {
+            if dynamics_enabled.get() {
+                BrushDefinition::DynamicInk(dynamics.get())
+            } else {
+                BrushDefinition::Ink(InkDefinition::default())
+            }
+        });
+
+        BindRef::from(brush_definition)
+    }
+
     ///
     /// Creates brush properties from the model bindings
     ///
@@ -91,7 +121,8 @@ impl InkModel {
             BrushProperties {
                 size:       size.get(),
                 opacity:    opacity.get(),
-                color:      color.get()
+                color:      color.get(),
+                fill:       BrushFill::Solid
             }
         });
 
@@ -124,7 +155,7 @@ impl<Anim: Animation+'static> Tool<Anim> for Ink {
     /// Creates the menu controller for this tool (or None if this tool has no menu controller)
     ///
     fn create_menu_controller(&self, _flo_model: Arc<FloModel<Anim>>, tool_model: &InkModel) -> Option<Arc<dyn Controller>> {
-        Some(Arc::new(InkMenuController::new(&tool_model.size, &tool_model.opacity, &tool_model.color, &tool_model.modification_mode, &tool_model.representation)))
+        Some(Arc::new(InkMenuController::new(&tool_model.size, &tool_model.opacity, &tool_model.color, &tool_model.modification_mode, &tool_model.representation, &tool_model.dynamics_enabled, &tool_model.dynamics, &tool_model.brush_definition)))
     }
 
     ///
@@ -133,6 +164,7 @@ impl<Anim: Animation+'static> Tool<Anim> for Ink {
     fn actions_for_model(&self, flo_model: Arc<FloModel<Anim>>, tool_model: &InkModel) -> BoxStream<'static, ToolAction<InkData>> {
         // Fetch the brush properties
         let brush_properties    = tool_model.brush_properties.clone();
+        let brush_definition    = tool_model.brush_definition.clone();
         let selected_layer      = flo_model.timeline().selected_layer.clone();
         let representation      = tool_model.representation.clone();
         let modification_mode   = tool_model.modification_mode.clone();
@@ -140,7 +172,7 @@ impl<Anim: Animation+'static> Tool<Anim> for Ink {
         // Create a computed binding that generates the data for the brush
         let ink_data            = computed(move || {
             InkData {
-                brush:              BrushDefinition::Ink(InkDefinition::default()),
+                brush:              brush_definition.get(),
                 brush_properties:   brush_properties.get(),
                 selected_layer:     selected_layer.get().unwrap_or(0),
                 representation:     representation.get(),
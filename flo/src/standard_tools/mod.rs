@@ -1,6 +1,8 @@
 mod select;
 mod select_tool_model;
 mod adjust;
+mod motion_path;
+mod skeleton;
 mod pan;
 mod ink;
 mod eraser;
@@ -10,6 +12,8 @@ mod tool_sets;
 pub use self::select::*;
 pub use self::select_tool_model::*;
 pub use self::adjust::*;
+pub use self::motion_path::*;
+pub use self::skeleton::*;
 pub use self::pan::*;
 pub use self::ink::*;
 pub use self::eraser::*;
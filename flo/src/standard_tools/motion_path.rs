@@ -0,0 +1,514 @@
+use super::super::tools::*;
+use super::super::model::*;
+use super::super::style::*;
+
+use flo_canvas::*;
+use flo_binding::*;
+use flo_animation::*;
+
+use futures::*;
+use futures::stream;
+use futures::stream::{BoxStream};
+
+use std::sync::*;
+use std::time::Duration;
+
+/// The maximum number of frame ticks to draw along a motion path
+const MAX_FRAME_TICKS: usize = 1000;
+
+/// How close the user has to click to a point on the path to start dragging it
+const MAX_DRAG_DISTANCE: f32 = 8.0;
+
+///
+/// The current action being performed by the motion path tool
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum MotionPathAction {
+    /// The tool is idle
+    NoAction,
+
+    /// Selected an element
+    Select,
+
+    /// A point on a motion path is being dragged (motion ID, control point index, drag start, drag end)
+    DragControlPoint(ElementId, usize, (f32, f32), (f32, f32)),
+
+    /// The easing for a point is being dragged (motion ID, time control point index, true for the future handle, drag position)
+    DragEasing(ElementId, usize, bool, (f32, f32))
+}
+
+///
+/// A motion path that can be edited by this tool
+///
+#[derive(Clone, Debug, PartialEq)]
+struct EditablePath {
+    /// The ID of the motion that this path belongs to
+    motion_id: ElementId,
+
+    /// The path followed by the motion
+    curve: TimeCurve
+}
+
+///
+/// Data for the MotionPath tool
+///
+#[derive(Clone)]
+pub struct MotionPathData {
+    /// The current state of this data
+    state: Binding<MotionPathAction>,
+
+    // The paths for the motions attached to the selected elements
+    paths: Arc<Vec<EditablePath>>
+}
+
+///
+/// Returns the distance between two points
+///
+fn distance(p1: (f32, f32), p2: (f32, f32)) -> f32 {
+    let (dx, dy) = (p1.0-p2.0, p1.1-p2.1);
+    (dx*dx + dy*dy).sqrt()
+}
+
+///
+/// Returns the position of the easing marker between a point and one of its handles
+///
+/// The marker is close to the handle when there's no easing and moves towards the point as the easing increases
+///
+fn easing_marker_position(point: (f32, f32), handle: (f32, f32), easing: f32) -> (f32, f32) {
+    let proportion = 0.75 - easing*0.5;
+    (point.0 + (handle.0-point.0)*proportion, point.1 + (handle.1-point.1)*proportion)
+}
+
+///
+/// Returns the easing value that places the easing marker nearest to the specified position
+///
+fn easing_for_marker_position(point: (f32, f32), handle: (f32, f32), position: (f32, f32)) -> f32 {
+    let (dx, dy)    = (handle.0-point.0, handle.1-point.1);
+    let length_sq   = dx*dx + dy*dy;
+
+    if length_sq <= 0.0 {
+        0.0
+    } else {
+        let proportion = ((position.0-point.0)*dx + (position.1-point.1)*dy) / length_sq;
+        ((0.75 - proportion) / 0.5).max(0.0).min(1.0)
+    }
+}
+
+impl EditablePath {
+    ///
+    /// Returns the easing markers for this path (time control point index, true for the future handle, position of the marker)
+    ///
+    fn easing_markers(&self) -> Vec<(usize, bool, (f32, f32))> {
+        let num_points  = self.curve.points.len();
+        let mut markers = vec![];
+
+        for (index, point) in self.curve.points.iter().enumerate() {
+            let (ease_in, ease_out) = self.curve.easing_at(index);
+
+            if index > 0 {
+                markers.push((index, false, easing_marker_position(point.point.coords(), point.past.coords(), ease_in)));
+            }
+
+            if index+1 < num_points {
+                markers.push((index, true, easing_marker_position(point.point.coords(), point.future.coords(), ease_out)));
+            }
+        }
+
+        markers
+    }
+
+    ///
+    /// Returns the path as it would be after applying an action
+    ///
+    fn edited(&self, action: &MotionPathAction) -> EditablePath {
+        match action {
+            MotionPathAction::DragControlPoint(motion_id, index, from, to) if *motion_id == self.motion_id => {
+                let (diff_x, diff_y)    = (to.0-from.0, to.1-from.1);
+
+                // Moving a point on the path also moves its handles
+                let is_point            = index%3 == 0;
+                let new_positions       = self.curve.control_points()
+                    .into_iter()
+                    .enumerate()
+                    .map(|(this_index, point)| {
+                        let (x, y) = point.coords();
+
+                        if this_index == *index || (is_point && (this_index+1 == *index || this_index == index+1)) {
+                            (x+diff_x, y+diff_y)
+                        } else {
+                            (x, y)
+                        }
+                    })
+                    .collect();
+
+                EditablePath {
+                    motion_id:  self.motion_id,
+                    curve:      self.curve.with_control_point_positions(new_positions)
+                }
+            }
+
+            MotionPathAction::DragEasing(motion_id, index, is_future, position) if *motion_id == self.motion_id && *index < self.curve.points.len() => {
+                let point                   = &self.curve.points[*index];
+                let (ease_in, ease_out)     = self.curve.easing_at(*index);
+
+                let (ease_in, ease_out)     = if *is_future {
+                    (ease_in, easing_for_marker_position(point.point.coords(), point.future.coords(), *position))
+                } else {
+                    (easing_for_marker_position(point.point.coords(), point.past.coords(), *position), ease_out)
+                };
+
+                EditablePath {
+                    motion_id:  self.motion_id,
+                    curve:      self.curve.with_easing(*index, ease_in, ease_out)
+                }
+            }
+
+            _ => self.clone()
+        }
+    }
+}
+
+impl MotionPathData {
+    ///
+    /// Finds the easing marker nearest to the specified location
+    ///
+    fn easing_marker_at_point(&self, location: (f32, f32)) -> Option<(ElementId, usize, bool)> {
+        let mut nearest     = None;
+        let mut min_dist    = MAX_DRAG_DISTANCE;
+
+        for path in self.paths.iter() {
+            for (index, is_future, position) in path.easing_markers() {
+                let dist = distance(location, position);
+
+                if dist < min_dist {
+                    min_dist    = dist;
+                    nearest     = Some((path.motion_id, index, is_future));
+                }
+            }
+        }
+
+        nearest
+    }
+
+    ///
+    /// Finds the control point nearest to the specified location
+    ///
+    fn control_point_at_point(&self, location: (f32, f32)) -> Option<(ElementId, usize)> {
+        let mut nearest     = None;
+        let mut min_dist    = MAX_DRAG_DISTANCE;
+
+        for path in self.paths.iter() {
+            for (index, point) in path.curve.control_points().into_iter().enumerate() {
+                let dist = distance(location, point.coords());
+
+                if dist < min_dist {
+                    min_dist    = dist;
+                    nearest     = Some((path.motion_id, index));
+                }
+            }
+        }
+
+        nearest
+    }
+}
+
+///
+/// The MotionPath tool (edits the paths followed by the motions attached to the selected elements)
+///
+pub struct MotionPath { }
+
+impl MotionPath {
+    ///
+    /// Creates a new instance of the MotionPath tool
+    ///
+    pub fn new() -> MotionPath {
+        MotionPath {}
+    }
+
+    ///
+    /// Returns a binding containing the paths for the translation motions attached to the selected elements
+    ///
+    fn paths<Anim: 'static+Animation>(flo_model: &FloModel<Anim>) -> BindRef<Arc<Vec<EditablePath>>> {
+        let selected_motions = flo_model.selection().selected_motions.clone();
+
+        BindRef::new(&computed(move || {
+            let paths = selected_motions.get()
+                .iter()
+                .map(|(motion_id, curve)| EditablePath { motion_id: *motion_id, curve: curve.clone() })
+                .collect();
+
+            Arc::new(paths)
+        }))
+    }
+
+    ///
+    /// Draws a motion path, along with its handles and a tick for each frame
+    ///
+    fn draw_path(path: &EditablePath, frame_duration: Duration) -> Vec<Draw> {
+        let mut draw    = vec![];
+        let curve       = &path.curve;
+
+        if curve.points.len() == 0 {
+            return draw;
+        }
+
+        // The path itself
+        draw.new_path();
+        let (x, y) = curve.points[0].point.coords();
+        draw.move_to(x, y);
+        for section in curve.as_sections() {
+            let (x1, y1)    = section.end.coords();
+            let (x2, y2)    = section.control_point1.coords();
+            let (x3, y3)    = section.control_point2.coords();
+
+            draw.bezier_curve_to(x1, y1, x2, y2, x3, y3);
+        }
+
+        draw.line_width_pixels(3.0);
+        draw.stroke_color(SELECTION_OUTLINE);
+        draw.stroke();
+        draw.line_width_pixels(1.5);
+        draw.stroke_color(MOTION_PATH);
+        draw.stroke();
+
+        // A tick for where the element is at each frame
+        let start_millis    = curve.points[0].point.milliseconds();
+        let end_millis      = curve.points[curve.points.len()-1].point.milliseconds();
+        let frame_millis    = to_millis(frame_duration) as f32;
+
+        if frame_millis > 0.0 {
+            let num_ticks = (((end_millis - start_millis) / frame_millis) as usize).min(MAX_FRAME_TICKS);
+
+            draw.new_path();
+            for tick in 0..=num_ticks {
+                if let Some(pos) = curve.point_at_time(start_millis + (tick as f32)*frame_millis) {
+                    draw.circle(pos.0, pos.1, 2.0);
+                }
+            }
+            draw.fill_color(MOTION_PATH_TICK);
+            draw.fill();
+        }
+
+        // The lines connecting the handles to their points
+        let control_points = curve.control_points();
+
+        draw.new_path();
+        for (index, handle) in control_points.iter().enumerate() {
+            if index%3 == 0 { continue; }
+
+            let point_index = if index%3 == 1 { index-1 } else { index+1 };
+            let (px, py)    = control_points[point_index].coords();
+            let (hx, hy)    = handle.coords();
+
+            draw.move_to(px, py);
+            draw.line_to(hx, hy);
+        }
+        draw.line_width_pixels(1.0);
+        draw.stroke_color(CP_LINES);
+        draw.stroke();
+
+        // The easing markers
+        draw.new_path();
+        for (_, _, (x, y)) in path.easing_markers() {
+            draw.move_to(x, y-4.0);
+            draw.line_to(x+4.0, y);
+            draw.line_to(x, y+4.0);
+            draw.line_to(x-4.0, y);
+            draw.close_path();
+        }
+        draw.fill_color(MOTION_PATH_EASING);
+        draw.fill();
+
+        // The points and their handles
+        for (index, point) in control_points.iter().enumerate() {
+            let (x, y) = point.coords();
+
+            draw.new_path();
+            if index%3 == 0 {
+                draw.circle(x, y, 5.0);
+                draw.fill_color(CP_BEZIER);
+            } else {
+                draw.rect(x-3.5, y-3.5, x+3.5, y+3.5);
+                draw.fill_color(CP_BEZIER_CP);
+            }
+            draw.fill();
+
+            draw.line_width_pixels(1.0);
+            draw.stroke_color(SELECTION_OUTLINE);
+            draw.stroke();
+        }
+
+        draw
+    }
+
+    ///
+    /// Creates an action stream that draws the motion paths for the selected elements, including any edit in progress
+    ///
+    fn draw_paths_overlay(paths: BindRef<Arc<Vec<EditablePath>>>, tool_state: BindRef<MotionPathAction>, frame_duration: BindRef<Duration>) -> impl Stream<Item=ToolAction<MotionPathData>> {
+        follow(computed(move || (paths.get(), tool_state.get(), frame_duration.get())))
+            .map(|(paths, tool_state, frame_duration)| {
+                let mut draw_paths = vec![];
+
+                draw_paths.layer(0);
+                draw_paths.clear_layer();
+
+                for path in paths.iter() {
+                    draw_paths.extend(Self::draw_path(&path.edited(&tool_state), frame_duration));
+                }
+
+                ToolAction::Overlay(OverlayAction::Draw(draw_paths))
+            })
+    }
+
+    ///
+    /// Generates the tool actions for a painting action
+    ///
+    fn paint<Anim: 'static+Animation>(&self, painting: Painting, data: &MotionPathData, model: &FloModel<Anim>) -> Vec<ToolAction<MotionPathData>> {
+        let state           = data.state.get();
+        let paint_action    = painting.action;
+
+        match (state, paint_action) {
+            (_, PaintAction::Start) => {
+                if let Some((motion_id, index, is_future)) = data.easing_marker_at_point(painting.location) {
+                    // Easing markers sit between the points and their handles, so they take priority
+                    data.state.set(MotionPathAction::DragEasing(motion_id, index, is_future, painting.location));
+                    vec![]
+                } else if let Some((motion_id, index)) = data.control_point_at_point(painting.location) {
+                    // Drag a point on the path
+                    data.state.set(MotionPathAction::DragControlPoint(motion_id, index, painting.location, painting.location));
+                    vec![]
+                } else {
+                    // Select the element under the cursor to see its motion path
+                    let mut selected_element = None;
+                    for elem in model.frame().elements_at_point(painting.location) {
+                        match elem {
+                            ElementMatch::InsidePath(element) => {
+                                selected_element = Some(element);
+                                break;
+                            }
+
+                            ElementMatch::OnlyInBounds(element) => {
+                                if selected_element.is_none() { selected_element = Some(element); }
+                            }
+                        }
+                    }
+
+                    data.state.set(MotionPathAction::Select);
+
+                    vec![ToolAction::ClearSelection]
+                        .into_iter()
+                        .chain(selected_element.map(|element| ToolAction::Select(element)))
+                        .collect()
+                }
+            },
+
+            (MotionPathAction::DragControlPoint(motion_id, index, from, _to), PaintAction::Continue)   |
+            (MotionPathAction::DragControlPoint(motion_id, index, from, _to), PaintAction::Prediction) => {
+                data.state.set(MotionPathAction::DragControlPoint(motion_id, index, from, painting.location));
+                vec![]
+            },
+
+            (MotionPathAction::DragEasing(motion_id, index, is_future, _pos), PaintAction::Continue)   |
+            (MotionPathAction::DragEasing(motion_id, index, is_future, _pos), PaintAction::Prediction) => {
+                data.state.set(MotionPathAction::DragEasing(motion_id, index, is_future, painting.location));
+                vec![]
+            },
+
+            (MotionPathAction::DragControlPoint(motion_id, index, from, _to), PaintAction::Finish) => {
+                data.state.set(MotionPathAction::NoAction);
+                Self::set_path(data, motion_id, MotionPathAction::DragControlPoint(motion_id, index, from, painting.location))
+            },
+
+            (MotionPathAction::DragEasing(motion_id, index, is_future, _pos), PaintAction::Finish) => {
+                data.state.set(MotionPathAction::NoAction);
+                Self::set_path(data, motion_id, MotionPathAction::DragEasing(motion_id, index, is_future, painting.location))
+            },
+
+            (_, PaintAction::Finish) |
+            (_, PaintAction::Cancel) => {
+                data.state.set(MotionPathAction::NoAction);
+                vec![]
+            },
+
+            _ => vec![]
+        }
+    }
+
+    ///
+    /// Generates the actions to update the path of a motion after an edit has finished
+    ///
+    fn set_path(data: &MotionPathData, motion_id: ElementId, action: MotionPathAction) -> Vec<ToolAction<MotionPathData>> {
+        let path = data.paths.iter().filter(|path| path.motion_id == motion_id).nth(0);
+
+        if let Some(path) = path {
+            let edited_path = path.edited(&action);
+
+            vec![
+                ToolAction::Edit(AnimationEdit::Motion(motion_id, MotionEdit::SetPath(edited_path.curve))),
+                ToolAction::InvalidateFrame
+            ]
+        } else {
+            vec![]
+        }
+    }
+}
+
+impl<Anim: 'static+Animation> Tool<Anim> for MotionPath {
+    type ToolData   = MotionPathData;
+    type Model      = ();
+
+    fn tool_name(&self) -> String { "Motion Path".to_string() }
+
+    fn image_name(&self) -> String { "motion_path".to_string() }
+
+    fn create_model(&self, _flo_model: Arc<FloModel<Anim>>) -> () { }
+
+    ///
+    /// Returns a stream containing the actions for the view and tool model for the motion path tool
+    ///
+    fn actions_for_model(&self, flo_model: Arc<FloModel<Anim>>, _tool_model: &()) -> BoxStream<'static, ToolAction<MotionPathData>> {
+        let frame_duration      = flo_model.timeline().frame_duration.clone();
+        let paths               = Self::paths(&*flo_model);
+
+        // State is initially 'no action'
+        let motion_path_state   = bind(MotionPathAction::NoAction);
+
+        // Draw the motion paths for the selected elements
+        let draw_paths          = Self::draw_paths_overlay(paths.clone(), BindRef::new(&motion_path_state), BindRef::new(&frame_duration));
+
+        // Build the tool data from the paths for the selected elements
+        let update_data         = follow(paths)
+            .map(move |paths| {
+                ToolAction::Data(MotionPathData {
+                    state:  motion_path_state.clone(),
+                    paths:  paths
+                })
+            });
+
+        Box::pin(stream::select(update_data, draw_paths))
+    }
+
+    fn actions_for_input<'a>(&'a self, flo_model: Arc<FloModel<Anim>>, data: Option<Arc<MotionPathData>>, input: Box<dyn 'a+Iterator<Item=ToolInput<MotionPathData>>>) -> Box<dyn 'a+Iterator<Item=ToolAction<MotionPathData>>> {
+        let mut data    = data;
+        let mut actions = vec![];
+        let input       = ToolInput::last_paint_actions_only(input);
+
+        for input in input {
+            match input {
+                ToolInput::Data(new_data) => {
+                    data = Some(new_data);
+                },
+
+                ToolInput::Paint(painting) => {
+                    if let Some(data) = data.as_ref() {
+                        actions.extend(self.paint(painting, &**data, &*flo_model));
+                    }
+                }
+
+                _ => ()
+            }
+        }
+
+        Box::new(actions.into_iter())
+    }
+}
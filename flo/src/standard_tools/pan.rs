@@ -1,12 +1,23 @@
+use super::super::menu::*;
 use super::super::tools::*;
 use super::super::model::*;
 
+use flo_ui::*;
 use flo_animation::*;
 
 use std::sync::*;
 
 ///
-/// The Pan tool (Pans control points of existing objects)
+/// Data for the pan tool
+///
+#[derive(Clone, Copy, Debug)]
+pub struct PanData {
+    /// The point where the previous paint action was made, if a pan is in progress
+    last_point: Option<(f32, f32)>
+}
+
+///
+/// The Pan tool (moves the view of the canvas without editing the animation)
 ///
 pub struct Pan { }
 
@@ -19,8 +30,8 @@ impl Pan {
     }
 }
 
-impl<Anim: Animation> Tool<Anim> for Pan {
-    type ToolData   = ();
+impl<Anim: 'static+Animation> Tool<Anim> for Pan {
+    type ToolData   = PanData;
     type Model      = ();
 
     fn tool_name(&self) -> String { "Pan".to_string() }
@@ -29,7 +40,48 @@ impl<Anim: Animation> Tool<Anim> for Pan {
 
     fn create_model(&self, _flo_model: Arc<FloModel<Anim>>) -> () { }
 
-    fn actions_for_input<'a>(&'a self, _flo_model: Arc<FloModel<Anim>>, _data: Option<Arc<()>>, _input: Box<dyn 'a+Iterator<Item=ToolInput<()>>>) -> Box<dyn Iterator<Item=ToolAction<()>>> {
-        Box::new(vec![].into_iter())
+    fn create_menu_controller(&self, flo_model: Arc<FloModel<Anim>>, _tool_model: &()) -> Option<Arc<dyn Controller>> {
+        Some(Arc::new(PanMenuController::new(&*flo_model)))
+    }
+
+    ///
+    /// Dragging with the pan tool keeps the point that was initially clicked underneath the pointer
+    ///
+    /// The paint coordinates for a whole stroke are in the coordinate system of the view when the stroke
+    /// started, so moving the view by the distance between successive points tracks the pointer.
+    ///
+    fn actions_for_input<'a>(&'a self, _flo_model: Arc<FloModel<Anim>>, data: Option<Arc<PanData>>, input: Box<dyn 'a+Iterator<Item=ToolInput<PanData>>>) -> Box<dyn Iterator<Item=ToolAction<PanData>>> {
+        let mut last_point  = data.and_then(|data| data.last_point);
+        let mut actions     = vec![];
+
+        for input in input {
+            match input {
+                ToolInput::Data(data)   => { last_point = data.last_point; },
+
+                ToolInput::Paint(painting) => {
+                    match painting.action {
+                        PaintAction::Start      => { last_point = Some(painting.location); },
+                        PaintAction::Cancel     => { last_point = None; },
+
+                        PaintAction::Continue   |
+                        PaintAction::Prediction |
+                        PaintAction::Finish     => {
+                            if let Some(last) = last_point {
+                                actions.push(ToolAction::View(ViewportAction::Pan(last, painting.location)));
+                            }
+
+                            last_point = if painting.action == PaintAction::Finish { None } else { Some(painting.location) };
+                        }
+                    }
+                },
+
+                _ => { }
+            }
+        }
+
+        // Remember where the pointer was for the next set of inputs
+        actions.push(ToolAction::Data(PanData { last_point: last_point }));
+
+        Box::new(actions.into_iter())
     }
 }
@@ -17,6 +17,9 @@ use std::sync::*;
 use std::time::Duration;
 use std::collections::{HashSet};
 
+/// The distance (in canvas units) within which a dragged edge or centre will snap to a guide
+const SNAP_DISTANCE: f32 = 6.0;
+
 ///
 /// The actions that the tool can take
 ///
@@ -58,6 +61,18 @@ enum SelectHandle {
     Rotate
 }
 
+///
+/// A guide line that a drag has snapped to
+///
+#[derive(Copy, Clone, PartialEq, Debug)]
+enum SnapGuide {
+    /// A vertical line at the specified x coordinate
+    Vertical(f32),
+
+    /// A horizontal line at the specified y coordinate
+    Horizontal(f32)
+}
+
 ///
 /// The select data provides feedback for the action being taken by the select tool
 ///
@@ -85,7 +100,16 @@ pub struct SelectData {
     initial_position: RawPoint,
 
     /// The position the user has dragged to
-    drag_position: Option<RawPoint>
+    drag_position: Option<RawPoint>,
+
+    /// The spacing of the grid to snap to (or None if snapping to the grid is turned off)
+    snap_grid: Option<f32>,
+
+    /// True if drags should snap to the edges and centres of the other elements and the canvas
+    smart_guides: bool,
+
+    /// The size of the canvas
+    canvas_size: (f32, f32)
 }
 
 ///
@@ -106,7 +130,10 @@ impl SelectData {
             selection_bounds:       self.selection_bounds.clone(),
             action:                 new_action,
             initial_position:       self.initial_position.clone(),
-            drag_position:          self.drag_position.clone()
+            drag_position:          self.drag_position.clone(),
+            snap_grid:              self.snap_grid,
+            smart_guides:           self.smart_guides,
+            canvas_size:            self.canvas_size
         }
     }
 
@@ -122,7 +149,10 @@ impl SelectData {
             selection_bounds:       self.selection_bounds.clone(),
             action:                 self.action,
             initial_position:       new_initial_position,
-            drag_position:          None
+            drag_position:          None,
+            snap_grid:              self.snap_grid,
+            smart_guides:           self.smart_guides,
+            canvas_size:            self.canvas_size
         }
     }
 
@@ -138,8 +168,35 @@ impl SelectData {
             selection_bounds:       self.selection_bounds.clone(),
             action:                 self.action,
             initial_position:       self.initial_position.clone(),
-            drag_position:          Some(new_drag_position)
+            drag_position:          Some(new_drag_position),
+            snap_grid:              self.snap_grid,
+            smart_guides:           self.smart_guides,
+            canvas_size:            self.canvas_size
+        }
+    }
+
+    ///
+    /// Returns the x and y positions that a drag can snap to (the edges and centres of the unselected elements and the canvas)
+    ///
+    fn snap_targets(&self) -> (Vec<f32>, Vec<f32>) {
+        if !self.smart_guides {
+            return (vec![], vec![]);
         }
+
+        let (width, height) = self.canvas_size;
+        let mut x_targets   = vec![0.0, width/2.0, width];
+        let mut y_targets   = vec![0.0, height/2.0, height];
+
+        for (element_id, _, bounds) in self.bounding_boxes.iter() {
+            if self.selected_elements.contains(element_id) || bounds.is_zero_size() {
+                continue;
+            }
+
+            x_targets.extend(vec![bounds.x1, (bounds.x1+bounds.x2)/2.0, bounds.x2]);
+            y_targets.extend(vec![bounds.y1, (bounds.y1+bounds.y2)/2.0, bounds.y2]);
+        }
+
+        (x_targets, y_targets)
     }
 }
 
@@ -447,6 +504,123 @@ impl Select {
         drawing
     }
 
+    ///
+    /// Works out how far to move a set of positions so that one of them snaps to a target or to the grid
+    ///
+    /// Returns the offset to apply and the target that was snapped to, if there was one. Targets take priority over the grid,
+    /// which is applied to the first position.
+    ///
+    fn snap_value(positions: &[f32], targets: &[f32], grid_size: Option<f32>) -> (f32, Option<f32>) {
+        // Find the closest target to any of the positions
+        let mut nearest: Option<(f32, f32)> = None;
+
+        for position in positions.iter() {
+            for target in targets.iter() {
+                let offset = target - position;
+
+                if offset.abs() <= SNAP_DISTANCE && nearest.map(|(nearest_offset, _)| offset.abs() < nearest_offset.abs()).unwrap_or(true) {
+                    nearest = Some((offset, *target));
+                }
+            }
+        }
+
+        // Snap to the target if there is one, or to the grid otherwise
+        match (nearest, grid_size, positions.first()) {
+            (Some((offset, target)), _, _)              => (offset, Some(target)),
+            (None, Some(grid_size), Some(position))     => if grid_size > 0.0 { ((position/grid_size).round()*grid_size - position, None) } else { (0.0, None) },
+            _                                           => (0.0, None)
+        }
+    }
+
+    ///
+    /// Returns the snapped drag point and the guides to display while the selection is being moved
+    ///
+    fn snap_drag(data: &SelectData, initial_point: (f32, f32), drag_point: (f32, f32)) -> ((f32, f32), Vec<SnapGuide>) {
+        let bounds = match data.selection_bounds {
+            Some(bounds)    => bounds,
+            None            => return (drag_point, vec![])
+        };
+
+        // Snap the edges and centre of the moved selection
+        let (dx, dy)                = (drag_point.0-initial_point.0, drag_point.1-initial_point.1);
+        let (x_targets, y_targets)  = data.snap_targets();
+
+        let (snap_x, guide_x)       = Self::snap_value(&[bounds.x1+dx, (bounds.x1+bounds.x2)/2.0+dx, bounds.x2+dx], &x_targets, data.snap_grid);
+        let (snap_y, guide_y)       = Self::snap_value(&[bounds.y1+dy, (bounds.y1+bounds.y2)/2.0+dy, bounds.y2+dy], &y_targets, data.snap_grid);
+
+        let guides = guide_x.map(SnapGuide::Vertical).into_iter()
+            .chain(guide_y.map(SnapGuide::Horizontal))
+            .collect();
+
+        ((drag_point.0+snap_x, drag_point.1+snap_y), guides)
+    }
+
+    ///
+    /// Returns the snapped drag point and the guides to display while one of the selection handles is being dragged
+    ///
+    fn snap_handle_drag(data: &SelectData, handle: SelectHandle, initial_point: (f32, f32), drag_point: (f32, f32)) -> ((f32, f32), Vec<SnapGuide>) {
+        use self::SelectHandle::*;
+
+        let bounds = match data.selection_bounds {
+            Some(bounds)    => bounds,
+            None            => return (drag_point, vec![])
+        };
+
+        // Only the edges that the handle moves are snapped (rotations are never snapped)
+        let x_edge = match handle {
+            ScaleTopLeft | ScaleLeft | ScaleBottomLeft          => Some(bounds.x1),
+            ScaleTopRight | ScaleRight | ScaleBottomRight       => Some(bounds.x2),
+            ScaleTop | ScaleBottom | Rotate                     => None
+        };
+        let y_edge = match handle {
+            ScaleTopLeft | ScaleTop | ScaleTopRight             => Some(bounds.y2),
+            ScaleBottomLeft | ScaleBottom | ScaleBottomRight    => Some(bounds.y1),
+            ScaleLeft | ScaleRight | Rotate                     => None
+        };
+
+        let (dx, dy)                = (drag_point.0-initial_point.0, drag_point.1-initial_point.1);
+        let (x_targets, y_targets)  = data.snap_targets();
+
+        let (snap_x, guide_x)       = x_edge.map(|x| Self::snap_value(&[x+dx], &x_targets, data.snap_grid)).unwrap_or((0.0, None));
+        let (snap_y, guide_y)       = y_edge.map(|y| Self::snap_value(&[y+dy], &y_targets, data.snap_grid)).unwrap_or((0.0, None));
+
+        let guides = guide_x.map(SnapGuide::Vertical).into_iter()
+            .chain(guide_y.map(SnapGuide::Horizontal))
+            .collect();
+
+        ((drag_point.0+snap_x, drag_point.1+snap_y), guides)
+    }
+
+    ///
+    /// Draws the guides that a drag has snapped to
+    ///
+    fn draw_snap_guides(data: &SelectData, guides: &Vec<SnapGuide>) -> Vec<Draw> {
+        let mut drawing = vec![];
+
+        if guides.len() == 0 {
+            return drawing;
+        }
+
+        // Guides cover the canvas and all of the elements
+        let (width, height) = data.canvas_size;
+        let extent          = data.bounding_boxes.iter()
+            .fold(Rect { x1: 0.0, y1: 0.0, x2: width, y2: height }, |extent, (_, _, bounds)| extent.union(*bounds));
+
+        drawing.new_path();
+        for guide in guides.iter() {
+            match guide {
+                SnapGuide::Vertical(x)      => { drawing.move_to(*x, extent.y1); drawing.line_to(*x, extent.y2); }
+                SnapGuide::Horizontal(y)    => { drawing.move_to(extent.x1, *y); drawing.line_to(extent.x2, *y); }
+            }
+        }
+
+        drawing.line_width_pixels(1.0);
+        drawing.stroke_color(SNAP_GUIDE);
+        drawing.stroke();
+
+        drawing
+    }
+
     ///
     /// Returns the transformation to use during a drag of a selection handle
     ///
@@ -896,7 +1070,10 @@ impl Select {
                     .map(|item| item.clone())
                     .collect();
 
-                let draw_drag = Self::draw_drag(&*data, selected, data.initial_position.position, paint.location);
+                let (drag_point, guides)    = Self::snap_drag(&*data, data.initial_position.position, paint.location);
+                let mut draw_drag           = Self::draw_drag(&*data, selected, data.initial_position.position, drag_point);
+                draw_drag.extend(Self::draw_snap_guides(&*data, &guides));
+
                 actions.push(ToolAction::Overlay(OverlayAction::Draw(draw_drag)));
             },
 
@@ -909,7 +1086,8 @@ impl Select {
                 // Create a motion for this element
                 let selected_element_ids    = data.selected_elements.iter().cloned().collect();
                 let edit_time               = data.frame.as_ref().map(|frame| frame.time_index()).unwrap_or(Duration::from_millis(0));
-                let move_elements           = MotionEditAction::MoveElements(selected_element_ids, edit_time, data.initial_position.position, paint.location);
+                let (drag_point, _)         = Self::snap_drag(&*data, data.initial_position.position, paint.location);
+                let move_elements           = MotionEditAction::MoveElements(selected_element_ids, edit_time, data.initial_position.position, drag_point);
 
                 actions.extend(move_elements.to_animation_edits(&*animation).into_iter().map(|elem| ToolAction::Edit(elem)));
 
@@ -937,7 +1115,10 @@ impl Select {
                     .map(|item| item.clone())
                     .collect();
 
-                let draw_drag = Self::draw_drag_handle(&*data, handle, selected, data.initial_position.position, paint.location);
+                let (drag_point, guides)    = Self::snap_handle_drag(&*data, handle, data.initial_position.position, paint.location);
+                let mut draw_drag           = Self::draw_drag_handle(&*data, handle, selected, data.initial_position.position, drag_point);
+                draw_drag.extend(Self::draw_snap_guides(&*data, &guides));
+
                 actions.push(ToolAction::Overlay(OverlayAction::Draw(draw_drag)));
             },
 
@@ -950,7 +1131,8 @@ impl Select {
                 // Transform these elements
                 // (TODO: also support motions for these kinds of transformations, and support raw transformations for the elements themselves)
                 let selected_element_ids    = data.selected_elements.iter().cloned().collect();
-                let (origin, transform)     = Self::handle_transformation(data.selection_bounds.unwrap_or(Rect::empty()), handle, data.initial_position.position, paint.location);
+                let (drag_point, _)         = Self::snap_handle_drag(&*data, handle, data.initial_position.position, paint.location);
+                let (origin, transform)     = Self::handle_transformation(data.selection_bounds.unwrap_or(Rect::empty()), handle, data.initial_position.position, drag_point);
                 let transform_elements      = vec![ElementTransform::SetAnchor(origin.x(), origin.y()), transform];
                 let transform_elements      = vec![AnimationEdit::Element(selected_element_ids, ElementEdit::Transform(transform_elements))];
 
@@ -1021,7 +1203,7 @@ impl<Anim: 'static+EditableAnimation+Animation> Tool<Anim> for Select {
     ///
     /// Returns a stream containing the actions for the view and tool model for the select tool
     ///
-    fn actions_for_model(&self, flo_model: Arc<FloModel<Anim>>, _tool_model: &SelectToolModel) -> BoxStream<'static, ToolAction<SelectData>> {
+    fn actions_for_model(&self, flo_model: Arc<FloModel<Anim>>, tool_model: &SelectToolModel) -> BoxStream<'static, ToolAction<SelectData>> {
         // The set of currently selected elements
         let selected_elements   = flo_model.selection().selected_elements.clone();
 
@@ -1101,8 +1283,18 @@ impl<Anim: 'static+EditableAnimation+Animation> Tool<Anim> for Select {
         // (this also resets any in-progress action)
         let current_frame       = flo_model.frame().frame.clone();
         let selected_elements   = flo_model.selection().selected_elements.clone();
-        let data_for_model  = follow(computed(move || (current_frame.get(), selected_elements.get(), combined_bounding_boxes.get())))
-            .map(|(current_frame, selected_elements, combined_bounding_boxes)| {
+        let snap_to_grid        = tool_model.snap_to_grid.clone();
+        let grid_size           = tool_model.grid_size.clone();
+        let smart_guides        = tool_model.smart_guides.clone();
+        let canvas_size         = flo_model.size.clone();
+        let snap_settings       = computed(move || {
+            let snap_grid           = if snap_to_grid.get() { Some(grid_size.get()) } else { None };
+            let (width, height)     = canvas_size.get();
+
+            (snap_grid, smart_guides.get(), (width as f32, height as f32))
+        });
+        let data_for_model  = follow(computed(move || (current_frame.get(), selected_elements.get(), combined_bounding_boxes.get(), snap_settings.get())))
+            .map(|(current_frame, selected_elements, combined_bounding_boxes, (snap_grid, smart_guides, canvas_size))| {
                 // Collapse the bounding boxes to the selection bounds
                 let selection_bounds = (*combined_bounding_boxes).iter()
                     .fold(None, |maybe_bounds: Option<Rect>, (element_id, _, next_rect)| {
@@ -1124,7 +1316,10 @@ impl<Anim: 'static+EditableAnimation+Animation> Tool<Anim> for Select {
                     selection_bounds:       selection_bounds,
                     action:                 SelectAction::NoAction,
                     initial_position:       RawPoint::from((0.0, 0.0)),
-                    drag_position:          None
+                    drag_position:          None,
+                    snap_grid:              snap_grid,
+                    smart_guides:           smart_guides,
+                    canvas_size:            canvas_size
                 })
             });
 
@@ -1182,3 +1377,32 @@ impl<Anim: 'static+EditableAnimation+Animation> Tool<Anim> for Select {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn snap_to_nearest_target() {
+        let (offset, guide) = Select::snap_value(&[10.0, 20.0, 30.0], &[0.0, 33.0, 100.0], None);
+
+        assert!((offset - 3.0).abs() < 0.001);
+        assert!(guide == Some(33.0));
+    }
+
+    #[test]
+    fn snap_to_grid_when_no_target_is_close() {
+        let (offset, guide) = Select::snap_value(&[12.0, 22.0], &[100.0], Some(10.0));
+
+        assert!((offset - -2.0).abs() < 0.001);
+        assert!(guide == None);
+    }
+
+    #[test]
+    fn targets_take_priority_over_grid() {
+        let (offset, guide) = Select::snap_value(&[12.0], &[14.0], Some(10.0));
+
+        assert!((offset - 2.0).abs() < 0.001);
+        assert!(guide == Some(14.0));
+    }
+}
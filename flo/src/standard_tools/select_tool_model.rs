@@ -11,7 +11,16 @@ pub struct SelectToolModel {
     pub num_elements_selected: BindRef<u64>,
 
     /// True if any items have been selected
-    pub anything_selected: BindRef<bool>
+    pub anything_selected: BindRef<bool>,
+
+    /// True if dragged elements should snap to the grid
+    pub snap_to_grid: Binding<bool>,
+
+    /// The spacing of the grid that elements snap to
+    pub grid_size: Binding<f32>,
+
+    /// True if dragged elements should snap to the edges and centres of the other elements and the canvas
+    pub smart_guides: Binding<bool>
 }
 
 impl SelectToolModel {
@@ -30,7 +39,10 @@ impl SelectToolModel {
         // Create the model
         SelectToolModel {
             num_elements_selected:  BindRef::new(&num_elements_selected),
-            anything_selected:      BindRef::new(&anything_selected)
+            anything_selected:      BindRef::new(&anything_selected),
+            snap_to_grid:           bind(false),
+            grid_size:              bind(10.0),
+            smart_guides:           bind(true)
         }
     }
 }
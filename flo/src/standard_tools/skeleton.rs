@@ -0,0 +1,468 @@
+use super::super::tools::*;
+use super::super::model::*;
+use super::super::style::*;
+
+use flo_canvas::*;
+use flo_curves::*;
+use flo_binding::*;
+use flo_animation::*;
+
+use futures::*;
+use futures::stream;
+use futures::stream::{BoxStream};
+
+use std::sync::*;
+use std::time::Duration;
+use std::collections::{HashSet, HashMap};
+
+///
+/// The current action being performed by the skeleton tool
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum SkeletonAction {
+    /// The tool is idle
+    NoAction,
+
+    /// A bone is being rotated (bone ID, posed origin of the bone, initial rotation, drag start, drag end)
+    Pose(ElementId, (f32, f32), f64, (f32, f32), (f32, f32)),
+
+    /// A new bone is being drawn (parent bone, start point, end point)
+    Create(Option<ElementId>, (f32, f32), (f32, f32))
+}
+
+///
+/// A bone as it appears in the current frame
+///
+#[derive(Clone, Debug, PartialEq)]
+struct PosedBone {
+    /// The ID of the bone
+    id: ElementId,
+
+    /// The bone definition
+    bone: Arc<Bone>,
+
+    /// The transformations applied by the parents of this bone
+    parent_transformations: Vec<Transformation>,
+
+    /// The position of the origin of the bone in the current frame
+    origin: (f32, f32),
+
+    /// The position of the tip of the bone in the current frame
+    tip: (f32, f32)
+}
+
+///
+/// Data for the Skeleton tool
+///
+#[derive(Clone)]
+pub struct SkeletonData {
+    /// The current frame
+    frame: Option<Arc<dyn Frame>>,
+
+    /// The time of the current frame
+    frame_time: Duration,
+
+    /// The current state of this data
+    state: Binding<SkeletonAction>,
+
+    // The current set of selected elements
+    selected_elements: Arc<HashSet<ElementId>>,
+
+    // The bones in the current frame
+    bones: Arc<Vec<PosedBone>>
+}
+
+///
+/// Applies a list of transformations to a point
+///
+fn transform_point<'a, TransformIter: IntoIterator<Item=&'a Transformation>>(transformations: TransformIter, point: (f32, f32)) -> (f32, f32) {
+    let Coord2(x, y) = transformations.into_iter()
+        .fold(Coord2(point.0 as f64, point.1 as f64), |point, transform| transform.transform_point(&point));
+
+    (x as f32, y as f32)
+}
+
+///
+/// Returns the distance between a point and a line segment
+///
+fn distance_to_segment(point: (f32, f32), start: (f32, f32), end: (f32, f32)) -> f32 {
+    let (dx, dy)    = (end.0-start.0, end.1-start.1);
+    let length_sq   = dx*dx + dy*dy;
+
+    let t           = if length_sq > 0.0 { ((point.0-start.0)*dx + (point.1-start.1)*dy) / length_sq } else { 0.0 };
+    let t           = t.max(0.0).min(1.0);
+
+    let nearest     = (start.0 + dx*t, start.1 + dy*t);
+    let (dx, dy)    = (point.0-nearest.0, point.1-nearest.1);
+
+    (dx*dx + dy*dy).sqrt()
+}
+
+///
+/// Returns the angle of a point relative to an origin
+///
+fn angle_from(origin: (f32, f32), point: (f32, f32)) -> f64 {
+    ((point.1-origin.1) as f64).atan2((point.0-origin.0) as f64)
+}
+
+impl SkeletonData {
+    ///
+    /// Finds the bone nearest to the specified point, if it's within a certain distance
+    ///
+    fn bone_at_point(&self, location: (f32, f32), max_distance: f32) -> Option<&PosedBone> {
+        let mut nearest     = None;
+        let mut min_dist    = max_distance;
+
+        for bone in self.bones.iter() {
+            let distance = distance_to_segment(location, bone.origin, bone.tip);
+
+            if distance < min_dist {
+                min_dist    = distance;
+                nearest     = Some(bone);
+            }
+        }
+
+        nearest
+    }
+
+    ///
+    /// Finds the bone whose tip is nearest to the specified point, if it's within a certain distance
+    ///
+    fn bone_tip_at_point(&self, location: (f32, f32), max_distance: f32) -> Option<&PosedBone> {
+        let mut nearest     = None;
+        let mut min_dist    = max_distance;
+
+        for bone in self.bones.iter() {
+            let (dx, dy)    = (location.0-bone.tip.0, location.1-bone.tip.1);
+            let distance    = (dx*dx + dy*dy).sqrt();
+
+            if distance < min_dist {
+                min_dist    = distance;
+                nearest     = Some(bone);
+            }
+        }
+
+        nearest
+    }
+}
+
+///
+/// The Skeleton tool (creates bones and poses them)
+///
+pub struct Skeleton { }
+
+impl Skeleton {
+    ///
+    /// Creates a new instance of the Skeleton tool
+    ///
+    pub fn new() -> Skeleton {
+        Skeleton {}
+    }
+
+    ///
+    /// Works out where the bones in a frame are at a particular time
+    ///
+    fn posed_bones(frame: &dyn Frame, when: Duration) -> Vec<PosedBone> {
+        let bones       = frame.bones();
+        let bone_lookup = bones.iter().map(|bone| (bone.id(), bone.bone())).collect::<HashMap<_, _>>();
+        let find_bone   = |bone_id| bone_lookup.get(&bone_id).cloned();
+
+        bones.iter()
+            .map(|bone_element| {
+                let bone                    = bone_element.bone();
+                let parent_transformations  = bone.parent
+                    .map(|parent_id| skeleton_transformations(parent_id, when, &find_bone))
+                    .unwrap_or_else(|| vec![]);
+                let own_transformation      = bone.transformation(when);
+
+                let origin                  = transform_point(parent_transformations.iter(), bone.origin);
+                let tip                     = transform_point(own_transformation.iter().chain(parent_transformations.iter()), bone.tip());
+
+                PosedBone {
+                    id:                     bone_element.id(),
+                    bone:                   bone,
+                    parent_transformations: parent_transformations,
+                    origin:                 origin,
+                    tip:                    tip
+                }
+            })
+            .collect()
+    }
+
+    ///
+    /// Returns a binding containing the bones in the current frame
+    ///
+    fn bones<Anim: 'static+Animation>(flo_model: &FloModel<Anim>) -> BindRef<Arc<Vec<PosedBone>>> {
+        let frame       = flo_model.frame().frame.clone();
+        let frame_time  = flo_model.timeline().current_time.clone();
+
+        BindRef::new(&computed(move || {
+            let bones = match frame.get() {
+                Some(frame) => Self::posed_bones(&*frame, frame_time.get()),
+                None        => vec![]
+            };
+
+            Arc::new(bones)
+        }))
+    }
+
+    ///
+    /// Draws a single bone
+    ///
+    fn draw_bone(origin: (f32, f32), tip: (f32, f32)) -> Vec<Draw> {
+        let mut draw = vec![];
+
+        draw.new_path();
+        draw.move_to(origin.0, origin.1);
+        draw.line_to(tip.0, tip.1);
+
+        draw.line_width_pixels(5.0);
+        draw.stroke_color(BONE_OUTLINE);
+        draw.stroke();
+        draw.line_width_pixels(3.0);
+        draw.stroke_color(BONE_FILL);
+        draw.stroke();
+
+        draw.new_path();
+        draw.circle(origin.0, origin.1, 4.0);
+        draw.fill_color(BONE_FILL);
+        draw.fill();
+        draw.line_width_pixels(1.0);
+        draw.stroke_color(BONE_OUTLINE);
+        draw.stroke();
+
+        draw
+    }
+
+    ///
+    /// Creates an action stream that draws the bones in the current frame
+    ///
+    fn draw_bones_overlay(bones: BindRef<Arc<Vec<PosedBone>>>) -> impl Stream<Item=ToolAction<SkeletonData>> {
+        follow(bones)
+            .map(|bones| {
+                let mut draw_bones = vec![];
+
+                draw_bones.layer(0);
+                draw_bones.clear_layer();
+
+                for bone in bones.iter() {
+                    draw_bones.extend(Self::draw_bone(bone.origin, bone.tip));
+                }
+
+                ToolAction::Overlay(OverlayAction::Draw(draw_bones))
+            })
+    }
+
+    ///
+    /// Creates an action stream that draws the bone that's being posed or created
+    ///
+    fn draw_edit_overlay(tool_state: BindRef<SkeletonAction>, bones: BindRef<Arc<Vec<PosedBone>>>) -> impl Stream<Item=ToolAction<SkeletonData>> {
+        let edit_state = computed(move || {
+            match tool_state.get() {
+                SkeletonAction::NoAction    => None,
+                state                       => Some((state, bones.get()))
+            }
+        });
+
+        follow(edit_state)
+            .map(|edit_state| {
+                let mut draw_edit = vec![];
+
+                draw_edit.layer(1);
+                draw_edit.clear_layer();
+
+                match edit_state {
+                    Some((SkeletonAction::Pose(bone_id, origin, _, from, to), bones)) => {
+                        // Rotate the tip of the bone around its origin
+                        if let Some(bone) = bones.iter().filter(|bone| bone.id == bone_id).nth(0) {
+                            let rotation    = Transformation::Rotate(angle_from(origin, to) - angle_from(origin, from), (origin.0 as f64, origin.1 as f64));
+                            let tip         = transform_point(Some(&rotation), bone.tip);
+
+                            draw_edit.extend(Self::draw_bone(origin, tip));
+                        }
+                    }
+
+                    Some((SkeletonAction::Create(_, from, to), _)) => {
+                        draw_edit.extend(Self::draw_bone(from, to));
+                    }
+
+                    _ => { }
+                }
+
+                ToolAction::Overlay(OverlayAction::Draw(draw_edit))
+            })
+    }
+
+    ///
+    /// Generates the edits that create a new bone and bind the selected elements to it
+    ///
+    fn create_bone<Anim: 'static+EditableAnimation+Animation>(data: &SkeletonData, model: &FloModel<Anim>, parent: Option<ElementId>, from: (f32, f32), to: (f32, f32)) -> Vec<ToolAction<SkeletonData>> {
+        // The points are in the posed coordinates of the frame: reverse the parent's pose to get the coordinates of the bone at rest
+        let parent_transformations  = parent
+            .and_then(|parent_id| data.bones.iter().filter(|bone| bone.id == parent_id).nth(0))
+            .map(|parent| {
+                let mut transformations = parent.bone.transformation(data.frame_time).into_iter().chain(parent.parent_transformations.iter().cloned()).collect::<Vec<_>>();
+                transformations.reverse();
+                transformations.into_iter().flat_map(|transform| transform.invert()).collect::<Vec<_>>()
+            })
+            .unwrap_or_else(|| vec![]);
+
+        let from                    = transform_point(parent_transformations.iter(), from);
+        let to                      = transform_point(parent_transformations.iter(), to);
+
+        // Elements can only be bound to a single bone, so detach them from any bones they're already bound to
+        let selected_elements       = data.selected_elements.iter().cloned().collect::<Vec<_>>();
+        let detach_bones            = data.frame.as_ref()
+            .map(|frame| {
+                selected_elements.iter()
+                    .flat_map(|element_id| frame.attached_elements(*element_id).into_iter().map(move |attachment| (*element_id, attachment)))
+                    .filter(|(_, (_, attachment_type))| *attachment_type == VectorType::Bone)
+                    .map(|(element_id, (bone_id, _))| AnimationEdit::Element(vec![element_id], ElementEdit::RemoveAttachment(bone_id)))
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_else(|| vec![]);
+
+        // Create the new bone
+        let create_bone             = BoneEditAction::CreateBone(parent, from, to, selected_elements).to_animation_edits(model);
+
+        detach_bones.into_iter()
+            .chain(create_bone)
+            .map(|edit| ToolAction::Edit(edit))
+            .chain(vec![ToolAction::InvalidateFrame])
+            .collect()
+    }
+
+    ///
+    /// Generates the tool actions for a painting action
+    ///
+    fn paint<Anim: 'static+EditableAnimation+Animation>(&self, painting: Painting, data: &SkeletonData, model: &FloModel<Anim>) -> Vec<ToolAction<SkeletonData>> {
+        let state           = data.state.get();
+        let paint_action    = painting.action;
+
+        match (state, paint_action) {
+            (_, PaintAction::Start) => {
+                if let Some(bone) = data.bone_at_point(painting.location, 6.0) {
+                    // Clicking on a bone starts posing it
+                    let rotation = bone.bone.rotation_at(data.frame_time);
+                    data.state.set(SkeletonAction::Pose(bone.id, bone.origin, rotation, painting.location, painting.location));
+                } else if data.selected_elements.len() > 0 {
+                    // Dragging elsewhere creates a new bone for the selected elements, joined to any bone whose tip it starts at
+                    let parent = data.bone_tip_at_point(painting.location, 8.0).map(|bone| bone.id);
+                    data.state.set(SkeletonAction::Create(parent, painting.location, painting.location));
+                }
+
+                vec![]
+            },
+
+            (SkeletonAction::Pose(bone_id, origin, rotation, from, _to), PaintAction::Continue)    |
+            (SkeletonAction::Pose(bone_id, origin, rotation, from, _to), PaintAction::Prediction)  => {
+                data.state.set(SkeletonAction::Pose(bone_id, origin, rotation, from, painting.location));
+                vec![]
+            },
+
+            (SkeletonAction::Create(parent, from, _to), PaintAction::Continue)     |
+            (SkeletonAction::Create(parent, from, _to), PaintAction::Prediction)   => {
+                data.state.set(SkeletonAction::Create(parent, from, painting.location));
+                vec![]
+            },
+
+            (SkeletonAction::Pose(bone_id, origin, rotation, from, _to), PaintAction::Finish) => {
+                data.state.set(SkeletonAction::NoAction);
+
+                // Set the rotation of the bone at the current time
+                let new_rotation = rotation + angle_from(origin, painting.location) - angle_from(origin, from);
+
+                vec![
+                    ToolAction::Edit(AnimationEdit::Bone(bone_id, BoneEdit::SetRotation(data.frame_time, new_rotation))),
+                    ToolAction::InvalidateFrame
+                ]
+            },
+
+            (SkeletonAction::Create(parent, from, _to), PaintAction::Finish) => {
+                data.state.set(SkeletonAction::NoAction);
+
+                // Ignore clicks that are too short to make a bone
+                let (dx, dy) = (painting.location.0-from.0, painting.location.1-from.1);
+                if (dx*dx + dy*dy).sqrt() < 4.0 {
+                    return vec![];
+                }
+
+                Self::create_bone(data, model, parent, from, painting.location)
+            },
+
+            (_, PaintAction::Finish) |
+            (_, PaintAction::Cancel) => {
+                data.state.set(SkeletonAction::NoAction);
+                vec![]
+            },
+
+            _ => vec![]
+        }
+    }
+}
+
+impl<Anim: 'static+EditableAnimation+Animation> Tool<Anim> for Skeleton {
+    type ToolData   = SkeletonData;
+    type Model      = ();
+
+    fn tool_name(&self) -> String { "Skeleton".to_string() }
+
+    fn image_name(&self) -> String { "skeleton".to_string() }
+
+    fn create_model(&self, _flo_model: Arc<FloModel<Anim>>) -> () { }
+
+    ///
+    /// Returns a stream containing the actions for the view and tool model for the skeleton tool
+    ///
+    fn actions_for_model(&self, flo_model: Arc<FloModel<Anim>>, _tool_model: &()) -> BoxStream<'static, ToolAction<SkeletonData>> {
+        let current_frame       = flo_model.frame().frame.clone();
+        let selected_elements   = flo_model.selection().selected_elements.clone();
+        let frame_time          = flo_model.timeline().current_time.clone();
+        let bones               = Self::bones(&*flo_model);
+
+        // State is initially 'no action'
+        let skeleton_state      = bind(SkeletonAction::NoAction);
+
+        // Draw the bones, and the bone that's being edited
+        let draw_bones          = Self::draw_bones_overlay(bones.clone());
+        let draw_edit           = Self::draw_edit_overlay(BindRef::new(&skeleton_state), bones.clone());
+
+        // Build the tool data from the current frame
+        let update_data         = follow(computed(move || (current_frame.get(), selected_elements.get(), bones.get(), frame_time.get())))
+            .map(move |(frame, selected_elements, bones, frame_time)| {
+                ToolAction::Data(SkeletonData {
+                    frame:              frame,
+                    frame_time:         frame_time,
+                    state:              skeleton_state.clone(),
+                    selected_elements:  selected_elements,
+                    bones:              bones
+                })
+            });
+
+        Box::pin(stream::select(stream::select(update_data, draw_bones), draw_edit))
+    }
+
+    fn actions_for_input<'a>(&'a self, flo_model: Arc<FloModel<Anim>>, data: Option<Arc<SkeletonData>>, input: Box<dyn 'a+Iterator<Item=ToolInput<SkeletonData>>>) -> Box<dyn 'a+Iterator<Item=ToolAction<SkeletonData>>> {
+        let mut data    = data;
+        let mut actions = vec![];
+        let input       = ToolInput::last_paint_actions_only(input);
+
+        for input in input {
+            match input {
+                ToolInput::Data(new_data) => {
+                    data = Some(new_data);
+                },
+
+                ToolInput::Paint(painting) => {
+                    if let Some(data) = data.as_ref() {
+                        actions.extend(self.paint(painting, &**data, &*flo_model));
+                    }
+                }
+
+                _ => ()
+            }
+        }
+
+        Box::new(actions.into_iter())
+    }
+}
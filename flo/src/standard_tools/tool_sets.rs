@@ -14,9 +14,11 @@ lazy_static! {
 /// The selection toolset
 ///
 pub struct SelectionTools<Anim: 'static+Animation> {
-    select: Arc<FloTool<Anim>>,
-    adjust: Arc<FloTool<Anim>>,
-    pan:    Arc<FloTool<Anim>>
+    select:         Arc<FloTool<Anim>>,
+    adjust:         Arc<FloTool<Anim>>,
+    motion_path:    Arc<FloTool<Anim>>,
+    skeleton:       Arc<FloTool<Anim>>,
+    pan:            Arc<FloTool<Anim>>
 }
 
 ///
@@ -31,9 +33,11 @@ pub struct PaintTools<Anim: 'static+Animation> {
 impl<Anim: EditableAnimation+Animation> SelectionTools<Anim> {
     pub fn new() -> SelectionTools<Anim> {
         SelectionTools {
-            select: Select::new().to_flo_tool(),
-            adjust: Adjust::new().to_flo_tool(),
-            pan:    Pan::new().to_flo_tool()
+            select:         Select::new().to_flo_tool(),
+            adjust:         Adjust::new().to_flo_tool(),
+            motion_path:    MotionPath::new().to_flo_tool(),
+            skeleton:       Skeleton::new().to_flo_tool(),
+            pan:            Pan::new().to_flo_tool()
         }
     }
 }
@@ -57,6 +61,8 @@ impl<Anim: Animation> ToolSet<Anim> for SelectionTools<Anim> {
         vec![
             Arc::clone(&self.select),
             Arc::clone(&self.adjust),
+            Arc::clone(&self.motion_path),
+            Arc::clone(&self.skeleton),
             Arc::clone(&self.pan)
         ]
     }
@@ -12,6 +12,7 @@ pub const TIMESCALE_LAYERS:                 Color = Color::Rgba(0.42, 0.45, 0.45
 pub const TIMESCALE_CELL:                   Color = Color::Rgba(0.36, 0.4, 0.4, 1.0);
 pub const TIMESCALE_BACKGROUND:             Color = Color::Rgba(0.3, 0.3, 0.3, 1.0);
 pub const TIMESCALE_KEYFRAME:               Color = Color::Rgba(0.2, 0.6, 0.7, 1.0);
+pub const TIMESCALE_KEYFRAME_SELECTED:      Color = Color::Rgba(0.9, 0.85, 0.4, 1.0);
 pub const TIMESCALE_INDICATOR:              Color = Color::Rgba(0.2, 0.6, 0.7, 1.0);
 pub const TIMESCALE_INDICATOR2:             Color = Color::Rgba(0.5, 0.85, 1.0, 1.0);
 pub const TIMESCALE_INDICATOR_OUTER_GLOW:   Color = Color::Rgba(0.2, 0.5, 0.8, 1.0);
@@ -32,12 +33,34 @@ pub const SELECTION_OUTLINE:                Color = Color::Rgba(0.0, 0.0, 0.0, 0
 pub const SELECTION_BBOX:                   Color = Color::Rgba(0.2, 0.8, 1.0, 1.0);
 pub const SELECTION_HIGHLIGHT:              Color = Color::Rgba(0.6, 1.0, 1.0, 1.0);
 pub const SELECTION_FILL:                   Color = Color::Rgba(0.6, 0.8, 0.9, 0.3);
+pub const SNAP_GUIDE:                       Color = Color::Rgba(1.0, 0.3, 0.7, 0.9);
 
 pub const CP_BEZIER:                        Color = Color::Rgba(0.9, 0.7, 0.5, 0.85);
 pub const CP_BEZIER_CP:                     Color = Color::Rgba(0.6, 0.8, 0.9, 0.85);
 pub const CP_LINES:                         Color = Color::Rgba(0.6, 0.6, 0.6, 0.7);
 
+pub const MOTION_PATH:                      Color = Color::Rgba(0.9, 0.6, 0.3, 0.9);
+pub const MOTION_PATH_TICK:                 Color = Color::Rgba(0.95, 0.8, 0.5, 0.9);
+pub const MOTION_PATH_EASING:               Color = Color::Rgba(0.5, 0.9, 0.6, 0.9);
+
+pub const BONE_OUTLINE:                     Color = Color::Rgba(0.1, 0.1, 0.1, 0.6);
+pub const BONE_FILL:                        Color = Color::Rgba(0.95, 0.85, 0.6, 0.9);
+
 pub const ONIONSKIN_PAST:                   Color = Color::Rgba(0.8, 0.3, 0.3, 1.0);
 pub const ONIONSKIN_FUTURE:                 Color = Color::Rgba(0.3, 0.6, 0.8, 1.0);
 
+pub const LOG_BACKGROUND:                   Color = Color::Rgba(0.18, 0.18, 0.20, 1.0);
+pub const LOG_EXPANDED_BACKGROUND:          Color = Color::Rgba(0.24, 0.24, 0.27, 1.0);
+pub const LOG_FILTER_SELECTED:              Color = Color::Rgba(0.20, 0.35, 0.50, 1.0);
+pub const LOG_ERROR:                        Color = Color::Rgba(1.0, 0.45, 0.4, 1.0);
+pub const LOG_WARNING:                      Color = Color::Rgba(1.0, 0.8, 0.4, 1.0);
+pub const LOG_INFO:                         Color = Color::Rgba(0.85, 0.95, 0.9, 1.0);
+pub const LOG_DEBUG:                        Color = Color::Rgba(0.6, 0.7, 0.75, 1.0);
+
+pub const GRAPH_BACKGROUND:                 Color = Color::Rgba(0.18, 0.18, 0.20, 1.0);
+pub const GRAPH_AXIS:                       Color = Color::Rgba(0.5, 0.5, 0.55, 1.0);
+pub const GRAPH_X_VALUE:                    Color = Color::Rgba(0.9, 0.45, 0.4, 1.0);
+pub const GRAPH_Y_VALUE:                    Color = Color::Rgba(0.45, 0.8, 0.5, 1.0);
+pub const GRAPH_TIMING_LANE:                Color = Color::Rgba(0.24, 0.24, 0.27, 1.0);
+
 pub const FILE_CHOOSER_BACKGROUND:          Color = Color::Rgba(0.165, 0.250, 0.198, 1.0);
@@ -80,7 +80,8 @@ impl GenericToolData {
             Overlay(overlay)        => Overlay(overlay),
             Select(element)         => Select(element),
             ClearSelection          => ClearSelection,
-            InvalidateFrame         => InvalidateFrame
+            InvalidateFrame         => InvalidateFrame,
+            View(view_action)       => View(view_action)
         }
     }
 
@@ -1,5 +1,6 @@
 use super::brush_preview_action::*;
 use super::overlay_action::*;
+use super::super::model::*;
 
 use flo_animation::*;
 
@@ -27,5 +28,8 @@ pub enum ToolAction<ToolData> {
     ClearSelection,
 
     /// Adds a particular element to the selection
-    Select(ElementId)
+    Select(ElementId),
+
+    /// Changes how the canvas is viewed (without editing the animation)
+    View(ViewportAction)
 }
@@ -1,35 +1,80 @@
-use std::io::{Read, Error};
-use std::mem;
+use super::encoding::*;
+
+use std::io::{Read, Error, ErrorKind};
 
 ///
 /// Reads a squished float from a source stream
 ///
+/// This can read values written by `squish_float()` or by `squish_float_with_encoding()` with any encoding.
+///
 pub fn unsquish_float<Source: Read>(src: &mut Source, last: f64) -> Result<f64, Error> {
     let last = if last.is_infinite() || last.is_nan() { 0.0 } else { last };
 
-    let nan: u16        = 0b1000_0000_0000_0000;
-
     // Read the fixed-point diff
     let mut diff_bytes  = [0,0];
     src.read_exact(&mut diff_bytes)?;
-    let diff: i16       = (diff_bytes[0] as i16) | ((diff_bytes[1] as i16)<<8);
-
-    if diff == (nan as i16) {
-        // Diff stored as a f32
-        let mut diff_bytes          = [0,0,0,0];
-        src.read_exact(&mut diff_bytes)?;
-        let diff_bit_pattern: u32   =
-              ((diff_bytes[0] as u32)<<0)
-            | ((diff_bytes[1] as u32)<<8)
-            | ((diff_bytes[2] as u32)<<16)
-            | ((diff_bytes[3] as u32)<<24);
-
-        let diff: f32 = unsafe { mem::transmute(diff_bit_pattern) };
-
-        Ok(last + (diff as f64))
-    } else {
+
+    if diff_bytes[1] != TAG_MARKER {
         // Turn the diff into a float
+        let diff = i16::from_le_bytes(diff_bytes);
         let diff = (diff as f64) / 256.0;
-        Ok(last + diff)
+        return Ok(last + diff);
+    }
+
+    // Values with the tag marker describe how the value is stored
+    match diff_bytes[0] {
+        TAG_F32 => {
+            // Diff stored as a f32
+            let mut diff_bytes  = [0,0,0,0];
+            src.read_exact(&mut diff_bytes)?;
+            let diff            = f32::from_bits(u32::from_le_bytes(diff_bytes));
+
+            Ok(last + (diff as f64))
+        }
+
+        TAG_F64 => {
+            // Value stored exactly
+            let mut value_bytes = [0,0,0,0,0,0,0,0];
+            src.read_exact(&mut value_bytes)?;
+
+            Ok(f64::from_bits(u64::from_le_bytes(value_bytes)))
+        }
+
+        tag if (tag & 0xf0) == TAG_FIXED_POINT => {
+            // Diff stored as an i16 with (tag & 0xf) fractional bits
+            let bits            = tag & 0x0f;
+            let mut diff_bytes  = [0,0];
+            src.read_exact(&mut diff_bytes)?;
+            let diff            = i16::from_le_bytes(diff_bytes);
+
+            Ok(last + (diff as f64) / ((1u32<<bits) as f64))
+        }
+
+        tag if (tag & 0xe0) == TAG_VARINT => {
+            // Diff stored as a zig-zag LEB128 value with (tag & 0x1f) fractional bits
+            let bits            = tag & 0x1f;
+            let mut zig_zag     = 0u64;
+            let mut shift       = 0;
+
+            loop {
+                let mut byte = [0];
+                src.read_exact(&mut byte)?;
+
+                if shift < 64 {
+                    zig_zag |= ((byte[0] & 0x7f) as u64) << shift;
+                }
+                shift += 7;
+
+                if (byte[0] & 0x80) == 0 {
+                    break;
+                }
+            }
+
+            let diff = ((zig_zag >> 1) as i64) ^ -((zig_zag & 1) as i64);
+
+            Ok(last + (diff as f64) / ((1u64<<bits) as f64))
+        }
+
+        _ => Err(Error::new(ErrorKind::InvalidData, "Unknown float encoding"))
     }
 }
@@ -1,8 +1,6 @@
-use std::io::{Write, Error};
-use std::mem;
+use super::encoding::*;
 
-/// Maximum distance to use for f16 difference encoding (larger differences increase data squishing but reduce precision)
-const MAX_DISTANCE: f32 = 126.0;
+use std::io::{Write, Error};
 
 ///
 /// Writes a squished float to the specified stream
@@ -11,27 +9,122 @@ const MAX_DISTANCE: f32 = 126.0;
 /// be chosen (provided it's re-used when de-squishing). 0.0 will work well.
 ///
 pub fn squish_float<Target: Write>(target: &mut Target, last: f64, next: f64) -> Result<usize, Error> {
+    squish_float_with_encoding(target, FloatEncoding::default(), last, next)
+}
+
+///
+/// Writes a squished float to the specified stream using a particular encoding
+///
+/// The value can be read back with `unsquish_float()`, which can decode any of the encodings. As with `squish_float()`,
+/// the same 'last' value must be used when de-squishing.
+///
+pub fn squish_float_with_encoding<Target: Write>(target: &mut Target, encoding: FloatEncoding, last: f64, next: f64) -> Result<usize, Error> {
     let last = if last.is_infinite() || last.is_nan() { 0.0 } else { last };
 
+    match encoding {
+        FloatEncoding::FixedPoint(8)            => squish_8_8(target, last, next),
+        FloatEncoding::FixedPoint(bits)         => squish_fixed_point(target, bits.min(15), last, next),
+        FloatEncoding::VarInt(bits)             => squish_varint(target, bits.min(31), last, next),
+        FloatEncoding::Lossless                 => squish_lossless(target, next)
+    }
+}
+
+///
+/// Writes a tag value to the target
+///
+fn write_tag<Target: Write>(target: &mut Target, tag: u8) -> Result<usize, Error> {
+    target.write_all(&[tag, TAG_MARKER])?;
+    Ok(2)
+}
+
+///
+/// Writes the difference between two floats as an f32 value
+///
+fn squish_f32<Target: Write>(target: &mut Target, diff: f32) -> Result<usize, Error> {
+    write_tag(target, TAG_F32)?;
+    target.write_all(&diff.to_bits().to_le_bytes())?;
+
+    Ok(6)
+}
+
+///
+/// Writes a float in the original format (8.8 fixed point, or f32 if the difference is too large)
+///
+fn squish_8_8<Target: Write>(target: &mut Target, last: f64, next: f64) -> Result<usize, Error> {
     // What we encode is the difference between two floats
     let diff = (next - last) as f32;
 
-    if last.is_nan() || next.is_nan() || diff.abs() > MAX_DISTANCE {
+    if next.is_nan() || diff.abs() > MAX_DISTANCE {
         // For 'bad' floats we encode as f16 NAN, f32 val
-        let nan: u16            = 0b1000_0000_0000_0000;
-        let bit_pattern: u32    = unsafe { mem::transmute(diff) };
-
-        target.write(&[(nan&0xff) as u8, (nan>>8) as u8])?;
-        target.write(&[
-            ((bit_pattern>>0) &0xff) as u8,
-            ((bit_pattern>>8) &0xff) as u8,
-            ((bit_pattern>>16)&0xff) as u8,
-            ((bit_pattern>>24)&0xff) as u8
-        ])
+        squish_f32(target, diff)
     } else {
         // For 'good' floats we encode as a 16-bit fixed point number
         let fixed_point = (diff * 256.0) as i16;
 
-        target.write(&[(fixed_point&0xff) as u8, (fixed_point>>8) as u8])
+        target.write_all(&fixed_point.to_le_bytes())?;
+        Ok(2)
+    }
+}
+
+///
+/// Writes a float as a 16-bit fixed point number with a specified number of fractional bits
+///
+fn squish_fixed_point<Target: Write>(target: &mut Target, bits: u8, last: f64, next: f64) -> Result<usize, Error> {
+    let diff    = next - last;
+    let scaled  = (diff * ((1u32<<bits) as f64)).round();
+
+    if scaled.is_nan() || scaled < (i16::MIN as f64) || scaled > (i16::MAX as f64) {
+        // Too large to represent with this precision
+        squish_f32(target, diff as f32)
+    } else {
+        write_tag(target, TAG_FIXED_POINT | bits)?;
+        target.write_all(&(scaled as i16).to_le_bytes())?;
+
+        Ok(4)
+    }
+}
+
+///
+/// Writes a float as a variable-length fixed point number with a specified number of fractional bits
+///
+fn squish_varint<Target: Write>(target: &mut Target, bits: u8, last: f64, next: f64) -> Result<usize, Error> {
+    let diff    = next - last;
+    let scaled  = (diff * ((1u64<<bits) as f64)).round();
+
+    if !scaled.is_finite() || scaled.abs() > ((1u64<<62) as f64) {
+        // Values that can't be represented as an integer are stored exactly instead
+        return squish_lossless(target, next);
     }
+
+    // Zig-zag encoding keeps small negative numbers small
+    let scaled          = scaled as i64;
+    let mut remaining   = ((scaled << 1) ^ (scaled >> 63)) as u64;
+    let mut bytes       = vec![];
+
+    loop {
+        let lower_bits  = (remaining & 0x7f) as u8;
+        remaining       >>= 7;
+
+        if remaining > 0 {
+            bytes.push(lower_bits | 0x80);
+        } else {
+            bytes.push(lower_bits);
+            break;
+        }
+    }
+
+    write_tag(target, TAG_VARINT | bits)?;
+    target.write_all(&bytes)?;
+
+    Ok(2 + bytes.len())
+}
+
+///
+/// Writes a float exactly
+///
+fn squish_lossless<Target: Write>(target: &mut Target, next: f64) -> Result<usize, Error> {
+    write_tag(target, TAG_F64)?;
+    target.write_all(&next.to_bits().to_le_bytes())?;
+
+    Ok(10)
 }
@@ -0,0 +1,58 @@
+///
+/// The ways that `squish_float_with_encoding()` can store a value
+///
+/// Every encoding can be read back by `unsquish_float()`, so a stream can mix values written with different encodings.
+/// Values are stored as a 16-bit little-endian value, which is either a difference from the previous value in 8.8 fixed
+/// point or (when the upper byte is `0x80`, which is never the case for a difference small enough to store this way) a
+/// tag describing how the value that follows is stored:
+///
+/// * `0x8000` - the difference as an `f32`
+/// * `0x8001` - the value itself as an `f64`
+/// * `0x8010 + bits` - the difference as an `i16` fixed point value with `bits` fractional bits
+/// * `0x8020 + bits` - the difference as a zig-zag encoded LEB128 fixed point value with `bits` fractional bits
+///
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum FloatEncoding {
+    /// Differences are stored as 16-bit fixed point values with the specified number of fractional bits (at most 15), falling
+    /// back to `f32` values for differences that are too large. 8 bits (a precision of 1/256) is the original, most compact format.
+    FixedPoint(u8),
+
+    /// Differences are stored as variable-length fixed point values with the specified number of fractional bits (at most 31).
+    /// Small differences use fewer bytes, and large differences keep their precision instead of becoming `f32` values.
+    VarInt(u8),
+
+    /// Values are stored exactly, as `f64` values
+    Lossless
+}
+
+/// Maximum distance to use for f16 difference encoding (larger differences increase data squishing but reduce precision)
+pub (crate) const MAX_DISTANCE: f32 = 126.0;
+
+/// Upper byte of the 16-bit value used to indicate that it's a tag rather than a difference
+pub (crate) const TAG_MARKER: u8 = 0x80;
+
+/// Tag indicating that an f32 difference follows
+pub (crate) const TAG_F32: u8 = 0x00;
+
+/// Tag indicating that an f64 value follows
+pub (crate) const TAG_F64: u8 = 0x01;
+
+/// Tag indicating that an i16 difference follows (the lower 4 bits are the number of fractional bits)
+pub (crate) const TAG_FIXED_POINT: u8 = 0x10;
+
+/// Tag indicating that a LEB128 difference follows (the lower 5 bits are the number of fractional bits)
+pub (crate) const TAG_VARINT: u8 = 0x20;
+
+impl FloatEncoding {
+    /// Fixed point values with a precision of 1/256 (the original encoding)
+    pub const PRECISION_256: FloatEncoding = FloatEncoding::FixedPoint(8);
+
+    /// Fixed point values with a precision of 1/4096
+    pub const PRECISION_4096: FloatEncoding = FloatEncoding::FixedPoint(12);
+}
+
+impl Default for FloatEncoding {
+    fn default() -> FloatEncoding {
+        FloatEncoding::PRECISION_256
+    }
+}
@@ -1,8 +1,10 @@
 #![warn(bare_trait_objects)]
 
+mod encoding;
 mod encode;
 mod decode;
 
+pub use self::encoding::{FloatEncoding};
 pub use self::encode::*;
 pub use self::decode::*;
 
@@ -222,4 +224,131 @@ mod test {
         squish_float(&mut target, 0.0, -1.0).unwrap();
         assert!(target.len() == 2);
     }
+
+    #[test]
+    fn default_encoding_is_unchanged() {
+        let mut target      = vec![];
+        let mut default     = vec![];
+
+        squish_float(&mut target, 0.0, 1.5).unwrap();
+        squish_float(&mut target, 1.5, 700.0).unwrap();
+        squish_float_with_encoding(&mut default, FloatEncoding::default(), 0.0, 1.5).unwrap();
+        squish_float_with_encoding(&mut default, FloatEncoding::default(), 1.5, 700.0).unwrap();
+
+        assert!(target == vec![0x80, 0x01, 0x00, 0x80, 0x00, 0xa0, 0x2e, 0x44]);
+        assert!(default == target);
+    }
+
+    #[test]
+    fn fine_precision_is_more_precise() {
+        let mut coarse  = vec![];
+        let mut fine    = vec![];
+
+        squish_float_with_encoding(&mut coarse, FloatEncoding::PRECISION_256, 0.0, 1.001).unwrap();
+        squish_float_with_encoding(&mut fine, FloatEncoding::PRECISION_4096, 0.0, 1.001).unwrap();
+
+        let coarse  = unsquish_float(&mut &coarse[..], 0.0).unwrap();
+        let fine    = unsquish_float(&mut &fine[..], 0.0).unwrap();
+
+        assert!((fine-1.001).abs() <= 1.0/8192.0);
+        assert!((fine-1.001).abs() < (coarse-1.001).abs());
+    }
+
+    #[test]
+    fn fine_precision_falls_back_for_large_values() {
+        let mut target = vec![];
+
+        squish_float_with_encoding(&mut target, FloatEncoding::PRECISION_4096, 0.0, 100.0).unwrap();
+        let res = unsquish_float(&mut &target[..], 0.0).unwrap();
+
+        assert!(target.len() == 6);
+        assert!((res-100.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn varint_small_values_are_small() {
+        let mut target = vec![];
+
+        squish_float_with_encoding(&mut target, FloatEncoding::VarInt(4), 10.0, 11.0).unwrap();
+        assert!(target.len() == 3);
+
+        let res = unsquish_float(&mut &target[..], 10.0).unwrap();
+        assert!((res-11.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn varint_medium_jumps_keep_precision() {
+        let mut target = vec![];
+
+        squish_float_with_encoding(&mut target, FloatEncoding::VarInt(8), 0.0, 1000.5).unwrap();
+        assert!(target.len() < 6);
+
+        let res = unsquish_float(&mut &target[..], 0.0).unwrap();
+        assert!(res == 1000.5);
+    }
+
+    #[test]
+    fn varint_negative_values() {
+        let mut target = vec![];
+
+        squish_float_with_encoding(&mut target, FloatEncoding::VarInt(12), 1000.0, -24000.25).unwrap();
+        let res = unsquish_float(&mut &target[..], 1000.0).unwrap();
+
+        assert!(res == -24000.25);
+    }
+
+    #[test]
+    fn varint_nan_is_lossless() {
+        let mut target = vec![];
+
+        squish_float_with_encoding(&mut target, FloatEncoding::VarInt(8), 0.0, f64::NAN).unwrap();
+        let res = unsquish_float(&mut &target[..], 0.0).unwrap();
+
+        assert!(res.is_nan());
+    }
+
+    #[test]
+    fn lossless_is_exact() {
+        let mut target  = vec![];
+        let values      = [0.1, 1.0/3.0, -123456.789, 1e300, f64::MIN_POSITIVE];
+
+        let mut last = 0.0;
+        for value in values.iter() {
+            squish_float_with_encoding(&mut target, FloatEncoding::Lossless, last, *value).unwrap();
+            last = *value;
+        }
+
+        let mut src: &[u8]  = &target;
+        let mut last        = 0.0;
+        for value in values.iter() {
+            let res = unsquish_float(&mut src, last).unwrap();
+            assert!(res == *value);
+            last = res;
+        }
+    }
+
+    #[test]
+    fn can_decode_mixed_encodings() {
+        let mut target  = vec![];
+
+        squish_float_with_encoding(&mut target, FloatEncoding::PRECISION_256, 0.0, 1.5).unwrap();
+        squish_float_with_encoding(&mut target, FloatEncoding::PRECISION_4096, 1.5, 1.25).unwrap();
+        squish_float_with_encoding(&mut target, FloatEncoding::VarInt(10), 1.25, 500.0).unwrap();
+        squish_float_with_encoding(&mut target, FloatEncoding::Lossless, 500.0, 0.1).unwrap();
+        squish_float(&mut target, 0.1, 4000.0).unwrap();
+
+        let mut src: &[u8]  = &target;
+        let res1            = unsquish_float(&mut src, 0.0).unwrap();
+        let res2            = unsquish_float(&mut src, res1).unwrap();
+        let res3            = unsquish_float(&mut src, res2).unwrap();
+        let res4            = unsquish_float(&mut src, res3).unwrap();
+        let res5            = unsquish_float(&mut src, res4).unwrap();
+
+        assert!(res1 == 1.5);
+        assert!(res2 == 1.25);
+        assert!(res3 == 500.0);
+        assert!(res4 == 0.1);
+        assert!((res5-4000.0).abs() < 0.01);
+        assert!(src.is_empty());
+    }
 }
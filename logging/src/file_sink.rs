@@ -0,0 +1,147 @@
+use super::filter::*;
+use super::log_msg::*;
+
+use desync::{Desync, pipe_in};
+use futures::*;
+
+use std::io;
+use std::io::{Write};
+use std::fs;
+use std::fs::{File, OpenOptions};
+use std::sync::*;
+use std::ffi::{OsString};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime};
+
+///
+/// A log file that is optionally rotated when it gets too large
+///
+struct LogFile {
+    /// The path to the current log file
+    path: PathBuf,
+
+    /// The file that's being written to
+    file: File,
+
+    /// The number of bytes in the current log file
+    size: u64,
+
+    /// The size the file can reach before it's rotated (None to never rotate)
+    max_size: Option<u64>,
+
+    /// The number of old log files to keep when rotating
+    max_old_files: usize
+}
+
+impl LogFile {
+    ///
+    /// Opens a log file, appending to it if it already exists
+    ///
+    fn open(path: &Path, max_size: Option<u64>, max_old_files: usize) -> io::Result<LogFile> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        let size = file.metadata()?.len();
+
+        Ok(LogFile {
+            path:           path.to_path_buf(),
+            file,
+            size,
+            max_size,
+            max_old_files
+        })
+    }
+
+    ///
+    /// Returns the path of an old log file (the current log file with '.<idx>' appended)
+    ///
+    fn old_path(&self, idx: usize) -> PathBuf {
+        let mut path = OsString::from(self.path.as_os_str());
+        path.push(format!(".{}", idx));
+
+        PathBuf::from(path)
+    }
+
+    ///
+    /// Moves the current log file to 'log.1', 'log.1' to 'log.2' etc, discarding the oldest file, and starts a new log file
+    ///
+    fn rotate(&mut self) -> io::Result<()> {
+        if self.max_old_files > 0 {
+            // Remove the oldest file (it's fine if it doesn't exist)
+            fs::remove_file(self.old_path(self.max_old_files)).ok();
+
+            // Move the other files along
+            for idx in (1..self.max_old_files).rev() {
+                let from = self.old_path(idx);
+                if from.exists() {
+                    fs::rename(from, self.old_path(idx+1))?;
+                }
+            }
+
+            fs::rename(&self.path, self.old_path(1))?;
+        }
+
+        // Start a new log file
+        self.file = OpenOptions::new().create(true).write(true).truncate(true).open(&self.path)?;
+        self.size = 0;
+
+        Ok(())
+    }
+
+    ///
+    /// Writes a line to the log file, rotating it first if it's going to be too large
+    ///
+    fn write_line(&mut self, line: &str) -> io::Result<()> {
+        let len = (line.len() + 1) as u64;
+
+        if let Some(max_size) = self.max_size {
+            if self.size > 0 && self.size + len > max_size {
+                self.rotate()?;
+            }
+        }
+
+        self.file.write_all(line.as_bytes())?;
+        self.file.write_all(b"\n")?;
+        self.file.flush()?;
+        self.size += len;
+
+        Ok(())
+    }
+}
+
+///
+/// Writes the log messages from a stream to a log file
+///
+fn send_to_log_file<LogStream: 'static+Unpin+Send+Stream<Item=LogMsg>>(stream: LogStream, log_file: LogFile, filter: LogFilter) {
+    let log_file = Arc::new(Desync::new(log_file));
+
+    pipe_in(log_file, filter.filter_stream(stream), |log_file, msg| {
+        // Errors are ignored as there's nowhere to report them
+        log_file.write_line(&msg.to_json(Some(SystemTime::now()))).ok();
+
+        Box::pin(future::ready(()))
+    });
+}
+
+///
+/// Writes the log messages from a stream to a file as JSON (with one message per line)
+///
+/// Messages are appended to the file if it already exists. Only the messages accepted by the filter are written.
+///
+pub fn send_to_json_file<LogStream: 'static+Unpin+Send+Stream<Item=LogMsg>, P: AsRef<Path>>(stream: LogStream, path: P, filter: LogFilter) -> io::Result<()> {
+    let log_file = LogFile::open(path.as_ref(), None, 0)?;
+    send_to_log_file(stream, log_file, filter);
+
+    Ok(())
+}
+
+///
+/// Writes the log messages from a stream to a file as JSON, rotating the file when it grows past a certain size
+///
+/// When the file would grow beyond `max_size` bytes, it's renamed to `<path>.1` (with any existing `<path>.1` being renamed to
+/// `<path>.2` and so on) and a new file is started. At most `max_old_files` old log files are kept.
+///
+pub fn send_to_rotating_json_file<LogStream: 'static+Unpin+Send+Stream<Item=LogMsg>, P: AsRef<Path>>(stream: LogStream, path: P, max_size: u64, max_old_files: usize, filter: LogFilter) -> io::Result<()> {
+    let log_file = LogFile::open(path.as_ref(), Some(max_size), max_old_files)?;
+    send_to_log_file(stream, log_file, filter);
+
+    Ok(())
+}
@@ -0,0 +1,111 @@
+use super::log_msg::*;
+use super::message::*;
+use super::privilege::*;
+
+use log;
+use futures::*;
+
+///
+/// Describes which log messages should be sent to a sink
+///
+/// Messages can be filtered by level, by target (the `target` field of the message) and by privilege. The default filter
+/// accepts every message.
+///
+#[derive(Clone, PartialEq, Debug)]
+pub struct LogFilter {
+    /// The least serious level to accept
+    max_level: log::Level,
+
+    /// The targets to accept (or empty to accept every target)
+    targets: Vec<String>,
+
+    /// The privilege levels to accept
+    privileges: Vec<LogPrivilege>
+}
+
+impl Default for LogFilter {
+    fn default() -> LogFilter {
+        LogFilter::new()
+    }
+}
+
+impl LogFilter {
+    ///
+    /// Creates a filter that accepts every message
+    ///
+    pub fn new() -> LogFilter {
+        LogFilter {
+            max_level:  log::Level::Trace,
+            targets:    vec![],
+            privileges: vec![LogPrivilege::All, LogPrivilege::User, LogPrivilege::Application]
+        }
+    }
+
+    ///
+    /// Only accepts messages that are at least as serious as the specified level (eg, `Level::Warn` accepts warnings and errors)
+    ///
+    pub fn with_max_level(mut self, level: log::Level) -> LogFilter {
+        self.max_level = level;
+        self
+    }
+
+    ///
+    /// Accepts messages from the specified target or any of its sub-targets (so `flo_animation` also accepts messages
+    /// from `flo_animation::editor`)
+    ///
+    /// Once a target is specified, messages from targets that were not specified are rejected.
+    ///
+    pub fn with_target(mut self, target: &str) -> LogFilter {
+        self.targets.push(target.to_string());
+        self
+    }
+
+    ///
+    /// Rejects messages with the specified privilege level
+    ///
+    /// For instance, `without_privilege(LogPrivilege::User)` removes messages that might contain the user's data, which
+    /// makes the log suitable for attaching to a crash report.
+    ///
+    pub fn without_privilege(mut self, privilege: LogPrivilege) -> LogFilter {
+        self.privileges.retain(|existing| existing != &privilege);
+        self
+    }
+
+    ///
+    /// Returns true if a message is accepted by this filter
+    ///
+    pub fn matches(&self, msg: &LogMsg) -> bool {
+        // Level must be at least as serious as the maximum level (in the log crate, more serious levels are 'smaller')
+        if msg.level() > self.max_level {
+            return false;
+        }
+
+        // Privilege must be one of the accepted privileges
+        if !self.privileges.contains(&msg.privilege()) {
+            return false;
+        }
+
+        // Target must match one of the targets, if any are specified
+        if !self.targets.is_empty() {
+            let msg_target = msg.field_value("target").unwrap_or("");
+
+            let target_matches = self.targets.iter()
+                .any(|target| msg_target == target || (msg_target.starts_with(&**target) && msg_target[target.len()..].starts_with("::")));
+
+            if !target_matches {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    ///
+    /// Filters a stream of log messages so that only the messages accepted by this filter are returned
+    ///
+    pub fn filter_stream<LogStream: 'static+Unpin+Send+Stream<Item=LogMsg>>(&self, stream: LogStream) -> impl 'static+Unpin+Send+Stream<Item=LogMsg> {
+        let filter = self.clone();
+
+        stream.filter(move |msg| future::ready(filter.matches(msg)))
+    }
+}
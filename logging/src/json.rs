@@ -0,0 +1,95 @@
+use super::log_msg::*;
+use super::message::*;
+use super::privilege::*;
+
+use log;
+
+use std::fmt::Write;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+///
+/// Appends a string to a JSON string as a quoted, escaped string value
+///
+fn write_json_string(target: &mut String, value: &str) {
+    target.push('"');
+
+    for chr in value.chars() {
+        match chr {
+            '"'                 => target.push_str("\\\""),
+            '\\'                => target.push_str("\\\\"),
+            '\n'                => target.push_str("\\n"),
+            '\r'                => target.push_str("\\r"),
+            '\t'                => target.push_str("\\t"),
+            chr if chr < ' '    => { write!(target, "\\u{:04x}", chr as u32).ok(); }
+            chr                 => target.push(chr)
+        }
+    }
+
+    target.push('"');
+}
+
+///
+/// Returns the name used for a level in the JSON representation of a log message
+///
+fn level_name(level: log::Level) -> &'static str {
+    match level {
+        log::Level::Trace   => "trace",
+        log::Level::Debug   => "debug",
+        log::Level::Info    => "info",
+        log::Level::Warn    => "warn",
+        log::Level::Error   => "error"
+    }
+}
+
+///
+/// Returns the name used for a privilege level in the JSON representation of a log message
+///
+fn privilege_name(privilege: LogPrivilege) -> &'static str {
+    match privilege {
+        LogPrivilege::All           => "all",
+        LogPrivilege::User          => "user",
+        LogPrivilege::Application   => "application"
+    }
+}
+
+impl LogMsg {
+    ///
+    /// Formats this message as a single line of JSON
+    ///
+    /// The result is an object with `level`, `privilege` and `message` values, and a `fields` object containing all of the
+    /// fields of the message. If a time is supplied, it's added as a `time` value, in milliseconds since the UNIX epoch.
+    ///
+    pub fn to_json(&self, when: Option<SystemTime>) -> String {
+        let mut json = String::new();
+
+        json.push('{');
+
+        if let Some(when) = when {
+            let millis = when.duration_since(UNIX_EPOCH).map(|since_epoch| since_epoch.as_millis()).unwrap_or(0);
+            write!(json, "\"time\":{},", millis).ok();
+        }
+
+        json.push_str("\"level\":");
+        write_json_string(&mut json, level_name(self.level()));
+        json.push_str(",\"privilege\":");
+        write_json_string(&mut json, privilege_name(self.privilege()));
+        json.push_str(",\"message\":");
+        write_json_string(&mut json, self.message());
+
+        // Fields are sorted so the output is consistent
+        let mut fields = self.fields();
+        fields.sort();
+
+        json.push_str(",\"fields\":{");
+        for (idx, (field_name, field_value)) in fields.into_iter().enumerate() {
+            if idx > 0 { json.push(','); }
+
+            write_json_string(&mut json, &field_name);
+            json.push(':');
+            write_json_string(&mut json, &field_value);
+        }
+        json.push_str("}}");
+
+        json
+    }
+}
@@ -13,6 +13,12 @@ mod publisher;
 mod static_log;
 mod log_stream;
 mod log_subscriber;
+mod filter;
+mod json;
+mod file_sink;
+mod ring_buffer;
+mod metrics;
+mod span;
 
 pub use log::Level;
 pub use self::privilege::*;
@@ -21,3 +27,8 @@ pub use self::log_msg::*;
 pub use self::publisher::*;
 pub use self::log_stream::*;
 pub use self::static_log::*;
+pub use self::filter::*;
+pub use self::file_sink::*;
+pub use self::ring_buffer::*;
+pub use self::metrics::*;
+pub use self::span::*;
@@ -0,0 +1,286 @@
+use super::publisher::*;
+
+use futures::*;
+use futures::channel::mpsc;
+
+use std::fmt;
+use std::thread;
+use std::sync::*;
+use std::time::{Duration};
+use std::collections::HashMap;
+
+///
+/// The upper bounds of the buckets used by histograms (values above the last bound go in an extra overflow bucket)
+///
+/// These are chosen so that timings in milliseconds are reasonably well described.
+///
+const HISTOGRAM_BOUNDS: [f64; 14] = [0.01, 0.1, 0.5, 1.0, 2.0, 5.0, 10.0, 20.0, 50.0, 100.0, 200.0, 500.0, 1000.0, 5000.0];
+
+lazy_static! {
+    ///
+    /// The metrics that have been recorded so far, indexed by target and name
+    ///
+    static ref METRICS: Mutex<HashMap<(String, String), MetricValue>> = Mutex::new(HashMap::new());
+}
+
+///
+/// Aggregated set of values recorded for a metric
+///
+#[derive(Clone, PartialEq, Debug)]
+pub struct Histogram {
+    /// The number of values that have been recorded
+    count: u64,
+
+    /// The total of all the values that have been recorded
+    sum: f64,
+
+    /// The smallest value that was recorded
+    min: f64,
+
+    /// The largest value that was recorded
+    max: f64,
+
+    /// The number of values in each bucket (one for each of the HISTOGRAM_BOUNDS plus one for values larger than the last bound)
+    buckets: Vec<u64>
+}
+
+///
+/// The aggregated value of a metric
+///
+#[derive(Clone, PartialEq, Debug)]
+pub enum MetricValue {
+    /// A count of how many times something has happened
+    Counter(u64),
+
+    /// The distribution of the values recorded for this metric
+    Histogram(Histogram)
+}
+
+///
+/// Summary of a metric recorded for a log target
+///
+#[derive(Clone, PartialEq, Debug)]
+pub struct MetricSummary {
+    /// The target of the log the metric was recorded for
+    pub target: String,
+
+    /// The name of the metric
+    pub name: String,
+
+    /// The aggregated value of the metric
+    pub value: MetricValue
+}
+
+impl Histogram {
+    ///
+    /// Creates a new histogram with no values
+    ///
+    pub fn new() -> Histogram {
+        Histogram {
+            count:      0,
+            sum:        0.0,
+            min:        0.0,
+            max:        0.0,
+            buckets:    vec![0; HISTOGRAM_BOUNDS.len()+1]
+        }
+    }
+
+    ///
+    /// Adds a value to this histogram
+    ///
+    pub fn add(&mut self, value: f64) {
+        if self.count == 0 {
+            self.min = value;
+            self.max = value;
+        } else {
+            self.min = self.min.min(value);
+            self.max = self.max.max(value);
+        }
+
+        self.count  += 1;
+        self.sum    += value;
+
+        let bucket = HISTOGRAM_BOUNDS.iter()
+            .position(|bound| value <= *bound)
+            .unwrap_or(HISTOGRAM_BOUNDS.len());
+        self.buckets[bucket] += 1;
+    }
+
+    ///
+    /// The number of values that have been added to this histogram
+    ///
+    pub fn count(&self) -> u64 { self.count }
+
+    ///
+    /// The total of the values that have been added to this histogram
+    ///
+    pub fn sum(&self) -> f64 { self.sum }
+
+    ///
+    /// The smallest value added to this histogram
+    ///
+    pub fn min(&self) -> f64 { self.min }
+
+    ///
+    /// The largest value added to this histogram
+    ///
+    pub fn max(&self) -> f64 { self.max }
+
+    ///
+    /// The mean of the values added to this histogram
+    ///
+    pub fn mean(&self) -> f64 {
+        if self.count == 0 {
+            0.0
+        } else {
+            self.sum / (self.count as f64)
+        }
+    }
+
+    ///
+    /// Estimates a percentile (0.0-1.0) of the values added to this histogram
+    ///
+    /// This is the upper bound of the bucket containing the percentile, limited to the range of values actually recorded.
+    ///
+    pub fn percentile(&self, percentile: f64) -> f64 {
+        if self.count == 0 { return 0.0; }
+
+        let rank        = ((self.count as f64) * percentile.clamp(0.0, 1.0)).ceil().max(1.0) as u64;
+        let mut seen    = 0;
+
+        for (bucket, bucket_count) in self.buckets.iter().enumerate() {
+            seen += bucket_count;
+
+            if seen >= rank {
+                let upper_bound = HISTOGRAM_BOUNDS.get(bucket).cloned().unwrap_or(self.max);
+                return upper_bound.max(self.min).min(self.max);
+            }
+        }
+
+        self.max
+    }
+
+    ///
+    /// Returns the bucket counts for this histogram, along with the upper bound of each bucket (None for the overflow bucket)
+    ///
+    pub fn buckets(&self) -> Vec<(Option<f64>, u64)> {
+        self.buckets.iter()
+            .enumerate()
+            .map(|(bucket, count)| (HISTOGRAM_BOUNDS.get(bucket).cloned(), *count))
+            .collect()
+    }
+}
+
+impl Default for Histogram {
+    fn default() -> Histogram {
+        Histogram::new()
+    }
+}
+
+impl fmt::Display for MetricSummary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.value {
+            MetricValue::Counter(count)         => write!(f, "{} {}: {}", self.target, self.name, count),
+            MetricValue::Histogram(histogram)   => write!(f, "{} {}: n={} mean={:.3} p50={:.3} p90={:.3} p99={:.3} max={:.3}",
+                self.target, self.name, histogram.count(), histogram.mean(), histogram.percentile(0.5), histogram.percentile(0.9), histogram.percentile(0.99), histogram.max())
+        }
+    }
+}
+
+///
+/// Locks the metrics (ignoring poisoning, as a panic while updating a metric can't leave it in an invalid state)
+///
+fn lock_metrics() -> MutexGuard<'static, HashMap<(String, String), MetricValue>> {
+    METRICS.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+///
+/// Adds to the counter with the specified target and name
+///
+pub fn count_metric(target: &str, name: &str, amount: u64) {
+    let mut metrics = lock_metrics();
+    let metric      = metrics.entry((target.to_string(), name.to_string())).or_insert(MetricValue::Counter(0));
+
+    match metric {
+        MetricValue::Counter(count) => { *count += amount; }
+        MetricValue::Histogram(_)   => { *metric = MetricValue::Counter(amount); }
+    }
+}
+
+///
+/// Adds a value to the histogram with the specified target and name
+///
+pub fn record_metric(target: &str, name: &str, value: f64) {
+    let mut metrics = lock_metrics();
+    let metric      = metrics.entry((target.to_string(), name.to_string())).or_insert_with(|| MetricValue::Histogram(Histogram::new()));
+
+    match metric {
+        MetricValue::Histogram(histogram)   => { histogram.add(value); }
+        MetricValue::Counter(_)             => {
+            let mut histogram = Histogram::new();
+            histogram.add(value);
+            *metric = MetricValue::Histogram(histogram);
+        }
+    }
+}
+
+///
+/// Retrieves a summary of all of the metrics recorded so far, ordered by target and name
+///
+pub fn metrics_summary() -> Vec<MetricSummary> {
+    let mut summary = lock_metrics().iter()
+        .map(|((target, name), value)| MetricSummary { target: target.clone(), name: name.clone(), value: value.clone() })
+        .collect::<Vec<_>>();
+
+    summary.sort_by(|a, b| (&a.target, &a.name).cmp(&(&b.target, &b.name)));
+    summary
+}
+
+///
+/// Discards all of the metrics recorded so far
+///
+pub fn reset_metrics() {
+    lock_metrics().clear();
+}
+
+///
+/// Returns a stream that publishes a summary of the metrics recorded so far at the specified interval
+///
+/// If the stream isn't read fast enough, summaries are skipped rather than queued up. The summaries stop being generated
+/// when the stream is dropped.
+///
+pub fn subscribe_to_metrics(interval: Duration) -> impl 'static+Unpin+Send+Stream<Item=Vec<MetricSummary>> {
+    let (mut sender, receiver) = mpsc::channel(1);
+
+    thread::Builder::new()
+        .name("flo_logging metrics".to_string())
+        .spawn(move || {
+            loop {
+                thread::sleep(interval);
+
+                if let Err(err) = sender.try_send(metrics_summary()) {
+                    // Stop once the stream has been dropped
+                    if err.is_disconnected() { break; }
+                }
+            }
+        })
+        .ok();
+
+    receiver
+}
+
+impl LogPublisher {
+    ///
+    /// Adds to a counter for the target of this log
+    ///
+    pub fn count(&self, name: &str, amount: u64) {
+        count_metric(&self.target(), name, amount);
+    }
+
+    ///
+    /// Records a value in a histogram for the target of this log
+    ///
+    pub fn record(&self, name: &str, value: f64) {
+        record_metric(&self.target(), name, value);
+    }
+}
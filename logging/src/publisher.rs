@@ -72,6 +72,18 @@ impl LogPublisher {
         logger
     }
 
+    ///
+    /// Returns the target of the messages sent to this log
+    ///
+    pub fn target(&self) -> String {
+        self.context.sync(|context| {
+            context.fields.iter()
+                .find(|(field_name, _)| field_name == "target")
+                .map(|(_, target)| target.clone())
+                .unwrap_or_default()
+        })
+    }
+
     ///
     /// Sends a log message to the context
     ///
@@ -0,0 +1,125 @@
+use super::filter::*;
+use super::log_msg::*;
+
+use desync::{Desync, pipe_in};
+use futures::*;
+
+use std::io;
+use std::io::{Write};
+use std::fs::{File};
+use std::panic;
+use std::sync::*;
+use std::path::{PathBuf};
+use std::time::{SystemTime};
+use std::collections::{VecDeque};
+
+///
+/// Stores the most recent log messages in memory
+///
+/// This is useful for attaching the messages that led up to a problem to a crash report. Cloning a ring buffer creates
+/// a new reference to the same set of messages.
+///
+#[derive(Clone)]
+pub struct LogRingBuffer {
+    /// The messages in this buffer, along with the time they were received, oldest first
+    messages: Arc<Mutex<VecDeque<(SystemTime, LogMsg)>>>,
+
+    /// The maximum number of messages to store
+    capacity: usize
+}
+
+impl LogRingBuffer {
+    ///
+    /// Creates a new ring buffer that will keep the specified number of messages
+    ///
+    pub fn new(capacity: usize) -> LogRingBuffer {
+        LogRingBuffer {
+            messages:   Arc::new(Mutex::new(VecDeque::with_capacity(capacity))),
+            capacity
+        }
+    }
+
+    ///
+    /// Locks the messages in this buffer (ignoring poisoning, as messages are still useful after a panic)
+    ///
+    fn lock(&self) -> MutexGuard<'_, VecDeque<(SystemTime, LogMsg)>> {
+        self.messages.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    ///
+    /// Adds a message to this buffer, discarding the oldest message if it's full
+    ///
+    pub fn push(&self, msg: LogMsg) {
+        if self.capacity == 0 { return; }
+
+        let mut messages = self.lock();
+
+        while messages.len() >= self.capacity {
+            messages.pop_front();
+        }
+        messages.push_back((SystemTime::now(), msg));
+    }
+
+    ///
+    /// Retrieves the messages currently in this buffer, oldest first
+    ///
+    pub fn messages(&self) -> Vec<LogMsg> {
+        self.lock().iter().map(|(_, msg)| msg.clone()).collect()
+    }
+
+    ///
+    /// Removes all of the messages from this buffer
+    ///
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    ///
+    /// Writes the messages in this buffer as JSON (one message per line), oldest first
+    ///
+    pub fn dump<Target: Write>(&self, target: &mut Target) -> io::Result<()> {
+        let messages = self.lock().iter().cloned().collect::<Vec<_>>();
+
+        for (when, msg) in messages {
+            target.write_all(msg.to_json(Some(when)).as_bytes())?;
+            target.write_all(b"\n")?;
+        }
+
+        target.flush()
+    }
+
+    ///
+    /// Writes the messages in this buffer to a file whenever a panic occurs
+    ///
+    /// This replaces the current panic hook with one that dumps the messages and then calls the original hook.
+    ///
+    pub fn dump_on_panic<P: Into<PathBuf>>(&self, path: P) {
+        let ring_buffer     = self.clone();
+        let path            = path.into();
+        let previous_hook   = panic::take_hook();
+
+        panic::set_hook(Box::new(move |panic_info| {
+            // Errors are ignored as we're already in the middle of a panic
+            if let Ok(mut file) = File::create(&path) {
+                ring_buffer.dump(&mut file).ok();
+            }
+
+            previous_hook(panic_info);
+        }));
+    }
+}
+
+///
+/// Stores the log messages from a stream in a ring buffer
+///
+/// Only the messages accepted by the filter are stored.
+///
+pub fn send_to_ring_buffer<LogStream: 'static+Unpin+Send+Stream<Item=LogMsg>>(stream: LogStream, ring_buffer: &LogRingBuffer, filter: LogFilter) {
+    let ring_buffer = Arc::new(Desync::new(ring_buffer.clone()));
+
+    pipe_in(ring_buffer, filter.filter_stream(stream), |ring_buffer, msg| {
+        ring_buffer.push(msg);
+
+        Box::pin(future::ready(()))
+    });
+}
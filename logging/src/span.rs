@@ -0,0 +1,119 @@
+use super::metrics::*;
+use super::publisher::*;
+
+use log;
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+use std::collections::HashMap;
+
+///
+/// The least serious level of span that generates a log message (0 to generate no messages)
+///
+static SPAN_MESSAGE_LEVEL: AtomicUsize = AtomicUsize::new(log::Level::Debug as usize);
+
+///
+/// Sets the least serious level of span that generates a log message when it finishes
+///
+/// By default, spans at the `Trace` level only update the metrics: this is useful for timing code that runs very frequently.
+/// Pass `None` to stop all spans from generating messages.
+///
+pub fn set_span_message_level(level: Option<log::Level>) {
+    SPAN_MESSAGE_LEVEL.store(level.map(|level| level as usize).unwrap_or(0), Ordering::Relaxed);
+}
+
+///
+/// A span measures how long it takes to perform an operation
+///
+/// Spans are scoped: the time is measured from when the span is created until it's dropped. When a span finishes, its
+/// duration is added to a histogram named after the span (in milliseconds, for the target of the log it was created from),
+/// and a log message with `span`, `duration_ms` and `duration_us` fields is sent to the log.
+///
+pub struct LogSpan {
+    /// The log that this span will report to
+    log: LogPublisher,
+
+    /// The target of the log, which names the metric this span updates
+    target: String,
+
+    /// The name of this span
+    name: String,
+
+    /// The level of the message generated when this span finishes
+    level: log::Level,
+
+    /// Extra fields to add to the message generated when this span finishes
+    fields: Vec<(String, String)>,
+
+    /// When this span started
+    start: Instant
+}
+
+impl LogSpan {
+    ///
+    /// Changes the level of the message generated when this span finishes (spans are at the `Debug` level by default)
+    ///
+    pub fn with_level(mut self, level: log::Level) -> LogSpan {
+        self.level = level;
+        self
+    }
+
+    ///
+    /// Adds a field to the message generated when this span finishes
+    ///
+    pub fn with_field<Value: ToString>(mut self, field_name: &str, field_value: Value) -> LogSpan {
+        self.fields.push((field_name.to_string(), field_value.to_string()));
+        self
+    }
+
+    ///
+    /// Returns the name of this span
+    ///
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    ///
+    /// Returns the time that has passed since this span started
+    ///
+    pub fn elapsed(&self) -> Duration {
+        self.start.elapsed()
+    }
+}
+
+impl Drop for LogSpan {
+    fn drop(&mut self) {
+        let duration    = self.start.elapsed();
+        let millis      = duration.as_secs_f64() * 1000.0;
+
+        // Spans always update the metrics
+        record_metric(&self.target, &self.name, millis);
+
+        // Only spans at a serious enough level generate messages
+        if (self.level as usize) <= SPAN_MESSAGE_LEVEL.load(Ordering::Relaxed) {
+            let mut fields = self.fields.drain(..).collect::<HashMap<_, _>>();
+            fields.insert("message".to_string(), format!("{}: {:.3}ms", self.name, millis));
+            fields.insert("span".to_string(), self.name.clone());
+            fields.insert("duration_ms".to_string(), format!("{:.3}", millis));
+            fields.insert("duration_us".to_string(), duration.as_micros().to_string());
+
+            self.log.log((self.level, fields));
+        }
+    }
+}
+
+impl LogPublisher {
+    ///
+    /// Starts a span that measures the time taken until it's dropped
+    ///
+    pub fn span(&self, name: &str) -> LogSpan {
+        LogSpan {
+            log:    self.clone(),
+            target: self.target(),
+            name:   name.to_string(),
+            level:  log::Level::Debug,
+            fields: vec![],
+            start:  Instant::now()
+        }
+    }
+}
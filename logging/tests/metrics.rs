@@ -0,0 +1,131 @@
+extern crate flo_logging;
+
+use flo_logging::*;
+
+use futures::prelude::*;
+use futures::executor;
+
+use std::thread;
+use std::time::Duration;
+
+///
+/// Finds the metric with the specified target and name
+///
+fn find_metric(target: &str, name: &str) -> Option<MetricValue> {
+    metrics_summary().into_iter()
+        .find(|summary| summary.target == target && summary.name == name)
+        .map(|summary| summary.value)
+}
+
+#[test]
+fn histogram_statistics() {
+    let mut histogram = Histogram::new();
+
+    for value in 1..=100 {
+        histogram.add(value as f64);
+    }
+
+    assert!(histogram.count() == 100);
+    assert!(histogram.min() == 1.0);
+    assert!(histogram.max() == 100.0);
+    assert!((histogram.mean() - 50.5).abs() < 0.001);
+
+    // Percentiles are estimated from the bucket bounds
+    assert!(histogram.percentile(0.5) == 50.0);
+    assert!(histogram.percentile(0.9) == 100.0);
+    assert!(histogram.percentile(1.0) == 100.0);
+}
+
+#[test]
+fn count_for_target() {
+    let log = LogPublisher::new("test_metrics_count");
+
+    log.count("frames", 1);
+    log.count("frames", 2);
+
+    assert!(find_metric("test_metrics_count", "frames") == Some(MetricValue::Counter(3)));
+    assert!(find_metric("test_metrics_count", "edits") == None);
+}
+
+#[test]
+fn record_for_target() {
+    let log = LogPublisher::new("test_metrics_record");
+
+    log.record("size", 4.0);
+    log.record("size", 8.0);
+
+    match find_metric("test_metrics_record", "size") {
+        Some(MetricValue::Histogram(histogram)) => {
+            assert!(histogram.count() == 2);
+            assert!(histogram.sum() == 12.0);
+        }
+
+        _ => assert!(false)
+    }
+}
+
+#[test]
+fn span_sends_duration() {
+    let log         = LogPublisher::new("test_metrics_span");
+    let mut stream  = Box::pin(log.subscribe());
+
+    {
+        let _span = log.span("sleep").with_field("frame", 42);
+        thread::sleep(Duration::from_millis(5));
+    }
+
+    executor::block_on(async {
+        let msg = stream.next().await.unwrap();
+
+        assert!(msg.level() == Level::Debug);
+        assert!(msg.field_value("span") == Some("sleep"));
+        assert!(msg.field_value("frame") == Some("42"));
+        assert!(msg.field_value("target") == Some("test_metrics_span"));
+        assert!(msg.field_value("duration_ms").unwrap().parse::<f64>().unwrap() >= 5.0);
+        assert!(msg.field_value("duration_us").unwrap().parse::<u64>().unwrap() >= 5000);
+    });
+
+    match find_metric("test_metrics_span", "sleep") {
+        Some(MetricValue::Histogram(histogram)) => {
+            assert!(histogram.count() == 1);
+            assert!(histogram.min() >= 5.0);
+        }
+
+        _ => assert!(false)
+    }
+}
+
+#[test]
+fn trace_span_only_records_metric() {
+    let log         = LogPublisher::new("test_metrics_trace_span");
+    let mut stream  = Box::pin(log.subscribe());
+
+    {
+        let _span = log.span("quiet").with_level(Level::Trace);
+    }
+    log.log("Done");
+
+    executor::block_on(async {
+        let msg = stream.next().await.unwrap();
+        assert!(msg.message() == "Done");
+    });
+
+    match find_metric("test_metrics_trace_span", "quiet") {
+        Some(MetricValue::Histogram(histogram)) => assert!(histogram.count() == 1),
+        _                                       => assert!(false)
+    }
+}
+
+#[test]
+fn metrics_stream() {
+    let log         = LogPublisher::new("test_metrics_stream");
+    let mut stream  = subscribe_to_metrics(Duration::from_millis(10));
+
+    log.count("ticks", 1);
+
+    executor::block_on(async {
+        let summary = stream.next().await.unwrap();
+
+        assert!(summary.iter().any(|metric| metric.target == "test_metrics_stream" && metric.name == "ticks"));
+    });
+}
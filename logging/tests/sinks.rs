@@ -0,0 +1,168 @@
+extern crate flo_logging;
+
+use flo_logging::*;
+
+use std::fs;
+use std::thread;
+use std::path::{PathBuf};
+use std::time::Duration;
+use std::collections::HashMap;
+
+///
+/// Creates an empty directory to write test logs to
+///
+fn test_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("flo_logging_{}_{}", name, std::process::id()));
+
+    fs::remove_dir_all(&dir).ok();
+    fs::create_dir_all(&dir).unwrap();
+
+    dir
+}
+
+///
+/// Creates a log message with a particular target
+///
+fn msg_with_target(level: Level, privilege: LogPrivilege, target: &str, message: &str) -> LogMsg {
+    let fields = vec![("target", target), ("message", message)].into_iter().collect::<HashMap<_, _>>();
+
+    LogMsg::from((privilege, (level, fields)))
+}
+
+#[test]
+fn default_filter_accepts_everything() {
+    let filter = LogFilter::new();
+
+    assert!(filter.matches(&msg_with_target(Level::Trace, LogPrivilege::User, "test", "Hello")));
+    assert!(filter.matches(&msg_with_target(Level::Error, LogPrivilege::Application, "test", "Hello")));
+}
+
+#[test]
+fn filter_by_level() {
+    let filter = LogFilter::new().with_max_level(Level::Warn);
+
+    assert!(filter.matches(&msg_with_target(Level::Error, LogPrivilege::All, "test", "Hello")));
+    assert!(filter.matches(&msg_with_target(Level::Warn, LogPrivilege::All, "test", "Hello")));
+    assert!(!filter.matches(&msg_with_target(Level::Info, LogPrivilege::All, "test", "Hello")));
+    assert!(!filter.matches(&msg_with_target(Level::Trace, LogPrivilege::All, "test", "Hello")));
+}
+
+#[test]
+fn filter_by_target() {
+    let filter = LogFilter::new().with_target("flo_animation");
+
+    assert!(filter.matches(&msg_with_target(Level::Info, LogPrivilege::All, "flo_animation", "Hello")));
+    assert!(filter.matches(&msg_with_target(Level::Info, LogPrivilege::All, "flo_animation::editor", "Hello")));
+    assert!(!filter.matches(&msg_with_target(Level::Info, LogPrivilege::All, "flo_animation_extra", "Hello")));
+    assert!(!filter.matches(&msg_with_target(Level::Info, LogPrivilege::All, "flo_ui", "Hello")));
+}
+
+#[test]
+fn filter_by_privilege() {
+    let filter = LogFilter::new().without_privilege(LogPrivilege::User);
+
+    assert!(filter.matches(&msg_with_target(Level::Info, LogPrivilege::All, "test", "Hello")));
+    assert!(filter.matches(&msg_with_target(Level::Info, LogPrivilege::Application, "test", "Hello")));
+    assert!(!filter.matches(&msg_with_target(Level::Info, LogPrivilege::User, "test", "Hello")));
+}
+
+#[test]
+fn format_as_json() {
+    let msg     = msg_with_target(Level::Warn, LogPrivilege::All, "test", "Say \"hello\"\n");
+    let json    = msg.to_json(None);
+
+    assert!(json == "{\"level\":\"warn\",\"privilege\":\"all\",\"message\":\"Say \\\"hello\\\"\\n\",\"fields\":{\"message\":\"Say \\\"hello\\\"\\n\",\"target\":\"test\"}}");
+}
+
+#[test]
+fn ring_buffer_keeps_last_messages() {
+    let ring_buffer = LogRingBuffer::new(3);
+
+    for idx in 0..5 {
+        ring_buffer.push(LogMsg::from(format!("Message {}", idx)));
+    }
+
+    let messages = ring_buffer.messages();
+    assert!(messages.len() == 3);
+    assert!(messages[0].message() == "Message 2");
+    assert!(messages[2].message() == "Message 4");
+
+    let mut dumped = vec![];
+    ring_buffer.dump(&mut dumped).unwrap();
+    let dumped = String::from_utf8(dumped).unwrap();
+
+    assert!(dumped.lines().count() == 3);
+    assert!(dumped.lines().all(|line| line.starts_with("{\"time\":")));
+}
+
+#[test]
+fn send_filtered_messages_to_ring_buffer() {
+    let log         = LogPublisher::new("test");
+    let ring_buffer = LogRingBuffer::new(10);
+
+    send_to_ring_buffer(log.subscribe(), &ring_buffer, LogFilter::new().without_privilege(LogPrivilege::User));
+
+    log.log("Hello, world");
+    log.log((LogPrivilege::User, "Secret"));
+    log.log("... goodbye, world :-(");
+
+    thread::sleep(Duration::from_millis(20));
+
+    let messages = ring_buffer.messages();
+
+    assert!(messages.len() == 2);
+    assert!(messages[0].message() == "Hello, world");
+    assert!(messages[1].message() == "... goodbye, world :-(");
+}
+
+#[test]
+fn write_json_file() {
+    let dir         = test_dir("json_file");
+    let path        = dir.join("test.log");
+    let log         = LogPublisher::new("test");
+
+    send_to_json_file(log.subscribe(), &path, LogFilter::new()).unwrap();
+
+    log.log("Hello, world");
+    log.log((Level::Error, "... goodbye, world :-("));
+
+    thread::sleep(Duration::from_millis(20));
+
+    let contents    = fs::read_to_string(&path).unwrap();
+    let lines       = contents.lines().collect::<Vec<_>>();
+
+    assert!(lines.len() == 2);
+    assert!(lines[0].contains("\"message\":\"Hello, world\""));
+    assert!(lines[0].contains("\"target\":\"test\""));
+    assert!(lines[1].contains("\"level\":\"error\""));
+
+    fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn rotate_json_file() {
+    let dir         = test_dir("rotate_file");
+    let path        = dir.join("test.log");
+    let log         = LogPublisher::new("test");
+
+    send_to_rotating_json_file(log.subscribe(), &path, 200, 2, LogFilter::new()).unwrap();
+
+    for idx in 0..20 {
+        log.log(format!("Message {}", idx));
+    }
+
+    thread::sleep(Duration::from_millis(50));
+
+    // Should have kept the log file and two old log files
+    assert!(path.exists());
+    assert!(dir.join("test.log.1").exists());
+    assert!(dir.join("test.log.2").exists());
+    assert!(!dir.join("test.log.3").exists());
+
+    // Every file should be below the size limit, and the newest message should be in the current file
+    assert!(fs::metadata(&path).unwrap().len() <= 200);
+    assert!(fs::metadata(dir.join("test.log.1")).unwrap().len() <= 200);
+    assert!(fs::read_to_string(&path).unwrap().contains("Message 19"));
+
+    fs::remove_dir_all(&dir).ok();
+}
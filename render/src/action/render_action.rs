@@ -7,6 +7,7 @@ use super::shader_type::*;
 use crate::buffer::*;
 
 use std::ops::{Range};
+use std::sync::*;
 
 ///
 /// Represents an action for a render target
@@ -78,6 +79,11 @@ pub enum RenderAction {
     ///
     CreateTextureBgra(TextureId, usize, usize),
 
+    ///
+    /// Writes BGRA pixel data to a region of a texture (the region is specified as min and max coordinates)
+    ///
+    WriteTextureData(TextureId, (usize, usize), (usize, usize), Arc<Vec<u8>>),
+
     ///
     /// Frees up an existing texture
     ///
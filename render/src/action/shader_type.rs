@@ -8,4 +8,8 @@ pub enum ShaderType {
     /// Flat colour shader
     /// The erase texture (which should be a MSAA texture) is subtracted from anything drawn, if present
     Simple { erase_texture: Option<TextureId> },
+
+    /// Shader that multiplies the vertex colour by the colour read from a texture (using the texture coordinates of the vertices)
+    /// As for the simple shader, the erase texture is subtracted from anything drawn if present
+    Texture { texture: TextureId, erase_texture: Option<TextureId> },
 }
@@ -11,6 +11,7 @@ use crate::action::*;
 use crate::buffer::*;
 
 use std::ptr;
+use std::sync::*;
 use std::ops::{Range};
 
 ///
@@ -42,7 +43,13 @@ pub struct GlRenderer {
     simple_shader: ShaderProgram<ShaderUniform>,
 
     /// The shader program that applies an erase buffer
-    simple_shader_with_erase: ShaderProgram<ShaderUniform>
+    simple_shader_with_erase: ShaderProgram<ShaderUniform>,
+
+    /// The shader program that reads colours from a texture
+    texture_shader: ShaderProgram<ShaderUniform>,
+
+    /// The shader program that reads colours from a texture and applies an erase buffer
+    texture_shader_with_erase: ShaderProgram<ShaderUniform>
 }
 
 impl GlRenderer {
@@ -58,6 +65,14 @@ impl GlRenderer {
         let simple_erase_fragment_shader    = Shader::compile(&(String::from("#version 330 core\n#define ERASE_MASK\n") + &String::from_utf8(include_bytes!["../../shaders/simple/simple.glslf"].to_vec()).unwrap()), GlShaderType::Fragment, vec![]);
         let simple_shader_with_erase        = ShaderProgram::from_shaders(vec![simple_vertex_shader, simple_erase_fragment_shader]);
 
+        let simple_vertex_shader            = Shader::compile(&String::from_utf8(include_bytes!["../../shaders/simple/simple.glslv"].to_vec()).unwrap(), GlShaderType::Vertex, vec!["a_Pos", "a_Color", "a_TexCoord"]);
+        let texture_fragment_shader         = Shader::compile(&(String::from("#version 330 core\n#define TEXTURE\n") + &String::from_utf8(include_bytes!["../../shaders/simple/simple.glslf"].to_vec()).unwrap()), GlShaderType::Fragment, vec![]);
+        let texture_shader                  = ShaderProgram::from_shaders(vec![simple_vertex_shader, texture_fragment_shader]);
+
+        let simple_vertex_shader            = Shader::compile(&String::from_utf8(include_bytes!["../../shaders/simple/simple.glslv"].to_vec()).unwrap(), GlShaderType::Vertex, vec!["a_Pos", "a_Color", "a_TexCoord"]);
+        let texture_erase_fragment_shader   = Shader::compile(&(String::from("#version 330 core\n#define TEXTURE\n#define ERASE_MASK\n") + &String::from_utf8(include_bytes!["../../shaders/simple/simple.glslf"].to_vec()).unwrap()), GlShaderType::Fragment, vec![]);
+        let texture_shader_with_erase       = ShaderProgram::from_shaders(vec![simple_vertex_shader, texture_erase_fragment_shader]);
+
         GlRenderer {
             buffers:                    vec![],
             index_buffers:              vec![],
@@ -67,7 +82,9 @@ impl GlRenderer {
             transform_matrix:           None,
             render_targets:             vec![],
             simple_shader:              simple_shader,
-            simple_shader_with_erase:   simple_shader_with_erase
+            simple_shader_with_erase:   simple_shader_with_erase,
+            texture_shader:             texture_shader,
+            texture_shader_with_erase:  texture_shader_with_erase
         }
     }
 
@@ -117,6 +134,7 @@ impl GlRenderer {
                 DrawFrameBuffer(render_id, x, y)                                        => { self.draw_frame_buffer(render_id, x, y); }
                 ShowFrameBuffer                                                         => { /* This doesn't double-buffer so nothing to do */ }
                 CreateTextureBgra(texture_id, width, height)                            => { self.create_bgra_texture(texture_id, width, height); }
+                WriteTextureData(texture_id, min, max, data)                            => { self.write_texture_data(texture_id, min, max, data); }
                 FreeTexture(texture_id)                                                 => { self.free_texture(texture_id); }
                 Clear(color)                                                            => { self.clear(color); }
                 UseShader(shader_type)                                                  => { self.use_shader(shader_type); }
@@ -289,6 +307,19 @@ impl GlRenderer {
         self.textures[texture_id] = Some(new_texture);
     }
 
+    ///
+    /// Writes BGRA data to a region of a texture
+    ///
+    fn write_texture_data(&mut self, TextureId(texture_id): TextureId, (x1, y1): (usize, usize), (x2, y2): (usize, usize), data: Arc<Vec<u8>>) {
+        if x2 <= x1 || y2 <= y1 {
+            return;
+        }
+
+        if let Some(Some(texture)) = self.textures.get_mut(texture_id) {
+            texture.set_data_bgra(x1, y1, x2-x1, y2-y1, &*data);
+        }
+    }
+
     ///
     /// Releases an existing render target
     ///
@@ -398,6 +429,37 @@ impl GlRenderer {
                     }
 
                 }
+
+                Texture { texture: TextureId(texture_id), erase_texture } => {
+                    let shader = if erase_texture.is_some() { &mut self.texture_shader_with_erase } else { &mut self.texture_shader };
+                    gl::UseProgram(**shader);
+
+                    if let Some(TextureId(erase_texture_id)) = erase_texture {
+                        if let Some(erase_texture) = &self.textures[erase_texture_id] {
+                            // Set the erase texture
+                            gl::ActiveTexture(gl::TEXTURE0);
+                            gl::BindTexture(gl::TEXTURE_2D_MULTISAMPLE, **erase_texture);
+
+                            shader.uniform_location(ShaderUniform::EraseTexture, "t_EraseMask")
+                                .map(|erase_mask| {
+                                    gl::Uniform1i(erase_mask, 0);
+                                });
+                        }
+                    }
+
+                    if let Some(Some(texture)) = self.textures.get(texture_id) {
+                        // Set the texture to read colours from
+                        gl::ActiveTexture(gl::TEXTURE1);
+                        gl::BindTexture(gl::TEXTURE_2D, **texture);
+
+                        shader.uniform_location(ShaderUniform::Texture, "t_Texture")
+                            .map(|texture_uniform| {
+                                gl::Uniform1i(texture_uniform, 1);
+                            });
+
+                        gl::ActiveTexture(gl::TEXTURE0);
+                    }
+                }
             }
 
             // Set the transform for the newly selected shader
@@ -460,6 +522,8 @@ impl GlRenderer {
             let shader = match &self.active_shader {
                 Some(Simple { erase_texture: None })        => Some(&mut self.simple_shader),
                 Some(Simple { erase_texture: Some(_) })     => Some(&mut self.simple_shader_with_erase),
+                Some(Texture { erase_texture: None, .. })   => Some(&mut self.texture_shader),
+                Some(Texture { erase_texture: Some(_), .. }) => Some(&mut self.texture_shader_with_erase),
 
                 None                                        => None
             };
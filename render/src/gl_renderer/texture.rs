@@ -44,6 +44,8 @@ impl Texture {
 
             gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_MIN_FILTER, gl::LINEAR as i32);
             gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_MAG_FILTER, gl::LINEAR as i32);
+            gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_WRAP_S, gl::CLAMP_TO_EDGE as i32);
+            gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_WRAP_T, gl::CLAMP_TO_EDGE as i32);
 
             gl::TexImage2D(gl::TEXTURE_2D, 0, gl::RGBA as i32, width as gl::types::GLsizei, height as gl::types::GLsizei, 0, gl::RGBA, gl::UNSIGNED_BYTE, ptr::null());
 
@@ -51,6 +53,25 @@ impl Texture {
         }
     }
 
+    ///
+    /// Writes BGRA pixel data to a region of this texture (which should have been created via create_empty)
+    ///
+    pub fn set_data_bgra(&mut self, x: usize, y: usize, width: usize, height: usize, data: &[u8]) {
+        // Nothing to do if there's not enough data to fill the region
+        if data.len() < width*height*4 {
+            return;
+        }
+
+        unsafe {
+            let texture_id = self.texture.texture_id;
+
+            gl::BindTexture(gl::TEXTURE_2D, texture_id);
+            gl::TexSubImage2D(gl::TEXTURE_2D, 0, x as gl::types::GLint, y as gl::types::GLint, width as gl::types::GLsizei, height as gl::types::GLsizei, gl::BGRA, gl::UNSIGNED_BYTE, data.as_ptr() as *const gl::types::GLvoid);
+
+            panic_on_gl_error("Write texture data");
+        }
+    }
+
     ///
     /// Creates an empty MSAA texture
     ///
@@ -11,7 +11,9 @@ use flo_canvas;
 
 use metal;
 
+use std::ffi;
 use std::ops::{Range};
+use std::sync::*;
 use std::collections::{HashMap};
 
 ///
@@ -59,6 +61,9 @@ struct RenderState<'a> {
     /// The texture used in the eraser slot
     erase_texture: Option<metal::Texture>,
 
+    /// The texture used by the texture shader
+    texture: Option<metal::Texture>,
+
     /// Buffer containing the current transformation matrix
     matrix: MatrixBuffer,
 
@@ -179,6 +184,7 @@ impl MetalRenderer {
         // Set the constant buffers
         state.command_encoder.set_vertex_buffer(VertexInputIndex_VertexInputIndexMatrix as u64, Some(&state.matrix), 0);
         state.command_encoder.set_fragment_texture(FragmentInputIndex_FragmentIndexEraseTexture as u64, state.erase_texture.as_ref().map::<&metal::TextureRef, _>(|t| t));
+        state.command_encoder.set_fragment_texture(FragmentInputIndex_FragmentIndexTexture as u64, state.texture.as_ref().map::<&metal::TextureRef, _>(|t| t));
     }
 
     ///
@@ -198,6 +204,7 @@ impl MetalRenderer {
             main_texture:           target_texture.clone(),
             target_texture:         target_texture.clone(),
             erase_texture:          None,
+            texture:                None,
             matrix:                 matrix,
             pipeline_config:        pipeline_config,
             pipeline_state:         pipeline_state,
@@ -225,6 +232,7 @@ impl MetalRenderer {
                 DrawFrameBuffer(render_id, x, y)                                        => { self.draw_frame_buffer(render_id, x, y, &mut render_state); }
                 ShowFrameBuffer                                                         => { /* This doesn't double-buffer so nothing to do */ }
                 CreateTextureBgra(texture_id, width, height)                            => { self.create_bgra_texture(texture_id, width, height); }
+                WriteTextureData(texture_id, min, max, data)                            => { self.write_texture_data(texture_id, min, max, data); }
                 FreeTexture(texture_id)                                                 => { self.free_texture(texture_id); }
                 Clear(color)                                                            => { self.clear(color, &mut render_state); }
                 UseShader(shader_type)                                                  => { self.use_shader(shader_type, &mut render_state); }
@@ -467,8 +475,55 @@ impl MetalRenderer {
         }
     }
 
+    ///
+    /// Creates a BGRA texture that can be read by the texture shaders
+    ///
     fn create_bgra_texture(&mut self, TextureId(texture_id): TextureId, width: usize, height: usize) {
+        // Allocate space for the texture
+        if texture_id >= self.textures.len() {
+            self.textures.extend((self.textures.len()..(texture_id+1))
+                .into_iter()
+                .map(|_| None));
+        }
+
+        // Free any existing texture
+        self.textures[texture_id] = None;
+
+        // Create the texture descriptor
+        let texture_descriptor = metal::TextureDescriptor::new();
+
+        texture_descriptor.set_texture_type(metal::MTLTextureType::D2);
+        texture_descriptor.set_width(width as u64);
+        texture_descriptor.set_height(height as u64);
+        texture_descriptor.set_pixel_format(metal::MTLPixelFormat::BGRA8Unorm);
+        texture_descriptor.set_usage(metal::MTLTextureUsage::ShaderRead);
+
+        // Store the new texture
+        self.textures[texture_id] = Some(self.device.new_texture(&texture_descriptor));
+    }
 
+    ///
+    /// Writes BGRA data to a region of a texture
+    ///
+    fn write_texture_data(&mut self, TextureId(texture_id): TextureId, (x1, y1): (usize, usize), (x2, y2): (usize, usize), data: Arc<Vec<u8>>) {
+        if x2 <= x1 || y2 <= y1 {
+            return;
+        }
+
+        // Do nothing if there's not enough data to fill the region
+        let (width, height) = (x2-x1, y2-y1);
+        if data.len() < width*height*4 {
+            return;
+        }
+
+        if let Some(Some(texture)) = self.textures.get(texture_id) {
+            let region = metal::MTLRegion {
+                origin: metal::MTLOrigin { x: x1 as u64, y: y1 as u64, z: 0 },
+                size:   metal::MTLSize { width: width as u64, height: height as u64, depth: 1 }
+            };
+
+            texture.replace_region(region, 0, data.as_ptr() as *const ffi::c_void, (width*4) as u64);
+        }
     }
 
     ///
@@ -495,6 +550,7 @@ impl MetalRenderer {
     fn use_shader(&mut self, shader_type: ShaderType, state: &mut RenderState) {
         // Reset the current shader state
         state.erase_texture = None;
+        state.texture       = None;
 
         // Update the state according to the shader type
         match shader_type {
@@ -506,6 +562,17 @@ impl MetalRenderer {
                 state.pipeline_config.fragment_shader   = String::from("simple_eraser_multisample_fragment");
                 state.erase_texture                     = self.textures[texture_id].clone();
             }
+
+            ShaderType::Texture { texture: TextureId(texture_id), erase_texture: None } => {
+                state.pipeline_config.fragment_shader   = String::from("texture_color_fragment");
+                state.texture                           = self.textures.get(texture_id).cloned().unwrap_or(None);
+            }
+
+            ShaderType::Texture { texture: TextureId(texture_id), erase_texture: Some(TextureId(erase_texture_id)) } => {
+                state.pipeline_config.fragment_shader   = String::from("texture_color_eraser_multisample_fragment");
+                state.texture                           = self.textures.get(texture_id).cloned().unwrap_or(None);
+                state.erase_texture                     = self.textures[erase_texture_id].clone();
+            }
         }
 
         // Update the command encoder with the new state
@@ -1,6 +1,7 @@
 use super::layer_state::*;
 use super::render_entity::*;
 use super::stroke_settings::*;
+use super::gradient::*;
 use super::renderer_core::*;
 use super::renderer_layer::*;
 use super::renderer_worker::*;
@@ -17,6 +18,7 @@ use futures::prelude::*;
 use num_cpus;
 use lyon::path;
 use lyon::math;
+use lyon::tessellation::{VertexBuffers};
 
 use std::collections::{HashMap};
 use std::ops::{Range};
@@ -48,6 +50,12 @@ pub struct CanvasRenderer {
     /// The transforms pushed to the stack when PushState was called
     transform_stack: Vec<canvas::Transform2D>,
 
+    /// The gradients that have been defined for this canvas
+    gradients: HashMap<canvas::GradientId, Vec<(f32, render::Rgba8)>>,
+
+    /// The textures that have been generated to render gradient fills (kept until the canvas is cleared)
+    gradient_textures: Vec<(GradientFill, render::TextureId)>,
+
     /// The next ID to assign to an entity for tessellation
     next_entity_id: usize,
 
@@ -79,7 +87,13 @@ impl CanvasRenderer {
             layer_definitions:      vec![],
             sprites:                HashMap::new(),
             unused_vertex_buffer:   0,
-            free_vertex_buffers:    vec![]
+            free_vertex_buffers:    vec![],
+            canvas_textures:        HashMap::new(),
+            unused_texture_id:      2,
+            free_textures:          vec![],
+            texture_usage:          HashMap::new(),
+            released_textures:      vec![],
+            pending_texture_actions: vec![]
         };
         let core = Arc::new(Desync::new(core));
 
@@ -108,6 +122,8 @@ impl CanvasRenderer {
             inverse_viewport_transform: canvas::Transform2D::identity(),
             active_transform:           canvas::Transform2D::identity(),
             transform_stack:            vec![],
+            gradients:                  HashMap::new(),
+            gradient_textures:          vec![],
             next_entity_id:             0,
             window_size:                (1.0, 1.0),
             window_scale:               1.0,
@@ -184,6 +200,7 @@ impl CanvasRenderer {
             render_order:       vec![RenderEntity::SetTransform(canvas::Transform2D::identity())],
             state:              LayerState {
                 fill_color:         render::Rgba8([0, 0, 0, 255]),
+                fill_gradient:      None,
                 stroke_settings:    StrokeSettings::new(),
                 current_matrix:     canvas::Transform2D::identity(),
                 sprite_matrix:      canvas::Transform2D::identity(),
@@ -217,6 +234,41 @@ impl CanvasRenderer {
         render::Rgba8([r, g, b, a])
     }
 
+    ///
+    /// Converts RGBA pixel data to the BGRA format used by the renderer
+    ///
+    fn rgba_to_bgra(bytes: &[u8]) -> Vec<u8> {
+        let mut bgra = bytes.to_vec();
+        for pixel in bgra.chunks_mut(4) {
+            if pixel.len() == 4 {
+                pixel.swap(0, 2);
+            }
+        }
+
+        bgra
+    }
+
+    ///
+    /// Finds or generates the texture used to render a gradient fill
+    ///
+    fn gradient_texture(gradient_textures: &mut Vec<(GradientFill, render::TextureId)>, core: &mut RenderCore, gradient: &GradientFill) -> render::TextureId {
+        if let Some((_, texture)) = gradient_textures.iter().find(|(existing, _)| existing.has_same_texture(gradient)) {
+            return *texture;
+        }
+
+        // Create a new texture for this gradient
+        let texture         = core.allocate_texture();
+        let (width, height) = gradient.texture_size();
+        let bgra            = Arc::new(gradient.texture_bgra());
+
+        core.add_texture_usage(texture);
+        core.pending_texture_actions.push(render::RenderAction::CreateTextureBgra(texture, width, height));
+        core.pending_texture_actions.push(render::RenderAction::WriteTextureData(texture, (0, 0), (width, height), bgra));
+
+        gradient_textures.push((gradient.clone(), texture));
+        texture
+    }
+
     ///
     /// Tessellates a drawing to the layers in this renderer
     ///
@@ -292,6 +344,7 @@ impl CanvasRenderer {
                             let layer_id            = self.current_layer;
                             let entity_id           = self.next_entity_id;
                             let active_transform    = &self.active_transform;
+                            let gradient_textures   = &mut self.gradient_textures;
 
                             self.next_entity_id += 1;
 
@@ -303,17 +356,37 @@ impl CanvasRenderer {
 
                                 // Create the render entity in the tessellating state
                                 let color               = layer.state.fill_color;
-                                let entity_index        = layer.render_order.len();
+                                let is_erase            = layer.state.blend_mode == canvas::BlendMode::DestinationOut;
 
                                 // When drawing to the erase layer (DesintationOut blend mode), all colour components are alpha components
-                                let color               = if layer.state.blend_mode == canvas::BlendMode::DestinationOut { render::Rgba8([color.0[3], color.0[3], color.0[3], color.0[3]]) } else { color };
+                                let color               = if is_erase { render::Rgba8([color.0[3], color.0[3], color.0[3], color.0[3]]) } else { color };
+                                let gradient            = layer.state.fill_gradient.as_ref().map(|gradient| if is_erase { gradient.alpha_only() } else { gradient.clone() });
 
+                                // Gradients are drawn from a texture, which the layer uses until it is cleared
+                                let gradient_texture    = gradient.as_ref().map(|gradient| Self::gradient_texture(gradient_textures, core, gradient));
+                                let layer               = if let Some(texture) = gradient_texture {
+                                    core.add_texture_usage(texture);
+
+                                    let layer = core.layer(layer_id);
+                                    layer.render_order.push(RenderEntity::SetTexture(Some(texture)));
+                                    layer
+                                } else {
+                                    core.layer(layer_id)
+                                };
+
+                                let entity_index        = layer.render_order.len();
                                 layer.render_order.push(RenderEntity::Tessellating(entity_id));
 
+                                if gradient_texture.is_some() { layer.render_order.push(RenderEntity::SetTexture(None)); }
+
                                 let entity          = LayerEntityRef { layer_id, entity_index, entity_id };
 
                                 // Create the canvas job
-                                CanvasJob::Fill { path, color, entity }
+                                if let Some(gradient) = gradient {
+                                    CanvasJob::FillGradient { path, gradient, entity }
+                                } else {
+                                    CanvasJob::Fill { path, color, entity }
+                                }
                             });
 
                             pending_jobs.push(job);
@@ -412,7 +485,103 @@ impl CanvasRenderer {
 
                     // Set the fill color
                     FillColor(color) => {
-                        core.sync(|core| core.layer(self.current_layer).state.fill_color = Self::render_color(color));
+                        core.sync(|core| {
+                            let state           = &mut core.layer(self.current_layer).state;
+                            state.fill_color    = Self::render_color(color);
+                            state.fill_gradient = None;
+                        });
+                    }
+
+                    // Defines or updates a gradient
+                    Gradient(gradient_id, canvas::GradientOp::Create(color)) => {
+                        self.gradients.insert(gradient_id, vec![(0.0, Self::render_color(color))]);
+                    }
+
+                    Gradient(gradient_id, canvas::GradientOp::AddStop(pos, color)) => {
+                        let stops = self.gradients.entry(gradient_id).or_insert_with(|| vec![]);
+                        stops.push((pos, Self::render_color(color)));
+                        stops.sort_by(|(pos1, _), (pos2, _)| pos1.partial_cmp(pos2).unwrap_or(std::cmp::Ordering::Equal));
+                    }
+
+                    // Sets a gradient as the fill (the stops are read at the point the gradient is selected)
+                    FillGradient(gradient_id, start, end) => {
+                        let stops       = self.gradients.get(&gradient_id).cloned().unwrap_or_else(|| vec![]);
+                        let gradient    = GradientFill::new(stops, GradientShape::Linear(start, end));
+
+                        core.sync(|core| core.layer(self.current_layer).state.fill_gradient = Some(gradient));
+                    }
+
+                    FillRadialGradient(gradient_id, center, radius) => {
+                        let stops       = self.gradients.get(&gradient_id).cloned().unwrap_or_else(|| vec![]);
+                        let gradient    = GradientFill::new(stops, GradientShape::Radial(center, radius));
+
+                        core.sync(|core| core.layer(self.current_layer).state.fill_gradient = Some(gradient));
+                    }
+
+                    // Creates a new texture (replacing any existing texture with the same ID)
+                    Texture(texture_id, canvas::TextureOp::Create(width, height, canvas::TextureFormat::Rgba)) => {
+                        core.sync(|core| {
+                            core.release_texture(texture_id);
+
+                            let render_texture = core.allocate_texture();
+                            core.canvas_textures.insert(texture_id, render_texture);
+                            core.add_texture_usage(render_texture);
+                            core.pending_texture_actions.push(render::RenderAction::CreateTextureBgra(render_texture, width as usize, height as usize));
+                        });
+                    }
+
+                    // Writes pixels to an existing texture
+                    Texture(texture_id, canvas::TextureOp::SetBytes(x, y, width, height, bytes)) => {
+                        let bgra = Arc::new(Self::rgba_to_bgra(&*bytes));
+
+                        core.sync(|core| {
+                            if let Some(render_texture) = core.canvas_textures.get(&texture_id).cloned() {
+                                let (x, y)          = (x as usize, y as usize);
+                                let (width, height) = (width as usize, height as usize);
+
+                                core.pending_texture_actions.push(render::RenderAction::WriteTextureData(render_texture, (x, y), (x+width, y+height), bgra));
+                            }
+                        });
+                    }
+
+                    // Releases a texture
+                    Texture(texture_id, canvas::TextureOp::Free) => {
+                        core.sync(|core| core.release_texture(texture_id));
+                    }
+
+                    // Renders a texture to a rectangle on the current layer
+                    DrawTexture(texture_id, (x1, y1), (x2, y2), alpha) => {
+                        let layer_id            = self.current_layer;
+                        let active_transform    = &self.active_transform;
+
+                        core.sync(move |core| {
+                            let render_texture = if let Some(render_texture) = core.canvas_textures.get(&texture_id) { *render_texture } else { return; };
+
+                            // The layer keeps the texture alive until it's cleared, even if the canvas texture is replaced or freed
+                            core.add_texture_usage(render_texture);
+                            let layer           = core.layer(layer_id);
+
+                            // Update the transformation matrix
+                            layer.update_transform(active_transform);
+
+                            // When drawing to the erase layer (DesintationOut blend mode), all colour components are alpha components
+                            let alpha           = Self::col_to_u8(alpha);
+                            let color           = if layer.state.blend_mode == canvas::BlendMode::DestinationOut { [alpha, alpha, alpha, alpha] } else { [255, 255, 255, alpha] };
+
+                            // The texture is drawn as a rectangle made of two triangles, with the first row of the texture at y1
+                            let mut geometry    = VertexBuffers::new();
+                            geometry.vertices   = vec![
+                                render::Vertex2D { pos: [x1, y1], tex_coord: [0.0, 0.0], color: color },
+                                render::Vertex2D { pos: [x2, y1], tex_coord: [1.0, 0.0], color: color },
+                                render::Vertex2D { pos: [x1, y2], tex_coord: [0.0, 1.0], color: color },
+                                render::Vertex2D { pos: [x2, y2], tex_coord: [1.0, 1.0], color: color },
+                            ];
+                            geometry.indices    = vec![0, 1, 2, 1, 3, 2];
+
+                            layer.render_order.push(RenderEntity::SetTexture(Some(render_texture)));
+                            layer.render_order.push(RenderEntity::VertexBuffer(geometry));
+                            layer.render_order.push(RenderEntity::SetTexture(None));
+                        });
                     }
 
                     // Set the line color
@@ -602,9 +771,17 @@ impl CanvasRenderer {
                             core.layers.push(layer0);
 
                             self.current_layer = layer0;
+
+                            // Textures are kept until they're freed (the old layers stop using them as their entities are freed)
+
+                            // Gradient textures are regenerated the next time they're used
+                            for (_, texture) in self.gradient_textures.drain(..) {
+                                core.remove_texture_usage(texture);
+                            }
                         });
 
                         self.active_transform   = canvas::Transform2D::identity();
+                        self.gradients          = HashMap::new();
                     }
 
                     // Selects a particular layer for drawing
@@ -0,0 +1,178 @@
+use flo_render as render;
+
+use std::mem;
+
+///
+/// The shape of a gradient fill
+///
+#[derive(Clone, PartialEq, Debug)]
+pub enum GradientShape {
+    /// Gradient runs along the line between two points
+    Linear((f32, f32), (f32, f32)),
+
+    /// Gradient runs outwards from a center point to a radius
+    Radial((f32, f32), f32)
+}
+
+///
+/// A gradient that has been selected as the fill for a layer
+///
+/// Gradients are rendered by sampling a texture generated from their stops. The texture coordinates are
+/// computed at the vertices generated by the tessellator, but as they vary linearly across the canvas, the
+/// gradient is still exact across large triangles.
+///
+#[derive(Clone, PartialEq, Debug)]
+pub struct GradientFill {
+    /// The stops for this gradient, ordered by position
+    pub stops: Vec<(f32, render::Rgba8)>,
+
+    /// The shape of this gradient
+    pub shape: GradientShape
+}
+
+impl GradientFill {
+    ///
+    /// Creates a new gradient fill from a set of stops
+    ///
+    pub fn new(stops: Vec<(f32, render::Rgba8)>, shape: GradientShape) -> GradientFill {
+        GradientFill { stops, shape }
+    }
+
+    ///
+    /// Returns a version of this gradient where all the colour components are set to the alpha component (used for the erase blend mode)
+    ///
+    pub fn alpha_only(&self) -> GradientFill {
+        let stops = self.stops.iter()
+            .map(|(pos, render::Rgba8(color))| (*pos, render::Rgba8([color[3], color[3], color[3], color[3]])))
+            .collect();
+
+        GradientFill { stops, shape: self.shape.clone() }
+    }
+
+    ///
+    /// True if this gradient is rendered using the same texture as another gradient
+    ///
+    pub fn has_same_texture(&self, other: &GradientFill) -> bool {
+        self.stops == other.stops && mem::discriminant(&self.shape) == mem::discriminant(&other.shape)
+    }
+
+    ///
+    /// The size of the texture used to render this gradient
+    ///
+    /// Linear gradients vary along one axis so only need a single row. Radial gradients can't be interpolated linearly
+    /// so they're rendered as an image of the gradient over the square that encloses its radius.
+    ///
+    pub fn texture_size(&self) -> (usize, usize) {
+        match self.shape {
+            GradientShape::Linear(_, _) => (256, 1),
+            GradientShape::Radial(_, _) => (256, 256)
+        }
+    }
+
+    ///
+    /// Generates the pixels for the texture used to render this gradient, in BGRA format
+    ///
+    pub fn texture_bgra(&self) -> Vec<u8> {
+        let (width, height) = self.texture_size();
+        let mut pixels      = Vec::with_capacity(width*height*4);
+
+        for y in 0..height {
+            for x in 0..width {
+                // Position of the center of this pixel in the gradient (0.0-1.0 from the start to the end of the line, or from the center to the radius)
+                let tx = ((x as f32) + 0.5) / (width as f32);
+                let ty = ((y as f32) + 0.5) / (height as f32);
+
+                let pos = match self.shape {
+                    GradientShape::Linear(_, _) => tx,
+                    GradientShape::Radial(_, _) => {
+                        let (dx, dy) = (tx*2.0-1.0, ty*2.0-1.0);
+                        (dx*dx + dy*dy).sqrt()
+                    }
+                };
+
+                let render::Rgba8([r, g, b, a]) = self.color_at_position(pos);
+                pixels.extend([b, g, r, a].iter().cloned());
+            }
+        }
+
+        pixels
+    }
+
+    ///
+    /// Returns the texture coordinates for a point in this gradient
+    ///
+    /// These vary linearly with the position of the point so they can be interpolated across triangles. Points past the
+    /// end of the gradient are outside the texture, which should clamp to the colours at its edges.
+    ///
+    pub fn tex_coord(&self, x: f32, y: f32) -> [f32; 2] {
+        match self.shape {
+            GradientShape::Linear(_, _)             => [self.position(x, y), 0.5],
+            GradientShape::Radial((cx, cy), radius) => {
+                if radius <= 0.0 {
+                    [1.0, 1.0]
+                } else {
+                    [(x-cx)/(radius*2.0) + 0.5, (y-cy)/(radius*2.0) + 0.5]
+                }
+            }
+        }
+    }
+
+    ///
+    /// Returns the position along the gradient of a point, which is less than 0 or more than 1 for points outside of the gradient
+    ///
+    fn position(&self, x: f32, y: f32) -> f32 {
+        match self.shape {
+            GradientShape::Linear((x1, y1), (x2, y2)) => {
+                let (dx, dy)    = (x2-x1, y2-y1);
+                let len_sq      = dx*dx + dy*dy;
+
+                if len_sq <= 0.0 {
+                    0.0
+                } else {
+                    ((x-x1)*dx + (y-y1)*dy) / len_sq
+                }
+            }
+
+            GradientShape::Radial((cx, cy), radius) => {
+                if radius <= 0.0 {
+                    1.0
+                } else {
+                    let (dx, dy) = (x-cx, y-cy);
+                    (dx*dx + dy*dy).sqrt() / radius
+                }
+            }
+        }
+    }
+
+    ///
+    /// Returns the colour of this gradient at a position along it (0.0-1.0)
+    ///
+    fn color_at_position(&self, pos: f32) -> render::Rgba8 {
+        let pos = pos.max(0.0).min(1.0);
+
+        // Find the stops either side of this position
+        let mut before  = None;
+        let mut after   = None;
+
+        for (stop_pos, color) in self.stops.iter() {
+            if *stop_pos <= pos {
+                before = Some((*stop_pos, *color));
+            } else {
+                after = Some((*stop_pos, *color));
+                break;
+            }
+        }
+
+        match (before, after) {
+            (None, None)                    => render::Rgba8([0, 0, 0, 255]),
+            (Some((_, color)), None)        => color,
+            (None, Some((_, color)))        => color,
+            (Some((pos1, render::Rgba8(col1))), Some((pos2, render::Rgba8(col2)))) => {
+                let ratio = if pos2 > pos1 { (pos-pos1)/(pos2-pos1) } else { 0.0 };
+                let mix   = |a: u8, b: u8| ((a as f32)*(1.0-ratio) + (b as f32)*ratio).round().max(0.0).min(255.0) as u8;
+
+                render::Rgba8([mix(col1[0], col2[0]), mix(col1[1], col2[1]), mix(col1[2], col2[2]), mix(col1[3], col2[3])])
+            }
+        }
+    }
+}
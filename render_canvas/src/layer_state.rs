@@ -1,4 +1,5 @@
 use super::stroke_settings::*;
+use super::gradient::*;
 
 use flo_canvas as canvas;
 use flo_render as render;
@@ -11,6 +12,9 @@ pub struct LayerState {
     /// The current fill colour
     pub fill_color: render::Rgba8,
 
+    /// The gradient to use for fills instead of the fill colour, if one is set
+    pub fill_gradient: Option<GradientFill>,
+
     /// The blend mode set for this layer
     pub blend_mode: canvas::BlendMode,
 
@@ -1,6 +1,7 @@
 mod render_entity;
 mod layer_state;
 mod stroke_settings;
+mod gradient;
 mod canvas_renderer;
 mod renderer_core;
 mod renderer_layer;
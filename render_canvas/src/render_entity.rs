@@ -26,5 +26,8 @@ pub enum RenderEntity {
     SetTransform(canvas::Transform2D),
 
     /// Sets the blend mode to use for the following rendering
-    SetBlendMode(render::BlendMode)
+    SetBlendMode(render::BlendMode),
+
+    /// Sets the texture that the following rendering should read its colours from (or None to go back to flat colours)
+    SetTexture(Option<render::TextureId>)
 }
@@ -36,7 +36,25 @@ pub struct RenderCore {
     pub unused_vertex_buffer: usize,
 
     /// Vertex buffers that were previously used but are now free
-    pub free_vertex_buffers: Vec<usize>
+    pub free_vertex_buffers: Vec<usize>,
+
+    /// The render textures assigned to the textures defined by the canvas
+    pub canvas_textures: HashMap<canvas::TextureId, render::TextureId>,
+
+    /// The first unused render texture ID (textures 0 and 1 are used by the render targets)
+    pub unused_texture_id: usize,
+
+    /// Render textures that were previously used but are now free
+    pub free_textures: Vec<usize>,
+
+    /// The number of users of each render texture (the canvas texture it's assigned to, and each layer entity that draws with it)
+    pub texture_usage: HashMap<usize, usize>,
+
+    /// Render textures that are no longer used by the canvas, and should be freed once the current frame has been rendered
+    pub released_textures: Vec<render::TextureId>,
+
+    /// Actions that update the textures, which need to be sent to the renderer before any layers are drawn
+    pub pending_texture_actions: Vec<render::RenderAction>
 }
 
 impl RenderCore {
@@ -61,6 +79,8 @@ impl RenderCore {
             VertexBuffer(_buffers)          => { }
             SetTransform(_)                 => { }
             SetBlendMode(_)                 => { }
+            SetTexture(None)                => { }
+            SetTexture(Some(texture_id))    => { self.remove_texture_usage(texture_id); }
             RenderSprite(_, _)              => { }
 
             DrawIndexed(render::VertexBufferId(vertex_id), render::IndexBufferId(index_id), _num_vertices) => {
@@ -120,6 +140,64 @@ impl RenderCore {
             })
     }
 
+    ///
+    /// Allocates a free texture ID
+    ///
+    pub fn allocate_texture(&mut self) -> render::TextureId {
+        let texture_id = self.free_textures.pop()
+            .unwrap_or_else(|| {
+                let texture_id = self.unused_texture_id;
+                self.unused_texture_id += 1;
+                texture_id
+            });
+
+        render::TextureId(texture_id)
+    }
+
+    ///
+    /// Records that something new (a canvas texture or a layer entity) is using a render texture
+    ///
+    pub fn add_texture_usage(&mut self, render::TextureId(texture_id): render::TextureId) {
+        *self.texture_usage.entry(texture_id).or_insert(0) += 1;
+    }
+
+    ///
+    /// Records that something has stopped using a render texture, releasing it if nothing is left using it
+    ///
+    pub fn remove_texture_usage(&mut self, render::TextureId(texture_id): render::TextureId) {
+        let usage = self.texture_usage.get_mut(&texture_id).map(|usage| { *usage -= 1; *usage });
+
+        if usage == Some(0) {
+            self.texture_usage.remove(&texture_id);
+            self.released_textures.push(render::TextureId(texture_id));
+        }
+    }
+
+    ///
+    /// Removes the render texture assigned to a canvas texture
+    ///
+    /// The render texture is released once no layers are using it anymore, and is freed after the next frame is rendered
+    ///
+    pub fn release_texture(&mut self, texture_id: canvas::TextureId) {
+        if let Some(render_texture) = self.canvas_textures.remove(&texture_id) {
+            self.remove_texture_usage(render_texture);
+        }
+    }
+
+    ///
+    /// Returns the actions needed to free the textures that were released by the last drawing, and returns their IDs to the free pool
+    ///
+    pub fn free_released_textures(&mut self) -> Vec<render::RenderAction> {
+        let released = self.released_textures.drain(..).collect::<Vec<_>>();
+
+        released.into_iter()
+            .map(|render::TextureId(texture_id)| {
+                self.free_textures.push(texture_id);
+                render::RenderAction::FreeTexture(render::TextureId(texture_id))
+            })
+            .collect()
+    }
+
     ///
     /// Returns the render actions required to send a vertex buffer (as a stack, so in reverse order)
     ///
@@ -212,6 +290,7 @@ impl RenderCore {
             render_order:       vec![RenderEntity::SetTransform(canvas::Transform2D::identity())],
             state:              LayerState {
                 fill_color:         render::Rgba8([0, 0, 0, 255]),
+                fill_gradient:      None,
                 stroke_settings:    StrokeSettings::new(),
                 current_matrix:     canvas::Transform2D::identity(),
                 sprite_matrix:      canvas::Transform2D::identity(),
@@ -122,6 +122,17 @@ impl RenderStreamState {
     }
 }
 
+///
+/// Returns the shader to use for a particular texture and erase texture
+///
+#[inline]
+fn shader_for(texture: Option<render::TextureId>, erase_texture: Option<render::TextureId>) -> render::ShaderType {
+    match texture {
+        Some(texture)   => render::ShaderType::Texture { texture, erase_texture },
+        None            => render::ShaderType::Simple { erase_texture }
+    }
+}
+
 impl RenderCore {
     ///
     /// Generates the rendering actions for the layer with the specified handle
@@ -139,6 +150,7 @@ impl RenderCore {
         let mut render_layer_stack  = vec![];
         let mut active_transform    = canvas::Transform2D::identity();
         let mut use_erase_texture   = false;
+        let mut active_texture      = None;
         let mut layer               = core.layer(layer_handle);
 
         render_state.transform      = Some(&viewport_transform * &active_transform);
@@ -213,13 +225,13 @@ impl RenderCore {
                     if new_blend_mode == &render::BlendMode::DestinationOut {
                         // The previous state should use the eraser texture that we're abount to generate
                         if old_state.render_target == Some(render::RenderTargetId(0)) {
-                            old_state.shader = Some(render::ShaderType::Simple { erase_texture: Some(render::TextureId(1)) });
+                            old_state.shader = Some(shader_for(active_texture, Some(render::TextureId(1))));
                         }
 
                         // Render to the eraser texture
                         render_state.blend_mode     = Some(render::BlendMode::AllChannelAlphaDestinationOver);
                         render_state.render_target  = Some(render::RenderTargetId(1));
-                        render_state.shader         = Some(render::ShaderType::Simple { erase_texture: None });
+                        render_state.shader         = Some(shader_for(active_texture, None));
 
                         // Flag that we're using the erase texture and it needs to be cleared for this layer
                         use_erase_texture       = true;
@@ -227,11 +239,11 @@ impl RenderCore {
                         // Render the main buffer
                         render_state.blend_mode     = Some(*new_blend_mode);
                         render_state.render_target  = Some(render::RenderTargetId(0));
-                        render_state.shader         = Some(render::ShaderType::Simple { erase_texture: None });
+                        render_state.shader         = Some(shader_for(active_texture, None));
 
                         // Use the eraser texture if one is specified
                         if use_erase_texture {
-                            render_state.shader     = Some(render::ShaderType::Simple { erase_texture: Some(render::TextureId(1)) });
+                            render_state.shader     = Some(shader_for(active_texture, Some(render::TextureId(1))));
                         }
                     }
 
@@ -239,6 +251,19 @@ impl RenderCore {
                     render_layer_stack.extend(old_state.update_from_state(render_state));
                 },
 
+                SetTexture(texture) => {
+                    active_texture          = *texture;
+
+                    // The erase texture is applied when drawing to the main buffer after an erase operation has been performed
+                    let erase_texture       = if use_erase_texture && render_state.render_target == Some(render::RenderTargetId(0)) { Some(render::TextureId(1)) } else { None };
+
+                    // The preceding instructions should render according to the previous state
+                    let old_state           = *render_state;
+                    render_state.shader     = Some(shader_for(active_texture, erase_texture));
+
+                    render_layer_stack.extend(old_state.update_from_state(render_state));
+                },
+
                 DrawIndexed(vertex_buffer, index_buffer, num_items) => {
                     // Draw the triangles
                     render_layer_stack.push(render::RenderAction::DrawIndexedTriangles(*vertex_buffer, *index_buffer, *num_items));
@@ -284,6 +309,14 @@ impl<'a> Stream for RenderStream<'a> {
                 self.processing_future  = None;
                 self.layer_id           = self.core.sync(|core| core.layers.len());
                 self.render_index       = 0;
+
+                // Any changes to the textures need to be sent before the layers are rendered
+                let mut texture_actions = self.core.sync(|core| core.pending_texture_actions.drain(..).collect::<Vec<_>>());
+                if texture_actions.len() > 0 {
+                    texture_actions.reverse();
+                    self.pending_stack = texture_actions;
+                    return Poll::Ready(self.pending_stack.pop());
+                }
             }
 
         }
@@ -321,9 +354,21 @@ impl<'a> Stream for RenderStream<'a> {
             // There are more actions to add to the pending stack
             self.pending_stack = result;
             return Poll::Ready(self.pending_stack.pop());
-        } else if let Some(final_actions) = self.final_stack.take() {
+        }
+
+        // Textures released by the drawing can be freed now nothing will render them (this is a stack, so these go on the end)
+        let free_textures = self.core.sync(|core| core.free_released_textures());
+
+        if let Some(final_actions) = self.final_stack.take() {
             // There are no more drawing actions, but we have a set of final post-render instructions to execute
             self.pending_stack = final_actions;
+            self.pending_stack.extend(free_textures);
+
+            return Poll::Ready(self.pending_stack.pop());
+        } else if free_textures.len() > 0 {
+            // No final actions, but there are still textures to free
+            self.pending_stack = free_textures;
+
             return Poll::Ready(self.pending_stack.pop());
         } else {
             // No further actions if the result was empty
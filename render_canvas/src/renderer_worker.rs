@@ -1,6 +1,7 @@
 use super::render_entity::*;
 use super::renderer_core::*;
 use super::stroke_settings::*;
+use super::gradient::*;
 
 use flo_render as render;
 use flo_canvas as canvas;
@@ -33,6 +34,15 @@ pub enum CanvasJob {
         entity:         LayerEntityRef
     },
 
+    ///
+    /// Tessellates a path by filling it with a gradient
+    ///
+    FillGradient {
+        path:           path::Path,
+        gradient:       GradientFill,
+        entity:         LayerEntityRef
+    },
+
     Stroke {
         path:           path::Path,
         stroke_options: StrokeSettings,
@@ -63,6 +73,7 @@ impl CanvasWorker {
 
         match job {
             Fill    { path, color, entity }             => self.fill(path, color, entity),
+            FillGradient { path, gradient, entity }     => self.fill_gradient(path, gradient, entity),
             Stroke  { path, stroke_options, entity }    => self.stroke(path, stroke_options, entity)
         }
     }
@@ -93,6 +104,32 @@ impl CanvasWorker {
         (entity, RenderEntity::VertexBuffer(geometry))
     }
 
+    ///
+    /// Fills the current path with a gradient and returns the resulting render entity
+    ///
+    fn fill_gradient(&mut self, path: path::Path, gradient: GradientFill, entity: LayerEntityRef) -> (LayerEntityRef, RenderEntity) {
+        // Create the tessellator and geometry
+        let mut tessellator     = tessellation::FillTessellator::new();
+        let mut geometry        = VertexBuffers::new();
+
+        // Set up the fill options
+        let mut fill_options    = FillOptions::default();
+        fill_options.fill_rule  = FillRule::NonZero;
+
+        // Tessellate the current path, mapping each vertex to its position in the gradient texture
+        tessellator.tessellate_path(&path, &fill_options,
+            &mut BuffersBuilder::new(&mut geometry, move |point: Point, _attr: FillAttributes| {
+                render::Vertex2D {
+                    pos:        point.to_array(),
+                    tex_coord:  gradient.tex_coord(point.x, point.y),
+                    color:      [255, 255, 255, 255]
+                }
+            })).unwrap();
+
+        // Result is a vertex buffer render entity
+        (entity, RenderEntity::VertexBuffer(geometry))
+    }
+
     ///
     /// Converts some stroke settings to Lyon stroke options
     ///
@@ -184,3 +184,101 @@ fn draw_twice() {
         assert!(match draw_vertices { Some(RenderAction::DrawIndexedTriangles(_, _, _)) => true, _ => false });
     })
 }
+
+#[test]
+fn draw_texture() {
+    // Create a 1x1 texture and draw it
+    let mut draw_texture = vec![];
+    draw_texture.texture(flo_canvas::TextureId(0), TextureOp::Create(1, 1, TextureFormat::Rgba));
+    draw_texture.texture(flo_canvas::TextureId(0), TextureOp::SetBytes(0, 0, 1, 1, std::sync::Arc::new(vec![255, 0, 0, 255])));
+    draw_texture.draw_texture(flo_canvas::TextureId(0), 0.0, 0.0, 100.0, 100.0, 1.0);
+
+    executor::block_on(async {
+        let mut renderer    = CanvasRenderer::new();
+        let draw_stream     = renderer.draw(draw_texture.into_iter());
+        let actions         = draw_stream.collect::<Vec<_>>().await;
+
+        // The texture should be created and filled in before it is used
+        let create_pos      = actions.iter().position(|action| match action { RenderAction::CreateTextureBgra(_, 1, 1) => true, _ => false });
+        let write_pos       = actions.iter().position(|action| match action { RenderAction::WriteTextureData(_, (0, 0), (1, 1), data) => **data == vec![0, 0, 255, 255], _ => false });
+        let shader_pos      = actions.iter().position(|action| match action { RenderAction::UseShader(ShaderType::Texture { .. }) => true, _ => false });
+
+        assert!(create_pos.is_some());
+        assert!(write_pos.is_some());
+        assert!(shader_pos.is_some());
+        assert!(create_pos < write_pos);
+        assert!(write_pos < shader_pos);
+    })
+}
+
+#[test]
+fn texture_is_kept_while_layer_uses_it() {
+    // Draw a texture to a layer
+    let mut draw_texture = vec![];
+    draw_texture.texture(flo_canvas::TextureId(0), TextureOp::Create(1, 1, TextureFormat::Rgba));
+    draw_texture.draw_texture(flo_canvas::TextureId(0), 0.0, 0.0, 100.0, 100.0, 1.0);
+
+    // Replace the texture without redrawing the layer
+    let mut replace_texture = vec![];
+    replace_texture.texture(flo_canvas::TextureId(0), TextureOp::Create(1, 1, TextureFormat::Rgba));
+
+    // Clear the layer, which is the last user of the original texture
+    let mut clear_layer = vec![];
+    clear_layer.clear_layer();
+
+    executor::block_on(async {
+        let mut renderer    = CanvasRenderer::new();
+
+        let first_frame     = renderer.draw(draw_texture.into_iter()).collect::<Vec<_>>().await;
+        let original        = first_frame.iter().filter_map(|action| match action { RenderAction::CreateTextureBgra(texture_id, _, _) => Some(*texture_id), _ => None }).nth(0).unwrap();
+
+        // The layer is still drawing the original texture, so it shouldn't be freed or reused
+        let second_frame    = renderer.draw(replace_texture.into_iter()).collect::<Vec<_>>().await;
+        let replacement     = second_frame.iter().filter_map(|action| match action { RenderAction::CreateTextureBgra(texture_id, _, _) => Some(*texture_id), _ => None }).nth(0).unwrap();
+
+        assert!(replacement != original);
+        assert!(!second_frame.iter().any(|action| match action { RenderAction::FreeTexture(texture_id) => *texture_id == original, _ => false }));
+
+        // Once the layer is cleared, the texture can be freed
+        let third_frame     = renderer.draw(clear_layer.into_iter()).collect::<Vec<_>>().await;
+
+        assert!(third_frame.iter().any(|action| match action { RenderAction::FreeTexture(texture_id) => *texture_id == original, _ => false }));
+    })
+}
+
+#[test]
+fn fill_linear_gradient_from_texture() {
+    // Fill a rectangle with a gradient running across it
+    let mut draw_gradient = vec![];
+    draw_gradient.gradient(GradientId(0), GradientOp::Create(Color::Rgba(1.0, 0.0, 0.0, 1.0)));
+    draw_gradient.gradient(GradientId(0), GradientOp::AddStop(0.5, Color::Rgba(0.0, 1.0, 0.0, 1.0)));
+    draw_gradient.gradient(GradientId(0), GradientOp::AddStop(1.0, Color::Rgba(0.0, 0.0, 1.0, 1.0)));
+    draw_gradient.fill_gradient(GradientId(0), 0.0, 0.0, 100.0, 0.0);
+
+    draw_gradient.new_path();
+    draw_gradient.rect(0.0, 0.0, 100.0, 100.0);
+    draw_gradient.fill();
+
+    executor::block_on(async {
+        let mut renderer    = CanvasRenderer::new();
+        let draw_stream     = renderer.draw(draw_gradient.into_iter());
+        let actions         = draw_stream.collect::<Vec<_>>().await;
+
+        // The gradient should be rendered from a texture
+        let create_pos      = actions.iter().position(|action| match action { RenderAction::CreateTextureBgra(_, 256, 1) => true, _ => false });
+        let shader_pos      = actions.iter().position(|action| match action { RenderAction::UseShader(ShaderType::Texture { .. }) => true, _ => false });
+
+        assert!(create_pos.is_some());
+        assert!(shader_pos.is_some());
+        assert!(create_pos < shader_pos);
+
+        // The vertices should be mapped onto the gradient (the colour in the middle can't be generated from the vertex colours)
+        let vertices        = actions.iter().filter_map(|action| match action { RenderAction::CreateVertex2DBuffer(_, vertices) => Some(vertices.clone()), _ => None }).nth(0).unwrap();
+
+        assert!(vertices.len() > 0);
+        for vertex in vertices.iter() {
+            assert!(vertex.color == [255, 255, 255, 255]);
+            assert!((vertex.tex_coord[0] - vertex.pos[0]/100.0).abs() < 0.01);
+        }
+    })
+}
@@ -0,0 +1,128 @@
+//!
+//! Compares the text and binary serialization formats, using the same edits as the round-trip tests but with much
+//! longer brush strokes
+//!
+//! Run with `cargo bench -p flo_sqlite_storage`
+//!
+
+use flo_animation::*;
+use flo_animation::storage::*;
+use flo_sqlite_storage::*;
+
+use futures::prelude::*;
+use futures::executor;
+
+use std::sync::*;
+use std::time::{Duration, Instant};
+
+const NUM_STROKES: usize        = 200;
+const POINTS_PER_STROKE: usize  = 500;
+
+///
+/// Generates the edits for an animation containing a lot of long brush strokes
+///
+fn brush_stroke_edits() -> Vec<AnimationEdit> {
+    let mut edits = vec![
+        AnimationEdit::AddNewLayer(2),
+        AnimationEdit::Layer(2, LayerEdit::AddKeyFrame(Duration::from_millis(0))),
+        AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::SelectBrush(
+                ElementId::Unassigned,
+                BrushDefinition::Ink(InkDefinition::default()),
+                BrushDrawingStyle::Draw
+            )
+        )),
+        AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::
+            BrushProperties(ElementId::Unassigned, BrushProperties::new()))),
+    ];
+
+    for stroke in 0..NUM_STROKES {
+        let points = (0..POINTS_PER_STROKE)
+            .map(|point| {
+                let t = point as f64 / 10.0;
+                RawPoint::from((100.0 + t*8.0, 200.0 + (stroke as f64)*2.0 + (t.sin()*30.0)))
+            })
+            .collect::<Vec<_>>();
+
+        edits.push(AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::BrushStroke(ElementId::Unassigned, Arc::new(points)))));
+    }
+
+    edits
+}
+
+///
+/// Runs a function a number of times and reports the average time it took
+///
+fn bench<TFn: FnMut()>(name: &str, iterations: u32, mut action: TFn) {
+    // Warm up
+    action();
+
+    let start = Instant::now();
+    for _ in 0..iterations {
+        action();
+    }
+    let average = start.elapsed() / iterations;
+
+    println!("{:<48} {:>10.2}ms", name, average.as_secs_f64() * 1000.0);
+}
+
+fn main() {
+    let edits = brush_stroke_edits();
+
+    // Serialized sizes of the edit log
+    let text_edits = edits.iter()
+        .map(|edit| { let mut data = String::new(); edit.serialize(&mut data); data })
+        .collect::<Vec<_>>();
+    let binary_edits = edits.iter()
+        .map(|edit| { let mut data: Vec<u8> = vec![]; edit.serialize(&mut data); data })
+        .collect::<Vec<_>>();
+
+    let text_size   = text_edits.iter().map(|edit| edit.len()).sum::<usize>();
+    let binary_size = binary_edits.iter().map(|edit| edit.len()).sum::<usize>();
+
+    println!("{} brush strokes of {} points", NUM_STROKES, POINTS_PER_STROKE);
+    println!();
+    println!("{:<48} {:>10} bytes", "Edit log (text)", text_size);
+    println!("{:<48} {:>10} bytes ({:.0}%)", "Edit log (binary)", binary_size, (binary_size as f64) / (text_size as f64) * 100.0);
+
+    // Serialized sizes of the elements generated by the edits
+    let store       = SqliteAnimationStorage::new_in_memory().unwrap();
+    let animation   = create_animation_editor(move |commands| store.get_responses(commands).boxed());
+    animation.perform_edits(edits.clone());
+
+    let frame       = animation.get_layer_with_id(2).unwrap().get_frame_at_time(Duration::from_millis(442));
+    let elements    = frame.vector_elements().unwrap().collect::<Vec<_>>();
+
+    let text_size   = elements.iter().map(|element| { let mut data = String::new(); element.serialize(&mut data); data.len() }).sum::<usize>();
+    let binary_size = elements.iter().map(|element| { let mut data: Vec<u8> = vec![]; element.serialize(&mut data); data.len() }).sum::<usize>();
+
+    println!("{:<48} {:>10} bytes", "Elements (text)", text_size);
+    println!("{:<48} {:>10} bytes ({:.0}%)", "Elements (binary)", binary_size, (binary_size as f64) / (text_size as f64) * 100.0);
+    println!();
+
+    // Time taken to serialize and deserialize the edits
+    bench("Serialize edit log (text)", 20, || {
+        edits.iter().for_each(|edit| { let mut data = String::new(); edit.serialize(&mut data); });
+    });
+    bench("Serialize edit log (binary)", 20, || {
+        edits.iter().for_each(|edit| { let mut data: Vec<u8> = vec![]; edit.serialize(&mut data); });
+    });
+    bench("Deserialize edit log (text)", 20, || {
+        text_edits.iter().for_each(|edit| { AnimationEdit::deserialize(&mut edit.chars()).unwrap(); });
+    });
+    bench("Deserialize edit log (binary)", 20, || {
+        binary_edits.iter().for_each(|edit| { AnimationEdit::deserialize(&mut &edit[..]).unwrap(); });
+    });
+
+    // Round trip through the storage, as in the round-trip tests
+    bench("Perform edits and read back (sqlite)", 3, || {
+        let store       = SqliteAnimationStorage::new_in_memory().unwrap();
+        let animation   = create_animation_editor(move |commands| store.get_responses(commands).boxed());
+        animation.perform_edits(edits.clone());
+
+        let frame       = animation.get_layer_with_id(2).unwrap().get_frame_at_time(Duration::from_millis(442));
+        assert!(frame.vector_elements().unwrap().count() == NUM_STROKES);
+
+        let edit_log    = executor::block_on(animation.read_edit_log(0..animation.get_num_edits()).collect::<Vec<_>>());
+        assert!(edit_log.len() == edits.len());
+    });
+}
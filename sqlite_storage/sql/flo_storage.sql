@@ -17,18 +17,28 @@ CREATE TABLE AnimationProperties (
 
 /** 
  * A log of all the edits the user has performed to the animation
+ *
+ * Edits are serialized using the binary encoding, but older files store them as TEXT, and both can be read
  */
 CREATE TABLE EditLog (
     EditId INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
-    Edit TEXT NOT NULL
+    Edit BLOB NOT NULL
 );
 
 /**
- * An element definition
+ * Named snapshots of the edit log (the animation as it was after the first EditIndex edits)
+ */
+CREATE TABLE Snapshots (
+    Name TEXT NOT NULL PRIMARY KEY,
+    EditIndex INTEGER NOT NULL
+) WITHOUT ROWID;
+
+/**
+ * An element definition (in the binary or the text encoding, like the edit log)
  */
 CREATE TABLE Elements (
     ElementId INTEGER NOT NULL PRIMARY KEY,
-    Element TEXT NOT NULL
+    Element BLOB NOT NULL
 ) WITHOUT ROWID;
 
 /**
@@ -74,3 +84,30 @@ CREATE TABLE LayerCache (
 
     PRIMARY KEY (LayerId, CacheType, TimeMicroseconds)
 ) WITHOUT ROWID;
+
+/**
+ * Images displayed by reference layers
+ */
+CREATE TABLE ReferenceImage (
+    LayerId INTEGER NOT NULL,
+    TimeMicroseconds INTEGER NOT NULL,
+    Image TEXT NOT NULL,
+
+    PRIMARY KEY (LayerId, TimeMicroseconds)
+) WITHOUT ROWID;
+
+/**
+ * Symbol definitions (the layers belonging to a symbol are stored in the Layers table)
+ */
+CREATE TABLE Symbols (
+    SymbolId INTEGER NOT NULL PRIMARY KEY,
+    Symbol TEXT NOT NULL
+) WITHOUT ROWID;
+
+/**
+ * Assets used by the animation (such as the images displayed by reference layers)
+ */
+CREATE TABLE Assets (
+    AssetId INTEGER NOT NULL PRIMARY KEY,
+    Asset TEXT NOT NULL
+) WITHOUT ROWID;
@@ -0,0 +1,43 @@
+/***
+ **
+ ** Upgrades a V4 file with the tables that have been added since it was created
+ **
+ **   The EditLog and Elements tables in older files declare their data columns as TEXT: these don't need to be changed
+ **   to store binary data, as SQLite never converts BLOB values to another type.
+ **
+ ***/
+
+/**
+ * Images displayed by reference layers
+ */
+CREATE TABLE IF NOT EXISTS ReferenceImage (
+    LayerId INTEGER NOT NULL,
+    TimeMicroseconds INTEGER NOT NULL,
+    Image TEXT NOT NULL,
+
+    PRIMARY KEY (LayerId, TimeMicroseconds)
+) WITHOUT ROWID;
+
+/**
+ * Named snapshots of the edit log (the animation as it was after the first EditIndex edits)
+ */
+CREATE TABLE IF NOT EXISTS Snapshots (
+    Name TEXT NOT NULL PRIMARY KEY,
+    EditIndex INTEGER NOT NULL
+) WITHOUT ROWID;
+
+/**
+ * Symbol definitions (the layers belonging to a symbol are stored in the Layers table)
+ */
+CREATE TABLE IF NOT EXISTS Symbols (
+    SymbolId INTEGER NOT NULL PRIMARY KEY,
+    Symbol TEXT NOT NULL
+) WITHOUT ROWID;
+
+/**
+ * Assets used by the animation (such as the images displayed by reference layers)
+ */
+CREATE TABLE IF NOT EXISTS Assets (
+    AssetId INTEGER NOT NULL PRIMARY KEY,
+    Asset TEXT NOT NULL
+) WITHOUT ROWID;
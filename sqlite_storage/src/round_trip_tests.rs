@@ -2,6 +2,7 @@ use super::*;
 
 use flo_animation::*;
 use flo_animation::storage::*;
+use flo_animation::serializer::{SerializedData};
 use flo_stream::*;
 use flo_canvas::*;
 
@@ -454,7 +455,7 @@ fn read_brush_strokes_from_edit_log() {
             )
         )),
         AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::
-            BrushProperties(ElementId::Unassigned, BrushProperties { color: Color::Rgba(0.5, 0.2, 0.7, 1.0), opacity: 1.0, size: 32.0 }))),
+            BrushProperties(ElementId::Unassigned, BrushProperties { color: Color::Rgba(0.5, 0.2, 0.7, 1.0), opacity: 1.0, size: 32.0, fill: BrushFill::Solid }))),
         AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::BrushStroke(ElementId::Unassigned, Arc::new(vec![
                     RawPoint::from((10.0, 10.0)),
                     RawPoint::from((20.0, 5.0))
@@ -488,7 +489,7 @@ fn read_brush_strokes_from_edit_log() {
     assert!(match edits[3] {
         AnimationEdit::Layer(2, LayerEdit::Paint(_when, PaintEdit::
             BrushProperties(ElementId::Assigned(_element_id), ref brush_properties)))
-                => brush_properties == &BrushProperties { color: Color::Rgba(0.5, 0.2, 0.7, 1.0), opacity: 1.0, size: 32.0 },
+                => brush_properties == &BrushProperties { color: Color::Rgba(0.5, 0.2, 0.7, 1.0), opacity: 1.0, size: 32.0, fill: BrushFill::Solid },
             _ => false
     });
     assert!(match edits[6] {
@@ -514,7 +515,7 @@ fn read_element_delete_from_edit_log() {
                 BrushDrawingStyle::Draw
             )
         )),
-        AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::BrushProperties(ElementId::Unassigned, BrushProperties { color: Color::Rgba(0.5, 0.2, 0.7, 1.0), opacity: 1.0, size: 32.0 }))),
+        AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::BrushProperties(ElementId::Unassigned, BrushProperties { color: Color::Rgba(0.5, 0.2, 0.7, 1.0), opacity: 1.0, size: 32.0, fill: BrushFill::Solid }))),
         AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::BrushStroke(ElementId::Assigned(126), Arc::new(vec![
                     RawPoint::from((10.0, 10.0)),
                     RawPoint::from((20.0, 5.0))
@@ -552,7 +553,7 @@ fn will_assign_element_ids() {
                 BrushDrawingStyle::Draw
             )
         )),
-        AnimationEdit::Layer(0, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::BrushProperties(ElementId::Unassigned, BrushProperties { color: Color::Rgba(0.5, 0.2, 0.7, 1.0), opacity: 1.0, size: 32.0 }))),
+        AnimationEdit::Layer(0, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::BrushProperties(ElementId::Unassigned, BrushProperties { color: Color::Rgba(0.5, 0.2, 0.7, 1.0), opacity: 1.0, size: 32.0, fill: BrushFill::Solid }))),
         AnimationEdit::Layer(0, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::BrushStroke(ElementId::Unassigned, Arc::new(vec![
                 RawPoint::from((10.0, 10.0)),
                 RawPoint::from((20.0, 5.0))
@@ -586,7 +587,7 @@ fn fetch_element_by_id() {
             )
         )),
         AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::
-            BrushProperties(ElementId::Unassigned, BrushProperties { color: Color::Rgba(0.5, 0.2, 0.7, 1.0), opacity: 1.0, size: 32.0 }))),
+            BrushProperties(ElementId::Unassigned, BrushProperties { color: Color::Rgba(0.5, 0.2, 0.7, 1.0), opacity: 1.0, size: 32.0, fill: BrushFill::Solid }))),
         AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::BrushStroke(ElementId::Assigned(126), Arc::new(vec![
                     RawPoint::from((10.0, 10.0)),
                     RawPoint::from((20.0, 5.0))
@@ -636,7 +637,7 @@ fn read_frame_after_edits() {
             )
         )),
         AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::
-            BrushProperties(ElementId::Unassigned, BrushProperties { color: Color::Rgba(0.5, 0.2, 0.7, 1.0), opacity: 1.0, size: 32.0 }))),
+            BrushProperties(ElementId::Unassigned, BrushProperties { color: Color::Rgba(0.5, 0.2, 0.7, 1.0), opacity: 1.0, size: 32.0, fill: BrushFill::Solid }))),
         AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::BrushStroke(ElementId::Assigned(126), Arc::new(vec![
                     RawPoint::from((10.0, 10.0)),
                     RawPoint::from((20.0, 5.0))
@@ -707,7 +708,7 @@ fn delete_element() {
             )
         )),
         AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::
-            BrushProperties(ElementId::Unassigned, BrushProperties { color: Color::Rgba(0.5, 0.2, 0.7, 1.0), opacity: 1.0, size: 32.0 }))),
+            BrushProperties(ElementId::Unassigned, BrushProperties { color: Color::Rgba(0.5, 0.2, 0.7, 1.0), opacity: 1.0, size: 32.0, fill: BrushFill::Solid }))),
         AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::BrushStroke(ElementId::Assigned(126), Arc::new(vec![
                     RawPoint::from((10.0, 10.0)),
                     RawPoint::from((20.0, 5.0))
@@ -1573,3 +1574,65 @@ fn retrieve_cached_onionskin_from_different_time() {
 
     assert!(cached_drawing == None);
 }
+
+#[test]
+fn read_text_encoded_edit_log() {
+    // Write an edit log in the text encoding, as older versions of FlowBetween did
+    let edits = vec![
+        AnimationEdit::AddNewLayer(2),
+        AnimationEdit::Layer(2, LayerEdit::AddKeyFrame(Duration::from_millis(0))),
+        AnimationEdit::SetSize(100.0, 200.0)
+    ];
+    let write_edits = edits.iter()
+        .map(|edit| {
+            let mut serialized = String::new();
+            edit.serialize(&mut serialized);
+            StorageCommand::WriteEdit(SerializedData::Text(serialized))
+        })
+        .collect::<Vec<_>>();
+
+    let sqlite_store    = SqliteAnimationStorage::new_from_connection(rusqlite::Connection::open_in_memory().unwrap());
+    executor::block_on(sqlite_store.get_responses(stream::iter(vec![write_edits])).next());
+
+    // Add another edit using the editor, which will use the binary encoding
+    let anim            = create_animation_editor(move |commands| sqlite_store.get_responses(commands).boxed());
+    anim.perform_edits(vec![AnimationEdit::RemoveLayer(2)]);
+
+    // Both encodings should be readable from the edit log
+    let edit_log        = executor::block_on(anim.read_edit_log(0..anim.get_num_edits()).collect::<Vec<_>>());
+
+    assert!(edit_log.len() == 4);
+    assert!(edit_log[0..3] == edits[..]);
+    assert!(edit_log[3] == AnimationEdit::RemoveLayer(2));
+}
+
+#[test]
+fn edits_made_during_rebuild_are_kept() {
+    let sqlite_store    = Arc::new(SqliteAnimationStorage::new_in_memory().unwrap());
+    let editor_store    = Arc::clone(&sqlite_store);
+    let anim            = create_animation_editor(move |commands| editor_store.get_responses(commands).boxed());
+
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewLayer(1),
+        AnimationEdit::Layer(1, LayerEdit::AddKeyFrame(Duration::from_millis(0)))
+    ]);
+
+    // Rebuild the animation while more layers are being added to it
+    let rebuild_store   = Arc::clone(&sqlite_store);
+    let rebuild         = std::thread::spawn(move || rebuild_store.rebuild_from_edit_log());
+
+    for layer_id in 2..20 {
+        anim.perform_edits(vec![AnimationEdit::AddNewLayer(layer_id)]);
+    }
+
+    assert!(rebuild.join().unwrap().is_ok());
+
+    // None of the edits should have been lost
+    anim.flush_caches();
+
+    let mut layer_ids = anim.get_layer_ids();
+    layer_ids.sort();
+
+    assert!(anim.get_num_edits() == 20);
+    assert!(layer_ids == (1..20).collect::<Vec<_>>());
+}
@@ -1,13 +1,43 @@
 use flo_animation::storage::*;
+use flo_animation::serializer::{SerializedData};
 
 use rusqlite;
 use rusqlite::{NO_PARAMS};
+use rusqlite::types::{ToSql, ToSqlOutput, FromSql, FromSqlResult, FromSqlError, ValueRef};
 
 use std::i64;
 use std::ops::{Range};
 use std::time::{Duration};
 
 const BASE_DATA_DEFN: &[u8]          = include_bytes!["../sql/flo_storage.sql"];
+const UPGRADE_DATA_DEFN: &[u8]       = include_bytes!["../sql/flo_storage_upgrade.sql"];
+
+///
+/// Serialized data as it's stored in the database
+///
+/// Data in the binary encoding is stored as a `BLOB` and data in the text encoding as `TEXT`, so files written by older
+/// versions of FlowBetween can still be read and can contain a mix of both encodings.
+///
+struct StoredData(SerializedData);
+
+impl ToSql for StoredData {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        match &self.0 {
+            SerializedData::Text(text)      => Ok(ToSqlOutput::from(text.as_str())),
+            SerializedData::Binary(bytes)   => Ok(ToSqlOutput::from(&bytes[..]))
+        }
+    }
+}
+
+impl FromSql for StoredData {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        match value {
+            ValueRef::Text(_)   => Ok(StoredData(SerializedData::Text(value.as_str()?.to_string()))),
+            ValueRef::Blob(_)   => Ok(StoredData(SerializedData::Binary(value.as_blob()?.to_vec()))),
+            _                   => Err(FromSqlError::InvalidType)
+        }
+    }
+}
 
 ///
 /// The SQLite core stores the synchronous data for the SQLite database
@@ -18,6 +48,12 @@ pub (super) struct SqliteCore {
 
     /// If the core has encountered an error it can't recover from, this is what it is
     error: Option<(StorageError, String)>,
+
+    /// True if the derived tables are being rebuilt from the edit log
+    rebuilding: bool,
+
+    /// Set to true if a command fails while the derived tables are being rebuilt
+    rebuild_failed: bool
 }
 
 impl SqliteCore {
@@ -26,8 +62,10 @@ impl SqliteCore {
     ///
     pub fn new(connection: rusqlite::Connection) -> SqliteCore {
         SqliteCore {
-            connection: connection,
-            error:      None
+            connection:     connection,
+            error:          None,
+            rebuilding:     false,
+            rebuild_failed: false
         }
     }
 
@@ -54,25 +92,68 @@ impl SqliteCore {
         self.check_error(self.connection.execute_batch(&defn))
     }
 
+    ///
+    /// When the connection is an existing file, adds any tables that were introduced after it was created
+    ///
+    pub fn upgrade(&mut self) -> Result<(), rusqlite::Error> {
+        let defn = String::from_utf8_lossy(UPGRADE_DATA_DEFN);
+
+        self.check_error(self.connection.execute_batch(&defn))
+    }
+
     ///
     /// Runs some commands on this storage database
     ///
+    /// The commands are run as a single transaction: if any command fails, none of the changes made by the batch
+    /// are kept and the result is a single error response. The core can carry on processing commands after this.
+    ///
     pub fn run_commands(&mut self, commands: Vec<StorageCommand>) -> Vec<StorageResponse> {
         // If we're in an error state, then the result is just to indicate that we can't continue
         if let Some((_err, msg)) = self.error.as_ref() {
+            if self.rebuilding { self.rebuild_failed = true; }
             return vec![StorageResponse::Error(StorageError::CannotContinueAfterError, msg.clone())];
         }
 
+        // Start a transaction for this batch (savepoints are used so that batches can be nested inside a rebuild)
+        if let Err(err) = self.connection.execute_batch("SAVEPOINT CommandBatch;") {
+            return vec![StorageResponse::Error(StorageError::General, err.to_string())];
+        }
+
         // Process each of the commands in turn and flatten to a single response
         let result = commands.into_iter()
             .map(|cmd| self.run_command(cmd))
             .collect::<Result<Vec<Vec<StorageResponse>>, _>>()
-            .map(|vec_of_vec| vec_of_vec.into_iter().flatten().collect());
+            .map(|vec_of_vec| vec_of_vec.into_iter().flatten().collect::<Vec<_>>());
 
-        match self.check_error(result) {
-            Err(err)    => vec![StorageResponse::Error(StorageError::General, err.to_string())],
-            Ok(result)  => result
+        // Commit the batch if it succeeded
+        let result = result.and_then(|result| {
+            self.connection.execute_batch("RELEASE CommandBatch;")?;
+            Ok(result)
+        });
+
+        match result {
+            Ok(result)  => result,
+            Err(err)    => {
+                // Undo everything the batch did
+                self.rollback_batch();
+
+                vec![StorageResponse::Error(StorageError::General, err.to_string())]
+            }
+        }
+    }
+
+    ///
+    /// Rolls back the changes made by the current batch of commands
+    ///
+    fn rollback_batch(&mut self) {
+        // A failure during a rebuild means the rebuild has to be abandoned
+        if self.rebuilding {
+            self.rebuild_failed = true;
         }
+
+        // If the rollback fails the state of the database is unknown, so we can't continue
+        let rollback = self.connection.execute_batch("ROLLBACK TO CommandBatch; RELEASE CommandBatch;");
+        self.check_error(rollback).ok();
     }
 
     ///
@@ -85,6 +166,10 @@ impl SqliteCore {
             WriteAnimationProperties(properties)                => { self.write_animation_properties(properties) },
             ReadAnimationProperties                             => { self.read_animation_properties() },
             WriteEdit(edit)                                     => { self.write_edit(edit) },
+            ReplaceEditLog(num_replaced, edits)                 => { self.replace_edit_log(num_replaced, edits) },
+            WriteSnapshot(name, edit_index)                     => { self.write_snapshot(name, edit_index) },
+            DeleteSnapshot(name)                                => { self.delete_snapshot(name) },
+            ReadSnapshots                                       => { self.read_snapshots() },
             ReadHighestUnusedElementId                          => { self.read_highest_unused_element_id() },
             ReadEditLogLength                                   => { self.read_edit_log_length() },
             ReadEdits(edit_range)                               => { self.read_edits(edit_range) },
@@ -106,9 +191,138 @@ impl SqliteCore {
             WriteLayerCache(layer_id, when, cache_type, value)  => { self.write_layer_cache(layer_id, when, cache_type, value) },
             DeleteLayerCache(layer_id, when, cache_type)        => { self.delete_layer_cache(layer_id, when, cache_type) },
             ReadLayerCache(layer_id, when, cache_type)          => { self.read_layer_cache(layer_id, when, cache_type) },
+            WriteReferenceImage(layer_id, when, image)          => { self.write_reference_image(layer_id, when, image) },
+            DeleteReferenceImage(layer_id, when)                => { self.delete_reference_image(layer_id, when) },
+            ReadReferenceImage(layer_id, when)                  => { self.read_reference_image(layer_id, when) },
+            WriteAsset(asset_id, asset)                         => { self.write_asset(asset_id, asset) },
+            ReadAsset(asset_id)                                 => { self.read_asset(asset_id) },
+            ReadHighestUnusedAssetId                            => { self.read_highest_unused_asset_id() },
+            WriteSymbol(symbol_id, properties)                  => { self.write_symbol(symbol_id, properties) },
+            DeleteSymbol(symbol_id)                             => { self.delete_symbol(symbol_id) },
+            ReadSymbols                                         => { self.read_symbols() },
         };
 
-        self.check_error(result)
+        result
+    }
+
+    ///
+    /// Checks the derived tables for consistency, returning a description of each problem found
+    ///
+    pub fn check_integrity(&mut self) -> Result<Vec<String>, rusqlite::Error> {
+        let mut problems = vec![];
+
+        // Ask SQLite to check the database file itself
+        let mut integrity_check = self.connection.prepare("PRAGMA integrity_check;")?;
+        let sqlite_problems     = integrity_check.query_map(NO_PARAMS, |row| row.get::<_, String>(0))?;
+
+        for problem in sqlite_problems {
+            let problem = problem?;
+            if problem != "ok" { problems.push(problem); }
+        }
+
+        // Look for rows that refer to things that don't exist
+        self.find_problems("SELECT ElementId, LayerId, TimeMicroseconds FROM ElementKeyframeAttachment WHERE ElementId NOT IN (SELECT ElementId FROM Elements);",
+            |element_id, layer_id, when| format!("Element {} is attached to layer {} at {}µs but does not exist", element_id, layer_id, when), &mut problems)?;
+        self.find_problems("SELECT ElementId, LayerId, TimeMicroseconds FROM ElementKeyframeAttachment WHERE LayerId NOT IN (SELECT LayerId FROM Layers);",
+            |element_id, layer_id, when| format!("Element {} is attached to layer {} at {}µs but the layer does not exist", element_id, layer_id, when), &mut problems)?;
+        self.find_problems("SELECT ElementId, LayerId, TimeMicroseconds FROM ElementKeyframeAttachment AS Attachment WHERE NOT EXISTS (SELECT 1 FROM Keyframe WHERE Keyframe.LayerId = Attachment.LayerId AND Keyframe.TimeMicroseconds = Attachment.TimeMicroseconds);",
+            |element_id, layer_id, when| format!("Element {} is attached to layer {} at {}µs but there is no keyframe at that time", element_id, layer_id, when), &mut problems)?;
+        self.find_problems("SELECT LayerId, TimeMicroseconds, 0 FROM Keyframe WHERE LayerId NOT IN (SELECT LayerId FROM Layers);",
+            |layer_id, when, _| format!("There is a keyframe at {}µs for layer {}, which does not exist", when, layer_id), &mut problems)?;
+        self.find_problems("SELECT LayerId, TimeMicroseconds, 0 FROM ReferenceImage WHERE LayerId NOT IN (SELECT LayerId FROM Layers);",
+            |layer_id, when, _| format!("There is a reference image at {}µs for layer {}, which does not exist", when, layer_id), &mut problems)?;
+
+        Ok(problems)
+    }
+
+    ///
+    /// Runs a query that returns three integer columns, and adds a problem for each row that is returned
+    ///
+    fn find_problems<DescribeFn: Fn(i64, i64, i64) -> String>(&mut self, query: &str, describe: DescribeFn, problems: &mut Vec<String>) -> Result<(), rusqlite::Error> {
+        let mut read    = self.connection.prepare(query)?;
+        let rows        = read.query_map(NO_PARAMS, |row| Ok((row.get::<_, i64>(0)?, row.get::<_, i64>(1)?, row.get::<_, i64>(2)?)))?;
+
+        for row in rows {
+            let (a, b, c) = row?;
+            problems.push(describe(a, b, c));
+        }
+
+        Ok(())
+    }
+
+    ///
+    /// Starts rebuilding the derived tables from the edit log, returning the edits that need to be replayed
+    ///
+    /// Everything apart from the edit log is cleared. Until `finish_rebuild()` is called, the changes are kept in a
+    /// transaction, and edits that are written while replaying are discarded as they're already in the log.
+    ///
+    pub fn begin_rebuild(&mut self) -> Result<Vec<SerializedData>, rusqlite::Error> {
+        self.connection.execute_batch("SAVEPOINT Rebuild;")?;
+
+        match self.clear_derived_tables() {
+            Ok(edits) => {
+                self.rebuilding     = true;
+                self.rebuild_failed = false;
+
+                Ok(edits)
+            },
+
+            Err(err) => {
+                let rollback = self.connection.execute_batch("ROLLBACK TO Rebuild; RELEASE Rebuild;");
+                self.check_error(rollback).ok();
+
+                Err(err)
+            }
+        }
+    }
+
+    ///
+    /// Reads the edit log and then clears all of the tables that are generated from it
+    ///
+    fn clear_derived_tables(&mut self) -> Result<Vec<SerializedData>, rusqlite::Error> {
+        let edits = {
+            let mut read    = self.connection.prepare("SELECT Edit FROM EditLog ORDER BY EditId ASC;")?;
+            let edits       = read.query_map(NO_PARAMS, |row| row.get::<_, StoredData>(0).map(|StoredData(edit)| edit))?;
+
+            edits.collect::<Result<Vec<_>, _>>()?
+        };
+
+        self.connection.execute_batch("
+            DELETE FROM AnimationProperties;
+            DELETE FROM Elements;
+            DELETE FROM Layers;
+            DELETE FROM Keyframe;
+            DELETE FROM ElementKeyframeAttachment;
+            DELETE FROM LayerCache;
+            DELETE FROM ReferenceImage;
+            DELETE FROM Symbols;")?;
+
+        Ok(edits)
+    }
+
+    ///
+    /// Finishes rebuilding the derived tables, committing the changes if `commit` is true and no commands failed
+    ///
+    /// Returns true if the rebuilt tables were committed
+    ///
+    pub fn finish_rebuild(&mut self, commit: bool) -> Result<bool, rusqlite::Error> {
+        if !self.rebuilding {
+            return Ok(false);
+        }
+
+        let commit          = commit && !self.rebuild_failed;
+        self.rebuilding     = false;
+        self.rebuild_failed = false;
+
+        if commit {
+            let release = self.connection.execute_batch("RELEASE Rebuild;");
+            self.check_error(release)?;
+        } else {
+            let rollback = self.connection.execute_batch("ROLLBACK TO Rebuild; RELEASE Rebuild;");
+            self.check_error(rollback)?;
+        }
+
+        Ok(commit)
     }
 
     ///
@@ -157,13 +371,72 @@ impl SqliteCore {
     ///
     /// Updates the animation properties for this animation
     ///
-    fn write_edit(&mut self, edit: String) -> Result<Vec<StorageResponse>, rusqlite::Error> {
+    fn write_edit(&mut self, edit: SerializedData) -> Result<Vec<StorageResponse>, rusqlite::Error> {
+        // Edits being replayed during a rebuild are already in the log
+        if self.rebuilding {
+            return Ok(vec![StorageResponse::Updated]);
+        }
+
+        let mut write   = self.connection.prepare_cached("INSERT INTO EditLog (Edit) VALUES (?);")?;
+        write.execute(&[StoredData(edit)])?;
+
+        Ok(vec![StorageResponse::Updated])
+    }
+
+    ///
+    /// Replaces the edits at the start of the edit log, keeping any edits that follow them
+    ///
+    fn replace_edit_log(&mut self, num_replaced: usize, edits: Vec<SerializedData>) -> Result<Vec<StorageResponse>, rusqlite::Error> {
+        // Edits made after the ones being replaced need to be moved to the end of the new log
+        let later_edits = {
+            let mut read    = self.connection.prepare_cached("SELECT Edit FROM EditLog WHERE EditId > ? ORDER BY EditId ASC;")?;
+            let later_edits = read.query_map(&[num_replaced as i64], |row| row.get::<_, StoredData>(0))?;
+            let later_edits = later_edits.map(|row| row.map(|StoredData(edit)| edit)).collect::<Result<Vec<_>, _>>()?;
+
+            later_edits
+        };
+
+        // Edit IDs are used as indexes into the log, so they need to start from 1 again
+        self.connection.execute_batch("DELETE FROM EditLog; DELETE FROM sqlite_sequence WHERE name = 'EditLog';")?;
+
         let mut write   = self.connection.prepare_cached("INSERT INTO EditLog (Edit) VALUES (?);")?;
-        write.execute(&[edit])?;
+        for edit in edits.into_iter().chain(later_edits) {
+            write.execute(&[StoredData(edit)])?;
+        }
+
+        Ok(vec![StorageResponse::Updated])
+    }
+
+    ///
+    /// Stores a named snapshot of the edit log
+    ///
+    fn write_snapshot(&mut self, name: String, edit_index: usize) -> Result<Vec<StorageResponse>, rusqlite::Error> {
+        let mut write   = self.connection.prepare_cached("INSERT OR REPLACE INTO Snapshots (Name, EditIndex) VALUES (?, ?);")?;
+        write.execute(params![name, edit_index as i64])?;
 
         Ok(vec![StorageResponse::Updated])
     }
 
+    ///
+    /// Removes a named snapshot
+    ///
+    fn delete_snapshot(&mut self, name: String) -> Result<Vec<StorageResponse>, rusqlite::Error> {
+        let mut delete  = self.connection.prepare_cached("DELETE FROM Snapshots WHERE Name = ?;")?;
+        delete.execute(&[name])?;
+
+        Ok(vec![StorageResponse::Updated])
+    }
+
+    ///
+    /// Reads the named snapshots of the edit log
+    ///
+    fn read_snapshots(&mut self) -> Result<Vec<StorageResponse>, rusqlite::Error> {
+        let mut read    = self.connection.prepare_cached("SELECT Name, EditIndex FROM Snapshots ORDER BY EditIndex ASC, Name ASC;")?;
+        let snapshots   = read.query_map(NO_PARAMS, |row| Ok(StorageResponse::Snapshot(row.get::<_, String>(0)?, row.get::<_, i64>(1)? as usize)))?;
+
+        Ok(snapshots.collect::<Result<_, _>>()?)
+    }
+
     ///
     /// Updates the animation properties for this animation
     ///
@@ -195,8 +468,8 @@ impl SqliteCore {
     ///
     fn read_edits(&mut self, range: Range<usize>) -> Result<Vec<StorageResponse>, rusqlite::Error> {
         let mut read    = self.connection.prepare_cached("SELECT EditId, Edit FROM EditLog WHERE EditId >= ? AND EditId < ? ORDER BY EditId ASC;")?;
-        let edits       = read.query_map(&[(range.start as i64)+1, (range.end as i64)+1], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?)))?;
-        let edits       = edits.map(|row| row.map(|(edit_id, edit)| StorageResponse::Edit((edit_id-1) as usize, edit)));
+        let edits       = read.query_map(&[(range.start as i64)+1, (range.end as i64)+1], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, StoredData>(1)?)))?;
+        let edits       = edits.map(|row| row.map(|(edit_id, StoredData(edit))| StorageResponse::Edit((edit_id-1) as usize, edit)));
 
         Ok(edits.collect::<Result<_, _>>()?)
     }
@@ -204,9 +477,9 @@ impl SqliteCore {
     ///
     /// Writes data for an element
     ///
-    fn write_element(&mut self, element_id: i64, element: String) -> Result<Vec<StorageResponse>, rusqlite::Error> {
+    fn write_element(&mut self, element_id: i64, element: SerializedData) -> Result<Vec<StorageResponse>, rusqlite::Error> {
         let mut write   = self.connection.prepare_cached("INSERT OR REPLACE INTO Elements (ElementId, Element) VALUES (?, ?);")?;
-        write.execute(params![element_id, element])?;
+        write.execute(params![element_id, StoredData(element)])?;
 
         Ok(vec![StorageResponse::Updated])
     }
@@ -218,10 +491,10 @@ impl SqliteCore {
         use rusqlite::Error::QueryReturnedNoRows;
 
         let mut read    = self.connection.prepare_cached("SELECT Element FROM Elements WHERE ElementId = ?;")?;
-        let element     = read.query_row(&[element_id], |row| row.get(0));
+        let element     = read.query_row(&[element_id], |row| row.get::<_, StoredData>(0));
 
         match element {
-            Ok(element)                 => Ok(vec![StorageResponse::Element(element_id, element)]),
+            Ok(StoredData(element))     => Ok(vec![StorageResponse::Element(element_id, element)]),
             Err(QueryReturnedNoRows)    => Ok(vec![StorageResponse::NotFound]),
             Err(err)                    => Err(err)
         }
@@ -231,7 +504,7 @@ impl SqliteCore {
     /// Deletes an element from the database
     ///
     fn delete_element(&mut self, element_id: i64) -> Result<Vec<StorageResponse>, rusqlite::Error> {
-        let transaction = self.connection.transaction()?;
+        let transaction = self.connection.savepoint()?;
 
         {
             let mut delete  = transaction.prepare_cached("DELETE FROM ElementKeyframeAttachment WHERE ElementId = ?;")?;
@@ -260,7 +533,7 @@ impl SqliteCore {
     /// Deletes a layer from the database
     ///
     fn delete_layer(&mut self, layer_id: u64) -> Result<Vec<StorageResponse>, rusqlite::Error> {
-        let transaction = self.connection.transaction()?;
+        let transaction = self.connection.savepoint()?;
 
         {
             let mut delete  = transaction.prepare_cached("DELETE FROM ElementKeyframeAttachment WHERE LayerId = ?;")?;
@@ -269,6 +542,9 @@ impl SqliteCore {
             let mut delete  = transaction.prepare_cached("DELETE FROM LayerCache WHERE LayerId = ?;")?;
             delete.execute(&[layer_id as i64])?;
 
+            let mut delete  = transaction.prepare_cached("DELETE FROM ReferenceImage WHERE LayerId = ?;")?;
+            delete.execute(&[layer_id as i64])?;
+
             let mut delete  = transaction.prepare_cached("DELETE FROM Layers WHERE LayerId = ?;")?;
             delete.execute(&[layer_id as i64])?;
         }
@@ -318,7 +594,7 @@ impl SqliteCore {
     /// Adds a new layer or updates its properties
     ///
     fn delete_key_frame(&mut self, layer_id: u64, when: Duration) -> Result<Vec<StorageResponse>, rusqlite::Error> {
-        let transaction = self.connection.transaction()?;
+        let transaction = self.connection.savepoint()?;
 
         {
             let time_microseconds   = Self::time_to_int(when);
@@ -496,8 +772,8 @@ impl SqliteCore {
             INNER JOIN ElementKeyframeAttachment ON ElementKeyframeAttachment.ElementId = Elements.ElementId
             WHERE ElementKeyframeAttachment.LayerId = ? AND ElementKeyFrameAttachment.TimeMicroseconds = ?;")?;
 
-        let elements    = read.query_map(&[layer_id as i64, when], |row| Ok((row.get(0)?, row.get(1)?)))?;
-        let elements    = elements.map(|element| element.map(|(element_id, element)| StorageResponse::Element(element_id, element)));
+        let elements    = read.query_map(&[layer_id as i64, when], |row| Ok((row.get(0)?, row.get::<_, StoredData>(1)?)))?;
+        let elements    = elements.map(|element| element.map(|(element_id, StoredData(element))| StorageResponse::Element(element_id, element)));
 
         elements.collect()
     }
@@ -543,4 +819,116 @@ impl SqliteCore {
             Err(other)                  => Err(other)
         }
     }
+
+    ///
+    /// Writes the reference image that's displayed from a particular time
+    ///
+    fn write_reference_image(&mut self, layer_id: u64, when: Duration, image: String) -> Result<Vec<StorageResponse>, rusqlite::Error> {
+        let when        = Self::time_to_int(when);
+
+        let mut write   = self.connection.prepare_cached("INSERT OR REPLACE INTO ReferenceImage (LayerId, TimeMicroseconds, Image) VALUES (?, ?, ?);")?;
+        write.execute(params![layer_id as i64, when, image])?;
+
+        Ok(vec![StorageResponse::Updated])
+    }
+
+    ///
+    /// Removes the reference image that's displayed from a particular time
+    ///
+    fn delete_reference_image(&mut self, layer_id: u64, when: Duration) -> Result<Vec<StorageResponse>, rusqlite::Error> {
+        let when        = Self::time_to_int(when);
+
+        let mut delete  = self.connection.prepare_cached("DELETE FROM ReferenceImage WHERE LayerId = ? AND TimeMicroseconds = ?;")?;
+        let deleted     = delete.execute(&[layer_id as i64, when])?;
+
+        if deleted > 0 {
+            Ok(vec![StorageResponse::Updated])
+        } else {
+            Ok(vec![StorageResponse::NotFound])
+        }
+    }
+
+    ///
+    /// Reads the reference image that's displayed at a particular time (the image at or before the specified time)
+    ///
+    fn read_reference_image(&mut self, layer_id: u64, when: Duration) -> Result<Vec<StorageResponse>, rusqlite::Error> {
+        use rusqlite::Error::QueryReturnedNoRows;
+
+        let when        = Self::time_to_int(when);
+
+        let mut read    = self.connection.prepare_cached("SELECT TimeMicroseconds, Image FROM ReferenceImage WHERE LayerId = ? AND TimeMicroseconds <= ? ORDER BY TimeMicroseconds DESC LIMIT 1;")?;
+        let result      = read.query_row(params![layer_id as i64, when], |row| Ok((row.get::<_, i64>(0)?, row.get(1)?)));
+
+        match result {
+            Ok((image_time, image))     => Ok(vec![StorageResponse::ReferenceImage(Self::int_to_time(image_time), image)]),
+            Err(QueryReturnedNoRows)    => Ok(vec![StorageResponse::NotFound]),
+            Err(other)                  => Err(other)
+        }
+    }
+
+    ///
+    /// Writes the data for an asset
+    ///
+    fn write_asset(&mut self, asset_id: u64, asset: String) -> Result<Vec<StorageResponse>, rusqlite::Error> {
+        let mut write   = self.connection.prepare_cached("INSERT OR REPLACE INTO Assets (AssetId, Asset) VALUES (?, ?);")?;
+        write.execute(params![asset_id as i64, asset])?;
+
+        Ok(vec![StorageResponse::Updated])
+    }
+
+    ///
+    /// Reads the data for an asset
+    ///
+    fn read_asset(&mut self, asset_id: u64) -> Result<Vec<StorageResponse>, rusqlite::Error> {
+        use rusqlite::Error::QueryReturnedNoRows;
+
+        let mut read    = self.connection.prepare_cached("SELECT Asset FROM Assets WHERE AssetId = ?;")?;
+        let result      = read.query_row(&[asset_id as i64], |row| row.get::<_, String>(0));
+
+        match result {
+            Ok(asset)                   => Ok(vec![StorageResponse::Asset(asset_id, asset)]),
+            Err(QueryReturnedNoRows)    => Ok(vec![StorageResponse::NotFound]),
+            Err(other)                  => Err(other)
+        }
+    }
+
+    ///
+    /// Retrieves the highest asset ID that has not been assigned yet
+    ///
+    fn read_highest_unused_asset_id(&mut self) -> Result<Vec<StorageResponse>, rusqlite::Error> {
+        let mut read    = self.connection.prepare_cached("SELECT COALESCE(MAX(AssetId)+1, 0) FROM Assets;")?;
+        let next_id     = read.query_row(NO_PARAMS, |row| row.get::<_, i64>(0))?;
+
+        Ok(vec![StorageResponse::HighestUnusedAssetId(next_id as u64)])
+    }
+
+    ///
+    /// Adds a new symbol or updates its properties
+    ///
+    fn write_symbol(&mut self, symbol_id: u64, properties: String) -> Result<Vec<StorageResponse>, rusqlite::Error> {
+        let mut write   = self.connection.prepare_cached("INSERT OR REPLACE INTO Symbols (SymbolId, Symbol) VALUES (?, ?);")?;
+        write.execute(params![symbol_id as i64, properties])?;
+
+        Ok(vec![StorageResponse::Updated])
+    }
+
+    ///
+    /// Removes a symbol from the database
+    ///
+    fn delete_symbol(&mut self, symbol_id: u64) -> Result<Vec<StorageResponse>, rusqlite::Error> {
+        let mut delete  = self.connection.prepare_cached("DELETE FROM Symbols WHERE SymbolId = ?;")?;
+        delete.execute(&[symbol_id as i64])?;
+
+        Ok(vec![StorageResponse::Updated])
+    }
+
+    ///
+    /// Reads the properties of all of the symbols
+    ///
+    fn read_symbols(&mut self) -> Result<Vec<StorageResponse>, rusqlite::Error> {
+        let mut read    = self.connection.prepare_cached("SELECT SymbolId, Symbol FROM Symbols ORDER BY SymbolId ASC;")?;
+        let symbols     = read.query_map(NO_PARAMS, |row| Ok(StorageResponse::Symbol(row.get::<_, i64>(0)? as u64, row.get(1)?)))?;
+
+        Ok(symbols.collect::<Result<_, _>>()?)
+    }
 }
@@ -1,4 +1,5 @@
 use flo_animation::storage::*;
+use flo_animation::serializer::{SerializedData};
 
 use rusqlite;
 use super::sqlite_core::*;
@@ -51,24 +52,84 @@ fn write_two_edits() {
     core.initialize().unwrap();
 
     assert!(core.run_commands(vec![
-            StorageCommand::WriteEdit("Test1".to_string()), 
-            StorageCommand::WriteEdit("Test2".to_string())
+            StorageCommand::WriteEdit("Test1".into()), 
+            StorageCommand::WriteEdit("Test2".into())
         ]) == vec![StorageResponse::Updated, StorageResponse::Updated]);
 
     assert!(core.run_commands(vec![StorageCommand::ReadEditLogLength]) == vec![StorageResponse::NumberOfEdits(2)]);
 }
 
+#[test]
+fn replace_edit_log() {
+    let mut core    = SqliteCore::new(rusqlite::Connection::open_in_memory().unwrap());
+    core.initialize().unwrap();
+
+    core.run_commands(vec![
+        StorageCommand::WriteEdit("Test1".into()), 
+        StorageCommand::WriteEdit("Test2".into()),
+        StorageCommand::WriteEdit("Test3".into())
+    ]);
+
+    assert!(core.run_commands(vec![StorageCommand::ReplaceEditLog(3, vec!["Test4".into()])]) == vec![StorageResponse::Updated]);
+    assert!(core.run_commands(vec![StorageCommand::ReadEditLogLength]) == vec![StorageResponse::NumberOfEdits(1)]);
+    assert!(core.run_commands(vec![StorageCommand::ReadEdits(0..1)]) == vec![StorageResponse::Edit(0, "Test4".into())]);
+}
+
+#[test]
+fn replace_edit_log_keeps_later_edits() {
+    let mut core    = SqliteCore::new(rusqlite::Connection::open_in_memory().unwrap());
+    core.initialize().unwrap();
+
+    core.run_commands(vec![
+        StorageCommand::WriteEdit("Test1".into()), 
+        StorageCommand::WriteEdit("Test2".into()),
+        StorageCommand::WriteEdit("Test3".into())
+    ]);
+
+    assert!(core.run_commands(vec![StorageCommand::ReplaceEditLog(2, vec!["Test4".into()])]) == vec![StorageResponse::Updated]);
+    assert!(core.run_commands(vec![StorageCommand::ReadEditLogLength]) == vec![StorageResponse::NumberOfEdits(2)]);
+    assert!(core.run_commands(vec![StorageCommand::ReadEdits(0..2)]) == vec![StorageResponse::Edit(0, "Test4".into()), StorageResponse::Edit(1, "Test3".into())]);
+}
+
+#[test]
+fn write_and_delete_snapshots() {
+    let mut core    = SqliteCore::new(rusqlite::Connection::open_in_memory().unwrap());
+    core.initialize().unwrap();
+
+    core.run_commands(vec![
+        StorageCommand::WriteSnapshot("Second".to_string(), 2),
+        StorageCommand::WriteSnapshot("First".to_string(), 1),
+        StorageCommand::WriteSnapshot("Removed".to_string(), 3)
+    ]);
+    core.run_commands(vec![StorageCommand::DeleteSnapshot("Removed".to_string())]);
+
+    assert!(core.run_commands(vec![StorageCommand::ReadSnapshots]) == vec![StorageResponse::Snapshot("First".to_string(), 1), StorageResponse::Snapshot("Second".to_string(), 2)]);
+}
+
 #[test]
 fn read_all_edits() {
     let mut core    = SqliteCore::new(rusqlite::Connection::open_in_memory().unwrap());
     core.initialize().unwrap();
 
     assert!(core.run_commands(vec![
-            StorageCommand::WriteEdit("Test1".to_string()), 
-            StorageCommand::WriteEdit("Test2".to_string())
+            StorageCommand::WriteEdit("Test1".into()), 
+            StorageCommand::WriteEdit("Test2".into())
+        ]) == vec![StorageResponse::Updated, StorageResponse::Updated]);
+
+    assert!(core.run_commands(vec![StorageCommand::ReadEdits(0..2)]) == vec![StorageResponse::Edit(0, "Test1".into()), StorageResponse::Edit(1, "Test2".into())]);
+}
+
+#[test]
+fn read_text_and_binary_edits() {
+    let mut core    = SqliteCore::new(rusqlite::Connection::open_in_memory().unwrap());
+    core.initialize().unwrap();
+
+    assert!(core.run_commands(vec![
+            StorageCommand::WriteEdit("Test1".into()), 
+            StorageCommand::WriteEdit(vec![0u8, 1u8, 255u8].into())
         ]) == vec![StorageResponse::Updated, StorageResponse::Updated]);
 
-    assert!(core.run_commands(vec![StorageCommand::ReadEdits(0..2)]) == vec![StorageResponse::Edit(0, "Test1".to_string()), StorageResponse::Edit(1, "Test2".to_string())]);
+    assert!(core.run_commands(vec![StorageCommand::ReadEdits(0..2)]) == vec![StorageResponse::Edit(0, "Test1".into()), StorageResponse::Edit(1, vec![0u8, 1u8, 255u8].into())]);
 }
 
 #[test]
@@ -93,12 +154,26 @@ fn write_and_read_elements() {
     core.initialize().unwrap();
 
     assert!(core.run_commands(vec![
-            StorageCommand::WriteElement(1, "Test1".to_string()), 
-            StorageCommand::WriteElement(3, "Test2".to_string())
+            StorageCommand::WriteElement(1, "Test1".into()), 
+            StorageCommand::WriteElement(3, "Test2".into())
+        ]) == vec![StorageResponse::Updated, StorageResponse::Updated]);
+
+    assert!(core.run_commands(vec![StorageCommand::ReadElement(1), StorageCommand::ReadElement(3)]) == 
+        vec![StorageResponse::Element(1, "Test1".into()), StorageResponse::Element(3, "Test2".into())]);
+}
+
+#[test]
+fn write_and_read_binary_elements() {
+    let mut core    = SqliteCore::new(rusqlite::Connection::open_in_memory().unwrap());
+    core.initialize().unwrap();
+
+    assert!(core.run_commands(vec![
+            StorageCommand::WriteElement(1, vec![1u8, 2u8, 3u8].into()), 
+            StorageCommand::WriteElement(3, "Test2".into())
         ]) == vec![StorageResponse::Updated, StorageResponse::Updated]);
 
     assert!(core.run_commands(vec![StorageCommand::ReadElement(1), StorageCommand::ReadElement(3)]) == 
-        vec![StorageResponse::Element(1, "Test1".to_string()), StorageResponse::Element(3, "Test2".to_string())]);
+        vec![StorageResponse::Element(1, vec![1u8, 2u8, 3u8].into()), StorageResponse::Element(3, "Test2".into())]);
 }
 
 #[test]
@@ -107,13 +182,13 @@ fn write_and_delete_elements() {
     core.initialize().unwrap();
 
     assert!(core.run_commands(vec![
-            StorageCommand::WriteElement(1, "Test1".to_string()), 
-            StorageCommand::WriteElement(3, "Test2".to_string()),
+            StorageCommand::WriteElement(1, "Test1".into()), 
+            StorageCommand::WriteElement(3, "Test2".into()),
             StorageCommand::DeleteElement(3)
         ]) == vec![StorageResponse::Updated, StorageResponse::Updated, StorageResponse::Updated]);
 
     assert!(core.run_commands(vec![StorageCommand::ReadElement(1), StorageCommand::ReadElement(3)]) == 
-        vec![StorageResponse::Element(1, "Test1".to_string()), StorageResponse::NotFound]);
+        vec![StorageResponse::Element(1, "Test1".into()), StorageResponse::NotFound]);
 }
 
 #[test]
@@ -361,8 +436,8 @@ fn attach_element_to_keyframe() {
             StorageCommand::AddKeyFrame(1, Duration::from_millis(420)),
             StorageCommand::AddKeyFrame(1, Duration::from_millis(500)),
 
-            StorageCommand::WriteElement(1, "Test1".to_string()),
-            StorageCommand::WriteElement(2, "Test2".to_string()),
+            StorageCommand::WriteElement(1, "Test1".into()),
+            StorageCommand::WriteElement(2, "Test2".into()),
 
             StorageCommand::AttachElementToLayer(1, 1, Duration::from_millis(420)),
             StorageCommand::AttachElementToLayer(1, 2, Duration::from_millis(420)),
@@ -370,8 +445,8 @@ fn attach_element_to_keyframe() {
 
     assert!(core.run_commands(vec![StorageCommand::ReadElementsForKeyFrame(1, Duration::from_millis(420))]) ==
         vec![
-            StorageResponse::Element(1, "Test1".to_string()),
-            StorageResponse::Element(2, "Test2".to_string()),
+            StorageResponse::Element(1, "Test1".into()),
+            StorageResponse::Element(2, "Test2".into()),
         ]);
 }
 
@@ -386,8 +461,8 @@ fn attach_element_to_several_keyframes() {
             StorageCommand::AddKeyFrame(1, Duration::from_millis(420)),
             StorageCommand::AddKeyFrame(1, Duration::from_millis(500)),
 
-            StorageCommand::WriteElement(1, "Test1".to_string()),
-            StorageCommand::WriteElement(2, "Test2".to_string()),
+            StorageCommand::WriteElement(1, "Test1".into()),
+            StorageCommand::WriteElement(2, "Test2".into()),
 
             StorageCommand::AttachElementToLayer(1, 1, Duration::from_millis(420)),
             StorageCommand::AttachElementToLayer(1, 2, Duration::from_millis(420)),
@@ -397,13 +472,13 @@ fn attach_element_to_several_keyframes() {
 
     assert!(core.run_commands(vec![StorageCommand::ReadElementsForKeyFrame(1, Duration::from_millis(420))]) ==
         vec![
-            StorageResponse::Element(1, "Test1".to_string()),
-            StorageResponse::Element(2, "Test2".to_string()),
+            StorageResponse::Element(1, "Test1".into()),
+            StorageResponse::Element(2, "Test2".into()),
         ]);
 
     assert!(core.run_commands(vec![StorageCommand::ReadElementsForKeyFrame(1, Duration::from_millis(500))]) ==
         vec![
-            StorageResponse::Element(2, "Test2".to_string()),
+            StorageResponse::Element(2, "Test2".into()),
         ]);
 }
 
@@ -418,8 +493,8 @@ fn double_attach_element_to_keyframe() {
             StorageCommand::AddKeyFrame(1, Duration::from_millis(420)),
             StorageCommand::AddKeyFrame(1, Duration::from_millis(500)),
 
-            StorageCommand::WriteElement(1, "Test1".to_string()),
-            StorageCommand::WriteElement(2, "Test2".to_string()),
+            StorageCommand::WriteElement(1, "Test1".into()),
+            StorageCommand::WriteElement(2, "Test2".into()),
 
             StorageCommand::AttachElementToLayer(1, 1, Duration::from_millis(420)),
             StorageCommand::AttachElementToLayer(1, 2, Duration::from_millis(420)),
@@ -430,8 +505,8 @@ fn double_attach_element_to_keyframe() {
 
     assert!(core.run_commands(vec![StorageCommand::ReadElementsForKeyFrame(1, Duration::from_millis(420))]) ==
         vec![
-            StorageResponse::Element(1, "Test1".to_string()),
-            StorageResponse::Element(2, "Test2".to_string()),
+            StorageResponse::Element(1, "Test1".into()),
+            StorageResponse::Element(2, "Test2".into()),
         ]);
 }
 
@@ -446,8 +521,8 @@ fn detach_element() {
             StorageCommand::AddKeyFrame(1, Duration::from_millis(420)),
             StorageCommand::AddKeyFrame(1, Duration::from_millis(500)),
 
-            StorageCommand::WriteElement(1, "Test1".to_string()),
-            StorageCommand::WriteElement(2, "Test2".to_string()),
+            StorageCommand::WriteElement(1, "Test1".into()),
+            StorageCommand::WriteElement(2, "Test2".into()),
 
             StorageCommand::AttachElementToLayer(1, 1, Duration::from_millis(420)),
             StorageCommand::AttachElementToLayer(1, 2, Duration::from_millis(420)),
@@ -461,7 +536,7 @@ fn detach_element() {
 
     assert!(core.run_commands(vec![StorageCommand::ReadElementsForKeyFrame(1, Duration::from_millis(420))]) ==
         vec![
-            StorageResponse::Element(1, "Test1".to_string()),
+            StorageResponse::Element(1, "Test1".into()),
         ]);
 
     assert!(core.run_commands(vec![StorageCommand::ReadElementsForKeyFrame(1, Duration::from_millis(500))]) ==
@@ -480,8 +555,8 @@ fn read_element_attachments() {
             StorageCommand::AddKeyFrame(1, Duration::from_millis(420)),
             StorageCommand::AddKeyFrame(1, Duration::from_millis(500)),
 
-            StorageCommand::WriteElement(1, "Test1".to_string()),
-            StorageCommand::WriteElement(2, "Test2".to_string()),
+            StorageCommand::WriteElement(1, "Test1".into()),
+            StorageCommand::WriteElement(2, "Test2".into()),
 
             StorageCommand::AttachElementToLayer(1, 1, Duration::from_millis(420)),
             StorageCommand::AttachElementToLayer(1, 2, Duration::from_millis(420)),
@@ -586,3 +661,170 @@ fn delete_from_layer_cache() {
     assert!(core.run_commands(vec![StorageCommand::ReadLayerCache(1, Duration::from_millis(500), "Type".to_string())]) ==
         vec![StorageResponse::LayerCache("Cache2".to_string())]);
 }
+
+#[test]
+fn upgrade_initialized_database() {
+    let mut core    = SqliteCore::new(rusqlite::Connection::open_in_memory().unwrap());
+    core.initialize().unwrap();
+
+    assert!(core.upgrade().is_ok());
+}
+
+#[test]
+fn read_reference_images() {
+    let mut core    = SqliteCore::new(rusqlite::Connection::open_in_memory().unwrap());
+    core.initialize().unwrap();
+
+    assert!(core.run_commands(vec![
+            StorageCommand::AddLayer(1, "Test1".to_string()), 
+            StorageCommand::WriteReferenceImage(1, Duration::from_millis(0), "Image1".to_string()),
+            StorageCommand::WriteReferenceImage(1, Duration::from_millis(500), "Image2".to_string())
+        ]) == vec![StorageResponse::Updated, StorageResponse::Updated, StorageResponse::Updated]);
+
+    assert!(core.run_commands(vec![StorageCommand::ReadReferenceImage(1, Duration::from_millis(0))]) ==
+        vec![StorageResponse::ReferenceImage(Duration::from_millis(0), "Image1".to_string())]);
+    assert!(core.run_commands(vec![StorageCommand::ReadReferenceImage(1, Duration::from_millis(420))]) ==
+        vec![StorageResponse::ReferenceImage(Duration::from_millis(0), "Image1".to_string())]);
+    assert!(core.run_commands(vec![StorageCommand::ReadReferenceImage(1, Duration::from_millis(750))]) ==
+        vec![StorageResponse::ReferenceImage(Duration::from_millis(500), "Image2".to_string())]);
+    assert!(core.run_commands(vec![StorageCommand::ReadReferenceImage(2, Duration::from_millis(750))]) ==
+        vec![StorageResponse::NotFound]);
+}
+
+#[test]
+fn delete_reference_image() {
+    let mut core    = SqliteCore::new(rusqlite::Connection::open_in_memory().unwrap());
+    core.initialize().unwrap();
+
+    assert!(core.run_commands(vec![
+            StorageCommand::AddLayer(1, "Test1".to_string()), 
+            StorageCommand::WriteReferenceImage(1, Duration::from_millis(0), "Image1".to_string()),
+            StorageCommand::WriteReferenceImage(1, Duration::from_millis(500), "Image2".to_string()),
+            StorageCommand::DeleteReferenceImage(1, Duration::from_millis(500))
+        ]) == vec![StorageResponse::Updated, StorageResponse::Updated, StorageResponse::Updated, StorageResponse::Updated]);
+
+    assert!(core.run_commands(vec![StorageCommand::ReadReferenceImage(1, Duration::from_millis(750))]) ==
+        vec![StorageResponse::ReferenceImage(Duration::from_millis(0), "Image1".to_string())]);
+}
+
+#[test]
+fn delete_missing_reference_image() {
+    let mut core    = SqliteCore::new(rusqlite::Connection::open_in_memory().unwrap());
+    core.initialize().unwrap();
+
+    assert!(core.run_commands(vec![
+            StorageCommand::AddLayer(1, "Test1".to_string()), 
+            StorageCommand::WriteReferenceImage(1, Duration::from_millis(0), "Image1".to_string()),
+            StorageCommand::DeleteReferenceImage(1, Duration::from_millis(500)),
+            StorageCommand::DeleteReferenceImage(2, Duration::from_millis(0))
+        ]) == vec![StorageResponse::Updated, StorageResponse::Updated, StorageResponse::NotFound, StorageResponse::NotFound]);
+}
+
+#[test]
+fn write_and_read_assets() {
+    let mut core    = SqliteCore::new(rusqlite::Connection::open_in_memory().unwrap());
+    core.initialize().unwrap();
+
+    assert!(core.run_commands(vec![StorageCommand::ReadHighestUnusedAssetId]) == vec![StorageResponse::HighestUnusedAssetId(0)]);
+
+    assert!(core.run_commands(vec![
+            StorageCommand::WriteAsset(0, "Asset0".to_string()),
+            StorageCommand::WriteAsset(3, "Asset3".to_string())
+        ]) == vec![StorageResponse::Updated, StorageResponse::Updated]);
+
+    assert!(core.run_commands(vec![StorageCommand::ReadAsset(3)]) == vec![StorageResponse::Asset(3, "Asset3".to_string())]);
+    assert!(core.run_commands(vec![StorageCommand::ReadAsset(1)]) == vec![StorageResponse::NotFound]);
+    assert!(core.run_commands(vec![StorageCommand::ReadHighestUnusedAssetId]) == vec![StorageResponse::HighestUnusedAssetId(4)]);
+}
+
+#[test]
+fn write_and_read_symbols() {
+    let mut core    = SqliteCore::new(rusqlite::Connection::open_in_memory().unwrap());
+    core.initialize().unwrap();
+
+    assert!(core.run_commands(vec![
+            StorageCommand::WriteSymbol(2, "Symbol2".to_string()),
+            StorageCommand::WriteSymbol(1, "Symbol1".to_string()),
+            StorageCommand::WriteSymbol(2, "Symbol2b".to_string())
+        ]) == vec![StorageResponse::Updated, StorageResponse::Updated, StorageResponse::Updated]);
+
+    assert!(core.run_commands(vec![StorageCommand::ReadSymbols]) ==
+        vec![StorageResponse::Symbol(1, "Symbol1".to_string()), StorageResponse::Symbol(2, "Symbol2b".to_string())]);
+}
+
+#[test]
+fn delete_symbol() {
+    let mut core    = SqliteCore::new(rusqlite::Connection::open_in_memory().unwrap());
+    core.initialize().unwrap();
+
+    core.run_commands(vec![
+        StorageCommand::WriteSymbol(1, "Symbol1".to_string()),
+        StorageCommand::WriteSymbol(2, "Symbol2".to_string())
+    ]);
+    core.run_commands(vec![StorageCommand::DeleteSymbol(1)]);
+
+    assert!(core.run_commands(vec![StorageCommand::ReadSymbols]) == vec![StorageResponse::Symbol(2, "Symbol2".to_string())]);
+}
+
+#[test]
+fn failed_batch_is_rolled_back() {
+    // Create a database where writing elements will fail
+    let connection  = rusqlite::Connection::open_in_memory().unwrap();
+    connection.execute_batch(include_str!("../sql/flo_storage.sql")).unwrap();
+    connection.execute_batch("DROP TABLE Elements;").unwrap();
+
+    let mut core    = SqliteCore::new(connection);
+
+    let result      = core.run_commands(vec![
+            StorageCommand::WriteEdit("Test1".into()),
+            StorageCommand::WriteElement(1, "Element".into())
+        ]);
+    assert!(result.len() == 1);
+    assert!(match result[0] { StorageResponse::Error(StorageError::General, _) => true, _ => false });
+
+    // The edit should not have been written, and the core should be able to carry on
+    assert!(core.run_commands(vec![StorageCommand::ReadEditLogLength]) == vec![StorageResponse::NumberOfEdits(0)]);
+    assert!(core.run_commands(vec![StorageCommand::WriteEdit("Test2".into())]) == vec![StorageResponse::Updated]);
+    assert!(core.run_commands(vec![StorageCommand::ReadEdits(0..1)]) == vec![StorageResponse::Edit(0, "Test2".into())]);
+}
+
+#[test]
+fn integrity_check_finds_missing_element() {
+    let mut core    = SqliteCore::new(rusqlite::Connection::open_in_memory().unwrap());
+    core.initialize().unwrap();
+
+    core.run_commands(vec![
+            StorageCommand::AddLayer(1, "Test1".to_string()), 
+            StorageCommand::AddKeyFrame(1, Duration::from_millis(0)),
+            StorageCommand::WriteElement(42, "Element".into()),
+            StorageCommand::AttachElementToLayer(1, 42, Duration::from_millis(0))
+        ]);
+    assert!(core.check_integrity().unwrap() == Vec::<String>::new());
+
+    // Attach an element that doesn't exist
+    core.run_commands(vec![StorageCommand::AttachElementToLayer(1, 43, Duration::from_millis(0))]);
+    assert!(core.check_integrity().unwrap().len() == 1);
+}
+
+#[test]
+fn abandoned_rebuild_keeps_existing_data() {
+    let mut core    = SqliteCore::new(rusqlite::Connection::open_in_memory().unwrap());
+    core.initialize().unwrap();
+
+    core.run_commands(vec![
+            StorageCommand::WriteEdit("Test1".into()),
+            StorageCommand::WriteElement(42, "Element".into())
+        ]);
+
+    // Starting a rebuild returns the edit log and clears the derived tables
+    assert!(core.begin_rebuild().unwrap() == vec![SerializedData::from("Test1")]);
+    assert!(core.run_commands(vec![StorageCommand::ReadElement(42)]) == vec![StorageResponse::NotFound]);
+
+    // Edits written during the rebuild are not added to the log
+    core.run_commands(vec![StorageCommand::WriteEdit("Test1".into())]);
+    assert!(core.run_commands(vec![StorageCommand::ReadEditLogLength]) == vec![StorageResponse::NumberOfEdits(1)]);
+
+    // Abandoning the rebuild restores the original data
+    assert!(core.finish_rebuild(false).unwrap() == false);
+    assert!(core.run_commands(vec![StorageCommand::ReadElement(42)]) == vec![StorageResponse::Element(42, "Element".into())]);
+}
@@ -6,11 +6,34 @@ use flo_animation::storage::*;
 use futures::prelude::*;
 use rusqlite::{Connection, OpenFlags};
 
+use std::path::Path;
+
+///
+/// Loads animations stored in SQLite files
+///
+pub struct SqliteAnimationLoader;
+
 ///
 /// Creates a loader for loading animations stored in SQLite files
 ///
 pub fn sqlite_animation_loader() -> impl FileAnimation {
-    AnimationLoader(|path| {
+    SqliteAnimationLoader
+}
+
+///
+/// Creates an editable animation for some SQLite storage
+///
+fn animation_for_storage(storage: SqliteAnimationStorage) -> StreamAnimation {
+    StreamAnimation::new(move |commands| storage.get_responses(commands).boxed())
+}
+
+impl FileAnimation for SqliteAnimationLoader {
+    type NewAnimation = StreamAnimation;
+
+    ///
+    /// Opens an animation from a file on disk
+    ///
+    fn open(&self, path: &Path) -> StreamAnimation {
         // Connect to the database
         let opening_existing = path.exists();
 
@@ -29,7 +52,7 @@ pub fn sqlite_animation_loader() -> impl FileAnimation {
         };
 
         // Create the editor for this animation
-        let editor      = create_animation_editor(move |commands| storage.get_responses(commands).boxed());
+        let editor      = animation_for_storage(storage);
 
         if !opening_existing {
             // Set up a default animation
@@ -41,5 +64,24 @@ pub fn sqlite_animation_loader() -> impl FileAnimation {
         }
 
         editor
-    })
+    }
+
+    ///
+    /// Creates a new animation at the `target` path from the first `until` edits of the animation at the `source` path
+    ///
+    fn fork(&self, source: &Path, target: &Path, until: usize) -> Option<StreamAnimation> {
+        let source_storage  = SqliteAnimationStorage::open_file(source).ok()?;
+        let forked_storage  = source_storage.fork(target, until).ok()?;
+
+        Some(animation_for_storage(forked_storage))
+    }
+
+    ///
+    /// Creates a new animation at the specified path with no edits
+    ///
+    fn create_empty(&self, path: &Path) -> Option<StreamAnimation> {
+        let storage = SqliteAnimationStorage::new_with_file(path).ok()?;
+
+        Some(animation_for_storage(storage))
+    }
 }
@@ -2,13 +2,22 @@ use super::sqlite_core::*;
 
 use flo_animation::storage::*;
 
+use flo_animation::*;
+
 use ::desync::*;
 use rusqlite;
 use futures::*;
+use futures::channel::{oneshot};
 
+use std::thread;
 use std::sync::*;
 use std::path::{Path};
 
+///
+/// A batch of storage commands sent by the editor that replays the edit log during a rebuild, and where to send the responses
+///
+type ReplayRequest = (Vec<StorageCommand>, oneshot::Sender<Vec<StorageResponse>>);
+
 ///
 /// Stores an animation using a SQLite database
 ///
@@ -45,6 +54,9 @@ impl SqliteAnimationStorage {
         let core    = SqliteCore::new(connection);
         let core    = Arc::new(Desync::new(core));
 
+        // Add any tables that are missing from older files
+        core.desync(|core| { core.upgrade().ok(); });
+
         // Create the storage object
         SqliteAnimationStorage {
             core:   core
@@ -82,4 +94,120 @@ impl SqliteAnimationStorage {
             future::ready(core.run_commands(commands)).boxed()
         })
     }
+
+    ///
+    /// Checks the tables derived from the edit log for consistency, returning a description of each problem that was found
+    ///
+    pub fn check_integrity(&self) -> Result<Vec<String>, rusqlite::Error> {
+        self.core.sync(|core| core.check_integrity())
+    }
+
+    ///
+    /// Rebuilds everything in this animation apart from the edit log by replaying the edits in the log
+    ///
+    /// The rebuild is performed as a single transaction, so the existing data is left intact if it fails. The
+    /// result is the number of edits that were replayed.
+    ///
+    /// The core is held for the whole of the rebuild: commands sent to this storage by anything else wait until
+    /// the rebuild has finished, so edits made while the rebuild is running are added to the log afterwards.
+    ///
+    pub fn rebuild_from_edit_log(&self) -> Result<usize, (StorageError, String)> {
+        self.core.sync(|core| Self::rebuild_core(core))
+    }
+
+    ///
+    /// Rebuilds the derived tables in a core by replaying its edit log
+    ///
+    fn rebuild_core(core: &mut SqliteCore) -> Result<usize, (StorageError, String)> {
+        // Read the edit log and clear the existing data
+        let edits       = core.begin_rebuild()
+            .map_err(|err| (StorageError::General, err.to_string()))?;
+
+        // Deserialize all of the edits before we start replaying them
+        let mut parsed_edits = Vec::with_capacity(edits.len());
+        for (edit_num, edit) in edits.iter().enumerate() {
+            match AnimationEdit::deserialize_data(edit) {
+                Some(edit)  => parsed_edits.push(edit),
+                None        => {
+                    core.finish_rebuild(false).ok();
+                    return Err((StorageError::General, format!("Edit {} could not be deserialized", edit_num)));
+                }
+            }
+        }
+
+        // Replay the edits on another thread, using an editor that sends its storage commands back to this thread
+        let num_edits                           = parsed_edits.len();
+        let (send_request, receive_request)     = mpsc::channel::<Option<ReplayRequest>>();
+        let editor_requests                     = send_request.clone();
+
+        let replay = thread::spawn(move || {
+            let editor = create_animation_editor(move |commands| {
+                commands.then(move |commands| {
+                    let (send_responses, responses) = oneshot::channel();
+                    editor_requests.send(Some((commands, send_responses))).ok();
+
+                    responses.map(|responses| responses.unwrap_or_else(|_| vec![]))
+                }).boxed()
+            });
+
+            editor.perform_edits(parsed_edits);
+
+            // Signal that the replay has finished
+            send_request.send(None).ok();
+        });
+
+        // Run the commands from the replay until it finishes
+        while let Ok(Some((commands, send_responses))) = receive_request.recv() {
+            send_responses.send(core.run_commands(commands)).ok();
+        }
+
+        if replay.join().is_err() {
+            core.finish_rebuild(false).ok();
+            return Err((StorageError::General, "The edit log could not be replayed".to_string()));
+        }
+
+        // Commit the result if nothing went wrong during the replay
+        match core.finish_rebuild(true) {
+            Ok(true)    => Ok(num_edits),
+            Ok(false)   => Err((StorageError::General, "A storage command failed while replaying the edit log".to_string())),
+            Err(err)    => Err((StorageError::CannotContinueAfterError, err.to_string()))
+        }
+    }
+
+    ///
+    /// Creates a new animation at the specified path from the first `until` edits of this animation
+    ///
+    /// This is used to branch an animation from one of its snapshots: any snapshots that are within the
+    /// copied part of the edit log are copied to the new animation too.
+    ///
+    pub fn fork(&self, path: &Path, until: usize) -> Result<SqliteAnimationStorage, (StorageError, String)> {
+        // Read the edits and the snapshots from this animation
+        let responses   = self.core.sync(move |core| core.run_commands(vec![StorageCommand::ReadEdits(0..until), StorageCommand::ReadSnapshots]));
+
+        let mut commands = vec![];
+        for response in responses {
+            match response {
+                StorageResponse::Edit(_, edit)                                      => commands.push(StorageCommand::WriteEdit(edit)),
+                StorageResponse::Snapshot(name, edit_index) if edit_index <= until  => commands.push(StorageCommand::WriteSnapshot(name, edit_index)),
+                StorageResponse::Snapshot(_, _)                                     => { }
+                StorageResponse::Error(err, msg)                                    => return Err((err, msg)),
+                _                                                                   => { }
+            }
+        }
+
+        // Create the new animation and write the edits to it
+        let forked      = Self::new_with_file(path).map_err(|err| (StorageError::General, err.to_string()))?;
+        let responses   = forked.core.sync(move |core| core.run_commands(commands));
+
+        for response in responses {
+            if let StorageResponse::Error(err, msg) = response {
+                return Err((err, msg));
+            }
+        }
+
+        // Generate the rest of the file by replaying the edits
+        forked.rebuild_from_edit_log()?;
+
+        Ok(forked)
+    }
 }
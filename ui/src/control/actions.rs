@@ -34,7 +34,13 @@ pub enum ActionTrigger {
     CancelEdit,
 
     /// Divides a scrollable region into a grid, and generates an event whenever the region in the top-left corner changes
-    VirtualScroll(f32, f32)
+    VirtualScroll(f32, f32),
+
+    /// Tracks scroll wheel (and trackpad scroll) events over this item
+    ScrollWheel,
+
+    /// Tracks pinch (zoom and rotate) gestures over this item
+    Pinch
 }
 
 ///
@@ -48,6 +54,24 @@ pub enum DragAction {
     Cancel  = 3
 }
 
+///
+/// The modifier keys that were held down when an action occurred
+///
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Debug, Default)]
+pub struct ModifierKeys {
+    /// The shift key was held down
+    pub shift: bool,
+
+    /// The control key was held down
+    pub control: bool,
+
+    /// The alt (or option) key was held down
+    pub alt: bool,
+
+    /// The command (or meta) key was held down
+    pub command: bool
+}
+
 ///
 /// Data that can be sent alongside an action
 ///
@@ -59,8 +83,9 @@ pub enum ActionParameter {
     /// Painting information
     Paint(PaintDevice, Vec<Painting>),
 
-    /// Item drag action. Coordinates are relative to a fixed point during a drag action
-    Drag(DragAction, (f32, f32), (f32, f32)),
+    /// Item drag action. Coordinates are relative to a fixed point during a drag action, followed by the modifier keys
+    /// that are held down
+    Drag(DragAction, (f32, f32), (f32, f32), ModifierKeys),
 
     /// The new value for an item
     Value(PropertyValue),
@@ -73,5 +98,15 @@ pub enum ActionParameter {
     /// of 3, 2 in the second would indicate that the client area of the scroll
     /// region is 1536x1024 (ie, you need to draw 3 512x512 squares horizontally
     /// and 2 vertically in order to cover everything the user can currently see)
-    VirtualScroll((u32, u32), (u32, u32))
+    VirtualScroll((u32, u32), (u32, u32)),
+
+    /// The scroll wheel was moved. The first pair is the position of the pointer and the second is the
+    /// amount that was scrolled horizontally and vertically. For canvases, the position is in canvas
+    /// coordinates.
+    ScrollWheel((f32, f32), (f32, f32)),
+
+    /// A pinch gesture has updated. The first pair is the center of the gesture, then the change in scale
+    /// (as a factor) and the change in rotation (in radians) since the last update. For canvases, the
+    /// position is in canvas coordinates.
+    Pinch((f32, f32), f32, f32)
 }
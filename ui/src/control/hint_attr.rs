@@ -12,7 +12,10 @@ pub enum Hint {
     FastDrawing,
 
     /// Provides a class for this control (modifying its behaviour or appearance)
-    Class(String)
+    Class(String),
+
+    /// When this control is clicked, the specified text should be copied to the clipboard
+    CopyOnClick(String)
 }
 
 impl Modifier<Control> for Hint {
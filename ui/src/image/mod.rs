@@ -1,3 +1,5 @@
+use flo_canvas::*;
+
 use futures::stream::{BoxStream};
 use bytes::Bytes;
 
@@ -34,6 +36,28 @@ impl Image {
     pub fn png_from_rgba_data(rgba: &[u8], width: u32, height: u32) -> Image {
         Image::Png(Arc::new(png::png_data_for_rgba(rgba, width, height)))
     }
+
+    ///
+    /// Decodes this image to RGBA pixels, returning the width, height and pixel data (or None if the image is not in a bitmap format)
+    ///
+    pub fn to_rgba_data(&self) -> Option<(u32, u32, Vec<u8>)> {
+        match self {
+            Image::Png(data)    => png::rgba_data_for_png(data.read()),
+            Image::Svg(_)       => None
+        }
+    }
+
+    ///
+    /// Returns the canvas instructions to create a texture containing this image
+    ///
+    pub fn texture_drawing(&self, texture_id: TextureId) -> Option<Vec<Draw>> {
+        let (width, height, rgba) = self.to_rgba_data()?;
+
+        Some(vec![
+            Draw::Texture(texture_id, TextureOp::Create(width, height, TextureFormat::Rgba)),
+            Draw::Texture(texture_id, TextureOp::SetBytes(0, 0, width, height, Arc::new(rgba)))
+        ])
+    }
 }
 
 mod inmemory;
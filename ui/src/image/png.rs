@@ -25,3 +25,44 @@ pub fn png_data_for_rgba(rgba: &[u8], width: u32, height: u32) -> InMemoryImageD
     // Generate the image data object for the final PNG
     InMemoryImageData::from(png_data)
 }
+
+///
+/// Decodes PNG data into an RGBA buffer, returning the width, height and pixels of the image
+///
+pub fn rgba_data_for_png<R: Read>(png_data: R) -> Option<(u32, u32, Vec<u8>)> {
+    // Decode to 8 bits per channel, expanding any palette
+    let mut png_decoder = png::Decoder::new(png_data);
+    png_decoder.set_transformations(png::Transformations::EXPAND | png::Transformations::STRIP_16);
+
+    let (info, mut png_reader)  = png_decoder.read_info().ok()?;
+    let mut pixels              = vec![0; info.buffer_size()];
+    png_reader.next_frame(&mut pixels).ok()?;
+
+    // Convert the pixels to RGBA
+    let rgba = match info.color_type {
+        png::ColorType::RGBA            => pixels,
+        png::ColorType::RGB             => pixels.chunks(3).flat_map(|rgb| vec![rgb[0], rgb[1], rgb[2], 255]).collect(),
+        png::ColorType::GrayscaleAlpha  => pixels.chunks(2).flat_map(|ga| vec![ga[0], ga[0], ga[0], ga[1]]).collect(),
+        png::ColorType::Grayscale       => pixels.into_iter().flat_map(|g| vec![g, g, g, 255]).collect(),
+
+        // Indexed images should have been expanded by the decoder
+        png::ColorType::Indexed         => { return None; }
+    };
+
+    Some((info.width, info.height, rgba))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn decode_rgba_png() {
+        let rgba        = vec![255, 0, 0, 255,  0, 255, 0, 128,  0, 0, 255, 0,  1, 2, 3, 4];
+        let png_data    = png_data_for_rgba(&rgba, 2, 2);
+
+        let decoded     = rgba_data_for_png(png_data.read());
+
+        assert!(decoded == Some((2, 2, rgba)));
+    }
+}
@@ -3,6 +3,7 @@ use super::file_update::*;
 use futures::stream::{BoxStream};
 
 use std::path::{Path, PathBuf};
+use std::collections::HashSet;
 
 ///
 /// The file manager is used to retrieve what files are available and organize them
@@ -45,4 +46,22 @@ pub trait FileManager : Send+Sync {
     /// Returns a stream of updates indicating changes made to the file manager
     ///
     fn update_stream(&self) -> BoxStream<'static, FileUpdate>;
+
+    ///
+    /// Returns a display name based on `name` that isn't used by any of the existing files (eg, 'New file (1)')
+    ///
+    fn unused_display_name(&self, name: &str) -> String {
+        let all_files       = self.get_all_files();
+        let used_names      = all_files.into_iter().filter_map(|path| self.display_name_for_path(path.as_path())).collect::<HashSet<_>>();
+
+        let mut new_name    = String::from(name);
+        let mut name_index  = 0;
+
+        while used_names.contains(&new_name) {
+            name_index += 1;
+            new_name = format!("{} ({})", name, name_index);
+        }
+
+        new_name
+    }
 }
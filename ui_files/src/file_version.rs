@@ -0,0 +1,11 @@
+///
+/// Describes a named version of a file (for instance, a snapshot of an animation that can be branched from)
+///
+#[derive(Clone, PartialEq, Debug)]
+pub struct FileVersion {
+    /// The name of this version
+    pub name: String,
+
+    /// The number of edits from the file that are included in this version
+    pub edit_index: usize
+}
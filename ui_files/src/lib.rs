@@ -17,6 +17,7 @@ mod file_model;
 mod open_file_store;
 mod file_manager;
 mod file_update;
+mod file_version;
 pub mod ui;
 pub mod sqlite;
 
@@ -24,3 +25,4 @@ pub use self::file_model::*;
 pub use self::open_file_store::*;
 pub use self::file_manager::*;
 pub use self::file_update::*;
+pub use self::file_version::*;
@@ -19,6 +19,7 @@ use std::collections::HashMap;
 
 const FILES_DB: &str = "files.db";
 const DATA_DIR: &str = "data";
+const EXCHANGE_DIR: &str = "exchange";
 
 lazy_static! {
     // Exising file manager cores for particular application paths (and ensures only one can be being created at once)
@@ -129,6 +130,18 @@ impl SqliteFileManager {
         }
     }
 
+    ///
+    /// Retrieves the folder used for exchanging files with other applications (files are exported to this folder, and can be imported from it)
+    ///
+    pub fn exchange_folder(&self) -> PathBuf {
+        let mut exchange_path = self.root_path.clone();
+        exchange_path.push(EXCHANGE_DIR);
+
+        fs::create_dir_all(exchange_path.as_path()).ok();
+
+        exchange_path
+    }
+
     ///
     /// Retrieves the log for this file manager
     ///
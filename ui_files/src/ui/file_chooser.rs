@@ -1,13 +1,15 @@
 use super::file_controller::*;
 use super::super::file_manager::*;
+use super::super::file_version::*;
 use super::super::open_file_store::*;
 
 use std::sync::*;
+use std::path::{Path, PathBuf};
 
 ///
 /// The file chooser trait is implemented by structs that describe a file chooser
 ///
-pub trait FileChooser {
+pub trait FileChooser : Send+Sync {
     /// The controller that edits/displays open files
     type Controller: FileController;
 
@@ -23,4 +25,43 @@ pub trait FileChooser {
     /// Retrieves the shared file store for this chooser
     ///
     fn get_file_store(&self) -> Arc<OpenFileStore<<Self::Controller as FileController>::Model>>;
+
+    ///
+    /// Retrieves the named versions that are stored in the file at the specified path
+    ///
+    fn get_file_versions(&self, _path: &Path) -> Vec<FileVersion> {
+        vec![]
+    }
+
+    ///
+    /// Creates a new file from a version of an existing file, returning the path of the new file
+    ///
+    /// The new file should be created using the file manager, so that it appears in the list of files.
+    ///
+    fn fork_file_version(&self, _path: &Path, _version: &FileVersion) -> Option<PathBuf> {
+        None
+    }
+
+    ///
+    /// Writes the file at the specified path to a portable archive that can be imported elsewhere, returning the path of the archive
+    ///
+    /// If `compact` is true, the edit log of the file is compacted before it's written to the archive.
+    ///
+    fn export_file(&self, _path: &Path, _compact: bool) -> Option<PathBuf> {
+        None
+    }
+
+    ///
+    /// Retrieves the paths of the archives that are available to be imported
+    ///
+    fn get_importable_files(&self) -> Vec<PathBuf> {
+        vec![]
+    }
+
+    ///
+    /// Imports an archive as a new file, returning the path of the new file
+    ///
+    fn import_file(&self, _archive_path: &Path) -> Option<PathBuf> {
+        None
+    }
 }
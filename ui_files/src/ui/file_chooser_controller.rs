@@ -3,6 +3,7 @@ use super::file_chooser_model::*;
 use super::file_controller::*;
 use super::super::file_model::*;
 use super::super::file_manager::*;
+use super::super::file_version::*;
 use super::super::open_file_store::*;
 
 use flo_ui::*;
@@ -10,8 +11,7 @@ use flo_canvas::*;
 use flo_binding::*;
 
 use std::sync::*;
-use std::path::Path;
-use std::collections::HashSet;
+use std::path::{Path, PathBuf};
 
 const LOGO_HEIGHT: f32      = 256.0;
 const NUM_COLUMNS: u32      = 3;
@@ -39,6 +39,9 @@ pub struct FileChooserController<Chooser: FileChooser> {
     /// The background colour for the controller
     background_color: Binding<Color>,
 
+    /// The file chooser that this controller is displaying
+    chooser: Arc<Chooser>,
+
     /// The file manager used for finding the files to be displayed by this controller
     file_manager: Arc<Chooser::FileManager>,
 
@@ -52,13 +55,14 @@ impl<Chooser: FileChooser+'static> FileChooserController<Chooser> {
     ///
     pub fn new<LogoController: Controller+'static>(chooser: Chooser, logo_controller: LogoController) -> FileChooserController<Chooser> {
         let logo_controller     = Arc::new(logo_controller);
+        let chooser             = Arc::new(chooser);
 
         // Fetch the file manager and file store from the chooser
         let file_manager        = chooser.get_file_manager();
         let open_file_store     = chooser.get_file_store();
 
         // Create the model
-        let model               = FileChooserModel::new(&chooser);
+        let model               = FileChooserModel::new(&*chooser);
 
         // Set up the viewmodel
         let viewmodel           = DynamicViewModel::new();
@@ -80,6 +84,7 @@ impl<Chooser: FileChooser+'static> FileChooserController<Chooser> {
             viewmodel:          viewmodel,
             logo_controller:    logo_controller,
             ui:                 ui,
+            chooser:            chooser,
             file_manager:       file_manager,
             background_color:   background_color,
             open_file_store:    open_file_store
@@ -177,7 +182,16 @@ impl<Chooser: FileChooser+'static> FileChooserController<Chooser> {
                     .with(ControlAttribute::ZIndex(1))
                     .with(State::Value(Property::Bind(format!("Selected-{}", path_string))))
                     .with((ActionTrigger::SetValue, format!("SetSelect-{}", index)))
-                    .with(Bounds { x1: Position::At(2.0), y1: Position::At(2.0), x2: Position::At(22.0), y2: Position::At(22.0) })
+                    .with(Bounds { x1: Position::At(2.0), y1: Position::At(2.0), x2: Position::At(22.0), y2: Position::At(22.0) }),
+                Control::button()
+                    .with(ControlAttribute::ZIndex(1))
+                    .with(vec![Control::label()
+                        .with(Bounds::fill_all())
+                        .with(TextAlign::Center)
+                        .with("Versions")
+                    ])
+                    .with((ActionTrigger::Click, format!("Versions-{}", index)))
+                    .with(Bounds { x1: Position::At(FILE_WIDTH-80.0), y1: Position::At(2.0), x2: Position::At(FILE_WIDTH-8.0), y2: Position::At(22.0) })
             ])
             .with(ControlAttribute::Padding((2, 2), (2, 2)))
     }
@@ -195,6 +209,9 @@ impl<Chooser: FileChooser+'static> FileChooserController<Chooser> {
         let editing_filename_index  = model.editing_filename_index.clone();
         let selected_file_count     = model.selected_file_count.clone();
         let confirming_deletion     = model.confirming_deletion.clone();
+        let versions_file_index     = model.versions_file_index.clone();
+        let file_versions           = model.file_versions.clone();
+        let importable_files        = model.importable_files.clone();
 
         // Generate the UI
         let ui = computed(move || {
@@ -313,7 +330,7 @@ impl<Chooser: FileChooser+'static> FileChooserController<Chooser> {
                                 x1: Position::At(8.0),
                                 x2: Position::At(192.0),
                                 y1: Position::At(8.0),
-                                y2: Position::At(88.0)
+                                y2: Position::At(128.0)
                             })
                             .with(ControlAttribute::Padding((4, 4), (4, 4)))
                             .with(vec![
@@ -349,7 +366,31 @@ impl<Chooser: FileChooser+'static> FileChooserController<Chooser> {
                                             .with(&format!("Delete {} selected file{}", selected_file_count, if selected_file_count == 1 { "" } else { "s" }))
                                         ])
                                         .with((ActionTrigger::Click, "ConfirmDeleteSelectedFiles"))
-                                    }
+                                    },
+
+                                Control::empty()
+                                    .with(Bounds::next_vert(8.0)),
+
+                                Control::button()
+                                    .with(Bounds::next_vert(32.0))
+                                    .with(vec![Control::label()
+                                        .with(Bounds::fill_all())
+                                        .with(TextAlign::Center)
+                                        .with(&format!("Export {} selected file{}", selected_file_count, if selected_file_count == 1 { "" } else { "s" }))
+                                    ])
+                                    .with((ActionTrigger::Click, "ExportSelectedFiles")),
+
+                                Control::empty()
+                                    .with(Bounds::next_vert(8.0)),
+
+                                Control::button()
+                                    .with(Bounds::next_vert(32.0))
+                                    .with(vec![Control::label()
+                                        .with(Bounds::fill_all())
+                                        .with(TextAlign::Center)
+                                        .with("Export compacted")
+                                    ])
+                                    .with((ActionTrigger::Click, "ExportSelectedFilesCompacted"))
                             ])
                             .with(Appearance::Background(Color::Rgba(0.0, 0.0, 0.0, 0.3)))
                             .with(Scroll::Fix(FixedAxis::Vertical))
@@ -360,6 +401,143 @@ impl<Chooser: FileChooser+'static> FileChooserController<Chooser> {
                     vec![]
                 };
 
+                // If the user is browsing the versions of a file, display a list of the versions that can be opened
+                let version_controls = if let Some(versions_file_index) = versions_file_index.get() {
+                    let file_name       = file_list.get(versions_file_index).map(|file| file.name.get()).unwrap_or_else(|| String::from("Untitled"));
+                    let file_versions   = file_versions.get();
+
+                    let version_buttons = if file_versions.len() == 0 {
+                        vec![
+                            Control::label()
+                                .with(Bounds::next_vert(32.0))
+                                .with(TextAlign::Center)
+                                .with("No versions")
+                        ]
+                    } else {
+                        file_versions.iter().enumerate()
+                            .flat_map(|(version_index, version)| vec![
+                                Control::button()
+                                    .with(Bounds::next_vert(32.0))
+                                    .with(vec![Control::label()
+                                        .with(Bounds::fill_all())
+                                        .with(TextAlign::Center)
+                                        .with(&format!("{} ({} edits)", version.name, version.edit_index))
+                                    ])
+                                    .with((ActionTrigger::Click, format!("OpenVersion-{}", version_index))),
+                                Control::empty()
+                                    .with(Bounds::next_vert(4.0))
+                            ])
+                            .collect()
+                    };
+
+                    let height = 32.0 + 8.0 + 36.0 * (file_versions.len().max(1) as f32) + 32.0 + 8.0;
+
+                    vec![
+                        Control::container()
+                            .with(Bounds {
+                                x1: Position::At(200.0),
+                                x2: Position::At(456.0),
+                                y1: Position::At(8.0),
+                                y2: Position::At(8.0 + height)
+                            })
+                            .with(ControlAttribute::Padding((4, 4), (4, 4)))
+                            .with(vec![
+                                Control::label()
+                                    .with(Bounds::next_vert(32.0))
+                                    .with(TextAlign::Center)
+                                    .with(&format!("Versions of '{}'", file_name)),
+                                Control::empty()
+                                    .with(Bounds::next_vert(8.0))
+                            ]
+                            .into_iter()
+                            .chain(version_buttons)
+                            .chain(vec![
+                                Control::button()
+                                    .with(Bounds::next_vert(32.0))
+                                    .with(vec![Control::label()
+                                        .with(Bounds::fill_all())
+                                        .with(TextAlign::Center)
+                                        .with("Close")
+                                    ])
+                                    .with((ActionTrigger::Click, "CloseVersions"))
+                            ])
+                            .collect::<Vec<_>>())
+                            .with(Appearance::Background(Color::Rgba(0.0, 0.0, 0.0, 0.6)))
+                            .with(Scroll::Fix(FixedAxis::Vertical))
+                            .with(ControlAttribute::ZIndex(6))
+                    ]
+                } else {
+                    // Not browsing any versions
+                    vec![]
+                };
+
+                // If the user is importing a file, display the archives that can be imported
+                let import_controls = if let Some(importable_files) = importable_files.get() {
+                    let import_buttons = if importable_files.len() == 0 {
+                        vec![
+                            Control::label()
+                                .with(Bounds::next_vert(32.0))
+                                .with(TextAlign::Center)
+                                .with("No files to import")
+                        ]
+                    } else {
+                        importable_files.iter().enumerate()
+                            .flat_map(|(import_index, path)| vec![
+                                Control::button()
+                                    .with(Bounds::next_vert(32.0))
+                                    .with(vec![Control::label()
+                                        .with(Bounds::fill_all())
+                                        .with(TextAlign::Center)
+                                        .with(&Self::string_for_path(path.as_path()))
+                                    ])
+                                    .with((ActionTrigger::Click, format!("Import-{}", import_index))),
+                                Control::empty()
+                                    .with(Bounds::next_vert(4.0))
+                            ])
+                            .collect()
+                    };
+
+                    let height = 32.0 + 8.0 + 36.0 * (importable_files.len().max(1) as f32) + 32.0 + 8.0;
+
+                    vec![
+                        Control::container()
+                            .with(Bounds {
+                                x1: Position::At(200.0),
+                                x2: Position::At(456.0),
+                                y1: Position::At(8.0),
+                                y2: Position::At(8.0 + height)
+                            })
+                            .with(ControlAttribute::Padding((4, 4), (4, 4)))
+                            .with(vec![
+                                Control::label()
+                                    .with(Bounds::next_vert(32.0))
+                                    .with(TextAlign::Center)
+                                    .with("Import a file"),
+                                Control::empty()
+                                    .with(Bounds::next_vert(8.0))
+                            ]
+                            .into_iter()
+                            .chain(import_buttons)
+                            .chain(vec![
+                                Control::button()
+                                    .with(Bounds::next_vert(32.0))
+                                    .with(vec![Control::label()
+                                        .with(Bounds::fill_all())
+                                        .with(TextAlign::Center)
+                                        .with("Close")
+                                    ])
+                                    .with((ActionTrigger::Click, "CloseImport"))
+                            ])
+                            .collect::<Vec<_>>())
+                            .with(Appearance::Background(Color::Rgba(0.0, 0.0, 0.0, 0.6)))
+                            .with(Scroll::Fix(FixedAxis::Vertical))
+                            .with(ControlAttribute::ZIndex(6))
+                    ]
+                } else {
+                    // Not importing a file
+                    vec![]
+                };
+
                 // Work out the height of the container
                 let num_rows    = ((file_list.len() as i32)-1) / (NUM_COLUMNS as i32) + 1;
                 let height      = LOGO_HEIGHT + 8.0 + 24.0 + FILE_HEIGHT * (num_rows as f32);
@@ -398,6 +576,15 @@ impl<Chooser: FileChooser+'static> FileChooserController<Chooser> {
                                         .with(TextAlign::Center)
                                         .with("+ New file")])
                                         .with((ActionTrigger::Click, "CreateNewFile")),
+                                Control::empty()
+                                    .with(Bounds::next_horiz(8.0)),
+                                Control::button()
+                                    .with(Bounds::next_horiz(120.0))
+                                    .with(vec![Control::label()
+                                        .with(Bounds::fill_all())
+                                        .with(TextAlign::Center)
+                                        .with("Import...")])
+                                        .with((ActionTrigger::Click, "ShowImport")),
                                 Control::empty()
                                     .with(Bounds::stretch_horiz(1.0))
                             ])
@@ -424,6 +611,8 @@ impl<Chooser: FileChooser+'static> FileChooserController<Chooser> {
                     ]
                     .into_iter()
                     .chain(selected_file_controls)
+                    .chain(version_controls)
+                    .chain(import_controls)
                     .collect::<Vec<_>>()
                     )
 
@@ -451,6 +640,39 @@ impl<Chooser: FileChooser+'static> FileChooserController<Chooser> {
             self.file_manager.set_display_name_for_path(&file_path.as_path(), new_filename);
         }
     }
+
+    ///
+    /// Opens the file at the specified path as the main controller
+    ///
+    fn open_path(&self, path: PathBuf) {
+        // Create a new controller for the file
+        let shared_state    = self.open_file_store.open_shared(path.as_path());
+        let instance_state  = shared_state.new_instance();
+        let new_controller  = Chooser::Controller::open(instance_state);
+        let new_controller  = Arc::new(new_controller);
+
+        // Set as the main controller
+        *self.model.shared_state.lock().unwrap() = Some(shared_state);
+        self.model.open_file.set(Some(path));
+        self.model.active_controller.set(Some(new_controller));
+    }
+
+    ///
+    /// Opens a new file created from one of the versions of the file whose versions are being browsed
+    ///
+    fn open_version(&self, version: &FileVersion) {
+        // Stop browsing versions
+        let file_index = self.model.versions_file_index.get();
+        self.model.versions_file_index.set(None);
+
+        // Fork the version into a new file, then open it
+        let file_path       = file_index.and_then(|file_index| self.model.file_list.get().get(file_index).map(|file| file.path.get()));
+        let forked_path     = file_path.and_then(|file_path| self.chooser.fork_file_version(file_path.as_path(), version));
+
+        if let Some(forked_path) = forked_path {
+            self.open_path(forked_path);
+        }
+    }
 }
 
 impl<Chooser: FileChooser+'static> Controller for FileChooserController<Chooser> {
@@ -516,17 +738,7 @@ impl<Chooser: FileChooser+'static> Controller for FileChooserController<Chooser>
                 let new_file = self.file_manager.create_new_path();
 
                 // Give it a unique name
-                let all_files       = self.file_manager.get_all_files();
-                let used_names      = all_files.into_iter().filter_map(|path| self.file_manager.display_name_for_path(path.as_path())).collect::<HashSet<_>>();
-
-                let mut new_name    = String::from("New file");
-                let mut name_index  = 0;
-
-                while used_names.contains(&new_name) {
-                    name_index += 1;
-                    new_name = format!("New file ({})", name_index);
-                }
-
+                let new_name = self.file_manager.unused_display_name("New file");
                 self.file_manager.set_display_name_for_path(new_file.as_path(), new_name.clone());
 
                 // Edit the name
@@ -568,6 +780,33 @@ impl<Chooser: FileChooser+'static> Controller for FileChooserController<Chooser>
                 self.model.confirming_deletion.set(false);
             },
 
+            ("CloseVersions", _) => {
+                self.model.versions_file_index.set(None);
+            },
+
+            ("ExportSelectedFiles", _)          |
+            ("ExportSelectedFilesCompacted", _) => {
+                // Write out an archive for each of the selected files, compacting their edit logs if requested
+                let compact = action_id == "ExportSelectedFilesCompacted";
+
+                self.model.file_list.get()
+                    .iter()
+                    .filter(|file_model| file_model.selected.get())
+                    .for_each(|file_model| { self.chooser.export_file(file_model.path.get().as_path(), compact); });
+            },
+
+            ("ShowImport", _) => {
+                self.stop_editing_filename();
+
+                let importable_files = self.chooser.get_importable_files();
+                self.model.versions_file_index.set(None);
+                self.model.importable_files.set(Some(Arc::new(importable_files)));
+            },
+
+            ("CloseImport", _) => {
+                self.model.importable_files.set(None);
+            },
+
             ("CancelEditingFilename", _) => {
                 // Just unset the editing index without storing the edited value
                 self.model.editing_filename_index.set(None);
@@ -597,16 +836,46 @@ impl<Chooser: FileChooser+'static> Controller for FileChooserController<Chooser>
                     let file_model      = &self.model.file_list.get()[file_index];
                     let path            = file_model.path.get();
 
-                    // Create a new controller for the file
-                    let shared_state    = self.open_file_store.open_shared(path.as_path());
-                    let instance_state  = shared_state.new_instance();
-                    let new_controller  = Chooser::Controller::open(instance_state);
-                    let new_controller  = Arc::new(new_controller);
+                    // Make it the active file
+                    self.model.versions_file_index.set(None);
+                    self.open_path(path);
 
-                    // Set as the main controller
-                    *self.model.shared_state.lock().unwrap() = Some(shared_state);
-                    self.model.open_file.set(Some(path));
-                    self.model.active_controller.set(Some(new_controller));
+                } else if action.starts_with("Versions-") {
+
+                    // Finish up any filename editing that might be occurring
+                    self.stop_editing_filename();
+
+                    // Get the index of the file whose versions should be displayed
+                    let (_, file_index) = action.split_at("Versions-".len());
+                    let file_index      = usize::from_str_radix(file_index, 10).unwrap();
+                    let file_model      = &self.model.file_list.get()[file_index];
+
+                    // Read the versions from the chooser
+                    let versions        = self.chooser.get_file_versions(file_model.path.get().as_path());
+                    self.model.importable_files.set(None);
+                    self.model.file_versions.set(Arc::new(versions));
+                    self.model.versions_file_index.set(Some(file_index));
+
+                } else if action.starts_with("Import-") {
+
+                    // Get the index of the archive being imported
+                    let (_, import_index)   = action.split_at("Import-".len());
+                    let import_index        = usize::from_str_radix(import_index, 10).unwrap();
+                    let archive_path        = self.model.importable_files.get().and_then(|files| files.get(import_index).cloned());
+
+                    // Import it as a new file (which will appear at the start of the file list)
+                    self.model.importable_files.set(None);
+                    archive_path.map(|archive_path| self.chooser.import_file(archive_path.as_path()));
+
+                } else if action.starts_with("OpenVersion-") {
+
+                    // Get the index of the version being opened
+                    let (_, version_index)  = action.split_at("OpenVersion-".len());
+                    let version_index       = usize::from_str_radix(version_index, 10).unwrap();
+
+                    if let Some(version) = self.model.file_versions.get().get(version_index).cloned() {
+                        self.open_version(&version);
+                    }
 
                 } else if action.starts_with("SetSelect-") {
 
@@ -642,11 +911,11 @@ impl<Chooser: FileChooser+'static> Controller for FileChooserController<Chooser>
 
                     // Action depends on the parameter
                     match action_parameter {
-                        ActionParameter::Drag(DragAction::Start, _, _) => {
+                        ActionParameter::Drag(DragAction::Start, _, _, _) => {
                             self.model.dragging_offset.set((0.0, 0.0));
                         },
 
-                        ActionParameter::Drag(DragAction::Finish, _, _) => {
+                        ActionParameter::Drag(DragAction::Finish, _, _, _) => {
                             let drag_after_index = self.model.drag_after_index.get();
 
                             // Move the file if there's a drag index
@@ -669,12 +938,12 @@ impl<Chooser: FileChooser+'static> Controller for FileChooserController<Chooser>
                             self.model.drag_after_index.set(None);
                         },
 
-                        ActionParameter::Drag(DragAction::Cancel, _, _) => {
+                        ActionParameter::Drag(DragAction::Cancel, _, _, _) => {
                             self.model.dragging_file.set(None);
                             self.model.drag_after_index.set(None);
                         },
 
-                        ActionParameter::Drag(DragAction::Drag, (from_x, from_y), (to_x, to_y)) => {
+                        ActionParameter::Drag(DragAction::Drag, (from_x, from_y), (to_x, to_y), _) => {
                             if (from_x-to_x).abs() > 6.0 || (from_y-to_y).abs() > 6.0 {
                                 self.model.dragging_file.set(Some(file_index as usize));
                             }
@@ -2,6 +2,7 @@ use super::file_chooser::*;
 use super::file_controller::*;
 use super::super::file_update::*;
 use super::super::file_manager::*;
+use super::super::file_version::*;
 
 use flo_binding::*;
 
@@ -68,7 +69,16 @@ pub struct FileChooserModel<Chooser: FileChooser> {
     pub selected_file_count: BindRef<usize>,
 
     /// True if we're confirming a deletion request
-    pub confirming_deletion: Binding<bool>
+    pub confirming_deletion: Binding<bool>,
+
+    /// The index of the file whose versions are being browsed
+    pub versions_file_index: Binding<Option<usize>>,
+
+    /// The versions of the file that is being browsed
+    pub file_versions: Binding<Arc<Vec<FileVersion>>>,
+
+    /// The archives that can be imported, if the user is choosing a file to import
+    pub importable_files: Binding<Option<Arc<Vec<PathBuf>>>>
 }
 
 impl<Chooser: 'static+FileChooser> FileChooserModel<Chooser> {
@@ -99,7 +109,10 @@ impl<Chooser: 'static+FileChooser> FileChooserModel<Chooser> {
             file_list:              file_list,
             file_range:             bind(0..0),
             selected_file_count:    selected_file_count,
-            confirming_deletion:    bind(false)
+            confirming_deletion:    bind(false),
+            versions_file_index:    bind(None),
+            file_versions:          bind(Arc::new(vec![])),
+            importable_files:       bind(None)
         }
     }
 
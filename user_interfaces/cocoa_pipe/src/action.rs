@@ -187,7 +187,10 @@ pub enum ViewStateUpdate {
     FixScrollAxis(FixedAxis),
 
     /// Adds a class name to this view (used as a hint to change rendering styles)
-    AddClass(String)
+    AddClass(String),
+
+    /// Copies the specified text to the pasteboard when this view is clicked
+    CopyOnClick(String)
 }
 
 ///
@@ -264,7 +267,13 @@ pub enum ViewEvent {
     SetValue,
 
     /// Event sent when some EditValues were sent but the editing was cancelled
-    CancelEdit
+    CancelEdit,
+
+    /// Send events when the scroll wheel is moved over this view
+    ScrollWheel,
+
+    /// Send events when the user performs a pinch (magnify or rotate) gesture over this view
+    Pinch
 }
 
 ///
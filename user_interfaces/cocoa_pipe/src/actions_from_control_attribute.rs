@@ -82,6 +82,8 @@ fn event_actions(trigger: &ActionTrigger, name: &String) -> Vec<ViewAction> {
         SetValue                        => vec![ViewAction::RequestEvent(ViewEvent::SetValue, name.clone())],
         CancelEdit                      => vec![ViewAction::RequestEvent(ViewEvent::CancelEdit, name.clone())],
         VirtualScroll(width, height)    => vec![ViewAction::RequestEvent(ViewEvent::VirtualScroll(*width as f64, *height as f64), name.clone())],
+        ScrollWheel                     => vec![ViewAction::RequestEvent(ViewEvent::ScrollWheel, name.clone())],
+        Pinch                           => vec![ViewAction::RequestEvent(ViewEvent::Pinch, name.clone())],
     }
 }
 
@@ -155,8 +157,9 @@ impl ActionsFrom<ViewAction> for Hint {
         use self::Hint::*;
 
         match self {
-            FastDrawing     => vec![],
-            Class(name)     => vec![ViewAction::SetState(ViewStateUpdate::AddClass(name.clone()))]
+            FastDrawing       => vec![],
+            Class(name)       => vec![ViewAction::SetState(ViewStateUpdate::AddClass(name.clone()))],
+            CopyOnClick(text) => vec![ViewAction::SetState(ViewStateUpdate::CopyOnClick(text.clone()))]
         }
     }
 }
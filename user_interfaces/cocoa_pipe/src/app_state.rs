@@ -101,22 +101,25 @@ impl AppState {
 
             VirtualScroll(view_id, name, top_left, size)        => vec![UiEvent::Action(self.get_controller_path_for_view(view_id), name, ActionParameter::VirtualScroll(top_left, size))],
 
-            Drag(view_id, name, DragAction::Start, from, to)    => {
+            Drag(view_id, name, DragAction::Start, from, to, keys)  => {
                 self.activate_view(view_id);
                 let controller_path = self.get_controller_path_for_view(view_id);
-                vec![UiEvent::Action(controller_path, name, ActionParameter::Drag(DragAction::Start, (from.0 as f32, from.1 as f32), (to.0 as f32, to.1 as f32)))]
+                vec![UiEvent::Action(controller_path, name, ActionParameter::Drag(DragAction::Start, (from.0 as f32, from.1 as f32), (to.0 as f32, to.1 as f32), keys))]
             },
-            Drag(view_id, name, DragAction::Finish, from, to)    => {
+            Drag(view_id, name, DragAction::Finish, from, to, keys) => {
                 let controller_path = self.get_controller_path_for_view(view_id);
                 self.deactivate_view(view_id);
-                vec![UiEvent::Action(controller_path, name, ActionParameter::Drag(DragAction::Finish, (from.0 as f32, from.1 as f32), (to.0 as f32, to.1 as f32)))]
+                vec![UiEvent::Action(controller_path, name, ActionParameter::Drag(DragAction::Finish, (from.0 as f32, from.1 as f32), (to.0 as f32, to.1 as f32), keys))]
             },
-            Drag(view_id, name, action, from, to)               => vec![UiEvent::Action(self.get_controller_path_for_view(view_id), name, ActionParameter::Drag(action, (from.0 as f32, from.1 as f32), (to.0 as f32, to.1 as f32)))],
+            Drag(view_id, name, action, from, to, keys)             => vec![UiEvent::Action(self.get_controller_path_for_view(view_id), name, ActionParameter::Drag(action, (from.0 as f32, from.1 as f32), (to.0 as f32, to.1 as f32), keys))],
 
             PaintStart(view_id, name, device, painting)         => vec![UiEvent::Action(self.get_controller_path_for_view(view_id), name, ActionParameter::Paint(device.into_paint_device(), vec![painting.into_painting(PaintAction::Start)]))],
             PaintContinue(view_id, name, device, painting)      => vec![UiEvent::Action(self.get_controller_path_for_view(view_id), name, ActionParameter::Paint(device.into_paint_device(), vec![painting.into_painting(PaintAction::Continue)]))],
             PaintFinish(view_id, name, device, painting)        => vec![UiEvent::Action(self.get_controller_path_for_view(view_id), name, ActionParameter::Paint(device.into_paint_device(), vec![painting.into_painting(PaintAction::Finish)]))],
-            PaintCancel(view_id, name, device, painting)        => vec![UiEvent::Action(self.get_controller_path_for_view(view_id), name, ActionParameter::Paint(device.into_paint_device(), vec![painting.into_painting(PaintAction::Cancel)]))]
+            PaintCancel(view_id, name, device, painting)        => vec![UiEvent::Action(self.get_controller_path_for_view(view_id), name, ActionParameter::Paint(device.into_paint_device(), vec![painting.into_painting(PaintAction::Cancel)]))],
+
+            ScrollWheel(view_id, name, (x, y), (dx, dy))        => vec![UiEvent::Action(self.get_controller_path_for_view(view_id), name, ActionParameter::ScrollWheel((x as f32, y as f32), (dx as f32, dy as f32)))],
+            Pinch(view_id, name, (x, y), scale, rotation)       => vec![UiEvent::Action(self.get_controller_path_for_view(view_id), name, ActionParameter::Pinch((x as f32, y as f32), scale as f32, rotation as f32))]
         }
     }
 
@@ -62,8 +62,8 @@ pub enum AppEvent {
     /// The scrolling region has changed
     VirtualScroll(usize, String, (u32, u32), (u32, u32)),
 
-    /// Indicates that a point has been dragged to another location
-    Drag(usize, String, DragAction, (f64, f64), (f64, f64)),
+    /// Indicates that a point has been dragged to another location (with the modifier keys that are held down)
+    Drag(usize, String, DragAction, (f64, f64), (f64, f64), ModifierKeys),
 
     /// A painting action has started with the device in the specified state
     PaintStart(usize, String, AppPaintDevice, AppPainting),
@@ -75,7 +75,13 @@ pub enum AppEvent {
     PaintFinish(usize, String, AppPaintDevice, AppPainting),
 
     /// The painting action was cancelled
-    PaintCancel(usize, String, AppPaintDevice, AppPainting)
+    PaintCancel(usize, String, AppPaintDevice, AppPainting),
+
+    /// The scroll wheel was moved (position of the pointer and distance scrolled)
+    ScrollWheel(usize, String, (f64, f64), (f64, f64)),
+
+    /// A pinch gesture updated (center of the gesture, change in scale and change in rotation)
+    Pinch(usize, String, (f64, f64), f64, f64)
 }
 
 impl AppPainting {
@@ -4,6 +4,8 @@ use flo_canvas::*;
 
 use objc::rc::*;
 
+use std::collections::HashMap;
+
 ///
 /// Possible actions stored in the path for this state
 ///
@@ -15,6 +17,15 @@ enum PathAction {
     Curve(CGFloat, CGFloat, CGFloat, CGFloat, CGFloat, CGFloat)
 }
 
+///
+/// A gradient fill for future paths (the gradient is created when the fill is set, so later changes to the gradient don't affect it)
+///
+#[derive(Clone)]
+enum GradientFill {
+    Linear(CFRef<CGGradientRef>, CGPoint, CGPoint),
+    Radial(CFRef<CGGradientRef>, CGPoint, CGFloat)
+}
+
 ///
 /// The values stores in a canvas state
 ///
@@ -23,6 +34,7 @@ struct CanvasStateValues {
     sprite:             Option<SpriteId>,
     color_space:        CFRef<CGColorSpaceRef>,
     fill_color:         CFRef<CGColorRef>,
+    fill_gradient:      Option<GradientFill>,
     stroke_color:       CFRef<CGColorRef>,
     transform:          CGAffineTransform,
     blend_mode:         CGBlendMode,
@@ -43,7 +55,8 @@ struct CanvasStateValues {
 pub struct CanvasState {
     context:        Option<CFRef<CGContextRef>>,
     values:         CanvasStateValues,
-    stack:          Vec<CanvasStateValues>
+    stack:          Vec<CanvasStateValues>,
+    gradients:      HashMap<GradientId, Vec<(f32, Color)>>
 }
 
 impl CanvasState {
@@ -62,6 +75,7 @@ impl CanvasState {
                     sprite:             None,
                     color_space:        color_space,
                     fill_color:         fill_color,
+                    fill_gradient:      None,
                     stroke_color:       stroke_color,
                     transform:          transform,
                     blend_mode:         CGBlendMode::Normal,
@@ -74,7 +88,8 @@ impl CanvasState {
                     stored_layer:       None,
                     clip:               None
                 },
-                stack:      vec![]
+                stack:      vec![],
+                gradients:  HashMap::new()
             }
         }
     }
@@ -300,7 +315,8 @@ impl CanvasState {
             let new_color           = CFRef::from(CGColorCreate(*self.values.color_space, [r as CGFloat, g as CGFloat, b as CGFloat, a as CGFloat].as_ptr()));
 
             // Store it in this object
-            self.values.fill_color  = new_color;
+            self.values.fill_color      = new_color;
+            self.values.fill_gradient   = None;
 
             // Set in the context
             if let Some(ref context) = self.context {
@@ -309,6 +325,90 @@ impl CanvasState {
         }
     }
 
+    ///
+    /// Performs an operation on one of the gradients defined in this state
+    ///
+    pub fn update_gradient(&mut self, gradient_id: GradientId, op: &GradientOp) {
+        match op {
+            GradientOp::Create(start_color)     => { self.gradients.insert(gradient_id, vec![(0.0, *start_color)]); }
+            GradientOp::AddStop(pos, color)     => { self.gradients.entry(gradient_id).or_insert_with(|| vec![]).push((*pos, *color)); }
+        }
+    }
+
+    ///
+    /// Moves the gradient definitions from another state into this one
+    ///
+    pub fn take_gradients(&mut self, from_state: &mut CanvasState) {
+        self.gradients = std::mem::take(&mut from_state.gradients);
+    }
+
+    ///
+    /// Creates a CGGradient from the current definition of a gradient
+    ///
+    fn create_gradient(&self, gradient_id: GradientId) -> Option<CFRef<CGGradientRef>> {
+        let stops = self.gradients.get(&gradient_id)?;
+        if stops.len() == 0 { return None; }
+
+        let mut components  = vec![];
+        let mut locations   = vec![];
+
+        for (pos, color) in stops.iter() {
+            let (r, g, b, a) = color.to_rgba_components();
+            components.extend([r as CGFloat, g as CGFloat, b as CGFloat, a as CGFloat].iter().cloned());
+            locations.push(*pos as CGFloat);
+        }
+
+        unsafe {
+            Some(CFRef::from(CGGradientCreateWithColorComponents(*self.values.color_space, components.as_ptr(), locations.as_ptr(), stops.len())))
+        }
+    }
+
+    ///
+    /// Sets future fills to use a linear gradient
+    ///
+    pub fn set_fill_linear_gradient(&mut self, gradient_id: GradientId, start: (CGFloat, CGFloat), end: (CGFloat, CGFloat)) {
+        self.values.fill_gradient = self.create_gradient(gradient_id)
+            .map(|gradient| GradientFill::Linear(gradient, CGPoint { x: start.0, y: start.1 }, CGPoint { x: end.0, y: end.1 }));
+    }
+
+    ///
+    /// Sets future fills to use a radial gradient
+    ///
+    pub fn set_fill_radial_gradient(&mut self, gradient_id: GradientId, center: (CGFloat, CGFloat), radius: CGFloat) {
+        self.values.fill_gradient = self.create_gradient(gradient_id)
+            .map(|gradient| GradientFill::Radial(gradient, CGPoint { x: center.0, y: center.1 }, radius));
+    }
+
+    ///
+    /// Fills the current path using the fill colour or gradient
+    ///
+    pub fn fill(&self) {
+        unsafe {
+            if let Some(ref context) = self.context {
+                self.load_path();
+
+                match self.values.fill_gradient {
+                    None                                                => { CGContextFillPath(**context); }
+
+                    Some(ref gradient_fill)                             => {
+                        // Gradients are drawn by clipping to the path
+                        let options = kCGGradientDrawsBeforeStartLocation | kCGGradientDrawsAfterEndLocation;
+
+                        CGContextSaveGState(**context);
+                        CGContextClip(**context);
+
+                        match gradient_fill {
+                            GradientFill::Linear(gradient, start, end)      => { CGContextDrawLinearGradient(**context, **gradient, *start, *end, options); }
+                            GradientFill::Radial(gradient, center, radius)  => { CGContextDrawRadialGradient(**context, **gradient, *center, 0.0, *center, *radius, options); }
+                        }
+
+                        CGContextRestoreGState(**context);
+                    }
+                }
+            }
+        }
+    }
+
     ///
     /// Sets the stroke color of this state
     ///
@@ -21,6 +21,13 @@ pub type CGColorRef = *mut CGColor;
 #[repr(C)] pub struct CGMutablePath { _private: [u8; 0] }
 pub type CGMutablePathRef = *mut CGMutablePath;
 
+#[repr(C)] pub struct CGGradient { _private: [u8; 0] }
+pub type CGGradientRef = *mut CGGradient;
+
+pub type CGGradientDrawingOptions = u32;
+#[allow(non_upper_case_globals)] pub const kCGGradientDrawsBeforeStartLocation: CGGradientDrawingOptions = 1;
+#[allow(non_upper_case_globals)] pub const kCGGradientDrawsAfterEndLocation: CGGradientDrawingOptions    = 2;
+
 #[derive(Copy, Clone, Debug)]
 #[repr(C)] pub struct CGAffineTransform {
     pub a: CGFloat,
@@ -143,6 +150,10 @@ extern {
     pub fn CGAffineTransformConcat(t1: CGAffineTransform, t2: CGAffineTransform) -> CGAffineTransform;
     pub fn CGPointApplyAffineTransform(CGPoint: CGPoint, t: CGAffineTransform) -> CGPoint;
 
+    pub fn CGGradientCreateWithColorComponents(space: CGColorSpaceRef, components: *const CGFloat, locations: *const CGFloat, count: usize) -> CGGradientRef;
+    pub fn CGGradientRetain(gradient: CGGradientRef) -> CGGradientRef;
+    pub fn CGGradientRelease(gradient: CGGradientRef);
+
     pub fn CGPathCreateMutable() -> CGMutablePathRef;
     pub fn CGPathCreateMutableCopy(path: CGMutablePathRef) -> CGMutablePathRef;
     pub fn CGPathRetain(path: CGMutablePathRef);
@@ -174,6 +185,8 @@ extern {
     pub fn CGContextAddPath(ctxt: CGContextRef, path: CGMutablePathRef);
     pub fn CGContextClearRect(ctxt: CGContextRef, rect: CGRect);
     pub fn CGContextClip(ctxt: CGContextRef);
+    pub fn CGContextDrawLinearGradient(ctxt: CGContextRef, gradient: CGGradientRef, startPoint: CGPoint, endPoint: CGPoint, options: CGGradientDrawingOptions);
+    pub fn CGContextDrawRadialGradient(ctxt: CGContextRef, gradient: CGGradientRef, startCenter: CGPoint, startRadius: CGFloat, endCenter: CGPoint, endRadius: CGFloat, options: CGGradientDrawingOptions);
 }
 
 pub trait CFReleasable {
@@ -225,6 +238,17 @@ impl CFReleasable for CGMutablePathRef {
     }
 }
 
+impl CFReleasable for CGGradientRef {
+    #[inline] fn retain(&self) -> Self {
+        unsafe { CGGradientRetain(*self); }
+        *self
+    }
+
+    #[inline] fn release(&self) {
+        unsafe { CGGradientRelease(*self); }
+    }
+}
+
 pub struct CFRef<T: CFReleasable>(T);
 
 impl<T: CFReleasable> Clone for CFRef<T> {
@@ -266,7 +266,7 @@ pub fn declare_flo_events_class() -> &'static Class {
         }
 
         // Sends the 'drag' event
-        extern fn send_drag(this: &mut Object, _sel: Sel, name: *mut Object, drag_action: u32, from_x: f64, from_y: f64, to_x: f64, to_y: f64) {
+        extern fn send_drag(this: &mut Object, _sel: Sel, name: *mut Object, drag_action: u32, from_x: f64, from_y: f64, to_x: f64, to_y: f64, modifiers: u32) {
             unsafe {
                 let view_id     = get_view_id(this);
                 let name        = name_for_name(&mut *name);
@@ -277,9 +277,39 @@ pub fn declare_flo_events_class() -> &'static Class {
                     3 => DragAction::Cancel,
                     _ => DragAction::Drag,
                 };
+                let modifiers   = ModifierKeys {
+                    shift:      (modifiers & 1) != 0,
+                    control:    (modifiers & 2) != 0,
+                    alt:        (modifiers & 4) != 0,
+                    command:    (modifiers & 8) != 0
+                };
+
+                if let Some(view_id) = view_id {
+                    send_event(this, AppEvent::Drag(view_id, name, drag_action, (from_x, from_y), (to_x, to_y), modifiers));
+                }
+            }
+        }
+
+        // Sends the 'scroll wheel' event
+        extern fn send_scroll_wheel(this: &mut Object, _sel: Sel, name: *mut Object, x: f64, y: f64, delta_x: f64, delta_y: f64) {
+            unsafe {
+                let view_id = get_view_id(this);
+                let name    = name_for_name(&mut *name);
+
+                if let Some(view_id) = view_id {
+                    send_event(this, AppEvent::ScrollWheel(view_id, name, (x, y), (delta_x, delta_y)));
+                }
+            }
+        }
+
+        // Sends the 'pinch' event
+        extern fn send_pinch(this: &mut Object, _sel: Sel, name: *mut Object, x: f64, y: f64, scale: f64, rotation: f64) {
+            unsafe {
+                let view_id = get_view_id(this);
+                let name    = name_for_name(&mut *name);
 
                 if let Some(view_id) = view_id {
-                    send_event(this, AppEvent::Drag(view_id, name, drag_action, (from_x, from_y), (to_x, to_y)));
+                    send_event(this, AppEvent::Pinch(view_id, name, (x, y), scale, rotation));
                 }
             }
         }
@@ -417,7 +447,9 @@ pub fn declare_flo_events_class() -> &'static Class {
         flo_events.add_method(sel!(sendChangeValue:isSet:withDouble:), send_change_value_double as extern fn(&mut Object, Sel, *mut Object, bool, f64));
         flo_events.add_method(sel!(sendChangeValue:isSet:withString:), send_change_value_string as extern fn(&mut Object, Sel, *mut Object, bool, *mut Object));
         flo_events.add_method(sel!(sendVirtualScroll:left:top:width:height:), send_virtual_scroll as extern fn(&mut Object, Sel, *mut Object, u32, u32, u32, u32));
-        flo_events.add_method(sel!(sendDrag:dragAction:fromX:fromY:toX:toY:), send_drag as extern fn(&mut Object, Sel, *mut Object, u32, f64, f64, f64, f64));
+        flo_events.add_method(sel!(sendDrag:dragAction:fromX:fromY:toX:toY:modifiers:), send_drag as extern fn(&mut Object, Sel, *mut Object, u32, f64, f64, f64, f64, u32));
+        flo_events.add_method(sel!(sendScrollWheel:x:y:deltaX:deltaY:), send_scroll_wheel as extern fn(&mut Object, Sel, *mut Object, f64, f64, f64, f64));
+        flo_events.add_method(sel!(sendPinch:x:y:scale:rotation:), send_pinch as extern fn(&mut Object, Sel, *mut Object, f64, f64, f64, f64));
         flo_events.add_method(sel!(sendPaintStartForDevice:name:action:), send_paint_start as extern fn(&mut Object, Sel, u32, *mut Object, AppPainting));
         flo_events.add_method(sel!(sendPaintContinueForDevice:name:action:), send_paint_continue as extern fn(&mut Object, Sel, u32, *mut Object, AppPainting));
         flo_events.add_method(sel!(sendPaintFinishForDevice:name:action:), send_paint_finish as extern fn(&mut Object, Sel, u32, *mut Object, AppPainting));
@@ -60,8 +60,11 @@ impl QuartzContext {
             self.state.deactivate_context();
 
             // Create a new state
-            let srgb    = CGColorSpaceCreateWithName(kCGColorSpaceSRGB);
-            let state   = CanvasState::new(CFRef::from(srgb));
+            let srgb        = CGColorSpaceCreateWithName(kCGColorSpaceSRGB);
+            let mut state   = CanvasState::new(CFRef::from(srgb));
+
+            // Gradient definitions are not part of the state that gets reset
+            state.take_gradients(&mut self.state);
 
             // Activate the new state
             self.state = state;
@@ -130,7 +133,7 @@ impl QuartzContext {
                 Line(x, y)                                          => { self.state.path_line(*x as CGFloat, *y as CGFloat); }
                 BezierCurve((ex, ey), (c1x, c1y), (c2x, c2y))       => { self.state.path_bezier_curve((*c1x as CGFloat, *c1y as CGFloat), (*c2x as CGFloat, *c2y as CGFloat), (*ex as CGFloat, *ey as CGFloat)); }
                 ClosePath                                           => { self.state.path_close(); }
-                Fill                                                => { self.state.load_path(); CGContextFillPath(*self.context); }
+                Fill                                                => { self.state.fill(); }
                 Stroke                                              => { self.state.load_path(); CGContextStrokePath(*self.context); }
                 LineWidth(width)                                    => { self.state.set_line_width(*width as CGFloat); }
                 LineWidthPixels(width_pixels)                       => {
@@ -149,6 +152,11 @@ impl QuartzContext {
                 DashOffset(offset)                                  => { /* TODO */ }
                 FillColor(col)                                      => { self.state.set_fill_color(col); }
                 StrokeColor(col)                                    => { self.state.set_stroke_color(col); }
+                Gradient(gradient_id, op)                           => { self.state.update_gradient(*gradient_id, op); }
+                FillGradient(gradient_id, (x1, y1), (x2, y2))       => { self.state.set_fill_linear_gradient(*gradient_id, (*x1 as CGFloat, *y1 as CGFloat), (*x2 as CGFloat, *y2 as CGFloat)); }
+                FillRadialGradient(gradient_id, (x, y), radius)     => { self.state.set_fill_radial_gradient(*gradient_id, (*x as CGFloat, *y as CGFloat), *radius as CGFloat); }
+                Texture(_texture_id, _op)                           => { /* TODO */ }
+                DrawTexture(_texture_id, _min, _max, _alpha)        => { /* TODO */ }
                 BlendMode(blend)                                    => { self.state.set_blend_mode(blend); }
                 Unclip                                              => { self.state.unclip(); }
                 Clip                                                => { self.state.clip(); }
@@ -421,6 +421,7 @@ impl CocoaSession {
                 FocusPriority(property)     => { let _: () = msg_send!(**view, viewSetFocusPriority: *self.flo_property(property)); }
                 FixScrollAxis(axis)         => { let _: () = msg_send!(**view, viewFixScrollAxis: self.id_for_scroll_axis(axis)); }
                 AddClass(class_name)        => { let _: () = msg_send!(**view, viewAddClassName: NSString::alloc(nil).init_str(&class_name)); }
+                CopyOnClick(text)           => { let _: () = msg_send!(**view, viewSetCopyOnClick: NSString::alloc(nil).init_str(&text)); }
             }
         }
     }
@@ -674,6 +675,8 @@ impl CocoaSession {
                     EditValue                       => { let _: () = msg_send!(**view, requestEditValue: *flo_events withName: *name); }
                     SetValue                        => { let _: () = msg_send!(**view, requestSetValue: *flo_events withName: *name); }
                     CancelEdit                      => { let _: () = msg_send!(**view, requestCancelEdit: *flo_events withName: *name); }
+                    ScrollWheel                     => { let _: () = msg_send!(**view, requestScrollWheel: *flo_events withName: *name); }
+                    Pinch                           => { let _: () = msg_send!(**view, requestPinch: *flo_events withName: *name); }
                 }
             }
         }
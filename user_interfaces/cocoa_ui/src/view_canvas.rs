@@ -126,7 +126,7 @@ impl ViewCanvas {
                 ClearCanvas => {
                     unsafe {
                         // Invalidate the context and clear
-                        context.to_state();
+                        let mut old_state = context.to_state();
                         (self.clear_canvas)();
 
                         // Reset to layer 0
@@ -134,6 +134,9 @@ impl ViewCanvas {
                         if let Some(layer_context) = layer_context {
                             // The canvas context doesn't deactivate itself on drop, so force it to deactivate by going through to_state
                             context = QuartzContext::new(layer_context, viewport_origin, viewport_size, canvas_size);
+
+                            // Gradients are kept when the canvas is cleared
+                            context.get_state().take_gradients(&mut old_state);
                         } else {
                             // Stop drawing
                             return;
@@ -6,6 +6,9 @@ use flo_canvas::*;
 use cairo;
 use cairo::*;
 
+use std::rc::*;
+use std::collections::HashMap;
+
 ///
 /// The current source colour that's set
 ///
@@ -16,13 +19,33 @@ enum ColorTarget {
     Fill
 }
 
+///
+/// A gradient that has been selected as the fill colour
+///
+#[derive(Clone)]
+enum GradientFill {
+    Linear((f32, f32), (f32, f32), Vec<(f32, Color)>),
+    Radial((f32, f32), f32, Vec<(f32, Color)>)
+}
+
+///
+/// A texture that has been defined on this canvas (stored as premultiplied ARGB32 pixels in the native byte order)
+///
+#[derive(Clone)]
+struct CairoTexture {
+    width:  i32,
+    height: i32,
+    pixels: Vec<u8>
+}
+
 ///
 /// A saved state in a Cario drawing surface
 ///
 struct SavedState {
     dash_pattern:   Vec<f64>,
     stroke_color:   Color,
-    fill_color:     Color
+    fill_color:     Color,
+    fill_gradient:  Option<GradientFill>
 }
 
 impl SavedState {
@@ -33,7 +56,8 @@ impl SavedState {
         SavedState {
             dash_pattern:   drawing.dash_pattern.clone(),
             stroke_color:   drawing.stroke_color.clone(),
-            fill_color:     drawing.fill_color.clone()
+            fill_color:     drawing.fill_color.clone(),
+            fill_gradient:  drawing.fill_gradient.clone()
         }
     }
 
@@ -44,6 +68,7 @@ impl SavedState {
         drawing.dash_pattern    = self.dash_pattern;
         drawing.stroke_color    = self.stroke_color;
         drawing.fill_color      = self.fill_color;
+        drawing.fill_gradient   = self.fill_gradient;
         drawing.set_color       = ColorTarget::None;
     }
 }
@@ -58,7 +83,10 @@ pub struct CairoState {
     line_cap:       cairo::LineCap,
     fill_color:     Color,
     stroke_color:   Color,
-    dash_pattern:   Vec<f64>
+    dash_pattern:   Vec<f64>,
+    fill_gradient:  Option<GradientFill>,
+    gradients:      HashMap<GradientId, Vec<(f32, Color)>>,
+    textures:       HashMap<TextureId, Rc<CairoTexture>>
 }
 
 ///
@@ -83,6 +111,15 @@ pub struct CairoDraw {
     /// The current fill colour
     fill_color: Color,
 
+    /// The gradient to use instead of the fill colour, if one is set
+    fill_gradient: Option<GradientFill>,
+
+    /// The gradients that have been defined
+    gradients: HashMap<GradientId, Vec<(f32, Color)>>,
+
+    /// The textures that have been defined
+    textures: HashMap<TextureId, Rc<CairoTexture>>,
+
     /// The colour that's currently set
     set_color: ColorTarget,
 
@@ -116,6 +153,9 @@ impl CairoDraw {
             dash_pattern:   vec![],
             stroke_color:   Color::Rgba(0.0, 0.0, 0.0, 1.0),
             fill_color:     Color::Rgba(0.0, 0.0, 0.0, 1.0),
+            fill_gradient:  None,
+            gradients:      HashMap::new(),
+            textures:       HashMap::new(),
             set_color:      ColorTarget::None,
             initial_matrix: Matrix::from(&viewport),
             viewport:       viewport
@@ -149,6 +189,12 @@ impl CairoDraw {
     ///
     #[inline]
     fn set_color(&mut self, target: ColorTarget) {
+        // Gradients are set in user space, so they're always regenerated in case the transform has changed
+        if target == ColorTarget::Fill && self.fill_gradient.is_some() {
+            self.set_fill_gradient();
+            return;
+        }
+
         // Only change the colour if it's not already set
         if self.set_color != target {
             // Get the RGBA components for this target
@@ -168,6 +214,124 @@ impl CairoDraw {
         }
     }
 
+    ///
+    /// Sets the source to the current fill gradient
+    ///
+    fn set_fill_gradient(&mut self) {
+        // Adds the stops for a gradient to a pattern
+        fn add_stops(pattern: &Gradient, stops: &Vec<(f32, Color)>) {
+            for (pos, color) in stops.iter() {
+                let (r, g, b, a) = color.to_rgba_components();
+                pattern.add_color_stop_rgba(*pos as f64, r as f64, g as f64, b as f64, a as f64);
+            }
+        }
+
+        match &self.fill_gradient {
+            None                                                    => { }
+            Some(GradientFill::Linear((x1, y1), (x2, y2), stops))   => {
+                let pattern = LinearGradient::new(*x1 as f64, *y1 as f64, *x2 as f64, *y2 as f64);
+                add_stops(&pattern, stops);
+                self.ctxt.set_source(&pattern);
+            }
+            Some(GradientFill::Radial((x, y), radius, stops))       => {
+                let pattern = RadialGradient::new(*x as f64, *y as f64, 0.0, *x as f64, *y as f64, *radius as f64);
+                add_stops(&pattern, stops);
+                self.ctxt.set_source(&pattern);
+            }
+        }
+
+        self.set_color = ColorTarget::None;
+    }
+
+    ///
+    /// Defines or updates a gradient
+    ///
+    fn update_gradient(&mut self, gradient_id: GradientId, op: GradientOp) {
+        match op {
+            GradientOp::Create(color)           => { self.gradients.insert(gradient_id, vec![(0.0, color)]); }
+            GradientOp::AddStop(pos, color)     => { 
+                let stops = self.gradients.entry(gradient_id).or_insert_with(|| vec![]);
+                stops.push((pos, color));
+                stops.sort_by(|(pos1, _), (pos2, _)| pos1.partial_cmp(pos2).unwrap_or(std::cmp::Ordering::Equal));
+            }
+        }
+    }
+
+    ///
+    /// Returns the stops for a gradient
+    ///
+    fn gradient_stops(&self, gradient_id: GradientId) -> Vec<(f32, Color)> {
+        self.gradients.get(&gradient_id).cloned().unwrap_or_else(|| vec![])
+    }
+
+    ///
+    /// Defines or updates a texture
+    ///
+    fn update_texture(&mut self, texture_id: TextureId, op: TextureOp) {
+        match op {
+            TextureOp::Create(width, height, TextureFormat::Rgba) => {
+                let texture = CairoTexture {
+                    width:  width as i32,
+                    height: height as i32,
+                    pixels: vec![0; (width as usize)*(height as usize)*4]
+                };
+
+                self.textures.insert(texture_id, Rc::new(texture));
+            }
+
+            TextureOp::SetBytes(x, y, width, height, bytes) => {
+                if let Some(texture) = self.textures.get_mut(&texture_id) {
+                    let texture = Rc::make_mut(texture);
+                    let stride  = (texture.width as usize)*4;
+
+                    for ypos in 0..(height as usize) {
+                        for xpos in 0..(width as usize) {
+                            // Clip to the texture
+                            let (tx, ty) = (xpos + (x as usize), ypos + (y as usize));
+                            if tx >= texture.width as usize || ty >= texture.height as usize { continue; }
+
+                            // Read the RGBA source pixel
+                            let src_idx = (ypos*(width as usize) + xpos)*4;
+                            if src_idx+4 > bytes.len() { continue; }
+
+                            let (r, g, b, a)    = (bytes[src_idx] as u32, bytes[src_idx+1] as u32, bytes[src_idx+2] as u32, bytes[src_idx+3] as u32);
+                            let premultiply     = |c: u32| (c*a + 127)/255;
+
+                            // Write as a premultiplied, native-endian ARGB32 pixel
+                            let pixel           = (a << 24) | (premultiply(r) << 16) | (premultiply(g) << 8) | premultiply(b);
+                            let dst_idx         = ty*stride + tx*4;
+                            texture.pixels[dst_idx..(dst_idx+4)].copy_from_slice(&pixel.to_ne_bytes());
+                        }
+                    }
+                }
+            }
+
+            TextureOp::Free => { self.textures.remove(&texture_id); }
+        }
+    }
+
+    ///
+    /// Draws a texture into the rectangle between two points
+    ///
+    fn draw_texture(&mut self, texture_id: TextureId, (x1, y1): (f32, f32), (x2, y2): (f32, f32), alpha: f32) {
+        let texture = if let Some(texture) = self.textures.get(&texture_id) { texture } else { return; };
+        if texture.width <= 0 || texture.height <= 0 { return; }
+
+        let surface = ImageSurface::create_for_data(texture.pixels.clone(), Format::ARgb32, texture.width, texture.height, texture.width*4);
+        let surface = if let Ok(surface) = surface { surface } else { return; };
+
+        // Map the texture onto the rectangle (the first row of the texture is at y1)
+        self.ctxt.save();
+        self.ctxt.translate(x1 as f64, y1 as f64);
+        self.ctxt.scale(((x2-x1) as f64)/(texture.width as f64), ((y2-y1) as f64)/(texture.height as f64));
+
+        self.ctxt.set_source_surface(&surface, 0.0, 0.0);
+        self.ctxt.paint_with_alpha(alpha as f64);
+        self.ctxt.restore();
+
+        self.set_color = ColorTarget::None;
+    }
+
     ///
     /// Converts a blend mode into an operator
     ///
@@ -289,6 +453,9 @@ impl CairoDraw {
         let fill_color      = self.fill_color;
         let stroke_color    = self.stroke_color;
         let dash_pattern    = self.dash_pattern.clone();
+        let fill_gradient   = self.fill_gradient.clone();
+        let gradients       = self.gradients.clone();
+        let textures        = self.textures.clone();
 
         CairoState {
             transform,
@@ -297,7 +464,10 @@ impl CairoDraw {
             line_cap,
             fill_color,
             stroke_color,
-            dash_pattern
+            dash_pattern,
+            fill_gradient,
+            gradients,
+            textures
         }
     }
 
@@ -312,6 +482,9 @@ impl CairoDraw {
         self.fill_color     = state.fill_color;
         self.stroke_color   = state.stroke_color;
         self.dash_pattern   = state.dash_pattern.clone();
+        self.fill_gradient  = state.fill_gradient.clone();
+        self.gradients      = state.gradients.clone();
+        self.textures       = state.textures.clone();
         self.set_color      = ColorTarget::None;
     }
 
@@ -336,8 +509,13 @@ impl CairoDraw {
             NewDashPattern                              => { self.dash_pattern = vec![]; self.ctxt.set_dash(&[], 0.0); },
             DashLength(length)                          => { self.dash_pattern.push(length as f64); self.ctxt.set_dash(&self.dash_pattern, self.ctxt.get_dash_offset()); },
             DashOffset(offset)                          => { self.ctxt.set_dash(&self.dash_pattern, offset as f64); },
-            FillColor(color)                            => { self.set_color = ColorTarget::None; self.fill_color = color; },
+            FillColor(color)                            => { self.set_color = ColorTarget::None; self.fill_color = color; self.fill_gradient = None; },
             StrokeColor(color)                          => { self.set_color = ColorTarget::None; self.stroke_color = color; },
+            Gradient(gradient_id, op)                   => { self.update_gradient(gradient_id, op); },
+            FillGradient(gradient_id, start, end)       => { self.set_color = ColorTarget::None; self.fill_gradient = Some(GradientFill::Linear(start, end, self.gradient_stops(gradient_id))); },
+            FillRadialGradient(gradient_id, center, radius) => { self.set_color = ColorTarget::None; self.fill_gradient = Some(GradientFill::Radial(center, radius, self.gradient_stops(gradient_id))); },
+            Texture(texture_id, op)                     => { self.update_texture(texture_id, op); },
+            DrawTexture(texture_id, min, max, alpha)    => { self.draw_texture(texture_id, min, max, alpha); },
             BlendMode(blend)                            => { self.ctxt.set_operator(Self::get_operator(blend)); },
             IdentityTransform                           => { self.ctxt.set_matrix(self.initial_matrix); },
             MultiplyTransform(transform)                => { self.ctxt.transform(Self::get_transform(transform)); },
@@ -377,6 +555,7 @@ impl CairoDraw {
                 // Reset state
                 self.fill_color     = Color::Rgba(0.0, 0.0, 0.0, 1.0);
                 self.stroke_color   = Color::Rgba(0.0, 0.0, 0.0, 1.0);
+                self.fill_gradient  = None;
                 self.set_color      = ColorTarget::None;
                 self.dash_pattern   = vec![];
 
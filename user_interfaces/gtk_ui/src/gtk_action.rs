@@ -123,6 +123,9 @@ pub enum WidgetContent {
     /// Sets the tooltip for this control to the specified text
     Tooltip(Option<String>),
 
+    /// Copies the specified text to the clipboard when this widget is clicked
+    CopyOnClick(String),
+
     /// Specifies a drawing to perform on this widget
     Draw(Vec<canvas::Draw>)
 }
@@ -36,17 +36,23 @@ pub enum GtkEventParameter {
     /// Painting cancelled
     PaintCancel(PaintDevice),
 
-    /// User has started dragging over a widget
-    DragStart(f64, f64),
+    /// User has started dragging over a widget (with the modifier keys that are held down)
+    DragStart(f64, f64, ModifierKeys),
 
     /// User is continuing to drag a widget
-    DragContinue((f64, f64), (f64, f64)),
+    DragContinue((f64, f64), (f64, f64), ModifierKeys),
 
     /// User has finished dragging a widget
-    DragFinish((f64, f64), (f64, f64)),
+    DragFinish((f64, f64), (f64, f64), ModifierKeys),
 
     /// Virtual scroll region has moved (tuples are the x and y coordinates and the width and height of the grid)
-    VirtualScroll((u32, u32), (u32, u32))
+    VirtualScroll((u32, u32), (u32, u32)),
+
+    /// Scroll wheel has moved (tuples are the pointer position and the distance scrolled)
+    ScrollWheel((f64, f64), (f64, f64)),
+
+    /// Pinch gesture has updated (center of the gesture, change in scale and change in rotation)
+    Pinch((f64, f64), f64, f64)
 }
 
 ///
@@ -63,18 +69,20 @@ pub struct GtkPainting {
 impl From<GtkEventParameter> for ActionParameter {
     fn from(event: GtkEventParameter) -> ActionParameter {
         match event {
-            GtkEventParameter::None                                         => ActionParameter::None,
-            GtkEventParameter::ScaleValue(value)                            => ActionParameter::Value(PropertyValue::Float(value)),
-            GtkEventParameter::SelectedValue(value)                         => ActionParameter::Value(PropertyValue::Bool(value)),
-            GtkEventParameter::NewText(value)                               => ActionParameter::Value(PropertyValue::String(value)),
-            GtkEventParameter::PaintStart(paint)                            => ActionParameter::Paint(paint.get_device(), vec![ paint.to_painting(PaintAction::Start) ]),
-            GtkEventParameter::PaintContinue(paint)                         => ActionParameter::Paint(paint.get_device(), vec![ paint.to_painting(PaintAction::Continue) ]),
-            GtkEventParameter::PaintFinish(paint)                           => ActionParameter::Paint(paint.get_device(), vec![ paint.to_painting(PaintAction::Finish) ]),
-            GtkEventParameter::PaintCancel(device)                          => ActionParameter::Paint(device, vec![]),
-            GtkEventParameter::DragStart(x, y)                              => ActionParameter::Drag(DragAction::Start, (x as f32, y as f32), (x as f32, y as f32)),
-            GtkEventParameter::DragContinue((from_x, from_y), (to_x, to_y)) => ActionParameter::Drag(DragAction::Drag, (from_x as f32, from_y as f32), (to_x as f32, to_y as f32)),
-            GtkEventParameter::DragFinish((from_x, from_y), (to_x, to_y))   => ActionParameter::Drag(DragAction::Finish, (from_x as f32, from_y as f32), (to_x as f32, to_y as f32)),
-            GtkEventParameter::VirtualScroll(top_left, size)                => ActionParameter::VirtualScroll(top_left, size)
+            GtkEventParameter::None                                                 => ActionParameter::None,
+            GtkEventParameter::ScaleValue(value)                                    => ActionParameter::Value(PropertyValue::Float(value)),
+            GtkEventParameter::SelectedValue(value)                                 => ActionParameter::Value(PropertyValue::Bool(value)),
+            GtkEventParameter::NewText(value)                                       => ActionParameter::Value(PropertyValue::String(value)),
+            GtkEventParameter::PaintStart(paint)                                    => ActionParameter::Paint(paint.get_device(), vec![ paint.to_painting(PaintAction::Start) ]),
+            GtkEventParameter::PaintContinue(paint)                                 => ActionParameter::Paint(paint.get_device(), vec![ paint.to_painting(PaintAction::Continue) ]),
+            GtkEventParameter::PaintFinish(paint)                                   => ActionParameter::Paint(paint.get_device(), vec![ paint.to_painting(PaintAction::Finish) ]),
+            GtkEventParameter::PaintCancel(device)                                  => ActionParameter::Paint(device, vec![]),
+            GtkEventParameter::DragStart(x, y, keys)                                => ActionParameter::Drag(DragAction::Start, (x as f32, y as f32), (x as f32, y as f32), keys),
+            GtkEventParameter::DragContinue((from_x, from_y), (to_x, to_y), keys)   => ActionParameter::Drag(DragAction::Drag, (from_x as f32, from_y as f32), (to_x as f32, to_y as f32), keys),
+            GtkEventParameter::DragFinish((from_x, from_y), (to_x, to_y), keys)     => ActionParameter::Drag(DragAction::Finish, (from_x as f32, from_y as f32), (to_x as f32, to_y as f32), keys),
+            GtkEventParameter::VirtualScroll(top_left, size)                        => ActionParameter::VirtualScroll(top_left, size),
+            GtkEventParameter::ScrollWheel((x, y), (dx, dy))                        => ActionParameter::ScrollWheel((x as f32, y as f32), (dx as f32, dy as f32)),
+            GtkEventParameter::Pinch((x, y), scale, rotation)                       => ActionParameter::Pinch((x as f32, y as f32), scale as f32, rotation as f32)
         }
     }
 }
@@ -65,6 +65,12 @@ pub enum GtkWidgetEventType {
     /// Performs virtual scrolling using a grid with the specified width and height
     VirtualScroll(f32, f32),
 
+    /// User moved the scroll wheel over the widget
+    ScrollWheel,
+
+    /// User performed a pinch gesture over the widget
+    Pinch,
+
     /// User has interacted outside of this widget
     Dismiss
 }
@@ -207,7 +207,8 @@ impl ToGtkActions for Hint {
     fn to_gtk_actions(&self) -> Vec<PropertyWidgetAction> {
         match self {
             Hint::FastDrawing       => vec![],
-            Hint::Class(class_name) => vec![ GtkWidgetAction::Content(WidgetContent::AddClass(class_name.clone())) ].into_actions()
+            Hint::Class(class_name) => vec![ GtkWidgetAction::Content(WidgetContent::AddClass(class_name.clone())) ].into_actions(),
+            Hint::CopyOnClick(text) => vec![ GtkWidgetAction::Content(WidgetContent::CopyOnClick(text.clone())) ].into_actions()
         }
     }
 }
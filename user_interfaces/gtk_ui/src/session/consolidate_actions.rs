@@ -70,10 +70,10 @@ impl<ActionStream: Stream<Item=Vec<UiEvent>>+Unpin> ConsolidateActionsStream<Act
                     }
                 },
 
-                (UiEvent::Action(controller1, event_name1, ActionParameter::Drag(DragAction::Drag, _from1, _to1)), UiEvent::Action(controller2, event_name2, ActionParameter::Drag(DragAction::Drag, from2, to2))) => {
+                (UiEvent::Action(controller1, event_name1, ActionParameter::Drag(DragAction::Drag, _from1, _to1, _keys1)), UiEvent::Action(controller2, event_name2, ActionParameter::Drag(DragAction::Drag, from2, to2, keys2))) => {
                     if event_name1 == event_name2 && controller1 == controller2 {
                         // Only the most recent drag continue event makes it through
-                        events[index] = UiEvent::Action(controller1, event_name1, ActionParameter::Drag(DragAction::Drag, from2, to2));
+                        events[index] = UiEvent::Action(controller1, event_name1, ActionParameter::Drag(DragAction::Drag, from2, to2, keys2));
                         events.remove(index+1);
                     } else {
                         // Two drag continue events but for different controls
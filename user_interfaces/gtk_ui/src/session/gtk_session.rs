@@ -601,7 +601,9 @@ impl GtkSessionCore {
                     CancelEdit                      => vec![ /* TODO */ ],
                     EditValue                       => vec![ RequestEvent(GtkWidgetEventType::EditValue, action_name) ],
                     SetValue                        => vec![ RequestEvent(GtkWidgetEventType::SetValue, action_name) ],
-                    VirtualScroll(width, height)    => vec![ RequestEvent(GtkWidgetEventType::VirtualScroll(width, height), action_name) ]
+                    VirtualScroll(width, height)    => vec![ RequestEvent(GtkWidgetEventType::VirtualScroll(width, height), action_name) ],
+                    ScrollWheel                     => vec![ RequestEvent(GtkWidgetEventType::ScrollWheel, action_name) ],
+                    Pinch                           => vec![ RequestEvent(GtkWidgetEventType::Pinch, action_name) ]
                 }
             })
             .collect()
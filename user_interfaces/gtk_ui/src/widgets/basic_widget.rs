@@ -1,6 +1,7 @@
 use super::drag::*;
 use super::click::*;
 use super::paint::*;
+use super::gesture::*;
 use super::layout::*;
 use super::widget::*;
 use super::flo_layout::*;
@@ -22,6 +23,11 @@ use std::cell::*;
 ///
 pub struct BasicWidget(pub WidgetId, pub gtk::Widget);
 
+///
+/// Widget data storing the signal handler that copies text to the clipboard when a widget is clicked
+///
+struct CopyOnClickHandler(Option<glib::SignalHandlerId>);
+
 impl BasicWidget {
     ///
     /// Creates a basic widget
@@ -188,6 +194,33 @@ pub fn process_basic_widget_content<W: GtkUiWidget>(widget: &mut W, flo_gtk: &mu
             widget.set_tooltip_text(tooltip.as_ref().map(|text| text.as_str()));
         }
 
+        &CopyOnClick(ref text)          => {
+            let text        = text.clone();
+            let widget_id   = widget.id();
+            let widget      = widget.get_underlying();
+            let widget_data = flo_gtk.widget_data();
+
+            // Replace any handler added by an earlier request for this widget
+            let previous_handler = widget_data.get_widget_data::<CopyOnClickHandler>(widget_id)
+                .and_then(|handler| handler.borrow_mut().0.take());
+            if let Some(previous_handler) = previous_handler {
+                widget.disconnect(previous_handler);
+            }
+
+            // Copy the text when the left button is released (the event isn't inhibited so any click action will still fire)
+            widget.add_events(gdk::EventMask::BUTTON_RELEASE_MASK);
+            let handler = widget.connect_button_release_event(move |_, button| {
+                if button.get_button() == 1 {
+                    let clipboard = gtk::Clipboard::get(&gdk::SELECTION_CLIPBOARD);
+                    clipboard.set_text(&text);
+                }
+
+                Inhibit(false)
+            });
+
+            widget_data.set_widget_data(widget_id, CopyOnClickHandler(Some(handler)));
+        }
+
         &AddClass(ref class_name)       => {
             let widget          = widget.get_underlying();
             let style_context   = widget.get_style_context();
@@ -312,6 +345,14 @@ pub fn process_basic_event_request<W: GtkUiWidget>(widget: &W, flo_gtk: &mut Flo
             DragActions::wire_widget(flo_gtk.widget_data(), event_sink, widget, action_name.clone());
         },
 
+        ScrollWheel => {
+            ScrollWheelActions::wire_widget(flo_gtk.widget_data(), event_sink, widget, action_name.clone());
+        },
+
+        Pinch => {
+            PinchActions::wire_widget(flo_gtk.widget_data(), event_sink, widget, action_name.clone());
+        },
+
         VirtualScroll(_, _) | EditValue | SetValue | Dismiss => { }
     }
 }
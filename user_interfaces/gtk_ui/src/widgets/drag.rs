@@ -5,6 +5,8 @@ use super::super::gtk_thread::*;
 use super::super::gtk_action::*;
 use super::super::gtk_event_parameter::*;
 
+use flo_ui::*;
+
 use gtk;
 use gtk::prelude::*;
 use gdk;
@@ -81,6 +83,18 @@ impl DragActions {
         (position.0 as f64, position.1 as f64)
     }
 
+    ///
+    /// Returns the modifier keys that are held down for an event state
+    ///
+    fn modifier_keys(state: gdk::ModifierType) -> ModifierKeys {
+        ModifierKeys {
+            shift:      state.contains(gdk::ModifierType::SHIFT_MASK),
+            control:    state.contains(gdk::ModifierType::CONTROL_MASK),
+            alt:        state.contains(gdk::ModifierType::MOD1_MASK),
+            command:    state.contains(gdk::ModifierType::SUPER_MASK) || state.contains(gdk::ModifierType::META_MASK)
+        }
+    }
+
     ///
     /// Connects the events for a drag actions object
     ///
@@ -104,6 +118,7 @@ impl DragActions {
             if !drag_actions.dragging {
                 // Start dragging
                 let position = Self::drag_position_for_position(widget, button.get_position());
+                let keys     = Self::modifier_keys(button.get_state());
 
                 drag_actions.dragging       = true;
                 drag_actions.start_point    = position;
@@ -113,7 +128,7 @@ impl DragActions {
                 let event_sink  = &drag_actions.event_sink;
 
                 event_names.into_iter().for_each(|name| {
-                    publish_event(event_sink, GtkEvent::Event(widget_id, name, GtkEventParameter::DragStart(position.0, position.1)));
+                    publish_event(event_sink, GtkEvent::Event(widget_id, name, GtkEventParameter::DragStart(position.0, position.1, keys)));
                 });
 
                 Inhibit(true)
@@ -133,6 +148,7 @@ impl DragActions {
             if drag_actions.dragging {
                 // Continue dragging
                 let position = Self::drag_position_for_position(widget, button.get_position());
+                let keys     = Self::modifier_keys(button.get_state());
 
                 // Send the start event
                 let start_point = drag_actions.start_point;
@@ -140,7 +156,7 @@ impl DragActions {
                 let event_sink  = &drag_actions.event_sink;
 
                 event_names.into_iter().for_each(|name| {
-                    publish_event(event_sink, GtkEvent::Event(widget_id, name, GtkEventParameter::DragContinue(start_point, position)));
+                    publish_event(event_sink, GtkEvent::Event(widget_id, name, GtkEventParameter::DragContinue(start_point, position, keys)));
                 });
 
                 Inhibit(true)
@@ -162,14 +178,15 @@ impl DragActions {
                 drag_actions.dragging = false;
 
                 let position = Self::drag_position_for_position(widget, button.get_position());
+                let keys     = Self::modifier_keys(button.get_state());
 
-                // Send the start event
+                // Send the finish event
                 let start_point = drag_actions.start_point;
                 let event_names = drag_actions.event_names.clone();
                 let event_sink  = &drag_actions.event_sink;
 
                 event_names.into_iter().for_each(|name| {
-                    publish_event(&event_sink, GtkEvent::Event(widget_id, name, GtkEventParameter::DragContinue(start_point, position)));
+                    publish_event(&event_sink, GtkEvent::Event(widget_id, name, GtkEventParameter::DragFinish(start_point, position, keys)));
                 });
 
                 Inhibit(true)
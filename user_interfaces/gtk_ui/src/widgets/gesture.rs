@@ -0,0 +1,229 @@
+use super::widget::*;
+use super::widget_data::*;
+use super::super::gtk_event::*;
+use super::super::gtk_thread::*;
+use super::super::gtk_event_parameter::*;
+
+use gtk;
+use gtk::prelude::*;
+use gdk;
+use cairo;
+
+use std::rc::*;
+use std::cell::*;
+
+/// Number of pixels that a single (non-smooth) click of the scroll wheel moves
+const SCROLL_STEP: f64 = 32.0;
+
+///
+/// Returns the transformation matrix for the canvas in a widget (or the identity matrix if there's no canvas)
+///
+fn transform_for_widget(widget_data: &WidgetData, widget_id: WidgetId) -> cairo::Matrix {
+    widget_data.get_widget_data::<cairo::Matrix>(widget_id)
+        .map(|transform| *transform.borrow())
+        .unwrap_or_else(|| cairo::Matrix::identity())
+}
+
+///
+/// Provides the implementation of the 'scroll wheel' action for Flo widgets
+///
+pub struct ScrollWheelActions {
+    /// Where events for these actions should be sent
+    event_sink: GtkEventSink,
+
+    /// Names of the events to generate for this widget
+    event_names: Vec<String>
+}
+
+impl ScrollWheelActions {
+    ///
+    /// Wires a widget up for the scroll wheel action
+    ///
+    pub fn wire_widget<W: GtkUiWidget>(widget_data: Rc<WidgetData>, event_sink: GtkEventSink, widget: &W, event_name: String) {
+        let widget_id       = widget.id();
+        let scroll_wiring   = widget_data.get_widget_data::<ScrollWheelActions>(widget_id);
+
+        match scroll_wiring {
+            Some(existing_wiring) => {
+                // Scroll actions are already attached to this widget: just add new event names
+                existing_wiring.borrow_mut().event_names.push(event_name)
+            },
+
+            None => {
+                // Create some new wiring
+                let scroll_wiring = ScrollWheelActions {
+                    event_sink:     event_sink,
+                    event_names:    vec![event_name]
+                };
+
+                widget_data.set_widget_data(widget_id, scroll_wiring);
+
+                // Connect events
+                let scroll_wiring = widget_data.get_widget_data::<ScrollWheelActions>(widget_id).unwrap();
+                Self::connect_events(widget_data, widget.get_underlying(), widget_id, Rc::clone(&*scroll_wiring));
+            }
+        }
+    }
+
+    ///
+    /// Connects the scroll event for a widget
+    ///
+    fn connect_events(widget_data: Rc<WidgetData>, widget: &gtk::Widget, widget_id: WidgetId, scroll_actions: Rc<RefCell<Self>>) {
+        widget.add_events(gdk::EventMask::SCROLL_MASK | gdk::EventMask::SMOOTH_SCROLL_MASK);
+
+        widget.connect_scroll_event(move |_widget, event| {
+            let scroll_actions  = scroll_actions.borrow();
+
+            // Work out how far the wheel has moved
+            let delta           = match event.get_direction() {
+                gdk::ScrollDirection::Up        => (0.0, -SCROLL_STEP),
+                gdk::ScrollDirection::Down      => (0.0, SCROLL_STEP),
+                gdk::ScrollDirection::Left      => (-SCROLL_STEP, 0.0),
+                gdk::ScrollDirection::Right     => (SCROLL_STEP, 0.0),
+                _                               => { let (dx, dy) = event.get_delta(); (dx*SCROLL_STEP, dy*SCROLL_STEP) }
+            };
+
+            // Position is in canvas coordinates if the widget has a canvas
+            let transform       = transform_for_widget(&*widget_data, widget_id);
+            let (x, y)          = event.get_position();
+            let position        = transform.transform_point(x, y);
+
+            // Send the events
+            scroll_actions.event_names.iter().for_each(|name| {
+                publish_event(&scroll_actions.event_sink, GtkEvent::Event(widget_id, name.clone(), GtkEventParameter::ScrollWheel(position, delta)));
+            });
+
+            Inhibit(true)
+        });
+    }
+}
+
+///
+/// Provides the implementation of the 'pinch' action for Flo widgets
+///
+pub struct PinchActions {
+    /// Where events for these actions should be sent
+    event_sink: GtkEventSink,
+
+    /// Names of the events to generate for this widget
+    event_names: Vec<String>,
+
+    /// The zoom gesture recognizer (kept alive for as long as the widget is wired)
+    zoom_gesture: Option<gtk::GestureZoom>,
+
+    /// The rotate gesture recognizer
+    rotate_gesture: Option<gtk::GestureRotate>,
+
+    /// The scale the last time the zoom gesture updated
+    last_scale: f64,
+
+    /// The angle the last time the rotate gesture updated
+    last_angle: f64
+}
+
+impl PinchActions {
+    ///
+    /// Wires a widget up for the pinch action
+    ///
+    pub fn wire_widget<W: GtkUiWidget>(widget_data: Rc<WidgetData>, event_sink: GtkEventSink, widget: &W, event_name: String) {
+        let widget_id       = widget.id();
+        let pinch_wiring    = widget_data.get_widget_data::<PinchActions>(widget_id);
+
+        match pinch_wiring {
+            Some(existing_wiring) => {
+                // Pinch actions are already attached to this widget: just add new event names
+                existing_wiring.borrow_mut().event_names.push(event_name)
+            },
+
+            None => {
+                // Create some new wiring
+                let pinch_wiring = PinchActions {
+                    event_sink:     event_sink,
+                    event_names:    vec![event_name],
+                    zoom_gesture:   None,
+                    rotate_gesture: None,
+                    last_scale:     1.0,
+                    last_angle:     0.0
+                };
+
+                widget_data.set_widget_data(widget_id, pinch_wiring);
+
+                // Connect events
+                let pinch_wiring = widget_data.get_widget_data::<PinchActions>(widget_id).unwrap();
+                Self::connect_events(widget_data, widget.get_underlying(), widget_id, Rc::clone(&*pinch_wiring));
+            }
+        }
+    }
+
+    ///
+    /// Sends a pinch event to the event sink
+    ///
+    fn send_pinch(&self, widget_data: &WidgetData, widget_id: WidgetId, center: (f64, f64), scale: f64, rotation: f64) {
+        let transform   = transform_for_widget(widget_data, widget_id);
+        let center      = transform.transform_point(center.0, center.1);
+
+        self.event_names.iter().for_each(|name| {
+            publish_event(&self.event_sink, GtkEvent::Event(widget_id, name.clone(), GtkEventParameter::Pinch(center, scale, rotation)));
+        });
+    }
+
+    ///
+    /// Connects the gesture recognizers for a widget
+    ///
+    fn connect_events(widget_data: Rc<WidgetData>, widget: &gtk::Widget, widget_id: WidgetId, pinch_actions: Rc<RefCell<Self>>) {
+        widget.add_events(gdk::EventMask::TOUCH_MASK);
+
+        let zoom    = gtk::GestureZoom::new(widget);
+        let rotate  = gtk::GestureRotate::new(widget);
+
+        // The gestures report values relative to the start of the gesture, so track the last values to generate changes
+        {
+            let pinch_actions = Rc::clone(&pinch_actions);
+            zoom.connect_begin(move |_gesture, _sequence| {
+                pinch_actions.borrow_mut().last_scale = 1.0;
+            });
+        }
+
+        {
+            let pinch_actions = Rc::clone(&pinch_actions);
+            rotate.connect_begin(move |_gesture, _sequence| {
+                pinch_actions.borrow_mut().last_angle = 0.0;
+            });
+        }
+
+        {
+            let pinch_actions   = Rc::clone(&pinch_actions);
+            let widget_data     = Rc::clone(&widget_data);
+            zoom.connect_scale_changed(move |gesture, scale| {
+                let mut pinch_actions   = pinch_actions.borrow_mut();
+                let last_scale          = pinch_actions.last_scale;
+                let center              = gesture.get_bounding_box_center().unwrap_or((0.0, 0.0));
+
+                if last_scale > 0.0 && scale > 0.0 {
+                    pinch_actions.last_scale = scale;
+                    pinch_actions.send_pinch(&*widget_data, widget_id, center, scale/last_scale, 0.0);
+                }
+            });
+        }
+
+        {
+            let pinch_actions   = Rc::clone(&pinch_actions);
+            let widget_data     = Rc::clone(&widget_data);
+            rotate.connect_angle_changed(move |gesture, _angle, angle_delta| {
+                let mut pinch_actions   = pinch_actions.borrow_mut();
+                let last_angle          = pinch_actions.last_angle;
+                let center              = gesture.get_bounding_box_center().unwrap_or((0.0, 0.0));
+
+                pinch_actions.last_angle = angle_delta;
+
+                // GTK measures angles clockwise in window coordinates, but the canvas y axis points upwards
+                pinch_actions.send_pinch(&*widget_data, widget_id, center, 1.0, -(angle_delta-last_angle));
+            });
+        }
+
+        // Store the gestures so they stay alive along with the widget
+        let mut pinch_actions           = pinch_actions.borrow_mut();
+        pinch_actions.zoom_gesture      = Some(zoom);
+        pinch_actions.rotate_gesture    = Some(rotate);
+    }
+}
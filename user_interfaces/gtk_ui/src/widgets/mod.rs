@@ -26,6 +26,7 @@ mod image;
 mod click;
 mod drag;
 mod paint;
+mod gesture;
 mod events;
 mod scroll_size;
 
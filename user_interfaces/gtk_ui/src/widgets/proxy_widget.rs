@@ -80,9 +80,11 @@ impl<Widget> GtkUiWidget for ProxyWidget<Widget> {
             },
 
             // Events should be processed by the proxy widget if they pass through the main widget
-            RequestEvent(Click, _)  |
-            RequestEvent(Drag, _)   |
-            RequestEvent(Paint(_), _)  => {
+            RequestEvent(Click, _)          |
+            RequestEvent(Drag, _)           |
+            RequestEvent(ScrollWheel, _)    |
+            RequestEvent(Pinch, _)          |
+            RequestEvent(Paint(_), _)       => {
                 // Some widgets (eg, fixed boxes) can't process mouse events directly, so we track them in the proxy widget instead
                 process_basic_widget_action(self, flo_gtk, action);
                 self.underlying_widget.borrow_mut().process(flo_gtk, action);
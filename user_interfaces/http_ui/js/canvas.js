@@ -162,6 +162,8 @@ let flo_canvas = (function() {
         let current_sprite              = [ ];
         let sprites                     = { };
         let sprite_transform            = [1,0,0, 0,1,0, 0,0,1];
+        let gradients                   = { };
+        let textures                    = { };
 
         ///
         /// Sets the current transform (lack of browser support for currentTransform means we have to track this independently)
@@ -221,6 +223,29 @@ let flo_canvas = (function() {
             inverse_transform   = null;
         }
 
+        ///
+        /// Converts a colour to a CSS colour string
+        ///
+        function css_color(r, g, b, a) {
+            r = Math.floor(r*255.0);
+            g = Math.floor(g*255.0);
+            b = Math.floor(b*255.0);
+
+            return 'rgba(' + r + ',' + g + ',' + b + ',' + a + ')';
+        }
+
+        ///
+        /// Adds the stops for a gradient to a canvas gradient object
+        ///
+        function add_gradient_stops(canvas_gradient, gradient_id) {
+            let stops = gradients[gradient_id] || [ [0.0, 0,0,0,1] ];
+
+            stops.forEach(stop => {
+                let pos = Math.min(1.0, Math.max(0.0, stop[0]));
+                canvas_gradient.addColorStop(pos, css_color(stop[1], stop[2], stop[3], stop[4]));
+            });
+        }
+
         ///
         /// Copies the contents of one canvas to another one
         ///
@@ -311,6 +336,75 @@ let flo_canvas = (function() {
                 context.strokeStyle = 'rgba(' + r + ',' + g + ',' + b + ',' + a + ')';
             },
 
+            gradient_create: (gradient_id, r, g, b, a) => {
+                gradients[gradient_id] = [ [0.0, r, g, b, a] ];
+            },
+
+            gradient_add_stop: (gradient_id, pos, r, g, b, a) => {
+                let stops = gradients[gradient_id];
+                if (!stops) {
+                    stops = gradients[gradient_id] = [];
+                }
+
+                stops.push([pos, r, g, b, a]);
+                stops.sort((a, b) => a[0] - b[0]);
+            },
+
+            fill_gradient: (gradient_id, x1, y1, x2, y2) => {
+                // The stops are read at the point the fill is set, so later changes to the gradient don't affect this fill
+                let linear_gradient = context.createLinearGradient(x1, y1, x2, y2);
+                add_gradient_stops(linear_gradient, gradient_id);
+
+                context.fillStyle = linear_gradient;
+            },
+
+            fill_radial_gradient: (gradient_id, x, y, radius) => {
+                let radial_gradient = context.createRadialGradient(x, y, 0, x, y, radius);
+                add_gradient_stops(radial_gradient, gradient_id);
+
+                context.fillStyle = radial_gradient;
+            },
+
+            texture_create: (texture_id, width, height) => {
+                // Textures are stored as offscreen canvases
+                let texture_canvas      = document.createElement('canvas');
+                texture_canvas.width    = width;
+                texture_canvas.height   = height;
+
+                textures[texture_id]    = texture_canvas;
+            },
+
+            texture_set_bytes: (texture_id, x, y, width, height, bytes) => {
+                let texture_canvas = textures[texture_id];
+                if (!texture_canvas || width <= 0 || height <= 0) {
+                    return;
+                }
+
+                let image_data = new ImageData(width, height);
+                image_data.data.set(bytes.subarray(0, Math.min(bytes.length, width*height*4)));
+
+                texture_canvas.getContext('2d').putImageData(image_data, x, y);
+            },
+
+            texture_free: (texture_id) => {
+                delete textures[texture_id];
+            },
+
+            draw_texture: (texture_id, x1, y1, x2, y2, alpha) => {
+                let texture_canvas = textures[texture_id];
+                if (!texture_canvas || texture_canvas.width <= 0 || texture_canvas.height <= 0) {
+                    return;
+                }
+
+                // Map the texture onto the rectangle (the first row of the texture is drawn at y1)
+                context.save();
+                context.globalAlpha = alpha;
+                context.translate(x1, y1);
+                context.scale((x2-x1)/texture_canvas.width, (y2-y1)/texture_canvas.height);
+                context.drawImage(texture_canvas, 0, 0);
+                context.restore();
+            },
+
             line_width: (width) => {
                 context.lineWidth = width;
             },
@@ -747,6 +841,14 @@ let flo_canvas = (function() {
             return flo_matrix.mulvec3(inverse_transform, [x*ratio, y*ratio, 1]);
         }
 
+        function snapshot_map_coords() {
+            // Captures the active transformation so coordinates stay consistent if the canvas transform changes (eg, for the duration of a paint stroke)
+            let inverse = flo_matrix.invert3(transform);
+            let ratio   = canvas.width / canvas.clientWidth;
+
+            return (x, y) => flo_matrix.mulvec3(inverse, [x*ratio, y*ratio, 1]);
+        }
+
         function draw_layers() {
             // If we're using layers, then this must be called to update the canvas (if layers are not in use, it'll update directly)
             // (This is a bit awkward if we're updating the canvas manually: we want to avoid calling this too often, though)
@@ -787,6 +889,14 @@ let flo_canvas = (function() {
             dash_offset:                    (offset)                    => { current_sprite.push([dash_offset, [offset]]); },
             fill_color:                     (r, g, b, a)                => { current_sprite.push([fill_color, [r, g, b, a]]); },
             stroke_color:                   (r, g, b, a)                => { current_sprite.push([stroke_color, [r, g, b, a]]); },
+            gradient_create:                (id, r, g, b, a)            => { layer_renderer.gradient_create(id, r, g, b, a); },
+            gradient_add_stop:              (id, pos, r, g, b, a)       => { layer_renderer.gradient_add_stop(id, pos, r, g, b, a); },
+            fill_gradient:                  (id, x1, y1, x2, y2)        => { current_sprite.push([fill_gradient, [id, x1, y1, x2, y2]]); },
+            fill_radial_gradient:           (id, x, y, radius)          => { current_sprite.push([fill_radial_gradient, [id, x, y, radius]]); },
+            texture_create:                 (id, width, height)         => { layer_renderer.texture_create(id, width, height); },
+            texture_set_bytes:              (id, x, y, w, h, bytes)     => { layer_renderer.texture_set_bytes(id, x, y, w, h, bytes); },
+            texture_free:                   (id)                        => { layer_renderer.texture_free(id); },
+            draw_texture:                   (id, x1, y1, x2, y2, alpha) => { current_sprite.push([draw_texture, [id, x1, y1, x2, y2, alpha]]); },
             blend_mode:                     (mode)                      => { current_sprite.push([blend_mode, [mode]]); },
             identity_transform:             ()                          => { current_sprite.push([identity_transform, []]); },
             canvas_height:                  (height)                    => { current_sprite.push([canvas_height, [height]]); },
@@ -834,6 +944,14 @@ let flo_canvas = (function() {
         function dash_offset(offset)                    { render.dash_offset(offset); }
         function fill_color(r, g, b, a)                 { render.fill_color(r, g, b, a); }
         function stroke_color(r, g, b, a)               { render.stroke_color(r, g, b, a); }
+        function gradient_create(id, r, g, b, a)        { render.gradient_create(id, r, g, b, a); }
+        function gradient_add_stop(id, pos, r, g, b, a) { render.gradient_add_stop(id, pos, r, g, b, a); }
+        function fill_gradient(id, x1, y1, x2, y2)      { render.fill_gradient(id, x1, y1, x2, y2); }
+        function fill_radial_gradient(id, x, y, radius) { render.fill_radial_gradient(id, x, y, radius); }
+        function texture_create(id, width, height)      { render.texture_create(id, width, height); }
+        function texture_set_bytes(id, x, y, w, h, bytes) { render.texture_set_bytes(id, x, y, w, h, bytes); }
+        function texture_free(id)                       { render.texture_free(id); }
+        function draw_texture(id, x1, y1, x2, y2, alpha) { render.draw_texture(id, x1, y1, x2, y2, alpha); }
         function blend_mode(mode)                       { render.blend_mode(mode); }
         function identity_transform()                   { render.identity_transform(); }
         function canvas_height(height)                  { render.canvas_height(height); }
@@ -862,6 +980,15 @@ let flo_canvas = (function() {
         // The replay log will replay the actions that draw this canvas (for example when resizing)
         let replay  = [ [ clear_canvas, [] ] ];
 
+        // Textures are kept when the canvas is cleared, so their definitions stay in the replay log until they're freed
+        function texture_replay() {
+            return replay.filter(action => action[0] === texture_create || action[0] === texture_set_bytes);
+        }
+
+        function is_texture_action(action, texture_id) {
+            return (action[0] === texture_create || action[0] === texture_set_bytes) && action[1][0] === texture_id;
+        }
+
         return {
             new_path:           ()              => { replay.push([new_path, [], current_layer_id]);                         render.new_path();                     },
             move_to:            (x, y)          => { replay.push([move_to, [x, y], current_layer_id]);                      render.move_to(x, y);                  },
@@ -879,6 +1006,14 @@ let flo_canvas = (function() {
             dash_offset:        (offset)        => { replay.push([dash_offset, [offset], current_layer_id]);                render.dash_length(offset);            },
             fill_color:         (r, g, b, a)    => { replay.push([fill_color, [r, g, b, a], current_layer_id]);             render.fill_color(r, g, b, a);         },
             stroke_color:       (r, g, b, a)    => { replay.push([stroke_color, [r, g, b, a], current_layer_id]);           render.stroke_color(r, g, b, a);       },
+            gradient_create:    (id, r, g, b, a) => { replay.push([gradient_create, [id, r, g, b, a], -1]);                 render.gradient_create(id, r, g, b, a); },
+            gradient_add_stop:  (id, pos, r, g, b, a) => { replay.push([gradient_add_stop, [id, pos, r, g, b, a], -1]);     render.gradient_add_stop(id, pos, r, g, b, a); },
+            fill_gradient:      (id, x1, y1, x2, y2) => { replay.push([fill_gradient, [id, x1, y1, x2, y2], current_layer_id]); render.fill_gradient(id, x1, y1, x2, y2); },
+            fill_radial_gradient: (id, x, y, radius) => { replay.push([fill_radial_gradient, [id, x, y, radius], current_layer_id]); render.fill_radial_gradient(id, x, y, radius); },
+            texture_create:     (id, width, height) => { replay.push([texture_create, [id, width, height], -1]);            render.texture_create(id, width, height); },
+            texture_set_bytes:  (id, x, y, w, h, bytes) => { replay.push([texture_set_bytes, [id, x, y, w, h, bytes], -1]); render.texture_set_bytes(id, x, y, w, h, bytes); },
+            texture_free:       (id)            => { replay = replay.filter(action => !is_texture_action(action, id));      render.texture_free(id);               },
+            draw_texture:       (id, x1, y1, x2, y2, alpha) => { replay.push([draw_texture, [id, x1, y1, x2, y2, alpha], current_layer_id]); render.draw_texture(id, x1, y1, x2, y2, alpha); },
             blend_mode:         (mode)          => { replay.push([blend_mode, [mode], current_layer_id]);                   render.blend_mode(mode);               },
             identity_transform: ()              => { replay.push([identity_transform, [], current_layer_id]);               render.identity_transform();           },
             canvas_height:      (height)        => { replay.push([canvas_height, [height], current_layer_id]);              render.canvas_height(height);          },
@@ -894,7 +1029,7 @@ let flo_canvas = (function() {
             layer:              (layer_id)      => { replay.push([layer, [layer_id], layer]);                               render.layer(layer_id);                },
             layer_blend:        (layer_id, blend_mode) => { replay.push([layer_blend, [layer_id, blend_mode], -1]);         render.layer_blend(layer_id, blend_mode); },
             clear_layer:        ()              => { replay.push([clear_layer, [], current_layer_id]);                      render.clear_layer();                  },
-            clear_canvas:       ()              => { replay = [ [clear_canvas, [], current_layer_id] ];                     render.clear_canvas();                 },
+            clear_canvas:       ()              => { replay = [ [clear_canvas, [], current_layer_id] ].concat(texture_replay()); render.clear_canvas();            },
             sprite:             (sprite_id)     => { replay = [ [sprite, [sprite_id], current_layer_id] ];                  render.sprite(sprite_id);              },
             clear_sprite:       ()              => { replay = [ [clear_sprite, [], current_layer_id] ];                     render.clear_sprite();                 },
             draw_sprite:        (sprite_id)     => { replay = [ [draw_sprite, [sprite_id], current_layer_id] ];             render.draw_sprite(sprite_id);         },
@@ -907,6 +1042,7 @@ let flo_canvas = (function() {
 
             replay_drawing:     replay_drawing,
             map_coords:         map_coords,
+            snapshot_map_coords: snapshot_map_coords,
             draw_layers:        draw_layers,
 
             stats:              ()              => { 
@@ -1036,7 +1172,29 @@ let flo_canvas = (function() {
                 return result;
             };
 
-            let read_sprite_id = read_truncated_u64;
+            let read_sprite_id      = read_truncated_u64;
+            let read_gradient_id    = read_truncated_u64;
+            let read_texture_id     = read_truncated_u64;
+
+            ///
+            /// Reads a set of bytes (encoded as groups of 3 bytes in 4 characters)
+            ///
+            let read_bytes = (len) => {
+                let result = new Uint8Array(len);
+
+                for (let byte_pos = 0; byte_pos < len; byte_pos += 3) {
+                    let value = 0;
+                    for (let p = 0; p<4; ++p) {
+                        value |= fragment_val(read_char()) << (p*6);
+                    }
+
+                    for (let p = 0; p<3 && byte_pos+p < len; ++p) {
+                        result[byte_pos+p] = (value >> (p*8)) & 0xff;
+                    }
+                }
+
+                return result;
+            };
 
             ///
             /// Reads a RGBA colour
@@ -1069,15 +1227,93 @@ let flo_canvas = (function() {
             ///
             let decode_color = () => {
                 let color_target    = read_char();
-                let color           = read_rgba();
 
                 switch (color_target) {
-                case 's':   draw.stroke_color(color[0], color[1], color[2], color[3]);  break;
-                case 'f':   draw.fill_color(color[0], color[1], color[2], color[3]);    break;
+                case 's':   
+                    {
+                        let color = read_rgba();
+                        draw.stroke_color(color[0], color[1], color[2], color[3]);
+                    }
+                    break;
+
+                case 'f':
+                    {
+                        let color = read_rgba();
+                        draw.fill_color(color[0], color[1], color[2], color[3]);
+                    }
+                    break;
+
+                case 'g':   draw.fill_gradient(read_gradient_id(), read_float(), read_float(), read_float(), read_float()); break;
+                case 'r':   draw.fill_radial_gradient(read_gradient_id(), read_float(), read_float(), read_float()); break;
                 default:    throw 'Unknown color target: \'' + color_target + '\'';
                 }
             };
 
+            ///
+            /// Decodes a gradient operation
+            ///
+            let decode_gradient = () => {
+                let gradient_id = read_gradient_id();
+                let operation   = read_char();
+
+                switch (operation) {
+                case 'n':
+                    {
+                        let color = read_rgba();
+                        draw.gradient_create(gradient_id, color[0], color[1], color[2], color[3]);
+                    }
+                    break;
+
+                case 's':
+                    {
+                        let pos     = read_float();
+                        let color   = read_rgba();
+                        draw.gradient_add_stop(gradient_id, pos, color[0], color[1], color[2], color[3]);
+                    }
+                    break;
+
+                default:    throw 'Unknown gradient operation: \'' + operation + '\'';
+                }
+            };
+
+            ///
+            /// Decodes a texture operation
+            ///
+            let decode_texture = () => {
+                let texture_id  = read_texture_id();
+                let operation   = read_char();
+
+                switch (operation) {
+                case 'N':
+                    {
+                        let width   = read_u32();
+                        let height  = read_u32();
+                        let format  = read_char();
+
+                        if (format !== 'r') throw 'Unknown texture format: \'' + format + '\'';
+                        draw.texture_create(texture_id, width, height);
+                    }
+                    break;
+
+                case 'D':
+                    {
+                        let x       = read_u32();
+                        let y       = read_u32();
+                        let width   = read_u32();
+                        let height  = read_u32();
+                        let len     = read_u32();
+                        let bytes   = read_bytes(len);
+
+                        draw.texture_set_bytes(texture_id, x, y, width, height, bytes);
+                    }
+                    break;
+
+                case 'X':   draw.texture_free(texture_id); break;
+                case 'd':   draw.draw_texture(texture_id, read_float(), read_float(), read_float(), read_float(), read_float()); break;
+                default:    throw 'Unknown texture operation: \'' + operation + '\'';
+                }
+            };
+
             ///
             /// Decodes a line properties command
             ///
@@ -1208,6 +1444,8 @@ let flo_canvas = (function() {
                 case 'P':   draw.push_state();                          break;
                 case 'p':   draw.pop_state();                           break;
                 case 's':   decode_sprite();                            break;
+                case 'G':   decode_gradient();                          break;
+                case 'B':   decode_texture();                           break;
 
                 default:    throw 'Unknown instruction \'' + instruction + '\' at ' + pos;
                 }
@@ -1367,6 +1605,7 @@ let flo_canvas = (function() {
         element.flo_canvas_decoder  = canvas.flo_canvas_decoder;
         element.flo_draw            = canvas.flo_draw;
         element.flo_map_coords      = canvas.flo_map_coords;
+        element.flo_snapshot_map_coords = canvas.flo_snapshot_map_coords;
         element.flo_controller      = flo_controller;
         element.flo_name            = flo_name;
 
@@ -1401,6 +1640,7 @@ let flo_canvas = (function() {
         element.flo_canvas_decoder  = decoder;
         element.flo_draw            = draw;
         element.flo_map_coords      = draw.map_coords;
+        element.flo_snapshot_map_coords = draw.snapshot_map_coords;
         element.flo_controller      = flo_controller;
         element.flo_name            = flo_name;
         canvas.flo_draw             = draw;
@@ -1408,6 +1648,7 @@ let flo_canvas = (function() {
         canvas.flo_name             = flo_name;
         canvas.flo_controller       = flo_controller;
         canvas.flo_map_coords       = draw.map_coords;
+        canvas.flo_snapshot_map_coords = draw.snapshot_map_coords;
 
         apply_canvas_style(canvas);
         monitor_canvas_events(canvas);
@@ -135,6 +135,11 @@ let flo_control = (function () {
         continue_drag       = continue_drag || (() => {});
         finish_drag         = finish_drag || (() => {});
         cancel_drag         = cancel_drag || finish_drag;
+
+        // The modifier keys that are held down for an event
+        let modifier_keys   = event => {
+            return { 'shift': event.shiftKey, 'control': event.ctrlKey, 'alt': event.altKey, 'command': event.metaKey };
+        };
         
         let start_client_x  = 0;
         let start_client_y  = 0;
@@ -176,7 +181,7 @@ let flo_control = (function () {
             start_drag_y = y;
     
             // Flag that the drag event is starting
-            start_drag(x, y);
+            start_drag(x, y, modifier_keys(event));
         };
 
         // Handles the 'touch start' event (which also creates a drag effect)
@@ -210,7 +215,7 @@ let flo_control = (function () {
             start_drag_x = x;
             start_drag_y = y;
 
-            start_drag(x, y);
+            start_drag(x, y, modifier_keys(event));
         };
 
         // Moving the mouse continues the drag operation
@@ -225,7 +230,7 @@ let flo_control = (function () {
             y += (start_drag_y - start_client_y);
 
             // Continue the drag operation
-            continue_drag(x, y);
+            continue_drag(x, y, modifier_keys(event));
         };
 
         // Releasing the mouse finishes the drag
@@ -237,7 +242,7 @@ let flo_control = (function () {
             document.removeEventListener('mouseup', mouse_up, true);
 
             // Dragging has finished
-            finish_drag();
+            finish_drag(modifier_keys(event));
         };
 
         // Moving a touchpoint continues the drag operation
@@ -252,7 +257,7 @@ let flo_control = (function () {
             y += (start_drag_y - start_client_y);
 
             // Continue the drag operation
-            continue_drag(x, y);
+            continue_drag(x, y, modifier_keys(event));
         };
 
         // Releasing a touch ends the drag operation
@@ -265,7 +270,7 @@ let flo_control = (function () {
             document.removeEventListener('touchcancel', touch_cancel, true);
 
             // Dragging has finished
-            finish_drag();
+            finish_drag(modifier_keys(event));
         };
 
         // Touch drags can wind up being cancelled (eg, by palm rejection)
@@ -653,6 +653,27 @@ function flowbetween(root_node) {
         dom_node.className = new_components.join(' ');
     };
 
+    ///
+    /// Copies some text to the clipboard
+    ///
+    let copy_to_clipboard = (text) => {
+        if (navigator.clipboard && navigator.clipboard.writeText) {
+            navigator.clipboard.writeText(text);
+        } else {
+            // The clipboard API is only available in secure contexts, so fall back to copying from a temporary text area
+            let text_area = document.createElement('textarea');
+
+            text_area.value             = text;
+            text_area.style.position    = 'fixed';
+            text_area.style.opacity     = '0';
+
+            document.body.appendChild(text_area);
+            text_area.select();
+            document.execCommand('copy');
+            document.body.removeChild(text_area);
+        }
+    };
+
     ///
     /// Finds the flo node at the specified address
     ///
@@ -1111,42 +1132,130 @@ function flowbetween(root_node) {
     /// Wires up a drag action to a node
     ///
     let wire_drag = (action_name, node, controller_path) => {
-        // Last known drag coordinates
+        // Last known drag coordinates and modifier keys
         let start_x = 0;
         let start_y = 0;
         let last_x  = 0;
         let last_y  = 0;
+        let keys    = { 'shift': false, 'control': false, 'alt': false, 'command': false };
 
         // Drag operation is starting
-        let start_drag = (x, y) => {
+        let start_drag = (x, y, modifier_keys) => {
             start_x = last_x = x;
             start_y = last_y = y;
+            keys    = modifier_keys || keys;
 
-            perform_action(controller_path, action_name, { 'Drag': [ 'Start', [start_x, start_y], [x, y]] });
+            perform_action(controller_path, action_name, { 'Drag': [ 'Start', [start_x, start_y], [x, y], keys] });
         };
 
         // Drag operation continues
-        let continue_drag = (x, y) => {
-            last_x = x;
-            last_y = y;
+        let continue_drag = (x, y, modifier_keys) => {
+            last_x  = x;
+            last_y  = y;
+            keys    = modifier_keys || keys;
 
-            perform_action(controller_path, action_name, { 'Drag': [ 'Drag', [start_x, start_y], [x, y]] });
+            perform_action(controller_path, action_name, { 'Drag': [ 'Drag', [start_x, start_y], [x, y], keys] });
         };
 
         // Drag operation finishes
-        let finish_drag = () => {
-            perform_action(controller_path, action_name, { 'Drag': [ 'Finish', [start_x, start_y], [last_x, last_y]] });
+        let finish_drag = (modifier_keys) => {
+            keys    = modifier_keys || keys;
+
+            perform_action(controller_path, action_name, { 'Drag': [ 'Finish', [start_x, start_y], [last_x, last_y], keys] });
         };
 
         // Drag operation got cancelled
         let cancel_drag = () => {
-            perform_action(controller_path, action_name, { 'Drag': [ 'Cancel', [start_x, start_y], [start_x, start_y]] });
+            perform_action(controller_path, action_name, { 'Drag': [ 'Cancel', [start_x, start_y], [start_x, start_y], keys] });
         };
 
         // Wire up the event
         flo_control.on_drag(node, add_action_event, start_drag, continue_drag, finish_drag, cancel_drag);
     };
 
+    ///
+    /// Maps client coordinates to the coordinates used by a node (canvas coordinates if the node is a canvas)
+    ///
+    let map_client_coords = (node, client_x, client_y) => {
+        let client_rect = node.getBoundingClientRect();
+        let x           = client_x - client_rect.left;
+        let y           = client_y - client_rect.top;
+
+        if (node.flo_map_coords) {
+            let coords = node.flo_map_coords(x, y);
+            x = coords[0];
+            y = coords[1];
+        }
+
+        return [x, y];
+    };
+
+    ///
+    /// Wires up a node for the scroll wheel action
+    ///
+    let wire_scroll_wheel = (action_name, node, controller_path) => {
+        add_action_event(node, 'wheel', event => {
+            // Browsers report trackpad pinches as wheel events with the control key held down: these are handled by the pinch action
+            if (event.ctrlKey) {
+                return;
+            }
+
+            event.preventDefault();
+
+            // Convert the delta to pixels
+            let scale = 1.0;
+            if (event.deltaMode === 1) {
+                scale = 32.0;
+            } else if (event.deltaMode === 2) {
+                scale = node.clientHeight;
+            }
+
+            let position = map_client_coords(node, event.clientX, event.clientY);
+            perform_action(controller_path, action_name, { 'ScrollWheel': [ [position[0], position[1]], [event.deltaX*scale, event.deltaY*scale] ] });
+        }, { passive: false });
+    };
+
+    ///
+    /// Wires up a node for the pinch action
+    ///
+    let wire_pinch = (action_name, node, controller_path) => {
+        // Trackpad pinches are reported as wheel events with the control key held down
+        add_action_event(node, 'wheel', event => {
+            if (!event.ctrlKey) {
+                return;
+            }
+
+            event.preventDefault();
+
+            let position = map_client_coords(node, event.clientX, event.clientY);
+            let scale    = Math.exp(-event.deltaY/100.0);
+            perform_action(controller_path, action_name, { 'Pinch': [ [position[0], position[1]], scale, 0.0 ] });
+        }, { passive: false });
+
+        // Safari generates gesture events, which report the scale and rotation since the start of the gesture
+        let last_scale      = 1.0;
+        let last_rotation   = 0.0;
+
+        add_action_event(node, 'gesturestart', event => {
+            event.preventDefault();
+            last_scale      = 1.0;
+            last_rotation   = 0.0;
+        }, { passive: false });
+
+        add_action_event(node, 'gesturechange', event => {
+            event.preventDefault();
+
+            let position        = map_client_coords(node, event.clientX, event.clientY);
+            let scale           = event.scale / last_scale;
+            let rotation        = -(event.rotation - last_rotation) * Math.PI / 180.0;
+
+            last_scale          = event.scale;
+            last_rotation       = event.rotation;
+
+            perform_action(controller_path, action_name, { 'Pinch': [ [position[0], position[1]], scale, rotation ] });
+        }, { passive: false });
+    };
+
     ///
     /// Rewires any intrinsic events that might have been removed by a
     /// call to remove_action_events_from_node
@@ -1178,6 +1287,12 @@ function flowbetween(root_node) {
         } else if (action_type['VirtualScroll']) {
             wire_virtual_scroll(action_name, node, controller_path, action_type['VirtualScroll'][0], action_type['VirtualScroll'][1]);
 
+        } else if (action_type === 'ScrollWheel') {
+            wire_scroll_wheel(action_name, node, controller_path);
+
+        } else if (action_type === 'Pinch') {
+            wire_pinch(action_name, node, controller_path);
+
         } else if (action_type['Paint']) {
             flo_paint.wire_paint(action_type['Paint'], action_name, node, controller_path);
 
@@ -1412,6 +1527,14 @@ function flowbetween(root_node) {
                 canvas_deco.style.height    = scroll['MinimumContentSize'][1] + 'px';
             }
 
+        } else if (attribute['Hint'] && attribute['Hint']['CopyOnClick'] !== undefined) {
+            // Copy the text to the clipboard whenever this node is clicked
+            let text        = attribute['Hint']['CopyOnClick'];
+            let on_click    = () => copy_to_clipboard(text);
+
+            node.addEventListener('click', on_click);
+            remove_action = () => node.removeEventListener('click', on_click);
+
         } else if (attribute['FocusPriority']) {
             // Updates the focus priority for this node
             remove_action = on_property_change(controller_path, attribute['FocusPriority'], focus_priority => {
@@ -22,6 +22,20 @@ let flo_paint = (function() {
     let supports_pointer_events = 'onpointerdown' in window;
     let supports_touch_events   = 'ontouchstart' in window;
 
+    ///
+    /// Fixes the coordinate mapping for a node for the duration of a paint stroke
+    ///
+    /// The canvas transform can change while painting (eg, when the view is panned), but
+    /// all of the events in a stroke should use the same coordinate system.
+    ///
+    let start_stroke_coords = (target_element) => {
+        if (target_element.flo_snapshot_map_coords) {
+            target_element.flo_stroke_map_coords = target_element.flo_snapshot_map_coords();
+        } else {
+            target_element.flo_stroke_map_coords = null;
+        }
+    };
+
     ///
     /// Converts a MouseEvent to a Paint object.
     ///
@@ -39,8 +53,9 @@ let flo_paint = (function() {
         y -= client_rect.top;
 
         // Re-map the coordinates if the target element has any to map
-        if (target_element.flo_map_coords) {
-            let coords = target_element.flo_map_coords(x, y);
+        let map_coords = target_element.flo_stroke_map_coords || target_element.flo_map_coords;
+        if (map_coords) {
+            let coords = map_coords(x, y);
             x = coords[0];
             y = coords[1];
         }
@@ -77,8 +92,9 @@ let flo_paint = (function() {
         y -= client_rect.top;
         
         // Re-map the coordinates if the target element has any to map
-        if (target_element.flo_map_coords) {
-            let coords = target_element.flo_map_coords(x, y);
+        let map_coords = target_element.flo_stroke_map_coords || target_element.flo_map_coords;
+        if (map_coords) {
+            let coords = map_coords(x, y);
             x = coords[0];
             y = coords[1];
         }
@@ -112,8 +128,9 @@ let flo_paint = (function() {
         y -= client_rect.top;
         
         // Re-map the coordinates if the target element has any to map
-        if (target_element.flo_map_coords) {
-            let coords = target_element.flo_map_coords(x, y);
+        let map_coords = target_element.flo_stroke_map_coords || target_element.flo_map_coords;
+        if (map_coords) {
+            let coords = map_coords(x, y);
             x = coords[0];
             y = coords[1];
         }
@@ -166,6 +183,7 @@ let flo_paint = (function() {
             mouse_event.preventDefault();
 
             // Create the 'start' event
+            start_stroke_coords(node);
             let start_parameter = {
                 Paint: [
                     target_device,
@@ -259,6 +277,7 @@ let flo_paint = (function() {
 
             // Create the 'start' event
             last_event = touch_event;
+            start_stroke_coords(node);
             let start_parameter = {
                 Paint: [
                     target_device,
@@ -388,6 +407,7 @@ let flo_paint = (function() {
                     node.setPointerCapture(pointer_event.pointerId);
 
                     // Create the 'start' event
+                    start_stroke_coords(node);
                     let start_parameter = {
                         Paint: [
                             target_device,
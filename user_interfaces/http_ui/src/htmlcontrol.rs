@@ -217,7 +217,8 @@ impl ToHtml for Hint {
 
         match self {
             FastDrawing         => DomEmpty::new(),
-            Class(class_name)   => DomAttribute::new("class", class_name)
+            Class(class_name)   => DomAttribute::new("class", class_name),
+            CopyOnClick(_)      => DomEmpty::new()       /* Wired up when the viewmodel is bound */
         }
     }
 }
@@ -51,7 +51,9 @@ typedef struct AppPainting AppPainting;
 - (void) sendChangeValue: (NSString*) name isSet: (BOOL) isSet withDouble: (double) value;
 - (void) sendChangeValue: (NSString*) name isSet: (BOOL) isSet withString: (NSString*) value;
 - (void) sendVirtualScroll: (NSString*) name left: (uint32_t) left top: (uint32_t) top width: (uint32_t) width height: (uint32_t) height;
-- (void) sendDrag: (NSString*) name dragAction: (uint32_t) action fromX: (double) fromX fromY: (double) fromY toX: (double) toX toY: (double) toY;
+- (void) sendDrag: (NSString*) name dragAction: (uint32_t) action fromX: (double) fromX fromY: (double) fromY toX: (double) toX toY: (double) toY modifiers: (uint32_t) modifiers;
+- (void) sendScrollWheel: (NSString*) name x: (double) x y: (double) y deltaX: (double) deltaX deltaY: (double) deltaY;
+- (void) sendPinch: (NSString*) name x: (double) x y: (double) y scale: (double) scale rotation: (double) rotation;
 - (void) sendPaintStartForDevice: (uint32_t) deviceId name: (NSString*) name action: (AppPainting) action;
 - (void) sendPaintContinueForDevice: (uint32_t) deviceId name: (NSString*) name action: (AppPainting) action;
 - (void) sendPaintFinishForDevice: (uint32_t) deviceId name: (NSString*) name action: (AppPainting) action;
@@ -92,6 +94,8 @@ typedef struct AppPainting AppPainting;
 - (void) requestEditValue: (FloEvents*) events withName: (NSString*) name;
 - (void) requestSetValue: (FloEvents*) events withName: (NSString*) name;
 - (void) requestCancelEdit: (FloEvents*) events withName: (NSString*) name;
+- (void) requestScrollWheel: (FloEvents*) events withName: (NSString*) name;
+- (void) requestPinch: (FloEvents*) events withName: (NSString*) name;
 
 - (void) viewRemoveFromSuperview;
 - (void) viewAddSubView: (NSObject*) subview;
@@ -124,6 +128,7 @@ typedef struct AppPainting AppPainting;
 - (void) viewSetFocusPriority: (FloProperty*) property;
 - (void) viewFixScrollAxis: (uint32_t) axis;
 - (void) viewAddClassName: (NSString*) className;
+- (void) viewSetCopyOnClick: (NSString*) text;
 
 - (void) viewSetPopupOpen: (FloProperty*) isOpen;
 - (void) viewSetPopupDirection: (uint32_t) direction;
@@ -38,6 +38,9 @@ public class FloView : NSObject, FloViewDelegate {
     fileprivate var _onClick: (() -> ())?
     fileprivate var _onDismiss: (() -> ())?
 
+    /// Text to copy to the pasteboard when this view is clicked
+    fileprivate var _copyOnClick: String?
+
     /// The layer to draw on, if there is one
     fileprivate var _drawingLayer: FloCanvasLayer?
     
@@ -80,7 +83,7 @@ public class FloView : NSObject, FloViewDelegate {
         weak var this = self
 
         _view.performLayout = { size in this?.performLayout(size) }
-        _view.onClick       = { if let onClick = this?._onClick { onClick(); return true } else { return false } }
+        _view.onClick       = { this?.performClick() ?? false }
     }
 
     ///
@@ -203,10 +206,27 @@ public class FloView : NSObject, FloViewDelegate {
     ///
     @objc public func requestClick(_ events: FloEvents!, withName: String?) {
         weak var this = self
-        _view.onClick = { if let onClick = this?._onClick { onClick(); return true } else { return false } }
+        _view.onClick = { this?.performClick() ?? false }
         _onClick = { events.sendClick(withName) }
     }
 
+    ///
+    /// Copies the text for this view to the pasteboard (if there is any) and sends the click event
+    ///
+    fileprivate func performClick() -> Bool {
+        if let text = _copyOnClick {
+            NSPasteboard.general.clearContents()
+            NSPasteboard.general.setString(text, forType: .string)
+        }
+
+        if let onClick = _onClick {
+            onClick()
+            return true
+        } else {
+            return _copyOnClick != nil
+        }
+    }
+
     ///
     /// Sends an event if this view is dismissed (any action is performed 'outside' of this view)
     ///
@@ -244,9 +264,18 @@ public class FloView : NSObject, FloViewDelegate {
             case .Cancel:   actionNum = 3
             }
 
+            // The modifier keys are sent as a bitmask (shift, control, option, command)
+            let modifierFlags   = NSEvent.modifierFlags
+            var modifiers       = UInt32(0)
+            if modifierFlags.contains(.shift)   { modifiers |= 1 }
+            if modifierFlags.contains(.control) { modifiers |= 2 }
+            if modifierFlags.contains(.option)  { modifiers |= 4 }
+            if modifierFlags.contains(.command) { modifiers |= 8 }
+
             events.sendDrag(name, dragAction: actionNum,
                             fromX: Double(from.x), fromY: Double(from.y),
-                            toX: Double(to.x), toY: Double(to.y))
+                            toX: Double(to.x), toY: Double(to.y),
+                            modifiers: modifiers)
         }
     }
 
@@ -294,6 +323,32 @@ public class FloView : NSObject, FloViewDelegate {
         NSLog("RequestCancelEdit not implemented")
     }
 
+    ///
+    /// Sends an event when the scroll wheel is moved over this view
+    ///
+    @objc public func requestScrollWheel(_ events: FloEvents!, withName name: String!) {
+        if let emptyView = _view as? FloEmptyView {
+            emptyView.onScrollWheel = { position, delta in
+                events.sendScrollWheel(name, x: Double(position.x), y: Double(position.y), deltaX: Double(delta.x), deltaY: Double(delta.y))
+            }
+        } else {
+            NSLog("RequestScrollWheel not implemented for this view type")
+        }
+    }
+
+    ///
+    /// Sends an event when the user performs a magnify or rotate gesture over this view
+    ///
+    @objc public func requestPinch(_ events: FloEvents!, withName name: String!) {
+        if let emptyView = _view as? FloEmptyView {
+            emptyView.onPinch = { center, scale, rotation in
+                events.sendPinch(name, x: Double(center.x), y: Double(center.y), scale: Double(scale), rotation: Double(rotation))
+            }
+        } else {
+            NSLog("RequestPinch not implemented for this view type")
+        }
+    }
+
     @objc public func viewSetSelected(_ property: FloProperty!) {
         _view.setState(selector: ViewStateSelector.Selected, toProperty: property)
     }
@@ -329,6 +384,12 @@ public class FloView : NSObject, FloViewDelegate {
         _view.viewState.classes.append(className)
     }
 
+    @objc public func viewSetCopyOnClick(_ text: String!) {
+        weak var this = self
+        _view.onClick = { this?.performClick() ?? false }
+        _copyOnClick = text
+    }
+
     ///
     /// Sends an event if this view is scrolled
     ///
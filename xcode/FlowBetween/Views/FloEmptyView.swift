@@ -71,6 +71,12 @@ class FloEmptyView : NSView, FloContainerView {
     /// Event handlers when particular devices are used for painting actions
     public var onPaint: [FloPaintDevice: (FloPaintStage, AppPainting) -> ()] = [FloPaintDevice: (FloPaintStage, AppPainting) -> ()]()
 
+    /// Event handler: scroll wheel has moved over this view (position in canvas coordinates, distance scrolled)
+    public var onScrollWheel: ((CGPoint, CGPoint) -> ())?
+
+    /// Event handler: user has performed a magnify or rotate gesture (center in canvas coordinates, change in scale, change in rotation)
+    public var onPinch: ((CGPoint, CGFloat, CGFloat) -> ())?
+
     var _canvasAffineTransform: CGAffineTransform?
     var _invertCanvasTransform: CGAffineTransform = .identity
 
@@ -278,7 +284,23 @@ class FloEmptyView : NSView, FloContainerView {
     ///
     /// Generates the AppPainting data from an NSEvent
     ///
-    func createAppPainting(event: NSEvent) -> AppPainting {
+    func createAppPainting(event: NSEvent, inverseTransform: CGAffineTransform) -> AppPainting {
+        let locationInCanvas = canvasLocation(event: event, inverseTransform: inverseTransform)
+
+        return AppPainting(
+            pointer_id: 0,
+            position_x: Double(locationInCanvas.x),
+            position_y: Double(locationInCanvas.y),
+            pressure:   Double(event.pressure),
+            tilt_x:     0.0,
+            tilt_y:     0.0
+        )
+    }
+
+    ///
+    /// Finds the location of an event in canvas coordinates
+    ///
+    func canvasLocation(event: NSEvent, inverseTransform: CGAffineTransform) -> CGPoint {
         // Work out the location of the event
         let bounds              = self.bounds
         let locationInWindow    = event.locationInWindow
@@ -297,16 +319,40 @@ class FloEmptyView : NSView, FloContainerView {
             locationInCanvas.y  = bounds.size.height - locationInCanvas.y
         }
 
-        locationInCanvas = locationInCanvas.applying(_invertCanvasTransform)
+        return locationInCanvas.applying(inverseTransform)
+    }
 
-        return AppPainting(
-            pointer_id: 0,
-            position_x: Double(locationInCanvas.x),
-            position_y: Double(locationInCanvas.y),
-            pressure:   Double(event.pressure),
-            tilt_x:     0.0,
-            tilt_y:     0.0
-        )
+    ///
+    /// Scroll wheel has moved
+    ///
+    override func scrollWheel(with event: NSEvent) {
+        if let onScrollWheel = onScrollWheel {
+            onScrollWheel(canvasLocation(event: event, inverseTransform: _invertCanvasTransform), CGPoint(x: -event.scrollingDeltaX, y: -event.scrollingDeltaY))
+        } else {
+            super.scrollWheel(with: event)
+        }
+    }
+
+    ///
+    /// User is performing a magnify gesture
+    ///
+    override func magnify(with event: NSEvent) {
+        if let onPinch = onPinch {
+            onPinch(canvasLocation(event: event, inverseTransform: _invertCanvasTransform), 1.0 + event.magnification, 0.0)
+        } else {
+            super.magnify(with: event)
+        }
+    }
+
+    ///
+    /// User is performing a rotate gesture
+    ///
+    override func rotate(with event: NSEvent) {
+        if let onPinch = onPinch {
+            onPinch(canvasLocation(event: event, inverseTransform: _invertCanvasTransform), 1.0, CGFloat(event.rotation) * CGFloat.pi / 180.0)
+        } else {
+            super.rotate(with: event)
+        }
     }
 
     ///
@@ -335,8 +381,11 @@ class FloEmptyView : NSView, FloContainerView {
     /// type event that initiated the paint actions.
     ///
     func paint(with device: FloPaintDevice, initialEvent: NSEvent, paintAction: (FloPaintStage, AppPainting) -> ()) {
+        // The canvas transform can change during the paint action (eg, if the view is panned): use the same coordinates for the whole stroke
+        let inverseTransform = _invertCanvasTransform
+
         // Send the paint start event
-        paintAction(FloPaintStage.Start, createAppPainting(event: initialEvent))
+        paintAction(FloPaintStage.Start, createAppPainting(event: initialEvent, inverseTransform: inverseTransform))
 
         // Event mask depends on the initial event
         let eventMask = eventMaskForInitialMouseEvent(event: initialEvent)
@@ -357,9 +406,9 @@ class FloEmptyView : NSView, FloContainerView {
                 // Send the painting action
                 autoreleasepool {
                     if !isFinished {
-                        paintAction(FloPaintStage.Continue, createAppPainting(event: nextEvent))
+                        paintAction(FloPaintStage.Continue, createAppPainting(event: nextEvent, inverseTransform: inverseTransform))
                     } else {
-                        paintAction(FloPaintStage.Finish, createAppPainting(event: nextEvent))
+                        paintAction(FloPaintStage.Finish, createAppPainting(event: nextEvent, inverseTransform: inverseTransform))
                         done = true
                     }
                 }